@@ -1,26 +1,36 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::config::settings::AppConfig;
+use crate::ai::provider::{AiProvider, ChatOptions, ChatReply};
+use crate::ai::usage::{self, TokenUsage};
+use crate::config::settings::{AiConfig, AppConfig};
 
 /// AI 分析引擎
 pub struct AiAnalyzer {
     client: reqwest::Client,
+    provider: AiProvider,
     api_url: String,
     api_key: String,
     model: String,
     max_tokens: u32,
     temperature: f32,
+    /// 多轮对话历史 (不含系统提示词)
+    history: Vec<ChatMessage>,
+    /// 是否使用流式响应
+    stream: bool,
+    /// 流式响应的增量回调
+    on_token: Option<TokenHandler>,
+    /// 是否通过工具调用获取操作建议
+    tools: bool,
+    /// 用于用量记账与预算检查的 AI 配置
+    ai_config: AiConfig,
 }
 
-/// OpenAI 兼容的聊天请求
-#[derive(Debug, Serialize)]
-struct ChatRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    max_tokens: u32,
-    temperature: f32,
-}
+/// 流式响应增量回调
+pub type TokenHandler = Arc<dyn Fn(&str) + Send + Sync>;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChatMessage {
@@ -28,24 +38,37 @@ pub struct ChatMessage {
     pub content: String,
 }
 
-/// OpenAI 兼容的聊天响应
+/// 流式响应的单个数据块
+#[derive(Debug, Deserialize)]
+struct ChatStreamChunk {
+    #[serde(default)]
+    choices: Vec<ChatStreamChoice>,
+    usage: Option<TokenUsage>,
+}
+
 #[derive(Debug, Deserialize)]
-struct ChatResponse {
-    choices: Vec<ChatChoice>,
-    usage: Option<ChatUsage>,
+struct ChatStreamChoice {
+    #[serde(default)]
+    delta: ChatStreamDelta,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ChatStreamDelta {
+    content: Option<String>,
+    tool_calls: Option<Vec<ToolCallDelta>>,
 }
 
+/// 流式响应中的工具调用片段
 #[derive(Debug, Deserialize)]
-struct ChatChoice {
-    message: ChatMessage,
-    finish_reason: Option<String>,
+struct ToolCallDelta {
+    #[serde(default)]
+    index: usize,
+    function: Option<ToolCallFunction>,
 }
 
 #[derive(Debug, Deserialize)]
-struct ChatUsage {
-    prompt_tokens: Option<u32>,
-    completion_tokens: Option<u32>,
-    total_tokens: Option<u32>,
+struct ToolCallFunction {
+    arguments: Option<String>,
 }
 
 /// AI 分析结果
@@ -64,6 +87,12 @@ pub struct SuggestedAction {
     pub description: String,
     pub params: serde_json::Value,
     pub risk: String,
+    /// 安全审查发现的问题 (不来自 AI 响应)
+    #[serde(skip)]
+    pub review_notes: Vec<String>,
+    /// 安全审查判定不应执行
+    #[serde(skip)]
+    pub blocked: bool,
 }
 
 /// AI 操作方案
@@ -82,49 +111,76 @@ impl AiAnalyzer {
             .clone()
             .context("未配置 AI API Key，请运行 `cfai config setup` 或设置 AI_API_KEY 环境变量")?;
 
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(120))
+        let client = config
+            .network
+            .apply(reqwest::Client::builder(), std::time::Duration::from_secs(120))?
             .build()
             .context("创建 HTTP 客户端失败")?;
 
         Ok(Self {
             client,
+            provider: config.ai_provider()?,
             api_url: config.ai_api_url(),
             api_key,
             model: config.ai_model(),
             max_tokens: config.ai.max_tokens.unwrap_or(4096),
             temperature: config.ai.temperature.unwrap_or(0.7),
+            history: Vec::new(),
+            stream: config.ai.stream.unwrap_or(true),
+            on_token: None,
+            tools: config.ai.tools.unwrap_or(true),
+            ai_config: config.ai.clone(),
         })
     }
 
+    /// 设置流式响应回调，每收到一段文本调用一次
+    ///
+    /// 配置中关闭了流式输出时不会生效，可通过 [`AiAnalyzer::is_streaming`] 判断。
+    pub fn on_token(&mut self, handler: impl Fn(&str) + Send + Sync + 'static) {
+        self.on_token = Some(Arc::new(handler));
+    }
+
+    /// 是否会以流式方式返回响应
+    pub fn is_streaming(&self) -> bool {
+        self.stream && self.on_token.is_some()
+    }
+
     /// 发送聊天请求
     async fn chat(&self, system_prompt: &str, user_message: &str) -> Result<AnalysisResult> {
-        let request = ChatRequest {
-            model: self.model.clone(),
-            messages: vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: system_prompt.to_string(),
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: user_message.to_string(),
-                },
-            ],
+        let messages = vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: system_prompt.to_string(),
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: user_message.to_string(),
+            },
+        ];
+        self.complete(messages).await
+    }
+
+    /// 发送完整消息列表并解析响应
+    ///
+    /// 启用工具调用时通过 `suggest_actions` 工具获取结构化的操作建议；
+    /// 服务不支持工具 (请求被拒绝) 或模型未调用工具时，回退为从正文中提取 JSON。
+    async fn complete(&self, messages: Vec<ChatMessage>) -> Result<AnalysisResult> {
+        usage::check_budget(&self.ai_config)?;
+
+        let stream = self.is_streaming();
+        let mut options = ChatOptions {
+            model: &self.model,
             max_tokens: self.max_tokens,
             temperature: self.temperature,
+            stream,
+            tools: self.tools,
         };
-
-        let url = format!("{}/chat/completions", self.api_url);
-        let resp = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("AI API 请求失败")?;
+        let mut resp = self.send(&messages, &options).await?;
+        if options.tools && matches!(resp.status().as_u16(), 400 | 404 | 422) {
+            tracing::debug!("AI 服务不支持工具调用 (HTTP {})，改为从正文提取操作建议", resp.status());
+            options.tools = false;
+            resp = self.send(&messages, &options).await?;
+        }
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -132,26 +188,188 @@ impl AiAnalyzer {
             anyhow::bail!("AI API 错误 (HTTP {}): {}", status, body);
         }
 
-        let chat_resp: ChatResponse = resp.json().await.context("解析 AI 响应失败")?;
-
-        let content = chat_resp
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .unwrap_or_default();
+        let mut reply = if stream {
+            self.read_stream(resp).await?
+        } else {
+            let body: serde_json::Value = resp.json().await.context("解析 AI 响应失败")?;
+            self.provider.parse_response(&body)?
+        };
 
-        let tokens_used = chat_resp.usage.and_then(|u| u.total_tokens);
+        if let Some(u) = &reply.usage {
+            if let Err(e) = usage::record(&self.ai_config, &self.model, u) {
+                tracing::debug!("记录 AI 用量失败: {:#}", e);
+            }
+        }
+        let tokens_used = reply.usage.map(|u| u.total_tokens);
 
-        // 尝试解析 AI 建议的操作
-        let actions = self.extract_actions(&content);
+        let actions = match tool_actions(&reply.tool_calls) {
+            Some((actions, explanation)) => {
+                // 模型只调用了工具时，以工具给出的说明作为正文
+                if reply.content.trim().is_empty() {
+                    if let Some(explanation) = explanation {
+                        if let Some(handler) = self.on_token.as_ref().filter(|_| stream) {
+                            handler(&explanation);
+                        }
+                        reply.content = explanation;
+                    }
+                }
+                Some(actions)
+            }
+            None => self.extract_actions(&reply.content),
+        };
 
         Ok(AnalysisResult {
-            content,
+            content: reply.content,
             actions,
             tokens_used,
         })
     }
 
+    /// 用 1 个 Token 的补全请求测试 API 地址、Key 与模型是否可用，返回响应耗时
+    ///
+    /// 不检查预算、不记录用量，供配置向导与 `config verify` 使用。
+    pub async fn probe(&self) -> Result<std::time::Duration> {
+        let messages = [ChatMessage {
+            role: "user".to_string(),
+            content: "ping".to_string(),
+        }];
+        let options = ChatOptions {
+            model: &self.model,
+            max_tokens: 1,
+            temperature: 0.0,
+            stream: false,
+            tools: false,
+        };
+        let started = std::time::Instant::now();
+        let resp = self.send(&messages, &options).await?;
+        let latency = started.elapsed();
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            let body: String = body.trim().chars().take(200).collect();
+            match probe_hint(status.as_u16(), &self.model) {
+                Some(hint) => anyhow::bail!("{} (HTTP {}): {}", hint, status, body),
+                None => anyhow::bail!("AI API 错误 (HTTP {}): {}", status, body),
+            }
+        }
+        resp.json::<serde_json::Value>()
+            .await
+            .context("AI 服务返回的不是 JSON，请检查 API 地址")?;
+        Ok(latency)
+    }
+
+    /// 发送聊天请求，启用工具时追加工具使用说明
+    async fn send(&self, messages: &[ChatMessage], options: &ChatOptions<'_>) -> Result<reqwest::Response> {
+        let mut messages = messages.to_vec();
+        if options.tools {
+            messages.insert(
+                1.min(messages.len()),
+                ChatMessage {
+                    role: "system".to_string(),
+                    content: super::prompts::TOOL_USE_PROMPT.to_string(),
+                },
+            );
+        }
+        self.provider
+            .request(&self.client, &self.api_url, &self.api_key, &messages, options)
+            .send()
+            .await
+            .context("AI API 请求失败")
+    }
+
+    /// 读取 SSE 流式响应，逐段回调并拼接完整内容
+    async fn read_stream(&self, mut resp: reqwest::Response) -> Result<ChatReply> {
+        let mut reply = ChatReply::default();
+        // 按序号拼接的工具调用参数片段
+        let mut fragments: BTreeMap<usize, String> = BTreeMap::new();
+        let mut buffer: Vec<u8> = Vec::new();
+
+        'read: while let Some(chunk) = resp.chunk().await.context("读取 AI 流式响应失败")? {
+            buffer.extend_from_slice(&chunk);
+            // 按行处理，不完整的行留到下一个数据块
+            while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=pos).collect();
+                let line = String::from_utf8_lossy(&line);
+                for event in self.provider.parse_sse_line(&line)? {
+                    match event {
+                        SseEvent::Delta(text, usage) => {
+                            if !text.is_empty() {
+                                if let Some(handler) = &self.on_token {
+                                    handler(&text);
+                                }
+                                reply.content.push_str(&text);
+                            }
+                            if let Some(u) = usage {
+                                reply.usage = Some(u.merge(reply.usage.unwrap_or_default()));
+                            }
+                        }
+                        SseEvent::ToolCall(Some(index), args) => {
+                            fragments.entry(index).or_default().push_str(&args);
+                        }
+                        SseEvent::ToolCall(None, args) => reply.tool_calls.push(args),
+                        SseEvent::Done => break 'read,
+                        SseEvent::Skip => {}
+                    }
+                }
+            }
+        }
+
+        reply.tool_calls.extend(fragments.into_values());
+        Ok(reply)
+    }
+
+    // ==================== 多轮对话 ====================
+
+    /// 载入已有的对话历史
+    pub fn set_history(&mut self, history: Vec<ChatMessage>) {
+        self.history = history;
+    }
+
+    /// 当前对话历史
+    pub fn history(&self) -> &[ChatMessage] {
+        &self.history
+    }
+
+    /// 清空对话历史
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// 向对话注入上下文 (如域名实时配置)，不触发请求
+    pub fn add_context(&mut self, context: &str) {
+        self.history.push(ChatMessage {
+            role: "system".to_string(),
+            content: context.to_string(),
+        });
+    }
+
+    /// 多轮对话：携带历史发送消息，并将问答追加到历史
+    pub async fn chat_turn(&mut self, user_message: &str) -> Result<AnalysisResult> {
+        let mut messages = Vec::with_capacity(self.history.len() + 2);
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: super::prompts::SYSTEM_PROMPT.to_string(),
+        });
+        messages.extend(self.history.iter().cloned());
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: user_message.to_string(),
+        });
+
+        let result = self.complete(messages).await?;
+
+        self.history.push(ChatMessage {
+            role: "user".to_string(),
+            content: user_message.to_string(),
+        });
+        self.history.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: result.content.clone(),
+        });
+        Ok(result)
+    }
+
     /// 从 AI 响应中提取操作建议
     fn extract_actions(&self, content: &str) -> Option<Vec<SuggestedAction>> {
         // 查找 JSON 代码块
@@ -233,3 +451,113 @@ impl AiAnalyzer {
             .await
     }
 }
+
+/// 解析工具调用给出的操作方案，返回操作列表与说明；没有有效的工具调用时返回 None
+fn tool_actions(tool_calls: &[String]) -> Option<(Vec<SuggestedAction>, Option<String>)> {
+    let mut actions = Vec::new();
+    let mut explanations = Vec::new();
+    let mut parsed = false;
+    for args in tool_calls {
+        match serde_json::from_str::<AiActionPlan>(args) {
+            Ok(plan) => {
+                parsed = true;
+                actions.extend(plan.actions.unwrap_or_default());
+                explanations.extend(plan.explanation);
+            }
+            Err(e) => tracing::debug!("解析工具调用参数失败: {} ({})", e, args),
+        }
+    }
+    let explanation = (!explanations.is_empty()).then(|| explanations.join("\n\n"));
+    parsed.then_some((actions, explanation))
+}
+
+/// SSE 单行解析结果
+#[derive(Debug, PartialEq)]
+pub(crate) enum SseEvent {
+    /// 文本增量及 (可能附带的) Token 用量
+    Delta(String, Option<TokenUsage>),
+    /// 工具调用参数: 带序号的为需按序号拼接的片段，不带序号的为完整参数
+    ToolCall(Option<usize>, String),
+    /// 流结束
+    Done,
+    /// 空行、注释或其他字段
+    Skip,
+}
+
+/// 解析 OpenAI 兼容接口的 SSE 行
+pub(crate) fn parse_sse_line(line: &str) -> Result<SseEvent> {
+    let Some(data) = line.trim_end_matches(['\r', '\n']).strip_prefix("data:") else {
+        return Ok(SseEvent::Skip);
+    };
+    let data = data.trim();
+    if data == "[DONE]" {
+        return Ok(SseEvent::Done);
+    }
+    if data.is_empty() {
+        return Ok(SseEvent::Skip);
+    }
+    let chunk: ChatStreamChunk =
+        serde_json::from_str(data).context(format!("解析 AI 流式响应失败: {}", data))?;
+    let tool_call = chunk
+        .choices
+        .iter()
+        .filter_map(|c| c.delta.tool_calls.as_ref())
+        .flatten()
+        .find_map(|t| Some((t.index, t.function.as_ref()?.arguments.clone()?)));
+    if let Some((index, args)) = tool_call {
+        return Ok(SseEvent::ToolCall(Some(index), args));
+    }
+    let text = chunk
+        .choices
+        .into_iter()
+        .filter_map(|c| c.delta.content)
+        .collect::<String>();
+    Ok(SseEvent::Delta(text, chunk.usage))
+}
+
+/// 测试请求失败时按状态码给出的原因
+fn probe_hint(status: u16, model: &str) -> Option<String> {
+    match status {
+        401 | 403 => Some("AI API Key 无效或没有权限".to_string()),
+        404 => Some(format!("模型 {} 不存在或 API 地址错误", model)),
+        429 => Some("请求过于频繁或账户额度不足".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sse_line() {
+        assert_eq!(
+            parse_sse_line("data: {\"choices\":[{\"delta\":{\"content\":\"你好\"}}]}\n").unwrap(),
+            SseEvent::Delta("你好".to_string(), None)
+        );
+        assert_eq!(
+            parse_sse_line("data: {\"choices\":[],\"usage\":{\"total_tokens\":42}}").unwrap(),
+            SseEvent::Delta(
+                String::new(),
+                Some(TokenUsage {
+                    total_tokens: 42,
+                    ..Default::default()
+                })
+            )
+        );
+        assert_eq!(
+            parse_sse_line("data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"{\\\"actions\\\"\"}}]}}]}").unwrap(),
+            SseEvent::ToolCall(Some(0), "{\"actions\"".to_string())
+        );
+        assert_eq!(parse_sse_line("data: [DONE]\r\n").unwrap(), SseEvent::Done);
+        assert_eq!(parse_sse_line(": keep-alive\n").unwrap(), SseEvent::Skip);
+        assert_eq!(parse_sse_line("\n").unwrap(), SseEvent::Skip);
+    }
+
+    #[test]
+    fn test_probe_hint() {
+        assert_eq!(probe_hint(401, "gpt-4o").as_deref(), Some("AI API Key 无效或没有权限"));
+        assert_eq!(probe_hint(404, "gpt-4o").as_deref(), Some("模型 gpt-4o 不存在或 API 地址错误"));
+        assert_eq!(probe_hint(500, "gpt-4o"), None);
+    }
+}
@@ -1,105 +1,208 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
-use dialoguer::Confirm;
 
 use crate::ai::analyzer::SuggestedAction;
 use crate::api::client::CfClient;
 use crate::cli::output;
-use crate::models::dns::DnsRecordRequest;
+use crate::cli::prompt;
+use crate::models::dns::{managed_comment, DnsRecordRequest};
+
+/// 单个操作的执行结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionOutcome {
+    /// 执行成功，附带结果说明
+    Applied(String),
+    /// 执行失败，附带错误信息
+    Failed(String),
+    /// 未获确认或已中止，未执行
+    Skipped,
+    /// 被安全审查阻止，附带审查意见
+    Blocked(String),
+}
 
-/// 执行 AI 建议的操作列表
-pub async fn execute_actions(
+impl ActionOutcome {
+    /// 转为 Result (跳过与阻止视为错误)，便于只执行单个操作的调用方
+    pub fn into_result(self) -> Result<String> {
+        match self {
+            ActionOutcome::Applied(msg) => Ok(msg),
+            ActionOutcome::Failed(e) => anyhow::bail!(e),
+            ActionOutcome::Skipped => anyhow::bail!("操作已跳过"),
+            ActionOutcome::Blocked(notes) => anyhow::bail!("已被安全审查阻止: {}", notes),
+        }
+    }
+}
+
+/// 执行过程中的确认与进度回调
+///
+/// 执行引擎本身不做任何终端交互，CLI、GUI 与测试各自实现此 trait。
+pub trait ActionDelegate: Send {
+    /// 执行前确认整个操作计划，返回 false 时不执行任何操作
+    fn confirm_plan(&mut self, actions: &[SuggestedAction]) -> Result<bool>;
+
+    /// 高风险操作的单独确认
+    fn confirm_high_risk(&mut self, action: &SuggestedAction) -> Result<bool>;
+
+    /// 某个操作失败后是否继续执行剩余操作
+    fn continue_after_failure(&mut self, action: &SuggestedAction, error: &str) -> Result<bool>;
+
+    /// 开始执行第 index 个操作 (从 0 开始)
+    fn on_start(&mut self, _index: usize, _total: usize, _action: &SuggestedAction) {}
+
+    /// 第 index 个操作已有结果
+    fn on_outcome(&mut self, _index: usize, _action: &SuggestedAction, _outcome: &ActionOutcome) {}
+}
+
+/// 无需交互、确认全部操作的执行方式 (调用方已自行完成确认，如 GUI 确认框)
+pub struct AutoApprove;
+
+impl ActionDelegate for AutoApprove {
+    fn confirm_plan(&mut self, _actions: &[SuggestedAction]) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn confirm_high_risk(&mut self, _action: &SuggestedAction) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn continue_after_failure(&mut self, _action: &SuggestedAction, _error: &str) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// 执行 AI 建议的操作列表 (与界面无关的执行引擎)
+///
+/// 返回与 `actions` 一一对应的结果；被阻止的操作不会执行，高风险操作需经过单独确认，
+/// 中止后剩余的操作记为 `Skipped`。整体计划未获确认时返回空列表。
+pub async fn run_actions<D: ActionDelegate + ?Sized>(
     client: &CfClient,
     zone_id: &str,
     actions: &[SuggestedAction],
-) -> Result<()> {
-    if actions.is_empty() {
-        return Ok(());
+    delegate: &mut D,
+) -> Result<Vec<ActionOutcome>> {
+    if actions.is_empty() || !delegate.confirm_plan(actions)? {
+        return Ok(Vec::new());
     }
 
-    println!("\n{}", "🚀 准备执行以下操作:".bold().yellow());
-    output::separator();
+    let total = actions.len();
+    let mut outcomes = Vec::with_capacity(total);
+    let mut aborted = false;
 
     for (i, action) in actions.iter().enumerate() {
-        let risk_icon = match action.risk.as_str() {
-            "low" => "🟢",
-            "medium" => "🟡",
-            "high" => "🔴",
-            _ => "⚪",
+        let outcome = if aborted {
+            ActionOutcome::Skipped
+        } else {
+            delegate.on_start(i, total, action);
+            if action.blocked {
+                ActionOutcome::Blocked(action.review_notes.join("; "))
+            } else if action.risk == "high" && !delegate.confirm_high_risk(action)? {
+                ActionOutcome::Skipped
+            } else {
+                match execute_single_action(client, zone_id, action).await {
+                    Ok(msg) => ActionOutcome::Applied(msg),
+                    Err(e) => ActionOutcome::Failed(format!("{:#}", e)),
+                }
+            }
         };
-        println!(
-            "  {}. {} {} [风险: {}]",
-            i + 1,
-            risk_icon,
-            action.description,
-            action.risk
-        );
+
+        if !aborted {
+            delegate.on_outcome(i, action, &outcome);
+            if let ActionOutcome::Failed(e) = &outcome {
+                if i + 1 < total && !delegate.continue_after_failure(action, e)? {
+                    aborted = true;
+                }
+            }
+        }
+        outcomes.push(outcome);
     }
 
-    output::separator();
+    Ok(outcomes)
+}
 
-    // 总体确认
-    let confirm = Confirm::new()
-        .with_prompt("是否执行以上操作?")
-        .default(false)
-        .interact()?;
+/// 终端交互方式: dialoguer 确认并打印执行进度
+struct TerminalDelegate;
+
+impl ActionDelegate for TerminalDelegate {
+    fn confirm_plan(&mut self, actions: &[SuggestedAction]) -> Result<bool> {
+        println!("\n{}", "🚀 准备执行以下操作:".bold().yellow());
+        output::separator();
+
+        for (i, action) in actions.iter().enumerate() {
+            let risk_icon = match action.risk.as_str() {
+                "low" => "🟢",
+                "medium" => "🟡",
+                "high" => "🔴",
+                _ => "⚪",
+            };
+            println!(
+                "  {}. {} {} [风险: {}]{}",
+                i + 1,
+                risk_icon,
+                action.description,
+                action.risk,
+                if action.blocked { " ⛔ 已阻止".red().to_string() } else { String::new() }
+            );
+        }
 
-    if !confirm {
-        println!("{}", "已取消执行".dimmed());
-        return Ok(());
-    }
+        output::separator();
 
-    let total = actions.len();
-    let mut success_count = 0;
-    let mut fail_count = 0;
+        // 无法提问时不执行任何操作
+        if !prompt::is_interactive() {
+            output::warn("非交互模式，未执行以上操作 (--yes 不适用于 AI 建议的操作)");
+            return Ok(false);
+        }
 
-    for (i, action) in actions.iter().enumerate() {
-        println!(
-            "\n{} [{}/{}] {}",
-            "▶".cyan(),
-            i + 1,
-            total,
-            action.description
-        );
+        // 总体确认
+        let confirm = prompt::confirm_ai_action("是否执行以上操作?", false)?;
 
-        // 高风险操作需要单独确认
-        if action.risk == "high" {
-            let high_confirm = Confirm::new()
-                .with_prompt(format!(
-                    "🔴 高风险操作: {}，确认执行?",
-                    action.description
-                ))
-                .default(false)
-                .interact()?;
-
-            if !high_confirm {
-                println!("  {} 已跳过", "⏭️".dimmed());
-                continue;
-            }
+        if !confirm {
+            println!("{}", "已取消执行".dimmed());
+        }
+        Ok(confirm)
+    }
+
+    fn confirm_high_risk(&mut self, action: &SuggestedAction) -> Result<bool> {
+        prompt::confirm_ai_action(format!("🔴 高风险操作: {}，确认执行?", action.description), false)
+    }
+
+    fn continue_after_failure(&mut self, _action: &SuggestedAction, _error: &str) -> Result<bool> {
+        if !prompt::is_interactive() {
+            println!("{}", "非交互模式，已中止剩余操作".dimmed());
+            return Ok(false);
         }
+        let cont = prompt::confirm_ai_action("是否继续执行剩余操作?", true)?;
+        if !cont {
+            println!("{}", "已中止剩余操作".dimmed());
+        }
+        Ok(cont)
+    }
 
-        match execute_single_action(client, zone_id, action).await {
-            Ok(msg) => {
-                success_count += 1;
-                output::success(&format!("{}", msg));
-            }
-            Err(e) => {
-                fail_count += 1;
-                output::error(&format!("执行失败: {}", e));
-
-                if i + 1 < total {
-                    let cont = Confirm::new()
-                        .with_prompt("是否继续执行剩余操作?")
-                        .default(true)
-                        .interact()?;
-                    if !cont {
-                        println!("{}", "已中止剩余操作".dimmed());
-                        break;
-                    }
-                }
-            }
+    fn on_start(&mut self, index: usize, total: usize, action: &SuggestedAction) {
+        println!("\n{} [{}/{}] {}", "▶".cyan(), index + 1, total, action.description);
+    }
+
+    fn on_outcome(&mut self, _index: usize, _action: &SuggestedAction, outcome: &ActionOutcome) {
+        match outcome {
+            ActionOutcome::Applied(msg) => output::success(msg),
+            ActionOutcome::Failed(e) => output::error(&format!("执行失败: {}", e)),
+            ActionOutcome::Skipped => println!("  {} 已跳过", "⏭️".dimmed()),
+            ActionOutcome::Blocked(notes) => println!("  {} 已被安全审查阻止: {}", "⛔".red(), notes),
         }
     }
+}
+
+/// 在终端中交互式执行 AI 建议的操作列表
+pub async fn execute_actions(
+    client: &CfClient,
+    zone_id: &str,
+    actions: &[SuggestedAction],
+) -> Result<()> {
+    let outcomes = run_actions(client, zone_id, actions, &mut TerminalDelegate).await?;
+    if outcomes.is_empty() {
+        return Ok(());
+    }
+
+    let success_count = outcomes.iter().filter(|o| matches!(o, ActionOutcome::Applied(_))).count();
+    let fail_count = outcomes.iter().filter(|o| matches!(o, ActionOutcome::Failed(_))).count();
 
     println!();
     output::separator();
@@ -107,13 +210,13 @@ pub async fn execute_actions(
         "📊 执行完成: {} 成功, {} 失败, {} 总计",
         success_count.to_string().green(),
         fail_count.to_string().red(),
-        total.to_string().dimmed()
+        outcomes.len().to_string().dimmed()
     );
 
     Ok(())
 }
 
-/// 执行单个操作
+/// 执行单个操作 (不做任何交互确认)
 async fn execute_single_action(
     client: &CfClient,
     zone_id: &str,
@@ -210,33 +313,13 @@ async fn execute_dns_create(
     zone_id: &str,
     params: &serde_json::Value,
 ) -> Result<String> {
-    let record_type = params["type"]
-        .as_str()
-        .context("dns_create 缺少 type 参数")?;
-    let name = params["name"]
-        .as_str()
-        .context("dns_create 缺少 name 参数")?;
-    let content = params["content"]
-        .as_str()
-        .context("dns_create 缺少 content 参数")?;
-
-    let request = DnsRecordRequest {
-        record_type: record_type.to_string(),
-        name: name.to_string(),
-        content: content.to_string(),
-        ttl: params["ttl"].as_u64().map(|v| v as u32),
-        proxied: params["proxied"].as_bool(),
-        priority: params["priority"].as_u64().map(|v| v as u16),
-        comment: params["comment"].as_str().map(|s| s.to_string()),
-        tags: None,
-    };
-
+    let request = dns_request_from_params(params, "dns_create")?;
     let record = client.create_dns_record(zone_id, &request).await?;
     Ok(format!(
         "DNS 记录已创建: {} {} → {} (ID: {})",
-        record_type,
-        name,
-        content,
+        request.record_type,
+        request.name,
+        request.content,
         record.id.unwrap_or_default()
     ))
 }
@@ -249,36 +332,43 @@ async fn execute_dns_update(
     let record_id = params["record_id"]
         .as_str()
         .context("dns_update 缺少 record_id 参数")?;
-    let record_type = params["type"]
-        .as_str()
-        .context("dns_update 缺少 type 参数")?;
-    let name = params["name"]
-        .as_str()
-        .context("dns_update 缺少 name 参数")?;
-    let content = params["content"]
-        .as_str()
-        .context("dns_update 缺少 content 参数")?;
-
-    let request = DnsRecordRequest {
-        record_type: record_type.to_string(),
-        name: name.to_string(),
-        content: content.to_string(),
-        ttl: params["ttl"].as_u64().map(|v| v as u32),
-        proxied: params["proxied"].as_bool(),
-        priority: params["priority"].as_u64().map(|v| v as u16),
-        comment: params["comment"].as_str().map(|s| s.to_string()),
-        tags: None,
-    };
+    let request = dns_request_from_params(params, "dns_update")?;
 
     client
         .update_dns_record(zone_id, record_id, &request)
         .await?;
     Ok(format!(
         "DNS 记录已更新: {} {} → {}",
-        record_type, name, content
+        request.record_type, request.name, request.content
     ))
 }
 
+/// 从 dns_create / dns_update 的 params 构造 DNS 记录请求
+pub fn dns_request_from_params(params: &serde_json::Value, action_type: &str) -> Result<DnsRecordRequest> {
+    let field = |key: &str| {
+        params[key]
+            .as_str()
+            .map(|s| s.to_string())
+            .with_context(|| format!("{} 缺少 {} 参数", action_type, key))
+    };
+
+    Ok(DnsRecordRequest {
+        record_type: field("type")?,
+        name: field("name")?,
+        content: field("content")?,
+        ttl: params["ttl"].as_u64().map(|v| v as u32),
+        proxied: params["proxied"].as_bool(),
+        priority: params["priority"].as_u64().map(|v| v as u16),
+        // 新建的记录带上管理标记，之后的 apply / restore 才能修改它们
+        comment: match action_type {
+            "dns_create" => Some(managed_comment(params["comment"].as_str())),
+            _ => params["comment"].as_str().map(|s| s.to_string()),
+        },
+        tags: None,
+        data: None,
+    })
+}
+
 async fn execute_dns_delete(
     client: &CfClient,
     zone_id: &str,
@@ -388,7 +478,7 @@ async fn execute_firewall_rule(
 // ==================== 辅助函数 ====================
 
 /// 从 params 中提取 bool 值，支持 bool 和 string 类型
-fn params_to_bool(params: &serde_json::Value, key: &str) -> Result<bool> {
+pub fn params_to_bool(params: &serde_json::Value, key: &str) -> Result<bool> {
     if let Some(b) = params[key].as_bool() {
         return Ok(b);
     }
@@ -404,10 +494,66 @@ fn params_to_bool(params: &serde_json::Value, key: &str) -> Result<bool> {
 }
 
 /// 将 JSON 数组转为 Vec<String>
-fn json_array_to_strings(value: &serde_json::Value) -> Option<Vec<String>> {
+pub fn json_array_to_strings(value: &serde_json::Value) -> Option<Vec<String>> {
     value.as_array().map(|arr| {
         arr.iter()
             .filter_map(|v| v.as_str().map(|s| s.to_string()))
             .collect()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::client::AuthMethod;
+
+    /// 确认计划、拒绝所有高风险操作并记录回调
+    struct DeclineHighRisk {
+        seen: Vec<(usize, ActionOutcome)>,
+    }
+
+    impl ActionDelegate for DeclineHighRisk {
+        fn confirm_plan(&mut self, _actions: &[SuggestedAction]) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn confirm_high_risk(&mut self, _action: &SuggestedAction) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn continue_after_failure(&mut self, _action: &SuggestedAction, _error: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn on_outcome(&mut self, index: usize, _action: &SuggestedAction, outcome: &ActionOutcome) {
+            self.seen.push((index, outcome.clone()));
+        }
+    }
+
+    fn action(risk: &str, blocked: bool) -> SuggestedAction {
+        SuggestedAction {
+            action_type: "cache_purge".into(),
+            description: "清除全部缓存".into(),
+            params: serde_json::json!({ "type": "purge_all" }),
+            risk: risk.into(),
+            review_notes: vec!["测试".into()],
+            blocked,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_actions_without_terminal() {
+        let client = CfClient::new(AuthMethod::ApiToken("test".into()), &Default::default()).unwrap();
+        let actions = [action("low", true), action("high", false)];
+        let mut delegate = DeclineHighRisk { seen: Vec::new() };
+
+        let outcomes = run_actions(&client, "zone", &actions, &mut delegate).await.unwrap();
+        assert_eq!(
+            outcomes,
+            vec![ActionOutcome::Blocked("测试".into()), ActionOutcome::Skipped]
+        );
+        assert_eq!(delegate.seen.len(), 2);
+        assert!(ActionOutcome::Skipped.into_result().is_err());
+    }
+}
+
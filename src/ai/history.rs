@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::ai::analyzer::ChatMessage;
+use crate::config::settings::AppConfig;
+
+/// 持久化时保留的最大消息条数
+const MAX_HISTORY_MESSAGES: usize = 50;
+
+/// 持久化的 AI 对话会话
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ChatSession {
+    /// 通过 /context 关联的域名
+    pub domain: Option<String>,
+    pub updated_at: Option<String>,
+    pub messages: Vec<ChatMessage>,
+}
+
+impl ChatSession {
+    /// 对话历史文件路径
+    pub fn path() -> Result<PathBuf> {
+        Ok(AppConfig::config_dir()?.join("chat_history.json"))
+    }
+
+    /// 加载上次的对话，文件不存在时返回空会话
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("读取对话历史失败: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("解析对话历史失败: {}", path.display()))
+    }
+
+    /// 保存对话 (仅保留最近的消息)
+    pub fn save(&mut self) -> Result<()> {
+        if self.messages.len() > MAX_HISTORY_MESSAGES {
+            let excess = self.messages.len() - MAX_HISTORY_MESSAGES;
+            self.messages.drain(..excess);
+        }
+        self.updated_at = Some(chrono::Local::now().to_rfc3339());
+
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("写入对话历史失败: {}", path.display()))
+    }
+}
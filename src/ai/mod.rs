@@ -1,3 +1,9 @@
 pub mod analyzer;
 pub mod executor;
 pub mod prompts;
+pub mod provider;
+pub mod history;
+pub mod report;
+pub mod safety;
+pub mod simulator;
+pub mod usage;
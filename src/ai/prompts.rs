@@ -30,6 +30,16 @@ pub const SYSTEM_PROMPT: &str = r#"你是 CFAI 的智能助手，专门负责 Cl
 ```
 "#;
 
+/// 操作建议工具名称
+pub const ACTION_TOOL_NAME: &str = "suggest_actions";
+
+/// 操作建议工具说明
+pub const ACTION_TOOL_DESCRIPTION: &str =
+    "提交建议执行的 Cloudflare 配置操作，CFAI 会在用户确认后执行。仅在确实需要修改配置时调用。";
+
+/// 启用工具调用时追加的系统提示词，替代 JSON 代码块格式
+pub const TOOL_USE_PROMPT: &str = r#"本次对话提供了 suggest_actions 工具：需要建议执行操作时，请调用该工具提交操作列表，不要在回复正文中输出 JSON 代码块。正文只需给出分析与说明。"#;
+
 /// DNS 分析提示词
 pub const DNS_ANALYSIS_PROMPT: &str = r#"请分析以下 DNS 记录配置，检查是否存在以下问题：
 1. 缺少常见的重要记录（如 MX、SPF、DKIM、DMARC）
@@ -60,9 +70,10 @@ pub const PERFORMANCE_ANALYSIS_PROMPT: &str = r#"请分析以下 Cloudflare 域
 1. 缓存级别是否最优
 2. 浏览器缓存 TTL 设置
 3. 是否启用了适当的优化功能（如 Minify、Brotli）
-4. 页面规则是否合理
-5. 开发模式是否应该关闭
-6. 分析数据中的缓存命中率
+4. 图片优化：Polish (off/lossless/lossy)、WebP、Mirage，可通过 setting_update 修改对应 setting_id
+5. 页面规则是否合理
+6. 开发模式是否应该关闭
+7. 分析数据中的缓存命中率
 
 当前配置和分析数据：
 "#;
@@ -86,3 +97,14 @@ pub const AUTO_CONFIG_PROMPT: &str = r#"用户希望自动配置 Cloudflare，
 
 用户需求：
 "#;
+
+/// 健康报告提示词
+pub const HEALTH_REPORT_PROMPT: &str = r#"请根据以下域名配置与最近 24 小时流量生成一份健康检查报告：
+1. 总体健康评分 (0-100) 与一句话结论
+2. 发现的问题，按严重程度排序
+3. 具体的改进建议
+4. 流量与缓存命中率是否存在异常
+
+报告将直接嵌入 Markdown 文档，请使用 Markdown 格式，标题从三级 (###) 开始。
+
+"#;
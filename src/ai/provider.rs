@@ -0,0 +1,453 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use crate::ai::analyzer::{parse_sse_line, ChatMessage, SseEvent};
+use crate::ai::prompts::{ACTION_TOOL_DESCRIPTION, ACTION_TOOL_NAME};
+use crate::ai::usage::TokenUsage;
+use crate::api::error::ValidationError;
+
+/// Anthropic Messages API 版本
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// AI 服务接口类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AiProvider {
+    /// OpenAI 兼容的 Chat Completions (OpenAI、DeepSeek 等)
+    #[default]
+    OpenAi,
+    /// Anthropic Messages API
+    Anthropic,
+    /// Google Gemini generateContent
+    Gemini,
+}
+
+/// 单次聊天请求的生成参数
+pub struct ChatOptions<'a> {
+    pub model: &'a str,
+    pub max_tokens: u32,
+    pub temperature: f32,
+    pub stream: bool,
+    /// 是否提供操作建议工具
+    pub tools: bool,
+}
+
+/// 一次聊天请求的响应
+#[derive(Debug, Default)]
+pub struct ChatReply {
+    pub content: String,
+    pub usage: Option<TokenUsage>,
+    /// 操作建议工具调用的参数 (JSON 文本)
+    pub tool_calls: Vec<String>,
+}
+
+/// 可选的接口类型名称
+pub const PROVIDER_NAMES: &[&str] = &["openai", "anthropic", "gemini"];
+
+impl AiProvider {
+    /// 由配置值解析，未设置时为 OpenAI 兼容接口
+    pub fn parse(name: Option<&str>) -> Result<Self> {
+        match name.map(|n| n.to_ascii_lowercase()).as_deref() {
+            None | Some("openai") => Ok(Self::OpenAi),
+            Some("anthropic") | Some("claude") => Ok(Self::Anthropic),
+            Some("gemini") | Some("google") => Ok(Self::Gemini),
+            Some(other) => anyhow::bail!(ValidationError(format!(
+                "未知的 AI 接口类型: {} (可选: {})",
+                other,
+                PROVIDER_NAMES.join("/")
+            ))),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Anthropic => "anthropic",
+            Self::Gemini => "gemini",
+        }
+    }
+
+    /// 默认 API 地址
+    pub fn default_api_url(&self) -> &'static str {
+        match self {
+            Self::OpenAi => "https://api.openai.com/v1",
+            Self::Anthropic => "https://api.anthropic.com/v1",
+            Self::Gemini => "https://generativelanguage.googleapis.com/v1beta",
+        }
+    }
+
+    /// 默认模型
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            Self::OpenAi => "gpt-4o",
+            Self::Anthropic => "claude-sonnet-4-5",
+            Self::Gemini => "gemini-2.5-flash",
+        }
+    }
+
+    /// 构造聊天请求 (地址、认证头与请求体)
+    pub fn request(
+        &self,
+        client: &reqwest::Client,
+        api_url: &str,
+        api_key: &str,
+        messages: &[ChatMessage],
+        options: &ChatOptions,
+    ) -> reqwest::RequestBuilder {
+        let api_url = api_url.trim_end_matches('/');
+        let ChatOptions {
+            model,
+            max_tokens,
+            temperature,
+            stream,
+            tools,
+        } = *options;
+        match self {
+            Self::OpenAi => {
+                let mut body = json!({
+                    "model": model,
+                    "messages": messages,
+                    "max_tokens": max_tokens,
+                    "temperature": temperature,
+                });
+                if stream {
+                    body["stream"] = json!(true);
+                    body["stream_options"] = json!({ "include_usage": true });
+                }
+                if tools {
+                    body["tools"] = json!([{
+                        "type": "function",
+                        "function": {
+                            "name": ACTION_TOOL_NAME,
+                            "description": ACTION_TOOL_DESCRIPTION,
+                            "parameters": action_tool_schema(),
+                        },
+                    }]);
+                }
+                client
+                    .post(format!("{}/chat/completions", api_url))
+                    .bearer_auth(api_key)
+                    .json(&body)
+            }
+            Self::Anthropic => {
+                let (system, turns) = split_system(messages);
+                let mut body = json!({
+                    "model": model,
+                    "max_tokens": max_tokens,
+                    "temperature": temperature,
+                    "messages": turns
+                        .iter()
+                        .map(|m| json!({ "role": m.role, "content": m.content }))
+                        .collect::<Vec<_>>(),
+                });
+                if !system.is_empty() {
+                    body["system"] = json!(system);
+                }
+                if stream {
+                    body["stream"] = json!(true);
+                }
+                if tools {
+                    body["tools"] = json!([{
+                        "name": ACTION_TOOL_NAME,
+                        "description": ACTION_TOOL_DESCRIPTION,
+                        "input_schema": action_tool_schema(),
+                    }]);
+                }
+                client
+                    .post(format!("{}/messages", api_url))
+                    .header("x-api-key", api_key)
+                    .header("anthropic-version", ANTHROPIC_VERSION)
+                    .json(&body)
+            }
+            Self::Gemini => {
+                let (system, turns) = split_system(messages);
+                let mut body = json!({
+                    "contents": turns
+                        .iter()
+                        .map(|m| json!({
+                            "role": if m.role == "assistant" { "model" } else { "user" },
+                            "parts": [{ "text": m.content }],
+                        }))
+                        .collect::<Vec<_>>(),
+                    "generationConfig": {
+                        "maxOutputTokens": max_tokens,
+                        "temperature": temperature,
+                    },
+                });
+                if !system.is_empty() {
+                    body["systemInstruction"] = json!({ "parts": [{ "text": system }] });
+                }
+                if tools {
+                    body["tools"] = json!([{
+                        "functionDeclarations": [{
+                            "name": ACTION_TOOL_NAME,
+                            "description": ACTION_TOOL_DESCRIPTION,
+                            "parametersJsonSchema": action_tool_schema(),
+                        }],
+                    }]);
+                }
+                let url = if stream {
+                    format!("{}/models/{}:streamGenerateContent?alt=sse", api_url, model)
+                } else {
+                    format!("{}/models/{}:generateContent", api_url, model)
+                };
+                client.post(url).header("x-goog-api-key", api_key).json(&body)
+            }
+        }
+    }
+
+    /// 解析非流式响应的文本、Token 用量与工具调用
+    pub fn parse_response(&self, body: &Value) -> Result<ChatReply> {
+        match self {
+            Self::OpenAi => {
+                let message = &body["choices"][0]["message"];
+                let usage = body
+                    .get("usage")
+                    .filter(|u| !u.is_null())
+                    .map(|u| serde_json::from_value(u.clone()))
+                    .transpose()
+                    .context("解析 AI 响应失败")?;
+                let tool_calls = message["tool_calls"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|c| c["function"]["name"] == ACTION_TOOL_NAME)
+                    .filter_map(|c| c["function"]["arguments"].as_str().map(str::to_string))
+                    .collect();
+                Ok(ChatReply {
+                    content: message["content"].as_str().unwrap_or_default().to_string(),
+                    usage,
+                    tool_calls,
+                })
+            }
+            Self::Anthropic => Ok(ChatReply {
+                content: anthropic_text(body),
+                usage: anthropic_usage(&body["usage"]),
+                tool_calls: body["content"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|b| b["type"] == "tool_use" && b["name"] == ACTION_TOOL_NAME)
+                    .map(|b| b["input"].to_string())
+                    .collect(),
+            }),
+            Self::Gemini => Ok(ChatReply {
+                content: gemini_text(body),
+                usage: gemini_usage(body),
+                tool_calls: gemini_calls(body),
+            }),
+        }
+    }
+
+    /// 解析流式响应的一行 (Gemini 的一个数据块可能同时包含文本、工具调用与用量)
+    pub fn parse_sse_line(&self, line: &str) -> Result<Vec<SseEvent>> {
+        if *self == Self::OpenAi {
+            return Ok(vec![parse_sse_line(line)?]);
+        }
+        let Some(data) = line.trim_end_matches(['\r', '\n']).strip_prefix("data:") else {
+            return Ok(vec![SseEvent::Skip]);
+        };
+        let data = data.trim();
+        if data.is_empty() {
+            return Ok(vec![SseEvent::Skip]);
+        }
+        let event: Value =
+            serde_json::from_str(data).context(format!("解析 AI 流式响应失败: {}", data))?;
+
+        let parsed = match self {
+            Self::Anthropic => match event["type"].as_str() {
+                Some("message_start") => SseEvent::Delta(
+                    String::new(),
+                    anthropic_usage(&event["message"]["usage"]),
+                ),
+                Some("content_block_start")
+                    if event["content_block"]["type"] == "tool_use"
+                        && event["content_block"]["name"] == ACTION_TOOL_NAME =>
+                {
+                    SseEvent::ToolCall(event["index"].as_u64().map(|i| i as usize), String::new())
+                }
+                Some("content_block_delta") => match event["delta"]["type"].as_str() {
+                    Some("input_json_delta") => SseEvent::ToolCall(
+                        event["index"].as_u64().map(|i| i as usize),
+                        event["delta"]["partial_json"].as_str().unwrap_or_default().to_string(),
+                    ),
+                    _ => SseEvent::Delta(
+                        event["delta"]["text"].as_str().unwrap_or_default().to_string(),
+                        None,
+                    ),
+                },
+                Some("message_delta") => SseEvent::Delta(String::new(), anthropic_usage(&event["usage"])),
+                Some("message_stop") => SseEvent::Done,
+                Some("error") => anyhow::bail!(
+                    "AI API 错误: {}",
+                    event["error"]["message"].as_str().unwrap_or(data)
+                ),
+                _ => SseEvent::Skip,
+            },
+            _ => {
+                let mut events = vec![SseEvent::Delta(gemini_text(&event), gemini_usage(&event))];
+                events.extend(gemini_calls(&event).into_iter().map(|args| SseEvent::ToolCall(None, args)));
+                return Ok(events);
+            }
+        };
+        Ok(vec![parsed])
+    }
+}
+
+/// 操作建议工具的参数结构 (JSON Schema)，与提示词中的 JSON 格式一致
+fn action_tool_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "actions": {
+                "type": "array",
+                "description": "建议执行的操作列表",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "type": {
+                            "type": "string",
+                            "enum": ACTION_TYPES,
+                            "description": "操作类型",
+                        },
+                        "description": { "type": "string", "description": "操作描述" },
+                        "params": { "type": "object", "description": "操作参数" },
+                        "risk": { "type": "string", "enum": ["low", "medium", "high"] },
+                    },
+                    "required": ["type", "description", "params", "risk"],
+                },
+            },
+            "explanation": { "type": "string", "description": "解释说明" },
+        },
+        "required": ["actions"],
+    })
+}
+
+/// 执行器支持的操作类型
+const ACTION_TYPES: &[&str] = &[
+    "dns_create",
+    "dns_update",
+    "dns_delete",
+    "ssl_set",
+    "cache_purge",
+    "firewall_rule",
+    "setting_update",
+];
+
+/// 拆出系统提示词 (含对话中注入的上下文)，其余为 user / assistant 轮次
+fn split_system(messages: &[ChatMessage]) -> (String, Vec<&ChatMessage>) {
+    let system = messages
+        .iter()
+        .filter(|m| m.role == "system")
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let turns = messages.iter().filter(|m| m.role != "system").collect();
+    (system, turns)
+}
+
+fn anthropic_text(body: &Value) -> String {
+    body["content"]
+        .as_array()
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|b| b["text"].as_str())
+                .collect::<String>()
+        })
+        .unwrap_or_default()
+}
+
+fn anthropic_usage(usage: &Value) -> Option<TokenUsage> {
+    let prompt = usage["input_tokens"].as_u64();
+    let completion = usage["output_tokens"].as_u64();
+    if prompt.is_none() && completion.is_none() {
+        return None;
+    }
+    let (prompt, completion) = (prompt.unwrap_or(0) as u32, completion.unwrap_or(0) as u32);
+    Some(TokenUsage {
+        prompt_tokens: prompt,
+        completion_tokens: completion,
+        total_tokens: prompt + completion,
+    })
+}
+
+fn gemini_text(body: &Value) -> String {
+    body["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(|parts| parts.iter().filter_map(|p| p["text"].as_str()).collect::<String>())
+        .unwrap_or_default()
+}
+
+fn gemini_calls(body: &Value) -> Vec<String> {
+    body["candidates"][0]["content"]["parts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|p| p["functionCall"]["name"] == ACTION_TOOL_NAME)
+        .map(|p| p["functionCall"]["args"].to_string())
+        .collect()
+}
+
+fn gemini_usage(body: &Value) -> Option<TokenUsage> {
+    let usage = body.get("usageMetadata")?;
+    Some(TokenUsage {
+        prompt_tokens: usage["promptTokenCount"].as_u64().unwrap_or(0) as u32,
+        completion_tokens: usage["candidatesTokenCount"].as_u64().unwrap_or(0) as u32,
+        total_tokens: usage["totalTokenCount"].as_u64().unwrap_or(0) as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_stream_events() {
+        let claude = AiProvider::Anthropic;
+        assert_eq!(
+            claude
+                .parse_sse_line(r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"你好"}}"#)
+                .unwrap(),
+            vec![SseEvent::Delta("你好".to_string(), None)]
+        );
+        assert_eq!(
+            claude
+                .parse_sse_line(r#"data: {"type":"message_delta","delta":{},"usage":{"output_tokens":15}}"#)
+                .unwrap(),
+            vec![SseEvent::Delta(
+                String::new(),
+                Some(TokenUsage {
+                    completion_tokens: 15,
+                    total_tokens: 15,
+                    ..Default::default()
+                })
+            )]
+        );
+        assert_eq!(
+            claude
+                .parse_sse_line(r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"actions\":"}}"#)
+                .unwrap(),
+            vec![SseEvent::ToolCall(Some(1), r#"{"actions":"#.to_string())]
+        );
+        assert_eq!(claude.parse_sse_line("event: message_stop\n").unwrap(), vec![SseEvent::Skip]);
+        assert_eq!(claude.parse_sse_line(r#"data: {"type":"message_stop"}"#).unwrap(), vec![SseEvent::Done]);
+
+        let gemini = AiProvider::Gemini;
+        assert_eq!(
+            gemini
+                .parse_sse_line(r#"data: {"candidates":[{"content":{"parts":[{"text":"Hi"},{"functionCall":{"name":"suggest_actions","args":{"actions":[]}}}],"role":"model"}}],"usageMetadata":{"promptTokenCount":8,"candidatesTokenCount":2,"totalTokenCount":10}}"#)
+                .unwrap(),
+            vec![
+                SseEvent::Delta(
+                    "Hi".to_string(),
+                    Some(TokenUsage {
+                        prompt_tokens: 8,
+                        completion_tokens: 2,
+                        total_tokens: 10,
+                    })
+                ),
+                SseEvent::ToolCall(None, r#"{"actions":[]}"#.to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::ai::analyzer::{AiAnalyzer, SuggestedAction};
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output::{format_bytes, format_number};
+use crate::models::zone::Zone;
+
+/// 定时报告的周期
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportSchedule {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl ReportSchedule {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "hourly" => Ok(Self::Hourly),
+            "daily" => Ok(Self::Daily),
+            "weekly" => Ok(Self::Weekly),
+            _ => anyhow::bail!(ValidationError(format!(
+                "未知的报告周期: {} (可选: hourly/daily/weekly)",
+                value
+            ))),
+        }
+    }
+
+    /// 两次报告之间的间隔
+    pub fn interval(&self) -> Duration {
+        match self {
+            Self::Hourly => Duration::from_secs(3600),
+            Self::Daily => Duration::from_secs(24 * 3600),
+            Self::Weekly => Duration::from_secs(7 * 24 * 3600),
+        }
+    }
+}
+
+/// 生成 Markdown 格式的域名健康报告 (概况 + 24 小时流量 + 当前配置 + AI 建议)
+///
+/// `context` 为已收集的域名配置文本，与 `ai analyze` 使用的上下文相同。
+pub async fn build_report(
+    client: &CfClient,
+    analyzer: &AiAnalyzer,
+    zone: &Zone,
+    context: &str,
+) -> Result<String> {
+    let mut report = format!("# 🩺 {} 健康报告\n\n", zone.name);
+    report.push_str(&format!(
+        "生成时间: {}\n\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S %:z")
+    ));
+
+    report.push_str("## 概况\n\n| 项目 | 值 |\n|------|----|\n");
+    report.push_str(&format!("| 状态 | {} |\n", zone.status));
+    if let Some(plan) = zone.plan.as_ref().and_then(|p| p.name.as_deref()) {
+        report.push_str(&format!("| 套餐 | {} |\n", plan));
+    }
+    let dev_mode = zone.development_mode.unwrap_or(0) > 0;
+    report.push_str(&format!("| 开发模式 | {} |\n", if dev_mode { "开启" } else { "关闭" }));
+    if let Some(ns) = &zone.name_servers {
+        report.push_str(&format!("| 名称服务器 | {} |\n", ns.join(", ")));
+    }
+
+    let traffic = traffic_summary(client, &zone.id).await;
+    report.push_str("\n## 最近 24 小时流量\n\n");
+    report.push_str(&traffic);
+
+    report.push_str("\n## 当前配置\n\n");
+    // 配置上下文使用二级标题，报告中降为三级
+    report.push_str(&context.replace("## ", "### "));
+
+    let prompt = format!(
+        "{}域名: {}\n状态: {}\n\n## 最近 24 小时流量\n{}\n{}",
+        super::prompts::HEALTH_REPORT_PROMPT,
+        zone.name,
+        zone.status,
+        traffic,
+        context
+    );
+    let result = analyzer.ask(&prompt).await?;
+
+    report.push_str("\n## AI 建议\n\n");
+    report.push_str(result.content.trim());
+    report.push('\n');
+    if let Some(actions) = result.actions.as_deref().filter(|a| !a.is_empty()) {
+        report.push_str("\n### 建议操作\n\n");
+        report.push_str(&actions_markdown(actions));
+        report.push_str(&format!(
+            "\n可运行 `cfai ai analyze {} --dry-run` 审核后执行。\n",
+            zone.name
+        ));
+    }
+    Ok(report)
+}
+
+/// 最近 24 小时流量的 Markdown 表格，获取失败时返回说明文字
+async fn traffic_summary(client: &CfClient, zone_id: &str) -> String {
+    let totals = match client.get_analytics_24h(zone_id).await {
+        Ok(dashboard) => dashboard.totals,
+        Err(e) => return format!("无法获取流量数据: {:#}\n", e),
+    };
+    let Some(totals) = totals else {
+        return "暂无流量数据\n".to_string();
+    };
+
+    let requests = totals.requests.as_ref().and_then(|r| r.all).unwrap_or(0);
+    let cached = totals.requests.as_ref().and_then(|r| r.cached).unwrap_or(0);
+    let hit_rate = if requests > 0 {
+        format!("{:.1}%", cached as f64 * 100.0 / requests as f64)
+    } else {
+        "-".to_string()
+    };
+
+    let mut table = String::from("| 指标 | 值 |\n|------|----|\n");
+    table.push_str(&format!("| 请求总数 | {} |\n", format_number(requests)));
+    table.push_str(&format!("| 缓存命中率 | {} |\n", hit_rate));
+    table.push_str(&format!(
+        "| 带宽 | {} |\n",
+        format_bytes(totals.bandwidth.as_ref().and_then(|b| b.all).unwrap_or(0))
+    ));
+    table.push_str(&format!(
+        "| 威胁 | {} |\n",
+        format_number(totals.threats.as_ref().and_then(|t| t.all).unwrap_or(0))
+    ));
+    table.push_str(&format!(
+        "| 独立访客 | {} |\n",
+        format_number(totals.uniques.as_ref().and_then(|u| u.all).unwrap_or(0))
+    ));
+    table
+}
+
+fn actions_markdown(actions: &[SuggestedAction]) -> String {
+    actions
+        .iter()
+        .map(|a| {
+            let risk = match a.risk.as_str() {
+                "low" => "🟢",
+                "medium" => "🟡",
+                "high" => "🔴",
+                _ => "⚪",
+            };
+            format!("- {} `{}` {}\n", risk, a.action_type, a.description)
+        })
+        .collect()
+}
@@ -0,0 +1,331 @@
+use anyhow::Result;
+
+use crate::ai::analyzer::SuggestedAction;
+use crate::api::client::CfClient;
+use crate::models::dns::DnsRecord;
+
+/// 安全审查所需的域名实时状态
+#[derive(Debug, Default, Clone)]
+pub struct LiveZoneState {
+    pub zone_name: String,
+    pub dns_records: Vec<DnsRecord>,
+    pub ssl_mode: Option<String>,
+    pub min_tls_version: Option<String>,
+}
+
+impl LiveZoneState {
+    /// 从 Cloudflare 获取审查所需的实时配置
+    pub async fn fetch(client: &CfClient, zone_id: &str) -> Result<Self> {
+        let zone = client.get_zone(zone_id).await?;
+        let dns_records = client.list_all_dns_records(zone_id).await?;
+        Ok(Self {
+            zone_name: zone.name,
+            dns_records,
+            ssl_mode: client.get_ssl_mode(zone_id).await.ok(),
+            min_tls_version: client
+                .get_zone_setting(zone_id, "min_tls_version")
+                .await
+                .ok()
+                .and_then(|s| s.value.as_str().map(|v| v.to_string())),
+        })
+    }
+}
+
+/// 风险等级排序 (未知等级按 medium 处理)
+pub fn risk_rank(risk: &str) -> u8 {
+    match risk {
+        "low" => 0,
+        "medium" => 1,
+        "high" => 2,
+        _ => 1,
+    }
+}
+
+/// 提高风险等级 (只升不降) 并记录原因
+fn raise(action: &mut SuggestedAction, risk: &str, note: impl Into<String>) {
+    if risk_rank(risk) > risk_rank(&action.risk) {
+        action.risk = risk.to_string();
+    }
+    action.review_notes.push(note.into());
+}
+
+/// 操作类型本身的最低风险等级，不依赖调用方自报的 `risk`
+///
+/// 删除、修改已有记录、修改域名设置与防火墙 / IP 访问规则至少为 medium；
+/// 新建记录与按 URL 等清除缓存为 low，清除全部缓存为 medium。
+pub fn minimum_risk(action_type: &str, params: &serde_json::Value) -> &'static str {
+    match action_type {
+        "dns_create" => "low",
+        // 未指定 type 时执行器按清除全部缓存处理
+        "cache_purge" if params["type"].as_str().unwrap_or("purge_all") != "purge_all" => "low",
+        _ => "medium",
+    }
+}
+
+/// 把风险等级提高到操作类型的最低等级
+pub fn enforce_minimum_risk(action: &mut SuggestedAction) {
+    let floor = minimum_risk(&action.action_type, &action.params);
+    if risk_rank(floor) > risk_rank(&action.risk) {
+        let note = format!("{} 操作的风险等级至少为 {}", action.action_type, floor);
+        raise(action, floor, note);
+    }
+}
+
+/// 阻止执行并记录原因
+fn block(action: &mut SuggestedAction, note: impl Into<String>) {
+    action.blocked = true;
+    action.risk = "high".to_string();
+    action.review_notes.push(note.into());
+}
+
+fn ssl_mode_rank(mode: &str) -> u8 {
+    match mode {
+        "off" => 0,
+        "flexible" => 1,
+        "full" => 2,
+        "strict" | "origin_pull" => 3,
+        _ => 2,
+    }
+}
+
+/// 对照域名实时状态审查 AI 建议的操作
+///
+/// AI 自报的 `risk` 只会被调高；明显会造成故障或必然失败的操作会被标记为 blocked，
+/// 执行器会跳过这些操作。
+pub fn review_actions(actions: &mut [SuggestedAction], state: &LiveZoneState) {
+    for action in actions.iter_mut() {
+        let params = action.params.clone();
+        match action.action_type.as_str() {
+            "dns_delete" => review_dns_delete(action, &params, state),
+            "dns_update" => review_dns_update(action, &params, state),
+            "dns_create" => review_dns_create(action, &params, state),
+            "ssl_set" => {
+                let setting = params["setting"].as_str().unwrap_or("");
+                let value = params["value"].as_str();
+                match (setting, value) {
+                    ("ssl_mode", Some(mode)) => review_ssl_mode(action, mode, state),
+                    ("min_tls_version", Some(v)) => review_min_tls(action, v, state),
+                    ("always_https", _) if params["enable"].as_bool() == Some(false) => {
+                        raise(action, "medium", "关闭 Always HTTPS 会允许明文访问");
+                    }
+                    _ => {}
+                }
+            }
+            "setting_update" => {
+                let setting = params["setting_id"].as_str().unwrap_or("");
+                let value = params["value"].as_str();
+                match (setting, value) {
+                    ("ssl", Some(mode)) => review_ssl_mode(action, mode, state),
+                    ("min_tls_version", Some(v)) => review_min_tls(action, v, state),
+                    ("security_level", Some(level)) => review_security_level(action, level),
+                    ("development_mode", Some("on")) => {
+                        raise(action, "medium", "开发模式会绕过缓存，源站压力会上升");
+                    }
+                    ("polish", Some("lossy")) => {
+                        raise(action, "medium", "有损压缩会降低图片质量，请确认可以接受");
+                    }
+                    _ => {}
+                }
+            }
+            "cache_purge" if params["type"].as_str().unwrap_or("purge_all") == "purge_all" => {
+                raise(action, "medium", "清除全部缓存会导致短时间内源站回源量激增");
+            }
+            "firewall_rule" => match params["type"].as_str().unwrap_or("") {
+                "block_ip" => {
+                    let ip = params["ip"].as_str().unwrap_or("");
+                    let is_origin = state
+                        .dns_records
+                        .iter()
+                        .any(|r| matches!(r.record_type.as_str(), "A" | "AAAA") && r.content == ip);
+                    if is_origin {
+                        block(action, format!("{} 是本域名 DNS 记录指向的源站 IP，封禁会导致服务中断", ip));
+                    }
+                }
+                "security_level" => {
+                    review_security_level(action, params["level"].as_str().unwrap_or(""));
+                }
+                "under_attack" if params["enable"].as_bool() != Some(false) => {
+                    raise(action, "medium", "Under Attack 模式会对所有访客进行质询，影响 API 和爬虫");
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+fn find_record<'a>(state: &'a LiveZoneState, params: &serde_json::Value) -> Option<&'a DnsRecord> {
+    let id = params["record_id"].as_str()?;
+    state.dns_records.iter().find(|r| r.id.as_deref() == Some(id))
+}
+
+/// 同名同类型的记录数量
+fn count_same(state: &LiveZoneState, record_type: &str, name: &str) -> usize {
+    state
+        .dns_records
+        .iter()
+        .filter(|r| r.record_type == record_type && r.name.eq_ignore_ascii_case(name))
+        .count()
+}
+
+fn review_dns_delete(action: &mut SuggestedAction, params: &serde_json::Value, state: &LiveZoneState) {
+    let Some(record) = find_record(state, params) else {
+        block(action, "要删除的 DNS 记录不存在");
+        return;
+    };
+    let is_apex = record.name.eq_ignore_ascii_case(&state.zone_name);
+    match record.record_type.as_str() {
+        "NS" if is_apex => block(action, "根域 NS 记录由 Cloudflare 管理，不能删除"),
+        "MX" if count_same(state, "MX", &record.name) == 1 => {
+            raise(action, "high", format!("这是 {} 唯一的 MX 记录，删除后将无法收信", record.name));
+        }
+        "A" | "AAAA" | "CNAME" if is_apex => {
+            let apex_targets = ["A", "AAAA", "CNAME"]
+                .iter()
+                .map(|t| count_same(state, t, &record.name))
+                .sum::<usize>();
+            if apex_targets == 1 {
+                raise(action, "high", "这是根域唯一的解析记录，删除后网站将无法访问");
+            }
+        }
+        "TXT" if record.content.contains("v=spf1") || record.content.contains("v=DMARC1") => {
+            raise(action, "medium", "删除 SPF/DMARC 记录会影响邮件投递");
+        }
+        _ => {}
+    }
+}
+
+fn review_dns_update(action: &mut SuggestedAction, params: &serde_json::Value, state: &LiveZoneState) {
+    let Some(record) = find_record(state, params) else {
+        block(action, "要更新的 DNS 记录不存在");
+        return;
+    };
+    if let Some(new_type) = params["type"].as_str() {
+        if new_type != record.record_type {
+            raise(action, "high", format!("记录类型将从 {} 改为 {}", record.record_type, new_type));
+        }
+    }
+    if let Some(content) = params["content"].as_str() {
+        if content != record.content
+            && record.record_type == "MX"
+            && count_same(state, "MX", &record.name) == 1
+        {
+            raise(action, "high", "将修改唯一的 MX 记录，请确认新的邮件服务器可用");
+        }
+    }
+    if record.proxied == Some(true) && params["proxied"].as_bool() == Some(false) {
+        raise(action, "medium", "关闭代理会暴露源站 IP");
+    }
+}
+
+fn review_dns_create(action: &mut SuggestedAction, params: &serde_json::Value, state: &LiveZoneState) {
+    let (Some(record_type), Some(name)) = (params["type"].as_str(), params["name"].as_str()) else {
+        return;
+    };
+    // 相对名称补全为完整域名
+    let fqdn = if name == "@" {
+        state.zone_name.clone()
+    } else if name.ends_with(&state.zone_name) {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, state.zone_name)
+    };
+    let content = params["content"].as_str().unwrap_or("");
+
+    let same_name: Vec<&DnsRecord> = state
+        .dns_records
+        .iter()
+        .filter(|r| r.name.eq_ignore_ascii_case(&fqdn))
+        .collect();
+    if same_name.iter().any(|r| r.record_type == record_type && r.content == content) {
+        block(action, format!("{} {} → {} 已存在", record_type, fqdn, content));
+    } else if record_type == "CNAME" && !same_name.is_empty() {
+        block(action, format!("{} 已有其他记录，不能再添加 CNAME", fqdn));
+    } else if same_name.iter().any(|r| r.record_type == "CNAME") {
+        block(action, format!("{} 已有 CNAME 记录，不能再添加其他类型记录", fqdn));
+    }
+}
+
+fn review_ssl_mode(action: &mut SuggestedAction, mode: &str, state: &LiveZoneState) {
+    if mode == "off" {
+        raise(action, "high", "关闭 SSL 后所有 HTTPS 访问将失败");
+        return;
+    }
+    if let Some(current) = &state.ssl_mode {
+        if ssl_mode_rank(mode) < ssl_mode_rank(current) {
+            raise(action, "high", format!("SSL 模式将从 {} 降级为 {}", current, mode));
+        }
+    }
+}
+
+fn review_min_tls(action: &mut SuggestedAction, version: &str, state: &LiveZoneState) {
+    if let Some(current) = &state.min_tls_version {
+        if version < current.as_str() {
+            raise(action, "medium", format!("最小 TLS 版本将从 {} 降低到 {}", current, version));
+        } else if version == "1.3" && current.as_str() != "1.3" {
+            raise(action, "medium", "仅允许 TLS 1.3 会导致部分旧客户端无法访问");
+        }
+    }
+}
+
+fn review_security_level(action: &mut SuggestedAction, level: &str) {
+    match level {
+        "essentially_off" | "off" => raise(action, "high", "安全级别将几乎关闭"),
+        "under_attack" => raise(action, "medium", "Under Attack 模式会对所有访客进行质询"),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, record_type: &str, name: &str, content: &str) -> DnsRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "type": record_type, "name": name, "content": content
+        }))
+        .unwrap()
+    }
+
+    fn action(action_type: &str, params: serde_json::Value) -> SuggestedAction {
+        serde_json::from_value(serde_json::json!({
+            "type": action_type, "description": "", "params": params, "risk": "low"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_review_actions() {
+        let state = LiveZoneState {
+            zone_name: "example.com".to_string(),
+            dns_records: vec![
+                record("1", "MX", "example.com", "mx.example.com"),
+                record("2", "CNAME", "www.example.com", "example.com"),
+            ],
+            ssl_mode: Some("strict".to_string()),
+            ..Default::default()
+        };
+        let mut actions = vec![
+            action("dns_delete", serde_json::json!({ "record_id": "1" })),
+            action("dns_create", serde_json::json!({ "type": "A", "name": "www", "content": "1.2.3.4" })),
+            action("ssl_set", serde_json::json!({ "setting": "ssl_mode", "value": "flexible" })),
+            action("dns_delete", serde_json::json!({ "record_id": "404" })),
+        ];
+        review_actions(&mut actions, &state);
+
+        assert_eq!(actions[0].risk, "high");
+        assert!(!actions[0].blocked);
+        assert!(actions[1].blocked);
+        assert_eq!(actions[2].risk, "high");
+        assert!(actions[3].blocked);
+
+        let mut update = action("setting_update", serde_json::json!({ "setting_id": "ssl", "value": "off" }));
+        enforce_minimum_risk(&mut update);
+        assert_eq!(update.risk, "medium");
+        let mut purge = action("cache_purge", serde_json::json!({ "type": "purge_urls", "urls": [] }));
+        enforce_minimum_risk(&mut purge);
+        assert_eq!(purge.risk, "low");
+        assert_eq!(minimum_risk("cache_purge", &serde_json::json!({})), "medium");
+        assert_eq!(minimum_risk("cache_purge", &serde_json::json!({ "type": "purge_all" })), "medium");
+    }
+}
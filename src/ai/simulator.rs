@@ -0,0 +1,244 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::ai::analyzer::SuggestedAction;
+use crate::ai::executor::{dns_request_from_params, json_array_to_strings, params_to_bool};
+use crate::api::firewall::ip_access_rule_request;
+use crate::cli::output;
+use crate::models::cache::PurgeCacheRequest;
+
+/// 模拟执行时将要发出的 Cloudflare API 请求
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedApiCall {
+    pub method: &'static str,
+    pub path: String,
+    pub body: Option<serde_json::Value>,
+}
+
+impl PlannedApiCall {
+    fn new(method: &'static str, path: String, body: Option<serde_json::Value>) -> Self {
+        Self { method, path, body }
+    }
+
+    fn patch_setting(zone_id: &str, setting: &str, value: serde_json::Value) -> Self {
+        Self::new(
+            "PATCH",
+            format!("/zones/{}/settings/{}", zone_id, setting),
+            Some(serde_json::json!({ "value": value })),
+        )
+    }
+}
+
+/// 将 AI 建议的操作转换为执行时会发出的 API 请求 (不发出任何请求)
+///
+/// 参数解析与 `executor` 实际执行时一致，参数不合法时返回同样的错误。
+pub fn simulate_action(zone_id: &str, action: &SuggestedAction) -> Result<PlannedApiCall> {
+    let params = &action.params;
+    let on_off = |enable: bool| serde_json::json!(if enable { "on" } else { "off" });
+
+    let call = match action.action_type.as_str() {
+        "ssl_set" => {
+            let setting = params["setting"]
+                .as_str()
+                .context("ssl_set 缺少 setting 参数")?;
+            match setting {
+                "ssl_mode" => {
+                    let value = params["value"].as_str().context("缺少 value 参数")?;
+                    PlannedApiCall::patch_setting(zone_id, "ssl", serde_json::json!(value))
+                }
+                "always_https" => PlannedApiCall::patch_setting(
+                    zone_id,
+                    "always_use_https",
+                    on_off(params_to_bool(params, "enable")?),
+                ),
+                "min_tls_version" => {
+                    let value = params["value"].as_str().context("缺少 value 参数")?;
+                    PlannedApiCall::patch_setting(zone_id, "min_tls_version", serde_json::json!(value))
+                }
+                "opportunistic_encryption" | "automatic_https_rewrites" => PlannedApiCall::patch_setting(
+                    zone_id,
+                    setting,
+                    on_off(params_to_bool(params, "enable")?),
+                ),
+                _ => anyhow::bail!("未知的 SSL 设置: {}", setting),
+            }
+        }
+        "setting_update" => {
+            let setting_id = params["setting_id"]
+                .as_str()
+                .context("setting_update 缺少 setting_id 参数")?;
+            let value = params
+                .get("value")
+                .context("setting_update 缺少 value 参数")?
+                .clone();
+            PlannedApiCall::patch_setting(zone_id, setting_id, value)
+        }
+        "dns_create" => {
+            let request = dns_request_from_params(params, "dns_create")?;
+            PlannedApiCall::new(
+                "POST",
+                format!("/zones/{}/dns_records", zone_id),
+                Some(serde_json::to_value(&request)?),
+            )
+        }
+        "dns_update" => {
+            let record_id = params["record_id"]
+                .as_str()
+                .context("dns_update 缺少 record_id 参数")?;
+            let request = dns_request_from_params(params, "dns_update")?;
+            PlannedApiCall::new(
+                "PUT",
+                format!("/zones/{}/dns_records/{}", zone_id, record_id),
+                Some(serde_json::to_value(&request)?),
+            )
+        }
+        "dns_delete" => {
+            let record_id = params["record_id"]
+                .as_str()
+                .context("dns_delete 缺少 record_id 参数")?;
+            PlannedApiCall::new("DELETE", format!("/zones/{}/dns_records/{}", zone_id, record_id), None)
+        }
+        "cache_purge" => {
+            let purge_type = params["type"].as_str().unwrap_or("purge_all");
+            let mut body = PurgeCacheRequest {
+                purge_everything: None,
+                files: None,
+                tags: None,
+                hosts: None,
+                prefixes: None,
+            };
+            match purge_type {
+                "purge_all" => body.purge_everything = Some(true),
+                "purge_urls" => {
+                    body.files = Some(
+                        json_array_to_strings(&params["urls"])
+                            .context("cache_purge purge_urls 缺少 urls 参数")?,
+                    )
+                }
+                "purge_tags" => {
+                    body.tags = Some(
+                        json_array_to_strings(&params["tags"])
+                            .context("cache_purge purge_tags 缺少 tags 参数")?,
+                    )
+                }
+                "purge_hosts" => {
+                    body.hosts = Some(
+                        json_array_to_strings(&params["hosts"])
+                            .context("cache_purge purge_hosts 缺少 hosts 参数")?,
+                    )
+                }
+                _ => anyhow::bail!("未知的缓存清除类型: {}", purge_type),
+            }
+            PlannedApiCall::new(
+                "POST",
+                format!("/zones/{}/purge_cache", zone_id),
+                Some(serde_json::to_value(&body)?),
+            )
+        }
+        "firewall_rule" => {
+            let rule_type = params["type"]
+                .as_str()
+                .context("firewall_rule 缺少 type 参数")?;
+            match rule_type {
+                "block_ip" | "whitelist_ip" => {
+                    let ip = params["ip"]
+                        .as_str()
+                        .with_context(|| format!("{} 缺少 ip 参数", rule_type))?;
+                    let mode = if rule_type == "block_ip" { "block" } else { "whitelist" };
+                    let request = ip_access_rule_request(mode, ip, params["note"].as_str())?;
+                    PlannedApiCall::new(
+                        "POST",
+                        format!("/zones/{}/firewall/access_rules/rules", zone_id),
+                        Some(serde_json::to_value(&request)?),
+                    )
+                }
+                "security_level" => {
+                    let level = params["level"]
+                        .as_str()
+                        .context("security_level 缺少 level 参数")?;
+                    PlannedApiCall::patch_setting(zone_id, "security_level", serde_json::json!(level))
+                }
+                "under_attack" => {
+                    let enable = params_to_bool(params, "enable")?;
+                    let level = if enable { "under_attack" } else { "medium" };
+                    PlannedApiCall::patch_setting(zone_id, "security_level", serde_json::json!(level))
+                }
+                "browser_check" => PlannedApiCall::patch_setting(
+                    zone_id,
+                    "browser_check",
+                    on_off(params_to_bool(params, "enable")?),
+                ),
+                _ => anyhow::bail!("未知的防火墙规则类型: {}", rule_type),
+            }
+        }
+        other => anyhow::bail!("未知的操作类型: {}", other),
+    };
+    Ok(call)
+}
+
+/// 打印每个建议操作将发出的 API 请求 (--dry-run)，不执行任何操作
+pub fn print_dry_run(zone_id: &str, actions: &[SuggestedAction]) {
+    if actions.is_empty() {
+        return;
+    }
+
+    println!("\n{}", "🧪 模拟执行 (--dry-run，不会发出任何变更请求)".bold().cyan());
+    output::separator();
+
+    let mut invalid = 0;
+    for (i, action) in actions.iter().enumerate() {
+        println!(
+            "  {}. {} [风险: {}]{}",
+            i + 1,
+            action.description,
+            action.risk,
+            if action.blocked { " ⛔ 已阻止，实际执行时将跳过".red().to_string() } else { String::new() }
+        );
+        match simulate_action(zone_id, action) {
+            Ok(call) => {
+                println!("     {} {}", call.method.bold(), call.path);
+                if let Some(body) = &call.body {
+                    println!("     {}", body.to_string().dimmed());
+                }
+            }
+            Err(e) => {
+                invalid += 1;
+                println!("     {} {:#}", "✗ 无法执行:".red(), e);
+            }
+        }
+    }
+
+    output::separator();
+    if invalid > 0 {
+        output::warn(&format!("{} 个操作参数无效，实际执行时会失败", invalid));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulate_action() {
+        let action = SuggestedAction {
+            action_type: "ssl_set".into(),
+            description: "开启 Always HTTPS".into(),
+            params: serde_json::json!({ "setting": "always_https", "enable": "on" }),
+            risk: "low".into(),
+            review_notes: Vec::new(),
+            blocked: false,
+        };
+        let call = simulate_action("z1", &action).unwrap();
+        assert_eq!(call.method, "PATCH");
+        assert_eq!(call.path, "/zones/z1/settings/always_use_https");
+        assert_eq!(call.body, Some(serde_json::json!({ "value": "on" })));
+
+        let action = SuggestedAction {
+            action_type: "dns_create".into(),
+            params: serde_json::json!({ "type": "A", "name": "www" }),
+            ..action
+        };
+        assert!(simulate_action("z1", &action).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::config::settings::{AiConfig, AppConfig};
+
+/// 账本保留的天数
+const MAX_LEDGER_DAYS: usize = 400;
+
+/// 本次运行是否已提示过超出预算
+static BUDGET_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// 单次请求的 Token 用量
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
+    #[serde(default)]
+    pub total_tokens: u32,
+}
+
+impl TokenUsage {
+    /// 合并流式响应中分段给出的用量 (各服务给出的都是累计值，取较大者)
+    pub fn merge(self, other: TokenUsage) -> TokenUsage {
+        let prompt_tokens = self.prompt_tokens.max(other.prompt_tokens);
+        let completion_tokens = self.completion_tokens.max(other.completion_tokens);
+        TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: self
+                .total_tokens
+                .max(other.total_tokens)
+                .max(prompt_tokens + completion_tokens),
+        }
+    }
+}
+
+/// 某天某个模型的累计用量
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UsageEntry {
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    /// 按记录时的单价估算的费用 (美元)
+    pub cost: f64,
+}
+
+impl UsageEntry {
+    pub fn add(&mut self, other: &UsageEntry) {
+        self.requests += other.requests;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+        self.cost += other.cost;
+    }
+}
+
+/// AI 用量账本: 日期 (YYYY-MM-DD) → 模型 → 累计用量
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct UsageLedger {
+    #[serde(default)]
+    pub days: BTreeMap<String, BTreeMap<String, UsageEntry>>,
+}
+
+impl UsageLedger {
+    /// 账本文件路径
+    pub fn path() -> Result<PathBuf> {
+        Ok(AppConfig::config_dir()?.join("ai_usage.json"))
+    }
+
+    /// 加载账本，文件不存在时返回空账本
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("读取 AI 用量记录失败: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("解析 AI 用量记录失败: {}", path.display()))
+    }
+
+    /// 保存账本 (仅保留最近的记录)
+    pub fn save(&mut self) -> Result<()> {
+        while self.days.len() > MAX_LEDGER_DAYS {
+            self.days.pop_first();
+        }
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("写入 AI 用量记录失败: {}", path.display()))
+    }
+
+    /// 累加一次请求的用量
+    pub fn record(&mut self, date: &str, model: &str, usage: &TokenUsage, cost: f64) {
+        let entry = self
+            .days
+            .entry(date.to_string())
+            .or_default()
+            .entry(model.to_string())
+            .or_default();
+        entry.add(&UsageEntry {
+            requests: 1,
+            prompt_tokens: usage.prompt_tokens as u64,
+            completion_tokens: usage.completion_tokens as u64,
+            total_tokens: usage.total_tokens.max(usage.prompt_tokens + usage.completion_tokens) as u64,
+            cost,
+        });
+    }
+
+    /// 指定月份 (YYYY-MM) 的累计用量
+    pub fn month_total(&self, month: &str) -> UsageEntry {
+        let mut total = UsageEntry::default();
+        for (_, models) in self.days.iter().filter(|(date, _)| date.starts_with(month)) {
+            for entry in models.values() {
+                total.add(entry);
+            }
+        }
+        total
+    }
+}
+
+/// 按配置的每千 Token 单价估算费用 (美元)，未配置单价时为 0
+///
+/// 服务端只返回总量时按输出单价计算，宁可高估也不低估预算消耗。
+pub fn estimate_cost(ai: &AiConfig, usage: &TokenUsage) -> f64 {
+    if usage.prompt_tokens == 0 && usage.completion_tokens == 0 {
+        return ai.output_price_per_1k.unwrap_or(0.0) * usage.total_tokens as f64 / 1000.0;
+    }
+    let input = ai.input_price_per_1k.unwrap_or(0.0) * usage.prompt_tokens as f64 / 1000.0;
+    let output = ai.output_price_per_1k.unwrap_or(0.0) * usage.completion_tokens as f64 / 1000.0;
+    input + output
+}
+
+/// 当前月份 (YYYY-MM)
+pub fn current_month() -> String {
+    chrono::Local::now().format("%Y-%m").to_string()
+}
+
+/// 将一次请求的用量写入账本
+pub fn record(ai: &AiConfig, model: &str, usage: &TokenUsage) -> Result<()> {
+    let mut ledger = UsageLedger::load()?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    ledger.record(&today, model, usage, estimate_cost(ai, usage));
+    ledger.save()
+}
+
+/// 请求前检查本月预算: 超出时按 `ai.budget_action` 警告或阻止
+pub fn check_budget(ai: &AiConfig) -> Result<()> {
+    let Some(budget) = ai.monthly_budget else {
+        return Ok(());
+    };
+    let spent = UsageLedger::load()?.month_total(&current_month()).cost;
+    if spent < budget {
+        return Ok(());
+    }
+
+    let message = format!("本月 AI 费用估算 ${:.2} 已达到预算 ${:.2}", spent, budget);
+    if ai.budget_action.as_deref() == Some("block") {
+        anyhow::bail!(ValidationError(format!(
+            "{}，已阻止请求 (可调整 ai.monthly_budget 或 ai.budget_action)",
+            message
+        )));
+    }
+    if !BUDGET_WARNED.swap(true, Ordering::SeqCst) {
+        output::warn(&message);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ledger_month_total() {
+        let ai = AiConfig {
+            input_price_per_1k: Some(0.005),
+            output_price_per_1k: Some(0.015),
+            ..AppConfig::default().ai
+        };
+        let usage = TokenUsage {
+            prompt_tokens: 2000,
+            completion_tokens: 1000,
+            total_tokens: 3000,
+        };
+        let cost = estimate_cost(&ai, &usage);
+        assert!((cost - 0.025).abs() < 1e-9);
+
+        let mut ledger = UsageLedger::default();
+        ledger.record("2026-09-30", "gpt-4o", &usage, cost);
+        ledger.record("2026-10-01", "gpt-4o", &usage, cost);
+        ledger.record("2026-10-02", "gpt-4o-mini", &usage, cost);
+
+        let october = ledger.month_total("2026-10");
+        assert_eq!(october.requests, 2);
+        assert_eq!(october.total_tokens, 6000);
+        assert!((october.cost - 0.05).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::access::*;
+use crate::models::common::CfResponse;
+
+impl CfClient {
+    // ==================== Zero Trust Access ====================
+
+    /// 列出 Access 应用
+    pub async fn list_access_apps(&self, account_id: &str) -> Result<Vec<AccessApplication>> {
+        let resp: CfResponse<Vec<AccessApplication>> = self
+            .get(&format!("/accounts/{}/access/apps", account_id))
+            .await?;
+        resp.result.context("获取 Access 应用列表失败")
+    }
+
+    /// 创建 Access 应用
+    pub async fn create_access_app(
+        &self,
+        account_id: &str,
+        request: &CreateAccessAppRequest,
+    ) -> Result<AccessApplication> {
+        let resp: CfResponse<AccessApplication> = self
+            .post(&format!("/accounts/{}/access/apps", account_id), request)
+            .await?;
+        resp.result.context("创建 Access 应用失败")
+    }
+
+    /// 删除 Access 应用
+    pub async fn delete_access_app(&self, account_id: &str, app_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/accounts/{}/access/apps/{}", account_id, app_id))
+            .await?;
+        Ok(())
+    }
+
+    /// 列出 Access 应用的策略
+    pub async fn list_access_policies(
+        &self,
+        account_id: &str,
+        app_id: &str,
+    ) -> Result<Vec<AccessPolicy>> {
+        let resp: CfResponse<Vec<AccessPolicy>> = self
+            .get(&format!(
+                "/accounts/{}/access/apps/{}/policies",
+                account_id, app_id
+            ))
+            .await?;
+        resp.result.context("获取 Access 策略失败")
+    }
+
+    /// 为 Access 应用创建策略
+    pub async fn create_access_policy(
+        &self,
+        account_id: &str,
+        app_id: &str,
+        request: &CreateAccessPolicyRequest,
+    ) -> Result<AccessPolicy> {
+        let resp: CfResponse<AccessPolicy> = self
+            .post(
+                &format!("/accounts/{}/access/apps/{}/policies", account_id, app_id),
+                request,
+            )
+            .await?;
+        resp.result.context("创建 Access 策略失败")
+    }
+
+    /// 删除 Access 策略
+    pub async fn delete_access_policy(
+        &self,
+        account_id: &str,
+        app_id: &str,
+        policy_id: &str,
+    ) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!(
+                "/accounts/{}/access/apps/{}/policies/{}",
+                account_id, app_id, policy_id
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+
+use crate::api::client::CfClient;
+use crate::models::account::*;
+use crate::models::common::{CfResponse, PaginationParams};
+use crate::models::token::*;
+
+impl CfClient {
+    // ==================== 账户管理 ====================
+
+    /// 列出当前凭据可访问的全部账户 (自动翻页)
+    pub async fn list_accounts(&self) -> Result<Vec<Account>> {
+        self.get_all_pages("/accounts").await.context("获取账户列表失败")
+    }
+
+    /// 获取账户详情
+    pub async fn get_account(&self, account_id: &str) -> Result<Account> {
+        let resp: CfResponse<Account> = self.get(&format!("/accounts/{}", account_id)).await?;
+        resp.result.context("获取账户详情失败")
+    }
+
+    /// 列出账户成员 (自动翻页)
+    pub async fn list_account_members(&self, account_id: &str) -> Result<Vec<AccountMember>> {
+        self.get_all_pages(&format!("/accounts/{}/members", account_id))
+            .await
+            .context("获取账户成员失败")
+    }
+
+    // ==================== API Token ====================
+
+    /// 验证当前 API Token，返回 Token ID 与状态
+    pub async fn verify_token_details(&self) -> Result<TokenVerification> {
+        let resp: CfResponse<TokenVerification> = self.get("/user/tokens/verify").await?;
+        resp.result.context("验证 API Token 失败")
+    }
+
+    /// 获取 API Token 详情 (含权限策略，需要 Token 具有 "API Tokens Read" 权限)
+    pub async fn get_api_token(&self, token_id: &str) -> Result<ApiToken> {
+        let resp: CfResponse<ApiToken> = self.get(&format!("/user/tokens/{}", token_id)).await?;
+        resp.result.context("获取 API Token 详情失败")
+    }
+
+    /// 按 result_info.total_pages 逐页获取列表
+    pub(crate) async fn get_all_pages<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut page = 1;
+        loop {
+            let params = PaginationParams {
+                page: Some(page),
+                per_page: Some(50),
+            };
+            let resp: CfResponse<Vec<T>> = self.get_with_params(path, &params).await?;
+            let total_pages = resp
+                .result_info
+                .as_ref()
+                .and_then(|i| i.total_pages)
+                .unwrap_or(1);
+            items.extend(resp.result.unwrap_or_default());
+            if page >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+        Ok(items)
+    }
+}
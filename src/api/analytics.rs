@@ -2,19 +2,20 @@ use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
 
 use crate::api::client::CfClient;
+use crate::api::error::ApiError;
 use crate::models::analytics::*;
 
 impl CfClient {
     // ==================== 分析数据 (GraphQL API) ====================
 
     /// 执行 GraphQL 查询
-    async fn graphql_query(&self, query: &str, variables: serde_json::Value) -> Result<serde_json::Value> {
+    pub(crate) async fn graphql_query(&self, query: &str, variables: serde_json::Value) -> Result<serde_json::Value> {
         let body = serde_json::json!({
             "query": query,
             "variables": variables
         });
 
-        let resp = self.post_raw("https://api.cloudflare.com/client/v4/graphql", &body).await?;
+        let resp = self.post_raw(&self.graphql_url(), &body).await?;
 
         // 检查 GraphQL 错误
         if let Some(errors) = resp.get("errors") {
@@ -24,7 +25,12 @@ impl CfClient {
                         .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
                         .map(|s| s.to_string())
                         .collect();
-                    anyhow::bail!("GraphQL 错误: {}", error_msgs.join("; "));
+                    return Err(ApiError {
+                        status: 200,
+                        codes: Vec::new(),
+                        message: format!("GraphQL 错误: {}", error_msgs.join("; ")),
+                    }
+                    .into());
                 }
             }
         }
@@ -33,32 +39,48 @@ impl CfClient {
     }
 
     /// 获取域名分析数据 (GraphQL)
+    ///
+    /// 按 `params` 的时间范围和粒度查询时间序列，汇总数据由时间序列累加得到。
     pub async fn get_analytics(
         &self,
         zone_id: &str,
-        _params: &AnalyticsParams,
+        params: &AnalyticsParams,
     ) -> Result<AnalyticsDashboard> {
-        let now = Utc::now();
-        let yesterday = now - Duration::days(1);
-        let week_ago = now - Duration::days(7);
-
-        // 日期格式: YYYY-MM-DD (用于 httpRequests1dGroups)
-        let date_since = week_ago.format("%Y-%m-%d").to_string();
-        let date_until = now.format("%Y-%m-%d").to_string();
-
-        // 时间戳格式: ISO8601 (用于 httpRequests1hGroups)
-        let datetime_since = yesterday.format("%Y-%m-%dT%H:%M:%SZ").to_string();
-        let datetime_until = now.format("%Y-%m-%dT%H:%M:%SZ").to_string();
-
-        let query = r#"
-            query GetZoneAnalytics($zoneTag: String!, $dateSince: Date!, $dateUntil: Date!, $datetimeSince: Time!, $datetimeUntil: Time!) {
-                viewer {
-                    zones(filter: { zoneTag: $zoneTag }) {
-                        httpRequests1dGroups(
-                            limit: 7
-                            filter: { date_geq: $dateSince, date_leq: $dateUntil }
-                        ) {
-                            sum {
+        let (since, until, granularity) = params.resolve()?;
+
+        // 小时粒度按时间戳过滤，天粒度按日期过滤
+        let (dimension, filter, since_value, until_value, limit) = match granularity {
+            AnalyticsGranularity::Hour => (
+                "datetime",
+                "datetime_geq: $since, datetime_lt: $until",
+                since.format("%Y-%m-%dT%H:00:00Z").to_string(),
+                until.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                (until - since).num_hours() + 1,
+            ),
+            AnalyticsGranularity::Day => (
+                "date",
+                "date_geq: $since, date_leq: $until",
+                since.format("%Y-%m-%d").to_string(),
+                until.format("%Y-%m-%d").to_string(),
+                (until - since).num_days() + 1,
+            ),
+        };
+        let var_type = if granularity == AnalyticsGranularity::Hour { "Time" } else { "Date" };
+
+        let query = format!(
+            r#"
+            query GetZoneAnalytics($zoneTag: String!, $since: {var_type}!, $until: {var_type}!) {{
+                viewer {{
+                    zones(filter: {{ zoneTag: $zoneTag }}) {{
+                        {dataset}(
+                            limit: {limit}
+                            filter: {{ {filter} }}
+                            orderBy: [{dimension}_ASC]
+                        ) {{
+                            dimensions {{
+                                {dimension}
+                            }}
+                            sum {{
                                 requests
                                 cachedRequests
                                 encryptedRequests
@@ -67,41 +89,41 @@ impl CfClient {
                                 encryptedBytes
                                 threats
                                 pageViews
-                            }
-                            uniq {
+                                responseStatusMap {{
+                                    edgeResponseStatus
+                                    requests
+                                }}
+                                countryMap {{
+                                    clientCountryName
+                                    requests
+                                    bytes
+                                    threats
+                                }}
+                                contentTypeMap {{
+                                    edgeResponseContentTypeName
+                                    requests
+                                    bytes
+                                }}
+                            }}
+                            uniq {{
                                 uniques
-                            }
-                        }
-                        httpRequests1hGroups(
-                            limit: 24
-                            filter: { datetime_geq: $datetimeSince, datetime_leq: $datetimeUntil }
-                            orderBy: [datetime_ASC]
-                        ) {
-                            dimensions {
-                                datetime
-                            }
-                            sum {
-                                requests
-                                cachedRequests
-                                bytes
-                                cachedBytes
-                                threats
-                            }
-                        }
-                    }
-                }
-            }
-        "#;
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            dataset = granularity.dataset(),
+            limit = limit.clamp(1, 10000),
+        );
 
         let variables = serde_json::json!({
             "zoneTag": zone_id,
-            "dateSince": date_since,
-            "dateUntil": date_until,
-            "datetimeSince": datetime_since,
-            "datetimeUntil": datetime_until
+            "since": since_value,
+            "until": until_value
         });
 
-        let resp = self.graphql_query(query, variables).await?;
+        let resp = self.graphql_query(&query, variables).await?;
 
         // 解析响应
         let zones = resp
@@ -115,148 +137,153 @@ impl CfClient {
             anyhow::bail!("未找到域名分析数据");
         }
 
-        let zone = &zones[0];
+        let groups = zones[0]
+            .get(granularity.dataset())
+            .and_then(|g| g.as_array())
+            .map(|g| g.as_slice())
+            .unwrap_or_default();
 
-        // 解析汇总数据
-        let totals = self.parse_totals(zone);
+        let series: Vec<AnalyticsTimeseries> = groups
+            .iter()
+            .filter_map(|group| Self::parse_group(group, dimension, granularity))
+            .collect();
 
-        // 解析时间序列数据
-        let timeseries = self.parse_timeseries(zone);
-
-        Ok(AnalyticsDashboard { totals, timeseries })
+        Ok(AnalyticsDashboard {
+            totals: Self::sum_totals(&series),
+            timeseries: if series.is_empty() { None } else { Some(series) },
+        })
     }
 
-    /// 解析汇总数据
-    fn parse_totals(&self, zone: &serde_json::Value) -> Option<AnalyticsTotals> {
-        let groups = zone.get("httpRequests1dGroups")?.as_array()?;
-        if groups.is_empty() {
-            return None;
-        }
-
-        let group = &groups[0];
+    /// 解析单个时间分组
+    fn parse_group(
+        group: &serde_json::Value,
+        dimension: &str,
+        granularity: AnalyticsGranularity,
+    ) -> Option<AnalyticsTimeseries> {
         let sum = group.get("sum")?;
-        let uniq = group.get("uniq");
-
-        let requests = Some(AnalyticsRequests {
-            all: sum.get("requests").and_then(|v| v.as_u64()),
-            cached: sum.get("cachedRequests").and_then(|v| v.as_u64()),
-            uncached: {
-                let all = sum.get("requests").and_then(|v| v.as_u64()).unwrap_or(0);
-                let cached = sum.get("cachedRequests").and_then(|v| v.as_u64()).unwrap_or(0);
-                Some(all.saturating_sub(cached))
-            },
-            ssl: Some(AnalyticsSslRequests {
-                encrypted: sum.get("encryptedRequests").and_then(|v| v.as_u64()),
-                unencrypted: {
-                    let all = sum.get("requests").and_then(|v| v.as_u64()).unwrap_or(0);
-                    let encrypted = sum.get("encryptedRequests").and_then(|v| v.as_u64()).unwrap_or(0);
-                    Some(all.saturating_sub(encrypted))
-                },
-            }),
-            http_status: None,
-            content_type: None,
-            country: None,
+        let field = |name: &str| sum.get(name).and_then(|v| v.as_u64());
+        let since = group
+            .get("dimensions")
+            .and_then(|d| d.get(dimension))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let until = since.as_deref().and_then(|s| {
+            let now = Utc::now();
+            let start = parse_time(s, now).ok()?;
+            let end = match granularity {
+                AnalyticsGranularity::Hour => start + Duration::hours(1),
+                AnalyticsGranularity::Day => start + Duration::days(1),
+            };
+            Some(end.format("%Y-%m-%dT%H:%M:%SZ").to_string())
         });
 
-        let bandwidth = Some(AnalyticsBandwidth {
-            all: sum.get("bytes").and_then(|v| v.as_u64()),
-            cached: sum.get("cachedBytes").and_then(|v| v.as_u64()),
-            uncached: {
-                let all = sum.get("bytes").and_then(|v| v.as_u64()).unwrap_or(0);
-                let cached = sum.get("cachedBytes").and_then(|v| v.as_u64()).unwrap_or(0);
-                Some(all.saturating_sub(cached))
-            },
-            ssl: Some(AnalyticsSslBandwidth {
-                encrypted: sum.get("encryptedBytes").and_then(|v| v.as_u64()),
-                unencrypted: {
-                    let all = sum.get("bytes").and_then(|v| v.as_u64()).unwrap_or(0);
-                    let encrypted = sum.get("encryptedBytes").and_then(|v| v.as_u64()).unwrap_or(0);
-                    Some(all.saturating_sub(encrypted))
-                },
+        let requests = field("requests").unwrap_or(0);
+        let cached = field("cachedRequests").unwrap_or(0);
+        let encrypted = field("encryptedRequests").unwrap_or(0);
+        let bytes = field("bytes").unwrap_or(0);
+        let cached_bytes = field("cachedBytes").unwrap_or(0);
+        let encrypted_bytes = field("encryptedBytes").unwrap_or(0);
+
+        Some(AnalyticsTimeseries {
+            since,
+            until,
+            requests: Some(AnalyticsRequests {
+                all: Some(requests),
+                cached: Some(cached),
+                uncached: Some(requests.saturating_sub(cached)),
+                ssl: Some(AnalyticsSslRequests {
+                    encrypted: Some(encrypted),
+                    unencrypted: Some(requests.saturating_sub(encrypted)),
+                }),
+                http_status: breakdown(sum, "responseStatusMap", "edgeResponseStatus", "requests"),
+                content_type: breakdown(sum, "contentTypeMap", "edgeResponseContentTypeName", "requests"),
+                country: breakdown(sum, "countryMap", "clientCountryName", "requests"),
             }),
-            content_type: None,
-            country: None,
-        });
-
-        let threats = Some(AnalyticsThreats {
-            all: sum.get("threats").and_then(|v| v.as_u64()),
-            country: None,
-            threat_type: None,
-        });
+            bandwidth: Some(AnalyticsBandwidth {
+                all: Some(bytes),
+                cached: Some(cached_bytes),
+                uncached: Some(bytes.saturating_sub(cached_bytes)),
+                ssl: Some(AnalyticsSslBandwidth {
+                    encrypted: Some(encrypted_bytes),
+                    unencrypted: Some(bytes.saturating_sub(encrypted_bytes)),
+                }),
+                content_type: breakdown(sum, "contentTypeMap", "edgeResponseContentTypeName", "bytes"),
+                country: breakdown(sum, "countryMap", "clientCountryName", "bytes"),
+            }),
+            threats: Some(AnalyticsThreats {
+                all: field("threats"),
+                country: breakdown(sum, "countryMap", "clientCountryName", "threats"),
+                threat_type: None,
+            }),
+            pageviews: Some(AnalyticsPageviews {
+                all: field("pageViews"),
+                search_engines: None,
+            }),
+            uniques: Some(AnalyticsUniques {
+                all: group
+                    .get("uniq")
+                    .and_then(|u| u.get("uniques"))
+                    .and_then(|v| v.as_u64()),
+            }),
+        })
+    }
 
-        let pageviews = Some(AnalyticsPageviews {
-            all: sum.get("pageViews").and_then(|v| v.as_u64()),
-            search_engines: None,
-        });
+    /// 累加时间序列得到汇总数据 (独立访客为各分组之和，跨分组会有重复计数)
+    fn sum_totals(series: &[AnalyticsTimeseries]) -> Option<AnalyticsTotals> {
+        if series.is_empty() {
+            return None;
+        }
 
-        let uniques = uniq.and_then(|u| {
-            Some(AnalyticsUniques {
-                all: u.get("uniques").and_then(|v| v.as_u64()),
-            })
-        });
+        let total = |f: &dyn Fn(&AnalyticsTimeseries) -> Option<u64>| -> u64 {
+            series.iter().filter_map(f).sum()
+        };
+        let merge = |f: &dyn Fn(&AnalyticsTimeseries) -> Option<&serde_json::Value>| {
+            merge_breakdowns(series.iter().map(f))
+        };
+        let requests = total(&|t| t.requests.as_ref()?.all);
+        let cached = total(&|t| t.requests.as_ref()?.cached);
+        let encrypted = total(&|t| t.requests.as_ref()?.ssl.as_ref()?.encrypted);
+        let bytes = total(&|t| t.bandwidth.as_ref()?.all);
+        let cached_bytes = total(&|t| t.bandwidth.as_ref()?.cached);
+        let encrypted_bytes = total(&|t| t.bandwidth.as_ref()?.ssl.as_ref()?.encrypted);
 
         Some(AnalyticsTotals {
-            requests,
-            bandwidth,
-            threats,
-            pageviews,
-            uniques,
-        })
-    }
-
-    /// 解析时间序列数据
-    fn parse_timeseries(&self, zone: &serde_json::Value) -> Option<Vec<AnalyticsTimeseries>> {
-        let groups = zone.get("httpRequests1hGroups")?.as_array()?;
-
-        let series: Vec<AnalyticsTimeseries> = groups.iter().filter_map(|group| {
-            let dims = group.get("dimensions")?;
-            let sum = group.get("sum")?;
-
-            let datetime = dims.get("datetime").and_then(|v| v.as_str()).map(|s| s.to_string());
-
-            Some(AnalyticsTimeseries {
-                since: datetime.clone(),
-                until: datetime,
-                requests: Some(AnalyticsRequests {
-                    all: sum.get("requests").and_then(|v| v.as_u64()),
-                    cached: sum.get("cachedRequests").and_then(|v| v.as_u64()),
-                    uncached: {
-                        let all = sum.get("requests").and_then(|v| v.as_u64()).unwrap_or(0);
-                        let cached = sum.get("cachedRequests").and_then(|v| v.as_u64()).unwrap_or(0);
-                        Some(all.saturating_sub(cached))
-                    },
-                    ssl: None,
-                    http_status: None,
-                    content_type: None,
-                    country: None,
-                }),
-                bandwidth: Some(AnalyticsBandwidth {
-                    all: sum.get("bytes").and_then(|v| v.as_u64()),
-                    cached: sum.get("cachedBytes").and_then(|v| v.as_u64()),
-                    uncached: {
-                        let all = sum.get("bytes").and_then(|v| v.as_u64()).unwrap_or(0);
-                        let cached = sum.get("cachedBytes").and_then(|v| v.as_u64()).unwrap_or(0);
-                        Some(all.saturating_sub(cached))
-                    },
-                    ssl: None,
-                    content_type: None,
-                    country: None,
+            requests: Some(AnalyticsRequests {
+                all: Some(requests),
+                cached: Some(cached),
+                uncached: Some(requests.saturating_sub(cached)),
+                ssl: Some(AnalyticsSslRequests {
+                    encrypted: Some(encrypted),
+                    unencrypted: Some(requests.saturating_sub(encrypted)),
                 }),
-                threats: Some(AnalyticsThreats {
-                    all: sum.get("threats").and_then(|v| v.as_u64()),
-                    country: None,
-                    threat_type: None,
+                http_status: merge(&|t| t.requests.as_ref()?.http_status.as_ref()),
+                content_type: merge(&|t| t.requests.as_ref()?.content_type.as_ref()),
+                country: merge(&|t| t.requests.as_ref()?.country.as_ref()),
+            }),
+            bandwidth: Some(AnalyticsBandwidth {
+                all: Some(bytes),
+                cached: Some(cached_bytes),
+                uncached: Some(bytes.saturating_sub(cached_bytes)),
+                ssl: Some(AnalyticsSslBandwidth {
+                    encrypted: Some(encrypted_bytes),
+                    unencrypted: Some(bytes.saturating_sub(encrypted_bytes)),
                 }),
-                pageviews: None,
-                uniques: None,
-            })
-        }).collect();
-
-        if series.is_empty() {
-            None
-        } else {
-            Some(series)
-        }
+                content_type: merge(&|t| t.bandwidth.as_ref()?.content_type.as_ref()),
+                country: merge(&|t| t.bandwidth.as_ref()?.country.as_ref()),
+            }),
+            threats: Some(AnalyticsThreats {
+                all: Some(total(&|t| t.threats.as_ref()?.all)),
+                country: merge(&|t| t.threats.as_ref()?.country.as_ref()),
+                threat_type: None,
+            }),
+            pageviews: Some(AnalyticsPageviews {
+                all: Some(total(&|t| t.pageviews.as_ref()?.all)),
+                search_engines: None,
+            }),
+            uniques: Some(AnalyticsUniques {
+                all: Some(total(&|t| t.uniques.as_ref()?.all)),
+            }),
+        })
     }
 
     /// 获取最近 24 小时的分析数据
@@ -265,27 +292,197 @@ impl CfClient {
         self.get_analytics(zone_id, &params).await
     }
 
-    /// 获取 DNS 分析数据 (GraphQL)
+    /// 获取最近几分钟的实时流量 (按分钟聚合)
+    pub async fn get_live_analytics(&self, zone_id: &str, window_minutes: i64) -> Result<LiveAnalytics> {
+        let now = Utc::now();
+        let since = now - Duration::minutes(window_minutes);
+        let since_str = since.format("%Y-%m-%dT%H:%M:00Z").to_string();
+        let until_str = now.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+        let query = r#"
+            query GetLiveAnalytics($zoneTag: String!, $since: Time!, $until: Time!) {
+                viewer {
+                    zones(filter: { zoneTag: $zoneTag }) {
+                        httpRequests1mGroups(
+                            limit: 1000
+                            filter: { datetime_geq: $since, datetime_lt: $until }
+                            orderBy: [datetimeMinute_ASC]
+                        ) {
+                            dimensions {
+                                datetimeMinute
+                            }
+                            sum {
+                                requests
+                                cachedRequests
+                                bytes
+                                threats
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "zoneTag": zone_id,
+            "since": since_str,
+            "until": until_str
+        });
+
+        let resp = self.graphql_query(query, variables).await?;
+
+        let groups = resp
+            .get("data")
+            .and_then(|d| d.get("viewer"))
+            .and_then(|v| v.get("zones"))
+            .and_then(|z| z.as_array())
+            .and_then(|z| z.first())
+            .and_then(|z| z.get("httpRequests1mGroups"))
+            .and_then(|g| g.as_array())
+            .context("无法解析 GraphQL 响应")?;
+
+        let mut live = LiveAnalytics {
+            since: since_str,
+            until: until_str,
+            window_secs: (now - since).num_seconds().max(0) as u64,
+            ..Default::default()
+        };
+        for group in groups {
+            let Some(sum) = group.get("sum") else { continue };
+            let field = |name: &str| sum.get(name).and_then(|v| v.as_u64()).unwrap_or(0);
+            live.requests += field("requests");
+            live.cached_requests += field("cachedRequests");
+            live.bytes += field("bytes");
+            live.threats += field("threats");
+            live.per_minute.push(field("requests"));
+        }
+
+        Ok(live)
+    }
+
+    /// 获取 DNS 查询统计 (GraphQL)，按查询名称、记录类型、响应码分别取前 `top` 项
     pub async fn get_dns_analytics(
         &self,
         zone_id: &str,
-        params: &AnalyticsParams,
-    ) -> Result<serde_json::Value> {
-        let (since, until) = params.get_time_range();
+        since: chrono::DateTime<Utc>,
+        until: chrono::DateTime<Utc>,
+        top: usize,
+    ) -> Result<DnsAnalytics> {
+        let query = format!(
+            r#"
+            query GetDnsAnalytics($zoneTag: String!, $since: Time!, $until: Time!) {{
+                viewer {{
+                    zones(filter: {{ zoneTag: $zoneTag }}) {{
+                        total: dnsAnalyticsAdaptiveGroups(
+                            limit: 1
+                            filter: {{ datetime_geq: $since, datetime_leq: $until }}
+                        ) {{
+                            count
+                        }}
+                        byName: dnsAnalyticsAdaptiveGroups(
+                            limit: {top}
+                            filter: {{ datetime_geq: $since, datetime_leq: $until }}
+                            orderBy: [count_DESC]
+                        ) {{
+                            count
+                            dimensions {{
+                                value: queryName
+                            }}
+                        }}
+                        byType: dnsAnalyticsAdaptiveGroups(
+                            limit: {top}
+                            filter: {{ datetime_geq: $since, datetime_leq: $until }}
+                            orderBy: [count_DESC]
+                        ) {{
+                            count
+                            dimensions {{
+                                value: queryType
+                            }}
+                        }}
+                        byResponseCode: dnsAnalyticsAdaptiveGroups(
+                            limit: {top}
+                            filter: {{ datetime_geq: $since, datetime_leq: $until }}
+                            orderBy: [count_DESC]
+                        ) {{
+                            count
+                            dimensions {{
+                                value: responseCode
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            top = top.clamp(1, 1000),
+        );
+
+        let since_str = since.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let until_str = until.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let variables = serde_json::json!({
+            "zoneTag": zone_id,
+            "since": since_str,
+            "until": until_str
+        });
 
+        let resp = self.graphql_query(&query, variables).await?;
+        let zone = resp
+            .get("data")
+            .and_then(|d| d.get("viewer"))
+            .and_then(|v| v.get("zones"))
+            .and_then(|z| z.as_array())
+            .and_then(|z| z.first())
+            .context("无法解析 DNS 分析数据")?;
+
+        let groups = |alias: &str| -> Vec<(String, u64)> {
+            zone.get(alias)
+                .and_then(|g| g.as_array())
+                .map(|groups| {
+                    groups
+                        .iter()
+                        .map(|g| {
+                            let value = g
+                                .get("dimensions")
+                                .and_then(|d| d.get("value"))
+                                .and_then(|v| v.as_str())
+                                .filter(|v| !v.is_empty())
+                                .unwrap_or("-")
+                                .to_string();
+                            (value, g.get("count").and_then(|c| c.as_u64()).unwrap_or(0))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        Ok(DnsAnalytics {
+            since: since_str,
+            until: until_str,
+            total: groups("total").first().map(|(_, n)| *n).unwrap_or(0),
+            by_name: groups("byName"),
+            by_type: groups("byType"),
+            by_response_code: groups("byResponseCode"),
+        })
+    }
+
+    /// 获取 Bot 评分分布 (GraphQL，需要 Bot Management 或 Super Bot Fight Mode)
+    pub async fn get_bot_score_analytics(
+        &self,
+        zone_id: &str,
+        since: chrono::DateTime<Utc>,
+        until: chrono::DateTime<Utc>,
+    ) -> Result<BotScoreAnalytics> {
         let query = r#"
-            query GetDnsAnalytics($zoneTag: String!, $since: Time!, $until: Time!) {
+            query GetBotScores($zoneTag: String!, $since: Time!, $until: Time!) {
                 viewer {
                     zones(filter: { zoneTag: $zoneTag }) {
-                        dnsAnalyticsAdaptiveGroups(
-                            limit: 100
+                        httpRequestsAdaptiveGroups(
+                            limit: 1000
                             filter: { datetime_geq: $since, datetime_leq: $until }
                         ) {
                             count
                             dimensions {
-                                queryName
-                                queryType
-                                responseCode
+                                botScore
+                                botScoreSrcName
                             }
                         }
                     }
@@ -293,16 +490,58 @@ impl CfClient {
             }
         "#;
 
+        let since_str = since.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let until_str = until.format("%Y-%m-%dT%H:%M:%SZ").to_string();
         let variables = serde_json::json!({
             "zoneTag": zone_id,
-            "since": since,
-            "until": until
+            "since": since_str,
+            "until": until_str
         });
 
         let resp = self.graphql_query(query, variables).await?;
 
-        resp.get("data")
-            .cloned()
-            .context("获取 DNS 分析数据失败")
+        let groups = resp
+            .get("data")
+            .and_then(|d| d.get("viewer"))
+            .and_then(|v| v.get("zones"))
+            .and_then(|z| z.as_array())
+            .and_then(|z| z.first())
+            .and_then(|z| z.get("httpRequestsAdaptiveGroups"))
+            .and_then(|g| g.as_array())
+            .context("无法解析 Bot 评分数据")?;
+
+        let mut analytics = BotScoreAnalytics {
+            since: since_str,
+            until: until_str,
+            ..Default::default()
+        };
+        for group in groups {
+            let count = group.get("count").and_then(|c| c.as_u64()).unwrap_or(0);
+            let dims = group.get("dimensions");
+            analytics.add(
+                dims.and_then(|d| d.get("botScore")).and_then(|s| s.as_u64()),
+                dims.and_then(|d| d.get("botScoreSrcName")).and_then(|s| s.as_str()),
+                count,
+            );
+        }
+        analytics.by_source.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        Ok(analytics)
+    }
+}
+
+/// 将 GraphQL 的分布列表 (如 `responseStatusMap`) 转为 `{键: 数量}`，键为空的项记为 "unknown"
+fn breakdown(sum: &serde_json::Value, map: &str, key: &str, metric: &str) -> Option<serde_json::Value> {
+    let items = sum.get(map)?.as_array()?;
+    let mut result = serde_json::Map::new();
+    for item in items {
+        let name = match item.get(key) {
+            Some(serde_json::Value::String(s)) if !s.is_empty() => s.clone(),
+            Some(serde_json::Value::Number(n)) => n.to_string(),
+            _ => "unknown".to_string(),
+        };
+        let count = item.get(metric).and_then(|v| v.as_u64()).unwrap_or(0);
+        let total = result.get(&name).and_then(|v| v.as_u64()).unwrap_or(0) + count;
+        result.insert(name, total.into());
     }
+    Some(serde_json::Value::Object(result))
 }
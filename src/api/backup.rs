@@ -0,0 +1,640 @@
+use anyhow::Result;
+use tracing::debug;
+
+use crate::api::client::CfClient;
+use crate::models::backup::*;
+use crate::models::dns::{DnsRecord, DnsRecordRequest};
+use crate::models::firewall::{
+    CreateFirewallFilter, CreateFirewallRuleRequest, CreateIpAccessRuleRequest, FirewallRule, IpAccessRule,
+};
+use crate::models::page_rules::{CreatePageRuleRequest, PageRule};
+use crate::models::zone::ZoneSetting;
+
+impl CfClient {
+    // ==================== 配置备份 / 还原 ====================
+
+    /// 生成域名配置快照
+    ///
+    /// 域名信息、设置和 DNS 记录获取失败会直接报错；页面规则和防火墙规则
+    /// 在部分套餐/令牌权限下不可用，获取失败时对应字段为 None。
+    pub async fn snapshot_zone(&self, zone_id: &str) -> Result<ZoneBackup> {
+        let zone = self.get_zone(zone_id).await?;
+        let (ssl, settings): (Vec<ZoneSetting>, Vec<ZoneSetting>) = self
+            .get_zone_settings(zone_id)
+            .await?
+            .into_iter()
+            .partition(|s| SSL_SETTING_IDS.contains(&s.id.as_str()));
+        let dns_records = self.list_all_dns_records(zone_id).await?;
+
+        let page_rules = self
+            .list_page_rules(zone_id)
+            .await
+            .map_err(|e| debug!("跳过页面规则: {:#}", e))
+            .ok();
+        let firewall_rules = self
+            .list_firewall_rules(zone_id)
+            .await
+            .map_err(|e| debug!("跳过防火墙规则: {:#}", e))
+            .ok();
+
+        Ok(ZoneBackup {
+            version: BACKUP_FORMAT_VERSION,
+            cfai_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            zone: BackupZone {
+                id: zone.id,
+                name: zone.name,
+                plan: zone.plan.and_then(|p| p.name),
+            },
+            ssl: Some(ssl),
+            settings: Some(settings),
+            dns_records: Some(dns_records),
+            page_rules,
+            firewall_rules,
+        })
+    }
+
+    /// 执行单条还原变更
+    pub async fn apply_change(&self, zone_id: &str, op: &ChangeOp) -> Result<()> {
+        match op {
+            ChangeOp::CreateDns(req) => {
+                self.create_dns_record(zone_id, req).await?;
+            }
+            ChangeOp::UpdateDns(id, req) => {
+                self.update_dns_record(zone_id, id, req).await?;
+            }
+            ChangeOp::DeleteDns(id) => self.delete_dns_record(zone_id, id).await?,
+            ChangeOp::UpdateSetting(id, value) => {
+                self.update_zone_setting(zone_id, id, value.clone()).await?;
+            }
+            ChangeOp::CreatePageRule(req) => {
+                self.create_page_rule(zone_id, req).await?;
+            }
+            ChangeOp::UpdatePageRule(id, req) => {
+                self.update_page_rule(zone_id, id, req).await?;
+            }
+            ChangeOp::DeletePageRule(id) => self.delete_page_rule(zone_id, id).await?,
+            ChangeOp::CreateFirewallRule(req) => {
+                self.create_firewall_rule(zone_id, req).await?;
+            }
+            ChangeOp::DeleteFirewallRule(id) => self.delete_firewall_rule(zone_id, id).await?,
+            ChangeOp::CreateIpAccessRule(req) => {
+                self.create_ip_access_rule(zone_id, req).await?;
+            }
+            ChangeOp::UpdateIpAccessRule(id, req) => {
+                self.update_ip_access_rule(zone_id, id, &req.mode, req.notes.as_deref())
+                    .await?;
+            }
+            ChangeOp::DeleteIpAccessRule(id) => self.delete_ip_access_rule(zone_id, id).await?,
+        }
+        Ok(())
+    }
+}
+
+/// 计算将 `target` 同步为 `source` 所需的变更
+///
+/// 两者域名不同时，记录名和页面规则中的域名会自动替换为目标域名。
+/// `prune` 为 true 时会删除目标中多出的 DNS 记录、页面规则和防火墙规则。
+/// 任一侧缺失的分组 (None) 不参与比较。
+pub fn plan_restore(source: &ZoneBackup, target: &ZoneBackup, prune: bool) -> Vec<PlannedChange> {
+    let from = source.zone.name.as_str();
+    let to = target.zone.name.as_str();
+    let mut changes = Vec::new();
+
+    if let (Some(src), Some(cur)) = (&source.ssl, &target.ssl) {
+        plan_settings("SSL", src, cur, &mut changes);
+    }
+    if let (Some(src), Some(cur)) = (&source.settings, &target.settings) {
+        plan_settings("设置", src, cur, &mut changes);
+    }
+    if let (Some(src), Some(cur)) = (&source.dns_records, &target.dns_records) {
+        plan_dns(src, cur, from, to, prune, &mut changes);
+    }
+    if let (Some(src), Some(cur)) = (&source.page_rules, &target.page_rules) {
+        plan_page_rules(src, cur, from, to, prune, &mut changes);
+    }
+    if let (Some(src), Some(cur)) = (&source.firewall_rules, &target.firewall_rules) {
+        plan_firewall_rules(src, cur, prune, &mut changes);
+    }
+
+    changes
+}
+
+/// 将属于 `from` 域名的主机名替换为 `to` 下的对应名称
+pub fn rename_host(name: &str, from: &str, to: &str) -> String {
+    if from == to {
+        return name.to_string();
+    }
+    if name.eq_ignore_ascii_case(from) {
+        return to.to_string();
+    }
+    match name.len().checked_sub(from.len() + 1) {
+        Some(split)
+            if name.as_bytes()[split] == b'.' && name[split + 1..].eq_ignore_ascii_case(from) =>
+        {
+            format!("{}.{}", &name[..split], to)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// 替换文本 (记录值、TXT / SPF 等) 中作为完整主机名出现的域名，`notexample.com` 之类的不受影响
+fn rename_in_text(text: &str, from: &str, to: &str) -> String {
+    if from.is_empty() || from.eq_ignore_ascii_case(to) {
+        return text.to_string();
+    }
+    let is_host_char = |c: &u8| c.is_ascii_alphanumeric() || *c == b'-';
+    let bytes = text.as_bytes();
+    let mut renamed = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in text.to_ascii_lowercase().match_indices(&from.to_ascii_lowercase()) {
+        let end = start + from.len();
+        let bounded_before = start == 0 || !is_host_char(&bytes[start - 1]);
+        // 末尾的 "." 可能是 FQDN 的根，后面仍有标签时是其他域名 (如 example.com.au)
+        let bounded_after = match bytes.get(end) {
+            Some(b'.') => bytes.get(end + 1).is_none_or(|c| !is_host_char(c)),
+            next => next.is_none_or(|c| !is_host_char(c)),
+        };
+        if start >= last && bounded_before && bounded_after {
+            renamed.push_str(&text[last..start]);
+            renamed.push_str(to);
+            last = end;
+        }
+    }
+    renamed.push_str(&text[last..]);
+    renamed
+}
+
+/// 替换 URL (或页面规则匹配模式) 主机部分的域名，协议、端口、路径与通配符前缀保持不变
+fn rename_url_host(url: &str, from: &str, to: &str) -> String {
+    let (scheme, rest) = match url.find("://") {
+        Some(i) => url.split_at(i + 3),
+        None => ("", url),
+    };
+    let (host, tail) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+    let (host, port) = host.split_at(host.rfind(':').unwrap_or(host.len()));
+    let (wildcard, name) = match host.strip_prefix('*') {
+        Some(name) => ("*", name),
+        None => ("", host),
+    };
+    format!("{}{}{}{}{}", scheme, wildcard, rename_host(name, from, to), port, tail)
+}
+
+fn setting_value_str(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn plan_settings(
+    section: &'static str,
+    source: &[ZoneSetting],
+    target: &[ZoneSetting],
+    changes: &mut Vec<PlannedChange>,
+) {
+    for src in source {
+        let Some(cur) = target.iter().find(|s| s.id == src.id) else {
+            continue;
+        };
+        if cur.editable == Some(false) || cur.value == src.value {
+            continue;
+        }
+        changes.push(PlannedChange {
+            kind: ChangeKind::Update,
+            section,
+            target: src.id.clone(),
+            before: Some(setting_value_str(&cur.value)),
+            after: Some(setting_value_str(&src.value)),
+            op: ChangeOp::UpdateSetting(src.id.clone(), src.value.clone()),
+        });
+    }
+}
+
+/// 由 Cloudflare 托管、无法通过 API 还原的记录 (根域 NS / SOA)
+fn is_cloudflare_owned(record: &DnsRecord, zone_name: &str) -> bool {
+    record.record_type == "SOA"
+        || (record.record_type == "NS" && record.name.eq_ignore_ascii_case(zone_name))
+}
+
+fn dns_summary(record: &DnsRecord) -> String {
+    let mut s = record.content.clone();
+    if let Some(p) = record.priority {
+        s.push_str(&format!(" prio={}", p));
+    }
+    s.push_str(&format!(" ttl={}", record.ttl.unwrap_or(1)));
+    if record.proxied.unwrap_or(false) {
+        s.push_str(" proxied");
+    }
+    s
+}
+
+fn plan_dns(
+    source: &[DnsRecord],
+    target: &[DnsRecord],
+    from: &str,
+    to: &str,
+    prune: bool,
+    changes: &mut Vec<PlannedChange>,
+) {
+    let mut matched = vec![false; target.len()];
+
+    for src in source.iter().filter(|r| !is_cloudflare_owned(r, from)) {
+        let name = rename_host(&src.name, from, to);
+        // 记录值中出现的域名 (CNAME / MX 目标、SPF include、子域 NS 等) 同样替换，否则匹配不到现有记录
+        let content = rename_in_text(&src.content, from, to);
+        // SRV 的目标主机同样需要替换为目标域名下的名称
+        let mut data = src.data.clone();
+        if let Some(target) = data.as_mut().and_then(|d| d.get_mut("target")) {
+            if let Some(host) = target.as_str() {
+                *target = serde_json::json!(rename_host(host, from, to));
+            }
+        }
+        let request = DnsRecordRequest {
+            record_type: src.record_type.clone(),
+            name: name.clone(),
+            content: if data.is_some() { String::new() } else { content.clone() },
+            ttl: src.ttl,
+            proxied: src.proxied,
+            priority: src.priority,
+            comment: src.comment.clone(),
+            tags: src.tags.clone().filter(|t| !t.is_empty()),
+            data,
+        };
+        let label = format!("{} {}", src.record_type, name);
+        let mut after = src.clone();
+        after.content = content.clone();
+
+        let existing = target.iter().enumerate().find(|(i, r)| {
+            !matched[*i]
+                && r.record_type == src.record_type
+                && r.name.eq_ignore_ascii_case(&name)
+                && r.content == content
+        });
+        match existing {
+            Some((i, cur)) => {
+                matched[i] = true;
+                let differs = cur.ttl.unwrap_or(1) != src.ttl.unwrap_or(1)
+                    || cur.proxied.unwrap_or(false) != src.proxied.unwrap_or(false)
+                    || cur.priority != src.priority
+                    || cur.comment.as_deref().unwrap_or("") != src.comment.as_deref().unwrap_or("");
+                if let (true, Some(id)) = (differs, &cur.id) {
+                    changes.push(PlannedChange {
+                        kind: ChangeKind::Update,
+                        section: "DNS",
+                        target: label,
+                        before: Some(dns_summary(cur)),
+                        after: Some(dns_summary(&after)),
+                        op: ChangeOp::UpdateDns(id.clone(), request),
+                    });
+                }
+            }
+            None => changes.push(PlannedChange {
+                kind: ChangeKind::Create,
+                section: "DNS",
+                target: label,
+                before: None,
+                after: Some(dns_summary(&after)),
+                op: ChangeOp::CreateDns(request),
+            }),
+        }
+    }
+
+    if !prune {
+        return;
+    }
+    for (i, cur) in target.iter().enumerate() {
+        if matched[i] || is_cloudflare_owned(cur, to) {
+            continue;
+        }
+        if let Some(id) = &cur.id {
+            changes.push(PlannedChange {
+                kind: ChangeKind::Delete,
+                section: "DNS",
+                target: format!("{} {}", cur.record_type, cur.name),
+                before: Some(dns_summary(cur)),
+                after: None,
+                op: ChangeOp::DeleteDns(id.clone()),
+            });
+        }
+    }
+}
+
+fn page_rule_pattern(rule: &PageRule) -> String {
+    rule.targets
+        .as_ref()
+        .and_then(|t| t.first())
+        .and_then(|t| t.constraint.as_ref())
+        .and_then(|c| c.value.clone())
+        .unwrap_or_default()
+}
+
+fn page_rule_actions(rule: &PageRule) -> String {
+    serde_json::to_string(&rule.actions).unwrap_or_default()
+}
+
+fn plan_page_rules(
+    source: &[PageRule],
+    target: &[PageRule],
+    from: &str,
+    to: &str,
+    prune: bool,
+    changes: &mut Vec<PlannedChange>,
+) {
+    let mut matched = vec![false; target.len()];
+
+    for src in source {
+        // 匹配模式和跳转地址中的域名一并替换
+        let mut rule = src.clone();
+        if from != to {
+            for constraint in rule.targets.iter_mut().flatten().filter_map(|t| t.constraint.as_mut()) {
+                if let Some(value) = &constraint.value {
+                    constraint.value = Some(rename_url_host(value, from, to));
+                }
+            }
+            for action in rule.actions.iter_mut().flatten() {
+                if action.id.as_deref() != Some("forwarding_url") {
+                    continue;
+                }
+                if let Some(url) = action.value.as_mut().and_then(|v| v.get_mut("url")) {
+                    if let Some(value) = url.as_str() {
+                        *url = serde_json::json!(rename_url_host(value, from, to));
+                    }
+                }
+            }
+        }
+        let pattern = page_rule_pattern(&rule);
+        let request = CreatePageRuleRequest {
+            targets: rule.targets.clone().unwrap_or_default(),
+            actions: rule.actions.clone().unwrap_or_default(),
+            priority: rule.priority,
+            status: rule.status.clone(),
+        };
+
+        let existing = target
+            .iter()
+            .enumerate()
+            .find(|(i, r)| !matched[*i] && page_rule_pattern(r) == pattern);
+        match existing {
+            Some((i, cur)) => {
+                matched[i] = true;
+                let differs = page_rule_actions(cur) != page_rule_actions(&rule)
+                    || cur.status != rule.status;
+                if let (true, Some(id)) = (differs, &cur.id) {
+                    changes.push(PlannedChange {
+                        kind: ChangeKind::Update,
+                        section: "页面规则",
+                        target: pattern,
+                        before: Some(page_rule_actions(cur)),
+                        after: Some(page_rule_actions(&rule)),
+                        op: ChangeOp::UpdatePageRule(id.clone(), request),
+                    });
+                }
+            }
+            None => changes.push(PlannedChange {
+                kind: ChangeKind::Create,
+                section: "页面规则",
+                target: pattern,
+                before: None,
+                after: Some(page_rule_actions(&rule)),
+                op: ChangeOp::CreatePageRule(request),
+            }),
+        }
+    }
+
+    if !prune {
+        return;
+    }
+    for (i, cur) in target.iter().enumerate() {
+        if let (false, Some(id)) = (matched[i], &cur.id) {
+            changes.push(PlannedChange {
+                kind: ChangeKind::Delete,
+                section: "页面规则",
+                target: page_rule_pattern(cur),
+                before: Some(page_rule_actions(cur)),
+                after: None,
+                op: ChangeOp::DeletePageRule(id.clone()),
+            });
+        }
+    }
+}
+
+fn firewall_expression(rule: &FirewallRule) -> String {
+    rule.filter
+        .as_ref()
+        .and_then(|f| f.expression.clone())
+        .unwrap_or_default()
+}
+
+fn plan_firewall_rules(
+    source: &[FirewallRule],
+    target: &[FirewallRule],
+    prune: bool,
+    changes: &mut Vec<PlannedChange>,
+) {
+    let same = |a: &FirewallRule, b: &FirewallRule| {
+        a.action == b.action && firewall_expression(a) == firewall_expression(b)
+    };
+
+    for src in source {
+        if target.iter().any(|cur| same(src, cur)) {
+            continue;
+        }
+        let expression = firewall_expression(src);
+        let action = src.action.clone().unwrap_or_else(|| "block".to_string());
+        changes.push(PlannedChange {
+            kind: ChangeKind::Create,
+            section: "防火墙规则",
+            target: src.description.clone().unwrap_or_else(|| expression.clone()),
+            before: None,
+            after: Some(format!("{} → {}", expression, action)),
+            op: ChangeOp::CreateFirewallRule(CreateFirewallRuleRequest {
+                filter: CreateFirewallFilter {
+                    expression,
+                    description: src.filter.as_ref().and_then(|f| f.description.clone()),
+                },
+                action,
+                description: src.description.clone(),
+                paused: src.paused,
+                priority: src.priority,
+            }),
+        });
+    }
+
+    if !prune {
+        return;
+    }
+    for cur in target {
+        if source.iter().any(|src| same(src, cur)) {
+            continue;
+        }
+        if let Some(id) = &cur.id {
+            let expression = firewall_expression(cur);
+            changes.push(PlannedChange {
+                kind: ChangeKind::Delete,
+                section: "防火墙规则",
+                target: cur.description.clone().unwrap_or_else(|| expression.clone()),
+                before: Some(format!(
+                    "{} → {}",
+                    expression,
+                    cur.action.as_deref().unwrap_or("-")
+                )),
+                after: None,
+                op: ChangeOp::DeleteFirewallRule(id.clone()),
+            });
+        }
+    }
+}
+
+/// 拆分出会修改或删除非 cfai 管理的 DNS 记录的变更，返回 (可执行的变更, 被保护的变更)
+pub fn split_unmanaged_dns_changes(
+    changes: Vec<PlannedChange>,
+    current: &[DnsRecord],
+) -> (Vec<PlannedChange>, Vec<PlannedChange>) {
+    changes.into_iter().partition(|change| {
+        let id = match &change.op {
+            ChangeOp::UpdateDns(id, _) | ChangeOp::DeleteDns(id) => id,
+            _ => return true,
+        };
+        current
+            .iter()
+            .find(|r| r.id.as_ref() == Some(id))
+            .is_none_or(|r| r.is_managed())
+    })
+}
+
+fn ip_rule_summary(mode: &str, notes: Option<&str>) -> String {
+    match notes.filter(|n| !n.is_empty()) {
+        Some(notes) => format!("{} ({})", mode, notes),
+        None => mode.to_string(),
+    }
+}
+
+/// 计算将域名的 IP 访问规则同步为 `desired` 所需的变更
+///
+/// 按 target + value 匹配规则，动作或备注不同时修改；只处理域名级规则，
+/// `prune` 为 true 时删除 `desired` 中不存在的规则。
+pub fn plan_ip_rules(
+    desired: &[CreateIpAccessRuleRequest],
+    current: &[IpAccessRule],
+    prune: bool,
+) -> Vec<PlannedChange> {
+    let current: Vec<&IpAccessRule> = current
+        .iter()
+        .filter(|r| r.scope.as_ref().and_then(|s| s.scope_type.as_deref()).unwrap_or("zone") == "zone")
+        .collect();
+    let key = |rule: &IpAccessRule| {
+        rule.configuration
+            .as_ref()
+            .map(|c| (c.target.clone().unwrap_or_default(), c.value.clone().unwrap_or_default()))
+            .unwrap_or_default()
+    };
+    let mut changes = Vec::new();
+    let mut matched = vec![false; current.len()];
+
+    for req in desired {
+        let label = format!("{} {}", req.configuration.target, req.configuration.value);
+        let existing = current.iter().enumerate().find(|(_, r)| {
+            let (target, value) = key(r);
+            target == req.configuration.target && value.eq_ignore_ascii_case(&req.configuration.value)
+        });
+        let after = ip_rule_summary(&req.mode, req.notes.as_deref());
+        match existing {
+            Some((i, cur)) => {
+                matched[i] = true;
+                let notes = cur.notes.as_deref().unwrap_or("");
+                let differs = cur.mode.as_deref() != Some(req.mode.as_str())
+                    || notes != req.notes.as_deref().unwrap_or("");
+                if let (true, Some(id)) = (differs, &cur.id) {
+                    changes.push(PlannedChange {
+                        kind: ChangeKind::Update,
+                        section: "IP 访问规则",
+                        target: label,
+                        before: Some(ip_rule_summary(cur.mode.as_deref().unwrap_or("-"), Some(notes))),
+                        after: Some(after),
+                        op: ChangeOp::UpdateIpAccessRule(id.clone(), req.clone()),
+                    });
+                }
+            }
+            None => changes.push(PlannedChange {
+                kind: ChangeKind::Create,
+                section: "IP 访问规则",
+                target: label,
+                before: None,
+                after: Some(after),
+                op: ChangeOp::CreateIpAccessRule(req.clone()),
+            }),
+        }
+    }
+
+    if !prune {
+        return changes;
+    }
+    for (i, cur) in current.iter().enumerate() {
+        if let (false, Some(id)) = (matched[i], &cur.id) {
+            let (target, value) = key(cur);
+            changes.push(PlannedChange {
+                kind: ChangeKind::Delete,
+                section: "IP 访问规则",
+                target: format!("{} {}", target, value),
+                before: Some(ip_rule_summary(cur.mode.as_deref().unwrap_or("-"), cur.notes.as_deref())),
+                after: None,
+                op: ChangeOp::DeleteIpAccessRule(id.clone()),
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_host() {
+        assert_eq!(rename_host("example.com", "example.com", "example.org"), "example.org");
+        assert_eq!(rename_host("www.Example.com", "example.com", "example.org"), "www.example.org");
+        assert_eq!(rename_host("notexample.com", "example.com", "example.org"), "notexample.com");
+        assert_eq!(rename_host("mail.other.net", "example.com", "example.org"), "mail.other.net");
+
+        let rename = |url| rename_url_host(url, "example.com", "example.org");
+        assert_eq!(rename("*example.com/blog/*"), "*example.org/blog/*");
+        assert_eq!(rename("https://www.example.com:8443/a?b=example.com"), "https://www.example.org:8443/a?b=example.com");
+        assert_eq!(rename("*.example.com/*"), "*.example.org/*");
+        assert_eq!(rename("notexample.com/*"), "notexample.com/*");
+
+        let rename = |text| rename_in_text(text, "example.com", "example.org");
+        assert_eq!(rename("\"v=spf1 include:_spf.Example.com ~all\""), "\"v=spf1 include:_spf.example.org ~all\"");
+        assert_eq!(rename("ns1.example.com."), "ns1.example.org.");
+        assert_eq!(rename("notexample.com example.com.au"), "notexample.com example.com.au");
+    }
+
+    #[test]
+    fn test_split_unmanaged_dns_changes() {
+        use crate::models::dns::managed_comment;
+
+        assert_eq!(managed_comment(None), "[cfai]");
+        assert_eq!(managed_comment(Some("SPF")), "SPF [cfai]");
+        assert_eq!(managed_comment(Some("SPF [cfai]")), "SPF [cfai]");
+
+        let current: Vec<DnsRecord> = serde_json::from_value(serde_json::json!([
+            { "id": "manual", "type": "A", "name": "a.example.com", "content": "192.0.2.1", "comment": "office" },
+            { "id": "owned", "type": "A", "name": "b.example.com", "content": "192.0.2.2", "comment": "[cfai]" },
+            { "id": "tagged", "type": "A", "name": "c.example.com", "content": "192.0.2.3", "tags": ["cfai:managed"] },
+        ]))
+        .unwrap();
+        let delete = |id: &str| PlannedChange {
+            kind: ChangeKind::Delete,
+            section: "DNS",
+            target: id.to_string(),
+            before: None,
+            after: None,
+            op: ChangeOp::DeleteDns(id.to_string()),
+        };
+        let changes = vec![delete("manual"), delete("owned"), delete("tagged"), delete("gone")];
+        let (allowed, protected) = split_unmanaged_dns_changes(changes, &current);
+        let targets = |c: &[PlannedChange]| c.iter().map(|c| c.target.clone()).collect::<Vec<_>>();
+        assert_eq!(targets(&allowed), ["owned", "tagged", "gone"]);
+        assert_eq!(targets(&protected), ["manual"]);
+    }
+}
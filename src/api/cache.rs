@@ -79,6 +79,56 @@ impl CfClient {
         resp.result.context("按主机名清除缓存失败")
     }
 
+    /// 按 URL 前缀清除缓存 (前缀不含协议，如 www.example.com/images)
+    pub async fn purge_cache_by_prefixes(
+        &self,
+        zone_id: &str,
+        prefixes: Vec<String>,
+    ) -> Result<serde_json::Value> {
+        let body = PurgeCacheRequest {
+            purge_everything: None,
+            files: None,
+            tags: None,
+            hosts: None,
+            prefixes: Some(prefixes),
+        };
+        let resp: CfResponse<serde_json::Value> = self
+            .post(&format!("/zones/{}/purge_cache", zone_id), &body)
+            .await?;
+        resp.result.context("按前缀清除缓存失败")
+    }
+
+    /// 分批清除缓存，每批最多 [`PURGE_BATCH_SIZE`] 条
+    ///
+    /// 每完成一批调用 `on_batch(当前批次, 总批次, 本批数量)`；某批失败时立即返回，
+    /// 错误信息中注明已完成的数量。
+    pub async fn purge_cache_batched(
+        &self,
+        zone_id: &str,
+        target: PurgeTarget,
+        items: &[String],
+        mut on_batch: impl FnMut(usize, usize, usize),
+    ) -> Result<()> {
+        let total = items.len().div_ceil(PURGE_BATCH_SIZE);
+        for (i, batch) in items.chunks(PURGE_BATCH_SIZE).enumerate() {
+            let result = match target {
+                PurgeTarget::Urls => self.purge_cache_by_urls(zone_id, batch.to_vec()).await,
+                PurgeTarget::Prefixes => self.purge_cache_by_prefixes(zone_id, batch.to_vec()).await,
+            };
+            result.with_context(|| {
+                format!(
+                    "第 {}/{} 批清除失败 (已清除 {} 个{})",
+                    i + 1,
+                    total,
+                    i * PURGE_BATCH_SIZE,
+                    target.label()
+                )
+            })?;
+            on_batch(i + 1, total, batch.len());
+        }
+        Ok(())
+    }
+
     /// 获取缓存级别
     pub async fn get_cache_level(&self, zone_id: &str) -> Result<String> {
         let resp: CfResponse<serde_json::Value> = self
@@ -97,12 +147,8 @@ impl CfClient {
         zone_id: &str,
         level: &str,
     ) -> Result<serde_json::Value> {
-        let body = serde_json::json!({ "value": level });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/cache_level", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "cache_level", serde_json::json!(level))
             .await?;
         resp.result.context("设置缓存级别失败")
     }
@@ -128,12 +174,8 @@ impl CfClient {
         zone_id: &str,
         ttl: u32,
     ) -> Result<serde_json::Value> {
-        let body = serde_json::json!({ "value": ttl });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/browser_cache_ttl", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "browser_cache_ttl", serde_json::json!(ttl))
             .await?;
         resp.result.context("设置浏览器缓存 TTL 失败")
     }
@@ -145,13 +187,56 @@ impl CfClient {
         enable: bool,
     ) -> Result<serde_json::Value> {
         let value = if enable { "on" } else { "off" };
-        let body = serde_json::json!({ "value": value });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/development_mode", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "development_mode", serde_json::json!(value))
             .await?;
         resp.result.context("设置开发模式失败")
     }
+
+    /// 获取 Always Online 状态 (源站不可用时提供 Internet Archive 的存档页面)
+    pub async fn get_always_online(&self, zone_id: &str) -> Result<String> {
+        let setting = self.get_zone_setting(zone_id, "always_online").await?;
+        setting
+            .value
+            .as_str()
+            .map(|s| s.to_string())
+            .context("解析 Always Online 状态失败")
+    }
+
+    /// 开启/关闭 Always Online
+    pub async fn set_always_online(
+        &self,
+        zone_id: &str,
+        enable: bool,
+    ) -> Result<serde_json::Value> {
+        let value = if enable { "on" } else { "off" };
+        let resp: CfResponse<serde_json::Value> = self
+            .patch_zone_setting(zone_id, "always_online", serde_json::json!(value))
+            .await?;
+        resp.result.context("设置 Always Online 失败")
+    }
+
+    /// 获取 Crawler Hints 状态 (内容变化时通知搜索引擎重新抓取)
+    pub async fn get_crawler_hints(&self, zone_id: &str) -> Result<bool> {
+        let resp: CfResponse<serde_json::Value> = self
+            .get(&format!("/zones/{}/flags/products/cache/changes", zone_id))
+            .await?;
+        let result = resp.result.context("获取 Crawler Hints 状态失败")?;
+        result["crawlhints_enabled"]
+            .as_bool()
+            .context("解析 Crawler Hints 状态失败")
+    }
+
+    /// 开启/关闭 Crawler Hints
+    pub async fn set_crawler_hints(
+        &self,
+        zone_id: &str,
+        enable: bool,
+    ) -> Result<serde_json::Value> {
+        let body = serde_json::json!({ "feature": "crawlhints_enabled", "value": enable });
+        let resp: CfResponse<serde_json::Value> = self
+            .put(&format!("/zones/{}/flags/products/cache/changes", zone_id), &body)
+            .await?;
+        resp.result.context("设置 Crawler Hints 失败")
+    }
 }
@@ -1,17 +1,93 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use anyhow::{Context, Result};
-use reqwest::{header, Client, Response};
+use reqwest::{header, Client, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use tracing::debug;
 
+use super::error::ApiError;
+use super::response_cache::ResponseCache;
+use super::timing::{EndpointTiming, RequestTimings};
+use crate::config::audit;
+use crate::config::settings::NetworkConfig;
+use crate::models::audit::AuditChange;
 use crate::models::common::CfResponse;
 
-const CF_API_BASE: &str = "https://api.cloudflare.com/client/v4";
+/// 默认的 Cloudflare API 地址
+pub const CF_API_BASE: &str = "https://api.cloudflare.com/client/v4";
 
 /// Cloudflare API 客户端
 #[derive(Clone)]
 pub struct CfClient {
     client: Client,
     base_url: String,
+    retry: RetryPolicy,
+    /// 是否将 DNS 记录与 Zone 设置的变更写入审计日志 (供 undo / rollback 使用)
+    audit: bool,
+    /// 域名 → Zone ID 本地缓存使用的键 (Profile)，None 表示不使用缓存
+    zone_cache: Option<String>,
+    /// 本次从本地缓存读取的 Zone ID (请求失败时据此清除过期的缓存)
+    cached_zone_ids: Arc<Mutex<Vec<String>>>,
+    /// 已发出的变更请求 (方法与路径)，用于命令完成通知
+    writes: Arc<Mutex<Vec<String>>>,
+    /// 请求耗时记录，None 表示不记录
+    timings: Option<Arc<RequestTimings>>,
+    /// GET 响应缓存，None 表示不使用
+    response_cache: Option<Arc<ResponseCache>>,
+}
+
+/// 请求重试策略 (429 限流、5xx 及网络错误时按指数退避重试)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 最大重试次数 (0 表示不重试)
+    pub max_retries: u32,
+    /// 首次重试前的等待时间，之后每次翻倍
+    pub base_delay: Duration,
+    /// 单次等待的上限 (同样限制 `Retry-After`)
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 第 `attempt` 次重试 (从 1 开始) 前的等待时间，优先使用服务端给出的 `Retry-After`
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        retry_after.unwrap_or(backoff).min(self.max_delay)
+    }
+}
+
+/// 重发不会产生额外副作用的请求方法
+fn is_idempotent(method: &str) -> bool {
+    matches!(method, "GET" | "HEAD" | "DELETE")
+}
+
+/// 是否值得重试的状态码: 5xx 时服务端可能已执行了变更，POST / PUT / PATCH 只在 429 时重试
+fn is_retryable(method: &str, status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || (is_idempotent(method) && status.is_server_error())
+}
+
+/// 解析 `Retry-After` 响应头 (仅支持秒数形式)
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
 }
 
 /// 认证方式
@@ -23,8 +99,8 @@ pub enum AuthMethod {
 }
 
 impl CfClient {
-    /// 创建新的 Cloudflare API 客户端
-    pub fn new(auth: AuthMethod) -> Result<Self> {
+    /// 创建新的 Cloudflare API 客户端 (按网络配置设置代理、根证书与超时)
+    pub fn new(auth: AuthMethod, network: &NetworkConfig) -> Result<Self> {
         let mut headers = header::HeaderMap::new();
         headers.insert(
             header::CONTENT_TYPE,
@@ -51,29 +127,226 @@ impl CfClient {
             }
         }
 
-        let client = Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(30))
+        let client = network
+            .apply(Client::builder().default_headers(headers), Duration::from_secs(30))?
             .build()
             .context("创建 HTTP 客户端失败")?;
 
         Ok(Self {
             client,
             base_url: CF_API_BASE.to_string(),
-        })
+            retry: RetryPolicy::default(),
+            audit: false,
+            zone_cache: None,
+            cached_zone_ids: Arc::default(),
+            writes: Arc::default(),
+            timings: None,
+            response_cache: None,
+        }
+        .with_base_url(network.api_base_url.as_deref().unwrap_or(CF_API_BASE)))
+    }
+
+    /// 设置 API 地址 (如测试用的 Mock 服务或 API 网关)，GraphQL 端点随之变化
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// 当前使用的 API 地址
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// GraphQL Analytics 端点
+    pub fn graphql_url(&self) -> String {
+        self.url("/graphql")
+    }
+
+    /// 设置请求重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 开启 / 关闭变更审计日志
+    pub fn with_audit(mut self, audit: bool) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// 设置域名 → Zone ID 本地缓存的键 (传入 None 关闭缓存)
+    pub fn with_zone_cache(mut self, key: Option<String>) -> Self {
+        self.zone_cache = key;
+        self
+    }
+
+    /// 域名 → Zone ID 本地缓存的键
+    pub fn zone_cache_key(&self) -> Option<&str> {
+        self.zone_cache.as_deref()
+    }
+
+    /// 记录一个从本地缓存读取的 Zone ID
+    pub(crate) fn note_cached_zone_id(&self, zone_id: &str) {
+        if let Ok(mut ids) = self.cached_zone_ids.lock() {
+            ids.push(zone_id.to_string());
+        }
+    }
+
+    /// 取出本次从本地缓存读取的 Zone ID
+    pub(crate) fn take_cached_zone_ids(&self) -> Vec<String> {
+        self.cached_zone_ids.lock().map(|mut ids| std::mem::take(&mut *ids)).unwrap_or_default()
+    }
+
+    /// 是否开启了变更审计
+    pub fn audit_enabled(&self) -> bool {
+        self.audit
+    }
+
+    /// 记录一次变更 (未开启审计时忽略；写入失败不影响已完成的操作)
+    pub fn record_audit(&self, zone_id: &str, change: AuditChange) {
+        if !self.audit {
+            return;
+        }
+        if let Err(e) = audit::record(zone_id, change) {
+            debug!("写入审计日志失败: {:#}", e);
+        }
+    }
+
+    /// 清空变更请求记录 (克隆出的客户端默认与原客户端共享记录)
+    pub fn with_fresh_writes(mut self) -> Self {
+        self.writes = Arc::default();
+        self
+    }
+
+    /// 本客户端已发出的变更请求 (如 `POST /zones/<id>/dns_records`)
+    pub fn writes(&self) -> Vec<String> {
+        self.writes.lock().map(|w| w.clone()).unwrap_or_default()
+    }
+
+    /// 开启 / 关闭请求耗时记录
+    pub fn with_timings(mut self, enabled: bool) -> Self {
+        self.timings = enabled.then(Arc::default);
+        self
+    }
+
+    /// 设置 GET 响应缓存 (传入 None 关闭缓存)
+    pub fn with_response_cache(mut self, cache: Option<ResponseCache>) -> Self {
+        self.response_cache = cache.map(Arc::new);
+        self
+    }
+
+    /// 按接口汇总的请求耗时 (未开启记录时为空)
+    pub fn request_timings(&self) -> Vec<EndpointTiming> {
+        self.timings.as_ref().map(|t| t.summary()).unwrap_or_default()
+    }
+
+    /// 记录一次变更请求
+    fn note_write(&self, method: &str, path: &str) {
+        if let Ok(mut writes) = self.writes.lock() {
+            writes.push(format!("{} {}", method, path));
+        }
+    }
+
+    /// 发送请求，遇到 429 / 5xx / 网络错误时按重试策略重发 (变更请求只在 429 与连接失败时重发)
+    async fn send(&self, method: &str, url: &str, build: impl Fn() -> RequestBuilder) -> Result<Response> {
+        let started = Instant::now();
+        let mut attempt = 0;
+        loop {
+            let result = build().send().await;
+            let wait = match &result {
+                Ok(resp) if is_retryable(method, resp.status()) => {
+                    Some((format!("HTTP {}", resp.status().as_u16()), retry_after(resp)))
+                }
+                Ok(_) => None,
+                // 超时的请求可能已送达服务端，只有未建立连接时才重发变更请求
+                Err(e) if e.is_connect() || (e.is_timeout() && is_idempotent(method)) => {
+                    Some((e.to_string(), None))
+                }
+                Err(_) => None,
+            };
+
+            match wait {
+                Some((reason, retry_after)) if attempt < self.retry.max_retries => {
+                    attempt += 1;
+                    let delay = self.retry.delay(attempt, retry_after);
+                    debug!(
+                        "{} {} 失败 ({})，{} ms 后第 {}/{} 次重试",
+                        method,
+                        url,
+                        reason,
+                        delay.as_millis(),
+                        attempt,
+                        self.retry.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                _ => {
+                    if attempt > 0 {
+                        debug!("{} {} 共重试 {} 次", method, url, attempt);
+                    }
+                    let path = url.strip_prefix(self.base_url.as_str()).unwrap_or(url);
+                    if let Some(timings) = &self.timings {
+                        timings.record(method, path, started.elapsed());
+                    }
+                    // 变更请求 (无论成功与否) 后缓存的数据可能已过时
+                    if let Some(cache) = self.response_cache.as_ref().filter(|_| method != "GET") {
+                        cache.invalidate(path);
+                    }
+                    return result.with_context(|| format!("{} 请求失败", method));
+                }
+            }
+        }
     }
 
     /// 构建完整 URL
-    fn url(&self, path: &str) -> String {
+    pub(crate) fn url(&self, path: &str) -> String {
         format!("{}{}", self.base_url, path)
     }
 
-    /// GET 请求
+    /// GET 请求 (开启响应缓存时优先读取缓存)
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<CfResponse<T>> {
+        if let Some(cached) = self.cached_response(path) {
+            return cached;
+        }
         let url = self.url(path);
         debug!("GET {}", url);
-        let resp = self.client.get(&url).send().await.context("GET 请求失败")?;
-        self.handle_response(resp).await
+        let resp = self.send("GET", &url, || self.client.get(&url)).await?;
+        self.handle_cacheable_response(resp, path).await
+    }
+
+    /// GET 请求 (资源不存在时返回 None 而不是报错)
+    pub async fn get_optional<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<CfResponse<T>>> {
+        let url = self.url(path);
+        debug!("GET {}", url);
+        let resp = self.send("GET", &url, || self.client.get(&url)).await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        self.handle_response(resp).await.map(Some)
+    }
+
+    /// GET 请求 (返回原始 JSON，用于不带 Cloudflare 响应包装的接口；资源不存在时返回 None)
+    pub async fn get_raw_optional(&self, path: &str) -> Result<Option<serde_json::Value>> {
+        let url = self.url(path);
+        debug!("GET {}", url);
+        let resp = self.send("GET", &url, || self.client.get(&url)).await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let body = resp.text().await.context("读取响应体失败")?;
+        if !status.is_success() {
+            return Err(ApiError {
+                status: status.as_u16(),
+                codes: Vec::new(),
+                message: format!("HTTP 错误 {}: {}", status.as_u16(), body),
+            }
+            .into());
+        }
+        serde_json::from_str(&body).map(Some).context("解析 JSON 响应失败")
     }
 
     /// GET 请求 (带查询参数)
@@ -83,15 +356,41 @@ impl CfClient {
         params: &P,
     ) -> Result<CfResponse<T>> {
         let url = self.url(path);
+        let request = match self.client.get(&url).query(params).build() {
+            Ok(built) => match built.url().query() {
+                Some(query) => format!("{}?{}", path, query),
+                None => path.to_string(),
+            },
+            Err(_) => path.to_string(),
+        };
+        if let Some(cached) = self.cached_response(&request) {
+            return cached;
+        }
         debug!("GET {} (with params)", url);
         let resp = self
-            .client
-            .get(&url)
-            .query(params)
-            .send()
-            .await
-            .context("GET 请求失败")?;
-        self.handle_response(resp).await
+            .send("GET", &url, || self.client.get(&url).query(params))
+            .await?;
+        self.handle_cacheable_response(resp, &request).await
+    }
+
+    /// 读取缓存的响应 (`request` 为路径与查询参数)
+    fn cached_response<T: DeserializeOwned>(&self, request: &str) -> Option<Result<CfResponse<T>>> {
+        let body = self.response_cache.as_ref()?.get(request)?;
+        debug!("GET {} (缓存)", request);
+        Some(Self::parse_response(StatusCode::OK, &body))
+    }
+
+    /// 处理 GET 响应，成功时写入响应缓存
+    async fn handle_cacheable_response<T: DeserializeOwned>(
+        &self,
+        resp: Response,
+        request: &str,
+    ) -> Result<CfResponse<T>> {
+        let (body, parsed) = self.read_response(resp).await?;
+        if let Some(cache) = self.response_cache.as_ref().filter(|_| parsed.success) {
+            cache.put(request, &body);
+        }
+        Ok(parsed)
     }
 
     /// POST 请求
@@ -102,13 +401,10 @@ impl CfClient {
     ) -> Result<CfResponse<T>> {
         let url = self.url(path);
         debug!("POST {}", url);
+        self.note_write("POST", path);
         let resp = self
-            .client
-            .post(&url)
-            .json(body)
-            .send()
-            .await
-            .context("POST 请求失败")?;
+            .send("POST", &url, || self.client.post(&url).json(body))
+            .await?;
         self.handle_response(resp).await
     }
 
@@ -120,13 +416,10 @@ impl CfClient {
     ) -> Result<CfResponse<T>> {
         let url = self.url(path);
         debug!("PUT {}", url);
+        self.note_write("PUT", path);
         let resp = self
-            .client
-            .put(&url)
-            .json(body)
-            .send()
-            .await
-            .context("PUT 请求失败")?;
+            .send("PUT", &url, || self.client.put(&url).json(body))
+            .await?;
         self.handle_response(resp).await
     }
 
@@ -138,13 +431,10 @@ impl CfClient {
     ) -> Result<CfResponse<T>> {
         let url = self.url(path);
         debug!("PATCH {}", url);
+        self.note_write("PATCH", path);
         let resp = self
-            .client
-            .patch(&url)
-            .json(body)
-            .send()
-            .await
-            .context("PATCH 请求失败")?;
+            .send("PATCH", &url, || self.client.patch(&url).json(body))
+            .await?;
         self.handle_response(resp).await
     }
 
@@ -152,12 +442,8 @@ impl CfClient {
     pub async fn delete<T: DeserializeOwned>(&self, path: &str) -> Result<CfResponse<T>> {
         let url = self.url(path);
         debug!("DELETE {}", url);
-        let resp = self
-            .client
-            .delete(&url)
-            .send()
-            .await
-            .context("DELETE 请求失败")?;
+        self.note_write("DELETE", path);
+        let resp = self.send("DELETE", &url, || self.client.delete(&url)).await?;
         self.handle_response(resp).await
     }
 
@@ -169,13 +455,10 @@ impl CfClient {
     ) -> Result<CfResponse<T>> {
         let url = self.url(path);
         debug!("DELETE {} (with body)", url);
+        self.note_write("DELETE", path);
         let resp = self
-            .client
-            .delete(&url)
-            .json(body)
-            .send()
-            .await
-            .context("DELETE 请求失败")?;
+            .send("DELETE", &url, || self.client.delete(&url).json(body))
+            .await?;
         self.handle_response(resp).await
     }
 
@@ -186,19 +469,18 @@ impl CfClient {
         body: &B,
     ) -> Result<serde_json::Value> {
         debug!("POST {}", url);
-        let resp = self
-            .client
-            .post(url)
-            .json(body)
-            .send()
-            .await
-            .context("POST 请求失败")?;
+        let resp = self.send("POST", url, || self.client.post(url).json(body)).await?;
 
         let status = resp.status();
         let body_text = resp.text().await.context("读取响应体失败")?;
 
         if !status.is_success() {
-            anyhow::bail!("HTTP 错误 {}: {}", status.as_u16(), body_text);
+            return Err(ApiError {
+                status: status.as_u16(),
+                codes: Vec::new(),
+                message: format!("HTTP 错误 {}: {}", status.as_u16(), body_text),
+            }
+            .into());
         }
 
         serde_json::from_str(&body_text).context("解析 JSON 响应失败")
@@ -209,29 +491,52 @@ impl CfClient {
         &self,
         resp: Response,
     ) -> Result<CfResponse<T>> {
+        self.read_response(resp).await.map(|(_, parsed)| parsed)
+    }
+
+    /// 读取并解析响应，同时返回原始响应体
+    async fn read_response<T: DeserializeOwned>(&self, resp: Response) -> Result<(String, CfResponse<T>)> {
+        super::deprecation::record_from_headers(resp.url(), resp.headers());
         let status = resp.status();
         let body = resp.text().await.context("读取响应体失败")?;
 
         debug!("Response status: {}, body length: {}", status, body.len());
 
+        let parsed = Self::parse_response(status, &body)?;
+        Ok((body, parsed))
+    }
+
+    /// 解析响应体，非 2xx 状态码转为 ApiError
+    fn parse_response<T: DeserializeOwned>(status: StatusCode, body: &str) -> Result<CfResponse<T>> {
         if !status.is_success() {
             // 尝试解析错误响应
-            if let Ok(cf_resp) = serde_json::from_str::<CfResponse<serde_json::Value>>(&body) {
-                let errors: Vec<String> = cf_resp.errors.iter().map(|e| e.to_string()).collect();
-                anyhow::bail!(
-                    "Cloudflare API 错误 (HTTP {}): {}",
-                    status.as_u16(),
-                    if errors.is_empty() {
-                        body.clone()
-                    } else {
-                        errors.join("; ")
+            let error = match serde_json::from_str::<CfResponse<serde_json::Value>>(body) {
+                Ok(cf_resp) => {
+                    let errors: Vec<String> = cf_resp.errors.iter().map(|e| e.to_string()).collect();
+                    ApiError {
+                        status: status.as_u16(),
+                        codes: cf_resp.errors.iter().map(|e| e.code).collect(),
+                        message: format!(
+                            "Cloudflare API 错误 (HTTP {}): {}",
+                            status.as_u16(),
+                            if errors.is_empty() {
+                                body.to_string()
+                            } else {
+                                errors.join("; ")
+                            }
+                        ),
                     }
-                );
-            }
-            anyhow::bail!("HTTP 错误 {}: {}", status.as_u16(), body);
+                }
+                Err(_) => ApiError {
+                    status: status.as_u16(),
+                    codes: Vec::new(),
+                    message: format!("HTTP 错误 {}: {}", status.as_u16(), body),
+                },
+            };
+            return Err(error.into());
         }
 
-        serde_json::from_str::<CfResponse<T>>(&body)
+        serde_json::from_str::<CfResponse<T>>(body)
             .with_context(|| format!("解析 Cloudflare API 响应失败: {}", &body[..body.len().min(500)]))
     }
 
@@ -257,10 +562,47 @@ mod tests {
         let client = CfClient {
             client: Client::new(),
             base_url: CF_API_BASE.to_string(),
+            retry: RetryPolicy::default(),
+            audit: false,
+            zone_cache: None,
+            cached_zone_ids: Arc::default(),
+            response_cache: None,
+            writes: Arc::default(),
+            timings: None,
         };
         assert_eq!(
             client.url("/zones"),
             "https://api.cloudflare.com/client/v4/zones"
         );
     }
+
+    #[test]
+    fn test_custom_base_url() {
+        let client = CfClient::new(AuthMethod::ApiToken("test".into()), &NetworkConfig::default())
+            .unwrap()
+            .with_base_url("http://127.0.0.1:8080/client/v4/");
+        assert_eq!(client.url("/zones"), "http://127.0.0.1:8080/client/v4/zones");
+        assert_eq!(client.graphql_url(), "http://127.0.0.1:8080/client/v4/graphql");
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(1, None), Duration::from_millis(500));
+        assert_eq!(policy.delay(3, None), Duration::from_secs(2));
+        assert_eq!(policy.delay(20, None), Duration::from_secs(30));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(5))), Duration::from_secs(5));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(120))), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable("GET", StatusCode::BAD_GATEWAY));
+        assert!(is_retryable("DELETE", StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable("POST", StatusCode::TOO_MANY_REQUESTS));
+        // 5xx 时变更可能已生效，非幂等请求不重发
+        assert!(!is_retryable("POST", StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable("PATCH", StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_retryable("GET", StatusCode::NOT_FOUND));
+    }
 }
@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::common::CfResponse;
+use crate::models::d1::*;
+
+impl CfClient {
+    // ==================== D1 数据库 ====================
+
+    /// 列出 D1 数据库 (自动翻页)
+    pub async fn list_d1_databases(&self, account_id: &str) -> Result<Vec<D1Database>> {
+        self.get_all_pages(&format!("/accounts/{}/d1/database", account_id))
+            .await
+            .context("获取 D1 数据库列表失败")
+    }
+
+    /// 创建 D1 数据库
+    pub async fn create_d1_database(
+        &self,
+        account_id: &str,
+        request: &CreateD1DatabaseRequest,
+    ) -> Result<D1Database> {
+        let resp: CfResponse<D1Database> = self
+            .post(&format!("/accounts/{}/d1/database", account_id), request)
+            .await?;
+        resp.result.context("创建 D1 数据库失败")
+    }
+
+    /// 删除 D1 数据库
+    pub async fn delete_d1_database(&self, account_id: &str, database_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/accounts/{}/d1/database/{}", account_id, database_id))
+            .await?;
+        Ok(())
+    }
+
+    /// 执行 SQL (可包含多条语句，每条语句返回一个结果)
+    pub async fn query_d1_database(
+        &self,
+        account_id: &str,
+        database_id: &str,
+        request: &D1QueryRequest,
+    ) -> Result<Vec<D1QueryResult>> {
+        let resp: CfResponse<Vec<D1QueryResult>> = self
+            .post(
+                &format!("/accounts/{}/d1/database/{}/raw", account_id, database_id),
+                request,
+            )
+            .await?;
+        resp.result.context("执行 D1 查询失败")
+    }
+}
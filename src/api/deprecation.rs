@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::config::settings::AppConfig;
+
+/// 超过该天数未再出现的弃用通知不再视为"最近"
+const RECENT_DAYS: i64 = 30;
+
+/// 已宣布下线、但 CFAI 仍在使用的 Cloudflare API
+pub struct KnownSunset {
+    /// 规范化后的接口路径
+    pub endpoint: &'static str,
+    pub feature: &'static str,
+    /// 下线日期 (None 表示已弃用但未公布日期)
+    pub sunset: Option<&'static str>,
+    pub replacement: &'static str,
+}
+
+pub const KNOWN_SUNSETS: &[KnownSunset] = &[
+    KnownSunset {
+        endpoint: "/zones/{id}/firewall/rules",
+        feature: "Firewall Rules (防火墙规则)",
+        sunset: Some("2025-06-15"),
+        replacement: "WAF 自定义规则 (Rulesets API, http_request_firewall_custom 阶段)",
+    },
+    KnownSunset {
+        endpoint: "/zones/{id}/rate_limits",
+        feature: "速率限制 (旧版)",
+        sunset: Some("2025-06-15"),
+        replacement: "速率限制规则 (Rulesets API, http_ratelimit 阶段)",
+    },
+    KnownSunset {
+        endpoint: "/zones/{id}/pagerules",
+        feature: "Page Rules (页面规则)",
+        sunset: None,
+        replacement: "Redirect / Cache / Configuration Rules",
+    },
+];
+
+/// 从 API 响应头中观察到的弃用通知
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeprecationNotice {
+    /// 规范化后的接口路径 (ID 替换为 {id})
+    pub endpoint: String,
+    /// `Deprecation` 响应头
+    pub deprecation: Option<String>,
+    /// `Sunset` 响应头
+    pub sunset: Option<String>,
+    /// `Link` 响应头中的说明文档
+    pub link: Option<String>,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+/// 持久化的弃用通知记录
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DeprecationLog {
+    pub notices: Vec<DeprecationNotice>,
+}
+
+impl DeprecationLog {
+    /// 记录文件路径
+    pub fn path() -> Result<PathBuf> {
+        Ok(AppConfig::config_dir()?.join("api_deprecations.json"))
+    }
+
+    /// 加载记录，文件不存在时返回空记录
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("读取弃用记录失败: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("解析弃用记录失败: {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("写入弃用记录失败: {}", path.display()))
+    }
+
+    /// 最近一段时间内出现过的通知
+    pub fn recent(&self) -> Vec<&DeprecationNotice> {
+        let cutoff = chrono::Local::now() - chrono::Duration::days(RECENT_DAYS);
+        self.notices
+            .iter()
+            .filter(|n| {
+                chrono::DateTime::parse_from_rfc3339(&n.last_seen)
+                    .map(|t| t >= cutoff)
+                    .unwrap_or(true)
+            })
+            .collect()
+    }
+}
+
+/// 检查响应头，发现 `Deprecation` / `Sunset` 时写入记录 (记录失败不影响请求)
+pub(crate) fn record_from_headers(url: &reqwest::Url, headers: &HeaderMap) {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    let deprecation = header("deprecation");
+    let sunset = header("sunset");
+    if deprecation.is_none() && sunset.is_none() {
+        return;
+    }
+    let link = header("link").filter(|l| l.contains("deprecation") || l.contains("sunset"));
+
+    let endpoint = normalize_endpoint(url.path());
+    tracing::warn!("Cloudflare API 弃用通知: {} (Sunset: {:?})", endpoint, sunset);
+
+    let mut log = DeprecationLog::load().unwrap_or_default();
+    let now = chrono::Local::now().to_rfc3339();
+    match log.notices.iter_mut().find(|n| n.endpoint == endpoint) {
+        Some(notice) => {
+            notice.deprecation = deprecation;
+            notice.sunset = sunset;
+            notice.link = link;
+            notice.last_seen = now;
+        }
+        None => log.notices.push(DeprecationNotice {
+            endpoint,
+            deprecation,
+            sunset,
+            link,
+            first_seen: now.clone(),
+            last_seen: now,
+        }),
+    }
+    let _ = log.save();
+}
+
+/// 去掉 API 前缀并将资源 ID 替换为 {id}，便于按接口聚合
+pub fn normalize_endpoint(path: &str) -> String {
+    let path = path.strip_prefix("/client/v4").unwrap_or(path);
+    path.split('/')
+        .map(|seg| {
+            if seg.len() == 32 && seg.chars().all(|c| c.is_ascii_hexdigit()) {
+                "{id}"
+            } else {
+                seg
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_endpoint() {
+        assert_eq!(
+            normalize_endpoint("/client/v4/zones/023e105f4ecef8ad9ca31a8372d0c353/rate_limits"),
+            "/zones/{id}/rate_limits"
+        );
+        assert_eq!(normalize_endpoint("/client/v4/user/tokens/verify"), "/user/tokens/verify");
+    }
+}
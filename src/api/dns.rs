@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 
 use crate::api::client::CfClient;
+use crate::models::audit::AuditChange;
+use crate::models::backup::{ChangeKind, ChangeOp, PlannedChange};
 use crate::models::common::CfResponse;
 use crate::models::dns::*;
+use crate::models::dns_template::TemplatePlan;
 
 impl CfClient {
     // ==================== DNS 记录管理 ====================
@@ -17,6 +20,31 @@ impl CfClient {
             .await
     }
 
+    /// 列出域名下全部 DNS 记录 (自动翻页)
+    pub async fn list_all_dns_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        let mut records = Vec::new();
+        let mut page = 1;
+        loop {
+            let params = DnsListParams {
+                page: Some(page),
+                per_page: Some(100),
+                ..Default::default()
+            };
+            let resp = self.list_dns_records(zone_id, &params).await?;
+            let total_pages = resp
+                .result_info
+                .as_ref()
+                .and_then(|i| i.total_pages)
+                .unwrap_or(1);
+            records.extend(resp.result.context("获取 DNS 记录失败")?);
+            if page >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+        Ok(records)
+    }
+
     /// 获取 DNS 记录详情
     pub async fn get_dns_record(&self, zone_id: &str, record_id: &str) -> Result<DnsRecord> {
         let resp: CfResponse<DnsRecord> = self
@@ -34,7 +62,9 @@ impl CfClient {
         let resp: CfResponse<DnsRecord> = self
             .post(&format!("/zones/{}/dns_records", zone_id), request)
             .await?;
-        resp.result.context("创建 DNS 记录失败")
+        let record = resp.result.context("创建 DNS 记录失败")?;
+        self.record_audit(zone_id, AuditChange::DnsCreate { record: record.clone() });
+        Ok(record)
     }
 
     /// 更新 DNS 记录 (全量)
@@ -44,13 +74,24 @@ impl CfClient {
         record_id: &str,
         request: &DnsRecordRequest,
     ) -> Result<DnsRecord> {
+        let before = self.audit_dns_before(zone_id, record_id).await;
         let resp: CfResponse<DnsRecord> = self
             .put(
                 &format!("/zones/{}/dns_records/{}", zone_id, record_id),
                 request,
             )
             .await?;
-        resp.result.context("更新 DNS 记录失败")
+        let record = resp.result.context("更新 DNS 记录失败")?;
+        if let Some(before) = before {
+            self.record_audit(
+                zone_id,
+                AuditChange::DnsUpdate {
+                    before: Box::new(before),
+                    after: Box::new(record.clone()),
+                },
+            );
+        }
+        Ok(record)
     }
 
     /// 部分更新 DNS 记录
@@ -60,29 +101,49 @@ impl CfClient {
         record_id: &str,
         patch: &serde_json::Value,
     ) -> Result<DnsRecord> {
+        let before = self.audit_dns_before(zone_id, record_id).await;
         let resp: CfResponse<DnsRecord> = self
             .patch(
                 &format!("/zones/{}/dns_records/{}", zone_id, record_id),
                 patch,
             )
             .await?;
-        resp.result.context("更新 DNS 记录失败")
+        let record = resp.result.context("更新 DNS 记录失败")?;
+        if let Some(before) = before {
+            self.record_audit(
+                zone_id,
+                AuditChange::DnsUpdate {
+                    before: Box::new(before),
+                    after: Box::new(record.clone()),
+                },
+            );
+        }
+        Ok(record)
     }
 
     /// 删除 DNS 记录
     pub async fn delete_dns_record(&self, zone_id: &str, record_id: &str) -> Result<()> {
+        let before = self.audit_dns_before(zone_id, record_id).await;
         let _resp: CfResponse<serde_json::Value> = self
             .delete(&format!("/zones/{}/dns_records/{}", zone_id, record_id))
             .await?;
+        if let Some(before) = before {
+            self.record_audit(zone_id, AuditChange::DnsDelete { before });
+        }
         Ok(())
     }
 
+    /// 开启审计时获取记录修改前的状态 (获取失败时不记录，也不影响本次操作)
+    async fn audit_dns_before(&self, zone_id: &str, record_id: &str) -> Option<DnsRecord> {
+        if !self.audit_enabled() {
+            return None;
+        }
+        self.get_dns_record(zone_id, record_id).await.ok()
+    }
+
     /// 导出 DNS 记录 (BIND 格式)
     pub async fn export_dns_records(&self, zone_id: &str) -> Result<String> {
-        let url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records/export",
-            zone_id
-        );
+        let url = format!("{}/zones/{}/dns_records/export", self.base_url(), zone_id);
         // 导出返回纯文本，需要特殊处理
         let resp: CfResponse<serde_json::Value> = self
             .get(&format!("/zones/{}/dns_records/export", zone_id))
@@ -106,6 +167,19 @@ impl CfClient {
         resp.result.context("查找 DNS 记录失败")
     }
 
+    /// 获取 DNSSEC 状态
+    pub async fn get_dnssec(&self, zone_id: &str) -> Result<Dnssec> {
+        let resp: CfResponse<Dnssec> = self.get(&format!("/zones/{}/dnssec", zone_id)).await?;
+        resp.result.context("获取 DNSSEC 状态失败")
+    }
+
+    /// 开启或关闭 DNSSEC
+    pub async fn set_dnssec(&self, zone_id: &str, enable: bool) -> Result<Dnssec> {
+        let body = serde_json::json!({ "status": if enable { "active" } else { "disabled" } });
+        let resp: CfResponse<Dnssec> = self.patch(&format!("/zones/{}/dnssec", zone_id), &body).await?;
+        resp.result.context("更新 DNSSEC 状态失败")
+    }
+
     /// 批量创建 DNS 记录
     pub async fn batch_create_dns_records(
         &self,
@@ -119,3 +193,100 @@ impl CfClient {
         Ok(results)
     }
 }
+
+/// 对比现有记录，计算应用模板所需的变更
+///
+/// 与模板记录完全相同的现有记录会跳过；同名的 CNAME、其他 MX、同类 SPF / DMARC / DKIM TXT
+/// 以及模板之外的 A/AAAA 视为冲突。`replace` 为 true 时先删除冲突记录再创建。
+pub fn plan_dns_template(
+    records: &[DnsRecordRequest],
+    current: &[DnsRecord],
+    replace: bool,
+) -> TemplatePlan {
+    let mut plan = TemplatePlan {
+        changes: Vec::new(),
+        existing: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
+    let same_name = |a: &str, b: &str| a.trim_end_matches('.').eq_ignore_ascii_case(b.trim_end_matches('.'));
+    let identical = |cur: &DnsRecord, req: &DnsRecordRequest| {
+        cur.record_type == req.record_type
+            && same_name(&cur.name, &req.name)
+            && same_name(cur.content.trim_matches('"'), &req.content)
+            && (req.record_type != "MX" || cur.priority == req.priority)
+    };
+
+    for cur in current {
+        if records.iter().any(|req| identical(cur, req)) {
+            continue;
+        }
+        let reason = records
+            .iter()
+            .filter(|req| same_name(&cur.name, &req.name))
+            .find_map(|req| template_conflict(cur, req));
+        if let Some(reason) = reason {
+            plan.conflicts.push((cur.clone(), reason));
+        }
+    }
+
+    if replace {
+        for (cur, reason) in &plan.conflicts {
+            if let Some(id) = &cur.id {
+                plan.changes.push(PlannedChange {
+                    kind: ChangeKind::Delete,
+                    section: "DNS",
+                    target: format!("{} {}", cur.record_type, cur.name),
+                    before: Some(format!("{} ({})", cur.content, reason)),
+                    after: None,
+                    op: ChangeOp::DeleteDns(id.clone()),
+                });
+            }
+        }
+    }
+
+    for req in records {
+        let label = format!("{} {}", req.record_type, req.name);
+        if current.iter().any(|cur| identical(cur, req)) {
+            plan.existing.push(label);
+            continue;
+        }
+        let mut after = req.content.clone();
+        if let Some(p) = req.priority {
+            after.push_str(&format!(" prio={}", p));
+        }
+        plan.changes.push(PlannedChange {
+            kind: ChangeKind::Create,
+            section: "DNS",
+            target: label,
+            before: None,
+            after: Some(after),
+            op: ChangeOp::CreateDns(req.clone()),
+        });
+    }
+
+    plan
+}
+
+/// 同名的现有记录与模板记录冲突时返回原因
+fn template_conflict(cur: &DnsRecord, req: &DnsRecordRequest) -> Option<String> {
+    let txt_kind = |content: &str| {
+        let content = content.trim_matches('"');
+        ["v=spf1", "v=DMARC1", "v=DKIM1"]
+            .into_iter()
+            .find(|prefix| content.starts_with(prefix))
+    };
+    let is_address = |t: &str| t == "A" || t == "AAAA";
+
+    match (cur.record_type.as_str(), req.record_type.as_str()) {
+        (_, "CNAME") => Some("CNAME 不能与同名的其他记录共存".into()),
+        ("CNAME", _) => Some("已存在同名 CNAME 记录".into()),
+        ("MX", "MX") => Some("已有其他邮件服务商的 MX 记录".into()),
+        ("TXT", "TXT") => match (txt_kind(&cur.content), txt_kind(&req.content)) {
+            (Some(a), Some(b)) if a == b => Some(format!("同名只能有一条 {} 记录", a)),
+            _ => None,
+        },
+        (a, b) if is_address(a) && is_address(b) => Some("已指向其他地址".into()),
+        _ => None,
+    }
+}
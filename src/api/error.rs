@@ -0,0 +1,34 @@
+/// Cloudflare API 返回的错误响应 (非 2xx)
+///
+/// 保留 HTTP 状态码与 Cloudflare 错误码，便于上层区分认证失败与其他 API 错误。
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct ApiError {
+    pub status: u16,
+    /// Cloudflare 错误码 (响应体无法解析时为空)
+    pub codes: Vec<i64>,
+    pub message: String,
+}
+
+/// 表示认证 / 授权失败的 Cloudflare 错误码
+const AUTH_ERROR_CODES: &[i64] = &[
+    6003,  // Invalid request headers
+    9103,  // Unknown X-Auth-Key or X-Auth-Email
+    9106,  // Missing X-Auth-Key / X-Auth-Email / Authorization
+    9109,  // Invalid access token
+    10000, // Authentication error
+];
+
+impl ApiError {
+    /// 是否为认证失败 (Token 无效、过期或权限不足)
+    pub fn is_auth(&self) -> bool {
+        self.status == 401
+            || self.status == 403
+            || self.codes.iter().any(|c| AUTH_ERROR_CODES.contains(c))
+    }
+}
+
+/// 参数或输入校验失败 (在发出请求之前即可判定)
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct ValidationError(pub String);
@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 
 use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::models::audit::AuditChange;
 use crate::models::common::CfResponse;
 use crate::models::firewall::*;
 
@@ -18,6 +20,28 @@ impl CfClient {
         resp.result.context("获取防火墙规则失败")
     }
 
+    /// 创建防火墙规则
+    pub async fn create_firewall_rule(
+        &self,
+        zone_id: &str,
+        request: &CreateFirewallRuleRequest,
+    ) -> Result<FirewallRule> {
+        let resp: CfResponse<Vec<FirewallRule>> = self
+            .post(&format!("/zones/{}/firewall/rules", zone_id), &[request])
+            .await?;
+        resp.result
+            .and_then(|rules| rules.into_iter().next())
+            .context("创建防火墙规则失败")
+    }
+
+    /// 删除防火墙规则
+    pub async fn delete_firewall_rule(&self, zone_id: &str, rule_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/firewall/rules/{}", zone_id, rule_id))
+            .await?;
+        Ok(())
+    }
+
     /// 获取安全级别
     pub async fn get_security_level(&self, zone_id: &str) -> Result<String> {
         let resp: CfResponse<serde_json::Value> = self
@@ -35,10 +59,15 @@ impl CfClient {
         &self,
         zone_id: &str,
     ) -> Result<Vec<IpAccessRule>> {
-        let resp: CfResponse<Vec<IpAccessRule>> = self
-            .get(&format!("/zones/{}/firewall/access_rules/rules", zone_id))
-            .await?;
-        resp.result.context("获取 IP 访问规则失败")
+        self.get_all_pages(&format!("/zones/{}/firewall/access_rules/rules", zone_id))
+            .await
+            .context("获取 IP 访问规则失败")
+    }
+
+    /// 获取 Cloudflare 边缘节点的 IP 段
+    pub async fn get_cloudflare_ips(&self) -> Result<CloudflareIps> {
+        let resp: CfResponse<CloudflareIps> = self.get("/ips").await?;
+        resp.result.context("获取 Cloudflare IP 段失败")
     }
 
     /// 创建 IP 访问规则 (封禁/白名单)
@@ -56,6 +85,24 @@ impl CfClient {
         resp.result.context("创建 IP 访问规则失败")
     }
 
+    /// 修改 IP 访问规则的动作与备注
+    pub async fn update_ip_access_rule(
+        &self,
+        zone_id: &str,
+        rule_id: &str,
+        mode: &str,
+        notes: Option<&str>,
+    ) -> Result<IpAccessRule> {
+        let body = serde_json::json!({ "mode": mode, "notes": notes.unwrap_or_default() });
+        let resp: CfResponse<IpAccessRule> = self
+            .patch(
+                &format!("/zones/{}/firewall/access_rules/rules/{}", zone_id, rule_id),
+                &body,
+            )
+            .await?;
+        resp.result.context("修改 IP 访问规则失败")
+    }
+
     /// 删除 IP 访问规则
     pub async fn delete_ip_access_rule(&self, zone_id: &str, rule_id: &str) -> Result<()> {
         let _resp: CfResponse<serde_json::Value> = self
@@ -67,34 +114,20 @@ impl CfClient {
         Ok(())
     }
 
-    /// 封禁 IP
-    pub async fn block_ip(&self, zone_id: &str, ip: &str, note: Option<&str>) -> Result<IpAccessRule> {
-        let request = CreateIpAccessRuleRequest {
-            mode: "block".to_string(),
-            configuration: IpAccessRuleConfig {
-                target: "ip".to_string(),
-                value: ip.to_string(),
-            },
-            notes: note.map(|n| n.to_string()),
-        };
+    /// 封禁 IP、IP 段、ASN (AS13335) 或国家 (CN)
+    pub async fn block_ip(&self, zone_id: &str, target: &str, note: Option<&str>) -> Result<IpAccessRule> {
+        let request = ip_access_rule_request("block", target, note)?;
         self.create_ip_access_rule(zone_id, &request).await
     }
 
-    /// IP 白名单
+    /// 将 IP、IP 段、ASN 或国家加入白名单
     pub async fn whitelist_ip(
         &self,
         zone_id: &str,
-        ip: &str,
+        target: &str,
         note: Option<&str>,
     ) -> Result<IpAccessRule> {
-        let request = CreateIpAccessRuleRequest {
-            mode: "whitelist".to_string(),
-            configuration: IpAccessRuleConfig {
-                target: "ip".to_string(),
-                value: ip.to_string(),
-            },
-            notes: note.map(|n| n.to_string()),
-        };
+        let request = ip_access_rule_request("whitelist", target, note)?;
         self.create_ip_access_rule(zone_id, &request).await
     }
 
@@ -106,6 +139,112 @@ impl CfClient {
         resp.result.context("获取速率限制规则失败")
     }
 
+    /// 获取单条速率限制规则
+    pub async fn get_rate_limit(&self, zone_id: &str, rule_id: &str) -> Result<RateLimitRule> {
+        let resp: CfResponse<RateLimitRule> = self
+            .get(&format!("/zones/{}/rate_limits/{}", zone_id, rule_id))
+            .await?;
+        resp.result.context("获取速率限制规则失败")
+    }
+
+    /// 创建速率限制规则
+    pub async fn create_rate_limit(
+        &self,
+        zone_id: &str,
+        request: &RateLimitRequest,
+    ) -> Result<RateLimitRule> {
+        validate_rate_limit(request)?;
+        let resp: CfResponse<RateLimitRule> = self
+            .post(&format!("/zones/{}/rate_limits", zone_id), request)
+            .await?;
+        let rule = resp.result.context("创建速率限制规则失败")?;
+        self.record_audit(zone_id, AuditChange::RateLimitCreate { rule: rule.clone() });
+        Ok(rule)
+    }
+
+    /// 更新速率限制规则
+    pub async fn update_rate_limit(
+        &self,
+        zone_id: &str,
+        rule_id: &str,
+        request: &RateLimitRequest,
+    ) -> Result<RateLimitRule> {
+        validate_rate_limit(request)?;
+        let resp: CfResponse<RateLimitRule> = self
+            .put(&format!("/zones/{}/rate_limits/{}", zone_id, rule_id), request)
+            .await?;
+        resp.result.context("更新速率限制规则失败")
+    }
+
+    /// 删除速率限制规则
+    pub async fn delete_rate_limit(&self, zone_id: &str, rule_id: &str) -> Result<()> {
+        // 开启审计时先获取规则，供 undo 重新创建
+        let before = if self.audit_enabled() {
+            self.get_rate_limit(zone_id, rule_id).await.ok()
+        } else {
+            None
+        };
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/rate_limits/{}", zone_id, rule_id))
+            .await?;
+        if let Some(before) = before {
+            self.record_audit(zone_id, AuditChange::RateLimitDelete { before });
+        }
+        Ok(())
+    }
+
+    /// 列出用户代理 (User Agent Blocking) 规则
+    pub async fn list_ua_rules(&self, zone_id: &str) -> Result<Vec<UserAgentRule>> {
+        self.get_all_pages(&format!("/zones/{}/firewall/ua_rules", zone_id))
+            .await
+            .context("获取用户代理规则失败")
+    }
+
+    /// 创建用户代理规则
+    pub async fn create_ua_rule(&self, zone_id: &str, request: &UserAgentRuleRequest) -> Result<UserAgentRule> {
+        if request.configuration.value.trim().is_empty() {
+            anyhow::bail!(ValidationError("User-Agent 不能为空".into()));
+        }
+        let resp: CfResponse<UserAgentRule> = self
+            .post(&format!("/zones/{}/firewall/ua_rules", zone_id), request)
+            .await?;
+        resp.result.context("创建用户代理规则失败")
+    }
+
+    /// 删除用户代理规则
+    pub async fn delete_ua_rule(&self, zone_id: &str, rule_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/firewall/ua_rules/{}", zone_id, rule_id))
+            .await?;
+        Ok(())
+    }
+
+    /// 列出 Zone Lockdown 规则
+    pub async fn list_lockdowns(&self, zone_id: &str) -> Result<Vec<LockdownRule>> {
+        self.get_all_pages(&format!("/zones/{}/firewall/lockdowns", zone_id))
+            .await
+            .context("获取 Zone Lockdown 规则失败")
+    }
+
+    /// 创建 Zone Lockdown 规则
+    pub async fn create_lockdown(&self, zone_id: &str, request: &LockdownRuleRequest) -> Result<LockdownRule> {
+        if request.urls.is_empty() || request.configurations.is_empty() {
+            anyhow::bail!(ValidationError("Zone Lockdown 规则至少需要一个 URL 和一个允许的 IP".into()));
+        }
+        let resp: CfResponse<LockdownRule> = self
+            .post(&format!("/zones/{}/firewall/lockdowns", zone_id), request)
+            .await?;
+        resp.result.context("创建 Zone Lockdown 规则失败")
+    }
+
+    /// 删除 Zone Lockdown 规则
+    pub async fn delete_lockdown(&self, zone_id: &str, rule_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/firewall/lockdowns/{}", zone_id, rule_id))
+            .await?;
+        Ok(())
+    }
+
     /// 开启/关闭 Under Attack 模式
     pub async fn set_under_attack_mode(
         &self,
@@ -113,12 +252,8 @@ impl CfClient {
         enable: bool,
     ) -> Result<serde_json::Value> {
         let level = if enable { "under_attack" } else { "medium" };
-        let body = serde_json::json!({ "value": level });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/security_level", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "security_level", serde_json::json!(level))
             .await?;
         resp.result.context("设置 Under Attack 模式失败")
     }
@@ -130,13 +265,150 @@ impl CfClient {
         enable: bool,
     ) -> Result<serde_json::Value> {
         let value = if enable { "on" } else { "off" };
-        let body = serde_json::json!({ "value": value });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/browser_check", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "browser_check", serde_json::json!(value))
             .await?;
         resp.result.context("设置浏览器完整性检查失败")
     }
+
+    // ==================== Bot 管理 ====================
+
+    /// 获取 Bot 管理配置
+    pub async fn get_bot_management(&self, zone_id: &str) -> Result<BotManagementConfig> {
+        let resp: CfResponse<BotManagementConfig> = self
+            .get(&format!("/zones/{}/bot_management", zone_id))
+            .await?;
+        resp.result.context("获取 Bot 管理配置失败")
+    }
+
+    /// 更新 Bot 管理配置 (仅提交设置了值的字段)
+    pub async fn update_bot_management(
+        &self,
+        zone_id: &str,
+        config: &BotManagementConfig,
+    ) -> Result<BotManagementConfig> {
+        let resp: CfResponse<BotManagementConfig> = self
+            .put(&format!("/zones/{}/bot_management", zone_id), config)
+            .await?;
+        resp.result.context("更新 Bot 管理配置失败")
+    }
+
+    // ==================== 防火墙事件 (GraphQL API) ====================
+
+    /// 查询防火墙事件日志 (按时间倒序)
+    pub async fn list_firewall_events(
+        &self,
+        zone_id: &str,
+        query: &FirewallEventQuery,
+    ) -> Result<Vec<FirewallEvent>> {
+        if let Some(action) = &query.action {
+            if !FIREWALL_EVENT_ACTIONS.contains(&action.as_str()) {
+                anyhow::bail!(ValidationError(format!(
+                    "未知的事件动作: {} (可选: {})",
+                    action,
+                    FIREWALL_EVENT_ACTIONS.join("/")
+                )));
+            }
+        }
+
+        let gql = r#"
+            query GetFirewallEvents($zoneTag: String!, $filter: ZoneFirewallEventsAdaptiveFilter_InputObject!, $limit: Int!) {
+                viewer {
+                    zones(filter: { zoneTag: $zoneTag }) {
+                        firewallEventsAdaptive(
+                            filter: $filter
+                            limit: $limit
+                            orderBy: [datetime_DESC]
+                        ) {
+                            datetime
+                            action
+                            source
+                            ruleId
+                            clientIP
+                            clientCountryName
+                            clientRequestHTTPMethodName
+                            clientRequestHTTPHost
+                            clientRequestPath
+                            userAgent
+                            rayName
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let mut filter = serde_json::json!({
+            "datetime_geq": query.since.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            "datetime_leq": query.until.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        });
+        if let Some(action) = &query.action {
+            filter["action"] = serde_json::json!(action);
+        }
+        if let Some(ip) = &query.client_ip {
+            filter["clientIP"] = serde_json::json!(ip);
+        }
+
+        let variables = serde_json::json!({
+            "zoneTag": zone_id,
+            "filter": filter,
+            "limit": query.limit.clamp(1, 10000),
+        });
+
+        let resp = self.graphql_query(gql, variables).await?;
+
+        let events = resp
+            .get("data")
+            .and_then(|d| d.get("viewer"))
+            .and_then(|v| v.get("zones"))
+            .and_then(|z| z.as_array())
+            .and_then(|z| z.first())
+            .and_then(|z| z.get("firewallEventsAdaptive"))
+            .cloned()
+            .context("无法解析防火墙事件响应")?;
+
+        serde_json::from_value(events).context("解析防火墙事件失败")
+    }
+}
+
+/// 提交前检查速率限制规则参数，避免 API 返回含糊的校验错误
+fn validate_rate_limit(request: &RateLimitRequest) -> Result<()> {
+    let mode = request.action.mode.as_deref().unwrap_or("");
+    if !RATE_LIMIT_MODES.contains(&mode) {
+        anyhow::bail!(ValidationError(format!(
+            "未知的速率限制动作: {} (可选: {})",
+            mode,
+            RATE_LIMIT_MODES.join("/")
+        )));
+    }
+    if request.url().map(|u| u.trim().is_empty()).unwrap_or(true) {
+        anyhow::bail!(ValidationError("速率限制规则必须指定匹配的 URL".into()));
+    }
+    if request.threshold == 0 {
+        anyhow::bail!(ValidationError("请求阈值必须大于 0".into()));
+    }
+    if !(1..=86400).contains(&request.period) {
+        anyhow::bail!(ValidationError("统计周期必须在 1-86400 秒之间".into()));
+    }
+    match (mode, request.action.timeout) {
+        ("ban" | "simulate", None) => anyhow::bail!(ValidationError(format!("{} 动作必须指定封禁时长 (timeout)", mode))),
+        ("ban" | "simulate", Some(t)) if !(1..=86400).contains(&t) => {
+            anyhow::bail!(ValidationError("封禁时长必须在 1-86400 秒之间".into()))
+        }
+        ("challenge" | "js_challenge" | "managed_challenge", Some(_)) => {
+            anyhow::bail!(ValidationError(format!("{} 动作不支持 timeout", mode)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 构造 IP 访问规则请求，按目标的格式识别类型 (IP、CIDR、ASN 或国家代码)
+pub fn ip_access_rule_request(mode: &str, target: &str, note: Option<&str>) -> Result<CreateIpAccessRuleRequest> {
+    let configuration = IpAccessRuleConfig::parse(target).ok_or_else(|| {
+        ValidationError(format!("无法识别的目标: {} (应为 IP、CIDR、ASN 或国家代码)", target))
+    })?;
+    Ok(CreateIpAccessRuleRequest {
+        mode: mode.to_string(),
+        configuration,
+        notes: note.map(|n| n.to_string()),
+    })
 }
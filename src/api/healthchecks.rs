@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::common::CfResponse;
+use crate::models::healthcheck::*;
+
+impl CfClient {
+    // ==================== 健康检查 ====================
+
+    /// 列出健康检查
+    pub async fn list_healthchecks(&self, zone_id: &str) -> Result<Vec<Healthcheck>> {
+        let resp: CfResponse<Vec<Healthcheck>> = self
+            .get(&format!("/zones/{}/healthchecks", zone_id))
+            .await?;
+        resp.result.context("获取健康检查失败")
+    }
+
+    /// 获取健康检查详情
+    pub async fn get_healthcheck(&self, zone_id: &str, check_id: &str) -> Result<Healthcheck> {
+        let resp: CfResponse<Healthcheck> = self
+            .get(&format!("/zones/{}/healthchecks/{}", zone_id, check_id))
+            .await?;
+        resp.result.context("获取健康检查详情失败")
+    }
+
+    /// 创建健康检查
+    pub async fn create_healthcheck(
+        &self,
+        zone_id: &str,
+        request: &HealthcheckRequest,
+    ) -> Result<Healthcheck> {
+        let resp: CfResponse<Healthcheck> = self
+            .post(&format!("/zones/{}/healthchecks", zone_id), request)
+            .await?;
+        resp.result.context("创建健康检查失败")
+    }
+
+    /// 更新健康检查
+    pub async fn update_healthcheck(
+        &self,
+        zone_id: &str,
+        check_id: &str,
+        request: &HealthcheckRequest,
+    ) -> Result<Healthcheck> {
+        let resp: CfResponse<Healthcheck> = self
+            .put(
+                &format!("/zones/{}/healthchecks/{}", zone_id, check_id),
+                request,
+            )
+            .await?;
+        resp.result.context("更新健康检查失败")
+    }
+
+    /// 删除健康检查
+    pub async fn delete_healthcheck(&self, zone_id: &str, check_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/healthchecks/{}", zone_id, check_id))
+            .await?;
+        Ok(())
+    }
+}
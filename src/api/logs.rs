@@ -0,0 +1,35 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::api::workers::TailStream;
+use crate::models::common::CfResponse;
+use crate::models::logs::*;
+
+impl CfClient {
+    /// 创建 Instant Logs 会话
+    pub async fn create_instant_logs_job(
+        &self,
+        zone_id: &str,
+        fields: &[String],
+        sample: u32,
+    ) -> Result<InstantLogsJob> {
+        let request = InstantLogsRequest {
+            fields: fields.join(","),
+            sample,
+            filter: String::new(),
+            kind: "instant-logs".to_string(),
+        };
+        let resp: CfResponse<InstantLogsJob> = self
+            .post(&format!("/zones/{}/logpush/edge/jobs", zone_id), &request)
+            .await?;
+        resp.result.context("创建 Instant Logs 会话失败 (需要 Business 及以上套餐)")
+    }
+}
+
+/// 连接 Instant Logs 会话的 WebSocket
+pub async fn connect_instant_logs(job: &InstantLogsJob) -> Result<TailStream> {
+    let (stream, _) = tokio_tungstenite::connect_async(job.destination_conf.as_str())
+        .await
+        .context("连接 Instant Logs WebSocket 失败")?;
+    Ok(stream)
+}
@@ -1,4 +1,8 @@
 pub mod client;
+pub mod deprecation;
+pub mod error;
+pub mod response_cache;
+pub mod timing;
 pub mod zone;
 pub mod dns;
 pub mod ssl;
@@ -7,3 +11,14 @@ pub mod cache;
 pub mod page_rules;
 pub mod workers;
 pub mod analytics;
+pub mod logs;
+pub mod backup;
+pub mod rulesets;
+pub mod access;
+pub mod r2;
+pub mod d1;
+pub mod accounts;
+pub mod healthchecks;
+pub mod speed;
+pub mod zaraz;
+pub mod urlscanner;
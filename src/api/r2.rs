@@ -0,0 +1,63 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::common::CfResponse;
+use crate::models::r2::*;
+
+impl CfClient {
+    // ==================== R2 存储 ====================
+
+    /// 列出 R2 存储桶
+    pub async fn list_r2_buckets(&self, account_id: &str) -> Result<Vec<R2Bucket>> {
+        let resp: CfResponse<R2BucketList> = self
+            .get(&format!("/accounts/{}/r2/buckets", account_id))
+            .await?;
+        resp.result
+            .map(|r| r.buckets)
+            .context("获取 R2 存储桶列表失败")
+    }
+
+    /// 创建 R2 存储桶
+    pub async fn create_r2_bucket(
+        &self,
+        account_id: &str,
+        request: &CreateR2BucketRequest,
+    ) -> Result<R2Bucket> {
+        let resp: CfResponse<R2Bucket> = self
+            .post(&format!("/accounts/{}/r2/buckets", account_id), request)
+            .await?;
+        resp.result.context("创建 R2 存储桶失败")
+    }
+
+    /// 删除 R2 存储桶 (存储桶必须为空)
+    pub async fn delete_r2_bucket(&self, account_id: &str, bucket: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/accounts/{}/r2/buckets/{}", account_id, bucket))
+            .await?;
+        Ok(())
+    }
+
+    /// 列出存储桶中的对象
+    pub async fn list_r2_objects(
+        &self,
+        account_id: &str,
+        bucket: &str,
+        params: &R2ObjectListParams,
+    ) -> Result<Vec<R2Object>> {
+        let resp: CfResponse<Vec<R2Object>> = self
+            .get_with_params(
+                &format!("/accounts/{}/r2/buckets/{}/objects", account_id, bucket),
+                params,
+            )
+            .await?;
+        resp.result.context("获取 R2 对象列表失败")
+    }
+
+    /// 获取存储桶用量
+    pub async fn get_r2_bucket_usage(&self, account_id: &str, bucket: &str) -> Result<R2BucketUsage> {
+        let resp: CfResponse<R2BucketUsage> = self
+            .get(&format!("/accounts/{}/r2/buckets/{}/usage", account_id, bucket))
+            .await?;
+        resp.result.context("获取 R2 存储桶用量失败")
+    }
+}
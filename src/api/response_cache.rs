@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tracing::debug;
+
+use crate::config::response_cache as disk;
+
+/// GET 响应缓存 (内存 + 磁盘)，对同一 Zone / 账户的变更请求会使其失效
+#[derive(Debug)]
+pub struct ResponseCache {
+    /// 磁盘缓存使用的键 (Profile)
+    key: String,
+    ttl_secs: i64,
+    /// 请求 → (获取时间, 响应体)
+    memory: Mutex<HashMap<String, (i64, String)>>,
+}
+
+impl ResponseCache {
+    pub fn new(key: String, ttl_secs: u64) -> Self {
+        Self {
+            key,
+            ttl_secs: ttl_secs as i64,
+            memory: Mutex::default(),
+        }
+    }
+
+    /// 读取未过期的响应体 (`request` 为路径与查询参数)
+    pub fn get(&self, request: &str) -> Option<String> {
+        let now = chrono::Utc::now().timestamp();
+        let mut memory = self.memory.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((fetched_at, body)) = memory.get(request) {
+            if (0..self.ttl_secs).contains(&(now - fetched_at)) {
+                return Some(body.clone());
+            }
+            memory.remove(request);
+        }
+        let (body, fetched_at) = disk::load(&self.key, request, self.ttl_secs)?;
+        memory.insert(request.to_string(), (fetched_at, body.clone()));
+        Some(body)
+    }
+
+    /// 保存成功响应的响应体
+    pub fn put(&self, request: &str, body: &str) {
+        let now = chrono::Utc::now().timestamp();
+        self.memory
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request.to_string(), (now, body.to_string()));
+        if let Err(e) = disk::store(&self.key, request, body, self.ttl_secs) {
+            debug!("写入响应缓存失败: {:#}", e);
+        }
+    }
+
+    /// 变更请求后移除同一 Zone / 账户下的缓存 (以及包含它的列表，如 `/zones`)
+    pub fn invalidate(&self, path: &str) {
+        let scope = invalidation_scope(path);
+        let list = scope.rsplit_once('/').map(|(list, _)| list).filter(|list| !list.is_empty());
+        let matches = |request: &str| {
+            let within = request
+                .strip_prefix(scope)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']));
+            within || list.is_some_and(|list| request.split('?').next() == Some(list))
+        };
+        self.memory
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|request, _| !matches(request));
+        if let Err(e) = disk::invalidate(&self.key, matches) {
+            debug!("清理响应缓存失败: {:#}", e);
+        }
+    }
+}
+
+/// 变更请求影响的范围: `/zones/<id>/...` 与 `/accounts/<id>/...` 为对应的 Zone / 账户，
+/// 其余为第一段路径 (如 `POST /zones` 创建域名会使所有 Zone 的缓存失效)
+fn invalidation_scope(path: &str) -> &str {
+    let path = path.split('?').next().unwrap_or(path);
+    let mut end = 0;
+    for (i, segment) in path.split('/').enumerate().skip(1) {
+        end += 1 + segment.len();
+        let scoped = i == 1 && matches!(segment, "zones" | "accounts");
+        if !scoped {
+            break;
+        }
+    }
+    &path[..end.min(path.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidation_scope() {
+        assert_eq!(invalidation_scope("/zones/abc/dns_records/1"), "/zones/abc");
+        assert_eq!(invalidation_scope("/zones/abc"), "/zones/abc");
+        assert_eq!(invalidation_scope("/zones"), "/zones");
+        assert_eq!(invalidation_scope("/accounts/acc-1/workers/scripts/x"), "/accounts/acc-1");
+        assert_eq!(invalidation_scope("/user/tokens?page=1"), "/user");
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::common::CfResponse;
+use crate::models::rulesets::*;
+
+impl CfClient {
+    // ==================== 规则集 (Rulesets) ====================
+
+    /// 获取域名某个阶段 (phase) 的入口规则集，未创建时返回 None
+    pub async fn get_phase_ruleset(&self, zone_id: &str, phase: &str) -> Result<Option<Ruleset>> {
+        let resp: Option<CfResponse<Ruleset>> = self
+            .get_optional(&format!(
+                "/zones/{}/rulesets/phases/{}/entrypoint",
+                zone_id, phase
+            ))
+            .await?;
+        Ok(resp.and_then(|r| r.result))
+    }
+
+    /// 列出域名可用的规则集 (含可部署的托管规则集)
+    pub async fn list_zone_rulesets(&self, zone_id: &str) -> Result<Vec<Ruleset>> {
+        let resp: CfResponse<Vec<Ruleset>> =
+            self.get(&format!("/zones/{}/rulesets", zone_id)).await?;
+        resp.result.context("获取规则集列表失败")
+    }
+
+    /// 覆盖写入阶段入口规则集 (不存在时创建)
+    pub async fn update_phase_ruleset(
+        &self,
+        zone_id: &str,
+        phase: &str,
+        rules: &[RulesetRuleRequest],
+    ) -> Result<Ruleset> {
+        let resp: CfResponse<Ruleset> = self
+            .put(
+                &format!("/zones/{}/rulesets/phases/{}/entrypoint", zone_id, phase),
+                &serde_json::json!({ "rules": rules }),
+            )
+            .await?;
+        resp.result.context("更新阶段规则集失败")
+    }
+
+    /// 向阶段入口规则集末尾追加一条规则，入口规则集不存在时一并创建
+    pub async fn add_phase_rule(
+        &self,
+        zone_id: &str,
+        phase: &str,
+        rule: &RulesetRuleRequest,
+    ) -> Result<Ruleset> {
+        match self.get_phase_ruleset(zone_id, phase).await? {
+            Some(Ruleset { id: Some(id), .. }) => self.create_ruleset_rule(zone_id, &id, rule).await,
+            _ => {
+                self.update_phase_ruleset(zone_id, phase, std::slice::from_ref(rule))
+                    .await
+            }
+        }
+    }
+
+    /// 向规则集末尾追加一条规则
+    pub async fn create_ruleset_rule(
+        &self,
+        zone_id: &str,
+        ruleset_id: &str,
+        rule: &RulesetRuleRequest,
+    ) -> Result<Ruleset> {
+        let resp: CfResponse<Ruleset> = self
+            .post(&format!("/zones/{}/rulesets/{}/rules", zone_id, ruleset_id), rule)
+            .await?;
+        resp.result.context("创建规则失败")
+    }
+
+    /// 更新规则集中的一条规则
+    pub async fn update_ruleset_rule(
+        &self,
+        zone_id: &str,
+        ruleset_id: &str,
+        rule_id: &str,
+        rule: &RulesetRuleRequest,
+    ) -> Result<Ruleset> {
+        let resp: CfResponse<Ruleset> = self
+            .patch(
+                &format!("/zones/{}/rulesets/{}/rules/{}", zone_id, ruleset_id, rule_id),
+                rule,
+            )
+            .await?;
+        resp.result.context("更新规则失败")
+    }
+
+    /// 删除规则集中的一条规则
+    pub async fn delete_ruleset_rule(&self, zone_id: &str, ruleset_id: &str, rule_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!(
+                "/zones/{}/rulesets/{}/rules/{}",
+                zone_id, ruleset_id, rule_id
+            ))
+            .await?;
+        Ok(())
+    }
+}
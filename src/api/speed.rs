@@ -0,0 +1,75 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::common::CfResponse;
+use crate::models::zone::{ImageSettings, ZoneSetting, ZoneSettingPatch};
+
+impl CfClient {
+    // ==================== 加速 (Argo / Tiered Cache) ====================
+
+    /// 获取 Argo Smart Routing 状态
+    pub async fn get_argo_smart_routing(&self, zone_id: &str) -> Result<ZoneSetting> {
+        self.get_speed_setting(zone_id, "argo/smart_routing").await
+    }
+
+    /// 开启/关闭 Argo Smart Routing (需要账户已启用 Argo 计费)
+    pub async fn set_argo_smart_routing(&self, zone_id: &str, enable: bool) -> Result<ZoneSetting> {
+        self.patch_speed_setting(zone_id, "argo/smart_routing", enable).await
+    }
+
+    /// 获取 Tiered Cache 状态
+    pub async fn get_tiered_caching(&self, zone_id: &str) -> Result<ZoneSetting> {
+        self.get_speed_setting(zone_id, "argo/tiered_caching").await
+    }
+
+    /// 开启/关闭 Tiered Cache
+    pub async fn set_tiered_caching(&self, zone_id: &str, enable: bool) -> Result<ZoneSetting> {
+        self.patch_speed_setting(zone_id, "argo/tiered_caching", enable).await
+    }
+
+    /// 获取 Smart Tiered Cache 拓扑状态
+    pub async fn get_smart_tiered_cache(&self, zone_id: &str) -> Result<ZoneSetting> {
+        self.get_speed_setting(zone_id, "cache/tiered_cache_smart_topology_enable")
+            .await
+    }
+
+    /// 开启/关闭 Smart Tiered Cache 拓扑 (需同时开启 Tiered Cache)
+    pub async fn set_smart_tiered_cache(&self, zone_id: &str, enable: bool) -> Result<ZoneSetting> {
+        self.patch_speed_setting(zone_id, "cache/tiered_cache_smart_topology_enable", enable)
+            .await
+    }
+
+    /// 获取图片优化设置 (Polish / WebP / Mirage)
+    pub async fn get_image_settings(&self, zone_id: &str) -> ImageSettings {
+        let (polish, webp, mirage) = tokio::join!(
+            self.get_zone_setting(zone_id, "polish"),
+            self.get_zone_setting(zone_id, "webp"),
+            self.get_zone_setting(zone_id, "mirage"),
+        );
+        let value = |s: Result<ZoneSetting>| s.ok().and_then(|s| s.value.as_str().map(String::from));
+        ImageSettings {
+            polish: value(polish),
+            webp: value(webp),
+            mirage: value(mirage),
+        }
+    }
+
+    async fn get_speed_setting(&self, zone_id: &str, path: &str) -> Result<ZoneSetting> {
+        let resp: CfResponse<ZoneSetting> = self
+            .get(&format!("/zones/{}/{}", zone_id, path))
+            .await?;
+        resp.result
+            .with_context(|| format!("获取 {} 失败", path))
+    }
+
+    async fn patch_speed_setting(&self, zone_id: &str, path: &str, enable: bool) -> Result<ZoneSetting> {
+        let body = ZoneSettingPatch {
+            value: serde_json::json!(if enable { "on" } else { "off" }),
+        };
+        let resp: CfResponse<ZoneSetting> = self
+            .patch(&format!("/zones/{}/{}", zone_id, path), &body)
+            .await?;
+        resp.result
+            .with_context(|| format!("修改 {} 失败", path))
+    }
+}
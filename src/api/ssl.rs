@@ -21,9 +21,8 @@ impl CfClient {
 
     /// 设置 SSL/TLS 模式
     pub async fn set_ssl_mode(&self, zone_id: &str, mode: &str) -> Result<serde_json::Value> {
-        let body = serde_json::json!({ "value": mode });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(&format!("/zones/{}/settings/ssl", zone_id), &body)
+            .patch_zone_setting(zone_id, "ssl", serde_json::json!(mode))
             .await?;
         resp.result.context("设置 SSL 模式失败")
     }
@@ -44,15 +43,153 @@ impl CfClient {
         resp.result.context("获取 SSL 证书失败")
     }
 
+    /// 订购高级证书包 (需要 Advanced Certificate Manager)
+    pub async fn order_certificate_pack(&self, zone_id: &str, order: &CertificatePackOrder) -> Result<SslCertificate> {
+        let resp: CfResponse<SslCertificate> = self
+            .post(&format!("/zones/{}/ssl/certificate_packs/order", zone_id), order)
+            .await?;
+        resp.result.context("订购证书包失败")
+    }
+
+    /// 获取 Total TLS 设置
+    pub async fn get_total_tls(&self, zone_id: &str) -> Result<TotalTls> {
+        let resp: CfResponse<TotalTls> = self
+            .get(&format!("/zones/{}/acm/total_tls", zone_id))
+            .await?;
+        resp.result.context("获取 Total TLS 设置失败")
+    }
+
+    /// 开启或关闭 Total TLS，可指定 CA
+    pub async fn set_total_tls(&self, zone_id: &str, enabled: bool, ca: Option<&str>) -> Result<TotalTls> {
+        let mut body = serde_json::json!({ "enabled": enabled });
+        if let Some(ca) = ca {
+            body["certificate_authority"] = serde_json::json!(ca);
+        }
+        let resp: CfResponse<TotalTls> = self
+            .post(&format!("/zones/{}/acm/total_tls", zone_id), &body)
+            .await?;
+        resp.result.context("设置 Total TLS 失败")
+    }
+
+    /// 列出自定义证书
+    pub async fn list_custom_certificates(&self, zone_id: &str) -> Result<Vec<CustomCertificate>> {
+        let resp: CfResponse<Vec<CustomCertificate>> = self
+            .get(&format!("/zones/{}/custom_certificates", zone_id))
+            .await?;
+        resp.result.context("获取自定义证书失败")
+    }
+
+    /// 上传自定义证书
+    pub async fn upload_custom_certificate(
+        &self,
+        zone_id: &str,
+        request: &UploadCustomCertRequest,
+    ) -> Result<CustomCertificate> {
+        let resp: CfResponse<CustomCertificate> = self
+            .post(&format!("/zones/{}/custom_certificates", zone_id), request)
+            .await?;
+        resp.result.context("上传自定义证书失败")
+    }
+
+    /// 删除自定义证书
+    pub async fn delete_custom_certificate(&self, zone_id: &str, cert_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/custom_certificates/{}", zone_id, cert_id))
+            .await?;
+        Ok(())
+    }
+
+    // ==================== 自定义主机名 (SSL for SaaS) ====================
+
+    /// 列出自定义主机名 (可按主机名过滤)
+    pub async fn list_custom_hostnames(
+        &self,
+        zone_id: &str,
+        hostname: Option<&str>,
+    ) -> Result<Vec<CustomHostname>> {
+        let mut path = format!("/zones/{}/custom_hostnames?per_page=50", zone_id);
+        if let Some(hostname) = hostname {
+            path.push_str(&format!("&hostname={}", hostname));
+        }
+        let resp: CfResponse<Vec<CustomHostname>> = self.get(&path).await?;
+        resp.result.context("获取自定义主机名失败")
+    }
+
+    /// 获取自定义主机名详情 (含验证状态)
+    pub async fn get_custom_hostname(&self, zone_id: &str, id: &str) -> Result<CustomHostname> {
+        let resp: CfResponse<CustomHostname> = self
+            .get(&format!("/zones/{}/custom_hostnames/{}", zone_id, id))
+            .await?;
+        resp.result.context("获取自定义主机名详情失败")
+    }
+
+    /// 创建自定义主机名
+    pub async fn create_custom_hostname(
+        &self,
+        zone_id: &str,
+        request: &CustomHostnameRequest,
+    ) -> Result<CustomHostname> {
+        let resp: CfResponse<CustomHostname> = self
+            .post(&format!("/zones/{}/custom_hostnames", zone_id), request)
+            .await?;
+        resp.result.context("创建自定义主机名失败")
+    }
+
+    /// 重新触发证书验证 (以原验证方式提交)
+    pub async fn revalidate_custom_hostname(
+        &self,
+        zone_id: &str,
+        id: &str,
+        method: &str,
+        cert_type: &str,
+    ) -> Result<CustomHostname> {
+        let body = serde_json::json!({ "ssl": { "method": method, "type": cert_type } });
+        let resp: CfResponse<CustomHostname> = self
+            .patch(&format!("/zones/{}/custom_hostnames/{}", zone_id, id), &body)
+            .await?;
+        resp.result.context("重新验证自定义主机名失败")
+    }
+
+    /// 删除自定义主机名
+    pub async fn delete_custom_hostname(&self, zone_id: &str, id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/custom_hostnames/{}", zone_id, id))
+            .await?;
+        Ok(())
+    }
+
+    /// 获取默认回源地址
+    pub async fn get_fallback_origin(&self, zone_id: &str) -> Result<FallbackOrigin> {
+        let resp: CfResponse<FallbackOrigin> = self
+            .get(&format!("/zones/{}/custom_hostnames/fallback_origin", zone_id))
+            .await?;
+        resp.result.context("获取默认回源地址失败")
+    }
+
+    /// 设置默认回源地址 (需为本域名下已代理的 DNS 记录)
+    pub async fn set_fallback_origin(&self, zone_id: &str, origin: &str) -> Result<FallbackOrigin> {
+        let resp: CfResponse<FallbackOrigin> = self
+            .put(
+                &format!("/zones/{}/custom_hostnames/fallback_origin", zone_id),
+                &serde_json::json!({ "origin": origin }),
+            )
+            .await?;
+        resp.result.context("设置默认回源地址失败")
+    }
+
+    /// 删除默认回源地址
+    pub async fn delete_fallback_origin(&self, zone_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/zones/{}/custom_hostnames/fallback_origin", zone_id))
+            .await?;
+        Ok(())
+    }
+
     /// 设置 Always Use HTTPS
     pub async fn set_always_https(&self, zone_id: &str, enable: bool) -> Result<serde_json::Value> {
         let value = if enable { "on" } else { "off" };
-        let body = serde_json::json!({ "value": value });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/always_use_https", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "always_use_https", serde_json::json!(value))
             .await?;
         resp.result.context("设置 Always Use HTTPS 失败")
     }
@@ -68,16 +205,44 @@ impl CfClient {
 
     /// 设置最小 TLS 版本 (通过 SSL 模块)
     pub async fn set_ssl_min_tls(&self, zone_id: &str, version: &str) -> Result<serde_json::Value> {
-        let body = serde_json::json!({ "value": version });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/min_tls_version", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "min_tls_version", serde_json::json!(version))
             .await?;
         resp.result.context("设置最小 TLS 版本失败")
     }
 
+    /// 设置 TLS 1.3 (on/off)
+    pub async fn set_tls_1_3(&self, zone_id: &str, enable: bool) -> Result<serde_json::Value> {
+        let value = if enable { "on" } else { "off" };
+        let resp: CfResponse<serde_json::Value> = self
+            .patch_zone_setting(zone_id, "tls_1_3", serde_json::json!(value))
+            .await?;
+        resp.result.context("设置 TLS 1.3 失败")
+    }
+
+    /// 设置 0-RTT 连接恢复
+    pub async fn set_early_data(&self, zone_id: &str, enable: bool) -> Result<serde_json::Value> {
+        let value = if enable { "on" } else { "off" };
+        let resp: CfResponse<serde_json::Value> = self
+            .patch_zone_setting(zone_id, "0rtt", serde_json::json!(value))
+            .await?;
+        resp.result.context("设置 0-RTT 失败")
+    }
+
+    /// 获取允许的加密套件 (空列表表示使用 Cloudflare 默认)
+    pub async fn get_ciphers(&self, zone_id: &str) -> Result<Vec<String>> {
+        let setting = self.get_zone_setting(zone_id, "ciphers").await?;
+        Ok(serde_json::from_value(setting.value).unwrap_or_default())
+    }
+
+    /// 设置允许的加密套件，空列表恢复默认
+    pub async fn set_ciphers(&self, zone_id: &str, ciphers: &[String]) -> Result<serde_json::Value> {
+        let resp: CfResponse<serde_json::Value> = self
+            .patch_zone_setting(zone_id, "ciphers", serde_json::json!(ciphers))
+            .await?;
+        resp.result.context("设置加密套件失败")
+    }
+
     /// 获取源服务器证书列表
     pub async fn list_origin_certificates(&self, zone_id: &str) -> Result<Vec<OriginCertificate>> {
         let resp: CfResponse<Vec<OriginCertificate>> = self
@@ -112,12 +277,8 @@ impl CfClient {
         enable: bool,
     ) -> Result<serde_json::Value> {
         let value = if enable { "on" } else { "off" };
-        let body = serde_json::json!({ "value": value });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/opportunistic_encryption", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "opportunistic_encryption", serde_json::json!(value))
             .await?;
         resp.result.context("设置 Opportunistic Encryption 失败")
     }
@@ -129,12 +290,8 @@ impl CfClient {
         enable: bool,
     ) -> Result<serde_json::Value> {
         let value = if enable { "on" } else { "off" };
-        let body = serde_json::json!({ "value": value });
         let resp: CfResponse<serde_json::Value> = self
-            .patch(
-                &format!("/zones/{}/settings/automatic_https_rewrites", zone_id),
-                &body,
-            )
+            .patch_zone_setting(zone_id, "automatic_https_rewrites", serde_json::json!(value))
             .await?;
         resp.result
             .context("设置 Automatic HTTPS Rewrites 失败")
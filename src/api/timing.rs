@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::deprecation::normalize_endpoint;
+
+/// 请求耗时记录 (`--timings`)
+#[derive(Debug, Default)]
+pub struct RequestTimings {
+    samples: Mutex<Vec<(String, Duration)>>,
+}
+
+/// 单个接口的耗时统计
+#[derive(Debug, Clone)]
+pub struct EndpointTiming {
+    /// 方法与规范化后的路径，如 `GET /zones/{id}/dns_records`
+    pub endpoint: String,
+    pub count: usize,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    pub total: Duration,
+}
+
+impl RequestTimings {
+    /// 记录一次请求 (含重试) 的耗时
+    pub fn record(&self, method: &str, path: &str, elapsed: Duration) {
+        let path = path.split('?').next().unwrap_or(path);
+        let endpoint = format!("{} {}", method, normalize_endpoint(path));
+        if let Ok(mut samples) = self.samples.lock() {
+            samples.push((endpoint, elapsed));
+        }
+    }
+
+    /// 按接口汇总，总耗时最长的排在前面
+    pub fn summary(&self) -> Vec<EndpointTiming> {
+        let samples = self.samples.lock().map(|s| s.clone()).unwrap_or_default();
+        let mut grouped: HashMap<String, Vec<Duration>> = HashMap::new();
+        for (endpoint, elapsed) in samples {
+            grouped.entry(endpoint).or_default().push(elapsed);
+        }
+
+        let mut summary: Vec<EndpointTiming> = grouped
+            .into_iter()
+            .map(|(endpoint, durations)| {
+                let total: Duration = durations.iter().sum();
+                EndpointTiming {
+                    endpoint,
+                    count: durations.len(),
+                    min: durations.iter().copied().min().unwrap_or_default(),
+                    avg: total / durations.len() as u32,
+                    max: durations.iter().copied().max().unwrap_or_default(),
+                    total,
+                }
+            })
+            .collect();
+        summary.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.endpoint.cmp(&b.endpoint)));
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_groups_by_endpoint() {
+        let timings = RequestTimings::default();
+        let zone = "/zones/023e105f4ecef8ad9ca31a8372d0c353/dns_records";
+        timings.record("GET", &format!("{}?page=1", zone), Duration::from_millis(100));
+        timings.record("GET", &format!("{}?page=2", zone), Duration::from_millis(300));
+        timings.record("GET", "/zones", Duration::from_millis(50));
+
+        let summary = timings.summary();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].endpoint, "GET /zones/{id}/dns_records");
+        assert_eq!(summary[0].count, 2);
+        assert_eq!(summary[0].min, Duration::from_millis(100));
+        assert_eq!(summary[0].avg, Duration::from_millis(200));
+        assert_eq!(summary[0].max, Duration::from_millis(300));
+        assert_eq!(summary[1].endpoint, "GET /zones");
+    }
+}
@@ -0,0 +1,24 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::urlscanner::*;
+
+impl CfClient {
+    /// 提交 URL 扫描 (URL Scanner 接口不使用 Cloudflare 标准响应包装)
+    pub async fn submit_url_scan(&self, account_id: &str, request: &UrlScanRequest) -> Result<UrlScanSubmission> {
+        let url = self.url(&format!("/accounts/{}/urlscanner/v2/scan", account_id));
+        let resp = self.post_raw(&url, request).await.context("提交 URL 扫描失败")?;
+        serde_json::from_value(resp).context("解析扫描任务失败")
+    }
+
+    /// 获取扫描报告，扫描尚未完成时返回 None
+    pub async fn get_url_scan(&self, account_id: &str, scan_id: &str) -> Result<Option<UrlScanReport>> {
+        let report = self
+            .get_raw_optional(&format!("/accounts/{}/urlscanner/v2/result/{}", account_id, scan_id))
+            .await
+            .context("获取扫描报告失败")?;
+        report
+            .map(|r| serde_json::from_value(r).context("解析扫描报告失败"))
+            .transpose()
+    }
+}
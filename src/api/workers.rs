@@ -1,4 +1,8 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use futures_util::SinkExt;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
 
 use crate::api::client::CfClient;
 use crate::models::common::CfResponse;
@@ -75,4 +79,200 @@ impl CfClient {
             .await?;
         resp.result.context("获取 Workers 域名失败")
     }
+
+    /// 列出 Queues 队列 (自动翻页)
+    pub async fn list_queues(&self, account_id: &str) -> Result<Vec<Queue>> {
+        self.get_all_pages(&format!("/accounts/{}/queues", account_id))
+            .await
+            .context("获取队列列表失败")
+    }
+
+    /// 创建 Queues 队列
+    pub async fn create_queue(&self, account_id: &str, request: &CreateQueueRequest) -> Result<Queue> {
+        let resp: CfResponse<Queue> = self
+            .post(&format!("/accounts/{}/queues", account_id), request)
+            .await?;
+        resp.result.context("创建队列失败")
+    }
+
+    /// 删除 Queues 队列
+    pub async fn delete_queue(&self, account_id: &str, queue_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!("/accounts/{}/queues/{}", account_id, queue_id))
+            .await?;
+        Ok(())
+    }
+
+    /// 列出 Durable Object 命名空间 (自动翻页)
+    pub async fn list_durable_object_namespaces(
+        &self,
+        account_id: &str,
+    ) -> Result<Vec<DurableObjectNamespace>> {
+        self.get_all_pages(&format!(
+            "/accounts/{}/workers/durable_objects/namespaces",
+            account_id
+        ))
+        .await
+        .context("获取 Durable Object 命名空间失败")
+    }
+
+    /// 获取 Workers 脚本在时间范围内的调用统计 (GraphQL)
+    pub async fn get_worker_stats(
+        &self,
+        account_id: &str,
+        script_name: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<WorkerStats> {
+        let query = r#"
+            query GetWorkerStats($accountTag: String!, $script: String!, $since: Time!, $until: Time!) {
+                viewer {
+                    accounts(filter: { accountTag: $accountTag }) {
+                        totals: workersInvocationsAdaptive(
+                            limit: 1
+                            filter: { scriptName: $script, datetime_geq: $since, datetime_leq: $until }
+                        ) {
+                            sum {
+                                requests
+                                errors
+                                subrequests
+                            }
+                            quantiles {
+                                cpuTimeP50
+                                cpuTimeP90
+                                cpuTimeP99
+                            }
+                        }
+                        series: workersInvocationsAdaptive(
+                            limit: 10000
+                            filter: { scriptName: $script, datetime_geq: $since, datetime_leq: $until }
+                            orderBy: [datetimeHour_ASC]
+                        ) {
+                            dimensions {
+                                datetimeHour
+                            }
+                            sum {
+                                requests
+                                errors
+                                subrequests
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let since_str = since.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let until_str = until.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let variables = serde_json::json!({
+            "accountTag": account_id,
+            "script": script_name,
+            "since": since_str,
+            "until": until_str
+        });
+
+        let resp = self.graphql_query(query, variables).await?;
+        let account = resp
+            .get("data")
+            .and_then(|d| d.get("viewer"))
+            .and_then(|v| v.get("accounts"))
+            .and_then(|a| a.as_array())
+            .and_then(|a| a.first())
+            .context("无法解析 Workers 统计数据")?;
+
+        let sum_field = |group: &serde_json::Value, name: &str| {
+            group.get("sum").and_then(|s| s.get(name)).and_then(|v| v.as_u64()).unwrap_or(0)
+        };
+        let groups = |name: &str| {
+            account
+                .get(name)
+                .and_then(|g| g.as_array())
+                .map(|g| g.as_slice())
+                .unwrap_or_default()
+        };
+
+        let mut stats = WorkerStats {
+            script: script_name.to_string(),
+            since: since_str,
+            until: until_str,
+            ..Default::default()
+        };
+        if let Some(totals) = groups("totals").first() {
+            stats.requests = sum_field(totals, "requests");
+            stats.errors = sum_field(totals, "errors");
+            stats.subrequests = sum_field(totals, "subrequests");
+            let quantile = |name: &str| totals.get("quantiles").and_then(|q| q.get(name)).and_then(|v| v.as_f64());
+            stats.cpu_time_p50 = quantile("cpuTimeP50");
+            stats.cpu_time_p90 = quantile("cpuTimeP90");
+            stats.cpu_time_p99 = quantile("cpuTimeP99");
+        }
+        // 同一小时可能拆成多个分组，按时间合并
+        for group in groups("series") {
+            let time = group
+                .get("dimensions")
+                .and_then(|d| d.get("datetimeHour"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            if stats.timeseries.last().map(|p| p.time.as_str()) != Some(time.as_str()) {
+                stats.timeseries.push(WorkerStatsPoint { time, ..Default::default() });
+            }
+            if let Some(point) = stats.timeseries.last_mut() {
+                point.requests += sum_field(group, "requests");
+                point.errors += sum_field(group, "errors");
+                point.subrequests += sum_field(group, "subrequests");
+            }
+        }
+        Ok(stats)
+    }
+
+    /// 为 Workers 脚本创建实时日志 (tail) 会话
+    pub async fn create_worker_tail(&self, account_id: &str, script_name: &str) -> Result<WorkerTail> {
+        let resp: CfResponse<WorkerTail> = self
+            .post(
+                &format!("/accounts/{}/workers/scripts/{}/tails", account_id, script_name),
+                &serde_json::json!({}),
+            )
+            .await?;
+        resp.result.context("创建 Workers tail 会话失败")
+    }
+
+    /// 删除 Workers 实时日志会话
+    pub async fn delete_worker_tail(&self, account_id: &str, script_name: &str, tail_id: &str) -> Result<()> {
+        let _resp: CfResponse<serde_json::Value> = self
+            .delete(&format!(
+                "/accounts/{}/workers/scripts/{}/tails/{}",
+                account_id, script_name, tail_id
+            ))
+            .await?;
+        Ok(())
+    }
+}
+
+/// tail 会话的 WebSocket 连接
+pub type TailStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// 连接 tail 会话的 WebSocket 并发送服务端过滤条件
+///
+/// `filters` 为 Cloudflare tail 过滤器数组，如 `[{"outcome": ["exception"]}, {"method": ["POST"]}]`。
+pub async fn connect_worker_tail(tail: &WorkerTail, filters: serde_json::Value) -> Result<TailStream> {
+    let mut request = tail
+        .url
+        .as_str()
+        .into_client_request()
+        .context("无效的 tail WebSocket 地址")?;
+    request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", "trace-v1".parse()?);
+
+    let (mut stream, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .context("连接 tail WebSocket 失败")?;
+    let message = serde_json::json!({ "filters": filters, "debug": false });
+    stream
+        .send(Message::Text(message.to_string().into()))
+        .await
+        .context("发送 tail 过滤条件失败")?;
+    Ok(stream)
 }
@@ -0,0 +1,38 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::CfClient;
+use crate::models::common::CfResponse;
+
+impl CfClient {
+    // ==================== Zaraz ====================
+
+    /// 获取 Zaraz 配置 (保留原始 JSON，更新时整体写回)
+    pub async fn get_zaraz_config(&self, zone_id: &str) -> Result<serde_json::Value> {
+        let resp: CfResponse<serde_json::Value> = self
+            .get(&format!("/zones/{}/settings/zaraz/config", zone_id))
+            .await?;
+        resp.result.context("获取 Zaraz 配置失败")
+    }
+
+    /// 写回完整的 Zaraz 配置
+    pub async fn update_zaraz_config(
+        &self,
+        zone_id: &str,
+        config: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let resp: CfResponse<serde_json::Value> = self
+            .put(&format!("/zones/{}/settings/zaraz/config", zone_id), config)
+            .await?;
+        resp.result.context("更新 Zaraz 配置失败")
+    }
+
+    /// 获取 Zaraz 发布方式 (realtime: 修改立即生效 / preview: 需发布)
+    pub async fn get_zaraz_workflow(&self, zone_id: &str) -> Result<String> {
+        let resp: CfResponse<serde_json::Value> = self
+            .get(&format!("/zones/{}/settings/zaraz/workflow", zone_id))
+            .await?;
+        resp.result
+            .and_then(|v| v.as_str().map(String::from))
+            .context("获取 Zaraz 发布方式失败")
+    }
+}
@@ -1,6 +1,12 @@
 use anyhow::{Context, Result};
 
+use serde::de::DeserializeOwned;
+use tracing::debug;
+
 use crate::api::client::CfClient;
+use crate::api::error::{ApiError, ValidationError};
+use crate::config::zone_cache;
+use crate::models::audit::AuditChange;
 use crate::models::common::CfResponse;
 use crate::models::zone::*;
 
@@ -12,6 +18,11 @@ impl CfClient {
         self.get_with_params("/zones", params).await
     }
 
+    /// 列出账户下的全部域名 (自动翻页)
+    pub async fn list_all_zones(&self) -> Result<Vec<Zone>> {
+        self.get_all_pages("/zones").await.context("获取域名列表失败")
+    }
+
     /// 获取域名详情
     pub async fn get_zone(&self, zone_id: &str) -> Result<Zone> {
         let resp: CfResponse<Zone> = self.get(&format!("/zones/{}", zone_id)).await?;
@@ -20,16 +31,58 @@ impl CfClient {
 
     /// 通过域名名称查找 Zone ID
     pub async fn find_zone_id(&self, domain: &str) -> Result<String> {
+        if let Some(id) = self
+            .zone_cache_key()
+            .and_then(|key| zone_cache::load_zone_id(key, domain))
+        {
+            debug!("Zone ID 缓存命中: {} → {}", domain, id);
+            self.note_cached_zone_id(&id);
+            return Ok(id);
+        }
+
         let params = ZoneListParams {
             name: Some(domain.to_string()),
             ..Default::default()
         };
         let resp = self.list_zones(&params).await?;
         let zones = resp.result.context("查询域名失败")?;
-        zones
+        let id = zones
             .first()
             .map(|z| z.id.clone())
-            .context(format!("未找到域名: {}", domain))
+            .context(format!("未找到域名: {}", domain))?;
+
+        if let Some(key) = self.zone_cache_key() {
+            if let Err(e) = zone_cache::store_zone_id(key, domain, &id) {
+                debug!("写入 Zone ID 缓存失败: {:#}", e);
+            }
+        }
+        Ok(id)
+    }
+
+    /// 命令失败时清除本次使用的 Zone ID 缓存 (域名已在别处删除或凭据已无权访问时缓存会过期)
+    ///
+    /// 返回是否值得重新查找后重试一次: 错误为 404，且没有发往其他 Zone 的变更请求
+    pub fn evict_stale_zone_ids(&self, err: &anyhow::Error) -> bool {
+        let Some(api) = err.downcast_ref::<ApiError>() else {
+            return false;
+        };
+        let not_found = api.status == 404;
+        if !not_found && !api.is_auth() {
+            return false;
+        }
+        let Some(key) = self.zone_cache_key() else {
+            return false;
+        };
+        let ids = self.take_cached_zone_ids();
+        for id in &ids {
+            debug!("清除可能已失效的 Zone ID 缓存: {}", id);
+            if let Err(e) = zone_cache::forget_zone(key, id) {
+                debug!("清理 Zone ID 缓存失败: {:#}", e);
+            }
+        }
+        not_found
+            && !ids.is_empty()
+            && self.writes().iter().all(|w| ids.iter().any(|id| w.contains(&format!("/zones/{}", id))))
     }
 
     /// 创建域名
@@ -42,6 +95,11 @@ impl CfClient {
     pub async fn delete_zone(&self, zone_id: &str) -> Result<()> {
         let _resp: CfResponse<serde_json::Value> =
             self.delete(&format!("/zones/{}", zone_id)).await?;
+        if let Some(key) = self.zone_cache_key() {
+            if let Err(e) = zone_cache::forget_zone(key, zone_id) {
+                debug!("清理 Zone ID 缓存失败: {:#}", e);
+            }
+        }
         Ok(())
     }
 
@@ -85,11 +143,62 @@ impl CfClient {
         setting_id: &str,
         value: serde_json::Value,
     ) -> Result<ZoneSetting> {
+        let resp: CfResponse<ZoneSetting> = self.patch_zone_setting(zone_id, setting_id, value).await?;
+        resp.result.context("更新域名设置失败")
+    }
+
+    /// 发送 Zone 设置修改请求 (开启审计时先获取修改前的值并记录，供 undo 回滚)
+    pub async fn patch_zone_setting<T: DeserializeOwned>(
+        &self,
+        zone_id: &str,
+        setting_id: &str,
+        value: serde_json::Value,
+    ) -> Result<CfResponse<T>> {
+        let before = if self.audit_enabled() {
+            self.get_zone_setting(zone_id, setting_id).await.ok().map(|s| s.value)
+        } else {
+            None
+        };
         let body = ZoneSettingPatch { value };
-        let resp: CfResponse<ZoneSetting> = self
+        let resp: CfResponse<T> = self
             .patch(&format!("/zones/{}/settings/{}", zone_id, setting_id), &body)
             .await?;
-        resp.result.context("更新域名设置失败")
+        self.record_audit(
+            zone_id,
+            AuditChange::SettingChange {
+                setting_id: setting_id.to_string(),
+                before,
+                after: body.value,
+            },
+        );
+        Ok(resp)
+    }
+
+    /// 获取 Zone Hold 状态
+    pub async fn get_zone_hold(&self, zone_id: &str) -> Result<ZoneHold> {
+        let resp: CfResponse<ZoneHold> = self.get(&format!("/zones/{}/hold", zone_id)).await?;
+        resp.result.context("获取 Zone Hold 状态失败")
+    }
+
+    /// 开启 Zone Hold
+    pub async fn enable_zone_hold(&self, zone_id: &str, include_subdomains: bool) -> Result<ZoneHold> {
+        let resp: CfResponse<ZoneHold> = self
+            .post(
+                &format!("/zones/{}/hold?include_subdomains={}", zone_id, include_subdomains),
+                &serde_json::json!({}),
+            )
+            .await?;
+        resp.result.context("开启 Zone Hold 失败")
+    }
+
+    /// 解除 Zone Hold；指定 `hold_after` (UTC 时间，如 2024-01-01T00:00:00Z) 时只临时解除，到期后自动恢复
+    pub async fn disable_zone_hold(&self, zone_id: &str, hold_after: Option<&str>) -> Result<ZoneHold> {
+        let path = match hold_after {
+            Some(time) => format!("/zones/{}/hold?hold_after={}", zone_id, time),
+            None => format!("/zones/{}/hold", zone_id),
+        };
+        let resp: CfResponse<ZoneHold> = self.delete(&path).await?;
+        resp.result.context("解除 Zone Hold 失败")
     }
 
     /// 开启/关闭开发模式
@@ -114,4 +223,85 @@ impl CfClient {
         self.update_zone_setting(zone_id, "min_tls_version", serde_json::json!(version))
             .await
     }
+
+    // ==================== 套餐配额 ====================
+
+    /// 统计受套餐限制资源的当前用量
+    pub async fn get_quota_usage(&self, zone_id: &str, resource: QuotaResource) -> Result<u32> {
+        let used = match resource {
+            QuotaResource::PageRule => self.list_page_rules(zone_id).await?.len(),
+            QuotaResource::RateLimit => self.list_rate_limits(zone_id).await?.len(),
+        };
+        Ok(used as u32)
+    }
+
+    /// 创建前检查配额，已用满时直接给出明确提示
+    pub async fn check_quota(&self, zone_id: &str, resource: QuotaResource) -> Result<()> {
+        let zone = self.get_zone(zone_id).await?;
+        let limits = PlanLimits::for_plan(zone.plan.as_ref());
+        let max = limits.limit_of(resource);
+        let used = self.get_quota_usage(zone_id, resource).await?;
+        if used >= max {
+            anyhow::bail!(ValidationError(format!(
+                "{} 套餐最多 {} 条{}，当前已有 {} 条，请删除旧规则或升级套餐",
+                limits.plan,
+                max,
+                resource.label(),
+                used
+            )));
+        }
+        Ok(())
+    }
+
+    /// 汇总单个域名的关键健康指标 (各项并发获取，失败项为 None)
+    pub async fn get_zone_health(&self, zone: &Zone) -> ZoneHealth {
+        let (ssl_mode, security_level, analytics, dnssec) = tokio::join!(
+            self.get_ssl_mode(&zone.id),
+            self.get_security_level(&zone.id),
+            self.get_analytics_24h(&zone.id),
+            self.get_dnssec(&zone.id),
+        );
+        let cache_hit_rate = analytics
+            .ok()
+            .and_then(|a| a.totals)
+            .and_then(|t| t.requests)
+            .and_then(|r| match (r.all, r.cached) {
+                (Some(all), Some(cached)) if all > 0 => Some(cached as f64 / all as f64 * 100.0),
+                _ => None,
+            });
+        ZoneHealth {
+            zone_id: zone.id.clone(),
+            zone_name: zone.name.clone(),
+            status: zone.status.clone(),
+            ssl_mode: ssl_mode.ok(),
+            security_level: security_level.ok(),
+            cache_hit_rate,
+            dnssec: dnssec.ok().map(|d| d.status().to_string()),
+        }
+    }
+
+    /// 并发汇总多个域名的健康指标 (最多同时检查 HEALTH_CONCURRENCY 个域名)，按输入顺序返回
+    pub async fn get_zones_health(&self, zones: &[Zone]) -> Vec<ZoneHealth> {
+        const HEALTH_CONCURRENCY: usize = 6;
+
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(HEALTH_CONCURRENCY));
+        let mut tasks = tokio::task::JoinSet::new();
+        for (i, zone) in zones.iter().cloned().enumerate() {
+            let client = self.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire().await;
+                (i, client.get_zone_health(&zone).await)
+            });
+        }
+
+        let mut results = Vec::with_capacity(zones.len());
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(item) = joined {
+                results.push(item);
+            }
+        }
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, h)| h).collect()
+    }
 }
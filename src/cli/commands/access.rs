@@ -0,0 +1,303 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::config::settings::AppConfig;
+use crate::models::access::*;
+
+#[derive(Args, Debug)]
+pub struct AccessArgs {
+    #[command(subcommand)]
+    pub command: AccessCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccessCommands {
+    /// 管理 Access 应用
+    App {
+        #[command(subcommand)]
+        command: AccessAppCommands,
+    },
+
+    /// 管理 Access 策略
+    Policy {
+        #[command(subcommand)]
+        command: AccessPolicyCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccessAppCommands {
+    /// 列出 Access 应用
+    #[command(alias = "ls")]
+    List,
+
+    /// 创建自托管 Access 应用
+    Create {
+        /// 应用名称
+        name: String,
+        /// 受保护的域名 (可带路径，如 app.example.com/admin)
+        domain: String,
+        /// 应用类型 (self_hosted/saas/ssh/vnc)
+        #[arg(long = "type", default_value = "self_hosted")]
+        app_type: String,
+        /// 会话时长 (如 24h、30m)
+        #[arg(long)]
+        session_duration: Option<String>,
+    },
+
+    /// 删除 Access 应用
+    #[command(alias = "rm")]
+    Delete {
+        /// 应用 ID、名称或域名
+        app: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccessPolicyCommands {
+    /// 列出应用的策略
+    #[command(alias = "ls")]
+    List {
+        /// 应用 ID、名称或域名
+        app: String,
+    },
+
+    /// 为应用创建策略
+    Create {
+        /// 应用 ID、名称或域名
+        app: String,
+        /// 策略名称
+        name: String,
+        /// 决策 (allow/deny/bypass/non_identity)
+        #[arg(long, default_value = "allow")]
+        decision: String,
+        /// 允许的邮箱 (可重复)
+        #[arg(long = "email")]
+        emails: Vec<String>,
+        /// 允许的邮箱域名 (可重复，如 example.com)
+        #[arg(long = "email-domain")]
+        email_domains: Vec<String>,
+        /// 允许的 IP 段 (可重复，如 1.2.3.0/24)
+        #[arg(long = "ip")]
+        ips: Vec<String>,
+        /// 匹配所有人
+        #[arg(long, conflicts_with_all = ["emails", "email_domains", "ips"])]
+        everyone: bool,
+        /// 优先级 (数字越小越先匹配)
+        #[arg(long)]
+        precedence: Option<i32>,
+    },
+
+    /// 删除策略
+    #[command(alias = "rm")]
+    Delete {
+        /// 应用 ID、名称或域名
+        app: String,
+        /// 策略 ID
+        policy_id: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+impl AccessArgs {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        let account_id = config
+            .cloudflare
+            .account_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Access 管理需要 Account ID，请运行 `cfai config setup`"))?;
+
+        match &self.command {
+            AccessCommands::App { command } => match command {
+                AccessAppCommands::List => {
+                    let apps = client.list_access_apps(account_id).await?;
+
+                    if format == "json" {
+                        output::print_json(&apps);
+                        return Ok(());
+                    }
+
+                    output::title(&format!("Access 应用 (共 {} 个)", apps.len()));
+
+                    if apps.is_empty() {
+                        output::info("没有 Access 应用");
+                        return Ok(());
+                    }
+
+                    let mut table = output::create_table(vec!["ID", "名称", "域名", "类型", "会话时长"]);
+                    for app in &apps {
+                        table.add_row(vec![
+                            app.id.as_deref().unwrap_or("-"),
+                            app.name.as_deref().unwrap_or("-"),
+                            app.domain.as_deref().unwrap_or("-"),
+                            app.app_type.as_deref().unwrap_or("-"),
+                            app.session_duration.as_deref().unwrap_or("-"),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+
+                AccessAppCommands::Create {
+                    name,
+                    domain,
+                    app_type,
+                    session_duration,
+                } => {
+                    let request = CreateAccessAppRequest {
+                        name: name.clone(),
+                        domain: domain.clone(),
+                        app_type: app_type.clone(),
+                        session_duration: session_duration.clone(),
+                    };
+                    let app = client.create_access_app(account_id, &request).await?;
+
+                    if format == "json" {
+                        output::print_json(&app);
+                        return Ok(());
+                    }
+
+                    output::success(&format!("Access 应用 {} 已创建", name));
+                    output::kv("应用 ID", app.id.as_deref().unwrap_or("-"));
+                    output::kv("AUD", app.aud.as_deref().unwrap_or("-"));
+                    output::tip(&format!(
+                        "应用在添加策略前会拒绝所有访问，运行 `cfai access policy create {} <名称> --email ...` 添加策略",
+                        name
+                    ));
+                }
+
+                AccessAppCommands::Delete { app, yes } => {
+                    let app = resolve_access_app(client, account_id, app).await?;
+                    let label = app.name.clone().unwrap_or_default();
+                    let app_id = app.id.unwrap_or_default();
+
+                    if !yes {
+                        let confirm = prompt::confirm(format!("确定要删除 Access 应用 {} 吗？", label.yellow()))?;
+                        if !confirm {
+                            output::info("已取消");
+                            return Ok(());
+                        }
+                    }
+
+                    client.delete_access_app(account_id, &app_id).await?;
+                    output::success(&format!("Access 应用 {} 已删除", label));
+                }
+            },
+
+            AccessCommands::Policy { command } => match command {
+                AccessPolicyCommands::List { app } => {
+                    let app = resolve_access_app(client, account_id, app).await?;
+                    let app_id = app.id.clone().unwrap_or_default();
+                    let policies = client.list_access_policies(account_id, &app_id).await?;
+
+                    if format == "json" {
+                        output::print_json(&policies);
+                        return Ok(());
+                    }
+
+                    output::title(&format!(
+                        "Access 策略 - {} (共 {} 条)",
+                        app.name.as_deref().unwrap_or(&app_id),
+                        policies.len()
+                    ));
+
+                    let mut table = output::create_table(vec!["ID", "名称", "决策", "优先级", "包含", "排除"]);
+                    for p in &policies {
+                        let rules = |r: &Option<Vec<serde_json::Value>>| {
+                            r.as_ref()
+                                .filter(|r| !r.is_empty())
+                                .map(|r| r.iter().map(describe_access_rule).collect::<Vec<_>>().join(", "))
+                                .unwrap_or_else(|| "-".into())
+                        };
+                        table.add_row(vec![
+                            p.id.as_deref().unwrap_or("-"),
+                            p.name.as_deref().unwrap_or("-"),
+                            p.decision.as_deref().unwrap_or("-"),
+                            &p.precedence.map(|v| v.to_string()).unwrap_or("-".into()),
+                            &rules(&p.include),
+                            &rules(&p.exclude),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+
+                AccessPolicyCommands::Create {
+                    app,
+                    name,
+                    decision,
+                    emails,
+                    email_domains,
+                    ips,
+                    everyone,
+                    precedence,
+                } => {
+                    let include = access_include_rules(emails, email_domains, ips, *everyone);
+                    if include.is_empty() {
+                        anyhow::bail!(ValidationError("请至少指定 --email、--email-domain、--ip 或 --everyone 之一".into()));
+                    }
+
+                    let app = resolve_access_app(client, account_id, app).await?;
+                    let app_id = app.id.unwrap_or_default();
+                    let request = CreateAccessPolicyRequest {
+                        name: name.clone(),
+                        decision: decision.clone(),
+                        include,
+                        precedence: *precedence,
+                    };
+                    let policy = client.create_access_policy(account_id, &app_id, &request).await?;
+
+                    if format == "json" {
+                        output::print_json(&policy);
+                        return Ok(());
+                    }
+
+                    output::success(&format!("Access 策略 {} 已创建 ({})", name, decision));
+                    output::kv("策略 ID", policy.id.as_deref().unwrap_or("-"));
+                }
+
+                AccessPolicyCommands::Delete { app, policy_id, yes } => {
+                    let app = resolve_access_app(client, account_id, app).await?;
+                    let app_id = app.id.unwrap_or_default();
+
+                    if !yes {
+                        let confirm = prompt::confirm(format!("确定要删除策略 {} 吗？", policy_id))?;
+                        if !confirm {
+                            output::info("已取消");
+                            return Ok(());
+                        }
+                    }
+
+                    client.delete_access_policy(account_id, &app_id, policy_id).await?;
+                    output::success("Access 策略已删除");
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// 按 ID、名称或域名查找 Access 应用
+async fn resolve_access_app(
+    client: &CfClient,
+    account_id: &str,
+    app: &str,
+) -> Result<AccessApplication> {
+    let apps = client.list_access_apps(account_id).await?;
+    apps.into_iter()
+        .find(|a| {
+            a.id.as_deref() == Some(app)
+                || a.name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(app))
+                || a.domain.as_deref().is_some_and(|d| d.eq_ignore_ascii_case(app))
+        })
+        .ok_or_else(|| anyhow::anyhow!("未找到 Access 应用: {}", app))
+}
@@ -0,0 +1,147 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::cli::output;
+use crate::config::settings::AppConfig;
+
+#[derive(Args, Debug)]
+pub struct AccountArgs {
+    #[command(subcommand)]
+    pub command: AccountCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccountCommands {
+    /// 列出当前凭据可访问的账户
+    #[command(alias = "ls")]
+    List,
+
+    /// 查看账户详情
+    Get {
+        /// Account ID (默认使用配置中的 Account ID)
+        account_id: Option<String>,
+    },
+
+    /// 列出账户成员及其角色
+    Members {
+        /// Account ID (默认使用配置中的 Account ID)
+        account_id: Option<String>,
+    },
+}
+
+impl AccountArgs {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        let configured = config.cloudflare.account_id.as_deref();
+
+        match &self.command {
+            AccountCommands::List => {
+                let accounts = client.list_accounts().await?;
+
+                if format == "json" {
+                    output::print_json(&accounts);
+                    return Ok(());
+                }
+
+                output::title(&format!("Cloudflare 账户 (共 {} 个)", accounts.len()));
+                if accounts.is_empty() {
+                    output::info("当前凭据无法访问任何账户，请检查 API Token 的 Account 权限");
+                    return Ok(());
+                }
+
+                let mut table = output::create_table(vec!["", "Account ID", "名称", "类型", "创建时间"]);
+                for a in &accounts {
+                    let current = configured == Some(a.id.as_str());
+                    table.add_row(vec![
+                        if current { "*".green().to_string() } else { String::new() },
+                        a.id.clone(),
+                        a.name.clone(),
+                        a.account_type.clone().unwrap_or("-".into()),
+                        a.created_on.clone().unwrap_or("-".into()),
+                    ]);
+                }
+                println!("{table}");
+                if configured.is_none() {
+                    output::tip("使用 `cfai config set cloudflare.account_id <ID>` 设置默认账户");
+                }
+            }
+
+            AccountCommands::Get { account_id } => {
+                let account_id = account_id_or_config(account_id.as_deref(), configured)?;
+                let account = client.get_account(account_id).await?;
+
+                if format == "json" {
+                    output::print_json(&account);
+                    return Ok(());
+                }
+
+                output::title(&format!("账户 - {}", account.name));
+                output::kv("Account ID", &account.id);
+                output::kv("类型", account.account_type.as_deref().unwrap_or("-"));
+                output::kv("创建时间", account.created_on.as_deref().unwrap_or("-"));
+                if let Some(settings) = &account.settings {
+                    if let Some(enforce) = settings.enforce_twofactor {
+                        output::kv_colored("强制两步验证", if enforce { "是" } else { "否" }, enforce);
+                    }
+                    output::kv(
+                        "滥用投诉邮箱",
+                        settings.abuse_contact_email.as_deref().unwrap_or("-"),
+                    );
+                }
+            }
+
+            AccountCommands::Members { account_id } => {
+                let account_id = account_id_or_config(account_id.as_deref(), configured)?;
+                let members = client.list_account_members(account_id).await?;
+
+                if format == "json" {
+                    output::print_json(&members);
+                    return Ok(());
+                }
+
+                output::title(&format!("账户成员 (共 {} 人)", members.len()));
+                if members.is_empty() {
+                    output::info("没有成员");
+                    return Ok(());
+                }
+
+                let mut table = output::create_table(vec!["邮箱", "姓名", "状态", "两步验证", "角色"]);
+                for m in &members {
+                    let user = m.user.as_ref();
+                    let name = user
+                        .map(|u| {
+                            [u.first_name.as_deref(), u.last_name.as_deref()]
+                                .into_iter()
+                                .flatten()
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        })
+                        .filter(|n| !n.is_empty())
+                        .unwrap_or("-".into());
+                    table.add_row(vec![
+                        m.email().to_string(),
+                        name,
+                        m.status.clone().unwrap_or("-".into()),
+                        match user.and_then(|u| u.two_factor_authentication_enabled) {
+                            Some(true) => "是".green().to_string(),
+                            Some(false) => "否".red().to_string(),
+                            None => "-".into(),
+                        },
+                        m.role_names(),
+                    ]);
+                }
+                println!("{table}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 使用参数中的 Account ID，未指定时回退到配置
+fn account_id_or_config<'a>(arg: Option<&'a str>, configured: Option<&'a str>) -> Result<&'a str> {
+    arg.or(configured).ok_or_else(|| {
+        anyhow::anyhow!("请指定 Account ID，或运行 `cfai account list` 查看可用账户后通过 `cfai config setup` 设置")
+    })
+}
@@ -1,16 +1,32 @@
-use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
 use colored::Colorize;
-use dialoguer::Confirm;
+use dialoguer::theme::ColorfulTheme;
 
-use crate::ai::analyzer::AiAnalyzer;
+use crate::ai::analyzer::{AiAnalyzer, AnalysisResult};
 use crate::ai::executor;
+use crate::ai::history::ChatSession;
+use crate::ai::report::{self, ReportSchedule};
+use crate::ai::safety::{self, LiveZoneState};
+use crate::ai::simulator;
+use crate::ai::usage::{self, UsageLedger};
 use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
 use crate::cli::output;
-use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::prompt;
+use crate::cli::commands::zone::{pick_domain, resolve_zone_id, zone_names};
 use crate::config::settings::AppConfig;
+use crate::notify;
 use crate::models::dns::DnsListParams;
 
+/// 未指定域名时 --dry-run 输出中代替 Zone ID 的占位符
+const ZONE_PLACEHOLDER: &str = "<zone_id>";
+
 #[derive(Args, Debug)]
 pub struct AiArgs {
     #[command(subcommand)]
@@ -32,6 +48,9 @@ pub enum AiCommands {
         /// 分析类型 (all/dns/security/performance)
         #[arg(short = 't', long, default_value = "all")]
         analysis_type: String,
+        /// 只打印每个建议操作将发出的 API 请求，不执行
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// 故障诊断 - 描述问题让 AI 帮你排查
@@ -41,6 +60,9 @@ pub enum AiCommands {
         /// 相关域名 (可选)
         #[arg(short, long)]
         domain: Option<String>,
+        /// 只打印每个建议操作将发出的 API 请求，不执行
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// 自动配置 - 描述需求让 AI 生成配置方案
@@ -53,12 +75,70 @@ pub enum AiCommands {
         /// 自动执行建议的操作 (危险!)
         #[arg(long)]
         auto_apply: bool,
+        /// 只打印每个建议操作将发出的 API 请求，不执行 (优先于 --auto-apply)
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// 多轮对话模式 (支持 /context、/exec 等指令，历史自动保存)
+    Chat {
+        /// 关联域名，启动时注入其实时配置
+        #[arg(short, long)]
+        domain: Option<String>,
+        /// 开始新对话，不加载上次的历史
+        #[arg(long)]
+        new: bool,
+    },
+
+    /// 生成域名健康报告 (配置 + 流量 + AI 建议)，可定时生成并推送到 Webhook
+    Report {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 报告输出文件 (Markdown)，不指定时打印到终端；路径中的 {date} 替换为生成日期
+        #[arg(short, long)]
+        output: Option<String>,
+        /// 按周期持续生成，前台运行直到 Ctrl+C
+        #[arg(long, value_parser = ["hourly", "daily", "weekly"])]
+        schedule: Option<String>,
+        /// 只生成一次后退出 (默认行为，适合 cron 调用)
+        #[arg(long, conflicts_with = "schedule")]
+        once: bool,
+        /// 推送报告的 Webhook 地址 (Slack / Discord，默认使用 ai.report_webhook)
+        #[arg(long)]
+        webhook: Option<String>,
+        /// 不推送 Webhook
+        #[arg(long, conflicts_with = "webhook")]
+        no_webhook: bool,
+    },
+
+    /// AI 用量与费用估算 (按日期、模型统计，含本月预算)
+    Usage {
+        /// 显示最近几天的明细
+        #[arg(short, long, default_value = "30")]
+        days: u32,
     },
 }
 
 impl AiArgs {
-    pub async fn execute(&self, client: &CfClient, config: &AppConfig, _format: &str) -> Result<()> {
-        let analyzer = AiAnalyzer::new(config)?;
+    /// 是否无需 Cloudflare 与 AI 认证即可执行
+    pub fn is_local(&self) -> bool {
+        matches!(self.command, AiCommands::Usage { .. })
+    }
+
+    /// 执行本地子命令 (用量报告)
+    pub async fn execute_local(&self, format: &str, profile: Option<&str>) -> Result<()> {
+        let config = AppConfig::load_effective(profile)?;
+        match &self.command {
+            AiCommands::Usage { days } => show_usage(&config, *days, format),
+            _ => unreachable!(),
+        }
+    }
+
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        if let AiCommands::Usage { days } = &self.command {
+            return show_usage(config, *days, format);
+        }
+        let mut analyzer = AiAnalyzer::new(config)?;
 
         match &self.command {
             AiCommands::Ask { question } => {
@@ -67,10 +147,11 @@ impl AiArgs {
                 spinner.set_message("🤖 AI 正在思考...");
                 spinner.enable_steady_tick(std::time::Duration::from_millis(100));
 
+                let streamed = attach_stream(&mut analyzer, &spinner);
                 let result = analyzer.ask(&question_str).await?;
 
                 spinner.finish_and_clear();
-                output::print_ai_result(&result.content, result.tokens_used);
+                print_result(&result, &streamed);
 
                 if let Some(actions) = &result.actions {
                     output::print_ai_actions(actions);
@@ -87,6 +168,7 @@ impl AiArgs {
             AiCommands::Analyze {
                 domain,
                 analysis_type,
+                dry_run,
             } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
 
@@ -95,133 +177,40 @@ impl AiArgs {
                 spinner.enable_steady_tick(std::time::Duration::from_millis(100));
 
                 // 收集配置信息
-                let mut context = String::new();
-
-                match analysis_type.as_str() {
-                    "dns" | "all" => {
-                        context.push_str("## DNS 记录\n");
-                        let dns_params = DnsListParams::default();
-                        if let Ok(resp) = client.list_dns_records(&zone_id, &dns_params).await {
-                            if let Some(records) = resp.result {
-                                for r in &records {
-                                    context.push_str(&format!(
-                                        "{} {} → {} (代理: {}, TTL: {})\n",
-                                        r.record_type,
-                                        r.name,
-                                        r.content,
-                                        r.proxied.map(|p| p.to_string()).unwrap_or("-".into()),
-                                        r.ttl.map(|t| t.to_string()).unwrap_or("-".into()),
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-
-                match analysis_type.as_str() {
-                    "security" | "all" => {
-                        context.push_str("\n## 安全配置\n");
-                        if let Ok(mode) = client.get_ssl_mode(&zone_id).await {
-                            context.push_str(&format!("SSL 模式: {}\n", mode));
-                        }
-                        if let Ok(https) = client.get_always_https(&zone_id).await {
-                            context.push_str(&format!("Always HTTPS: {}\n", https));
-                        }
-                        if let Ok(level) = client.get_security_level(&zone_id).await {
-                            context.push_str(&format!("安全级别: {}\n", level));
-                        }
-                    }
-                    _ => {}
-                }
-
-                match analysis_type.as_str() {
-                    "performance" | "all" => {
-                        context.push_str("\n## 性能配置\n");
-                        if let Ok(level) = client.get_cache_level(&zone_id).await {
-                            context.push_str(&format!("缓存级别: {}\n", level));
-                        }
-                        if let Ok(ttl) = client.get_browser_cache_ttl(&zone_id).await {
-                            context.push_str(&format!("浏览器缓存 TTL: {}s\n", ttl));
-                        }
-                    }
-                    _ => {}
-                }
+                let context = collect_zone_context(client, &zone_id, analysis_type).await;
 
                 spinner.set_message("🤖 AI 正在分析...");
+                let streamed = attach_stream(&mut analyzer, &spinner);
 
-                let result = match analysis_type.as_str() {
-                    "dns" => analyzer.analyze_dns(&context).await?,
-                    "security" => analyzer.analyze_security(&context).await?,
-                    "performance" => analyzer.analyze_performance(&context).await?,
-                    "all" => {
-                        let full_prompt = format!(
-                            "请对域名 {} 进行全面分析，包括 DNS、安全和性能方面:\n\n{}",
-                            domain, context
-                        );
-                        analyzer.ask(&full_prompt).await?
-                    }
-                    _ => anyhow::bail!("未知的分析类型: {}", analysis_type),
-                };
+                let result = analyze_zone(&analyzer, domain, &context, analysis_type).await?;
 
                 spinner.finish_and_clear();
-                output::print_ai_result(&result.content, result.tokens_used);
-
-                if let Some(actions) = &result.actions {
-                    output::print_ai_actions(actions);
-                    prompt_execute_actions(client, &zone_id, actions).await?;
+                print_result(&result, &streamed);
+
+                if let Some(mut actions) = result.actions {
+                    review_actions(client, &zone_id, &mut actions).await;
+                    output::print_ai_actions(&actions);
+                    if *dry_run {
+                        simulator::print_dry_run(&zone_id, &actions);
+                    } else {
+                        prompt_execute_actions(client, &zone_id, &actions).await?;
+                    }
                 }
             }
 
-            AiCommands::Troubleshoot { issue, domain } => {
-                let issue_str = issue.join(" ");
-                let resolved_zone_id = if let Some(d) = domain {
-                    Some(resolve_zone_id(client, d).await?)
-                } else {
-                    None
-                };
-
-                let spinner = indicatif::ProgressBar::new_spinner();
-                spinner.set_message("🔍 正在诊断...");
-                spinner.enable_steady_tick(std::time::Duration::from_millis(100));
-
-                let result = if let (Some(domain), Some(zone_id)) = (domain, &resolved_zone_id) {
-                    let mut context = format!("域名: {}\n", domain);
-
-                    if let Ok(zone) = client.get_zone(zone_id).await {
-                        context.push_str(&format!("状态: {}\n", zone.status));
-                    }
-                    if let Ok(mode) = client.get_ssl_mode(zone_id).await {
-                        context.push_str(&format!("SSL: {}\n", mode));
-                    }
-
-                    analyzer
-                        .ask_with_context(&format!("故障诊断请求: {}", issue_str), &context)
-                        .await?
-                } else {
-                    analyzer.troubleshoot(&issue_str).await?
-                };
-
-                spinner.finish_and_clear();
-                output::print_ai_result(&result.content, result.tokens_used);
-
-                if let Some(actions) = &result.actions {
-                    output::print_ai_actions(actions);
-                    if let Some(zone_id) = &resolved_zone_id {
-                        prompt_execute_actions(client, zone_id, actions).await?;
-                    } else if !actions.is_empty() {
-                        println!(
-                            "\n{}",
-                            "💡 指定 --domain 参数后可执行建议操作".dimmed()
-                        );
-                    }
-                }
+            AiCommands::Troubleshoot {
+                issue,
+                domain,
+                dry_run,
+            } => {
+                troubleshoot(client, &mut analyzer, &issue.join(" "), domain.as_deref(), None, *dry_run).await?;
             }
 
             AiCommands::AutoConfig {
                 requirement,
                 domain,
                 auto_apply,
+                dry_run,
             } => {
                 let req_str = requirement.join(" ");
 
@@ -229,21 +218,30 @@ impl AiArgs {
                 spinner.set_message("🤖 AI 正在生成配置方案...");
                 spinner.enable_steady_tick(std::time::Duration::from_millis(100));
 
+                let streamed = attach_stream(&mut analyzer, &spinner);
                 let result = analyzer.auto_config(&req_str).await?;
 
                 spinner.finish_and_clear();
-                output::print_ai_result(&result.content, result.tokens_used);
-
-                if let Some(actions) = &result.actions {
-                    output::print_ai_actions(actions);
+                print_result(&result, &streamed);
+
+                if let Some(mut actions) = result.actions {
+                    let zone_id = match domain {
+                        Some(d) if !actions.is_empty() => Some(resolve_zone_id(client, d).await?),
+                        _ => None,
+                    };
+                    if let Some(zone_id) = &zone_id {
+                        review_actions(client, zone_id, &mut actions).await;
+                    }
+                    output::print_ai_actions(&actions);
 
-                    if !actions.is_empty() {
-                        if let Some(domain) = domain {
-                            let zone_id = resolve_zone_id(client, domain).await?;
+                    if *dry_run {
+                        simulator::print_dry_run(zone_id.as_deref().unwrap_or(ZONE_PLACEHOLDER), &actions);
+                    } else if !actions.is_empty() {
+                        if let Some(zone_id) = &zone_id {
                             if *auto_apply {
-                                executor::execute_actions(client, &zone_id, actions).await?;
+                                executor::execute_actions(client, zone_id, &actions).await?;
                             } else {
-                                prompt_execute_actions(client, &zone_id, actions).await?;
+                                prompt_execute_actions(client, zone_id, &actions).await?;
                             }
                         } else {
                             println!(
@@ -254,12 +252,464 @@ impl AiArgs {
                     }
                 }
             }
+
+            AiCommands::Chat { domain, new } => {
+                run_chat(client, &mut analyzer, domain.as_deref(), *new).await?;
+            }
+
+            AiCommands::Report {
+                domain,
+                output,
+                schedule,
+                once: _,
+                webhook,
+                no_webhook,
+            } => {
+                let webhook = if *no_webhook {
+                    None
+                } else {
+                    webhook.as_deref().or(config.ai.report_webhook.as_deref())
+                };
+                let schedule = schedule.as_deref().map(ReportSchedule::parse).transpose()?;
+                run_report(client, config, &analyzer, domain, output.as_deref(), schedule, webhook).await?;
+            }
+
+            AiCommands::Usage { .. } => unreachable!(),
         }
 
         Ok(())
     }
 }
 
+/// 故障诊断: 指定域名时附带域名状态，`extra_context` 为调用方收集的其他信息 (如 probe 结果)
+pub(crate) async fn troubleshoot(
+    client: &CfClient,
+    analyzer: &mut AiAnalyzer,
+    issue: &str,
+    domain: Option<&str>,
+    extra_context: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    let resolved_zone_id = match domain {
+        Some(d) => Some(resolve_zone_id(client, d).await?),
+        None => None,
+    };
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message("🔍 正在诊断...");
+    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+    let streamed = attach_stream(analyzer, &spinner);
+
+    let mut context = String::new();
+    if let (Some(domain), Some(zone_id)) = (domain, &resolved_zone_id) {
+        context.push_str(&format!("域名: {}\n", domain));
+        if let Ok(zone) = client.get_zone(zone_id).await {
+            context.push_str(&format!("状态: {}\n", zone.status));
+        }
+        if let Ok(mode) = client.get_ssl_mode(zone_id).await {
+            context.push_str(&format!("SSL: {}\n", mode));
+        }
+    }
+    if let Some(extra) = extra_context {
+        context.push_str(extra);
+    }
+    let result = if context.is_empty() {
+        analyzer.troubleshoot(issue).await?
+    } else {
+        analyzer
+            .ask_with_context(&format!("故障诊断请求: {}", issue), &context)
+            .await?
+    };
+
+    spinner.finish_and_clear();
+    print_result(&result, &streamed);
+
+    if let Some(mut actions) = result.actions {
+        if let Some(zone_id) = &resolved_zone_id {
+            review_actions(client, zone_id, &mut actions).await;
+        }
+        output::print_ai_actions(&actions);
+        if dry_run {
+            simulator::print_dry_run(resolved_zone_id.as_deref().unwrap_or(ZONE_PLACEHOLDER), &actions);
+        } else if let Some(zone_id) = &resolved_zone_id {
+            prompt_execute_actions(client, zone_id, &actions).await?;
+        } else if !actions.is_empty() {
+            println!(
+                "\n{}",
+                "💡 指定 --domain 参数后可执行建议操作".dimmed()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 对照域名实时状态对 AI 建议的操作做安全审查
+async fn review_actions(
+    client: &CfClient,
+    zone_id: &str,
+    actions: &mut [crate::ai::analyzer::SuggestedAction],
+) {
+    if actions.is_empty() {
+        return;
+    }
+    match LiveZoneState::fetch(client, zone_id).await {
+        Ok(state) => safety::review_actions(actions, &state),
+        Err(e) => output::warn(&format!("安全审查失败，请自行核对操作风险: {:#}", e)),
+    }
+}
+
+/// 对话模式帮助信息
+fn print_chat_help() {
+    println!("  {}  注入域名实时配置作为上下文 (省略域名时从列表中选择)", "/context [域名]".cyan());
+    println!("  {}            执行 AI 最近一次建议的操作", "/exec".cyan());
+    println!("  {}         查看对话历史", "/history".cyan());
+    println!("  {}           清空对话历史", "/clear".cyan());
+    println!("  {}            退出对话 (也可用 /quit 或 Ctrl+C)", "/exit".cyan());
+}
+
+/// 多轮对话 REPL
+async fn run_chat(
+    client: &CfClient,
+    analyzer: &mut AiAnalyzer,
+    domain: Option<&str>,
+    fresh: bool,
+) -> Result<()> {
+    prompt::require_interactive("请使用 `cfai ai ask <问题>` 单次提问")?;
+    let mut session = if fresh {
+        ChatSession::default()
+    } else {
+        ChatSession::load().unwrap_or_else(|e| {
+            output::warn(&format!("{:#}，已开始新对话", e));
+            ChatSession::default()
+        })
+    };
+    analyzer.set_history(std::mem::take(&mut session.messages));
+
+    output::title("🤖 AI 对话模式");
+    print_chat_help();
+    if !analyzer.history().is_empty() {
+        output::info(&format!(
+            "已恢复上次对话 ({} 条消息)，使用 /clear 或 --new 开始新对话",
+            analyzer.history().len()
+        ));
+    }
+
+    // (域名, Zone ID)
+    let mut zone: Option<(String, String)> = None;
+    if let Some(d) = domain {
+        zone = Some(inject_zone_context(client, analyzer, d).await?);
+    } else if let Some(d) = session.domain.clone() {
+        // 恢复的历史中已包含该域名的上下文，只需重新解析 Zone ID
+        if let Ok(id) = resolve_zone_id(client, &d).await {
+            output::info(&format!("当前域名: {}", d));
+            zone = Some((d, id));
+        }
+    }
+
+    let mut last_actions: Vec<crate::ai::analyzer::SuggestedAction> = Vec::new();
+
+    loop {
+        println!();
+        let input: String = match dialoguer::Input::new()
+            .with_prompt("你")
+            .allow_empty(true)
+            .interact_text()
+        {
+            Ok(s) => s,
+            Err(_) => break,
+        };
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        let (cmd, arg) = input.split_once(' ').unwrap_or((input, ""));
+        match cmd {
+            "/exit" | "/quit" => break,
+            "/help" => print_chat_help(),
+            "/clear" => {
+                analyzer.clear_history();
+                last_actions.clear();
+                zone = None;
+                output::success("对话历史已清空");
+            }
+            "/history" => {
+                for msg in analyzer.history() {
+                    let preview: String = msg.content.chars().take(80).collect();
+                    let role = match msg.role.as_str() {
+                        "user" => "你".green(),
+                        "assistant" => "AI".cyan(),
+                        _ => "上下文".dimmed(),
+                    };
+                    println!("  {} {}", format!("[{}]", role).bold(), preview.replace('\n', " "));
+                }
+                output::info(&format!("共 {} 条消息", analyzer.history().len()));
+            }
+            "/context" => {
+                let domain = match arg.trim() {
+                    "" => pick_context_domain(client).await,
+                    d => Some(d.to_string()),
+                };
+                if let Some(domain) = domain {
+                    match inject_zone_context(client, analyzer, &domain).await {
+                        Ok(z) => zone = Some(z),
+                        Err(e) => output::error(&format!("{:#}", e)),
+                    }
+                }
+            }
+            "/exec" => match &zone {
+                _ if last_actions.is_empty() => output::warn("没有可执行的建议操作"),
+                None => output::warn("请先使用 /context <域名> 指定要操作的域名"),
+                Some((_, zone_id)) => {
+                    output::print_ai_actions(&last_actions);
+                    prompt_execute_actions(client, zone_id, &last_actions).await?;
+                }
+            },
+            _ if cmd.starts_with('/') => {
+                output::warn(&format!("未知指令: {}，输入 /help 查看可用指令", cmd));
+            }
+            _ => {
+                let spinner = indicatif::ProgressBar::new_spinner();
+                spinner.set_message("🤖 AI 正在思考...");
+                spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+                let streamed = attach_stream(analyzer, &spinner);
+                let result = analyzer.chat_turn(input).await;
+                spinner.finish_and_clear();
+
+                match result {
+                    Ok(result) => {
+                        print_result(&result, &streamed);
+                        last_actions = result.actions.unwrap_or_default();
+                        if let Some((_, zone_id)) = &zone {
+                            review_actions(client, zone_id, &mut last_actions).await;
+                        }
+                        if !last_actions.is_empty() {
+                            output::print_ai_actions(&last_actions);
+                            output::tip("输入 /exec 执行以上建议操作");
+                        }
+                    }
+                    Err(e) => output::error(&format!("{:#}", e)),
+                }
+            }
+        }
+
+        session.domain = zone.as_ref().map(|(d, _)| d.clone());
+        session.messages = analyzer.history().to_vec();
+        if let Err(e) = session.save() {
+            output::warn(&format!("{:#}", e));
+        }
+    }
+
+    output::info("对话已保存，下次运行 `cfai ai chat` 可继续");
+    Ok(())
+}
+
+/// `/context` 未指定域名时从账户的域名中选择
+async fn pick_context_domain(client: &CfClient) -> Option<String> {
+    let domains = zone_names(client).await.unwrap_or_else(|e| {
+        output::warn(&format!("获取域名列表失败: {:#}", e));
+        Vec::new()
+    });
+    match pick_domain(&ColorfulTheme::default(), &domains) {
+        Ok(domain) => domain,
+        Err(e) => {
+            output::error(&format!("{:#}", e));
+            None
+        }
+    }
+}
+
+/// 收集域名实时配置并注入对话上下文
+async fn inject_zone_context(
+    client: &CfClient,
+    analyzer: &mut AiAnalyzer,
+    domain: &str,
+) -> Result<(String, String)> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    output::loading(&format!("正在收集 {} 的配置信息", domain));
+    let context = collect_zone_context(client, &zone_id, "all").await;
+    analyzer.add_context(&format!("用户当前操作的域名: {}\n{}", domain, context));
+    output::success(&format!("已注入 {} 的实时配置", domain));
+    Ok((domain.to_string(), zone_id))
+}
+
+/// 按分析类型 (dns/security/performance/all) 调用 AI 分析域名配置
+pub(crate) async fn analyze_zone(
+    analyzer: &AiAnalyzer,
+    domain: &str,
+    context: &str,
+    analysis_type: &str,
+) -> Result<AnalysisResult> {
+    match analysis_type {
+        "dns" => analyzer.analyze_dns(context).await,
+        "security" => analyzer.analyze_security(context).await,
+        "performance" => analyzer.analyze_performance(context).await,
+        "all" => {
+            let full_prompt = format!(
+                "请对域名 {} 进行全面分析，包括 DNS、安全和性能方面:\n\n{}",
+                domain, context
+            );
+            analyzer.ask(&full_prompt).await
+        }
+        _ => anyhow::bail!(ValidationError(format!("未知的分析类型: {}", analysis_type))),
+    }
+}
+
+/// 收集域名配置信息，作为 AI 分析的上下文
+pub(crate) async fn collect_zone_context(client: &CfClient, zone_id: &str, analysis_type: &str) -> String {
+    let mut context = String::new();
+
+    match analysis_type {
+        "dns" | "all" => {
+            context.push_str("## DNS 记录\n");
+            let dns_params = DnsListParams::default();
+            if let Ok(resp) = client.list_dns_records(zone_id, &dns_params).await {
+                if let Some(records) = resp.result {
+                    for r in &records {
+                        context.push_str(&format!(
+                            "{} {} → {} (代理: {}, TTL: {})\n",
+                            r.record_type,
+                            r.name,
+                            r.content,
+                            r.proxied.map(|p| p.to_string()).unwrap_or("-".into()),
+                            r.ttl.map(|t| t.to_string()).unwrap_or("-".into()),
+                        ));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    match analysis_type {
+        "security" | "all" => {
+            context.push_str("\n## 安全配置\n");
+            if let Ok(mode) = client.get_ssl_mode(zone_id).await {
+                context.push_str(&format!("SSL 模式: {}\n", mode));
+            }
+            if let Ok(https) = client.get_always_https(zone_id).await {
+                context.push_str(&format!("Always HTTPS: {}\n", https));
+            }
+            if let Ok(level) = client.get_security_level(zone_id).await {
+                context.push_str(&format!("安全级别: {}\n", level));
+            }
+            for (name, setting_id) in [
+                ("最小 TLS 版本", "min_tls_version"),
+                ("TLS 1.3", "tls_1_3"),
+                ("0-RTT", "0rtt"),
+                ("加密套件 (空表示默认)", "ciphers"),
+            ] {
+                if let Ok(setting) = client.get_zone_setting(zone_id, setting_id).await {
+                    context.push_str(&format!("{} (setting_id: {}): {}\n", name, setting_id, setting.value));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    match analysis_type {
+        "performance" | "all" => {
+            context.push_str("\n## 性能配置\n");
+            if let Ok(level) = client.get_cache_level(zone_id).await {
+                context.push_str(&format!("缓存级别: {}\n", level));
+            }
+            if let Ok(ttl) = client.get_browser_cache_ttl(zone_id).await {
+                context.push_str(&format!("浏览器缓存 TTL: {}s\n", ttl));
+            }
+            // 注明 setting_id，便于 AI 通过 setting_update 直接给出可执行的建议
+            let images = client.get_image_settings(zone_id).await;
+            for (name, setting_id, value) in [
+                ("Polish 图片压缩", "polish", &images.polish),
+                ("WebP 转换", "webp", &images.webp),
+                ("Mirage 移动端图片优化", "mirage", &images.mirage),
+            ] {
+                if let Some(value) = value {
+                    context.push_str(&format!("{} (setting_id: {}): {}\n", name, setting_id, value));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    context
+}
+
+/// 生成健康报告；指定周期时循环生成直到 Ctrl+C，单次失败只记录错误不退出
+async fn run_report(
+    client: &CfClient,
+    config: &AppConfig,
+    analyzer: &AiAnalyzer,
+    domain: &str,
+    output_path: Option<&str>,
+    schedule: Option<ReportSchedule>,
+    webhook: Option<&str>,
+) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let Some(schedule) = schedule else {
+        return generate_report(client, config, analyzer, &zone_id, output_path, webhook).await;
+    };
+
+    output::info(&format!(
+        "定时报告已启动，每 {} 小时生成一次，按 Ctrl+C 停止",
+        schedule.interval().as_secs() / 3600
+    ));
+    loop {
+        if let Err(e) = generate_report(client, config, analyzer, &zone_id, output_path, webhook).await {
+            output::error(&format!("生成报告失败: {:#}", e));
+        }
+        let next = chrono::Local::now() + schedule.interval();
+        output::info(&format!("下次报告时间: {}", next.format("%Y-%m-%d %H:%M:%S")));
+        tokio::select! {
+            _ = tokio::time::sleep(schedule.interval()) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    Ok(())
+}
+
+/// 生成一次报告，写入文件 (或打印) 并推送到 Webhook
+async fn generate_report(
+    client: &CfClient,
+    config: &AppConfig,
+    analyzer: &AiAnalyzer,
+    zone_id: &str,
+    output_path: Option<&str>,
+    webhook: Option<&str>,
+) -> Result<()> {
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message("📊 正在收集域名配置与流量数据...");
+    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let built = async {
+        let zone = client.get_zone(zone_id).await?;
+        let context = collect_zone_context(client, zone_id, "all").await;
+        spinner.set_message("🤖 AI 正在生成健康报告...");
+        report::build_report(client, analyzer, &zone, &context).await
+    }
+    .await;
+    spinner.finish_and_clear();
+    let markdown = built?;
+
+    match output_path {
+        Some(path) => {
+            let path = path.replace("{date}", &chrono::Local::now().format("%Y-%m-%d").to_string());
+            if let Some(parent) = std::path::Path::new(&path).parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("创建目录失败: {}", parent.display()))?;
+            }
+            std::fs::write(&path, &markdown).with_context(|| format!("写入报告失败: {}", path))?;
+            output::success(&format!("报告已保存: {}", path));
+        }
+        None => println!("{}", markdown),
+    }
+
+    if let Some(url) = webhook {
+        notify::post_webhook(config, url, &markdown).await?;
+        output::success("报告已推送到 Webhook");
+    }
+    Ok(())
+}
+
 /// 交互式提示用户是否执行 AI 建议的操作
 async fn prompt_execute_actions(
     client: &CfClient,
@@ -270,11 +720,12 @@ async fn prompt_execute_actions(
         return Ok(());
     }
 
+    if !prompt::is_interactive() {
+        output::info("非交互模式，未执行建议操作 (--yes 不适用于 AI 建议的操作)");
+        return Ok(());
+    }
     println!();
-    let confirm = Confirm::new()
-        .with_prompt("是否执行以上建议操作?")
-        .default(false)
-        .interact()?;
+    let confirm = prompt::confirm_ai_action("是否执行以上建议操作?", false)?;
 
     if confirm {
         executor::execute_actions(client, zone_id, actions).await?;
@@ -282,3 +733,103 @@ async fn prompt_execute_actions(
 
     Ok(())
 }
+
+/// 开启终端流式输出：首段文本到达时清除 spinner 并打印标题，之后逐段输出
+///
+/// 返回的标记表示是否已经以流式方式输出了内容。
+fn attach_stream(analyzer: &mut AiAnalyzer, spinner: &indicatif::ProgressBar) -> Arc<AtomicBool> {
+    let streamed = Arc::new(AtomicBool::new(false));
+    let flag = streamed.clone();
+    let spinner = spinner.clone();
+    analyzer.on_token(move |text| {
+        if !flag.swap(true, Ordering::SeqCst) {
+            spinner.finish_and_clear();
+            output::print_ai_result_header();
+        }
+        print!("{}", text);
+        let _ = std::io::stdout().flush();
+    });
+    streamed
+}
+
+/// 打印最近几天的 AI 用量明细与本月费用
+fn show_usage(config: &AppConfig, days: u32, format: &str) -> Result<()> {
+    let ledger = UsageLedger::load()?;
+    let since = (chrono::Local::now() - chrono::Duration::days(days.saturating_sub(1) as i64))
+        .format("%Y-%m-%d")
+        .to_string();
+    let recent: BTreeMap<_, _> = ledger.days.range(since..).collect();
+    let month = usage::current_month();
+    let month_total = ledger.month_total(&month);
+    let budget = config.ai.monthly_budget;
+
+    if format == "json" {
+        output::print_json(&serde_json::json!({
+            "days": recent,
+            "month": month,
+            "month_total": month_total,
+            "monthly_budget": budget,
+        }));
+        return Ok(());
+    }
+
+    output::title(&format!("AI 用量 (最近 {} 天)", days));
+    if recent.is_empty() {
+        output::info("暂无 AI 用量记录");
+    } else {
+        let mut table = output::create_table(vec![
+            "日期", "模型", "请求数", "输入 Token", "输出 Token", "总 Token", "估算费用",
+        ]);
+        for (date, models) in &recent {
+            for (model, entry) in *models {
+                table.add_row(vec![
+                    date.to_string(),
+                    model.clone(),
+                    entry.requests.to_string(),
+                    entry.prompt_tokens.to_string(),
+                    entry.completion_tokens.to_string(),
+                    entry.total_tokens.to_string(),
+                    format!("${:.4}", entry.cost),
+                ]);
+            }
+        }
+        println!("{table}");
+    }
+
+    println!();
+    output::kv(
+        &format!("本月 ({})", month),
+        &format!(
+            "{} 次请求，{} Token，约 ${:.2}",
+            month_total.requests, month_total.total_tokens, month_total.cost
+        ),
+    );
+    if let Some(budget) = budget {
+        let percent = if budget > 0.0 { month_total.cost / budget * 100.0 } else { 100.0 };
+        output::kv_colored(
+            "月度预算",
+            &format!(
+                "${:.2} / ${:.2} ({:.0}%，超出时{})",
+                month_total.cost,
+                budget,
+                percent,
+                if config.ai.budget_action.as_deref() == Some("block") { "阻止请求" } else { "仅提示" }
+            ),
+            month_total.cost < budget,
+        );
+    }
+    if config.ai.input_price_per_1k.is_none() && config.ai.output_price_per_1k.is_none() {
+        output::tip("未配置单价，费用按 0 计算: cfai config set ai.input_price_per_1k 0.0025");
+    }
+    Ok(())
+}
+
+/// 打印 AI 结果 (已流式输出的只补充结尾)
+fn print_result(result: &AnalysisResult, streamed: &AtomicBool) {
+    if streamed.load(Ordering::SeqCst) {
+        println!();
+        output::print_ai_result_footer(result.tokens_used);
+    } else {
+        output::print_ai_result(&result.content, result.tokens_used);
+    }
+}
@@ -1,10 +1,20 @@
-use anyhow::Result;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
+use colored::Colorize;
 
 use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
 use crate::cli::output;
 use crate::cli::commands::zone::resolve_zone_id;
-use crate::models::analytics::AnalyticsParams;
+use crate::config::settings::AppConfig;
+use crate::models::analytics::{
+    parse_time, top_breakdown, AnalyticsGranularity, AnalyticsParams, AnalyticsTimeseries, AnalyticsTotals,
+    LiveAnalytics,
+};
+use crate::notify::{self, Level, Notification};
 
 #[derive(Args, Debug)]
 pub struct AnalyticsArgs {
@@ -24,17 +34,115 @@ pub enum AnalyticsCommands {
     Detail {
         /// 域名或 Zone ID
         domain: String,
-        /// 起始时间 (如 -1440 表示 24 小时前, 或 ISO8601 格式)
-        #[arg(short, long, default_value = "-1440")]
+        /// 起始时间 (ISO8601、YYYY-MM-DD，或相对时间如 -1440 分钟 / -24h / -7d)
+        #[arg(short, long, default_value = "-1440", allow_hyphen_values = true)]
+        since: String,
+        /// 结束时间 (格式同 --since，0 表示现在)
+        #[arg(short, long, default_value = "0", allow_hyphen_values = true)]
+        until: String,
+        /// 时间序列粒度 (1h/1d)，默认 3 天以内按小时，否则按天
+        #[arg(short, long, value_parser = ["1h", "1d"])]
+        granularity: Option<String>,
+        /// 导出格式，将时间序列写入文件
+        #[arg(short, long, value_parser = ["csv"])]
+        output: Option<String>,
+        /// 导出文件路径 (默认 <域名>-analytics-<时间>.csv)
+        #[arg(long, requires = "output")]
+        file: Option<PathBuf>,
+        /// 状态码、国家/地区、内容类型分布各显示前 N 项
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// 查看 Bot 评分分布 (需要 Bot Management 或 Super Bot Fight Mode)
+    Bots {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 起始时间 (如 1h、30m、7d，或 ISO8601)
+        #[arg(short, long, default_value = "24h", allow_hyphen_values = true)]
+        since: String,
+        /// 结束时间 (默认现在)
+        #[arg(short, long, default_value = "0", allow_hyphen_values = true)]
+        until: String,
+    },
+
+    /// 查看 DNS 查询统计 (热门查询名称、记录类型、响应码与 NXDOMAIN 比例)
+    Dns {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 起始时间 (如 1h、30m、7d，或 ISO8601)
+        #[arg(short, long, default_value = "24h", allow_hyphen_values = true)]
         since: String,
-        /// 结束时间
-        #[arg(short, long, default_value = "0")]
+        /// 结束时间 (默认现在)
+        #[arg(short, long, default_value = "0", allow_hyphen_values = true)]
         until: String,
+        /// 每项统计显示前 N 项
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// 执行自定义 GraphQL 分析查询
+    ///
+    /// 查询中引用 `$zoneTag` / `$accountTag` 且未通过 --var 指定时，
+    /// 自动使用 --zone 指定的域名和配置中的 Account ID。
+    Query {
+        /// GraphQL 查询文件 ("-" 表示从标准输入读取)
+        #[arg(short, long, required_unless_present = "query", conflicts_with = "query")]
+        file: Option<PathBuf>,
+        /// 直接传入查询语句
+        #[arg(long)]
+        query: Option<String>,
+        /// 查询变量 (可重复，值按 JSON 解析，否则作为字符串)，如 --var limit=10
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
+        /// 域名或 Zone ID，作为 $zoneTag 变量
+        #[arg(short, long)]
+        zone: Option<String>,
     },
+
+    /// 实时监控流量 (终端仪表盘，Ctrl+C 退出)
+    Watch {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 刷新间隔 (秒)
+        #[arg(short, long, default_value = "10")]
+        interval: u64,
+        /// 运行时长 (秒)，不指定则持续运行
+        #[arg(short, long)]
+        duration: Option<u64>,
+        /// 统计窗口 (分钟)
+        #[arg(short, long, default_value = "5")]
+        window: i64,
+        /// 窗口内威胁数达到该值时发送告警通知 (需配置通知渠道)
+        #[arg(long)]
+        alert_threats: Option<u64>,
+        /// 每秒请求数达到该值时发送告警通知
+        #[arg(long)]
+        alert_rps: Option<f64>,
+    },
+}
+
+/// NXDOMAIN 比例达到该值时提示检查
+const DNS_NXDOMAIN_WARN_PERCENT: f64 = 20.0;
+
+/// 实时监控的参数
+struct WatchOptions {
+    interval: u64,
+    duration: Option<u64>,
+    window: i64,
+    alert_threats: Option<u64>,
+    alert_rps: Option<f64>,
+}
+
+impl WatchOptions {
+    /// 超过告警阈值时返回原因
+    fn exceeded(&self, live: &LiveAnalytics) -> Option<String> {
+        live.exceeds(self.alert_threats, self.alert_rps)
+    }
 }
 
 impl AnalyticsArgs {
-    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
         match &self.command {
             AnalyticsCommands::Overview { domain } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
@@ -139,21 +247,64 @@ impl AnalyticsArgs {
                 domain,
                 since,
                 until,
+                granularity,
+                output: export,
+                file,
+                top,
             } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
                 let params = AnalyticsParams {
                     since: Some(since.clone()),
                     until: Some(until.clone()),
                     continuous: Some(true),
+                    granularity: granularity
+                        .as_deref()
+                        .map(AnalyticsGranularity::parse)
+                        .transpose()?,
                 };
+                let (since_at, until_at, granularity) = params.resolve()?;
                 let dashboard = client.get_analytics(&zone_id, &params).await?;
+                let series = dashboard.timeseries.as_deref().unwrap_or_default();
+
+                if export.as_deref() == Some("csv") {
+                    let path = file.clone().unwrap_or_else(|| {
+                        PathBuf::from(format!(
+                            "{}-analytics-{}.csv",
+                            domain,
+                            chrono::Local::now().format("%Y%m%d-%H%M%S")
+                        ))
+                    });
+                    std::fs::write(&path, timeseries_csv(series))
+                        .with_context(|| format!("写入 CSV 文件失败: {}", path.display()))?;
+
+                    if format == "json" {
+                        output::print_json(&serde_json::json!({
+                            "file": path,
+                            "rows": series.len(),
+                        }));
+                    } else {
+                        output::success(&format!(
+                            "已导出 {} 行时间序列 ({}) 到 {}",
+                            series.len(),
+                            granularity.label(),
+                            path.display()
+                        ));
+                    }
+                    return Ok(());
+                }
 
                 if format == "json" {
                     output::print_json(&dashboard);
                     return Ok(());
                 }
 
-                output::title(&format!("详细分析 - {} ({} ~ {})", domain, since, until));
+                output::title(&format!(
+                    "详细分析 - {} ({} ~ {}, 粒度 {})",
+                    domain,
+                    since_at.format("%Y-%m-%d %H:%M"),
+                    until_at.format("%Y-%m-%d %H:%M"),
+                    granularity.label()
+                ));
                 // 打印与 Overview 相同的摘要
                 if let Some(totals) = &dashboard.totals {
                     if let Some(requests) = &totals.requests {
@@ -168,12 +319,564 @@ impl AnalyticsArgs {
                             &output::format_bytes(bandwidth.all.unwrap_or(0)),
                         );
                     }
+                    if let Some(threats) = &totals.threats {
+                        output::kv(
+                            "威胁总数",
+                            &output::format_number(threats.all.unwrap_or(0)),
+                        );
+                    }
+                }
+
+                if series.is_empty() {
+                    output::info("该时间范围内没有数据");
+                    return Ok(());
+                }
+
+                println!();
+                let mut table = output::create_table(vec!["时间", "请求数", "缓存命中率", "带宽", "威胁"]);
+                for ts in series {
+                    let requests = ts.requests.as_ref();
+                    let all = requests.and_then(|r| r.all).unwrap_or(0);
+                    let cached = requests.and_then(|r| r.cached).unwrap_or(0);
+                    table.add_row(vec![
+                        ts.since.clone().unwrap_or("-".into()),
+                        output::format_number(all),
+                        format!("{:.1}%", cached as f64 / all.max(1) as f64 * 100.0),
+                        output::format_bytes(ts.bandwidth.as_ref().and_then(|b| b.all).unwrap_or(0)),
+                        ts.threats.as_ref().and_then(|t| t.all).unwrap_or(0).to_string(),
+                    ]);
+                }
+                println!("{table}");
+
+                if let Some(totals) = &dashboard.totals {
+                    print_breakdowns(totals, *top);
+                }
+
+                output::info("💡 提示: 使用 --output csv 导出时间序列，或 --format json 获取完整数据");
+            }
+
+            AnalyticsCommands::Bots { domain, since, until } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let now = chrono::Utc::now();
+                let since = parse_time(since, now)?;
+                let until = parse_time(until, now)?;
+                let bots = client.get_bot_score_analytics(&zone_id, since, until).await?;
+
+                if format == "json" {
+                    output::print_json(&bots);
+                    return Ok(());
+                }
+
+                output::title(&format!(
+                    "Bot 评分分布 - {} ({} 起)",
+                    domain,
+                    since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                ));
+
+                if bots.total == 0 {
+                    output::info("该时间范围内没有请求数据");
+                    return Ok(());
+                }
+
+                let percent = |n: u64| format!("{:.1}%", n as f64 / bots.total as f64 * 100.0);
+                let mut table = output::create_table(vec!["类别", "Bot 评分", "请求数", "占比"]);
+                for (label, range, count) in [
+                    ("确定为自动化", "1", bots.automated),
+                    ("可能为自动化", "2-29", bots.likely_automated),
+                    ("可能为人类", "30-99", bots.likely_human),
+                    ("未评分", "-", bots.unscored),
+                ] {
+                    table.add_row(vec![
+                        label.to_string(),
+                        range.to_string(),
+                        output::format_number(count),
+                        percent(count),
+                    ]);
+                }
+                println!("{table}");
+
+                let mut table = output::create_table(vec!["评分来源", "请求数", "占比"]);
+                for (source, count) in &bots.by_source {
+                    table.add_row(vec![source.clone(), output::format_number(*count), percent(*count)]);
+                }
+                println!("{table}");
+
+                if bots.unscored == bots.total {
+                    output::tip("所有请求均未评分，Bot 评分需要 Bot Management (企业版) 或 Super Bot Fight Mode");
+                }
+            }
+
+            AnalyticsCommands::Dns { domain, since, until, top } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let now = chrono::Utc::now();
+                let since = parse_time(since, now)?;
+                let until = parse_time(until, now)?;
+                let dns = client.get_dns_analytics(&zone_id, since, until, *top).await?;
+
+                if format == "json" {
+                    output::print_json(&dns);
+                    return Ok(());
+                }
+
+                output::title(&format!(
+                    "DNS 查询统计 - {} ({} 起)",
+                    domain,
+                    since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                ));
+                if dns.total == 0 {
+                    output::info("该时间范围内没有 DNS 查询数据");
+                    return Ok(());
+                }
+
+                output::kv("总查询数", &output::format_number(dns.total));
+                let nxdomain = dns.nxdomain_rate();
+                output::kv_colored("NXDOMAIN 比例", &format!("{:.1}%", nxdomain), nxdomain < DNS_NXDOMAIN_WARN_PERCENT);
+
+                let percent = |n: u64| format!("{:.1}%", n as f64 / dns.total as f64 * 100.0);
+                for (header, rows) in [
+                    ("查询名称", &dns.by_name),
+                    ("记录类型", &dns.by_type),
+                    ("响应码", &dns.by_response_code),
+                ] {
+                    println!();
+                    let mut table = output::create_table(vec![header, "查询数", "占比"]);
+                    for (value, count) in rows {
+                        table.add_row(vec![value.clone(), output::format_number(*count), percent(*count)]);
+                    }
+                    println!("{table}");
                 }
 
-                output::info("💡 提示: 使用 --format json 获取完整的时间序列数据");
+                if nxdomain >= DNS_NXDOMAIN_WARN_PERCENT {
+                    output::tip("NXDOMAIN 比例偏高，可能是记录缺失、客户端配置错误或随机子域名攻击");
+                }
+            }
+
+            AnalyticsCommands::Query { file, query, vars, zone } => {
+                let query = match (query, file) {
+                    (Some(query), _) => query.clone(),
+                    (None, Some(path)) if path == std::path::Path::new("-") => {
+                        let mut text = String::new();
+                        std::io::Read::read_to_string(&mut std::io::stdin(), &mut text).context("读取标准输入失败")?;
+                        text
+                    }
+                    (None, Some(path)) => std::fs::read_to_string(path)
+                        .with_context(|| format!("读取查询文件失败: {}", path.display()))?,
+                    (None, None) => anyhow::bail!(ValidationError("请指定 --file 或 --query".into())),
+                };
+                if query.trim().is_empty() {
+                    anyhow::bail!(ValidationError("查询语句为空".into()));
+                }
+
+                let mut variables = parse_query_vars(vars)?;
+                if query.contains("$zoneTag") && !variables.contains_key("zoneTag") {
+                    if let Some(zone) = zone {
+                        variables.insert("zoneTag".into(), resolve_zone_id(client, zone).await?.into());
+                    }
+                }
+                if query.contains("$accountTag") && !variables.contains_key("accountTag") {
+                    if let Some(account_id) = &config.cloudflare.account_id {
+                        variables.insert("accountTag".into(), account_id.clone().into());
+                    }
+                }
+
+                let resp = client.graphql_query(&query, serde_json::Value::Object(variables)).await?;
+                let data = resp.get("data").cloned().unwrap_or(serde_json::Value::Null);
+                if format == "json" {
+                    output::print_json(&data);
+                    return Ok(());
+                }
+                match first_table(&data) {
+                    Some((headers, rows)) => {
+                        let mut table = output::create_table(headers.iter().map(String::as_str).collect());
+                        for row in &rows {
+                            table.add_row(row.clone());
+                        }
+                        println!("{table}");
+                        output::info(&format!("共 {} 行，使用 --format json 查看完整结果", rows.len()));
+                    }
+                    None => println!("{}", serde_json::to_string_pretty(&data)?),
+                }
+            }
+
+            AnalyticsCommands::Watch {
+                domain,
+                interval,
+                duration,
+                window,
+                alert_threats,
+                alert_rps,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let options = WatchOptions {
+                    interval: *interval,
+                    duration: *duration,
+                    window: *window,
+                    alert_threats: *alert_threats,
+                    alert_rps: *alert_rps,
+                };
+                if (options.alert_threats.is_some() || options.alert_rps.is_some())
+                    && config.notify.channels().is_empty()
+                {
+                    output::warn("未配置通知渠道，告警只显示在终端 (运行 `cfai notify test` 检查配置)");
+                }
+                watch(client, config, &zone_id, domain, &options, format).await?;
             }
         }
 
         Ok(())
     }
 }
+
+/// 轮询实时流量并在终端原地刷新仪表盘
+async fn watch(
+    client: &CfClient,
+    config: &AppConfig,
+    zone_id: &str,
+    domain: &str,
+    options: &WatchOptions,
+    format: &str,
+) -> Result<()> {
+    let WatchOptions {
+        interval,
+        duration,
+        window,
+        ..
+    } = *options;
+    if interval == 0 {
+        anyhow::bail!(ValidationError("刷新间隔必须大于 0 秒".into()));
+    }
+    if window <= 0 {
+        anyhow::bail!(ValidationError("统计窗口必须大于 0 分钟".into()));
+    }
+
+    let term = console::Term::stdout();
+    let redraw = format != "json" && term.is_term();
+    let deadline = duration.map(|secs| Instant::now() + Duration::from_secs(secs));
+    let mut last_lines = 0;
+    let mut previous: Option<LiveAnalytics> = None;
+    // 处于告警状态时不重复通知，恢复后再发送恢复通知
+    let mut alerting = false;
+
+    if redraw {
+        term.hide_cursor()?;
+    }
+
+    loop {
+        let snapshot = tokio::select! {
+            result = client.get_live_analytics(zone_id, window) => result,
+            _ = tokio::signal::ctrl_c() => break,
+        };
+
+        if format == "json" {
+            // 每次轮询输出一行 JSON，便于管道处理
+            match &snapshot {
+                Ok(live) => println!("{}", serde_json::to_string(live)?),
+                Err(e) => eprintln!("{:#}", e),
+            }
+        } else {
+            let frame = render_watch_frame(domain, interval, window, &snapshot, previous.as_ref());
+            if redraw && last_lines > 0 {
+                term.clear_last_lines(last_lines)?;
+            }
+            for line in &frame {
+                term.write_line(line)?;
+            }
+            last_lines = frame.len();
+        }
+
+        if let Ok(live) = snapshot {
+            match (options.exceeded(&live), alerting) {
+                (Some(reason), false) => {
+                    alerting = true;
+                    let title = format!("{} 流量告警", domain);
+                    output::warn(&format!("{}: {}", title, reason));
+                    let message = format!("最近 {} 分钟{}", window, reason);
+                    notify::send_quietly(config, &Notification::new(Level::Alert, title, message)).await;
+                }
+                (None, true) => {
+                    alerting = false;
+                    let title = format!("{} 流量已恢复正常", domain);
+                    let message = format!("最近 {} 分钟的流量已低于告警阈值", window);
+                    notify::send_quietly(config, &Notification::new(Level::Success, title, message)).await;
+                }
+                _ => {}
+            }
+            previous = Some(live);
+        }
+
+        let mut wait = Duration::from_secs(interval);
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            wait = wait.min(remaining);
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            break;
+        }
+    }
+
+    if redraw {
+        term.show_cursor()?;
+    }
+    Ok(())
+}
+
+/// 生成一帧仪表盘文本
+fn render_watch_frame(
+    domain: &str,
+    interval: u64,
+    window: i64,
+    snapshot: &Result<LiveAnalytics>,
+    previous: Option<&LiveAnalytics>,
+) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "{} {}  {}",
+            "📈 实时流量".bold().cyan(),
+            domain.bold(),
+            format!(
+                "(最近 {} 分钟, 每 {} 秒刷新, 更新于 {})",
+                window,
+                interval,
+                chrono::Local::now().format("%H:%M:%S")
+            )
+            .dimmed()
+        ),
+        "─".repeat(60).dimmed().to_string(),
+    ];
+
+    let live = match snapshot {
+        Ok(live) => live,
+        Err(e) => {
+            lines.push(format!("{} {:#}", "✗ 获取数据失败:".red(), e));
+            lines.push("将在下次刷新时重试".dimmed().to_string());
+            return lines;
+        }
+    };
+
+    let rps = live.requests_per_second();
+    let trend = match previous.map(|p| p.requests_per_second()) {
+        Some(prev) if rps > prev * 1.1 => " ↑".red().to_string(),
+        Some(prev) if rps < prev * 0.9 => " ↓".green().to_string(),
+        Some(_) => " →".dimmed().to_string(),
+        None => String::new(),
+    };
+    let hit_rate = live.cache_hit_rate();
+    let hit_rate_text = format!("{:.1}%", hit_rate);
+
+    lines.push(format!("  {:<12} {}{}", "请求/秒", format!("{:.2}", rps).bold(), trend));
+    lines.push(format!("  {:<12} {}", "请求总数", output::format_number(live.requests)));
+    lines.push(format!(
+        "  {:<12} {}",
+        "缓存命中率",
+        if hit_rate > 50.0 { hit_rate_text.green() } else { hit_rate_text.yellow() }
+    ));
+    lines.push(format!("  {:<12} {}", "带宽", output::format_bytes(live.bytes)));
+    lines.push(format!(
+        "  {:<12} {}",
+        "威胁",
+        if live.threats == 0 {
+            "0".green()
+        } else {
+            output::format_number(live.threats).red().bold()
+        }
+    ));
+    lines.push(format!("  {:<12} {}", "每分钟请求", sparkline(&live.per_minute).cyan()));
+    lines.push(String::new());
+    lines.push("按 Ctrl+C 退出 (分析数据通常有 1~2 分钟延迟)".dimmed().to_string());
+    lines
+}
+
+/// 解析 `NAME=VALUE` 形式的查询变量，值按 JSON 解析 (数字、布尔值、数组等)，否则作为字符串
+fn parse_query_vars(vars: &[String]) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let mut variables = serde_json::Map::new();
+    for var in vars {
+        let (name, value) = var
+            .split_once('=')
+            .ok_or_else(|| ValidationError(format!("变量格式应为 NAME=VALUE: {}", var)))?;
+        let value = serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+        variables.insert(name.trim().trim_start_matches('$').to_string(), value);
+    }
+    Ok(variables)
+}
+
+/// 查找结果中第一个不再包含数组的对象数组 (如 `zones[0].httpRequests1hGroups`)，
+/// 展开为表格 (嵌套字段以 `a.b` 作为列名)
+fn first_table(value: &serde_json::Value) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    fn flatten(prefix: &str, value: &serde_json::Value, row: &mut Vec<(String, String)>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, v) in map {
+                    let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                    flatten(&key, v, row);
+                }
+            }
+            serde_json::Value::String(s) => row.push((prefix.to_string(), s.clone())),
+            serde_json::Value::Null => row.push((prefix.to_string(), "-".to_string())),
+            other => row.push((prefix.to_string(), other.to_string())),
+        }
+    }
+
+    fn has_array(value: &serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Array(_) => true,
+            serde_json::Value::Object(map) => map.values().any(has_array),
+            _ => false,
+        }
+    }
+
+    match value {
+        serde_json::Value::Array(items)
+            if items.first().is_some_and(|v| v.is_object()) && !items.iter().any(has_array) =>
+        {
+            let flat: Vec<Vec<(String, String)>> = items
+                .iter()
+                .map(|item| {
+                    let mut row = Vec::new();
+                    flatten("", item, &mut row);
+                    row
+                })
+                .collect();
+            let mut headers: Vec<String> = Vec::new();
+            for (key, _) in flat.iter().flatten() {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+            let rows = flat
+                .iter()
+                .map(|row| {
+                    headers
+                        .iter()
+                        .map(|h| row.iter().find(|(k, _)| k == h).map(|(_, v)| v.clone()).unwrap_or_default())
+                        .collect()
+                })
+                .collect();
+            Some((headers, rows))
+        }
+        serde_json::Value::Array(items) => items.iter().find_map(first_table),
+        serde_json::Value::Object(map) => map.values().find_map(first_table),
+        _ => None,
+    }
+}
+
+/// 打印状态码、国家/地区、内容类型的前 N 项分布
+fn print_breakdowns(totals: &AnalyticsTotals, top: usize) {
+    let requests = totals.requests.as_ref();
+    let bandwidth = totals.bandwidth.as_ref();
+    let all = requests.and_then(|r| r.all).unwrap_or(0).max(1);
+    let share = |count: u64| format!("{:.1}%", count as f64 / all as f64 * 100.0);
+    let lookup = |map: Option<&serde_json::Value>, key: &str| map.and_then(|m| m.get(key)?.as_u64()).unwrap_or(0);
+
+    let status = top_breakdown(requests.and_then(|r| r.http_status.as_ref()), top);
+    if !status.is_empty() {
+        println!("\n{}", "状态码".bold());
+        let mut table = output::create_table(vec!["状态码", "请求数", "占比"]);
+        for (code, count) in status {
+            table.add_row(vec![code, output::format_number(count), share(count)]);
+        }
+        println!("{table}");
+    }
+
+    let countries = top_breakdown(requests.and_then(|r| r.country.as_ref()), top);
+    if !countries.is_empty() {
+        println!("\n{}", "国家/地区".bold());
+        let mut table = output::create_table(vec!["国家/地区", "请求数", "占比", "带宽", "威胁"]);
+        let threats = totals.threats.as_ref().and_then(|t| t.country.as_ref());
+        for (country, count) in countries {
+            let bytes = lookup(bandwidth.and_then(|b| b.country.as_ref()), &country);
+            let blocked = lookup(threats, &country);
+            table.add_row(vec![
+                country,
+                output::format_number(count),
+                share(count),
+                output::format_bytes(bytes),
+                output::format_number(blocked),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    let content_types = top_breakdown(requests.and_then(|r| r.content_type.as_ref()), top);
+    if !content_types.is_empty() {
+        println!("\n{}", "内容类型".bold());
+        let mut table = output::create_table(vec!["内容类型", "请求数", "占比", "带宽"]);
+        for (content_type, count) in content_types {
+            let bytes = lookup(bandwidth.and_then(|b| b.content_type.as_ref()), &content_type);
+            table.add_row(vec![content_type, output::format_number(count), share(count), output::format_bytes(bytes)]);
+        }
+        println!("{table}");
+    }
+}
+
+/// 用方块字符绘制迷你趋势图
+pub(crate) fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if values.is_empty() {
+        return "-".to_string();
+    }
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    values
+        .iter()
+        .map(|&v| BARS[((v * (BARS.len() as u64 - 1)) / max) as usize])
+        .collect()
+}
+
+/// 将时间序列转为 CSV 文本
+fn timeseries_csv(series: &[AnalyticsTimeseries]) -> String {
+    let mut csv = String::from(
+        "since,until,requests,cached_requests,uncached_requests,encrypted_requests,\
+         bytes,cached_bytes,threats,page_views,uniques\n",
+    );
+    let cell = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_default();
+    for ts in series {
+        let requests = ts.requests.as_ref();
+        let bandwidth = ts.bandwidth.as_ref();
+        let row = [
+            ts.since.clone().unwrap_or_default(),
+            ts.until.clone().unwrap_or_default(),
+            cell(requests.and_then(|r| r.all)),
+            cell(requests.and_then(|r| r.cached)),
+            cell(requests.and_then(|r| r.uncached)),
+            cell(requests.and_then(|r| r.ssl.as_ref()?.encrypted)),
+            cell(bandwidth.and_then(|b| b.all)),
+            cell(bandwidth.and_then(|b| b.cached)),
+            cell(ts.threats.as_ref().and_then(|t| t.all)),
+            cell(ts.pageviews.as_ref().and_then(|p| p.all)),
+            cell(ts.uniques.as_ref().and_then(|u| u.all)),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_helpers() {
+        let vars = parse_query_vars(&["limit=10".into(), "$since=2024-05-01T00:00:00Z".into()]).unwrap();
+        assert_eq!(vars["limit"], 10);
+        assert_eq!(vars["since"], "2024-05-01T00:00:00Z");
+        assert!(parse_query_vars(&["limit".into()]).is_err());
+
+        let data = serde_json::json!({"viewer": {"zones": [{"groups": [
+            {"dimensions": {"date": "2024-05-01"}, "sum": {"requests": 10}},
+            {"dimensions": {"date": "2024-05-02"}, "sum": {"requests": 20}, "extra": null},
+        ]}]}});
+        let (headers, rows) = first_table(&data).unwrap();
+        assert_eq!(headers, ["dimensions.date", "sum.requests", "extra"]);
+        assert_eq!(rows[0], ["2024-05-01", "10", ""]);
+        assert_eq!(rows[1], ["2024-05-02", "20", "-"]);
+        assert!(first_table(&serde_json::json!({"viewer": {}})).is_none());
+    }
+}
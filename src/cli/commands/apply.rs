@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+
+use crate::api::backup::{plan_ip_rules, plan_restore};
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::commands::backup::{apply_changes, print_changes, protect_unmanaged};
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::models::dns::managed_comment;
+use crate::models::zone_spec::ZoneSpec;
+
+#[derive(Args, Debug)]
+pub struct ApplyArgs {
+    /// 配置文件 (.yaml / .yml / .toml / .json)
+    #[arg(short, long)]
+    pub file: PathBuf,
+    /// 域名或 Zone ID (默认为文件中的 zone)
+    #[arg(short, long)]
+    pub zone: Option<String>,
+    /// 删除文件中未声明的 DNS 记录和 IP 访问规则 (只作用于文件中出现的分组)
+    #[arg(long)]
+    pub prune: bool,
+    /// 允许修改或删除不带 cfai 标记的 DNS 记录 (修改后的记录会带上标记，由 cfai 接管)
+    #[arg(long)]
+    pub force: bool,
+    /// 只显示变更计划，不执行
+    #[arg(long)]
+    pub dry_run: bool,
+    /// 跳过确认
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+impl ApplyArgs {
+    pub async fn execute(&self, client: &CfClient) -> Result<()> {
+        let spec = read_spec(&self.file)?;
+        let Some(target) = self.zone.as_deref().or(spec.zone.as_deref()) else {
+            anyhow::bail!(ValidationError("未指定域名: 请在文件中设置 zone 或使用 --zone".into()));
+        };
+        let desired_ip_rules = spec.ip_rules()?;
+        let zone_id = resolve_zone_id(client, target).await?;
+
+        output::loading(&format!("正在读取 {} 的当前配置", target));
+        let current = client.snapshot_zone(&zone_id).await?;
+        let mut desired = spec.to_backup(&current)?;
+        // 写入的记录都带上管理标记，之后的 apply 才能修改或删除它们
+        for record in desired.dns_records.iter_mut().flatten() {
+            record.comment = Some(managed_comment(record.comment.as_deref()));
+        }
+        let mut changes = plan_restore(&desired, &current, self.prune);
+        if let Some(desired) = &desired_ip_rules {
+            let rules = client.list_ip_access_rules(&zone_id).await?;
+            changes.extend(plan_ip_rules(desired, &rules, self.prune));
+        }
+
+        let (changes, protected) =
+            protect_unmanaged(changes, current.dns_records.as_deref().unwrap_or_default(), self.force);
+
+        let zone_name = &current.zone.name;
+        if changes.is_empty() {
+            if protected == 0 {
+                output::success(&format!("{} 的配置已与 {} 一致，无需变更", zone_name, self.file.display()));
+            }
+            return Ok(());
+        }
+
+        output::title(&format!("变更计划: {} ({} 项变更)", zone_name, changes.len()));
+        print_changes(&changes);
+
+        if self.dry_run {
+            output::info("预览模式 (--dry-run)，未执行任何变更");
+            return Ok(());
+        }
+
+        if !self.yes {
+            let confirm = prompt::confirm(format!("确定要对 {} 执行以上变更吗？", zone_name.yellow()))?;
+            if !confirm {
+                output::info("已取消操作");
+                return Ok(());
+            }
+        }
+
+        apply_changes(client, &zone_id, &changes).await
+    }
+}
+
+/// 按扩展名解析配置文件 (.yaml / .yml / .toml，其余按 JSON 解析)
+fn read_spec(path: &Path) -> Result<ZoneSpec> {
+    let content = std::fs::read_to_string(path).context(format!("读取配置文件失败: {}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let spec = match extension.as_str() {
+        "yaml" | "yml" => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+        "toml" => toml::from_str(&content).map_err(|e| e.to_string()),
+        _ => serde_json::from_str(&content).map_err(|e| e.to_string()),
+    };
+    spec.map_err(|e| ValidationError(format!("配置文件格式错误 ({}): {}", path.display(), e)).into())
+}
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::config::audit;
+use crate::models::audit::{AuditChange, AuditEntry};
+use crate::models::firewall::RateLimitRequest;
+
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    /// 最多显示的条目数 (最近的在前)
+    #[arg(short, long, default_value = "20")]
+    pub limit: usize,
+    /// 只显示指定 Zone ID 的变更
+    #[arg(long)]
+    pub zone: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct UndoArgs {
+    /// 跳过确认
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct RollbackArgs {
+    /// 审计日志条目 ID (可只输入前缀，见 `cfai audit`)
+    pub id: String,
+    /// 跳过确认
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+impl AuditArgs {
+    pub async fn execute(&self, format: &str) -> Result<()> {
+        let entries: Vec<AuditEntry> = audit::load()?
+            .into_iter()
+            .rev()
+            .filter(|e| self.zone.as_deref().is_none_or(|z| e.zone_id == z))
+            .take(self.limit)
+            .collect();
+
+        if format == "json" {
+            output::print_json(&entries);
+            return Ok(());
+        }
+
+        output::title(&format!("变更审计日志 (最近 {} 条)", entries.len()));
+        if entries.is_empty() {
+            output::info("暂无记录，DNS 记录与域名设置的变更会自动记录在此");
+            return Ok(());
+        }
+
+        let mut table = output::create_table(vec!["ID", "时间", "Zone ID", "变更", "状态"]);
+        for e in &entries {
+            let status = if e.undone {
+                "已撤销".dimmed().to_string()
+            } else if e.change.is_reversible() {
+                "可撤销".green().to_string()
+            } else {
+                "不可撤销".yellow().to_string()
+            };
+            table.add_row(vec![
+                e.id.clone(),
+                format_time(&e.timestamp),
+                e.zone_id.clone(),
+                e.change.describe(),
+                status,
+            ]);
+        }
+        println!("{table}");
+        output::tip("使用 `cfai undo` 撤销最近一次变更，或 `cfai rollback <ID>` 撤销指定变更");
+        Ok(())
+    }
+}
+
+impl UndoArgs {
+    pub async fn execute(&self, client: &CfClient) -> Result<()> {
+        let entry = audit::load()?
+            .into_iter()
+            .rev()
+            .find(|e| !e.undone && e.change.is_reversible())
+            .ok_or_else(|| anyhow::anyhow!("没有可撤销的变更"))?;
+        revert_entry(client, &entry, self.yes).await
+    }
+}
+
+impl RollbackArgs {
+    pub async fn execute(&self, client: &CfClient) -> Result<()> {
+        let matches: Vec<AuditEntry> = audit::load()?
+            .into_iter()
+            .filter(|e| e.id.starts_with(&self.id))
+            .collect();
+        let entry = match matches.as_slice() {
+            [entry] => entry,
+            [] => anyhow::bail!(ValidationError(format!("未找到审计记录: {}", self.id))),
+            _ => anyhow::bail!(ValidationError(format!(
+                "ID 前缀 {} 匹配到 {} 条记录，请输入更长的 ID",
+                self.id,
+                matches.len()
+            ))),
+        };
+        if entry.undone {
+            anyhow::bail!(ValidationError(format!("记录 {} 已撤销过", entry.id)));
+        }
+        if !entry.change.is_reversible() {
+            anyhow::bail!(ValidationError(format!(
+                "记录 {} 缺少修改前的状态，无法撤销",
+                entry.id
+            )));
+        }
+        revert_entry(client, entry, self.yes).await
+    }
+}
+
+/// 确认后恢复变更前的状态，并将条目标记为已撤销
+async fn revert_entry(client: &CfClient, entry: &AuditEntry, yes: bool) -> Result<()> {
+    output::kv("记录", &entry.id);
+    output::kv("时间", &format_time(&entry.timestamp));
+    output::kv("Zone ID", &entry.zone_id);
+    output::kv("变更", &entry.change.describe());
+
+    if !yes {
+        let confirm = prompt::confirm("确定要撤销此变更吗？")?;
+        if !confirm {
+            output::info("已取消");
+            return Ok(());
+        }
+    }
+
+    // 撤销操作本身不再写入审计日志，避免 undo 撤销上一次 undo
+    let client = client.clone().with_audit(false);
+    let zone_id = entry.zone_id.as_str();
+    let message = match &entry.change {
+        AuditChange::DnsCreate { record } => {
+            let id = record.id.as_deref().unwrap_or_default();
+            client.delete_dns_record(zone_id, id).await?;
+            format!("已删除新建的 DNS 记录 {} {}", record.record_type, record.name)
+        }
+        AuditChange::DnsUpdate { before, .. } => {
+            let id = before.id.as_deref().unwrap_or_default();
+            client.update_dns_record(zone_id, id, &before.to_request()).await?;
+            format!("DNS 记录 {} {} 已恢复为 {}", before.record_type, before.name, before.content)
+        }
+        AuditChange::DnsDelete { before } => {
+            let record = client.create_dns_record(zone_id, &before.to_request()).await?;
+            format!(
+                "已重新创建 DNS 记录 {} {} (新 ID: {})",
+                before.record_type,
+                before.name,
+                record.id.unwrap_or_default()
+            )
+        }
+        AuditChange::SettingChange {
+            setting_id, before, ..
+        } => {
+            let value = before.clone().unwrap_or_default();
+            client.update_zone_setting(zone_id, setting_id, value.clone()).await?;
+            format!("设置 {} 已恢复为 {}", setting_id, value)
+        }
+        AuditChange::RateLimitCreate { rule } => {
+            let id = rule.id.as_deref().unwrap_or_default();
+            client.delete_rate_limit(zone_id, id).await?;
+            format!("已删除新建的速率限制规则 {}", rule.url().unwrap_or(id))
+        }
+        AuditChange::RateLimitDelete { before } => {
+            let rule = client
+                .create_rate_limit(zone_id, &RateLimitRequest::from_rule(before))
+                .await?;
+            format!(
+                "已重新创建速率限制规则 {} (新 ID: {})",
+                before.url().unwrap_or("-"),
+                rule.id.unwrap_or_default()
+            )
+        }
+    };
+
+    audit::mark_undone(&entry.id)?;
+    output::success(&message);
+    Ok(())
+}
+
+fn format_time(ts: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(ts)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
@@ -0,0 +1,298 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::api::backup::{plan_restore, split_unmanaged_dns_changes};
+use crate::api::client::CfClient;
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::exit::PartialFailure;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::models::backup::*;
+use crate::models::dns::{DnsRecord, MANAGED_MARKER};
+use crate::progress::Progress;
+
+#[derive(Args, Debug)]
+pub struct BackupArgs {
+    #[command(subcommand)]
+    pub command: BackupCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupCommands {
+    /// 备份域名配置 (DNS、设置、SSL、页面规则、防火墙规则)
+    Create {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 输出文件 (按扩展名选择 .json / .toml，默认 <域名>-<时间>.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// 查看备份文件内容摘要
+    Show {
+        /// 备份文件路径
+        file: PathBuf,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct RestoreArgs {
+    /// 备份文件路径 (.json / .toml)
+    pub file: PathBuf,
+    /// 还原到指定域名 (默认为备份中的域名，可用于跨账户迁移)
+    #[arg(short, long)]
+    pub zone: Option<String>,
+    /// 删除目标域名中备份里不存在的 DNS 记录和规则
+    #[arg(long)]
+    pub prune: bool,
+    /// 只预览变更，不执行
+    #[arg(long)]
+    pub dry_run: bool,
+    /// 允许修改或删除不是由 cfai 创建的 DNS 记录
+    #[arg(long)]
+    pub force: bool,
+    /// 跳过确认
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+impl BackupArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match &self.command {
+            BackupCommands::Create { domain, output: path } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                output::loading(&format!("正在备份 {} 的配置", domain));
+                let backup = client.snapshot_zone(&zone_id).await?;
+
+                let path = path.clone().unwrap_or_else(|| {
+                    PathBuf::from(format!(
+                        "{}-{}.json",
+                        backup.zone.name,
+                        chrono::Local::now().format("%Y%m%d-%H%M%S")
+                    ))
+                });
+                write_backup(&path, &backup)?;
+
+                if format == "json" {
+                    output::print_json(&serde_json::json!({
+                        "file": path.display().to_string(),
+                        "zone": backup.zone.name,
+                    }));
+                    return Ok(());
+                }
+
+                print_summary(&backup);
+                output::success(&format!("备份已保存到 {}", path.display()));
+            }
+
+            BackupCommands::Show { file } => {
+                let backup = read_backup(file)?;
+                if format == "json" {
+                    output::print_json(&backup);
+                    return Ok(());
+                }
+                print_summary(&backup);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RestoreArgs {
+    pub async fn execute(&self, client: &CfClient, _format: &str) -> Result<()> {
+        let backup = read_backup(&self.file)?;
+        let target = self.zone.as_deref().unwrap_or(&backup.zone.name);
+        let zone_id = resolve_zone_id(client, target).await?;
+
+        output::loading(&format!("正在读取 {} 的当前配置", target));
+        let current = client.snapshot_zone(&zone_id).await?;
+        if current.zone.name != backup.zone.name {
+            output::info(&format!(
+                "跨域名还原: {} → {} (记录名将自动替换)",
+                backup.zone.name, current.zone.name
+            ));
+        }
+
+        let changes = plan_restore(&backup, &current, self.prune);
+        let (changes, protected) =
+            protect_unmanaged(changes, current.dns_records.as_deref().unwrap_or_default(), self.force);
+        if changes.is_empty() {
+            if protected == 0 {
+                output::success(&format!("{} 的配置已与备份一致，无需还原", current.zone.name));
+            }
+            return Ok(());
+        }
+
+        output::title(&format!("还原预览: {} ({} 项变更)", current.zone.name, changes.len()));
+        print_changes(&changes);
+
+        if self.dry_run {
+            output::info("预览模式 (--dry-run)，未执行任何变更");
+            return Ok(());
+        }
+
+        if !self.yes {
+            let confirm = prompt::confirm(format!("确定要对 {} 执行以上变更吗？", current.zone.name.yellow()))?;
+            if !confirm {
+                output::info("已取消还原操作");
+                return Ok(());
+            }
+        }
+
+        apply_changes(client, &zone_id, &changes).await
+    }
+}
+
+/// 写入备份文件 (.toml 扩展名写为 TOML，其余写为 JSON)
+fn write_backup(path: &Path, backup: &ZoneBackup) -> Result<()> {
+    let content = if is_toml(path) {
+        // TOML 不支持 null，先去掉空值
+        let mut value = serde_json::to_value(backup)?;
+        strip_nulls(&mut value);
+        toml::to_string_pretty(&value).context("序列化备份为 TOML 失败")?
+    } else {
+        serde_json::to_string_pretty(backup)?
+    };
+    std::fs::write(path, content).context(format!("写入备份文件失败: {}", path.display()))
+}
+
+/// 读取备份文件并检查格式版本
+fn read_backup(path: &Path) -> Result<ZoneBackup> {
+    let content = std::fs::read_to_string(path)
+        .context(format!("读取备份文件失败: {}", path.display()))?;
+    let backup: ZoneBackup = if is_toml(path) {
+        toml::from_str(&content).context("解析 TOML 备份文件失败")?
+    } else {
+        serde_json::from_str(&content).context("解析 JSON 备份文件失败")?
+    };
+    if backup.version > BACKUP_FORMAT_VERSION {
+        anyhow::bail!(
+            "备份文件格式版本 {} 高于当前支持的版本 {}，请先运行 `cfai update`",
+            backup.version,
+            BACKUP_FORMAT_VERSION
+        );
+    }
+    Ok(backup)
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.eq_ignore_ascii_case("toml"))
+        .unwrap_or(false)
+}
+
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(items) => {
+            items.retain(|v| !v.is_null());
+            items.iter_mut().for_each(strip_nulls);
+        }
+        _ => {}
+    }
+}
+
+fn print_summary(backup: &ZoneBackup) {
+    fn count<T>(section: &Option<Vec<T>>) -> String {
+        section
+            .as_ref()
+            .map(|v| v.len().to_string())
+            .unwrap_or_else(|| "未备份".to_string())
+    }
+
+    output::title(&format!("备份: {}", backup.zone.name));
+    output::kv("Zone ID", &backup.zone.id);
+    output::kv("套餐", backup.zone.plan.as_deref().unwrap_or("-"));
+    output::kv("备份时间", &backup.created_at);
+    output::kv("格式版本", &format!("v{} (cfai {})", backup.version, backup.cfai_version));
+    output::kv("DNS 记录", &count(&backup.dns_records));
+    output::kv("SSL 设置", &count(&backup.ssl));
+    output::kv("域名设置", &count(&backup.settings));
+    output::kv("页面规则", &count(&backup.page_rules));
+    output::kv("防火墙规则", &count(&backup.firewall_rules));
+}
+
+/// 按分组打印变更列表 (+ 新增 / ~ 修改 / - 删除)
+pub fn print_changes(changes: &[PlannedChange]) {
+    let mut section = "";
+    for change in changes {
+        if change.section != section {
+            section = change.section;
+            println!("\n{}", format!("[{}]", section).bold());
+        }
+        match change.kind {
+            ChangeKind::Create => println!(
+                "  {} {}  {}",
+                "+".green().bold(),
+                change.target.green(),
+                change.after.as_deref().unwrap_or("").green()
+            ),
+            ChangeKind::Update => println!(
+                "  {} {}  {} → {}",
+                "~".yellow().bold(),
+                change.target.yellow(),
+                change.before.as_deref().unwrap_or("").red(),
+                change.after.as_deref().unwrap_or("").green()
+            ),
+            ChangeKind::Delete => println!(
+                "  {} {}  {}",
+                "-".red().bold(),
+                change.target.red(),
+                change.before.as_deref().unwrap_or("").red()
+            ),
+        }
+    }
+    println!();
+}
+
+/// 未指定 --force 时拦截修改或删除非 cfai 管理 DNS 记录的变更，返回可执行的变更和被跳过的数量
+pub fn protect_unmanaged(
+    changes: Vec<PlannedChange>,
+    current: &[DnsRecord],
+    force: bool,
+) -> (Vec<PlannedChange>, usize) {
+    if force {
+        return (changes, 0);
+    }
+    let (allowed, protected) = split_unmanaged_dns_changes(changes, current);
+    if !protected.is_empty() {
+        output::warn(&format!(
+            "跳过 {} 项涉及非 cfai 管理记录的变更 (备注不含 {})，使用 --force 接管这些记录:",
+            protected.len(),
+            MANAGED_MARKER
+        ));
+        print_changes(&protected);
+    }
+    (allowed, protected.len())
+}
+
+/// 逐条执行变更，失败项不影响后续变更
+pub async fn apply_changes(client: &CfClient, zone_id: &str, changes: &[PlannedChange]) -> Result<()> {
+    let mut progress = Progress::terminal("应用变更", changes.len());
+    let mut last_error = None;
+    for change in changes {
+        progress.start(format!("[{}] {}", change.section, change.target));
+        let result = client.apply_change(zone_id, &change.op).await;
+        progress.record(&result);
+        if let Err(e) = result {
+            last_error = Some(e);
+        }
+    }
+
+    let failed = progress.finish().failed;
+    if let Some(e) = last_error {
+        if failed == changes.len() {
+            return Err(e.context(format!("{} 项变更全部失败", failed)));
+        }
+        return Err(PartialFailure { total: changes.len(), failed }.into());
+    }
+    output::success(&format!("已完成 {} 项变更", changes.len()));
+    Ok(())
+}
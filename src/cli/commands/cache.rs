@@ -1,9 +1,26 @@
-use anyhow::Result;
+use std::collections::{HashSet, VecDeque};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Local;
 use clap::{Args, Subcommand};
+use flate2::read::GzDecoder;
 
 use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::exit::PartialFailure;
 use crate::cli::output;
+use crate::cli::prompt;
+use crate::cli::commands::phase_rules::{delete_rule, list_rules, rule_expression};
+use crate::cli::commands::scheduler::{add_schedule, remove_schedule};
 use crate::cli::commands::zone::resolve_zone_id;
+use crate::config::settings::{AppConfig, ScheduleEntry};
+use crate::models::cache::{
+    parse_sitemap, url_in_zone, url_matches_paths, PurgeTarget, PURGE_BATCH_SIZE, SITEMAP_MAX_FILES,
+};
+use crate::models::rulesets::{RulesetRuleRequest, CACHE_RULES_PHASE};
 
 #[derive(Args, Debug)]
 pub struct CacheArgs {
@@ -14,7 +31,6 @@ pub struct CacheArgs {
 #[derive(Subcommand, Debug)]
 pub enum CacheCommands {
     /// 清除全部缓存
-    #[command(alias = "purge-all")]
     PurgeAll {
         /// 域名或 Zone ID
         domain: String,
@@ -23,14 +39,40 @@ pub enum CacheCommands {
         yes: bool,
     },
 
-    /// 按 URL 清除缓存
+    /// 按 URL 清除缓存 (超过 30 个时自动分批)
     #[command(alias = "purge")]
     PurgeUrl {
         /// 域名或 Zone ID
         domain: String,
         /// 要清除缓存的 URL 列表
-        #[arg(required = true)]
+        #[arg(required_unless_present = "file")]
         urls: Vec<String>,
+        /// 从文件读取 URL (每行一个，# 开头为注释)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+    },
+
+    /// 按 URL 前缀清除缓存 (如 www.example.com/images)
+    PurgePrefix {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 前缀列表 (不含 http:// 或 https://)
+        #[arg(required = true)]
+        prefixes: Vec<String>,
+    },
+
+    /// 按站点地图清除缓存 (下载 sitemap.xml，提取其中的 URL 分批清除)
+    PurgeSitemap {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 站点地图地址 (默认 https://<域名>/sitemap.xml，支持站点地图索引与 .gz)
+        sitemap: Option<String>,
+        /// 只清除路径以此开头的 URL (可多次指定，如 -p /blog -p /docs)
+        #[arg(short, long = "path")]
+        paths: Vec<String>,
+        /// 只列出将要清除的 URL，不执行
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// 按主机名清除缓存
@@ -64,27 +106,103 @@ pub enum CacheCommands {
         ttl: u32,
     },
 
-    /// 开启/关闭开发模式
+    /// 开启/关闭开发模式，或查看剩余时间 (status)
     DevMode {
         /// 域名或 Zone ID
         domain: String,
-        /// on/off
-        #[arg(default_value = "on")]
+        /// on/off/status
+        #[arg(default_value = "on", value_parser = ["on", "off", "status"])]
         toggle: String,
+        /// 开启后经过指定时长自动关闭 (如 30m、1h，最长 3h；需运行 `cfai scheduler run`)
+        #[arg(long = "for", value_name = "DURATION")]
+        duration: Option<String>,
+    },
+
+    /// 开启/关闭 Always Online (源站不可用时提供存档页面)
+    AlwaysOnline {
+        /// on/off
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 开启/关闭 Crawler Hints (内容变化时通知搜索引擎重新抓取)
+    CrawlerHints {
+        /// on/off
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 管理缓存规则 (Cache Rules: list/add/delete)
+    Rules {
+        #[command(subcommand)]
+        command: CacheRuleCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheRuleCommands {
+    /// 列出缓存规则
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 添加缓存规则
+    Add {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 匹配的 URL 模式 (如 example.com/static/*，支持 * 通配符)
+        #[arg(long, required_unless_present = "expression")]
+        from: Option<String>,
+        /// 直接使用规则表达式匹配 (代替 --from)
+        #[arg(long, conflicts_with = "from")]
+        expression: Option<String>,
+        /// 绕过缓存 (不缓存匹配的请求)
+        #[arg(long, conflicts_with_all = ["edge_ttl", "browser_ttl", "ignore_query", "query_include"])]
+        bypass: bool,
+        /// 边缘缓存 TTL (秒)，忽略源站的缓存头
+        #[arg(long)]
+        edge_ttl: Option<u32>,
+        /// 浏览器缓存 TTL (秒)，忽略源站的缓存头
+        #[arg(long)]
+        browser_ttl: Option<u32>,
+        /// 缓存键忽略全部查询参数
+        #[arg(long, conflicts_with = "query_include")]
+        ignore_query: bool,
+        /// 缓存键只包含指定的查询参数 (逗号分隔)
+        #[arg(long, value_delimiter = ',')]
+        query_include: Vec<String>,
+        /// 规则描述
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// 删除缓存规则
+    #[command(alias = "rm")]
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则 ID
+        rule_id: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
     },
 }
 
 impl CacheArgs {
-    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
         match &self.command {
             CacheCommands::PurgeAll { domain, yes } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
 
                 if !yes {
-                    let confirm = dialoguer::Confirm::new()
-                        .with_prompt(format!("确定要清除 {} 的全部缓存吗？", domain))
-                        .default(false)
-                        .interact()?;
+                    let confirm = prompt::confirm(format!("确定要清除 {} 的全部缓存吗？", domain))?;
                     if !confirm {
                         output::info("已取消");
                         return Ok(());
@@ -95,12 +213,84 @@ impl CacheArgs {
                 output::success(&format!("已清除 {} 的全部缓存", domain));
             }
 
-            CacheCommands::PurgeUrl { domain, urls } => {
+            CacheCommands::PurgeUrl { domain, urls, file } => {
+                let mut urls = urls.clone();
+                if let Some(path) = file {
+                    urls.extend(read_list_file(path)?);
+                }
+                let mut seen = HashSet::new();
+                urls.retain(|u| seen.insert(u.clone()));
+                if urls.is_empty() {
+                    anyhow::bail!(ValidationError("没有需要清除的 URL".into()));
+                }
+
                 let zone_id = resolve_zone_id(client, domain).await?;
-                client.purge_cache_by_urls(&zone_id, urls.clone()).await?;
+                purge_batched(client, &zone_id, PurgeTarget::Urls, &urls).await?;
                 output::success(&format!("已清除 {} 个 URL 的缓存", urls.len()));
             }
 
+            CacheCommands::PurgePrefix { domain, prefixes } => {
+                // 前缀清除不接受协议头，自动去掉
+                let prefixes: Vec<String> = prefixes
+                    .iter()
+                    .map(|p| {
+                        p.trim_start_matches("https://")
+                            .trim_start_matches("http://")
+                            .to_string()
+                    })
+                    .collect();
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                purge_batched(client, &zone_id, PurgeTarget::Prefixes, &prefixes).await?;
+                output::success(&format!("已清除 {} 个前缀下的缓存", prefixes.len()));
+            }
+
+            CacheCommands::PurgeSitemap {
+                domain,
+                sitemap,
+                paths,
+                dry_run,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let zone = client.get_zone(&zone_id).await?.name;
+                let sitemap = sitemap.clone().unwrap_or_else(|| format!("https://{}/sitemap.xml", zone));
+
+                let http = config
+                    .network
+                    .apply(reqwest::Client::builder(), Duration::from_secs(30))?
+                    .user_agent(concat!("cfai/", env!("CARGO_PKG_VERSION"), " (sitemap)"))
+                    .build()
+                    .context("创建 HTTP 客户端失败")?;
+                let found = sitemap_urls(&http, &sitemap).await?;
+
+                let mut seen = HashSet::new();
+                let (urls, foreign): (Vec<String>, Vec<String>) = found
+                    .into_iter()
+                    .filter(|u| url_matches_paths(u, paths) && seen.insert(u.clone()))
+                    .partition(|u| url_in_zone(u, &zone));
+                if !foreign.is_empty() {
+                    output::warn(&format!("跳过 {} 个不属于 {} 的 URL (如 {})", foreign.len(), zone, foreign[0]));
+                }
+                if urls.is_empty() {
+                    anyhow::bail!(ValidationError(format!("站点地图 {} 中没有需要清除的 URL", sitemap)));
+                }
+
+                if *dry_run {
+                    if format == "json" {
+                        output::print_json(&urls);
+                        return Ok(());
+                    }
+                    output::title(&format!("将清除 {} 个 URL 的缓存 (预览)", urls.len()));
+                    for url in &urls {
+                        println!("  {}", url);
+                    }
+                    return Ok(());
+                }
+
+                purge_batched(client, &zone_id, PurgeTarget::Urls, &urls).await?;
+                output::success(&format!("已清除站点地图中 {} 个 URL 的缓存", urls.len()));
+            }
+
             CacheCommands::PurgeHost { domain, hosts } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
                 client
@@ -113,11 +303,19 @@ impl CacheArgs {
                 let zone_id = resolve_zone_id(client, domain).await?;
                 let cache_level = client.get_cache_level(&zone_id).await?;
                 let browser_ttl = client.get_browser_cache_ttl(&zone_id).await?;
+                // 这两项获取失败 (如令牌权限不足) 时不影响其余设置的展示
+                let (always_online, crawler_hints) = tokio::join!(
+                    client.get_always_online(&zone_id),
+                    client.get_crawler_hints(&zone_id),
+                );
+                let (always_online, crawler_hints) = (always_online.ok(), crawler_hints.ok());
 
                 if format == "json" {
                     output::print_json(&serde_json::json!({
                         "cache_level": cache_level,
                         "browser_cache_ttl": browser_ttl,
+                        "always_online": always_online,
+                        "crawler_hints": crawler_hints.map(|on| if on { "on" } else { "off" }),
                     }));
                     return Ok(());
                 }
@@ -132,6 +330,9 @@ impl CacheArgs {
                         format!("{} 秒 ({} 小时)", browser_ttl, browser_ttl / 3600)
                     },
                 );
+                let badge = |value: Option<&str>| value.map(output::status_badge).unwrap_or_else(|| "获取失败".to_string());
+                output::kv("Always Online", &badge(always_online.as_deref()));
+                output::kv("Crawler Hints", &badge(crawler_hints.map(|on| if on { "on" } else { "off" })));
             }
 
             CacheCommands::Level { domain, level } => {
@@ -146,17 +347,312 @@ impl CacheArgs {
                 output::success(&format!("浏览器缓存 TTL 已设置为: {} 秒", ttl));
             }
 
-            CacheCommands::DevMode { domain, toggle } => {
+            CacheCommands::DevMode { domain, toggle, duration } => {
+                if toggle == "status" {
+                    return dev_mode_status(client, config, domain, format).await;
+                }
+                let duration = match duration {
+                    Some(_) if toggle != "on" => anyhow::bail!(ValidationError("--for 只能用于开启开发模式".into())),
+                    Some(duration) => Some(parse_dev_mode_duration(duration)?),
+                    None => None,
+                };
+
                 let zone_id = resolve_zone_id(client, domain).await?;
                 let enable = toggle == "on";
-                client.set_development_mode(&zone_id, enable).await?;
+                let Some(duration) = duration else {
+                    client.set_development_mode(&zone_id, enable).await?;
+                    output::success(&format!(
+                        "开发模式已{}（缓存将在 3 小时后重新启用）",
+                        if enable { "开启" } else { "关闭" }
+                    ));
+                    return Ok(());
+                };
+
+                // 先写入自动关闭的定时设置，保证开启后一定有对应的关闭计划
+                let off_at = Local::now() + duration;
+                let id = add_schedule(ScheduleEntry {
+                    id: 0,
+                    domain: domain.clone(),
+                    setting: "development_mode".into(),
+                    value: "off".into(),
+                    from: None,
+                    to: None,
+                    revert: None,
+                    cron: None,
+                    at: Some(off_at.to_rfc3339()),
+                })?;
+                if let Err(e) = client.set_development_mode(&zone_id, true).await {
+                    if let Err(rollback) = remove_schedule(id) {
+                        output::warn(&format!("撤销定时设置 #{} 失败: {}", id, rollback));
+                    }
+                    return Err(e);
+                }
                 output::success(&format!(
-                    "开发模式已{}（缓存将在 3 小时后重新启用）",
-                    if enable { "开启" } else { "关闭" }
+                    "开发模式已开启，将于 {} 自动关闭 (定时设置 #{})",
+                    off_at.format("%H:%M"),
+                    id
                 ));
+                output::tip("运行 `cfai scheduler run` 执行定时设置，关闭时会发送通知");
+            }
+
+            CacheCommands::AlwaysOnline { state, domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.set_always_online(&zone_id, state == "on").await?;
+                output::success(&format!("Always Online 已{}", if state == "on" { "开启" } else { "关闭" }));
+            }
+
+            CacheCommands::CrawlerHints { state, domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.set_crawler_hints(&zone_id, state == "on").await?;
+                output::success(&format!("Crawler Hints 已{}", if state == "on" { "开启" } else { "关闭" }));
             }
+
+            CacheCommands::Rules { command } => command.execute(client, format).await?,
         }
 
         Ok(())
     }
 }
+
+impl CacheRuleCommands {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match self {
+            CacheRuleCommands::List { domain } => {
+                list_rules(client, domain, CACHE_RULES_PHASE, "缓存规则", format).await?;
+            }
+
+            CacheRuleCommands::Add {
+                domain,
+                from,
+                expression,
+                bypass,
+                edge_ttl,
+                browser_ttl,
+                ignore_query,
+                query_include,
+                description,
+            } => {
+                let expression = rule_expression(from.as_deref(), expression.as_deref())?;
+
+                let mut params = serde_json::json!({ "cache": !bypass });
+                if let Some(ttl) = edge_ttl {
+                    params["edge_ttl"] = serde_json::json!({ "mode": "override_origin", "default": ttl });
+                }
+                if let Some(ttl) = browser_ttl {
+                    params["browser_ttl"] = serde_json::json!({ "mode": "override_origin", "default": ttl });
+                }
+                if *ignore_query {
+                    params["cache_key"] = serde_json::json!({
+                        "custom_key": { "query_string": { "exclude": { "all": true } } }
+                    });
+                } else if !query_include.is_empty() {
+                    params["cache_key"] = serde_json::json!({
+                        "custom_key": { "query_string": { "include": { "list": query_include } } }
+                    });
+                }
+
+                let mut summary = vec![if *bypass { "绕过缓存" } else { "缓存" }.to_string()];
+                if let Some(ttl) = edge_ttl {
+                    summary.push(format!("边缘 TTL {}s", ttl));
+                }
+                if let Some(ttl) = browser_ttl {
+                    summary.push(format!("浏览器 TTL {}s", ttl));
+                }
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let rule = RulesetRuleRequest {
+                    action: "set_cache_settings".into(),
+                    expression: expression.clone(),
+                    description: Some(description.clone().unwrap_or_else(|| {
+                        format!("{}: {}", from.as_deref().unwrap_or(&expression), summary.join(", "))
+                    })),
+                    enabled: Some(true),
+                    action_parameters: Some(params),
+                };
+                let result = client.add_phase_rule(&zone_id, CACHE_RULES_PHASE, &rule).await?;
+
+                if format == "json" {
+                    output::print_json(&result);
+                    return Ok(());
+                }
+                output::success(&format!("缓存规则已添加 ({})", summary.join(", ")));
+                output::kv("匹配表达式", &expression);
+            }
+
+            CacheRuleCommands::Delete {
+                domain,
+                rule_id,
+                yes,
+            } => {
+                delete_rule(client, domain, CACHE_RULES_PHASE, "缓存规则", rule_id, *yes).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 读取站点地图中的全部 URL: 依次展开站点地图索引，最多读取 [`SITEMAP_MAX_FILES`] 个文件
+///
+/// 首个站点地图获取失败时报错；子站点地图失败时只提示，继续处理其余文件。
+async fn sitemap_urls(http: &reqwest::Client, sitemap: &str) -> Result<Vec<String>> {
+    let mut queue = VecDeque::from([sitemap.to_string()]);
+    let mut visited = HashSet::new();
+    let mut urls = Vec::new();
+    while let Some(url) = queue.pop_front() {
+        if !visited.insert(url.clone()) {
+            continue;
+        }
+        if visited.len() > SITEMAP_MAX_FILES {
+            output::warn(&format!("站点地图文件超过 {} 个，其余文件已忽略", SITEMAP_MAX_FILES));
+            break;
+        }
+        output::loading(&format!("正在读取站点地图 {}", url));
+        let xml = match fetch_sitemap(http, &url).await {
+            Ok(xml) => xml,
+            Err(e) if url != sitemap => {
+                output::warn(&format!("{:#}", e));
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let parsed = parse_sitemap(&xml);
+        urls.extend(parsed.urls);
+        queue.extend(parsed.sitemaps);
+    }
+    Ok(urls)
+}
+
+/// 下载站点地图，gzip 压缩的内容 (sitemap.xml.gz) 自动解压
+async fn fetch_sitemap(http: &reqwest::Client, url: &str) -> Result<String> {
+    let body = http
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("获取站点地图 {} 失败", url))?
+        .bytes()
+        .await
+        .with_context(|| format!("读取站点地图 {} 失败", url))?;
+    if body.starts_with(&[0x1f, 0x8b]) {
+        let mut xml = String::new();
+        GzDecoder::new(&body[..])
+            .read_to_string(&mut xml)
+            .with_context(|| format!("解压站点地图 {} 失败", url))?;
+        return Ok(xml);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// 开发模式的开启状态、剩余时间与计划的自动关闭时间
+async fn dev_mode_status(client: &CfClient, config: &AppConfig, domain: &str, format: &str) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let zone = client.get_zone(&zone_id).await?;
+    // development_mode 为距离到期的秒数，已到期时为负数
+    let remaining = zone.development_mode.filter(|secs| *secs > 0);
+    let scheduled = config.schedules.iter().find(|s| {
+        s.at.is_some() && s.setting == "development_mode" && (s.domain == domain || s.domain == zone.name || s.domain == zone_id)
+    });
+
+    if format == "json" {
+        output::print_json(&serde_json::json!({
+            "development_mode": if remaining.is_some() { "on" } else { "off" },
+            "time_remaining": remaining.unwrap_or(0),
+            "scheduled_off": scheduled.and_then(|s| s.at.clone()),
+        }));
+        return Ok(());
+    }
+
+    output::title(&format!("开发模式 - {}", zone.name));
+    match remaining {
+        Some(secs) => {
+            output::kv("状态", &output::status_badge("on"));
+            output::kv("剩余时间", &format!("{} 小时 {} 分", secs / 3600, secs % 3600 / 60));
+        }
+        None => output::kv("状态", &output::status_badge("off")),
+    }
+    if let Some(entry) = scheduled {
+        output::kv("自动关闭", &format!("{} (定时设置 #{})", entry.describe(), entry.id));
+    }
+    Ok(())
+}
+
+/// 解析 `--for` 时长 (如 30m、1h、90m)，开发模式最长持续 3 小时
+fn parse_dev_mode_duration(input: &str) -> Result<chrono::Duration> {
+    let invalid = || ValidationError(format!("无效的时长: {} (如 30m、1h)", input));
+    let trimmed = input.trim();
+    let unit = trimmed.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = trimmed[..trimmed.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    let duration = match unit {
+        'm' => chrono::TimeDelta::try_minutes(amount),
+        'h' => chrono::TimeDelta::try_hours(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    if duration < chrono::Duration::minutes(1) || duration > chrono::Duration::hours(3) {
+        anyhow::bail!(ValidationError(format!("时长应在 1 分钟到 3 小时之间: {}", input)));
+    }
+    Ok(duration)
+}
+
+/// 分批清除，条目超过单批上限时逐批报告进度
+async fn purge_batched(
+    client: &CfClient,
+    zone_id: &str,
+    target: PurgeTarget,
+    items: &[String],
+) -> Result<()> {
+    let batched = items.len() > PURGE_BATCH_SIZE;
+    if batched {
+        output::info(&format!(
+            "共 {} 个{}，将分 {} 批清除 (每批最多 {} 个)",
+            items.len(),
+            target.label(),
+            items.len().div_ceil(PURGE_BATCH_SIZE),
+            PURGE_BATCH_SIZE
+        ));
+    }
+    let mut purged = 0;
+    let result = client
+        .purge_cache_batched(zone_id, target, items, |batch, total, count| {
+            purged += count;
+            if batched {
+                output::progress(batch, total, &format!("已清除 {} 个{}", count, target.label()));
+            }
+        })
+        .await;
+    match result {
+        Err(e) if purged > 0 => Err(e.context(PartialFailure {
+            total: items.len(),
+            failed: items.len() - purged,
+        })),
+        other => other,
+    }
+}
+
+/// 读取列表文件，忽略空行和 # 注释
+fn read_list_file(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("读取文件失败: {}", path.display()))?;
+    Ok(content
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dev_mode_duration() {
+        assert_eq!(parse_dev_mode_duration("90m").unwrap(), chrono::Duration::minutes(90));
+        assert_eq!(parse_dev_mode_duration("1h").unwrap(), chrono::Duration::hours(1));
+        assert!(parse_dev_mode_duration("4h").is_err());
+        assert!(parse_dev_mode_duration("30s").is_err());
+        // 超出范围的数值报错而不是 panic
+        assert!(parse_dev_mode_duration("9999999999999999h").is_err());
+    }
+}
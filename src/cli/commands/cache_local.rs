@@ -0,0 +1,34 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::cli::output;
+use crate::config::{response_cache, zone_cache};
+
+#[derive(Args, Debug)]
+pub struct CacheLocalArgs {
+    #[command(subcommand)]
+    pub command: CacheLocalCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheLocalCommands {
+    /// 清除本地缓存 (域名列表、Zone ID 与 API 响应缓存，新增或删除域名后未及时更新时使用)
+    Clear,
+}
+
+impl CacheLocalArgs {
+    pub async fn execute(&self) -> Result<()> {
+        match &self.command {
+            CacheLocalCommands::Clear => {
+                let zones = zone_cache::clear()?;
+                let responses = response_cache::clear()?;
+                if zones || responses {
+                    output::success("本地缓存已清除");
+                } else {
+                    output::info("本地缓存为空");
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,8 +2,15 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use dialoguer::{theme::ColorfulTheme, Input, Select};
 
+use crate::ai::analyzer::AiAnalyzer;
+use crate::ai::provider::AiProvider;
+use crate::api::client::{CfClient, CF_API_BASE};
+use crate::api::error::ValidationError;
 use crate::cli::output;
-use crate::config::settings::AppConfig;
+use crate::cli::prompt;
+use crate::config::secrets;
+use crate::config::settings::{AppConfig, CloudflareConfig, DEFAULT_PROFILE, GUI_REFRESH_PAGES};
+use crate::models::token::PermissionScope;
 
 #[derive(Args, Debug)]
 pub struct ConfigArgs {
@@ -39,25 +46,99 @@ pub enum ConfigCommands {
 
     /// 验证配置
     Verify,
+
+    /// 查看当前 API Token 的权限组与资源范围，并提示缺少权限的功能
+    #[command(name = "token-info")]
+    TokenInfo,
+
+    /// 将配置文件中的 API Token / Key 迁移到系统钥匙串 (并开启 secure_storage)
+    #[command(name = "migrate-secrets")]
+    MigrateSecrets {
+        /// 反向迁移: 从钥匙串写回配置文件并关闭 secure_storage
+        #[arg(long)]
+        to_file: bool,
+    },
+
+    /// 管理多账户 Profile
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProfileCommands {
+    /// 列出所有 Profile
+    #[command(alias = "ls")]
+    List,
+
+    /// 切换默认使用的 Profile (default 表示顶层 [cloudflare] 配置)
+    Use {
+        /// Profile 名称
+        name: String,
+    },
+
+    /// 添加或更新 Profile (未指定认证信息时交互式输入)
+    Add {
+        /// Profile 名称
+        name: String,
+        /// API Token
+        #[arg(long)]
+        api_token: Option<String>,
+        /// 邮箱 (配合 --api-key)
+        #[arg(long, requires = "api_key")]
+        email: Option<String>,
+        /// Global API Key (配合 --email)
+        #[arg(long, requires = "email")]
+        api_key: Option<String>,
+        /// 账户 ID
+        #[arg(long)]
+        account_id: Option<String>,
+    },
+
+    /// 删除 Profile
+    #[command(alias = "rm")]
+    Remove {
+        /// Profile 名称
+        name: String,
+    },
 }
 
 impl ConfigArgs {
-    pub async fn execute(&self) -> Result<()> {
+    /// 是否需要已认证的 Cloudflare 客户端 (由 main 创建后调用 `execute_with_client`)
+    pub fn needs_client(&self) -> bool {
+        matches!(self.command, ConfigCommands::TokenInfo)
+    }
+
+    /// 执行需要访问 Cloudflare API 的配置子命令
+    pub async fn execute_with_client(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        match &self.command {
+            ConfigCommands::TokenInfo => token_info(client, config, format).await,
+            _ => unreachable!(),
+        }
+    }
+
+    pub async fn execute(&self, profile: Option<&str>) -> Result<()> {
         match &self.command {
             ConfigCommands::Setup => {
-                AppConfig::interactive_setup()?;
+                prompt::require_interactive("请使用 `cfai config set <配置项> <值>` 或环境变量配置")?;
+                AppConfig::interactive_setup().await?;
             }
 
             ConfigCommands::Edit => {
+                prompt::require_interactive("请使用 `cfai config set <配置项> <值>` 修改配置")?;
                 interactive_edit()?;
             }
 
             ConfigCommands::Show { show_secrets } => {
-                let config = AppConfig::load()?.merge_env();
+                let config = AppConfig::load_effective(profile)?;
 
                 output::title("当前配置");
 
-                output::info("Cloudflare:");
+                output::info(&format!(
+                    "Cloudflare (Profile: {}):",
+                    config.selected_profile(profile).unwrap_or(DEFAULT_PROFILE)
+                ));
                 output::kv(
                     "API Token",
                     &mask_secret(
@@ -77,9 +158,19 @@ impl ConfigArgs {
                     "Account ID",
                     config.cloudflare.account_id.as_deref().unwrap_or("(未设置)"),
                 );
+                let retry = config.retry_policy();
+                output::kv(
+                    "凭据存储",
+                    if config.uses_secure_storage() { "系统钥匙串" } else { "配置文件 (明文)" },
+                );
+                output::kv(
+                    "请求重试",
+                    &format!("最多 {} 次，首次等待 {} ms", retry.max_retries, retry.base_delay.as_millis()),
+                );
 
                 println!();
                 output::info("AI:");
+                output::kv("接口类型", config.ai_provider().map(|p| p.name()).unwrap_or("(无效)"));
                 output::kv(
                     "API URL",
                     config.ai.api_url.as_deref().unwrap_or("(未设置)"),
@@ -108,6 +199,158 @@ impl ConfigArgs {
                         .map(|t| t.to_string())
                         .unwrap_or("(默认)".into()),
                 );
+                output::kv(
+                    "流式输出",
+                    if config.ai.stream.unwrap_or(true) { "开启" } else { "关闭" },
+                );
+                output::kv(
+                    "工具调用",
+                    if config.ai.tools.unwrap_or(true) { "开启" } else { "关闭" },
+                );
+                output::kv(
+                    "单价 (美元/千 Token)",
+                    &match (config.ai.input_price_per_1k, config.ai.output_price_per_1k) {
+                        (None, None) => "(未设置)".to_string(),
+                        (input, output) => format!(
+                            "输入 {} / 输出 {}",
+                            input.unwrap_or(0.0),
+                            output.unwrap_or(0.0)
+                        ),
+                    },
+                );
+                output::kv(
+                    "月度预算",
+                    &config
+                        .ai
+                        .monthly_budget
+                        .map(|b| {
+                            format!(
+                                "${} (超出时{})",
+                                b,
+                                if config.ai.budget_action.as_deref() == Some("block") { "阻止" } else { "提示" }
+                            )
+                        })
+                        .unwrap_or("(未设置)".into()),
+                );
+                output::kv(
+                    "报告 Webhook",
+                    &mask_secret(config.ai.report_webhook.as_deref(), *show_secrets),
+                );
+
+                println!();
+                output::info("网络:");
+                output::kv(
+                    "API 地址",
+                    config.network.api_base_url.as_deref().unwrap_or(CF_API_BASE),
+                );
+                output::kv(
+                    "代理",
+                    config.network.proxy_url.as_deref().unwrap_or("(未设置)"),
+                );
+                output::kv(
+                    "根证书",
+                    config.network.ca_bundle_path.as_deref().unwrap_or("(未设置)"),
+                );
+                output::kv(
+                    "请求超时",
+                    &config
+                        .network
+                        .timeout_secs
+                        .map(|t| format!("{} 秒", t))
+                        .unwrap_or("(默认)".into()),
+                );
+
+                println!();
+                output::info("通知:");
+                output::kv(
+                    "通知时机",
+                    match config.notify.on.as_deref() {
+                        Some("always") => "变更命令完成时",
+                        Some("failure") => "变更命令失败时",
+                        _ => "不通知 (可用 --notify 单次开启)",
+                    },
+                );
+                output::kv(
+                    "Slack",
+                    &mask_secret(config.notify.slack_webhook.as_deref(), *show_secrets),
+                );
+                output::kv(
+                    "Discord",
+                    &mask_secret(config.notify.discord_webhook.as_deref(), *show_secrets),
+                );
+                output::kv(
+                    "Webhook",
+                    &mask_secret(config.notify.webhook_url.as_deref(), *show_secrets),
+                );
+                output::kv(
+                    "邮件",
+                    &match (&config.notify.smtp_host, &config.notify.email_to) {
+                        (Some(host), Some(to)) => format!(
+                            "{}:{} → {}",
+                            host,
+                            config.notify.smtp_port.unwrap_or(587),
+                            to
+                        ),
+                        _ => "(未设置)".to_string(),
+                    },
+                );
+
+                println!();
+                output::info("自动防护 (guard):");
+                let guard = &config.guard;
+                let mut thresholds = Vec::new();
+                if let Some(rps) = guard.requests_per_second {
+                    thresholds.push(format!("{} 次/秒", rps));
+                }
+                if let Some(threats) = guard.threats {
+                    thresholds.push(format!("{} 次威胁", threats));
+                }
+                output::kv(
+                    "触发阈值",
+                    &if thresholds.is_empty() {
+                        "(未设置)".to_string()
+                    } else {
+                        thresholds.join(" 或 ")
+                    },
+                );
+                output::kv(
+                    "速率限制",
+                    &if guard.rate_limit.unwrap_or(false) {
+                        let (threshold, period, action) = guard.rate_limit_rule();
+                        format!("{} 次/{}s → {}", threshold, period, action)
+                    } else {
+                        "不创建".to_string()
+                    },
+                );
+
+                println!();
+                output::info("GUI 自动刷新:");
+                let refresh: Vec<String> = GUI_REFRESH_PAGES
+                    .iter()
+                    .filter_map(|page| config.gui.refresh_secs(page).map(|secs| format!("{} {}s", page, secs)))
+                    .collect();
+                output::kv(
+                    "刷新间隔",
+                    &if refresh.is_empty() { "(关闭)".to_string() } else { refresh.join(", ") },
+                );
+                let monitor = &config.gui.monitor;
+                output::kv("后台监控", if monitor.enabled { "开启" } else { "关闭" });
+                if monitor.enabled {
+                    output::kv(
+                        "监控域名",
+                        &if monitor.zones.is_empty() { "(当前选中的域名)".to_string() } else { monitor.zones.join(", ") },
+                    );
+                    output::kv(
+                        "告警条件",
+                        &format!(
+                            "每 {}s 检测，达到近期平均 {} 倍 (至少 {}/s 或 {} 次威胁)",
+                            monitor.interval_secs(),
+                            monitor.spike_factor(),
+                            monitor.min_requests_per_second(),
+                            monitor.min_threats()
+                        ),
+                    );
+                }
 
                 println!();
                 output::info("默认设置:");
@@ -129,32 +372,246 @@ impl ConfigArgs {
                 let mut config = AppConfig::load()?.merge_env();
 
                 match key.as_str() {
-                    "cloudflare.api_token" => config.cloudflare.api_token = Some(value.clone()),
-                    "cloudflare.email" => config.cloudflare.email = Some(value.clone()),
-                    "cloudflare.api_key" => config.cloudflare.api_key = Some(value.clone()),
-                    "cloudflare.account_id" => config.cloudflare.account_id = Some(value.clone()),
+                    "cloudflare.api_token" => config.cloudflare_mut(profile)?.api_token = Some(value.clone()),
+                    "cloudflare.email" => config.cloudflare_mut(profile)?.email = Some(value.clone()),
+                    "cloudflare.api_key" => config.cloudflare_mut(profile)?.api_key = Some(value.clone()),
+                    "cloudflare.account_id" => config.cloudflare_mut(profile)?.account_id = Some(value.clone()),
+                    "secure_storage" => {
+                        config.secure_storage = Some(value.parse().map_err(|_| {
+                            ValidationError("secure_storage 必须是 true 或 false".into())
+                        })?);
+                    }
+                    "cloudflare.max_retries" => {
+                        config.cloudflare_mut(profile)?.max_retries = Some(value.parse().map_err(|_| {
+                            ValidationError("max_retries 必须是非负整数".into())
+                        })?);
+                    }
+                    "cloudflare.retry_backoff_ms" => {
+                        config.cloudflare_mut(profile)?.retry_backoff_ms = Some(value.parse().map_err(|_| {
+                            ValidationError("retry_backoff_ms 必须是非负整数 (毫秒)".into())
+                        })?);
+                    }
+                    "ai.provider" => {
+                        let old = config.ai_provider().unwrap_or_default();
+                        let new = AiProvider::parse(Some(value.as_str()))?;
+                        // 地址与模型仍是原接口的默认值时随接口类型切换
+                        if config.ai.api_url.as_deref().is_none_or(|u| u == old.default_api_url()) {
+                            config.ai.api_url = Some(new.default_api_url().to_string());
+                        }
+                        if config.ai.model.as_deref().is_none_or(|m| m == old.default_model()) {
+                            config.ai.model = Some(new.default_model().to_string());
+                        }
+                        config.ai.provider = Some(new.name().to_string());
+                    }
                     "ai.api_url" => config.ai.api_url = Some(value.clone()),
                     "ai.api_key" => config.ai.api_key = Some(value.clone()),
                     "ai.model" => config.ai.model = Some(value.clone()),
                     "ai.max_tokens" => {
                         config.ai.max_tokens = Some(value.parse().map_err(|_| {
-                            anyhow::anyhow!("max_tokens 必须是数字")
+                            ValidationError("max_tokens 必须是数字".into())
                         })?);
                     }
                     "ai.temperature" => {
                         config.ai.temperature = Some(value.parse().map_err(|_| {
-                            anyhow::anyhow!("temperature 必须是数字")
+                            ValidationError("temperature 必须是数字".into())
+                        })?);
+                    }
+                    "ai.stream" => {
+                        config.ai.stream = Some(value.parse().map_err(|_| {
+                            ValidationError("stream 必须是 true 或 false".into())
+                        })?);
+                    }
+                    "ai.tools" => {
+                        config.ai.tools = Some(value.parse().map_err(|_| {
+                            ValidationError("tools 必须是 true 或 false".into())
+                        })?);
+                    }
+                    "network.api_base_url" => {
+                        if !value.starts_with("http://") && !value.starts_with("https://") {
+                            anyhow::bail!(ValidationError(format!(
+                                "API 地址必须以 http:// 或 https:// 开头: {}",
+                                value
+                            )));
+                        }
+                        config.network.api_base_url = Some(value.clone());
+                    }
+                    "network.proxy_url" => config.network.proxy_url = Some(value.clone()),
+                    "network.ca_bundle_path" => {
+                        if !std::path::Path::new(value).is_file() {
+                            anyhow::bail!(ValidationError(format!("根证书文件不存在: {}", value)));
+                        }
+                        config.network.ca_bundle_path = Some(value.clone());
+                    }
+                    "network.timeout_secs" => {
+                        config.network.timeout_secs = Some(value.parse().ok().filter(|t| *t > 0).ok_or_else(|| {
+                            ValidationError("timeout_secs 必须是正整数 (秒)".into())
+                        })?);
+                    }
+                    "ai.input_price_per_1k" | "ai.output_price_per_1k" | "ai.monthly_budget" => {
+                        let amount: f64 = value.parse().ok().filter(|v: &f64| *v >= 0.0).ok_or_else(|| {
+                            ValidationError(format!("{} 必须是非负数 (美元)", key))
+                        })?;
+                        match key.as_str() {
+                            "ai.input_price_per_1k" => config.ai.input_price_per_1k = Some(amount),
+                            "ai.output_price_per_1k" => config.ai.output_price_per_1k = Some(amount),
+                            _ => config.ai.monthly_budget = Some(amount),
+                        }
+                    }
+                    "ai.budget_action" => {
+                        if !matches!(value.as_str(), "warn" | "block") {
+                            anyhow::bail!(ValidationError("budget_action 必须是 warn 或 block".into()));
+                        }
+                        config.ai.budget_action = Some(value.clone());
+                    }
+                    "ai.report_webhook" => {
+                        if !value.starts_with("http://") && !value.starts_with("https://") {
+                            anyhow::bail!(ValidationError("report_webhook 必须是 http(s) 地址".into()));
+                        }
+                        config.ai.report_webhook = Some(value.clone());
+                    }
+                    "notify.on" => {
+                        if !matches!(value.as_str(), "never" | "failure" | "always") {
+                            anyhow::bail!(ValidationError("notify.on 必须是 never、failure 或 always".into()));
+                        }
+                        config.notify.on = Some(value.clone());
+                    }
+                    "notify.slack_webhook" | "notify.discord_webhook" | "notify.webhook_url" => {
+                        if !value.starts_with("http://") && !value.starts_with("https://") {
+                            anyhow::bail!(ValidationError(format!("{} 必须是 http(s) 地址", key)));
+                        }
+                        let slot = match key.as_str() {
+                            "notify.slack_webhook" => &mut config.notify.slack_webhook,
+                            "notify.discord_webhook" => &mut config.notify.discord_webhook,
+                            _ => &mut config.notify.webhook_url,
+                        };
+                        *slot = Some(value.clone());
+                    }
+                    "notify.smtp_host" => config.notify.smtp_host = Some(value.clone()),
+                    "notify.smtp_port" => {
+                        config.notify.smtp_port = Some(value.parse().map_err(|_| {
+                            ValidationError("smtp_port 必须是 1-65535 的端口号".into())
+                        })?);
+                    }
+                    "notify.smtp_username" => config.notify.smtp_username = Some(value.clone()),
+                    "notify.smtp_password" => config.notify.smtp_password = Some(value.clone()),
+                    "notify.email_from" => config.notify.email_from = Some(value.clone()),
+                    "notify.email_to" => config.notify.email_to = Some(value.clone()),
+                    "guard.requests_per_second" => {
+                        config.guard.requests_per_second =
+                            Some(value.parse().ok().filter(|v: &f64| *v > 0.0).ok_or_else(|| {
+                                ValidationError("requests_per_second 必须是正数".into())
+                            })?);
+                    }
+                    "guard.threats" => {
+                        config.guard.threats = Some(value.parse().ok().filter(|v| *v > 0).ok_or_else(|| {
+                            ValidationError("threats 必须是正整数".into())
+                        })?);
+                    }
+                    "guard.interval_secs" | "guard.cooldown_minutes" => {
+                        let number = value.parse().ok().filter(|v| *v > 0).ok_or_else(|| {
+                            ValidationError(format!("{} 必须是正整数", key))
+                        })?;
+                        match key.as_str() {
+                            "guard.interval_secs" => config.guard.interval_secs = Some(number),
+                            _ => config.guard.cooldown_minutes = Some(number),
+                        }
+                    }
+                    "guard.window_minutes" => {
+                        config.guard.window_minutes = Some(value.parse().ok().filter(|v| *v > 0).ok_or_else(|| {
+                            ValidationError("window_minutes 必须是正整数 (分钟)".into())
+                        })?);
+                    }
+                    "guard.rate_limit" => {
+                        config.guard.rate_limit = Some(value.parse().map_err(|_| {
+                            ValidationError("rate_limit 必须是 true 或 false".into())
+                        })?);
+                    }
+                    "guard.rate_limit_threshold" | "guard.rate_limit_period" => {
+                        let number = value.parse().ok().filter(|v| *v > 0).ok_or_else(|| {
+                            ValidationError(format!("{} 必须是正整数", key))
+                        })?;
+                        match key.as_str() {
+                            "guard.rate_limit_threshold" => config.guard.rate_limit_threshold = Some(number),
+                            _ => config.guard.rate_limit_period = Some(number),
+                        }
+                    }
+                    "guard.rate_limit_action" => {
+                        if !matches!(value.as_str(), "ban" | "challenge" | "js_challenge" | "managed_challenge") {
+                            anyhow::bail!(ValidationError(
+                                "rate_limit_action 必须是 ban、challenge、js_challenge 或 managed_challenge".into()
+                            ));
+                        }
+                        config.guard.rate_limit_action = Some(value.clone());
+                    }
+                    key if key.starts_with("gui.refresh.") => {
+                        let page = &key["gui.refresh.".len()..];
+                        if !GUI_REFRESH_PAGES.contains(&page) {
+                            anyhow::bail!(ValidationError(format!(
+                                "未知的页面: {} (可选: {})",
+                                page,
+                                GUI_REFRESH_PAGES.join(", ")
+                            )));
+                        }
+                        let secs = value.parse().map_err(|_| {
+                            ValidationError(format!("{} 必须是秒数 (0 表示关闭自动刷新)", key))
+                        })?;
+                        config.gui.refresh.insert(page.to_string(), secs);
+                    }
+                    "gui.monitor.enabled" => {
+                        config.gui.monitor.enabled = value.parse().map_err(|_| {
+                            ValidationError("gui.monitor.enabled 必须是 true 或 false".into())
+                        })?;
+                    }
+                    "gui.monitor.zones" => {
+                        config.gui.monitor.zones = value
+                            .split(',')
+                            .map(|z| z.trim().to_string())
+                            .filter(|z| !z.is_empty())
+                            .collect();
+                    }
+                    "gui.monitor.interval_secs" | "gui.monitor.min_threats" => {
+                        let number = value.parse().ok().filter(|v| *v > 0).ok_or_else(|| {
+                            ValidationError(format!("{} 必须是正整数", key))
+                        })?;
+                        match key.as_str() {
+                            "gui.monitor.interval_secs" => config.gui.monitor.interval_secs = Some(number),
+                            _ => config.gui.monitor.min_threats = Some(number),
+                        }
+                    }
+                    "gui.monitor.spike_factor" => {
+                        config.gui.monitor.spike_factor =
+                            Some(value.parse().ok().filter(|v: &f64| *v > 1.0).ok_or_else(|| {
+                                ValidationError("spike_factor 必须大于 1".into())
+                            })?);
+                    }
+                    "gui.monitor.min_requests_per_second" => {
+                        config.gui.monitor.min_requests_per_second =
+                            Some(value.parse().ok().filter(|v: &f64| *v >= 0.0).ok_or_else(|| {
+                                ValidationError("min_requests_per_second 不能为负数".into())
+                            })?);
+                    }
+                    "response_cache.enabled" => {
+                        config.response_cache.enabled = value.parse().map_err(|_| {
+                            ValidationError("response_cache.enabled 必须是 true 或 false".into())
+                        })?;
+                    }
+                    "response_cache.ttl_secs" => {
+                        config.response_cache.ttl_secs = Some(value.parse().ok().filter(|v| *v > 0).ok_or_else(|| {
+                            ValidationError("ttl_secs 必须是正整数 (秒)".into())
                         })?);
                     }
                     "defaults.domain" => config.defaults.domain = Some(value.clone()),
                     "defaults.output_format" => {
                         config.defaults.output_format = Some(value.clone());
                     }
-                    _ => anyhow::bail!("未知的配置项: {}\n可用配置项: cloudflare.api_token, cloudflare.email, cloudflare.api_key, cloudflare.account_id, ai.api_url, ai.api_key, ai.model, ai.max_tokens, ai.temperature, defaults.domain, defaults.output_format", key),
+                    _ => anyhow::bail!(ValidationError(format!("未知的配置项: {}\n可用配置项: secure_storage, cloudflare.api_token, cloudflare.email, cloudflare.api_key, cloudflare.account_id, cloudflare.max_retries, cloudflare.retry_backoff_ms, ai.provider, ai.api_url, ai.api_key, ai.model, ai.max_tokens, ai.temperature, ai.stream, ai.tools, ai.input_price_per_1k, ai.output_price_per_1k, ai.monthly_budget, ai.budget_action, ai.report_webhook, network.api_base_url, network.proxy_url, network.ca_bundle_path, network.timeout_secs, notify.on, notify.slack_webhook, notify.discord_webhook, notify.webhook_url, notify.smtp_host, notify.smtp_port, notify.smtp_username, notify.smtp_password, notify.email_from, notify.email_to, guard.requests_per_second, guard.threats, guard.interval_secs, guard.window_minutes, guard.cooldown_minutes, guard.rate_limit, guard.rate_limit_threshold, guard.rate_limit_period, guard.rate_limit_action, gui.refresh.<页面>, gui.monitor.enabled, gui.monitor.zones, gui.monitor.interval_secs, gui.monitor.spike_factor, gui.monitor.min_requests_per_second, gui.monitor.min_threats, response_cache.enabled, response_cache.ttl_secs, defaults.domain, defaults.output_format", key))),
                 }
 
                 config.save()?;
-                output::success(&format!("配置 {} 已更新", key));
+                match config.selected_profile(profile).filter(|_| key.starts_with("cloudflare.")) {
+                    Some(name) => output::success(&format!("Profile {} 的配置 {} 已更新", name, key)),
+                    None => output::success(&format!("配置 {} 已更新", key)),
+                }
             }
 
             ConfigCommands::Path => {
@@ -163,7 +620,7 @@ impl ConfigArgs {
             }
 
             ConfigCommands::Verify => {
-                let config = AppConfig::load()?.merge_env();
+                let config = AppConfig::load_effective(profile)?;
 
                 output::title("验证配置");
 
@@ -172,10 +629,22 @@ impl ConfigArgs {
                     Ok(()) => output::success("Cloudflare 认证配置 ✓"),
                     Err(e) => output::error(&format!("Cloudflare 认证: {}", e)),
                 }
+                if let Some(name) = config.selected_profile(profile) {
+                    output::info(&format!("当前 Profile: {}", name));
+                }
 
-                // 检查 AI 配置
+                // 检查 AI 配置，并发送测试请求确认 Key 与模型可用
                 if config.ai.api_key.is_some() {
                     output::success("AI API Key 已配置 ✓");
+                    let model = config.ai_model();
+                    match AiAnalyzer::new(&config)?.probe().await {
+                        Ok(latency) => output::success(&format!(
+                            "AI 模型 {} 可用 ✓ (响应 {} ms)",
+                            model,
+                            latency.as_millis()
+                        )),
+                        Err(e) => output::error(&format!("AI 服务 ({}): {:#}", model, e)),
+                    }
                 } else {
                     output::warn("AI API Key 未配置 (AI 功能将不可用)");
                 }
@@ -187,12 +656,233 @@ impl ConfigArgs {
                     output::warn("Account ID 未配置 (Workers 等功能将不可用)");
                 }
             }
+
+            ConfigCommands::MigrateSecrets { to_file } => {
+                // 不合并环境变量，避免把临时凭据写入钥匙串或配置文件
+                let mut config = AppConfig::load()?;
+
+                if *to_file {
+                    if !config.uses_secure_storage() {
+                        output::info("未开启 secure_storage，凭据已保存在配置文件中");
+                        return Ok(());
+                    }
+                    let keys: Vec<String> = config.secret_slots().into_iter().map(|(k, _)| k).collect();
+                    config.secure_storage = None;
+                    config.save()?;
+                    for key in &keys {
+                        secrets::delete(key)?;
+                    }
+                    output::success("凭据已写回配置文件，并已从系统钥匙串中删除");
+                    output::warn(&format!("配置文件以明文保存凭据: {}", AppConfig::config_path()?.display()));
+                    return Ok(());
+                }
+
+                if !secrets::is_supported() {
+                    anyhow::bail!("当前构建未启用 keyring 特性，无法使用系统钥匙串");
+                }
+                let count = config.secret_slots().iter().filter(|(_, v)| v.is_some()).count();
+                config.secure_storage = Some(true);
+                config.save()?;
+
+                output::success(&format!("已将 {} 项凭据迁移到系统钥匙串", count));
+                output::info("配置文件中不再保存 API Token / Key，之后的修改会自动写入钥匙串");
+            }
+
+            ConfigCommands::Profile { command } => profile_command(command, profile)?,
+            ConfigCommands::TokenInfo => unreachable!(),
         }
 
         Ok(())
     }
 }
 
+/// 显示 Token 状态、权限组与资源范围，并检查 cfai 各功能所需的权限
+async fn token_info(client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+    if config.cloudflare.api_token.is_none() {
+        output::info("当前使用 Email + Global API Key 认证，拥有账户的全部权限");
+        return Ok(());
+    }
+
+    let verification = client.verify_token_details().await?;
+    let token = client.get_api_token(&verification.id).await;
+
+    if format == "json" {
+        output::print_json(&serde_json::json!({
+            "verification": verification,
+            "token": token.as_ref().ok(),
+            "missing_features": token
+                .as_ref()
+                .map(|t| t.missing_features().iter().map(|r| r.feature).collect::<Vec<_>>())
+                .ok(),
+        }));
+        return Ok(());
+    }
+
+    output::title("API Token 信息");
+    output::kv("Token ID", &verification.id);
+    output::kv_colored("状态", &verification.status, verification.status == "active");
+    output::kv("生效时间", verification.not_before.as_deref().unwrap_or("-"));
+    output::kv("过期时间", verification.expires_on.as_deref().unwrap_or("永不过期"));
+
+    let token = match token {
+        Ok(token) => token,
+        Err(e) => {
+            output::warn(&format!("无法读取 Token 权限详情: {:#}", e));
+            output::tip("为 Token 添加 \"User → API Tokens → Read\" 权限后可查看权限组与资源范围");
+            return Ok(());
+        }
+    };
+    if let Some(name) = &token.name {
+        output::kv("名称", name);
+    }
+
+    let policies = token.policies.clone().unwrap_or_default();
+    println!();
+    let mut table = output::create_table(vec!["效果", "资源范围", "权限组"]);
+    for p in &policies {
+        table.add_row(vec![
+            p.effect.clone(),
+            p.scope_labels().join("\n"),
+            p.permission_groups
+                .iter()
+                .map(|g| g.name.clone().unwrap_or_else(|| g.id.clone()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ]);
+    }
+    println!("{table}");
+
+    let missing = token.missing_features();
+    if missing.is_empty() {
+        output::success("Token 权限覆盖 cfai 的全部功能");
+        return Ok(());
+    }
+    println!();
+    output::warn(&format!("以下 {} 项功能缺少所需权限:", missing.len()));
+    for r in missing {
+        let scope = match r.scope {
+            PermissionScope::Zone => "域名",
+            PermissionScope::Account => "账户",
+        };
+        println!("  • {} — 需要{}级权限: {}", r.feature, scope, r.permissions.join(" / "));
+    }
+    Ok(())
+}
+
+/// 执行 Profile 管理命令
+fn profile_command(command: &ProfileCommands, selected: Option<&str>) -> Result<()> {
+    let mut config = AppConfig::load()?;
+
+    match command {
+        ProfileCommands::List => {
+            let current = config.selected_profile(selected).unwrap_or(DEFAULT_PROFILE).to_string();
+            let describe = |cf: &CloudflareConfig| {
+                if cf.api_token.is_some() {
+                    "API Token".to_string()
+                } else if let Some(email) = &cf.email {
+                    format!("API Key ({})", email)
+                } else {
+                    "(未配置)".to_string()
+                }
+            };
+
+            output::title(&format!("配置 Profile (共 {} 个)", config.profiles.len() + 1));
+            let mut table = output::create_table(vec!["", "名称", "认证方式", "Account ID"]);
+            let rows = std::iter::once((DEFAULT_PROFILE, &config.cloudflare))
+                .chain(config.profiles.iter().map(|(name, cf)| (name.as_str(), cf)));
+            for (name, cf) in rows {
+                table.add_row(vec![
+                    if name == current { "*" } else { "" },
+                    name,
+                    &describe(cf),
+                    cf.account_id.as_deref().unwrap_or("-"),
+                ]);
+            }
+            println!("{table}");
+            output::tip("使用 `cfai config profile use <名称>` 切换，或在任意命令后加 `--profile <名称>` 临时使用");
+        }
+
+        ProfileCommands::Use { name } => {
+            if name == DEFAULT_PROFILE {
+                config.active_profile = None;
+            } else {
+                config.profile(name)?;
+                config.active_profile = Some(name.clone());
+            }
+            config.save()?;
+            output::success(&format!("已切换到 Profile {}", name));
+        }
+
+        ProfileCommands::Add {
+            name,
+            api_token,
+            email,
+            api_key,
+            account_id,
+        } => {
+            if name == DEFAULT_PROFILE {
+                anyhow::bail!(ValidationError(format!("{} 是保留名称，请使用 `cfai config set cloudflare.*` 修改顶层配置", DEFAULT_PROFILE)));
+            }
+            if name.trim().is_empty() || name.contains(['.', ' ']) {
+                anyhow::bail!(ValidationError("Profile 名称不能为空，且不能包含空格或 '.'".into()));
+            }
+
+            let mut cf = config.profiles.get(name).cloned().unwrap_or_default();
+            if api_token.is_some() {
+                cf.api_token = api_token.clone();
+                cf.email = None;
+                cf.api_key = None;
+            } else if email.is_some() {
+                cf.api_token = None;
+                cf.email = email.clone();
+                cf.api_key = api_key.clone();
+            } else if cf.api_token.is_none() && cf.api_key.is_none() {
+                prompt::require_interactive("请通过 --api-token 或 --email/--api-key 指定凭据")?;
+                let token: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("请输入 Profile {} 的 Cloudflare API Token", name))
+                    .interact_text()?;
+                cf.api_token = Some(token.trim().to_string());
+            }
+            if account_id.is_some() {
+                cf.account_id = account_id.clone();
+            }
+
+            // 切换认证方式时清除的凭据是用户主动清除的，保存时从钥匙串删除
+            for field in ["api_token", "api_key"] {
+                config.unreadable_secrets.remove(&format!("profiles.{}.{}", name, field));
+            }
+            let existed = config.profiles.insert(name.clone(), cf).is_some();
+            config.save()?;
+            output::success(&format!("Profile {} 已{}", name, if existed { "更新" } else { "添加" }));
+            if !existed {
+                output::tip(&format!("运行 `cfai config profile use {}` 切换到该 Profile", name));
+            }
+        }
+
+        ProfileCommands::Remove { name } => {
+            if config.profiles.remove(name).is_none() {
+                anyhow::bail!("未找到 Profile: {}", name);
+            }
+            let was_active = config.active_profile.as_deref() == Some(name.as_str());
+            if was_active {
+                config.active_profile = None;
+            }
+            config.save()?;
+            if config.uses_secure_storage() {
+                for field in ["api_token", "api_key"] {
+                    secrets::delete(&format!("profiles.{}.{}", name, field))?;
+                }
+            }
+            output::success(&format!("Profile {} 已删除", name));
+            if was_active {
+                output::info(&format!("已切换回 Profile {}", DEFAULT_PROFILE));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// 交互式编辑配置
 fn interactive_edit() -> Result<()> {
     let theme = ColorfulTheme::default();
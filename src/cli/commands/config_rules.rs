@@ -0,0 +1,111 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::cli::commands::phase_rules::{delete_rule, list_rules, rule_expression};
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::models::rulesets::*;
+
+#[derive(Args, Debug)]
+pub struct ConfigRulesArgs {
+    #[command(subcommand)]
+    pub command: ConfigRulesCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigRulesCommands {
+    /// 列出配置规则
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 添加配置规则 (对匹配的请求覆盖域名设置)
+    Add {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 匹配的 URL 模式 (如 example.com/admin*，支持 * 通配符)
+        #[arg(long, required_unless_present = "expression")]
+        from: Option<String>,
+        /// 直接使用规则表达式匹配 (代替 --from)
+        #[arg(long, conflicts_with = "from")]
+        expression: Option<String>,
+        /// 覆盖的设置，如 rocket_loader=off、ssl=strict、security_level=high (可重复指定)
+        #[arg(long = "set", required = true)]
+        settings: Vec<String>,
+        /// 规则描述
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// 删除配置规则
+    #[command(alias = "rm")]
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则 ID
+        rule_id: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+impl ConfigRulesArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match &self.command {
+            ConfigRulesCommands::List { domain } => {
+                list_rules(client, domain, CONFIG_RULES_PHASE, "配置规则", format).await?;
+            }
+
+            ConfigRulesCommands::Add {
+                domain,
+                from,
+                expression,
+                settings,
+                description,
+            } => {
+                let expression = rule_expression(from.as_deref(), expression.as_deref())?;
+                let mut params = serde_json::Map::new();
+                for pair in settings {
+                    let (key, value) = parse_config_setting(pair)?;
+                    if params.insert(key.clone(), value).is_some() {
+                        anyhow::bail!(ValidationError(format!("配置项 {} 重复指定", key)));
+                    }
+                }
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let rule = RulesetRuleRequest {
+                    action: "set_config".into(),
+                    expression: expression.clone(),
+                    description: Some(description.clone().unwrap_or_else(|| {
+                        format!("{}: {}", from.as_deref().unwrap_or(&expression), settings.join(", "))
+                    })),
+                    enabled: Some(true),
+                    action_parameters: Some(serde_json::Value::Object(params)),
+                };
+                let result = client.add_phase_rule(&zone_id, CONFIG_RULES_PHASE, &rule).await?;
+
+                if format == "json" {
+                    output::print_json(&result);
+                    return Ok(());
+                }
+                output::success(&format!("配置规则已添加 ({})", settings.join(", ")));
+                output::kv("匹配表达式", &expression);
+            }
+
+            ConfigRulesCommands::Delete {
+                domain,
+                rule_id,
+                yes,
+            } => {
+                delete_rule(client, domain, CONFIG_RULES_PHASE, "配置规则", rule_id, *yes).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::config::settings::AppConfig;
+use crate::models::d1::*;
+
+#[derive(Args, Debug)]
+pub struct D1Args {
+    #[command(subcommand)]
+    pub command: D1Commands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum D1Commands {
+    /// 列出 D1 数据库
+    #[command(alias = "ls")]
+    List,
+
+    /// 创建 D1 数据库
+    Create {
+        /// 数据库名称
+        name: String,
+        /// 位置提示 (apac/eeur/enam/weur/wnam/oc)
+        #[arg(long)]
+        location: Option<String>,
+    },
+
+    /// 删除 D1 数据库
+    #[command(alias = "rm")]
+    Delete {
+        /// 数据库名称或 UUID
+        database: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// 执行 SQL 语句
+    #[command(alias = "exec")]
+    Query {
+        /// 数据库名称或 UUID
+        database: String,
+        /// SQL 语句 (多条语句用 ; 分隔)
+        sql: String,
+        /// 绑定到 ? 占位符的参数 (可重复指定)
+        #[arg(short, long = "param")]
+        params: Vec<String>,
+    },
+}
+
+impl D1Args {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        let account_id = config
+            .cloudflare
+            .account_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("D1 管理需要 Account ID，请运行 `cfai config setup`"))?;
+
+        match &self.command {
+            D1Commands::List => {
+                let databases = client.list_d1_databases(account_id).await?;
+
+                if format == "json" {
+                    output::print_json(&databases);
+                    return Ok(());
+                }
+
+                output::title(&format!("D1 数据库 (共 {} 个)", databases.len()));
+
+                if databases.is_empty() {
+                    output::info("没有 D1 数据库");
+                    return Ok(());
+                }
+
+                let mut table = output::create_table(vec!["UUID", "名称", "表数", "大小", "创建时间"]);
+                for db in &databases {
+                    table.add_row(vec![
+                        db.uuid.clone(),
+                        db.name.clone(),
+                        db.num_tables.map(|n| n.to_string()).unwrap_or("-".into()),
+                        db.file_size.map(output::format_bytes).unwrap_or("-".into()),
+                        db.created_at.clone().unwrap_or("-".into()),
+                    ]);
+                }
+                println!("{table}");
+            }
+
+            D1Commands::Create { name, location } => {
+                let request = CreateD1DatabaseRequest {
+                    name: name.clone(),
+                    primary_location_hint: location.clone(),
+                };
+                let db = client.create_d1_database(account_id, &request).await?;
+
+                if format == "json" {
+                    output::print_json(&db);
+                    return Ok(());
+                }
+                output::success(&format!("D1 数据库 {} 已创建", db.name));
+                output::kv("UUID", &db.uuid);
+            }
+
+            D1Commands::Delete { database, yes } => {
+                let db = resolve_d1_database(client, account_id, database).await?;
+
+                if !yes {
+                    let confirm = prompt::confirm(format!(
+                        "确定要删除 D1 数据库 {} 吗？所有数据将永久丢失",
+                        db.name.yellow()
+                    ))?;
+                    if !confirm {
+                        output::info("已取消");
+                        return Ok(());
+                    }
+                }
+
+                client.delete_d1_database(account_id, &db.uuid).await?;
+                output::success(&format!("D1 数据库 {} 已删除", db.name));
+            }
+
+            D1Commands::Query {
+                database,
+                sql,
+                params,
+            } => {
+                let db = resolve_d1_database(client, account_id, database).await?;
+                let request = D1QueryRequest {
+                    sql: sql.clone(),
+                    params: params.clone(),
+                };
+                let results = client.query_d1_database(account_id, &db.uuid, &request).await?;
+
+                if format == "json" {
+                    output::print_json(&results);
+                    return Ok(());
+                }
+
+                for (i, result) in results.iter().enumerate() {
+                    if results.len() > 1 {
+                        output::title(&format!("语句 {}", i + 1));
+                    }
+                    print_query_result(result);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 按 UUID 或名称查找数据库
+async fn resolve_d1_database(client: &CfClient, account_id: &str, database: &str) -> Result<D1Database> {
+    let databases = client.list_d1_databases(account_id).await?;
+    databases
+        .into_iter()
+        .find(|db| db.uuid == database || db.name.eq_ignore_ascii_case(database))
+        .ok_or_else(|| anyhow::anyhow!("未找到 D1 数据库: {}", database))
+}
+
+fn print_query_result(result: &D1QueryResult) {
+    let raw = result.results.clone().unwrap_or_default();
+
+    if raw.columns.is_empty() {
+        output::success("执行成功");
+    } else if raw.rows.is_empty() {
+        output::info("查询结果为空");
+    } else {
+        let mut table = output::create_table(raw.columns.iter().map(|c| c.as_str()).collect());
+        for row in &raw.rows {
+            table.add_row(row.iter().map(format_cell).collect::<Vec<_>>());
+        }
+        println!("{table}");
+    }
+
+    if let Some(meta) = &result.meta {
+        let mut stats = vec![format!("{} 行", raw.rows.len())];
+        if let Some(changes) = meta.changes.filter(|c| *c > 0) {
+            stats.push(format!("修改 {} 行", changes));
+        }
+        if let Some(read) = meta.rows_read {
+            stats.push(format!("读取 {} 行", read));
+        }
+        if let Some(written) = meta.rows_written.filter(|w| *w > 0) {
+            stats.push(format!("写入 {} 行", written));
+        }
+        if let Some(duration) = meta.duration {
+            stats.push(format!("耗时 {:.2} ms", duration));
+        }
+        println!("{}", stats.join(" · ").dimmed());
+    }
+}
+
+fn format_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "NULL".dimmed().to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
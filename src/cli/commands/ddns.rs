@@ -0,0 +1,188 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::models::dns::{absolute_name, managed_comment, DnsRecordRequest};
+
+use super::zone::resolve_zone_id;
+
+/// 默认通过 Cloudflare trace 获取公网 IP
+const DEFAULT_IP_URL: &str = "https://cloudflare.com/cdn-cgi/trace";
+
+/// 动态 DNS: 检测本机公网 IP，与 A/AAAA 记录不一致时更新记录
+#[derive(Args, Debug)]
+pub struct DdnsArgs {
+    /// 域名或 Zone ID
+    pub domain: String,
+    /// 记录名称 (如 home、@ 或完整主机名)
+    pub name: String,
+    /// 同步 IPv6 地址 (AAAA 记录)，默认同步 IPv4 (A 记录)
+    #[arg(short = '6', long)]
+    pub ipv6: bool,
+    /// 获取公网 IP 的地址，响应为纯文本 IP 或 Cloudflare trace 格式 (默认使用 Cloudflare trace)
+    #[arg(long, value_name = "URL", env = "CFAI_DDNS_IP_URL")]
+    pub ip_url: Option<String>,
+    /// 记录不存在时创建为代理记录
+    #[arg(long)]
+    pub proxied: bool,
+    /// 持续运行，定期检测 (Ctrl+C 退出)
+    #[arg(long)]
+    pub daemon: bool,
+    /// 检测间隔 (秒，--daemon 时生效)
+    #[arg(short, long, default_value_t = 300)]
+    pub interval: u64,
+}
+
+impl DdnsArgs {
+    pub async fn execute(&self, client: &CfClient) -> Result<()> {
+        if self.daemon && self.interval < 30 {
+            anyhow::bail!(ValidationError("检测间隔不能小于 30 秒".into()));
+        }
+        let record_type = if self.ipv6 { "AAAA" } else { "A" };
+        let zone_id = resolve_zone_id(client, &self.domain).await?;
+        let zone = client.get_zone(&zone_id).await?;
+        let name = absolute_name(&self.name, &zone.name);
+        let http = ip_client(self.ipv6)?;
+        let url = self.ip_url.as_deref().unwrap_or(DEFAULT_IP_URL);
+
+        if !self.daemon {
+            return self.sync(client, &http, url, &zone_id, &name, record_type).await;
+        }
+
+        output::title(&format!("动态 DNS - {} {}", record_type, name));
+        output::kv("公网 IP 来源", url);
+        output::kv("检测间隔", &format!("{} 秒", self.interval));
+        println!();
+        loop {
+            tokio::select! {
+                result = self.sync(client, &http, url, &zone_id, &name, record_type) => {
+                    if let Err(e) = result {
+                        log(&format!("{} {:#}", "✗".red(), e));
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => break,
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(self.interval)) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// 检测一次公网 IP 并同步记录
+    async fn sync(
+        &self,
+        client: &CfClient,
+        http: &reqwest::Client,
+        url: &str,
+        zone_id: &str,
+        name: &str,
+        record_type: &str,
+    ) -> Result<()> {
+        let ip = detect_ip(http, url, self.ipv6).await?;
+        let records: Vec<_> = client
+            .list_all_dns_records(zone_id)
+            .await?
+            .into_iter()
+            .filter(|r| r.record_type == record_type && r.name.eq_ignore_ascii_case(name))
+            .collect();
+
+        if records.is_empty() {
+            let request = DnsRecordRequest {
+                record_type: record_type.to_string(),
+                name: name.to_string(),
+                content: ip.to_string(),
+                ttl: None,
+                proxied: Some(self.proxied),
+                priority: None,
+                comment: Some(managed_comment(None)),
+                tags: None,
+                data: None,
+            };
+            client.create_dns_record(zone_id, &request).await?;
+            log(&format!("{} 已创建 {} {} → {}", "✓".green(), record_type, name, ip));
+            return Ok(());
+        }
+
+        let mut changed = false;
+        for record in records.iter().filter(|r| r.content.parse::<IpAddr>().ok() != Some(ip)) {
+            let Some(id) = &record.id else { continue };
+            client
+                .patch_dns_record(zone_id, id, &serde_json::json!({ "content": ip.to_string() }))
+                .await?;
+            log(&format!("{} IP 已变化: {} {} → {}", "✓".green(), name, record.content, ip));
+            changed = true;
+        }
+        if !changed {
+            log(&format!("{} {} 未变化 ({})", "·".dimmed(), name, ip));
+        }
+        Ok(())
+    }
+}
+
+/// 检测公网 IP 使用的 HTTP 客户端
+///
+/// 绑定到对应协议族的本地地址，只通过 IPv4 或 IPv6 连接；不使用配置中的代理，否则检测到的是代理的地址。
+fn ip_client(ipv6: bool) -> Result<reqwest::Client> {
+    let local = if ipv6 {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    };
+    reqwest::Client::builder()
+        .local_address(local)
+        .no_proxy()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .context("创建 HTTP 客户端失败")
+}
+
+async fn detect_ip(http: &reqwest::Client, url: &str, ipv6: bool) -> Result<IpAddr> {
+    let body = http
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("获取公网 IP 失败: {}", url))?
+        .text()
+        .await
+        .context("读取公网 IP 响应失败")?;
+    let ip = parse_ip(&body).with_context(|| format!("无法从 {} 的响应中解析 IP 地址", url))?;
+    if ip.is_ipv6() != ipv6 {
+        anyhow::bail!("{} 返回的地址 {} 不是 {}", url, ip, if ipv6 { "IPv6" } else { "IPv4" });
+    }
+    Ok(ip)
+}
+
+/// 解析 Cloudflare trace (`ip=` 行) 或纯文本 IP
+fn parse_ip(body: &str) -> Option<IpAddr> {
+    let value = body
+        .lines()
+        .find_map(|line| line.strip_prefix("ip="))
+        .unwrap_or(body);
+    value.trim().parse().ok()
+}
+
+fn log(message: &str) {
+    println!("{} {}", format!("[{}]", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")).dimmed(), message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ip() {
+        let trace = "fl=123\nh=cloudflare.com\nip=203.0.113.9\nts=1700000000.1\n";
+        assert_eq!(parse_ip(trace), Some("203.0.113.9".parse().unwrap()));
+        assert_eq!(parse_ip("2001:db8::1\n"), Some("2001:db8::1".parse().unwrap()));
+        assert_eq!(parse_ip("<html>"), None);
+    }
+}
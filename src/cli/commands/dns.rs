@@ -1,11 +1,26 @@
-use anyhow::Result;
+use std::net::{IpAddr, SocketAddr};
+
+use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
 use colored::Colorize;
 
+use crate::ai::analyzer::AiAnalyzer;
+use crate::api::backup::split_unmanaged_dns_changes;
 use crate::api::client::CfClient;
+use crate::api::dns::plan_dns_template;
+use crate::api::error::ValidationError;
+use crate::cli::exit::PartialFailure;
 use crate::cli::output;
-use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::prompt;
+use crate::config::settings::AppConfig;
+use crate::cli::commands::ai::troubleshoot;
+use crate::cli::commands::backup;
+use crate::cli::commands::zone::{resolve_zone_id, zone_for_host};
 use crate::models::dns::*;
+use crate::models::dns_template::{parse_template_vars, DnsTemplate, DNS_TEMPLATES};
+use crate::models::email_security::*;
+use crate::progress::Progress;
+use crate::resolver;
 
 #[derive(Args, Debug)]
 pub struct DnsArgs {
@@ -49,21 +64,23 @@ pub enum DnsCommands {
         /// 记录名称 (如 www, @, sub)
         #[arg(short, long)]
         name: String,
-        /// 记录值
+        /// 记录值 (SRV、CAA 也可写成区域文件格式，如 "10 5 5060 sip.example.com")
         #[arg(short, long)]
-        content: String,
+        content: Option<String>,
         /// TTL (秒, 1=自动)
         #[arg(long, default_value = "1")]
         ttl: u32,
         /// 是否开启 Cloudflare 代理
         #[arg(short, long)]
         proxied: Option<bool>,
-        /// MX 优先级
+        /// MX / SRV 优先级
         #[arg(long)]
         priority: Option<u16>,
         /// 备注
         #[arg(long)]
         comment: Option<String>,
+        #[command(flatten)]
+        data: RecordDataArgs,
     },
 
     /// 更新 DNS 记录
@@ -87,9 +104,14 @@ pub enum DnsCommands {
         /// 是否开启代理
         #[arg(short, long)]
         proxied: Option<bool>,
+        /// MX / SRV 优先级
+        #[arg(long)]
+        priority: Option<u16>,
         /// 备注
         #[arg(long)]
         comment: Option<String>,
+        #[command(flatten)]
+        data: RecordDataArgs,
     },
 
     /// 删除 DNS 记录
@@ -104,6 +126,29 @@ pub enum DnsCommands {
         yes: bool,
     },
 
+    /// 开启或关闭记录的 Cloudflare 代理，名称中的 * 可匹配多条记录
+    Proxy {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 记录名称 (如 www、@、"*.example.com") 或记录 ID
+        record: String,
+        /// on 开启代理，off 只解析 DNS
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+        /// 只修改指定类型的记录 (A/AAAA/CNAME)
+        #[arg(short = 't', long)]
+        record_type: Option<String>,
+        /// 只预览变更，不执行
+        #[arg(long)]
+        dry_run: bool,
+        /// 通配符匹配时也修改不是由 cfai 创建的记录
+        #[arg(long)]
+        force: bool,
+        /// 跳过确认 (匹配多条记录时)
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
     /// 快速添加 A 记录
     #[command(name = "add-a")]
     AddA {
@@ -148,10 +193,215 @@ pub enum DnsCommands {
         #[arg(short = 't', long)]
         record_type: Option<String>,
     },
+
+    /// 检查记录在公共解析器上的生效情况 (修改记录后使用)
+    Check {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 记录名称 (如 www、@ 或完整主机名)
+        name: String,
+        /// 查询类型 (默认按现有记录推断，代理记录按 A 查询)
+        #[arg(short = 't', long)]
+        record_type: Option<String>,
+        /// 额外检查的解析器 IP (可重复)
+        #[arg(long = "resolver", value_name = "IP")]
+        resolvers: Vec<String>,
+    },
+
+    /// 检查 SPF、DKIM、DMARC 邮件安全记录，可让 AI 给出修复方案
+    EmailAudit {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 额外检查的 DKIM 选择器 (可重复；区域内的 *._domainkey 记录会自动检查)
+        #[arg(short, long = "selector")]
+        selectors: Vec<String>,
+        /// 不查询 include 引用的 SPF 记录 (只统计本域名记录中的查询次数)
+        #[arg(long)]
+        no_resolve: bool,
+        /// 将审计结果交给 AI 生成修复方案，确认后执行
+        #[arg(long)]
+        ai: bool,
+        /// 与 --ai 一起使用: 只预览 AI 建议的操作
+        #[arg(long, requires = "ai")]
+        dry_run: bool,
+    },
+
+    /// 根据区域内的记录说明 URL 或主机名由哪条记录应答 (含通配符与 CNAME 链)
+    Resolve {
+        /// URL 或主机名 (如 https://shop.example.com/cart)
+        url: String,
+        /// 查询类型 (默认 A/AAAA)
+        #[arg(short = 't', long)]
+        record_type: Option<String>,
+    },
+
+    /// 使用内置模板批量创建记录 (邮箱、托管平台等常见配置)
+    #[command(alias = "tpl")]
+    Template {
+        #[command(subcommand)]
+        command: DnsTemplateCommands,
+    },
+
+    /// DNSSEC 管理
+    Dnssec {
+        #[command(subcommand)]
+        command: DnssecCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DnssecCommands {
+    /// 查看 DNSSEC 状态与 DS 记录
+    Status {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 开启 DNSSEC (之后需在注册商处添加 DS 记录)
+    Enable {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 关闭 DNSSEC
+    Disable {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DnsTemplateCommands {
+    /// 列出内置模板
+    #[command(alias = "ls")]
+    List,
+
+    /// 查看模板包含的记录与变量
+    Show {
+        /// 模板名称
+        template: String,
+    },
+
+    /// 预览并应用模板
+    Apply {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 模板名称 (google-workspace/microsoft-365/github-pages/vercel/spf-dkim-dmarc-basic)
+        template: String,
+        /// 模板变量 (可重复，如 --var github_user=octocat)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+        /// 删除与模板冲突的现有记录后再创建
+        #[arg(long)]
+        replace: bool,
+        /// 与 --replace 一起使用时，允许删除不带 cfai 标记的记录
+        #[arg(long)]
+        force: bool,
+        /// 只预览变更，不执行
+        #[arg(long)]
+        dry_run: bool,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+/// SRV、CAA 等结构化记录的字段 (Cloudflare 通过 `data` 接收)
+#[derive(Args, Debug)]
+pub struct RecordDataArgs {
+    /// SRV 服务名 (如 _sip)，加在记录名称前
+    #[arg(long)]
+    srv_service: Option<String>,
+    /// SRV 协议 (如 _tcp、_udp)，加在记录名称前
+    #[arg(long)]
+    srv_proto: Option<String>,
+    /// SRV 权重
+    #[arg(long)]
+    srv_weight: Option<u16>,
+    /// SRV 端口
+    #[arg(long)]
+    srv_port: Option<u16>,
+    /// SRV 目标主机
+    #[arg(long)]
+    srv_target: Option<String>,
+    /// CAA 标志 (0，或 128 表示关键)
+    #[arg(long)]
+    caa_flags: Option<u8>,
+    /// CAA 标签
+    #[arg(long, value_parser = ["issue", "issuewild", "iodef"])]
+    caa_tag: Option<String>,
+    /// CAA 值 (如 letsencrypt.org、mailto:security@example.com)
+    #[arg(long)]
+    caa_value: Option<String>,
+    /// 其他结构化记录 (TLSA/SSHFP/URI 等) 的 data 字段 (可重复，如 --data usage=3)
+    #[arg(long = "data", value_name = "KEY=VALUE")]
+    fields: Vec<String>,
+}
+
+impl RecordDataArgs {
+    /// 按记录类型生成 data: 优先使用 --srv-* / --caa-* 参数，其次解析 SRV、CAA 的记录值；
+    /// --data 的字段合并在最后。其他类型且未指定 --data 时返回 None (使用 content)
+    fn build(&self, record_type: &str, priority: Option<u16>, content: Option<&str>) -> Result<Option<serde_json::Value>> {
+        let srv_name = self.srv_service.is_some() || self.srv_proto.is_some();
+        let srv = self.srv_weight.is_some() || self.srv_port.is_some() || self.srv_target.is_some();
+        let caa = self.caa_flags.is_some() || self.caa_tag.is_some() || self.caa_value.is_some();
+        if (srv || srv_name) && record_type != "SRV" {
+            anyhow::bail!(ValidationError("--srv-* 参数只能用于 SRV 记录".into()));
+        }
+        if caa && record_type != "CAA" {
+            anyhow::bail!(ValidationError("--caa-* 参数只能用于 CAA 记录".into()));
+        }
+
+        let mut data = match (record_type, content) {
+            ("SRV", _) if srv => {
+                let (Some(port), Some(target)) = (self.srv_port, &self.srv_target) else {
+                    anyhow::bail!(ValidationError("SRV 记录需要同时指定 --srv-port 与 --srv-target".into()));
+                };
+                Some(srv_data(priority.unwrap_or(0), self.srv_weight.unwrap_or(0), port, target))
+            }
+            ("CAA", _) if caa => {
+                let (Some(tag), Some(value)) = (&self.caa_tag, &self.caa_value) else {
+                    anyhow::bail!(ValidationError("CAA 记录需要同时指定 --caa-tag 与 --caa-value".into()));
+                };
+                Some(caa_data(self.caa_flags.unwrap_or(0), tag, value)?)
+            }
+            ("SRV", Some(content)) => Some(parse_srv_content(content, priority)?),
+            ("CAA", Some(content)) => Some(parse_caa_content(content)?),
+            _ => None,
+        };
+
+        if !self.fields.is_empty() {
+            let data = data.get_or_insert_with(|| serde_json::json!({}));
+            for field in &self.fields {
+                let (key, value) = field
+                    .split_once('=')
+                    .ok_or_else(|| ValidationError(format!("--data 格式应为 KEY=VALUE: {}", field)))?;
+                let value = serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+                data[key.trim()] = value;
+            }
+        }
+        Ok(data)
+    }
+
+    /// 记录名称: 指定 --srv-service/--srv-proto 时加上前缀，如 `_sip._tcp.voip`
+    fn record_name(&self, name: &str) -> Result<String> {
+        match (&self.srv_service, &self.srv_proto) {
+            (None, None) => Ok(name.to_string()),
+            (Some(service), Some(proto)) => {
+                let label = |s: &str| format!("_{}", s.trim_start_matches('_'));
+                let prefix = format!("{}.{}", label(service), label(proto));
+                Ok(if name == "@" { prefix } else { format!("{}.{}", prefix, name) })
+            }
+            _ => anyhow::bail!(ValidationError("--srv-service 与 --srv-proto 需要同时指定".into())),
+        }
+    }
 }
 
 impl DnsArgs {
-    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
         match &self.command {
             DnsCommands::List {
                 domain,
@@ -262,17 +512,25 @@ impl DnsArgs {
                 proxied,
                 priority,
                 comment,
+                data,
             } => {
+                let record_type = record_type.to_uppercase();
+                let record_data = data.build(&record_type, *priority, content.as_deref())?;
+                if content.is_none() && record_data.is_none() {
+                    anyhow::bail!(ValidationError("请指定记录值 (-c)".into()));
+                }
                 let zone_id = resolve_zone_id(client, domain).await?;
                 let request = DnsRecordRequest {
-                    record_type: record_type.to_uppercase(),
-                    name: name.clone(),
-                    content: content.clone(),
+                    name: data.record_name(name)?,
+                    // 使用 data 时不再提交 content (由 Cloudflare 根据 data 生成)
+                    content: if record_data.is_some() { String::new() } else { content.clone().unwrap_or_default() },
+                    record_type,
                     ttl: Some(*ttl),
                     proxied: *proxied,
                     priority: *priority,
-                    comment: comment.clone(),
+                    comment: Some(managed_comment(comment.as_deref())),
                     tags: None,
+                    data: record_data,
                 };
 
                 let record = client.create_dns_record(&zone_id, &request).await?;
@@ -292,22 +550,36 @@ impl DnsArgs {
                 content,
                 ttl,
                 proxied,
+                priority,
                 comment,
+                data,
             } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
 
                 // 先获取现有记录
                 let existing = client.get_dns_record(&zone_id, record_id).await?;
+                let new_type = record_type.as_ref().map(|t| t.to_uppercase()).unwrap_or(existing.record_type.clone());
+                let record_data = data.build(&new_type, priority.or(existing.priority), content.as_deref())?;
 
                 let mut patch = serde_json::Map::new();
                 if let Some(t) = record_type {
                     patch.insert("type".to_string(), serde_json::json!(t.to_uppercase()));
                 }
                 if let Some(n) = name {
-                    patch.insert("name".to_string(), serde_json::json!(n));
+                    patch.insert("name".to_string(), serde_json::json!(data.record_name(n)?));
                 }
-                if let Some(c) = content {
-                    patch.insert("content".to_string(), serde_json::json!(c));
+                match record_data {
+                    Some(d) => {
+                        patch.insert("data".to_string(), d);
+                    }
+                    None => {
+                        if let Some(c) = content {
+                            patch.insert("content".to_string(), serde_json::json!(c));
+                        }
+                    }
+                }
+                if let Some(p) = priority {
+                    patch.insert("priority".to_string(), serde_json::json!(p));
                 }
                 if let Some(t) = ttl {
                     patch.insert("ttl".to_string(), serde_json::json!(t));
@@ -327,7 +599,6 @@ impl DnsArgs {
                     "DNS 记录已更新: {} {} → {}",
                     record.record_type, record.name, record.content
                 ));
-                let _ = existing; // suppress unused warning
             }
 
             DnsCommands::Delete {
@@ -339,15 +610,12 @@ impl DnsArgs {
 
                 if !yes {
                     let record = client.get_dns_record(&zone_id, record_id).await?;
-                    let confirm = dialoguer::Confirm::new()
-                        .with_prompt(format!(
-                            "确定要删除 DNS 记录 {} {} → {} 吗？",
-                            record.record_type.red(),
-                            record.name,
-                            record.content
-                        ))
-                        .default(false)
-                        .interact()?;
+                    let confirm = prompt::confirm(format!(
+                        "确定要删除 DNS 记录 {} {} → {} 吗？",
+                        record.record_type.red(),
+                        record.name,
+                        record.content
+                    ))?;
                     if !confirm {
                         output::info("已取消");
                         return Ok(());
@@ -358,6 +626,20 @@ impl DnsArgs {
                 output::success("DNS 记录已删除");
             }
 
+            DnsCommands::Proxy {
+                domain,
+                record,
+                state,
+                record_type,
+                dry_run,
+                force,
+                yes,
+            } => {
+                let on = state == "on";
+                set_proxied(client, domain, record, on, record_type.as_deref(), *dry_run, *force, *yes, format)
+                    .await?
+            }
+
             DnsCommands::AddA {
                 domain,
                 name,
@@ -372,8 +654,9 @@ impl DnsArgs {
                     ttl: Some(1),
                     proxied: Some(*proxied),
                     priority: None,
-                    comment: None,
+                    comment: Some(managed_comment(None)),
                     tags: None,
+                    data: None,
                 };
                 let record = client.create_dns_record(&zone_id, &request).await?;
                 output::success(&format!("A 记录创建成功: {} → {}", record.name, record.content));
@@ -393,8 +676,9 @@ impl DnsArgs {
                     ttl: Some(1),
                     proxied: Some(*proxied),
                     priority: None,
-                    comment: None,
+                    comment: Some(managed_comment(None)),
                     tags: None,
+                    data: None,
                 };
                 let record = client.create_dns_record(&zone_id, &request).await?;
                 output::success(&format!(
@@ -438,8 +722,747 @@ impl DnsArgs {
                     );
                 }
             }
+
+            DnsCommands::Check {
+                domain,
+                name,
+                record_type,
+                resolvers,
+            } => check_propagation(client, domain, name, record_type.as_deref(), resolvers, format).await?,
+
+            DnsCommands::EmailAudit {
+                domain,
+                selectors,
+                no_resolve,
+                ai,
+                dry_run,
+            } => {
+                let audit = email_audit(client, domain, selectors, !no_resolve).await?;
+                if format == "json" {
+                    output::print_json(&audit);
+                } else {
+                    print_email_audit(&audit);
+                }
+                if *ai {
+                    let mut analyzer = AiAnalyzer::new(config)?;
+                    let issue = "请根据邮件安全审计结果修复 SPF、DKIM、DMARC 配置中的问题，需要修改的记录请给出 DNS 操作";
+                    troubleshoot(client, &mut analyzer, issue, Some(domain), Some(&email_audit_context(&audit)), *dry_run).await?;
+                }
+            }
+
+            DnsCommands::Resolve { url, record_type } => explain_resolution(client, url, record_type.as_deref(), format).await?,
+
+            DnsCommands::Template { command } => template_command(client, command, format).await?,
+
+            DnsCommands::Dnssec { command } => dnssec_command(client, command, format).await?,
         }
 
         Ok(())
     }
 }
+
+/// 修改匹配记录的代理状态，跳过不支持代理或已是目标状态的记录
+#[allow(clippy::too_many_arguments)]
+async fn set_proxied(
+    client: &CfClient,
+    domain: &str,
+    target: &str,
+    on: bool,
+    record_type: Option<&str>,
+    dry_run: bool,
+    force: bool,
+    yes: bool,
+    format: &str,
+) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let matched: Vec<DnsRecord> = if is_record_id(target) {
+        vec![client.get_dns_record(&zone_id, target).await?]
+    } else {
+        let zone = client.get_zone(&zone_id).await?;
+        let pattern = absolute_name(target, &zone.name);
+        client
+            .list_all_dns_records(&zone_id)
+            .await?
+            .into_iter()
+            .filter(|r| name_matches(&pattern, &r.name))
+            .filter(|r| record_type.is_none_or(|t| r.record_type.eq_ignore_ascii_case(t)))
+            .collect()
+    };
+    if matched.is_empty() {
+        anyhow::bail!(ValidationError(format!("没有匹配 {} 的记录", target)));
+    }
+
+    let (proxiable, unsupported): (Vec<DnsRecord>, Vec<DnsRecord>) =
+        matched.into_iter().partition(|r| r.proxiable != Some(false));
+    if !unsupported.is_empty() && format != "json" {
+        let names: Vec<String> = unsupported.iter().map(|r| format!("{} {}", r.record_type, r.name)).collect();
+        output::info(&format!("{} 条记录不支持代理，已跳过: {}", unsupported.len(), names.join(", ")));
+    }
+    let state = if on { "已代理" } else { "仅 DNS" };
+    let mut changes: Vec<DnsRecord> = proxiable.into_iter().filter(|r| r.proxied.unwrap_or(false) != on).collect();
+    // 通配符批量修改与 apply/restore 一致，默认不动非 cfai 管理的记录
+    let mut unmanaged = Vec::new();
+    if target.contains('*') && !force {
+        (changes, unmanaged) = changes.into_iter().partition(|r| r.is_managed());
+        if !unmanaged.is_empty() && format != "json" {
+            let names: Vec<String> = unmanaged.iter().map(|r| format!("{} {}", r.record_type, r.name)).collect();
+            output::warn(&format!(
+                "跳过 {} 条非 cfai 管理的记录 (备注不含 {})，使用 --force 一并修改: {}",
+                unmanaged.len(),
+                MANAGED_MARKER,
+                names.join(", ")
+            ));
+        }
+    }
+    if changes.is_empty() {
+        if format == "json" {
+            output::print_json(&changes);
+        } else if unmanaged.is_empty() {
+            output::success(&format!("匹配的记录均已是{}状态", state));
+        }
+        return Ok(());
+    }
+
+    if format != "json" {
+        output::title(&format!("将 {} 条记录改为{}", changes.len(), state));
+        for r in &changes {
+            println!("  {} {} → {}", format!("{:<6}", r.record_type).cyan(), r.name, r.content.dimmed());
+        }
+    }
+    if dry_run {
+        output::info("预览模式 (--dry-run)，未执行任何变更");
+        return Ok(());
+    }
+    if changes.len() > 1 && !yes && !prompt::confirm(format!("确定要修改以上 {} 条记录吗？", changes.len()))? {
+        output::info("已取消");
+        return Ok(());
+    }
+
+    let patch = serde_json::json!({ "proxied": on });
+    let mut progress = Progress::terminal("修改代理", changes.len());
+    let mut updated = Vec::new();
+    for r in &changes {
+        let Some(id) = &r.id else { continue };
+        progress.start(format!("{} {}", r.record_type, r.name));
+        let result = client.patch_dns_record(&zone_id, id, &patch).await;
+        progress.record(&result);
+        updated.extend(result.ok());
+    }
+    let failed = progress.finish().failed;
+
+    if format == "json" {
+        output::print_json(&updated);
+    } else if !updated.is_empty() {
+        output::success(&format!("已将 {} 条记录改为{}", updated.len(), state));
+    }
+    match failed {
+        0 => Ok(()),
+        f if f == changes.len() => anyhow::bail!("{} 条记录全部修改失败", f),
+        _ => Err(PartialFailure { total: changes.len(), failed }.into()),
+    }
+}
+
+/// 32 位十六进制的记录 ID
+fn is_record_id(value: &str) -> bool {
+    value.len() == 32 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// 单个解析器的检查结果
+#[derive(serde::Serialize)]
+struct ResolverCheck {
+    resolver: String,
+    address: String,
+    propagated: bool,
+    values: Vec<String>,
+    /// 解析器缓存的剩余时间 (秒)
+    ttl: Option<u32>,
+    error: Option<String>,
+}
+
+/// 从区域记录中收集 SPF、DMARC 与 DKIM 记录并检查；`resolve` 时展开 SPF 的 include 统计查询次数
+async fn email_audit(client: &CfClient, domain: &str, selectors: &[String], resolve: bool) -> Result<EmailAudit> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let zone = client.get_zone(&zone_id).await?.name.to_lowercase();
+    let records = client.list_all_dns_records(&zone_id).await?;
+    let owner = |r: &DnsRecord| r.name.trim_end_matches('.').to_lowercase();
+    let txt = |name: &str| -> Vec<String> {
+        records
+            .iter()
+            .filter(|r| r.record_type == "TXT" && owner(r) == name)
+            .map(|r| txt_value(&r.content))
+            .collect()
+    };
+
+    let spf: Vec<String> = txt(&zone).into_iter().filter(|v| is_spf(v)).collect();
+    let dmarc: Vec<String> = txt(&format!("_dmarc.{}", zone)).into_iter().filter(|v| is_dmarc(v)).collect();
+
+    let suffix = format!("._domainkey.{}", zone);
+    let mut dkim: Vec<DkimKey> = records
+        .iter()
+        .filter(|r| matches!(r.record_type.as_str(), "TXT" | "CNAME"))
+        .filter_map(|r| {
+            let selector = owner(r).strip_suffix(&suffix)?.to_string();
+            let cname = r.record_type == "CNAME";
+            Some(DkimKey {
+                selector,
+                value: (!cname).then(|| txt_value(&r.content)),
+                target: cname.then(|| r.content.trim_end_matches('.').to_string()),
+            })
+        })
+        .collect();
+
+    let mut findings = Vec::new();
+    let mut spf_lookups = None;
+    if let [record] = spf.as_slice() {
+        let mut lookups = spf_lookup_terms(record);
+        if resolve {
+            let (nested, unresolved) = spf_nested_lookups(record).await;
+            lookups += nested;
+            for domain in unresolved {
+                findings.push(EmailFinding {
+                    record: "SPF".into(),
+                    severity: Severity::Warning,
+                    message: format!("无法获取 {} 的 SPF 记录", domain),
+                });
+            }
+        }
+        spf_lookups = Some(lookups);
+    }
+    findings.extend(check_spf(&spf, spf_lookups.unwrap_or_default()));
+    findings.extend(check_dmarc(&dmarc));
+
+    for selector in selectors {
+        if !dkim.iter().any(|k| k.selector.eq_ignore_ascii_case(selector)) {
+            findings.push(EmailFinding {
+                record: format!("DKIM {}", selector),
+                severity: Severity::Error,
+                message: format!("区域中没有 {}{} 记录", selector, suffix),
+            });
+        }
+    }
+    if dkim.is_empty() && selectors.is_empty() {
+        findings.push(EmailFinding {
+            record: "DKIM".into(),
+            severity: Severity::Warning,
+            message: "区域中没有 *._domainkey 记录，发信服务商的 DKIM 可能未配置".into(),
+        });
+    }
+    dkim.sort_by(|a, b| a.selector.cmp(&b.selector));
+    for key in &dkim {
+        findings.extend(check_dkim(key));
+    }
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+
+    Ok(EmailAudit { domain: zone, spf, dmarc, dkim, spf_lookups, findings })
+}
+
+/// 逐层查询 include / redirect 引用的 SPF 记录，返回其中的查询次数与无法获取的域名
+async fn spf_nested_lookups(spf: &str) -> (usize, Vec<String>) {
+    let server = SocketAddr::new(resolver::system_resolver().unwrap_or(IpAddr::from([1, 1, 1, 1])), 53);
+    let mut queue: Vec<String> = spf_includes(spf);
+    let mut lookups = 0;
+    let mut unresolved = Vec::new();
+    let mut fetched = 0;
+    // 超过限制后结论已确定，不再继续查询
+    while let Some(domain) = queue.pop() {
+        if lookups > SPF_LOOKUP_LIMIT || fetched >= 2 * SPF_LOOKUP_LIMIT {
+            break;
+        }
+        fetched += 1;
+        let name = format!("{}.", domain.trim_end_matches('.'));
+        let found = match resolver::query(server, &name, "TXT", resolver::QUERY_TIMEOUT).await {
+            Ok(response) => response.values("TXT").into_iter().find(|v| is_spf(v)),
+            Err(_) => None,
+        };
+        match found {
+            Some(nested) => {
+                lookups += spf_lookup_terms(&nested);
+                queue.extend(spf_includes(&nested));
+            }
+            None => unresolved.push(domain),
+        }
+    }
+    (lookups, unresolved)
+}
+
+fn print_email_audit(audit: &EmailAudit) {
+    output::title(&format!("邮件安全审计 - {}", audit.domain));
+    let show = |values: &[String]| if values.is_empty() { "-".to_string() } else { values.join("\n") };
+    output::kv("SPF", &show(&audit.spf));
+    if let Some(lookups) = audit.spf_lookups {
+        output::kv("SPF 查询次数", &format!("{}/{}", lookups, SPF_LOOKUP_LIMIT));
+    }
+    output::kv("DMARC", &show(&audit.dmarc));
+    if audit.dkim.is_empty() {
+        output::kv("DKIM", "-");
+    }
+    for key in &audit.dkim {
+        let value = match (&key.value, &key.target) {
+            (_, Some(target)) => format!("CNAME → {}", target),
+            (Some(value), None) => value.chars().take(60).collect::<String>() + if value.chars().count() > 60 { "…" } else { "" },
+            (None, None) => "-".to_string(),
+        };
+        output::kv(&format!("DKIM {}", key.selector), &value);
+    }
+
+    println!("\n{}", "检查结果".bold());
+    if audit.findings.is_empty() {
+        output::success("未发现问题");
+        return;
+    }
+    for f in &audit.findings {
+        let message = format!("[{}] {}", f.record, f.message);
+        match f.severity {
+            Severity::Error => output::error(&message),
+            Severity::Warning => output::warn(&message),
+            Severity::Info => output::info(&message),
+        }
+    }
+    output::tip(&format!(
+        "{} 个错误、{} 个警告；加 --ai 可让 AI 给出修复方案",
+        audit.count(Severity::Error),
+        audit.count(Severity::Warning)
+    ));
+}
+
+/// 供 AI 使用的审计摘要
+fn email_audit_context(audit: &EmailAudit) -> String {
+    let mut context = format!("邮件安全审计 ({}):\n", audit.domain);
+    for spf in &audit.spf {
+        context.push_str(&format!("SPF (TXT @): {}\n", spf));
+    }
+    for dmarc in &audit.dmarc {
+        context.push_str(&format!("DMARC (TXT _dmarc): {}\n", dmarc));
+    }
+    for key in &audit.dkim {
+        match (&key.value, &key.target) {
+            (_, Some(target)) => context.push_str(&format!("DKIM (CNAME {}._domainkey): {}\n", key.selector, target)),
+            (Some(value), None) => context.push_str(&format!("DKIM (TXT {}._domainkey): {}\n", key.selector, value)),
+            (None, None) => {}
+        }
+    }
+    if let Some(lookups) = audit.spf_lookups {
+        context.push_str(&format!("SPF 查询次数: {}\n", lookups));
+    }
+    context.push_str("发现的问题:\n");
+    for f in &audit.findings {
+        context.push_str(&format!("- [{:?}] {}: {}\n", f.severity, f.record, f.message));
+    }
+    context
+}
+
+/// 找到主机名所属的域名，按区域内的记录推断解析路径
+async fn explain_resolution(client: &CfClient, url: &str, record_type: Option<&str>, format: &str) -> Result<()> {
+    let host = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .split(['/', ':', '?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('.')
+        .to_lowercase();
+    if host.is_empty() {
+        anyhow::bail!(ValidationError(format!("无效的 URL 或主机名: {}", url)));
+    }
+    let zone = zone_for_host(client, &host)
+        .await?
+        .ok_or_else(|| ValidationError(format!("账户下没有 {} 所属的域名", host)))?;
+    let records = client.list_all_dns_records(&zone.id).await?;
+    let record_type = record_type.map(str::to_uppercase);
+    let types = match &record_type {
+        Some(t) => vec![t.as_str()],
+        None => vec!["A", "AAAA"],
+    };
+    let resolution = resolve_host(&records, &host, &zone.name, &types);
+
+    if format == "json" {
+        output::print_json(&resolution);
+        return Ok(());
+    }
+    output::title(&format!("解析路径 - {}", host));
+    if resolution.steps.is_empty() {
+        output::warn(&format!("{} 中没有应答 {} 的记录 (精确匹配与通配符均无)", zone.name, host));
+        return Ok(());
+    }
+    for (i, step) in resolution.steps.iter().enumerate() {
+        let matched = if step.wildcard {
+            format!(" (匹配通配符 {})", step.matched).yellow().to_string()
+        } else {
+            String::new()
+        };
+        println!("  {}. {}{}", i + 1, step.name.bold(), matched);
+        if step.records.is_empty() {
+            println!("     {}", format!("名称存在，但没有 {} 记录", types.join("/")).dimmed());
+        }
+        for record in &step.records {
+            let proxy = match record.proxied {
+                Some(true) => " 🟠 已代理",
+                Some(false) => " ⚫ 仅 DNS",
+                None => "",
+            };
+            println!("     {} → {}{}", record.record_type.cyan(), record.content, proxy);
+        }
+    }
+    println!();
+    if resolution.proxied {
+        output::kv_colored("访客连接", "Cloudflare 边缘 (已代理)", true);
+        output::kv("回源目标", &effective_target(&resolution));
+    } else {
+        output::kv("访客连接", &effective_target(&resolution));
+    }
+    if resolution.looped {
+        output::warn("CNAME 链出现循环，解析会失败");
+    }
+    Ok(())
+}
+
+fn effective_target(resolution: &Resolution) -> String {
+    match &resolution.external {
+        Some(external) => format!("{} (区域外，由其权威服务器解析)", external),
+        None if resolution.targets.is_empty() => "-".to_string(),
+        None => resolution.targets.join(", "),
+    }
+}
+
+/// 以 Cloudflare 权威服务器的应答为准，对比各公共解析器的结果
+async fn check_propagation(
+    client: &CfClient,
+    domain: &str,
+    name: &str,
+    record_type: Option<&str>,
+    extra_resolvers: &[String],
+    format: &str,
+) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let zone = client.get_zone(&zone_id).await?;
+    let fqdn = absolute_name(name, &zone.name);
+    let records: Vec<DnsRecord> = client
+        .list_all_dns_records(&zone_id)
+        .await?
+        .into_iter()
+        .filter(|r| r.name.eq_ignore_ascii_case(&fqdn))
+        .collect();
+    // 代理记录对外解析为 Cloudflare 的 IP
+    let query_type = match (record_type, records.first()) {
+        (Some(t), _) => t.to_uppercase(),
+        (None, Some(r)) if r.proxied == Some(true) => "A".to_string(),
+        (None, Some(r)) => r.record_type.clone(),
+        (None, None) => "A".to_string(),
+    };
+
+    let mut servers: Vec<(String, IpAddr)> = resolver::PUBLIC_RESOLVERS
+        .iter()
+        .map(|(label, ip)| (label.to_string(), ip.parse().expect("内置解析器地址")))
+        .collect();
+    if let Some(ip) = resolver::system_resolver() {
+        servers.push(("本地".to_string(), ip));
+    }
+    for addr in extra_resolvers {
+        let ip = addr
+            .parse()
+            .map_err(|_| ValidationError(format!("无效的解析器地址: {}", addr)))?;
+        servers.push((addr.clone(), ip));
+    }
+
+    let ns = zone
+        .name_servers
+        .as_ref()
+        .and_then(|ns| ns.first())
+        .context("无法获取域名的 Cloudflare 名称服务器")?;
+    let authoritative = resolver::query(resolver::server_addr(ns).await?, &fqdn, &query_type, resolver::QUERY_TIMEOUT)
+        .await
+        .with_context(|| format!("查询权威服务器 {} 失败", ns))?;
+    let expected = authoritative.values(&query_type);
+
+    let queries: Vec<_> = servers
+        .iter()
+        .map(|(_, ip)| {
+            let (server, fqdn, query_type) = (SocketAddr::new(*ip, 53), fqdn.clone(), query_type.clone());
+            tokio::spawn(async move { resolver::query(server, &fqdn, &query_type, resolver::QUERY_TIMEOUT).await })
+        })
+        .collect();
+    let mut checks = Vec::new();
+    for ((label, ip), query) in servers.iter().zip(queries) {
+        let check = match query.await? {
+            Ok(response) => {
+                let values = response.values(&query_type);
+                ResolverCheck {
+                    resolver: label.clone(),
+                    address: ip.to_string(),
+                    propagated: values.len() == expected.len()
+                        && values.iter().zip(&expected).all(|(a, b)| a.eq_ignore_ascii_case(b)),
+                    ttl: response.cache_ttl(&query_type),
+                    values,
+                    error: None,
+                }
+            }
+            Err(e) => ResolverCheck {
+                resolver: label.clone(),
+                address: ip.to_string(),
+                propagated: false,
+                values: Vec::new(),
+                ttl: None,
+                error: Some(format!("{:#}", e)),
+            },
+        };
+        checks.push(check);
+    }
+
+    if format == "json" {
+        output::print_json(&serde_json::json!({
+            "name": fqdn,
+            "type": query_type,
+            "cloudflare": records,
+            "authoritative": { "server": ns, "rcode": authoritative.rcode, "values": expected },
+            "resolvers": checks,
+        }));
+        return Ok(());
+    }
+
+    output::title(&format!("解析生效检查 - {} {}", query_type, fqdn));
+    let configured: Vec<String> = records
+        .iter()
+        .map(|r| {
+            let proxied = if r.proxied == Some(true) { " (已代理)" } else { "" };
+            format!("{} {}{}", r.record_type, r.content, proxied)
+        })
+        .collect();
+    output::kv(
+        "Cloudflare 记录",
+        &if configured.is_empty() { "无".to_string() } else { configured.join(", ") },
+    );
+    output::kv(
+        &format!("权威应答 ({})", ns),
+        &if expected.is_empty() { format!("无记录 ({})", authoritative.rcode) } else { expected.join(", ") },
+    );
+    println!();
+
+    let mut table = output::create_table(vec!["解析器", "地址", "结果", "缓存剩余", "状态"]);
+    for check in &checks {
+        let result = match &check.error {
+            Some(e) => e.clone(),
+            None if check.values.is_empty() => "无记录".to_string(),
+            None => check.values.join("\n"),
+        };
+        let ttl = check.ttl.map(|t| format!("{}s", t)).unwrap_or_else(|| "-".to_string());
+        let status = match (&check.error, check.propagated) {
+            (Some(_), _) => "✗ 查询失败".red().to_string(),
+            (None, true) => "✓ 已生效".green().to_string(),
+            (None, false) => "✗ 未生效".yellow().to_string(),
+        };
+        table.add_row(vec![check.resolver.as_str(), check.address.as_str(), &result, &ttl, &status]);
+    }
+    println!("{table}");
+
+    let propagated = checks.iter().filter(|c| c.propagated).count();
+    if propagated == checks.len() {
+        output::success(&format!("已在全部 {} 个解析器生效", checks.len()));
+    } else {
+        output::warn(&format!("{}/{} 个解析器已生效", propagated, checks.len()));
+        let remaining = checks.iter().filter(|c| c.error.is_none() && !c.propagated).filter_map(|c| c.ttl).max();
+        if let Some(secs) = remaining {
+            output::tip(&format!("未生效的解析器缓存最迟约 {} 秒后过期", secs));
+        }
+    }
+    Ok(())
+}
+
+async fn template_command(client: &CfClient, command: &DnsTemplateCommands, format: &str) -> Result<()> {
+    match command {
+        DnsTemplateCommands::List => {
+            if format == "json" {
+                let items: Vec<_> = DNS_TEMPLATES
+                    .iter()
+                    .map(|t| serde_json::json!({ "name": t.name, "description": t.description }))
+                    .collect();
+                output::print_json(&items);
+                return Ok(());
+            }
+
+            output::title(&format!("DNS 模板 (共 {} 个)", DNS_TEMPLATES.len()));
+            let mut table = output::create_table(vec!["名称", "说明", "变量"]);
+            for t in DNS_TEMPLATES {
+                table.add_row(vec![t.name.to_string(), t.description.to_string(), t.var_names()]);
+            }
+            println!("{table}");
+            output::tip("运行 `cfai dns template show <模板>` 查看模板包含的记录");
+        }
+
+        DnsTemplateCommands::Show { template } => {
+            let tpl = DnsTemplate::find(template)?;
+
+            output::title(&format!("DNS 模板: {}", tpl.name));
+            output::kv("说明", tpl.description);
+
+            let mut table = output::create_table(vec!["类型", "名称", "内容", "优先级", "可选"]);
+            for r in tpl.records {
+                table.add_row(vec![
+                    r.record_type.to_string(),
+                    r.name.to_string(),
+                    r.content.to_string(),
+                    r.priority.map(|p| p.to_string()).unwrap_or("-".into()),
+                    if r.optional { "是" } else { "" }.to_string(),
+                ]);
+            }
+            println!("{table}");
+
+            if !tpl.vars.is_empty() {
+                println!();
+                output::title("变量");
+                for v in tpl.vars {
+                    output::kv(
+                        v.name,
+                        &match v.default {
+                            Some(d) => format!("{} (默认: {})", v.description, d),
+                            None => v.description.to_string(),
+                        },
+                    );
+                }
+            }
+        }
+
+        DnsTemplateCommands::Apply {
+            domain,
+            template,
+            vars,
+            replace,
+            force,
+            dry_run,
+            yes,
+        } => {
+            let tpl = DnsTemplate::find(template)?;
+            let vars = parse_template_vars(vars)?;
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let zone_name = client.get_zone(&zone_id).await?.name;
+            let (mut records, skipped) = tpl.render(&zone_name, &vars)?;
+            for record in &mut records {
+                record.comment = Some(managed_comment(record.comment.as_deref()));
+            }
+
+            output::loading(&format!("正在读取 {} 的现有 DNS 记录", zone_name));
+            let current = client.list_all_dns_records(&zone_id).await?;
+            let plan = plan_dns_template(&records, &current, *replace);
+
+            output::title(&format!("模板预览: {} → {}", tpl.name, zone_name));
+            for label in &plan.existing {
+                output::list_item(&format!("{} {}", label, "(已存在，跳过)".dimmed()));
+            }
+            for label in &skipped {
+                output::list_item(&format!("{} {}", label, "(可选，跳过)".dimmed()));
+            }
+            if !plan.changes.is_empty() {
+                backup::print_changes(&plan.changes);
+            }
+
+            if !plan.conflicts.is_empty() && !replace {
+                output::warn(&format!("发现 {} 条冲突记录:", plan.conflicts.len()));
+                for (record, reason) in &plan.conflicts {
+                    output::list_item(&format!(
+                        "{} {} → {}  {}",
+                        record.record_type.red(),
+                        record.name,
+                        record.content,
+                        format!("({})", reason).yellow()
+                    ));
+                }
+                anyhow::bail!(ValidationError(
+                    "模板与现有记录冲突，请手动处理或使用 --replace 删除冲突记录后再应用".into()
+                ));
+            }
+
+            if !force {
+                let (_, protected) = split_unmanaged_dns_changes(plan.changes.clone(), &current);
+                if !protected.is_empty() {
+                    anyhow::bail!(ValidationError(format!(
+                        "有 {} 条待删除的冲突记录不是由 cfai 创建的 (备注不含 {})，确认要删除请加上 --force",
+                        protected.len(),
+                        MANAGED_MARKER
+                    )));
+                }
+            }
+
+            if plan.changes.is_empty() {
+                output::success(&format!("{} 已包含模板 {} 的全部记录", zone_name, tpl.name));
+                return Ok(());
+            }
+
+            if *dry_run {
+                output::info("预览模式 (--dry-run)，未执行任何变更");
+                return Ok(());
+            }
+
+            if !yes {
+                let confirm = prompt::confirm(format!("确定要对 {} 应用模板 {} 吗？", zone_name.yellow(), tpl.name))?;
+                if !confirm {
+                    output::info("已取消");
+                    return Ok(());
+                }
+            }
+
+            backup::apply_changes(client, &zone_id, &plan.changes).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn dnssec_command(client: &CfClient, command: &DnssecCommands, format: &str) -> Result<()> {
+    let (domain, dnssec) = match command {
+        DnssecCommands::Status { domain } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            (domain, client.get_dnssec(&zone_id).await?)
+        }
+
+        DnssecCommands::Enable { domain } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let dnssec = client.set_dnssec(&zone_id, true).await?;
+            output::success(&format!("{} 的 DNSSEC 已开启", domain));
+            (domain, dnssec)
+        }
+
+        DnssecCommands::Disable { domain, yes } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            if !yes {
+                output::warn("关闭前请先在注册商处删除 DS 记录，否则支持 DNSSEC 校验的解析器将无法解析该域名");
+                let confirm = prompt::confirm(format!("确定要关闭 {} 的 DNSSEC 吗？", domain.yellow()))?;
+                if !confirm {
+                    output::info("已取消");
+                    return Ok(());
+                }
+            }
+            let dnssec = client.set_dnssec(&zone_id, false).await?;
+            output::success(&format!("{} 的 DNSSEC 已关闭", domain));
+            (domain, dnssec)
+        }
+    };
+
+    if format == "json" {
+        output::print_json(&dnssec);
+        return Ok(());
+    }
+
+    let status = dnssec.status();
+    output::title(&format!("DNSSEC - {}", domain));
+    output::kv_colored("状态", status, status == "active");
+    if status == "disabled" {
+        output::tip(&format!("运行 `cfai dns dnssec enable {}` 开启 DNSSEC", domain));
+        return Ok(());
+    }
+
+    let opt = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".into());
+    let num = |v: Option<u32>| v.map(|n| n.to_string()).unwrap_or_else(|| "-".into());
+    output::kv("Key Tag", &num(dnssec.key_tag));
+    output::kv("算法", &opt(&dnssec.algorithm));
+    output::kv("摘要类型", &opt(&dnssec.digest_type));
+    output::kv("摘要", &opt(&dnssec.digest));
+    output::kv("Flags", &num(dnssec.flags));
+    output::kv("公钥", &opt(&dnssec.public_key));
+    output::kv("DS 记录", &opt(&dnssec.ds));
+    output::kv("修改时间", &opt(&dnssec.modified_on));
+
+    if status == "pending" {
+        println!();
+        output::tip("请在域名注册商处添加以上 DS 记录 (Key Tag / 算法 / 摘要类型 / 摘要)，生效后状态会变为 active");
+    }
+    Ok(())
+}
@@ -0,0 +1,511 @@
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::api::client::CfClient;
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::output;
+use crate::models::backup::ZoneBackup;
+use crate::models::dns::DnsRecord;
+use crate::models::firewall::{FirewallRule, IpAccessRule};
+use crate::models::page_rules::PageRule;
+use crate::models::rulesets::{Ruleset, WAF_CUSTOM_PHASE};
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub command: ExportCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExportCommands {
+    /// 导出为 Terraform 配置 (cloudflare provider v5)，包含 DNS 记录、域名设置、页面规则与防火墙规则
+    #[command(alias = "tf")]
+    Terraform {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 输出文件 (默认输出到终端)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// 不生成 import 块 (默认生成，`terraform plan` 时将现有资源纳入管理而不是重新创建)
+        #[arg(long)]
+        no_import: bool,
+    },
+}
+
+/// 生成 Terraform 配置所需的域名配置
+struct TerraformSource {
+    backup: ZoneBackup,
+    access_rules: Vec<IpAccessRule>,
+    custom_rules: Option<Ruleset>,
+}
+
+impl ExportArgs {
+    pub async fn execute(&self, client: &CfClient) -> Result<()> {
+        match &self.command {
+            ExportCommands::Terraform {
+                domain,
+                output: path,
+                no_import,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                if path.is_some() {
+                    output::loading(&format!("正在读取 {} 的配置", domain));
+                }
+                let backup = client.snapshot_zone(&zone_id).await?;
+                // 与备份一致: 套餐或令牌权限不支持的部分跳过
+                let access_rules = client
+                    .list_ip_access_rules(&zone_id)
+                    .await
+                    .map_err(|e| debug!("跳过 IP 访问规则: {:#}", e))
+                    .unwrap_or_default();
+                let custom_rules = client
+                    .get_phase_ruleset(&zone_id, WAF_CUSTOM_PHASE)
+                    .await
+                    .map_err(|e| debug!("跳过 WAF 自定义规则: {:#}", e))
+                    .ok()
+                    .flatten();
+
+                let source = TerraformSource {
+                    backup,
+                    access_rules,
+                    custom_rules,
+                };
+                let hcl = render_terraform(&source, !no_import);
+                match path {
+                    Some(path) => {
+                        std::fs::write(path, &hcl)
+                            .with_context(|| format!("写入 {} 失败", path.display()))?;
+                        output::success(&format!("已导出到 {}", path.display()));
+                        output::info("运行 `terraform init && terraform plan` 检查导入结果");
+                    }
+                    None => print!("{}", hcl),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 生成完整的 Terraform 配置
+fn render_terraform(source: &TerraformSource, with_import: bool) -> String {
+    let backup = &source.backup;
+    let zone_id = &backup.zone.id;
+    let mut hcl = HclWriter::new(zone_id, with_import);
+
+    let _ = writeln!(
+        hcl.out,
+        "# 由 cfai {} 从 {} 导出 ({})\n# 认证: 设置环境变量 CLOUDFLARE_API_TOKEN\n",
+        env!("CARGO_PKG_VERSION"),
+        backup.zone.name,
+        backup.created_at
+    );
+    hcl.out.push_str(
+        "terraform {\n  required_providers {\n    cloudflare = {\n      source  = \"cloudflare/cloudflare\"\n      version = \"~> 5\"\n    }\n  }\n}\n\nprovider \"cloudflare\" {}\n\n",
+    );
+    let _ = writeln!(hcl.out, "locals {{\n  zone_id = {}\n}}", hcl_string(zone_id));
+
+    if let Some(records) = &backup.dns_records {
+        hcl.section("DNS 记录");
+        for record in records {
+            dns_record(&mut hcl, record, &backup.zone.name);
+        }
+    }
+
+    let settings = backup.ssl.iter().chain(&backup.settings).flatten();
+    hcl.section("域名设置");
+    for setting in settings.filter(|s| s.editable != Some(false)) {
+        hcl.resource(
+            "cloudflare_zone_setting",
+            &setting.id,
+            vec![
+                ("zone_id", "local.zone_id".to_string()),
+                ("setting_id", hcl_string(&setting.id)),
+                ("value", hcl_value(&setting.value, 1)),
+            ],
+            Some(format!("{}/{}", zone_id, setting.id)),
+        );
+    }
+
+    if let Some(rules) = backup.page_rules.as_ref().filter(|r| !r.is_empty()) {
+        hcl.section("页面规则");
+        for rule in rules {
+            page_rule(&mut hcl, rule);
+        }
+    }
+
+    let zone_access_rules: Vec<&IpAccessRule> = source
+        .access_rules
+        .iter()
+        .filter(|r| r.scope.as_ref().and_then(|s| s.scope_type.as_deref()).unwrap_or("zone") == "zone")
+        .collect();
+    if !zone_access_rules.is_empty() {
+        hcl.section("IP 访问规则");
+        for rule in zone_access_rules {
+            access_rule(&mut hcl, rule);
+        }
+    }
+
+    let legacy = backup.firewall_rules.as_deref().unwrap_or_default();
+    firewall_ruleset(&mut hcl, source.custom_rules.as_ref(), legacy);
+
+    hcl.out
+}
+
+fn dns_record(hcl: &mut HclWriter, record: &DnsRecord, zone_name: &str) {
+    let short_name = record
+        .name
+        .strip_suffix(zone_name)
+        .map(|n| n.trim_end_matches('.'))
+        .filter(|n| !n.is_empty())
+        .unwrap_or("root")
+        .replace('*', "wildcard");
+    let mut attrs = vec![
+        ("zone_id", "local.zone_id".to_string()),
+        ("name", hcl_string(&record.name)),
+        ("type", hcl_string(&record.record_type)),
+        ("content", hcl_string(&record.content)),
+        ("ttl", record.ttl.unwrap_or(1).to_string()),
+    ];
+    if let Some(proxied) = record.proxied.filter(|_| record.proxiable != Some(false)) {
+        attrs.push(("proxied", proxied.to_string()));
+    }
+    if let Some(priority) = record.priority {
+        attrs.push(("priority", priority.to_string()));
+    }
+    if let Some(comment) = record.comment.as_deref().filter(|c| !c.is_empty()) {
+        attrs.push(("comment", hcl_string(comment)));
+    }
+    if let Some(tags) = record.tags.as_ref().filter(|t| !t.is_empty()) {
+        attrs.push(("tags", hcl_value(&json!(tags), 1)));
+    }
+    let import = record.id.as_ref().map(|id| format!("{}/{}", hcl.zone_id, id));
+    hcl.resource(
+        "cloudflare_dns_record",
+        &format!("{}_{}", record.record_type, short_name),
+        attrs,
+        import,
+    );
+}
+
+fn page_rule(hcl: &mut HclWriter, rule: &PageRule) {
+    let target = rule
+        .targets
+        .iter()
+        .flatten()
+        .find_map(|t| t.constraint.as_ref().and_then(|c| c.value.clone()))
+        .unwrap_or_default();
+    // 无值的开关型动作 (如 always_use_https) 在 provider 中为布尔值
+    let actions: serde_json::Map<String, Value> = rule
+        .actions
+        .iter()
+        .flatten()
+        .filter_map(|a| {
+            let id = a.id.clone()?;
+            Some((id, a.value.clone().filter(|v| !v.is_null()).unwrap_or(Value::Bool(true))))
+        })
+        .collect();
+
+    let mut attrs = vec![
+        ("zone_id", "local.zone_id".to_string()),
+        ("target", hcl_string(&target)),
+        ("actions", hcl_value(&Value::Object(actions), 1)),
+    ];
+    if let Some(priority) = rule.priority {
+        attrs.push(("priority", priority.to_string()));
+    }
+    if let Some(status) = &rule.status {
+        attrs.push(("status", hcl_string(status)));
+    }
+    let import = rule.id.as_ref().map(|id| format!("{}/{}", hcl.zone_id, id));
+    hcl.resource("cloudflare_page_rule", &format!("rule_{}", target), attrs, import);
+}
+
+fn access_rule(hcl: &mut HclWriter, rule: &IpAccessRule) {
+    let Some(config) = &rule.configuration else {
+        return;
+    };
+    let target = config.target.clone().unwrap_or_default();
+    let value = config.value.clone().unwrap_or_default();
+    let mode = rule.mode.clone().unwrap_or_default();
+    let mut attrs = vec![
+        ("zone_id", "local.zone_id".to_string()),
+        ("mode", hcl_string(&mode)),
+        ("configuration", hcl_value(&json!({ "target": target, "value": value }), 1)),
+    ];
+    if let Some(notes) = rule.notes.as_deref().filter(|n| !n.is_empty()) {
+        attrs.push(("notes", hcl_string(notes)));
+    }
+    let import = rule.id.as_ref().map(|id| format!("zones/{}/{}", hcl.zone_id, id));
+    hcl.resource("cloudflare_access_rule", &format!("{}_{}", mode, value), attrs, import);
+}
+
+/// 旧版防火墙规则动作对应的 WAF 自定义规则动作 (allow / bypass 需要改写为 skip，不自动转换)
+fn legacy_action(action: &str) -> Option<&str> {
+    matches!(action, "block" | "challenge" | "js_challenge" | "managed_challenge" | "log").then_some(action)
+}
+
+/// WAF 自定义规则集；旧版防火墙规则转换后追加到末尾
+fn firewall_ruleset(hcl: &mut HclWriter, custom: Option<&Ruleset>, legacy: &[FirewallRule]) {
+    let mut rules: Vec<Value> = custom
+        .and_then(|r| r.rules.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|rule| {
+            let mut value = json!({
+                "action": rule.action,
+                "expression": rule.expression,
+                "description": rule.description,
+                "enabled": rule.enabled.unwrap_or(true),
+            });
+            if let Some(params) = &rule.action_parameters {
+                value["action_parameters"] = params.clone();
+            }
+            value
+        })
+        .collect();
+
+    let mut skipped = Vec::new();
+    let mut converted = 0;
+    for rule in legacy {
+        let expression = rule.filter.as_ref().and_then(|f| f.expression.clone());
+        let action = rule.action.as_deref().unwrap_or_default();
+        match (legacy_action(action), expression) {
+            (Some(action), Some(expression)) => {
+                converted += 1;
+                rules.push(json!({
+                    "action": action,
+                    "expression": expression,
+                    "description": rule.description,
+                    "enabled": !rule.paused.unwrap_or(false),
+                }));
+            }
+            _ => skipped.push(format!(
+                "#   {} ({})",
+                rule.description.as_deref().unwrap_or("-"),
+                if action.is_empty() { "-" } else { action }
+            )),
+        }
+    }
+    if rules.is_empty() && skipped.is_empty() {
+        return;
+    }
+
+    hcl.section("防火墙规则 (WAF 自定义规则)");
+    if converted > 0 {
+        let _ = writeln!(
+            hcl.out,
+            "# 其中 {} 条由旧版防火墙规则转换，apply 后请删除旧版规则以免重复生效",
+            converted
+        );
+    }
+    if !skipped.is_empty() {
+        let _ = writeln!(
+            hcl.out,
+            "# 以下旧版防火墙规则需要手动改写为 skip 规则，未导出:\n{}",
+            skipped.join("\n")
+        );
+    }
+    if rules.is_empty() {
+        return;
+    }
+    let name = custom
+        .and_then(|r| r.name.clone())
+        .unwrap_or_else(|| "default".to_string());
+    // 仅存在旧版规则时为新建规则集，没有可导入的 ID
+    let import = custom
+        .and_then(|r| r.id.as_ref())
+        .map(|id| format!("zones/{}/{}", hcl.zone_id, id));
+    hcl.resource(
+        "cloudflare_ruleset",
+        "firewall_custom",
+        vec![
+            ("zone_id", "local.zone_id".to_string()),
+            ("name", hcl_string(&name)),
+            ("kind", hcl_string("zone")),
+            ("phase", hcl_string(WAF_CUSTOM_PHASE)),
+            ("rules", hcl_value(&Value::Array(rules), 1)),
+        ],
+        import,
+    );
+}
+
+/// 按资源生成 HCL，保证同类资源名称唯一
+struct HclWriter {
+    out: String,
+    zone_id: String,
+    with_import: bool,
+    names: HashSet<String>,
+}
+
+impl HclWriter {
+    fn new(zone_id: &str, with_import: bool) -> Self {
+        Self {
+            out: String::new(),
+            zone_id: zone_id.to_string(),
+            with_import,
+            names: HashSet::new(),
+        }
+    }
+
+    fn section(&mut self, title: &str) {
+        let _ = writeln!(self.out, "\n# ==================== {} ====================", title);
+    }
+
+    /// 写入资源块 (属性值为已渲染的 HCL 表达式)，需要时附带 import 块
+    fn resource(&mut self, kind: &str, name: &str, attrs: Vec<(&str, String)>, import_id: Option<String>) {
+        let name = self.unique_name(kind, name);
+        let width = attrs.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+
+        let _ = writeln!(self.out, "\nresource \"{}\" \"{}\" {{", kind, name);
+        for (key, value) in &attrs {
+            let _ = writeln!(self.out, "  {:width$} = {}", key, value, width = width);
+        }
+        self.out.push_str("}\n");
+
+        if let Some(id) = import_id.filter(|_| self.with_import) {
+            let _ = writeln!(
+                self.out,
+                "\nimport {{\n  to = {}.{}\n  id = {}\n}}",
+                kind,
+                name,
+                hcl_string(&id)
+            );
+        }
+    }
+
+    fn unique_name(&mut self, kind: &str, name: &str) -> String {
+        let base = resource_name(name);
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while !self.names.insert(format!("{}.{}", kind, candidate)) {
+            candidate = format!("{}_{}", base, n);
+            n += 1;
+        }
+        candidate
+    }
+}
+
+/// 转为合法的 Terraform 资源名称 (小写字母、数字与下划线，不以数字开头)
+fn resource_name(raw: &str) -> String {
+    let mut name = String::new();
+    for c in raw.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c);
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name = name.trim_matches('_');
+    match name.chars().next() {
+        None => "unnamed".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{}", name),
+        Some(_) => name.to_string(),
+    }
+}
+
+/// HCL 字符串字面量 (转义模板插值 `${` / `%{`)
+fn hcl_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                out.push(c);
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// JSON 值转为 HCL 表达式，对象按缩进层级换行
+fn hcl_value(value: &Value, depth: usize) -> String {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => hcl_string(s),
+        Value::Array(items) if items.iter().any(|v| v.is_object()) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|v| format!("{}  {}", indent, hcl_value(v, depth + 1)))
+                .collect();
+            format!("[\n{}\n{}]", items.join(",\n"), indent)
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(|v| hcl_value(v, depth)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(map) if map.is_empty() => "{}".to_string(),
+        Value::Object(map) => {
+            let entries: Vec<(String, &Value)> = map
+                .iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (hcl_key(k), v))
+                .collect();
+            let width = entries.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+            let mut out = String::from("{\n");
+            for (key, v) in entries {
+                let _ = writeln!(out, "{}  {:width$} = {}", indent, key, hcl_value(v, depth + 1), width = width);
+            }
+            out.push_str(&indent);
+            out.push('}');
+            out
+        }
+    }
+}
+
+fn hcl_key(key: &str) -> String {
+    let is_identifier = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_identifier {
+        key.to_string()
+    } else {
+        hcl_string(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hcl_rendering() {
+        assert_eq!(hcl_string("say \"hi\" ${var}"), r#""say \"hi\" $${var}""#);
+        assert_eq!(resource_name("A_*.Example.com"), "a_example_com");
+        assert_eq!(resource_name("0rtt"), "_0rtt");
+
+        let value = json!({ "forwarding_url": { "url": "https://example.com/$1", "status_code": 301 }, "ssl": null });
+        assert_eq!(
+            hcl_value(&value, 1),
+            "{\n    forwarding_url = {\n      status_code = 301\n      url         = \"https://example.com/$1\"\n    }\n  }"
+        );
+        assert_eq!(hcl_value(&json!(["a", 1]), 0), r#"["a", 1]"#);
+    }
+
+    #[test]
+    fn test_unique_resource_names() {
+        let mut hcl = HclWriter::new("zone", true);
+        hcl.resource("cloudflare_dns_record", "A_www", vec![], Some("zone/1".into()));
+        hcl.resource("cloudflare_dns_record", "A_www", vec![], None);
+        assert!(hcl.out.contains("\"cloudflare_dns_record\" \"a_www\""));
+        assert!(hcl.out.contains("\"cloudflare_dns_record\" \"a_www_2\""));
+        assert!(hcl.out.contains("to = cloudflare_dns_record.a_www\n  id = \"zone/1\""));
+        assert_eq!(hcl.out.matches("import {").count(), 1);
+    }
+}
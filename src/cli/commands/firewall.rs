@@ -1,10 +1,26 @@
-use anyhow::Result;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
 use colored::Colorize;
 
 use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::exit::PartialFailure;
 use crate::cli::output;
+use crate::cli::prompt;
+use crate::cli::commands::scheduler::add_schedule;
 use crate::cli::commands::zone::resolve_zone_id;
+use crate::config::settings::ScheduleEntry;
+use crate::models::analytics::parse_time;
+use crate::models::firewall::{
+    BotManagementConfig, CreateIpAccessRuleRequest, FirewallEventQuery, IpAccessRule, IpAccessRuleConfig,
+    LockdownRuleRequest, RateLimitRequest, UserAgentRuleRequest,
+};
+use crate::models::rulesets::*;
+use crate::models::zone::QuotaResource;
+use crate::progress::Progress;
 
 #[derive(Args, Debug)]
 pub struct FirewallArgs {
@@ -34,23 +50,37 @@ pub enum FirewallCommands {
         domain: String,
     },
 
-    /// 封禁 IP
+    /// 封禁 IP、IP 段、ASN 或国家
     Block {
         /// 域名或 Zone ID
         domain: String,
-        /// IP 地址
-        ip: String,
+        /// IP 地址或 CIDR (如 198.51.100.0/24)
+        #[arg(required_unless_present_any = ["country", "asn"], conflicts_with_all = ["country", "asn"])]
+        ip: Option<String>,
+        /// 国家/地区代码 (如 CN)
+        #[arg(long, conflicts_with = "asn")]
+        country: Option<String>,
+        /// 自治系统号 (如 13335)
+        #[arg(long)]
+        asn: Option<u32>,
         /// 备注
         #[arg(short, long)]
         note: Option<String>,
     },
 
-    /// IP 白名单
+    /// 将 IP、IP 段、ASN 或国家加入白名单
     Whitelist {
         /// 域名或 Zone ID
         domain: String,
-        /// IP 地址
-        ip: String,
+        /// IP 地址或 CIDR (如 198.51.100.0/24)
+        #[arg(required_unless_present_any = ["country", "asn"], conflicts_with_all = ["country", "asn"])]
+        ip: Option<String>,
+        /// 国家/地区代码 (如 CN)
+        #[arg(long, conflicts_with = "asn")]
+        country: Option<String>,
+        /// 自治系统号 (如 13335)
+        #[arg(long)]
+        asn: Option<u32>,
         /// 备注
         #[arg(short, long)]
         note: Option<String>,
@@ -64,6 +94,46 @@ pub enum FirewallCommands {
         rule_id: String,
     },
 
+    /// 批量封禁: 从文件或参数读取 IP、CIDR、ASN (AS13335)、国家代码 (CN)，跳过已有规则
+    BlockList {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 列表 (也可用 --file 读取)
+        entries: Vec<String>,
+        /// 列表文件，每行一项，# 开头为注释 (- 表示标准输入)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        /// 动作 (block/challenge/js_challenge/managed_challenge/whitelist)
+        #[arg(short, long, default_value = "block")]
+        mode: String,
+        /// 备注
+        #[arg(short, long)]
+        note: Option<String>,
+        /// 只显示将要创建的规则
+        #[arg(long)]
+        dry_run: bool,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// 批量解除: 删除与列表中各项匹配的 IP 访问规则
+    UnblockList {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 列表 (也可用 --file 读取)
+        entries: Vec<String>,
+        /// 列表文件，每行一项，# 开头为注释 (- 表示标准输入)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        /// 只显示将要删除的规则
+        #[arg(long)]
+        dry_run: bool,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
     /// 设置安全级别
     Level {
         /// 域名或 Zone ID
@@ -72,6 +142,30 @@ pub enum FirewallCommands {
         level: String,
     },
 
+    /// 定时切换安全级别或其他域名设置 (由 `cfai scheduler run` 执行)
+    Schedule {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 安全级别 (off/essentially_off/low/medium/high/under_attack)
+        #[arg(long, required_unless_present = "setting", conflicts_with = "setting")]
+        level: Option<String>,
+        /// 其他域名设置 (如 rocket_loader=on)
+        #[arg(long, value_name = "NAME=VALUE")]
+        setting: Option<String>,
+        /// 时间段开始 (本地时间 HH:MM)
+        #[arg(long, requires = "to")]
+        from: Option<String>,
+        /// 时间段结束 (本地时间 HH:MM，可跨午夜)
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+        /// 时间段之外恢复的值 (--level 时默认 medium)
+        #[arg(long)]
+        revert: Option<String>,
+        /// cron 表达式 (5 段，本地时间)，到点时应用一次
+        #[arg(long, conflicts_with_all = ["from", "to", "revert"])]
+        cron: Option<String>,
+    },
+
     /// 开启 Under Attack 模式
     #[command(name = "ua-on")]
     UnderAttackOn {
@@ -91,6 +185,258 @@ pub enum FirewallCommands {
         /// 域名或 Zone ID
         domain: String,
     },
+
+    /// 查看防火墙事件日志 (被拦截/质询的请求)
+    Events {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 只显示指定动作 (block/challenge/managed_challenge/jschallenge/log/allow/skip)
+        #[arg(short, long)]
+        action: Option<String>,
+        /// 起始时间 (如 1h、30m、7d，或 ISO8601)
+        #[arg(short, long, default_value = "1h", allow_hyphen_values = true)]
+        since: String,
+        /// 结束时间 (默认现在)
+        #[arg(short, long, default_value = "0", allow_hyphen_values = true)]
+        until: String,
+        /// 只显示指定客户端 IP 的事件
+        #[arg(long)]
+        ip: Option<String>,
+        /// 最多显示的事件数
+        #[arg(short, long, default_value = "50")]
+        limit: u32,
+    },
+
+    /// 查看 Bot 管理配置，或开关 Bot Fight Mode
+    Bots {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 开启或关闭 Bot Fight Mode
+        #[arg(long, value_parser = ["on", "off"])]
+        fight_mode: Option<String>,
+    },
+
+    /// 管理速率限制规则 (add/update/delete)
+    #[command(name = "rate-limit")]
+    RateLimit {
+        #[command(subcommand)]
+        command: RateLimitCommands,
+    },
+
+    /// 管理用户代理封禁规则 (list/add/delete)
+    #[command(name = "ua-block")]
+    UaBlock {
+        #[command(subcommand)]
+        command: UaBlockCommands,
+    },
+
+    /// 管理 Zone Lockdown 规则: 只允许指定 IP 访问某些 URL (list/add/delete)
+    Lockdown {
+        #[command(subcommand)]
+        command: LockdownCommands,
+    },
+
+    /// 列出已部署与可部署的托管 WAF 规则集
+    #[command(name = "managed-rules")]
+    ManagedRules {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 部署/移除托管 WAF 规则集，或覆盖单条规则的动作 (deploy/undeploy/override)
+    #[command(name = "managed-rule")]
+    ManagedRule {
+        #[command(subcommand)]
+        command: ManagedRuleCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RateLimitCommands {
+    /// 创建速率限制规则
+    Add {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 匹配的 URL 模式 (如 *example.com/api/*)
+        #[arg(long)]
+        url: String,
+        /// 周期内允许的请求数
+        #[arg(long)]
+        threshold: u32,
+        /// 统计周期 (秒)
+        #[arg(long, default_value = "60")]
+        period: u32,
+        /// 超限动作 (ban/simulate/challenge/js_challenge/managed_challenge)
+        #[arg(long, default_value = "ban")]
+        action: String,
+        /// 封禁时长 (秒，仅 ban/simulate，默认等于统计周期)
+        #[arg(long)]
+        timeout: Option<u32>,
+        /// 描述
+        #[arg(long)]
+        description: Option<String>,
+        /// 创建后先不启用
+        #[arg(long)]
+        disabled: bool,
+    },
+
+    /// 更新速率限制规则 (未指定的参数保持不变)
+    Update {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则 ID
+        rule_id: String,
+        /// 匹配的 URL 模式
+        #[arg(long)]
+        url: Option<String>,
+        /// 周期内允许的请求数
+        #[arg(long)]
+        threshold: Option<u32>,
+        /// 统计周期 (秒)
+        #[arg(long)]
+        period: Option<u32>,
+        /// 超限动作 (ban/simulate/challenge/js_challenge/managed_challenge)
+        #[arg(long)]
+        action: Option<String>,
+        /// 封禁时长 (秒，仅 ban/simulate)
+        #[arg(long)]
+        timeout: Option<u32>,
+        /// 描述
+        #[arg(long)]
+        description: Option<String>,
+        /// 启用规则
+        #[arg(long, conflicts_with = "disable")]
+        enable: bool,
+        /// 停用规则
+        #[arg(long)]
+        disable: bool,
+    },
+
+    /// 删除速率限制规则
+    #[command(alias = "rm")]
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则 ID
+        rule_id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum UaBlockCommands {
+    /// 列出用户代理规则
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 按完整的 User-Agent 字符串创建规则
+    Add {
+        /// 域名或 Zone ID
+        domain: String,
+        /// User-Agent (需与请求头完全一致)
+        user_agent: String,
+        /// 动作 (block/challenge/js_challenge/managed_challenge)
+        #[arg(short, long, default_value = "block")]
+        mode: String,
+        /// 描述
+        #[arg(short, long)]
+        description: Option<String>,
+        /// 创建后先暂停
+        #[arg(long)]
+        paused: bool,
+    },
+
+    /// 删除用户代理规则
+    #[command(alias = "rm")]
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则 ID
+        rule_id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LockdownCommands {
+    /// 列出 Zone Lockdown 规则
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 创建规则，如锁定 /wp-admin 只允许办公室 IP 访问
+    Add {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 锁定的 URL (可重复，如 example.com/wp-admin*)
+        #[arg(short, long = "url", required = true)]
+        urls: Vec<String>,
+        /// 允许访问的 IP 或 CIDR (可重复)
+        #[arg(short, long = "ip", required = true)]
+        ips: Vec<String>,
+        /// 描述
+        #[arg(short, long)]
+        description: Option<String>,
+        /// 创建后先暂停
+        #[arg(long)]
+        paused: bool,
+    },
+
+    /// 删除 Zone Lockdown 规则
+    #[command(alias = "rm")]
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则 ID
+        rule_id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ManagedRuleCommands {
+    /// 部署托管规则集
+    Deploy {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则集 (managed/owasp/credentials/free 或规则集 ID)
+        ruleset: String,
+    },
+
+    /// 移除已部署的托管规则集
+    Undeploy {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则集 (managed/owasp/credentials/free 或规则集 ID)
+        ruleset: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// 覆盖托管规则集中单条规则的动作或开关
+    Override {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则集 (managed/owasp/credentials/free 或规则集 ID)
+        ruleset: String,
+        /// 托管规则 ID (可在 `firewall events` 的规则 ID 列中找到)
+        rule_id: String,
+        /// 覆盖后的动作 (block/challenge/managed_challenge/js_challenge/log)
+        #[arg(long)]
+        action: Option<String>,
+        /// 启用该规则
+        #[arg(long, conflicts_with = "disable")]
+        enable: bool,
+        /// 停用该规则
+        #[arg(long)]
+        disable: bool,
+        /// 移除该规则的覆盖，恢复默认行为
+        #[arg(long, conflicts_with_all = ["action", "enable", "disable"])]
+        reset: bool,
+    },
 }
 
 impl FirewallArgs {
@@ -175,23 +521,18 @@ impl FirewallArgs {
 
                 output::title(&format!("IP 访问规则 - {} (共 {} 条)", domain, rules.len()));
 
-                let mut table = output::create_table(vec!["ID", "模式", "目标", "值", "备注", "创建时间"]);
+                let mut table = output::create_table(vec!["ID", "模式", "类型", "值", "备注", "创建时间"]);
                 for rule in &rules {
-                    let (target, value) = rule
+                    let value = rule
                         .configuration
                         .as_ref()
-                        .map(|c| {
-                            (
-                                c.target.as_deref().unwrap_or("-"),
-                                c.value.as_deref().unwrap_or("-"),
-                            )
-                        })
-                        .unwrap_or(("-", "-"));
+                        .and_then(|c| c.value.as_deref())
+                        .unwrap_or("-");
 
                     table.add_row(vec![
                         rule.id.as_deref().unwrap_or("-"),
                         rule.mode.as_deref().unwrap_or("-"),
-                        target,
+                        rule_target_label(rule),
                         value,
                         rule.notes.as_deref().unwrap_or("-"),
                         rule.created_on.as_deref().unwrap_or("-"),
@@ -200,16 +541,18 @@ impl FirewallArgs {
                 println!("{table}");
             }
 
-            FirewallCommands::Block { domain, ip, note } => {
+            FirewallCommands::Block { domain, ip, country, asn, note } => {
+                let target = access_target(ip, country, *asn)?;
                 let zone_id = resolve_zone_id(client, domain).await?;
-                client.block_ip(&zone_id, ip, note.as_deref()).await?;
-                output::success(&format!("已封禁 IP: {}", ip.red()));
+                let rule = client.block_ip(&zone_id, &target, note.as_deref()).await?;
+                output::success(&format!("已封禁 {}: {}", rule_target_label(&rule), target.red()));
             }
 
-            FirewallCommands::Whitelist { domain, ip, note } => {
+            FirewallCommands::Whitelist { domain, ip, country, asn, note } => {
+                let target = access_target(ip, country, *asn)?;
                 let zone_id = resolve_zone_id(client, domain).await?;
-                client.whitelist_ip(&zone_id, ip, note.as_deref()).await?;
-                output::success(&format!("已添加白名单: {}", ip));
+                let rule = client.whitelist_ip(&zone_id, &target, note.as_deref()).await?;
+                output::success(&format!("已将 {} 加入白名单: {}", rule_target_label(&rule), target));
             }
 
             FirewallCommands::Unblock {
@@ -221,6 +564,63 @@ impl FirewallArgs {
                 output::success("IP 访问规则已删除");
             }
 
+            FirewallCommands::BlockList { domain, entries, file, mode, note, dry_run, yes } => {
+                let targets = read_access_targets(entries, file.as_deref())?;
+                block_list(client, domain, &targets, mode, note.as_deref(), *dry_run, *yes).await?;
+            }
+
+            FirewallCommands::UnblockList { domain, entries, file, dry_run, yes } => {
+                let targets = read_access_targets(entries, file.as_deref())?;
+                unblock_list(client, domain, &targets, *dry_run, *yes).await?;
+            }
+
+            FirewallCommands::Schedule { domain, level, setting, from, to, revert, cron } => {
+                let (setting, value, revert) = match (level, setting) {
+                    (Some(level), _) => {
+                        check_security_level(level)?;
+                        let revert = match (revert, cron) {
+                            (Some(revert), _) => Some(revert.clone()),
+                            (None, None) => Some("medium".to_string()),
+                            (None, Some(_)) => None,
+                        };
+                        if let Some(revert) = &revert {
+                            check_security_level(revert)?;
+                        }
+                        ("security_level".to_string(), level.clone(), revert)
+                    }
+                    (None, Some(setting)) => {
+                        let (name, value) = setting
+                            .split_once('=')
+                            .ok_or_else(|| ValidationError(format!("设置格式应为 NAME=VALUE: {}", setting)))?;
+                        (name.trim().to_string(), value.trim().to_string(), revert.clone())
+                    }
+                    (None, None) => anyhow::bail!(ValidationError("请指定 --level 或 --setting".into())),
+                };
+                let entry = ScheduleEntry {
+                    id: 0,
+                    domain: domain.clone(),
+                    setting,
+                    value,
+                    from: from.clone(),
+                    to: to.clone(),
+                    revert,
+                    cron: cron.clone(),
+                    at: None,
+                };
+                // 提前确认域名存在
+                resolve_zone_id(client, domain).await?;
+                let id = add_schedule(entry.clone())?;
+                output::success(&format!(
+                    "已添加定时设置 #{}: {} {} = {} ({})",
+                    id,
+                    domain,
+                    entry.setting,
+                    entry.value,
+                    entry.describe()
+                ));
+                output::tip("运行 `cfai scheduler run` 执行定时设置 (可配合 systemd 或 nohup 常驻)");
+            }
+
             FirewallCommands::Level { domain, level } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
                 client.set_security_level(&zone_id, level).await?;
@@ -239,6 +639,139 @@ impl FirewallArgs {
                 output::success(&format!("{} Under Attack 模式已关闭", domain));
             }
 
+            FirewallCommands::Bots { domain, fight_mode } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let config = match fight_mode {
+                    Some(mode) => {
+                        let request = BotManagementConfig {
+                            fight_mode: Some(mode == "on"),
+                            ..Default::default()
+                        };
+                        let config = client.update_bot_management(&zone_id, &request).await?;
+                        if format != "json" {
+                            output::success(&format!(
+                                "Bot Fight Mode 已{}",
+                                if mode == "on" { "开启" } else { "关闭" }
+                            ));
+                        }
+                        config
+                    }
+                    None => client.get_bot_management(&zone_id).await?,
+                };
+
+                if format == "json" {
+                    output::print_json(&config);
+                    return Ok(());
+                }
+
+                output::title(&format!("Bot 管理 - {}", domain));
+                let on_off = |v: Option<bool>| match v {
+                    Some(true) => "开启",
+                    Some(false) => "关闭",
+                    None => "-",
+                };
+                output::kv_colored(
+                    "Bot Fight Mode",
+                    on_off(config.fight_mode),
+                    config.fight_mode == Some(true),
+                );
+                output::kv("JavaScript 检测", on_off(config.enable_js));
+                if config.sbfm_definitely_automated.is_some() || config.sbfm_likely_automated.is_some() {
+                    output::kv(
+                        "确定自动化流量",
+                        config.sbfm_definitely_automated.as_deref().unwrap_or("-"),
+                    );
+                    output::kv(
+                        "可能自动化流量",
+                        config.sbfm_likely_automated.as_deref().unwrap_or("-"),
+                    );
+                    output::kv("已验证 Bot", config.sbfm_verified_bots.as_deref().unwrap_or("-"));
+                    output::kv("静态资源保护", on_off(config.sbfm_static_resource_protection));
+                    output::kv("WordPress 优化", on_off(config.optimize_wordpress));
+                }
+                if let Some(ai_bots) = &config.ai_bots_protection {
+                    output::kv_colored("AI 爬虫拦截", ai_bots, ai_bots == "block");
+                }
+                if fight_mode.is_none() {
+                    output::tip(&format!(
+                        "使用 `cfai firewall bots {} --fight-mode on` 开启 Bot Fight Mode，`cfai analytics bots {}` 查看 Bot 评分分布",
+                        domain, domain
+                    ));
+                }
+            }
+
+            FirewallCommands::Events {
+                domain,
+                action,
+                since,
+                until,
+                ip,
+                limit,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let now = chrono::Utc::now();
+                let query = FirewallEventQuery {
+                    since: parse_time(since, now)?,
+                    until: parse_time(until, now)?,
+                    action: action.clone(),
+                    client_ip: ip.clone(),
+                    limit: *limit,
+                };
+                let events = client.list_firewall_events(&zone_id, &query).await?;
+
+                if format == "json" {
+                    output::print_json(&events);
+                    return Ok(());
+                }
+
+                output::title(&format!(
+                    "防火墙事件 - {} ({} 起，共 {} 条)",
+                    domain,
+                    query.since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                    events.len()
+                ));
+
+                if events.is_empty() {
+                    output::success("该时间范围内没有防火墙事件");
+                    return Ok(());
+                }
+
+                let mut table = output::create_table(vec!["时间", "动作", "来源", "规则 ID", "客户端 IP", "国家", "请求"]);
+                for e in &events {
+                    let action = e.action.as_deref().unwrap_or("-");
+                    let action = match action {
+                        "block" => action.red().to_string(),
+                        "challenge" | "managed_challenge" | "jschallenge" => action.yellow().to_string(),
+                        _ => action.to_string(),
+                    };
+                    let time = e
+                        .datetime
+                        .as_deref()
+                        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                        .map(|t| t.with_timezone(&chrono::Local).format("%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_else(|| "-".into());
+                    table.add_row(vec![
+                        time,
+                        action,
+                        e.source.clone().unwrap_or("-".into()),
+                        e.rule_id.clone().unwrap_or("-".into()),
+                        e.client_ip.clone().unwrap_or("-".into()),
+                        e.client_country.clone().unwrap_or("-".into()),
+                        format!(
+                            "{} {}{}",
+                            e.method.as_deref().unwrap_or(""),
+                            e.host.as_deref().unwrap_or(""),
+                            e.path.as_deref().unwrap_or("")
+                        ),
+                    ]);
+                }
+                println!("{table}");
+
+                if events.len() as u32 >= *limit {
+                    output::tip(&format!("仅显示最近 {} 条，可使用 --limit 或 --action/--ip 缩小范围", limit));
+                }
+            }
+
             FirewallCommands::RateLimits { domain } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
                 let rules = client.list_rate_limits(&zone_id).await?;
@@ -267,9 +800,700 @@ impl FirewallArgs {
                             .and_then(|a| a.mode.clone())
                             .unwrap_or("-".into()),
                     );
+                    output::kv("URL", rule.url().unwrap_or("-"));
+                    if rule.disabled == Some(true) {
+                        output::kv("状态", "已停用");
+                    }
                     println!();
                 }
             }
+
+            FirewallCommands::RateLimit { command } => command.execute(client, format).await?,
+
+            FirewallCommands::UaBlock { command } => command.execute(client, format).await?,
+
+            FirewallCommands::Lockdown { command } => command.execute(client, format).await?,
+
+            FirewallCommands::ManagedRules { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let (entrypoint, rulesets) = tokio::try_join!(
+                    client.get_phase_ruleset(&zone_id, WAF_MANAGED_PHASE),
+                    client.list_zone_rulesets(&zone_id),
+                )?;
+                let deployed: Vec<RulesetRule> = entrypoint
+                    .and_then(|r| r.rules)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|r| r.executed_ruleset_id().is_some())
+                    .collect();
+                let available: Vec<&Ruleset> = rulesets
+                    .iter()
+                    .filter(|r| {
+                        r.kind.as_deref() == Some("managed")
+                            && r.phase.as_deref() == Some(WAF_MANAGED_PHASE)
+                    })
+                    .collect();
+
+                if format == "json" {
+                    output::print_json(&serde_json::json!({
+                        "deployed": deployed,
+                        "available": available,
+                    }));
+                    return Ok(());
+                }
+
+                let ruleset_name = |id: &str| {
+                    available
+                        .iter()
+                        .find(|r| r.id.as_deref() == Some(id))
+                        .and_then(|r| r.name.clone())
+                        .or_else(|| managed_ruleset_name(id).map(String::from))
+                        .unwrap_or_else(|| id.to_string())
+                };
+
+                output::title(&format!("托管 WAF 规则集 - {} (已部署 {} 个)", domain, deployed.len()));
+                if deployed.is_empty() {
+                    output::info("尚未部署托管规则集");
+                } else {
+                    let mut table = output::create_table(vec!["规则集", "规则集 ID", "状态", "规则覆盖", "表达式"]);
+                    for rule in &deployed {
+                        let id = rule.executed_ruleset_id().unwrap_or_default();
+                        let overrides = rule
+                            .action_parameters
+                            .as_ref()
+                            .and_then(|p| p.pointer("/overrides/rules"))
+                            .and_then(|r| r.as_array())
+                            .map(|r| r.len())
+                            .unwrap_or(0);
+                        table.add_row(vec![
+                            ruleset_name(id),
+                            id.to_string(),
+                            if rule.enabled == Some(false) {
+                                "已停用".yellow().to_string()
+                            } else {
+                                "已启用".green().to_string()
+                            },
+                            overrides.to_string(),
+                            rule.expression.clone().unwrap_or("-".into()),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+
+                let undeployed: Vec<&&Ruleset> = available
+                    .iter()
+                    .filter(|r| {
+                        !deployed
+                            .iter()
+                            .any(|d| d.executed_ruleset_id() == r.id.as_deref())
+                    })
+                    .collect();
+                if !undeployed.is_empty() {
+                    output::title("可部署的托管规则集");
+                    let mut table = output::create_table(vec!["名称", "规则集 ID", "描述"]);
+                    for r in undeployed {
+                        table.add_row(vec![
+                            r.name.clone().unwrap_or("-".into()),
+                            r.id.clone().unwrap_or("-".into()),
+                            r.description.clone().unwrap_or("-".into()),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+                output::tip(&format!(
+                    "使用 `cfai firewall managed-rule deploy {} owasp` 部署规则集，`managed-rule override` 调整单条规则",
+                    domain
+                ));
+            }
+
+            FirewallCommands::ManagedRule { command } => command.execute(client, format).await?,
+        }
+
+        Ok(())
+    }
+}
+
+impl ManagedRuleCommands {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match self {
+            ManagedRuleCommands::Deploy { domain, ruleset } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let ruleset_id = managed_ruleset_id(ruleset);
+                let entrypoint = client.get_phase_ruleset(&zone_id, WAF_MANAGED_PHASE).await?;
+                if find_managed_rule(entrypoint.as_ref(), ruleset_id).is_some() {
+                    anyhow::bail!(ValidationError(format!("规则集 {} 已部署", ruleset)));
+                }
+
+                let rule = RulesetRuleRequest {
+                    action: "execute".into(),
+                    expression: "true".into(),
+                    description: Some(
+                        managed_ruleset_name(ruleset_id)
+                            .map(String::from)
+                            .unwrap_or_else(|| format!("Managed ruleset {}", ruleset_id)),
+                    ),
+                    enabled: Some(true),
+                    action_parameters: Some(serde_json::json!({ "id": ruleset_id })),
+                };
+                let result = match entrypoint.as_ref().and_then(|r| r.id.as_deref()) {
+                    Some(entry_id) => client.create_ruleset_rule(&zone_id, entry_id, &rule).await?,
+                    None => {
+                        client
+                            .update_phase_ruleset(&zone_id, WAF_MANAGED_PHASE, &[rule])
+                            .await?
+                    }
+                };
+
+                if format == "json" {
+                    output::print_json(&result);
+                    return Ok(());
+                }
+                output::success(&format!("托管规则集 {} 已部署", ruleset));
+            }
+
+            ManagedRuleCommands::Undeploy {
+                domain,
+                ruleset,
+                yes,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let ruleset_id = managed_ruleset_id(ruleset);
+                let entrypoint = client.get_phase_ruleset(&zone_id, WAF_MANAGED_PHASE).await?;
+                let (entry_id, rule) = find_managed_rule(entrypoint.as_ref(), ruleset_id)
+                    .ok_or_else(|| ValidationError(format!("规则集 {} 未部署", ruleset)))?;
+
+                if !yes {
+                    let confirm = prompt::confirm(format!(
+                        "确定要移除托管规则集 {} 吗？其中的规则覆盖也会一并删除",
+                        ruleset.yellow()
+                    ))?;
+                    if !confirm {
+                        output::info("已取消");
+                        return Ok(());
+                    }
+                }
+
+                let rule_id = rule.id.as_deref().unwrap_or_default();
+                client.delete_ruleset_rule(&zone_id, entry_id, rule_id).await?;
+                output::success(&format!("托管规则集 {} 已移除", ruleset));
+            }
+
+            ManagedRuleCommands::Override {
+                domain,
+                ruleset,
+                rule_id,
+                action,
+                enable,
+                disable,
+                reset,
+            } => {
+                let enabled = match (enable, disable) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                };
+                if !reset && action.is_none() && enabled.is_none() {
+                    anyhow::bail!(ValidationError(
+                        "请至少指定 --action、--enable/--disable 或 --reset 之一".into()
+                    ));
+                }
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let ruleset_id = managed_ruleset_id(ruleset);
+                let entrypoint = client.get_phase_ruleset(&zone_id, WAF_MANAGED_PHASE).await?;
+                let (entry_id, rule) = find_managed_rule(entrypoint.as_ref(), ruleset_id)
+                    .ok_or_else(|| {
+                        ValidationError(format!(
+                            "规则集 {} 未部署，请先运行 `cfai firewall managed-rule deploy {} {}`",
+                            ruleset, domain, ruleset
+                        ))
+                    })?;
+
+                let mut request = rule.to_request();
+                let params = request
+                    .action_parameters
+                    .get_or_insert_with(|| serde_json::json!({ "id": ruleset_id }));
+                set_rule_override(params, rule_id, action.as_deref(), enabled);
+                let managed_rule_id = rule.id.as_deref().unwrap_or_default();
+                let result = client
+                    .update_ruleset_rule(&zone_id, entry_id, managed_rule_id, &request)
+                    .await?;
+
+                if format == "json" {
+                    output::print_json(&result);
+                    return Ok(());
+                }
+                if *reset {
+                    output::success(&format!("规则 {} 已恢复默认行为", rule_id));
+                } else {
+                    let mut changes = Vec::new();
+                    if let Some(action) = action {
+                        changes.push(format!("动作 → {}", action));
+                    }
+                    if let Some(enabled) = enabled {
+                        changes.push(if enabled { "已启用" } else { "已停用" }.to_string());
+                    }
+                    output::success(&format!("规则 {} 已覆盖: {}", rule_id, changes.join(", ")));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 检查安全级别取值
+fn check_security_level(level: &str) -> Result<()> {
+    const LEVELS: &[&str] = &["off", "essentially_off", "low", "medium", "high", "under_attack"];
+    if !LEVELS.contains(&level) {
+        anyhow::bail!(ValidationError(format!("未知的安全级别: {} (可选: {})", level, LEVELS.join("/"))));
+    }
+    Ok(())
+}
+
+/// 由 block / whitelist 的参数得到规则目标
+fn access_target(ip: &Option<String>, country: &Option<String>, asn: Option<u32>) -> Result<String> {
+    match (ip, country, asn) {
+        (Some(ip), _, _) if ip.contains('/') || ip.parse::<std::net::IpAddr>().is_ok() => Ok(ip.clone()),
+        (Some(ip), _, _) => anyhow::bail!(ValidationError(format!("无效的 IP 地址或 CIDR: {}", ip))),
+        (_, Some(country), _) if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) => {
+            Ok(country.to_uppercase())
+        }
+        (_, Some(country), _) => anyhow::bail!(ValidationError(format!("国家代码应为两个字母: {}", country))),
+        (_, _, Some(asn)) => Ok(format!("AS{}", asn)),
+        _ => anyhow::bail!(ValidationError("请指定 IP、--country 或 --asn".into())),
+    }
+}
+
+/// IP 访问规则的目标类型
+fn rule_target_label(rule: &IpAccessRule) -> &'static str {
+    match rule.configuration.as_ref().and_then(|c| c.target.as_deref()) {
+        Some("ip") => "IP",
+        Some("ip6") => "IPv6",
+        Some("ip_range") => "IP 段",
+        Some("asn") => "ASN",
+        Some("country") => "国家",
+        _ => "-",
+    }
+}
+
+/// IP 访问规则支持的动作
+const ACCESS_RULE_MODES: &[&str] = &["block", "challenge", "js_challenge", "managed_challenge", "whitelist"];
+
+/// 合并参数与文件中的列表项，识别类型并去重 (保持原有顺序)
+fn read_access_targets(entries: &[String], file: Option<&Path>) -> Result<Vec<IpAccessRuleConfig>> {
+    let mut content = entries.join("\n");
+    match file {
+        Some(path) if path == Path::new("-") => {
+            std::io::stdin().read_to_string(&mut content).context("读取标准输入失败")?;
+        }
+        Some(path) => {
+            let text = std::fs::read_to_string(path).with_context(|| format!("读取列表文件失败: {}", path.display()))?;
+            content.push('\n');
+            content.push_str(&text);
+        }
+        None => {}
+    }
+
+    let mut targets: Vec<IpAccessRuleConfig> = Vec::new();
+    let mut invalid = Vec::new();
+    let items = content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|item| !item.is_empty());
+    for item in items {
+        match IpAccessRuleConfig::parse(item) {
+            Some(target) if !targets.contains(&target) => targets.push(target),
+            Some(_) => {}
+            None => invalid.push(item.to_string()),
+        }
+    }
+    if !invalid.is_empty() {
+        anyhow::bail!(ValidationError(format!(
+            "无法识别的列表项 (应为 IP、CIDR、ASN 或国家代码): {}",
+            invalid.join(", ")
+        )));
+    }
+    if targets.is_empty() {
+        anyhow::bail!(ValidationError("列表为空: 请在参数中列出或使用 --file 指定文件".into()));
+    }
+    Ok(targets)
+}
+
+async fn block_list(
+    client: &CfClient,
+    domain: &str,
+    targets: &[IpAccessRuleConfig],
+    mode: &str,
+    note: Option<&str>,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    if !ACCESS_RULE_MODES.contains(&mode) {
+        anyhow::bail!(ValidationError(format!("未知的动作: {} (可选: {})", mode, ACCESS_RULE_MODES.join("/"))));
+    }
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let existing = client.list_ip_access_rules(&zone_id).await?;
+    let (pending, skipped): (Vec<_>, Vec<_>) = targets.iter().partition(|target| {
+        !existing
+            .iter()
+            .any(|rule| rule.configuration.as_ref().is_some_and(|c| target.matches(c)))
+    });
+
+    if !skipped.is_empty() {
+        output::info(&format!("跳过 {} 项已有规则的目标", skipped.len()));
+    }
+    if pending.is_empty() {
+        output::success("列表中的目标均已有规则，无需创建");
+        return Ok(());
+    }
+    output::title(&format!("将为 {} 创建 {} 条 {} 规则", domain, pending.len(), mode));
+    for target in &pending {
+        println!("  {} {}", format!("{:<9}", target.target).dimmed(), target.value);
+    }
+    if dry_run {
+        output::info("预览模式 (--dry-run)，未执行任何变更");
+        return Ok(());
+    }
+    if !yes {
+        let confirm = prompt::confirm(format!("确定要创建以上 {} 条规则吗？", pending.len()))?;
+        if !confirm {
+            output::info("已取消操作");
+            return Ok(());
+        }
+    }
+
+    let mut progress = Progress::terminal("创建规则", pending.len());
+    for target in &pending {
+        progress.start(format!("{} {}", mode, target.value));
+        let request = CreateIpAccessRuleRequest {
+            mode: mode.to_string(),
+            configuration: (*target).clone(),
+            notes: note.map(|n| n.to_string()),
+        };
+        progress.record(&client.create_ip_access_rule(&zone_id, &request).await);
+    }
+    finish_batch(pending.len(), progress.finish().failed, "创建")
+}
+
+async fn unblock_list(
+    client: &CfClient,
+    domain: &str,
+    targets: &[IpAccessRuleConfig],
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let existing = client.list_ip_access_rules(&zone_id).await?;
+    let matched: Vec<_> = existing
+        .iter()
+        .filter(|rule| {
+            rule.configuration
+                .as_ref()
+                .is_some_and(|c| targets.iter().any(|target| target.matches(c)))
+        })
+        .collect();
+
+    let missing = targets
+        .iter()
+        .filter(|target| !matched.iter().any(|rule| rule.configuration.as_ref().is_some_and(|c| target.matches(c))))
+        .count();
+    if missing > 0 {
+        output::info(&format!("{} 项没有对应的规则", missing));
+    }
+    if matched.is_empty() {
+        output::success("没有需要删除的规则");
+        return Ok(());
+    }
+    output::title(&format!("将从 {} 删除 {} 条规则", domain, matched.len()));
+    for rule in &matched {
+        let value = rule.configuration.as_ref().and_then(|c| c.value.as_deref()).unwrap_or("-");
+        println!("  {} {}", format!("{:<9}", rule.mode.as_deref().unwrap_or("-")).dimmed(), value);
+    }
+    if dry_run {
+        output::info("预览模式 (--dry-run)，未执行任何变更");
+        return Ok(());
+    }
+    if !yes {
+        let confirm = prompt::confirm(format!("确定要删除以上 {} 条规则吗？", matched.len()))?;
+        if !confirm {
+            output::info("已取消操作");
+            return Ok(());
+        }
+    }
+
+    let mut progress = Progress::terminal("删除规则", matched.len());
+    for rule in &matched {
+        let value = rule.configuration.as_ref().and_then(|c| c.value.as_deref()).unwrap_or("-");
+        progress.start(format!("删除 {}", value));
+        let result = match &rule.id {
+            Some(id) => client.delete_ip_access_rule(&zone_id, id).await,
+            None => Err(anyhow::anyhow!("规则缺少 ID")),
+        };
+        progress.record(&result);
+    }
+    finish_batch(matched.len(), progress.finish().failed, "删除")
+}
+
+/// 汇总批量操作结果，部分失败时返回 PartialFailure
+fn finish_batch(total: usize, failed: usize, action: &str) -> Result<()> {
+    match failed {
+        0 => {
+            output::success(&format!("已{} {} 条规则", action, total));
+            Ok(())
+        }
+        f if f == total => anyhow::bail!("{} 条规则全部{}失败", total, action),
+        _ => Err(PartialFailure { total, failed }.into()),
+    }
+}
+
+/// 在入口规则集中查找部署指定托管规则集的 execute 规则，返回 (入口规则集 ID, 规则)
+fn find_managed_rule<'a>(
+    entrypoint: Option<&'a Ruleset>,
+    ruleset_id: &str,
+) -> Option<(&'a str, &'a RulesetRule)> {
+    let entrypoint = entrypoint?;
+    let rule = entrypoint
+        .rules
+        .as_ref()?
+        .iter()
+        .find(|r| r.executed_ruleset_id() == Some(ruleset_id))?;
+    Some((entrypoint.id.as_deref()?, rule))
+}
+
+impl UaBlockCommands {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match self {
+            UaBlockCommands::List { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let rules = client.list_ua_rules(&zone_id).await?;
+
+                if format == "json" {
+                    output::print_json(&rules);
+                    return Ok(());
+                }
+                if rules.is_empty() {
+                    output::info("没有用户代理规则");
+                    return Ok(());
+                }
+
+                output::title(&format!("用户代理规则 - {} (共 {} 条)", domain, rules.len()));
+                let mut table = output::create_table(vec!["ID", "动作", "User-Agent", "描述", "状态"]);
+                for rule in &rules {
+                    table.add_row(vec![
+                        rule.id.as_deref().unwrap_or("-"),
+                        rule.mode.as_deref().unwrap_or("-"),
+                        rule.configuration.as_ref().and_then(|c| c.value.as_deref()).unwrap_or("-"),
+                        rule.description.as_deref().unwrap_or("-"),
+                        if rule.paused == Some(true) { "已暂停" } else { "启用" },
+                    ]);
+                }
+                println!("{table}");
+            }
+
+            UaBlockCommands::Add { domain, user_agent, mode, description, paused } => {
+                if !matches!(mode.as_str(), "block" | "challenge" | "js_challenge" | "managed_challenge") {
+                    anyhow::bail!(ValidationError(format!(
+                        "未知的动作: {} (可选: block/challenge/js_challenge/managed_challenge)",
+                        mode
+                    )));
+                }
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let mut request = UserAgentRuleRequest::new(user_agent, mode);
+                request.description = description.clone();
+                request.paused = *paused;
+
+                let rule = client.create_ua_rule(&zone_id, &request).await?;
+                if format == "json" {
+                    output::print_json(&rule);
+                    return Ok(());
+                }
+                output::success(&format!("用户代理规则已创建: {} → {}", user_agent, mode));
+                output::kv("规则 ID", rule.id.as_deref().unwrap_or("-"));
+            }
+
+            UaBlockCommands::Delete { domain, rule_id } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.delete_ua_rule(&zone_id, rule_id).await?;
+                output::success("用户代理规则已删除");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LockdownCommands {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match self {
+            LockdownCommands::List { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let rules = client.list_lockdowns(&zone_id).await?;
+
+                if format == "json" {
+                    output::print_json(&rules);
+                    return Ok(());
+                }
+                if rules.is_empty() {
+                    output::info("没有 Zone Lockdown 规则");
+                    return Ok(());
+                }
+
+                output::title(&format!("Zone Lockdown 规则 - {} (共 {} 条)", domain, rules.len()));
+                let mut table = output::create_table(vec!["ID", "URL", "允许的 IP", "描述", "状态"]);
+                for rule in &rules {
+                    let ips: Vec<&str> = rule
+                        .configurations
+                        .iter()
+                        .flatten()
+                        .filter_map(|c| c.value.as_deref())
+                        .collect();
+                    table.add_row(vec![
+                        rule.id.as_deref().unwrap_or("-").to_string(),
+                        rule.urls.as_deref().unwrap_or_default().join("\n"),
+                        ips.join("\n"),
+                        rule.description.as_deref().unwrap_or("-").to_string(),
+                        if rule.paused == Some(true) { "已暂停" } else { "启用" }.to_string(),
+                    ]);
+                }
+                println!("{table}");
+            }
+
+            LockdownCommands::Add { domain, urls, ips, description, paused } => {
+                let configurations = ips
+                    .iter()
+                    .map(|ip| match IpAccessRuleConfig::parse(ip) {
+                        Some(c) if c.target == "ip_range" => Ok(c),
+                        Some(c) if c.target == "ip" || c.target == "ip6" => {
+                            Ok(IpAccessRuleConfig { target: "ip".to_string(), value: c.value })
+                        }
+                        _ => Err(ValidationError(format!("无效的 IP 地址或 CIDR: {}", ip))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let request = LockdownRuleRequest {
+                    urls: urls.clone(),
+                    configurations,
+                    description: description.clone(),
+                    paused: *paused,
+                };
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let rule = client.create_lockdown(&zone_id, &request).await?;
+                if format == "json" {
+                    output::print_json(&rule);
+                    return Ok(());
+                }
+                output::success(&format!("Zone Lockdown 规则已创建: {} 只允许 {} 访问", urls.join(", "), ips.join(", ")));
+                output::kv("规则 ID", rule.id.as_deref().unwrap_or("-"));
+            }
+
+            LockdownCommands::Delete { domain, rule_id } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.delete_lockdown(&zone_id, rule_id).await?;
+                output::success("Zone Lockdown 规则已删除");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RateLimitCommands {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match self {
+            RateLimitCommands::Add {
+                domain,
+                url,
+                threshold,
+                period,
+                action,
+                timeout,
+                description,
+                disabled,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.check_quota(&zone_id, QuotaResource::RateLimit).await?;
+
+                let timeout = match action.as_str() {
+                    "ban" | "simulate" => Some(timeout.unwrap_or(*period)),
+                    _ => *timeout,
+                };
+                let mut request = RateLimitRequest::new(url, *threshold, *period, action, timeout);
+                request.description = description.clone();
+                request.disabled = disabled.then_some(true);
+
+                let rule = client.create_rate_limit(&zone_id, &request).await?;
+                if format == "json" {
+                    output::print_json(&rule);
+                    return Ok(());
+                }
+                output::success(&format!(
+                    "速率限制规则已创建: {} ({} 次/{}s → {})",
+                    url,
+                    threshold,
+                    period,
+                    action
+                ));
+                output::kv("规则 ID", rule.id.as_deref().unwrap_or("-"));
+            }
+
+            RateLimitCommands::Update {
+                domain,
+                rule_id,
+                url,
+                threshold,
+                period,
+                action,
+                timeout,
+                description,
+                enable,
+                disable,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let current = client.get_rate_limit(&zone_id, rule_id).await?;
+                let mut request = RateLimitRequest::from_rule(&current);
+
+                if let Some(url) = url {
+                    request.set_url(url);
+                }
+                if let Some(t) = threshold {
+                    request.threshold = *t;
+                }
+                if let Some(p) = period {
+                    request.period = *p;
+                }
+                if let Some(mode) = action {
+                    request.action.mode = Some(mode.clone());
+                    // 质询类动作不接受 timeout，ban/simulate 缺省时沿用统计周期
+                    request.action.timeout = match mode.as_str() {
+                        "ban" | "simulate" => request.action.timeout.or(Some(request.period)),
+                        _ => None,
+                    };
+                }
+                if timeout.is_some() {
+                    request.action.timeout = *timeout;
+                }
+                if description.is_some() {
+                    request.description = description.clone();
+                }
+                if *enable {
+                    request.disabled = Some(false);
+                } else if *disable {
+                    request.disabled = Some(true);
+                }
+
+                let rule = client.update_rate_limit(&zone_id, rule_id, &request).await?;
+                if format == "json" {
+                    output::print_json(&rule);
+                    return Ok(());
+                }
+                output::success(&format!("速率限制规则 {} 已更新", rule_id));
+            }
+
+            RateLimitCommands::Delete { domain, rule_id } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.delete_rate_limit(&zone_id, rule_id).await?;
+                output::success("速率限制规则已删除");
+            }
         }
 
         Ok(())
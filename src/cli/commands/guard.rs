@@ -0,0 +1,313 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::config::settings::{AppConfig, GuardConfig};
+use crate::models::analytics::LiveAnalytics;
+use crate::models::firewall::RateLimitRequest;
+use crate::notify::{self, Level, Notification};
+
+use super::zone::resolve_zone_id;
+
+/// 速率限制规则的默认阈值 (次)、周期 (秒) 与动作
+const DEFAULT_RATE_LIMIT: (u32, u32, &str) = (100, 60, "managed_challenge");
+
+/// 自动缓解: 监控实时流量，超过 `[guard]` 阈值时开启 Under Attack 模式，恢复正常后还原
+#[derive(Args, Debug)]
+pub struct GuardArgs {
+    /// 域名或 Zone ID
+    pub domain: String,
+    /// 每秒请求数阈值 (覆盖 guard.requests_per_second)
+    #[arg(long)]
+    pub rps: Option<f64>,
+    /// 统计窗口内的威胁数阈值 (覆盖 guard.threats)
+    #[arg(long)]
+    pub threats: Option<u64>,
+    /// 检测间隔 (秒，默认 60)
+    #[arg(short, long)]
+    pub interval: Option<u64>,
+    /// 统计窗口 (分钟，默认 5)
+    #[arg(short, long)]
+    pub window: Option<i64>,
+    /// 流量持续低于阈值多久后解除防护 (分钟，默认 15)
+    #[arg(long)]
+    pub cooldown: Option<u64>,
+    /// 开启防护时同时创建速率限制规则 (覆盖 guard.rate_limit)
+    #[arg(long)]
+    pub rate_limit: bool,
+    /// 运行多久后退出 (秒)，默认一直运行直到 Ctrl+C
+    #[arg(long)]
+    pub duration: Option<u64>,
+    /// 只检测并提示，不修改任何设置
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl GuardConfig {
+    /// 速率限制规则的阈值、周期与动作，未设置时使用默认值
+    pub fn rate_limit_rule(&self) -> (u32, u32, &str) {
+        (
+            self.rate_limit_threshold.unwrap_or(DEFAULT_RATE_LIMIT.0),
+            self.rate_limit_period.unwrap_or(DEFAULT_RATE_LIMIT.1),
+            self.rate_limit_action.as_deref().unwrap_or(DEFAULT_RATE_LIMIT.2),
+        )
+    }
+}
+
+/// 已开启的防护，解除时据此恢复
+struct Mitigation {
+    /// 开启前的安全级别
+    previous_level: String,
+    /// 自动创建的速率限制规则
+    rate_limit_id: Option<String>,
+    /// 流量开始低于阈值的时间
+    calm_since: Option<Instant>,
+}
+
+impl GuardArgs {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        let guard = &config.guard;
+        let rps = self.rps.or(guard.requests_per_second);
+        let threats = self.threats.or(guard.threats);
+        if rps.is_none() && threats.is_none() {
+            anyhow::bail!(ValidationError(
+                "未设置触发阈值，请使用 --rps / --threats 或 `cfai config set guard.requests_per_second 500`".into()
+            ));
+        }
+        let interval = self.interval.or(guard.interval_secs).unwrap_or(60);
+        let window = self.window.or(guard.window_minutes).unwrap_or(5);
+        let cooldown = Duration::from_secs(60 * self.cooldown.or(guard.cooldown_minutes).unwrap_or(15));
+        if interval == 0 {
+            anyhow::bail!(ValidationError("检测间隔必须大于 0 秒".into()));
+        }
+        if window <= 0 {
+            anyhow::bail!(ValidationError("统计窗口必须大于 0 分钟".into()));
+        }
+        let rate_limit = self.rate_limit || guard.rate_limit.unwrap_or(false);
+
+        let zone_id = resolve_zone_id(client, &self.domain).await?;
+        let zone = client.get_zone(&zone_id).await?;
+        let domain = zone.name.as_str();
+
+        output::title(&format!("自动防护 - {}", domain));
+        output::kv(
+            "触发阈值",
+            &[
+                rps.map(|r| format!("{} 次/秒", r)),
+                threats.map(|t| format!("{} 次威胁", t)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" 或 "),
+        );
+        output::kv("检测", &format!("每 {} 秒统计最近 {} 分钟", interval, window));
+        output::kv("解除", &format!("流量正常 {} 分钟后", cooldown.as_secs() / 60));
+        if rate_limit {
+            let (threshold, period, action) = guard.rate_limit_rule();
+            output::kv("速率限制", &format!("{} 次/{}s → {}", threshold, period, action));
+        }
+        if self.dry_run {
+            output::warn("试运行: 只检测并提示，不修改任何设置");
+        }
+        if !client.audit_enabled() {
+            output::warn("审计日志未开启，自动防护的变更将无法通过 `cfai undo` 撤销");
+        }
+
+        let initial_level = client.get_security_level(&zone_id).await?;
+        if initial_level == "under_attack" {
+            output::warn("当前已处于 Under Attack 模式，流量恢复后不会自动关闭");
+        }
+        println!();
+
+        let deadline = self.duration.map(|secs| Instant::now() + Duration::from_secs(secs));
+        let mut mitigation: Option<Mitigation> = None;
+
+        loop {
+            let snapshot = tokio::select! {
+                result = client.get_live_analytics(&zone_id, window) => result,
+                _ = tokio::signal::ctrl_c() => break,
+            };
+
+            match snapshot {
+                Ok(live) => {
+                    if format == "json" {
+                        println!("{}", serde_json::to_string(&live)?);
+                    }
+                    let breach = live.exceeds(threats, rps);
+                    let mut release = false;
+                    match (&mut mitigation, breach) {
+                        (None, Some(reason)) => {
+                            log(format, "🚨", &format!("{} ({})", reason, summary(&live)));
+                            if self.dry_run {
+                                log(format, "🔍", "试运行: 将开启 Under Attack 模式");
+                            } else if initial_level != "under_attack" {
+                                mitigation = Some(
+                                    self.enable(client, config, &zone_id, domain, &reason, rate_limit, format)
+                                        .await?,
+                                );
+                            }
+                        }
+                        (Some(active), Some(reason)) => {
+                            if active.calm_since.take().is_some() {
+                                log(format, "🚨", &format!("流量再次超过阈值: {}", reason));
+                            }
+                        }
+                        (Some(active), None) => {
+                            let calm_since = *active.calm_since.get_or_insert_with(Instant::now);
+                            let calm = calm_since.elapsed();
+                            if calm >= cooldown {
+                                release = true;
+                            } else {
+                                log(
+                                    format,
+                                    "⏳",
+                                    &format!(
+                                        "流量已低于阈值 ({})，{} 分钟后解除防护",
+                                        summary(&live),
+                                        (cooldown - calm).as_secs().div_ceil(60)
+                                    ),
+                                );
+                            }
+                        }
+                        (None, None) => {
+                            if format != "json" {
+                                log(format, "✓", &summary(&live));
+                            }
+                        }
+                    }
+                    if let Some(active) = mitigation.take_if(|_| release) {
+                        self.disable(client, config, &zone_id, domain, active, format).await?;
+                    }
+                }
+                Err(e) => output::warn(&format!("获取实时流量失败: {:#}", e)),
+            }
+
+            let mut wait = Duration::from_secs(interval);
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                wait = wait.min(remaining);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+            if deadline.is_some_and(|d| Instant::now() >= d) {
+                break;
+            }
+        }
+
+        // 退出时不自动解除，避免攻击仍在进行时失去防护
+        if let Some(active) = mitigation {
+            output::warn("退出时防护仍在生效，确认攻击结束后可手动恢复:");
+            println!("  cfai firewall level {} {}", self.domain, active.previous_level);
+            if let Some(id) = &active.rate_limit_id {
+                println!("  cfai firewall rate-limit delete {} {}", self.domain, id);
+            }
+        }
+        Ok(())
+    }
+
+    /// 开启 Under Attack 模式 (及速率限制规则)
+    #[allow(clippy::too_many_arguments)]
+    async fn enable(
+        &self,
+        client: &CfClient,
+        config: &AppConfig,
+        zone_id: &str,
+        domain: &str,
+        reason: &str,
+        rate_limit: bool,
+        format: &str,
+    ) -> Result<Mitigation> {
+        let previous_level = client.get_security_level(zone_id).await?;
+        client.set_security_level(zone_id, "under_attack").await?;
+        log(format, "🛡", &format!("已开启 Under Attack 模式 (原安全级别: {})", previous_level));
+
+        let mut rate_limit_id = None;
+        if rate_limit {
+            let (threshold, period, action) = config.guard.rate_limit_rule();
+            let timeout = (action == "ban").then_some(period);
+            let mut request = RateLimitRequest::new(&format!("*{}/*", domain), threshold, period, action, timeout);
+            request.description = Some("cfai guard 自动创建".to_string());
+            // 速率限制失败不影响已开启的 Under Attack 模式
+            match client.create_rate_limit(zone_id, &request).await {
+                Ok(rule) => {
+                    let id = rule.id.as_deref().unwrap_or("-");
+                    log(
+                        format,
+                        "🛡",
+                        &format!("已创建速率限制规则 {} ({} 次/{}s → {})", id, threshold, period, action),
+                    );
+                    rate_limit_id = rule.id;
+                }
+                Err(e) => output::warn(&format!("创建速率限制规则失败: {:#}", e)),
+            }
+        }
+
+        let mut message = format!("{}\n已开启 Under Attack 模式 (原安全级别: {})", reason, previous_level);
+        if let Some(id) = &rate_limit_id {
+            message.push_str(&format!("\n已创建速率限制规则 {}", id));
+        }
+        let title = format!("{} 已自动开启防护", domain);
+        notify::send_quietly(config, &Notification::new(Level::Alert, title, message)).await;
+
+        Ok(Mitigation {
+            previous_level,
+            rate_limit_id,
+            calm_since: None,
+        })
+    }
+
+    /// 恢复开启防护前的安全级别，并删除自动创建的速率限制规则
+    async fn disable(
+        &self,
+        client: &CfClient,
+        config: &AppConfig,
+        zone_id: &str,
+        domain: &str,
+        mitigation: Mitigation,
+        format: &str,
+    ) -> Result<()> {
+        client.set_security_level(zone_id, &mitigation.previous_level).await?;
+        log(
+            format,
+            "✅",
+            &format!("流量已恢复正常，安全级别已恢复为 {}", mitigation.previous_level),
+        );
+        if let Some(id) = &mitigation.rate_limit_id {
+            match client.delete_rate_limit(zone_id, id).await {
+                Ok(()) => log(format, "✅", &format!("已删除速率限制规则 {}", id)),
+                Err(e) => output::warn(&format!("删除速率限制规则 {} 失败: {:#}", id, e)),
+            }
+        }
+
+        let title = format!("{} 已自动解除防护", domain);
+        let message = format!("流量已恢复正常，安全级别已恢复为 {}", mitigation.previous_level);
+        notify::send_quietly(config, &Notification::new(Level::Success, title, message)).await;
+        Ok(())
+    }
+}
+
+/// 带时间戳的事件日志 (JSON 格式时输出到 stderr，stdout 只保留流量数据)
+fn log(format: &str, icon: &str, message: &str) {
+    let time = chrono::Local::now().format("%H:%M:%S");
+    if format == "json" {
+        eprintln!("[{}] {}", time, message);
+    } else {
+        println!("{} {} {}", format!("[{}]", time).dimmed(), icon, message);
+    }
+}
+
+/// 一行流量摘要
+fn summary(live: &LiveAnalytics) -> String {
+    format!("{:.1} 次/秒, {} 次威胁", live.requests_per_second(), live.threats)
+}
@@ -0,0 +1,389 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::models::healthcheck::*;
+
+#[derive(Args, Debug)]
+pub struct HealthcheckArgs {
+    #[command(subcommand)]
+    pub command: HealthcheckCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HealthcheckCommands {
+    /// 列出健康检查及其当前状态
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 查看健康检查详情
+    Get {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 健康检查 ID
+        check_id: String,
+    },
+
+    /// 创建健康检查
+    Create {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 名称
+        #[arg(long)]
+        name: String,
+        /// 源站地址 (主机名或 IP)
+        #[arg(long)]
+        address: String,
+        /// 检查类型 (HTTP/HTTPS/TCP)
+        #[arg(short = 't', long = "type", default_value = "HTTP")]
+        check_type: String,
+        #[command(flatten)]
+        options: HealthcheckOptions,
+    },
+
+    /// 更新健康检查 (未指定的参数保持不变)
+    Update {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 健康检查 ID
+        check_id: String,
+        /// 名称
+        #[arg(long)]
+        name: Option<String>,
+        /// 源站地址 (主机名或 IP)
+        #[arg(long)]
+        address: Option<String>,
+        /// 检查类型 (HTTP/HTTPS/TCP)
+        #[arg(short = 't', long = "type")]
+        check_type: Option<String>,
+        #[command(flatten)]
+        options: HealthcheckOptions,
+        /// 暂停检查
+        #[arg(long, conflicts_with = "resume")]
+        suspend: bool,
+        /// 恢复检查
+        #[arg(long)]
+        resume: bool,
+    },
+
+    /// 删除健康检查
+    #[command(alias = "rm")]
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 健康检查 ID
+        check_id: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+/// 创建与更新共用的检查参数
+#[derive(Args, Debug)]
+pub struct HealthcheckOptions {
+    /// 请求路径 (HTTP/HTTPS，默认 /)
+    #[arg(long)]
+    path: Option<String>,
+    /// 端口 (默认 HTTP 80、HTTPS 443)
+    #[arg(long)]
+    port: Option<u16>,
+    /// 请求方法 (HTTP: GET/HEAD，TCP: connection_established)
+    #[arg(long)]
+    method: Option<String>,
+    /// 期望的状态码，逗号分隔 (如 200,2xx)
+    #[arg(long, value_delimiter = ',')]
+    expected_codes: Option<Vec<String>>,
+    /// 响应体需包含的内容
+    #[arg(long)]
+    expected_body: Option<String>,
+    /// 额外的请求头 (Name: Value，可重复)
+    #[arg(long = "header")]
+    headers: Vec<String>,
+    /// 跟随重定向
+    #[arg(long)]
+    follow_redirects: Option<bool>,
+    /// 不校验 HTTPS 证书
+    #[arg(long)]
+    allow_insecure: Option<bool>,
+    /// 检查间隔 (秒)
+    #[arg(long)]
+    interval: Option<u32>,
+    /// 单次检查超时 (秒)
+    #[arg(long)]
+    timeout: Option<u32>,
+    /// 失败后的重试次数
+    #[arg(long)]
+    retries: Option<u32>,
+    /// 连续失败多少次判定为不健康
+    #[arg(long)]
+    consecutive_fails: Option<u32>,
+    /// 连续成功多少次判定为健康
+    #[arg(long)]
+    consecutive_successes: Option<u32>,
+    /// 检查区域，逗号分隔 (如 WNAM,WEU,SEAS，或 ALL_REGIONS)
+    #[arg(long, value_delimiter = ',')]
+    regions: Option<Vec<String>>,
+    /// 描述
+    #[arg(long)]
+    description: Option<String>,
+}
+
+impl HealthcheckOptions {
+    /// 将指定的参数写入请求
+    fn apply(&self, request: &mut HealthcheckRequest) -> Result<()> {
+        if let Some(regions) = &self.regions {
+            let regions: Vec<String> = regions.iter().map(|r| r.trim().to_uppercase()).collect();
+            if let Some(unknown) = regions.iter().find(|r| !HEALTHCHECK_REGIONS.contains(&r.as_str())) {
+                anyhow::bail!(ValidationError(format!(
+                    "未知的检查区域: {} (可选: {})",
+                    unknown,
+                    HEALTHCHECK_REGIONS.join("/")
+                )));
+            }
+            request.check_regions = Some(regions);
+        }
+        request.description = self.description.clone().or(request.description.take());
+        request.interval = self.interval.or(request.interval);
+        request.timeout = self.timeout.or(request.timeout);
+        request.retries = self.retries.or(request.retries);
+        request.consecutive_fails = self.consecutive_fails.or(request.consecutive_fails);
+        request.consecutive_successes = self.consecutive_successes.or(request.consecutive_successes);
+
+        if let Some(tcp) = &mut request.tcp_config {
+            if self.path.is_some() || self.expected_codes.is_some() || self.expected_body.is_some() {
+                anyhow::bail!(ValidationError("TCP 检查不支持 --path / --expected-codes / --expected-body".into()));
+            }
+            tcp.port = self.port.or(tcp.port);
+            tcp.method = self.method.clone().or(tcp.method.take());
+            return Ok(());
+        }
+
+        let http = request.http_config.get_or_insert_with(HealthcheckHttpConfig::default);
+        http.path = self.path.clone().or(http.path.take());
+        http.port = self.port.or(http.port);
+        http.method = self.method.clone().or(http.method.take());
+        http.expected_codes = self.expected_codes.clone().or(http.expected_codes.take());
+        http.expected_body = self.expected_body.clone().or(http.expected_body.take());
+        http.follow_redirects = self.follow_redirects.or(http.follow_redirects);
+        http.allow_insecure = self.allow_insecure.or(http.allow_insecure);
+        if !self.headers.is_empty() {
+            let mut header = serde_json::Map::new();
+            for entry in &self.headers {
+                let (name, value) = entry.split_once(':').ok_or_else(|| {
+                    ValidationError(format!("请求头格式应为 Name: Value: {}", entry))
+                })?;
+                header.insert(name.trim().to_string(), serde_json::json!([value.trim()]));
+            }
+            http.header = Some(serde_json::Value::Object(header));
+        }
+        Ok(())
+    }
+}
+
+impl HealthcheckArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match &self.command {
+            HealthcheckCommands::List { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let checks = client.list_healthchecks(&zone_id).await?;
+
+                if format == "json" {
+                    output::print_json(&checks);
+                    return Ok(());
+                }
+
+                output::title(&format!("健康检查 - {} (共 {} 个)", domain, checks.len()));
+
+                if checks.is_empty() {
+                    output::info("没有健康检查，使用 `cfai healthcheck create` 创建");
+                    return Ok(());
+                }
+
+                let mut table =
+                    output::create_table(vec!["ID", "名称", "目标", "间隔", "状态", "失败原因"]);
+                for check in &checks {
+                    let id = check.id.as_deref().unwrap_or("-");
+                    let reason: String =
+                        check.failure_reason.as_deref().unwrap_or("").chars().take(40).collect();
+                    table.add_row(vec![
+                        &id[..8.min(id.len())],
+                        check.name.as_deref().unwrap_or("-"),
+                        &target(check),
+                        &check.interval.map(|i| format!("{}s", i)).unwrap_or("-".into()),
+                        &output::status_badge(check.status.as_deref().unwrap_or("unknown")),
+                        &reason,
+                    ]);
+                }
+                println!("{table}");
+            }
+
+            HealthcheckCommands::Get { domain, check_id } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let check = client.get_healthcheck(&zone_id, check_id).await?;
+
+                if format == "json" {
+                    output::print_json(&check);
+                    return Ok(());
+                }
+
+                output::title(&format!("健康检查 - {}", check.name.as_deref().unwrap_or("-")));
+                output::kv("ID", check.id.as_deref().unwrap_or("-"));
+                output::kv("状态", &output::status_badge(check.status.as_deref().unwrap_or("unknown")));
+                if let Some(reason) = check.failure_reason.as_deref().filter(|r| !r.is_empty()) {
+                    output::kv("最近失败原因", reason);
+                }
+                output::kv("目标", &target(&check));
+                if let Some(http) = &check.http_config {
+                    output::kv("请求方法", http.method.as_deref().unwrap_or("GET"));
+                    output::kv(
+                        "期望状态码",
+                        &http.expected_codes.as_ref().map(|c| c.join(", ")).unwrap_or("-".into()),
+                    );
+                    if let Some(body) = &http.expected_body {
+                        output::kv("期望响应内容", body);
+                    }
+                }
+                output::kv(
+                    "间隔 / 超时 / 重试",
+                    &format!(
+                        "{}s / {}s / {} 次",
+                        check.interval.unwrap_or(60),
+                        check.timeout.unwrap_or(5),
+                        check.retries.unwrap_or(2)
+                    ),
+                );
+                output::kv(
+                    "判定",
+                    &format!(
+                        "连续失败 {} 次为不健康，连续成功 {} 次为健康",
+                        check.consecutive_fails.unwrap_or(1),
+                        check.consecutive_successes.unwrap_or(1)
+                    ),
+                );
+                output::kv(
+                    "检查区域",
+                    &check.check_regions.as_ref().map(|r| r.join(", ")).unwrap_or("-".into()),
+                );
+                if check.suspended == Some(true) {
+                    output::kv("暂停", "是");
+                }
+                if let Some(description) = &check.description {
+                    output::kv("描述", description);
+                }
+                output::kv("修改时间", check.modified_on.as_deref().unwrap_or("-"));
+            }
+
+            HealthcheckCommands::Create {
+                domain,
+                name,
+                address,
+                check_type,
+                options,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let mut request = HealthcheckRequest::new(name, address, &parse_type(check_type)?);
+                options.apply(&mut request)?;
+
+                let check = client.create_healthcheck(&zone_id, &request).await?;
+                if format == "json" {
+                    output::print_json(&check);
+                    return Ok(());
+                }
+                output::success(&format!("健康检查已创建: {} ({})", name, target(&check)));
+                output::kv("ID", check.id.as_deref().unwrap_or("-"));
+            }
+
+            HealthcheckCommands::Update {
+                domain,
+                check_id,
+                name,
+                address,
+                check_type,
+                options,
+                suspend,
+                resume,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let current = client.get_healthcheck(&zone_id, check_id).await?;
+                let mut request = HealthcheckRequest::from_healthcheck(&current);
+
+                if let Some(name) = name {
+                    request.name = name.clone();
+                }
+                if let Some(address) = address {
+                    request.address = address.clone();
+                }
+                if let Some(check_type) = check_type {
+                    request.set_type(&parse_type(check_type)?);
+                }
+                options.apply(&mut request)?;
+                if *suspend {
+                    request.suspended = Some(true);
+                } else if *resume {
+                    request.suspended = Some(false);
+                }
+
+                let check = client.update_healthcheck(&zone_id, check_id, &request).await?;
+                if format == "json" {
+                    output::print_json(&check);
+                    return Ok(());
+                }
+                output::success(&format!("健康检查 {} 已更新", check_id));
+            }
+
+            HealthcheckCommands::Delete {
+                domain,
+                check_id,
+                yes,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+
+                if !yes {
+                    let confirm = prompt::confirm("确定要删除此健康检查吗？")?;
+                    if !confirm {
+                        output::info("已取消");
+                        return Ok(());
+                    }
+                }
+
+                client.delete_healthcheck(&zone_id, check_id).await?;
+                output::success("健康检查已删除");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 校验并规范化检查类型
+fn parse_type(value: &str) -> Result<String> {
+    let upper = value.to_uppercase();
+    if !matches!(upper.as_str(), "HTTP" | "HTTPS" | "TCP") {
+        anyhow::bail!(ValidationError(format!("检查类型必须是 HTTP、HTTPS 或 TCP: {}", value)));
+    }
+    Ok(upper)
+}
+
+/// 检查目标的简短描述 (如 HTTPS origin.example.com:443/health)
+fn target(check: &Healthcheck) -> String {
+    let check_type = check.check_type.as_deref().unwrap_or("HTTP");
+    let address = check.address.as_deref().unwrap_or("-");
+    if let Some(tcp) = &check.tcp_config {
+        return match tcp.port {
+            Some(port) => format!("{} {}:{}", check_type, address, port),
+            None => format!("{} {}", check_type, address),
+        };
+    }
+    let http = check.http_config.as_ref();
+    let port = http.and_then(|h| h.port).map(|p| format!(":{}", p)).unwrap_or_default();
+    let path = http.and_then(|h| h.path.as_deref()).unwrap_or("/");
+    format!("{} {}{}{}", check_type, address, port, path)
+}
@@ -1,9 +1,16 @@
-use anyhow::{anyhow, Result};
-use clap::Args;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
-use std::process::Command;
 
+use crate::api::client::CfClient;
+use crate::cli::commands::{zone, Cli, Commands};
+use crate::cli::exit::AuthError;
 use crate::cli::output;
+use crate::cli::prompt;
+use crate::config::settings::AppConfig;
+use crate::notify;
 
 #[derive(Args, Debug)]
 pub struct InteractiveArgs {
@@ -13,8 +20,9 @@ pub struct InteractiveArgs {
 }
 
 impl InteractiveArgs {
-    pub async fn execute(&self, format: &str, verbose: bool) -> Result<()> {
-        let theme = ColorfulTheme::default();
+    pub async fn execute(&self, format: &str, profile: Option<&str>, no_cache: bool) -> Result<()> {
+        prompt::require_interactive("请直接运行子命令，如 `cfai dns list example.com` (`cfai --help` 查看全部命令)")?;
+        let mut session = Session::load(format, profile, no_cache);
 
         loop {
             output::title_box("🚀 CFAI 交互式菜单");
@@ -37,48 +45,40 @@ impl InteractiveArgs {
                 "❌ 退出",
             ];
 
-            let selection = Select::with_theme(&theme)
+            let selection = Select::with_theme(&session.theme)
                 .with_prompt("请选择功能")
                 .items(&items)
                 .default(0)
                 .interact()?;
 
             let args = match selection {
-                0 => build_zone_args(&theme)?,
-                1 => build_dns_args(&theme)?,
-                2 => build_ssl_args(&theme)?,
-                3 => build_firewall_args(&theme)?,
-                4 => build_cache_args(&theme)?,
-                5 => build_page_rules_args(&theme)?,
-                6 => build_workers_args(&theme)?,
-                7 => build_analytics_args(&theme)?,
-                8 => build_ai_args(&theme)?,
-                9 => build_config_args(&theme)?,
+                0 => build_zone_args(&session)?,
+                1 => build_dns_args(&session)?,
+                2 => build_ssl_args(&session)?,
+                3 => build_firewall_args(&session)?,
+                4 => build_cache_args(&session)?,
+                5 => build_page_rules_args(&session)?,
+                6 => build_workers_args(&session)?,
+                7 => build_analytics_args(&session)?,
+                8 => build_ai_args(&session)?,
+                9 => build_config_args(&session.theme)?,
                 10 => Some(vec!["install".to_string()]),
                 11 => Some(vec!["update".to_string()]),
-                12 => build_custom_args(&theme)?,
+                12 => build_custom_args(&session.theme)?,
                 _ => {
                     output::success("感谢使用 CFAI！");
                     break;
                 }
             };
 
-            if let Some(mut args) = args {
-                if !format.is_empty() && format != "table" {
-                    args.push("--format".to_string());
-                    args.push(format.to_string());
-                }
-                if verbose {
-                    args.push("--verbose".to_string());
-                }
-
+            if let Some(args) = args {
                 println!();
                 output::separator();
-                match run_cfai(args) {
+                match session.run(&args).await {
                     Ok(_) => {}
                     Err(e) => {
                         if e.to_string() != "用户取消操作" {
-                            output::error(&format!("{}", e));
+                            output::error(&format!("{:#}", e));
                         }
                     }
                 }
@@ -90,7 +90,7 @@ impl InteractiveArgs {
                 break;
             }
 
-            let cont = Confirm::with_theme(&theme)
+            let cont = Confirm::with_theme(&session.theme)
                 .with_prompt("是否继续其它操作?")
                 .default(true)
                 .interact()?;
@@ -106,16 +106,138 @@ impl InteractiveArgs {
     }
 }
 
-fn run_cfai(args: Vec<String>) -> Result<()> {
-    let exe = std::env::current_exe().map_err(|e| anyhow!("获取可执行文件失败: {}", e))?;
-    let status = Command::new(exe).args(&args).status()?;
-    if !status.success() {
-        return Err(anyhow!("命令执行失败"));
+/// 交互会话: 所有菜单操作共用同一份配置与 Cloudflare 客户端，在当前进程内执行
+struct Session {
+    theme: ColorfulTheme,
+    format: String,
+    profile: Option<String>,
+    no_cache: bool,
+    /// 配置文件无法加载时为 None
+    config: Option<AppConfig>,
+    client: Option<CfClient>,
+    /// 本次会话已获取的域名列表
+    zones: OnceLock<Vec<String>>,
+}
+
+impl Session {
+    fn load(format: &str, profile: Option<&str>, no_cache: bool) -> Self {
+        let config = match AppConfig::load_effective(profile) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                output::warn(&format!("加载配置失败: {:#}", e));
+                None
+            }
+        };
+        let client = config.as_ref().and_then(|c| {
+            let client = crate::create_client(c).ok()?;
+            Some(
+                client
+                    .with_zone_cache(crate::zone_cache_key(c, profile, no_cache))
+                    .with_response_cache(crate::response_cache(c, profile, no_cache)),
+            )
+        });
+        Self {
+            theme: ColorfulTheme::default(),
+            format: format.to_string(),
+            profile: profile.map(str::to_string),
+            no_cache,
+            config,
+            client,
+            zones: OnceLock::new(),
+        }
+    }
+
+    /// 配置可能已被修改，重新加载配置与客户端
+    fn reload(&mut self) {
+        *self = Self::load(&self.format, self.profile.as_deref(), self.no_cache);
+    }
+
+    /// 已配置认证时返回配置与客户端
+    fn authenticated(&self) -> Result<(&AppConfig, &CfClient)> {
+        let config = self
+            .config
+            .as_ref()
+            .filter(|c| c.validate().is_ok())
+            .ok_or_else(|| AuthError("未配置 Cloudflare 认证信息，请运行 `cfai config setup`".into()))?;
+        let client = self.client.as_ref().context("创建 Cloudflare 客户端失败")?;
+        Ok((config, client))
+    }
+
+    /// 解析菜单生成的参数并在进程内执行对应命令
+    async fn run(&mut self, args: &[String]) -> Result<()> {
+        let cli = match parse_args(args) {
+            Ok(cli) => cli,
+            Err(e) => {
+                // 参数错误与 --help 均由 clap 输出
+                e.print()?;
+                return Ok(());
+            }
+        };
+        let Some(command) = cli.command else {
+            return Ok(());
+        };
+        if cli.profile.is_some() && cli.profile != self.profile {
+            anyhow::bail!("交互模式中无法切换 Profile，请使用 `cfai --profile <名称>` 重新进入");
+        }
+        // 未显式指定 --format 时沿用进入交互模式时的格式
+        let format = if cli.format != "table" { &cli.format } else { &self.format };
+
+        match &command {
+            Commands::Interactive(_) => {
+                output::info("已处于交互模式");
+                return Ok(());
+            }
+            #[cfg(feature = "gui")]
+            Commands::Gui => return crate::gui::launch_gui(self.profile.as_deref()),
+            cmd if cmd.is_local() => {
+                let result = cmd.execute_local(format, self.profile.as_deref()).await;
+                if matches!(cmd, Commands::Config(_)) {
+                    self.reload();
+                }
+                return result;
+            }
+            _ => {}
+        }
+
+        let (config, client) = if command.needs_auth() {
+            self.authenticated()?
+        } else {
+            let config = self.config.as_ref().context("加载配置失败，请运行 `cfai config setup`")?;
+            let client = self.client.as_ref().context("创建 Cloudflare 客户端失败")?;
+            (config, client)
+        };
+        // 每次操作使用独立的客户端副本，变更请求记录只属于本次命令
+        let client = client.clone().with_fresh_writes();
+        let result = command.execute(&client, config, format).await;
+        notify::command_finished(config, &client, &args.join(" "), &result, cli.notify).await;
+        // 添加或删除域名后重新获取域名列表
+        if matches!(command, Commands::Zone(_)) {
+            self.zones.take();
+        }
+        result
+    }
+
+    /// 账户下的域名名称，每次会话只获取一次 (另有本地缓存)
+    fn zone_names(&self) -> Result<Vec<String>> {
+        if let Some(names) = self.zones.get() {
+            return Ok(names.clone());
+        }
+        let (_, client) = self.authenticated()?;
+        // 菜单的提示函数是同步的，在当前多线程运行时上阻塞等待请求完成
+        let names = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(zone::zone_names(client))
+        })?;
+        Ok(self.zones.get_or_init(|| names).clone())
     }
-    Ok(())
 }
 
-fn build_zone_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+/// 将菜单生成的参数 (不含程序名) 解析为命令行
+fn parse_args(args: &[String]) -> Result<Cli, clap::Error> {
+    Cli::try_parse_from(std::iter::once("cfai").chain(args.iter().map(String::as_str)))
+}
+
+fn build_zone_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(1, "域名管理");
 
     let items = vec![
@@ -136,30 +258,31 @@ fn build_zone_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
     match selection {
         0 => Ok(Some(vec!["zone".into(), "list".into()])),
         1 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             Ok(Some(vec!["zone".into(), "get".into(), domain]))
         }
         2 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             Ok(Some(vec!["zone".into(), "add".into(), domain]))
         }
         3 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             Ok(Some(vec!["zone".into(), "pause".into(), domain]))
         }
         4 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             Ok(Some(vec!["zone".into(), "resume".into(), domain]))
         }
         5 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             Ok(Some(vec!["zone".into(), "settings".into(), domain]))
         }
         _ => Ok(None),
     }
 }
 
-fn build_dns_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_dns_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(2, "DNS 管理");
 
     let items = vec![
@@ -181,7 +304,7 @@ fn build_dns_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
 
     match selection {
         0 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             let record_type: String = Input::with_theme(theme)
                 .with_prompt("记录类型 (可选, 如 A/AAAA/CNAME，留空显示全部)")
                 .allow_empty(true)
@@ -196,14 +319,14 @@ fn build_dns_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         1 => Ok(Some(vec![
             "dns".into(),
             "add-a".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "主机名 (如 www, 或 @ 表示根域名)")?,
             prompt_text(theme, "IPv4 地址")?,
         ])),
         2 => Ok(Some(vec![
             "dns".into(),
             "add".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "-t".into(),
             "AAAA".into(),
             "-n".into(),
@@ -214,14 +337,14 @@ fn build_dns_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         3 => Ok(Some(vec![
             "dns".into(),
             "add-cname".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "主机名 (如 blog)")?,
             prompt_text(theme, "目标域名")?,
         ])),
         4 => Ok(Some(vec![
             "dns".into(),
             "add".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "-t".into(),
             "MX".into(),
             "-n".into(),
@@ -232,7 +355,7 @@ fn build_dns_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         5 => Ok(Some(vec![
             "dns".into(),
             "add".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "-t".into(),
             "TXT".into(),
             "-n".into(),
@@ -243,20 +366,21 @@ fn build_dns_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         6 => Ok(Some(vec![
             "dns".into(),
             "delete".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "记录 ID")?,
         ])),
         7 => Ok(Some(vec![
             "dns".into(),
             "find".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "搜索关键词")?,
         ])),
         _ => Ok(None),
     }
 }
 
-fn build_ssl_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_ssl_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(3, "SSL/TLS 管理");
 
     let items = vec![
@@ -277,10 +401,10 @@ fn build_ssl_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         0 => Ok(Some(vec![
             "ssl".into(),
             "status".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         1 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             let modes = vec!["off (关闭)", "flexible (灵活)", "full (完全)", "strict (严格)"];
             let mode_sel = Select::with_theme(theme)
                 .with_prompt("选择 SSL 模式")
@@ -298,25 +422,26 @@ fn build_ssl_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         2 => Ok(Some(vec![
             "ssl".into(),
             "https".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "on".into(),
         ])),
         3 => Ok(Some(vec![
             "ssl".into(),
             "https".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "off".into(),
         ])),
         4 => Ok(Some(vec![
             "ssl".into(),
             "list".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         _ => Ok(None),
     }
 }
 
-fn build_firewall_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_firewall_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(4, "防火墙管理");
 
     let items = vec![
@@ -339,52 +464,54 @@ fn build_firewall_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         0 => Ok(Some(vec![
             "firewall".into(),
             "status".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         1 => Ok(Some(vec![
             "firewall".into(),
             "list".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         2 => Ok(Some(vec![
             "firewall".into(),
             "block".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "IP 地址")?,
         ])),
         3 => Ok(Some(vec![
             "firewall".into(),
             "whitelist".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "IP 地址")?,
         ])),
         4 => Ok(Some(vec![
             "firewall".into(),
             "unblock".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "规则 ID")?,
         ])),
         5 => Ok(Some(vec![
             "firewall".into(),
             "ua-on".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         6 => Ok(Some(vec![
             "firewall".into(),
             "ua-off".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         _ => Ok(None),
     }
 }
 
-fn build_cache_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_cache_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(5, "缓存管理");
 
     let items = vec![
         "🔍 查看缓存状态",
         "🗑️  清除全部缓存",
         "🎯 按 URL 清除缓存",
+        "📁 按前缀清除缓存",
         "⚙️  设置缓存级别",
         "⏰ 设置浏览器缓存 TTL",
         "🔧 开启开发模式",
@@ -400,10 +527,10 @@ fn build_cache_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         0 => Ok(Some(vec![
             "cache".into(),
             "status".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         1 => {
-            let domain = prompt_domain(theme)?;
+            let domain = prompt_domain(session)?;
             let confirm = Confirm::with_theme(theme)
                 .with_prompt("确认清除全部缓存？这将影响所有访问者")
                 .default(false)
@@ -418,11 +545,17 @@ fn build_cache_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         2 => Ok(Some(vec![
             "cache".into(),
             "purge-url".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "URL 地址")?,
         ])),
-        3 => {
-            let domain = prompt_domain(theme)?;
+        3 => Ok(Some(vec![
+            "cache".into(),
+            "purge-prefix".into(),
+            prompt_domain(session)?,
+            prompt_text(theme, "URL 前缀 (如 www.example.com/images)")?,
+        ])),
+        4 => {
+            let domain = prompt_domain(session)?;
             let levels = vec!["basic (基础)", "simplified (简化)", "aggressive (激进)"];
             let level_sel = Select::with_theme(theme)
                 .with_prompt("选择缓存级别")
@@ -436,23 +569,24 @@ fn build_cache_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
             };
             Ok(Some(vec!["cache".into(), "level".into(), domain, level.into()]))
         }
-        4 => Ok(Some(vec![
+        5 => Ok(Some(vec![
             "cache".into(),
             "browser-ttl".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "TTL 秒数")?,
         ])),
-        5 => Ok(Some(vec![
+        6 => Ok(Some(vec![
             "cache".into(),
             "dev-mode".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "on".into(),
         ])),
         _ => Ok(None),
     }
 }
 
-fn build_page_rules_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_page_rules_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(6, "页面规则");
 
     let items = vec![
@@ -471,25 +605,26 @@ fn build_page_rules_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         0 => Ok(Some(vec![
             "page-rules".into(),
             "list".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         1 => Ok(Some(vec![
             "page-rules".into(),
             "get".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "规则 ID")?,
         ])),
         2 => Ok(Some(vec![
             "page-rules".into(),
             "delete".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             prompt_text(theme, "规则 ID")?,
         ])),
         _ => Ok(None),
     }
 }
 
-fn build_workers_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_workers_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(7, "Workers 管理");
 
     let items = vec![
@@ -515,14 +650,15 @@ fn build_workers_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         2 => Ok(Some(vec![
             "workers".into(),
             "routes".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         3 => Ok(Some(vec!["workers".into(), "kv".into()])),
         _ => Ok(None),
     }
 }
 
-fn build_analytics_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_analytics_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(8, "流量分析");
 
     let items = vec![
@@ -540,18 +676,19 @@ fn build_analytics_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         0 => Ok(Some(vec![
             "analytics".into(),
             "overview".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         1 => Ok(Some(vec![
             "analytics".into(),
             "detail".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         _ => Ok(None),
     }
 }
 
-fn build_ai_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
+fn build_ai_args(session: &Session) -> Result<Option<Vec<String>>> {
+    let theme = &session.theme;
     output::step(9, "AI 智能助手 🤖");
 
     let items = vec![
@@ -561,6 +698,7 @@ fn build_ai_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         "⚡ AI 性能分析",
         "📡 AI DNS 分析",
         "🔧 AI 故障诊断",
+        "🗨️  AI 多轮对话",
         "⬅️  返回上级菜单",
     ];
     let selection = Select::with_theme(theme)
@@ -574,26 +712,26 @@ fn build_ai_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
         1 => Ok(Some(vec![
             "ai".into(),
             "analyze".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
         2 => Ok(Some(vec![
             "ai".into(),
             "analyze".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "-t".into(),
             "security".into(),
         ])),
         3 => Ok(Some(vec![
             "ai".into(),
             "analyze".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "-t".into(),
             "performance".into(),
         ])),
         4 => Ok(Some(vec![
             "ai".into(),
             "analyze".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
             "-t".into(),
             "dns".into(),
         ])),
@@ -602,8 +740,9 @@ fn build_ai_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
             "troubleshoot".into(),
             prompt_text(theme, "问题描述")?,
             "-d".into(),
-            prompt_domain(theme)?,
+            prompt_domain(session)?,
         ])),
+        6 => Ok(Some(vec!["ai".into(), "chat".into()])),
         _ => Ok(None),
     }
 }
@@ -655,79 +794,36 @@ fn build_custom_args(theme: &ColorfulTheme) -> Result<Option<Vec<String>>> {
     Ok(Some(args))
 }
 
-fn prompt_domain(theme: &ColorfulTheme) -> Result<String> {
-    let items = vec![
-        "📋 从域名列表中选择",
-        "✍️  手动输入域名",
-        "⬅️  返回上级菜单",
-    ];
-    let selection = Select::with_theme(theme)
-        .with_prompt("选择域名输入方式")
-        .items(&items)
-        .default(0)
-        .interact()?;
-
-    match selection {
-        0 => {
-            // 从域名列表选择
-            output::loading("正在获取域名列表...");
-            let exe = std::env::current_exe().map_err(|e| anyhow!("获取可执行文件失败: {}", e))?;
-            let output = Command::new(exe)
-                .args(&["zone", "list", "--format", "json"])
-                .output()?;
-
-            if !output.status.success() {
-                output::warn("获取域名列表失败，请手动输入");
-                return prompt_text(theme, "域名 (如: example.com)");
-            }
-
-            let stdout = String::from_utf8_lossy(&output.stdout);
-
-            // 解析 JSON 获取域名列表
-            let domains: Vec<String> = match serde_json::from_str::<serde_json::Value>(&stdout) {
-                Ok(json) => {
-                    if let Some(arr) = json.as_array() {
-                        arr.iter()
-                            .filter_map(|v| v.get("name").and_then(|n| n.as_str()).map(|s| s.to_string()))
-                            .collect()
-                    } else {
-                        vec![]
-                    }
-                }
-                Err(_) => vec![],
-            };
+fn prompt_domain(session: &Session) -> Result<String> {
+    let theme = &session.theme;
+    let domains = session.zone_names().unwrap_or_else(|e| {
+        output::warn(&format!("获取域名列表失败: {:#}，请手动输入", e));
+        Vec::new()
+    });
+    zone::pick_domain(theme, &domains)?.ok_or_else(|| anyhow!("用户取消操作"))
+}
 
-            if domains.is_empty() {
-                output::warn("未找到域名，请手动输入");
-                return prompt_text(theme, "域名 (如: example.com)");
-            }
+fn prompt_text(theme: &ColorfulTheme, prompt: &str) -> Result<String> {
+    Ok(Input::with_theme(theme).with_prompt(prompt).interact_text()?)
+}
 
-            let mut domain_items: Vec<&str> = domains.iter().map(|s| s.as_str()).collect();
-            domain_items.push("⬅️  返回");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            let domain_sel = Select::with_theme(theme)
-                .with_prompt("选择域名")
-                .items(&domain_items)
-                .default(0)
-                .interact()?;
+    #[test]
+    fn menu_args_parse_in_process() {
+        let args: Vec<String> = ["dns", "list", "example.com", "--format", "json"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let cli = parse_args(&args).unwrap();
+        assert_eq!(cli.format, "json");
+        assert!(matches!(cli.command, Some(Commands::Dns(_))));
 
-            if domain_sel == domain_items.len() - 1 {
-                return Err(anyhow!("用户取消操作"));
-            }
+        let install = parse_args(&["install".to_string()]).unwrap();
+        assert!(install.command.is_some_and(|c| c.is_local()));
 
-            Ok(domains[domain_sel].clone())
-        }
-        1 => {
-            // 手动输入
-            prompt_text(theme, "域名 (如: example.com)")
-        }
-        _ => {
-            // 返回上级菜单
-            Err(anyhow!("用户取消操作"))
-        }
+        assert!(parse_args(&["no-such-command".to_string()]).is_err());
     }
 }
-
-fn prompt_text(theme: &ColorfulTheme, prompt: &str) -> Result<String> {
-    Ok(Input::with_theme(theme).with_prompt(prompt).interact_text()?)
-}
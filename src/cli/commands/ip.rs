@@ -0,0 +1,239 @@
+use std::net::{IpAddr, SocketAddr};
+
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::config::ip_ranges::{self, IP_RANGES_CACHE_TTL_SECS};
+use crate::models::firewall::{cidr_contains, CloudflareIps, IpAccessRule};
+use crate::resolver;
+
+use super::zone::resolve_zone_id;
+
+/// 查询 IP: 是否属于 Cloudflare、反向解析、ASN，以及域名 IP 访问规则中的相关规则
+#[derive(Args, Debug)]
+pub struct IpArgs {
+    /// IP 地址
+    pub address: String,
+    /// 同时检查该域名的 IP 访问规则 (域名或 Zone ID)
+    #[arg(short, long)]
+    pub zone: Option<String>,
+    /// 重新获取 Cloudflare IP 段 (忽略缓存)
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+/// 查询结果
+#[derive(Debug, Serialize)]
+struct IpInfo {
+    ip: String,
+    /// 所属的 Cloudflare 网段
+    cloudflare_range: Option<String>,
+    ptr: Vec<String>,
+    asn: Option<AsnInfo>,
+    /// 命中的 IP 访问规则 (指定 --zone 时)
+    rules: Option<Vec<IpAccessRule>>,
+}
+
+/// ASN 信息 (来自 Team Cymru 的 DNS 查询服务)
+#[derive(Debug, Serialize, PartialEq)]
+struct AsnInfo {
+    asn: u32,
+    prefix: String,
+    country: String,
+    registry: String,
+    name: Option<String>,
+}
+
+impl IpArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        let ip: IpAddr = self
+            .address
+            .trim()
+            .parse()
+            .map_err(|_| ValidationError(format!("无效的 IP 地址: {}", self.address)))?;
+
+        let ranges = cloudflare_ips(client, self.refresh).await?;
+        let server = SocketAddr::new(resolver::system_resolver().unwrap_or(IpAddr::from([1, 1, 1, 1])), 53);
+        let (ptr, asn) = tokio::join!(lookup_ptr(server, ip), lookup_asn(server, ip));
+
+        let rules = match &self.zone {
+            Some(zone) => {
+                let zone_id = resolve_zone_id(client, zone).await?;
+                let rules = client.list_ip_access_rules(&zone_id).await?;
+                Some(rules.into_iter().filter(|r| rule_matches(r, ip, asn.as_ref())).collect())
+            }
+            None => None,
+        };
+
+        let info = IpInfo {
+            ip: ip.to_string(),
+            cloudflare_range: ranges.find(ip).map(str::to_string),
+            ptr,
+            asn,
+            rules,
+        };
+        if format == "json" {
+            output::print_json(&info);
+        } else {
+            print_info(&info, self.zone.as_deref());
+        }
+        Ok(())
+    }
+}
+
+/// 读取 Cloudflare IP 段: 优先使用未过期的缓存，获取失败时退回旧缓存
+async fn cloudflare_ips(client: &CfClient, refresh: bool) -> Result<CloudflareIps> {
+    let cached = ip_ranges::load();
+    if let Some((ranges, age)) = &cached {
+        if !refresh && (0..IP_RANGES_CACHE_TTL_SECS).contains(age) {
+            return Ok(ranges.clone());
+        }
+    }
+    match client.get_cloudflare_ips().await {
+        Ok(ranges) => {
+            if let Err(e) = ip_ranges::store(&ranges) {
+                output::warn(&format!("{:#}", e));
+            }
+            Ok(ranges)
+        }
+        Err(e) => match cached {
+            Some((ranges, _)) => {
+                output::warn(&format!("获取 Cloudflare IP 段失败，使用缓存: {:#}", e));
+                Ok(ranges)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// 反向解析使用的标签 (IPv4 按字节倒序，IPv6 按半字节倒序)
+fn reverse_labels(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => v4.octets().iter().rev().map(u8::to_string).collect::<Vec<_>>().join("."),
+        IpAddr::V6(v6) => format!("{:032x}", u128::from(v6))
+            .chars()
+            .rev()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join("."),
+    }
+}
+
+async fn lookup_ptr(server: SocketAddr, ip: IpAddr) -> Vec<String> {
+    let suffix = if ip.is_ipv4() { "in-addr.arpa." } else { "ip6.arpa." };
+    let name = format!("{}.{}", reverse_labels(ip), suffix);
+    match resolver::query(server, &name, "PTR", resolver::QUERY_TIMEOUT).await {
+        Ok(response) => response.values("PTR"),
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn lookup_asn(server: SocketAddr, ip: IpAddr) -> Option<AsnInfo> {
+    let zone = if ip.is_ipv4() { "origin.asn.cymru.com." } else { "origin6.asn.cymru.com." };
+    let name = format!("{}.{}", reverse_labels(ip), zone);
+    let response = resolver::query(server, &name, "TXT", resolver::QUERY_TIMEOUT).await.ok()?;
+    let mut info = response.values("TXT").iter().find_map(|txt| parse_origin(txt))?;
+
+    let name = format!("AS{}.asn.cymru.com.", info.asn);
+    if let Ok(response) = resolver::query(server, &name, "TXT", resolver::QUERY_TIMEOUT).await {
+        info.name = response
+            .values("TXT")
+            .first()
+            .and_then(|txt| txt.rsplit('|').next())
+            .map(|name| name.trim().to_string());
+    }
+    Some(info)
+}
+
+/// 解析 `13335 | 1.1.1.0/24 | AU | apnic | 2011-08-11` (多个 ASN 时取第一个)
+fn parse_origin(txt: &str) -> Option<AsnInfo> {
+    let fields: Vec<&str> = txt.split('|').map(str::trim).collect();
+    let [asn, prefix, country, registry, ..] = fields.as_slice() else {
+        return None;
+    };
+    Some(AsnInfo {
+        asn: asn.split_whitespace().next()?.parse().ok()?,
+        prefix: prefix.to_string(),
+        country: country.to_string(),
+        registry: registry.to_string(),
+        name: None,
+    })
+}
+
+/// IP 访问规则是否作用于该 IP (按 IP、网段、ASN 或国家匹配)
+fn rule_matches(rule: &IpAccessRule, ip: IpAddr, asn: Option<&AsnInfo>) -> bool {
+    let Some(config) = &rule.configuration else {
+        return false;
+    };
+    let value = config.value.as_deref().unwrap_or_default();
+    match config.target.as_deref() {
+        Some("ip" | "ip6" | "ip_range") => cidr_contains(value, ip),
+        Some("asn") => asn.is_some_and(|a| value.trim_start_matches("AS").parse() == Ok(a.asn)),
+        Some("country") => asn.is_some_and(|a| a.country.eq_ignore_ascii_case(value)),
+        _ => false,
+    }
+}
+
+fn print_info(info: &IpInfo, zone: Option<&str>) {
+    output::title(&format!("IP 信息 - {}", info.ip));
+    match &info.cloudflare_range {
+        Some(range) => output::kv_colored("Cloudflare", &format!("是 ({})", range), true),
+        None => output::kv("Cloudflare", "否"),
+    }
+    output::kv("反向解析", &if info.ptr.is_empty() { "-".to_string() } else { info.ptr.join(", ") });
+    match &info.asn {
+        Some(asn) => {
+            output::kv("ASN", &format!("AS{} {}", asn.asn, asn.name.as_deref().unwrap_or_default()));
+            output::kv("网段", &asn.prefix);
+            output::kv("国家/地区", &asn.country);
+            output::kv("注册机构", &asn.registry);
+        }
+        None => output::kv("ASN", "-"),
+    }
+
+    let (Some(rules), Some(zone)) = (&info.rules, zone) else {
+        return;
+    };
+    println!("\n{}", format!("{} 的 IP 访问规则", zone).bold());
+    if rules.is_empty() {
+        output::info("没有作用于该 IP 的规则");
+        return;
+    }
+    for rule in rules {
+        let (target, value) = rule
+            .configuration
+            .as_ref()
+            .map(|c| (c.target.as_deref().unwrap_or("-"), c.value.as_deref().unwrap_or("-")))
+            .unwrap_or(("-", "-"));
+        println!(
+            "  {} {}={} {}",
+            rule.mode.as_deref().unwrap_or("-").yellow(),
+            target,
+            value,
+            rule.notes.as_deref().unwrap_or_default().dimmed()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asn_lookup_helpers() {
+        assert_eq!(reverse_labels("192.0.2.10".parse().unwrap()), "10.2.0.192");
+        assert!(reverse_labels("2001:db8::1".parse().unwrap()).starts_with("1.0.0.0."));
+        assert!(reverse_labels("2001:db8::1".parse().unwrap()).ends_with("8.b.d.0.1.0.0.2"));
+
+        let info = parse_origin("13335 209242 | 1.1.1.0/24 | AU | apnic | 2011-08-11").unwrap();
+        assert_eq!(info.asn, 13335);
+        assert_eq!(info.prefix, "1.1.1.0/24");
+        assert_eq!(info.country, "AU");
+        assert_eq!(parse_origin("garbage"), None);
+    }
+}
@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use futures_util::StreamExt;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::api::logs::connect_instant_logs;
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::output;
+use crate::models::logs::{LogFilter, StatusFilter, DEFAULT_LOG_FIELDS};
+
+#[derive(Args, Debug)]
+pub struct LogsArgs {
+    #[command(subcommand)]
+    pub command: LogsCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LogsCommands {
+    /// 实时查看 HTTP 请求日志 (Instant Logs，Ctrl+C 退出)
+    Tail {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 输出字段，逗号分隔 (默认: 时间、状态码、方法、主机、路径、IP、国家、缓存状态、Ray ID)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+        /// 按状态码过滤 (如 500、>=500、5xx)
+        #[arg(long)]
+        status: Option<StatusFilter>,
+        /// 只显示指定路径前缀的请求
+        #[arg(long)]
+        path: Option<String>,
+        /// 只显示指定主机名的请求
+        #[arg(long)]
+        host: Option<String>,
+        /// 采样率 (1 为全部请求，10 为十分之一)
+        #[arg(long, default_value_t = 1)]
+        sample: u32,
+    },
+}
+
+impl LogsArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match &self.command {
+            LogsCommands::Tail {
+                domain,
+                fields,
+                status,
+                path,
+                host,
+                sample,
+            } => {
+                if *sample == 0 {
+                    anyhow::bail!(ValidationError("采样率必须大于 0".into()));
+                }
+                let filter = LogFilter {
+                    status: *status,
+                    path_prefix: path.clone(),
+                    host: host.clone(),
+                };
+                let mut fields: Vec<String> = if fields.is_empty() {
+                    DEFAULT_LOG_FIELDS.iter().map(|f| f.to_string()).collect()
+                } else {
+                    fields.iter().map(|f| f.trim().to_string()).filter(|f| !f.is_empty()).collect()
+                };
+                // 只用于过滤的字段不输出
+                let display = fields.clone();
+                for field in filter.required_fields() {
+                    if !fields.iter().any(|f| f == field) {
+                        fields.push(field.to_string());
+                    }
+                }
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                tail_logs(client, &zone_id, domain, &fields, &display, *sample, &filter, format).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 持续接收日志，会话结束或连接断开时自动创建新会话
+#[allow(clippy::too_many_arguments)]
+async fn tail_logs(
+    client: &CfClient,
+    zone_id: &str,
+    domain: &str,
+    fields: &[String],
+    display: &[String],
+    sample: u32,
+    filter: &LogFilter,
+    format: &str,
+) -> Result<()> {
+    let mut first = true;
+
+    loop {
+        let job = client.create_instant_logs_job(zone_id, fields, sample).await?;
+        let mut stream = connect_instant_logs(&job).await?;
+        if first {
+            output::info(&format!("正在实时查看 {} 的请求日志，按 Ctrl+C 退出", domain));
+            if format != "json" {
+                println!("{}", display.join("  ").dimmed());
+            }
+            first = false;
+        } else {
+            output::info("Instant Logs 会话已重新连接");
+        }
+
+        let stop = loop {
+            tokio::select! {
+                message = stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => print_log_batch(text.as_bytes(), display, filter, format),
+                    Some(Ok(Message::Binary(data))) => print_log_batch(&data, display, filter, format),
+                    Some(Ok(Message::Close(_))) | None => break false,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        output::warn(&format!("Instant Logs 连接中断: {}", e));
+                        break false;
+                    }
+                },
+                _ = tokio::signal::ctrl_c() => break true,
+            }
+        };
+
+        let _ = stream.close(None).await;
+        if stop {
+            return Ok(());
+        }
+        output::info("Instant Logs 会话已结束，正在重新连接...");
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// 一条消息可能包含多行日志 (每行一个 JSON 对象)
+fn print_log_batch(data: &[u8], display: &[String], filter: &LogFilter, format: &str) {
+    for line in String::from_utf8_lossy(data).lines().filter(|l| !l.trim().is_empty()) {
+        let record: serde_json::Value = match serde_json::from_str(line) {
+            Ok(record) => record,
+            Err(e) => {
+                output::warn(&format!("无法解析日志: {}", e));
+                continue;
+            }
+        };
+        if !filter.matches(&record) {
+            continue;
+        }
+
+        if format == "json" {
+            let selected: serde_json::Map<String, serde_json::Value> = display
+                .iter()
+                .map(|f| (f.clone(), record[f.as_str()].clone()))
+                .collect();
+            println!("{}", serde_json::Value::Object(selected));
+            continue;
+        }
+
+        let columns: Vec<String> = display.iter().map(|f| format_field(f, &record[f.as_str()])).collect();
+        println!("{}", columns.join("  "));
+    }
+}
+
+fn format_field(field: &str, value: &serde_json::Value) -> String {
+    match (field, value) {
+        (_, serde_json::Value::Null) => "-".dimmed().to_string(),
+        ("EdgeResponseStatus" | "OriginResponseStatus", v) => {
+            let code = v.as_u64().unwrap_or(0);
+            match code {
+                500.. => code.to_string().red().bold().to_string(),
+                400..=499 => code.to_string().yellow().to_string(),
+                _ => code.to_string().green().to_string(),
+            }
+        }
+        ("EdgeStartTimestamp" | "EdgeEndTimestamp", v) => timestamp(v)
+            .map(|t| t.dimmed().to_string())
+            .unwrap_or_else(|| v.to_string()),
+        ("ClientRequestMethod", serde_json::Value::String(s)) => s.bold().to_string(),
+        (_, serde_json::Value::String(s)) => s.clone(),
+        (_, v) => v.to_string(),
+    }
+}
+
+/// 时间戳可能是 RFC3339 字符串或 Unix 纳秒，统一显示为本地时间
+fn timestamp(value: &serde_json::Value) -> Option<String> {
+    let time = match value {
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()?
+            .with_timezone(&chrono::Local),
+        serde_json::Value::Number(n) => {
+            chrono::DateTime::from_timestamp_nanos(n.as_i64()?).with_timezone(&chrono::Local)
+        }
+        _ => return None,
+    };
+    Some(time.format("%H:%M:%S").to_string())
+}
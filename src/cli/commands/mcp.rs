@@ -0,0 +1,275 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::ai::analyzer::SuggestedAction;
+use crate::ai::executor::{self, ActionOutcome, AutoApprove};
+use crate::ai::safety::{self, LiveZoneState};
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+
+use super::ai::collect_zone_context;
+use super::zone::resolve_zone_id;
+
+/// 支持的 MCP 协议版本 (新的在前)
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Model Context Protocol 服务 (stdio)，供 Claude Desktop 等 MCP 客户端调用
+#[derive(Args, Debug)]
+pub struct McpArgs {
+    /// 不超过此风险等级的操作直接执行，更高风险的操作需要调用方传入 confirm: true
+    #[arg(long, default_value = "low", value_parser = ["low", "medium", "high"])]
+    pub auto_approve: String,
+    /// 只提供查询工具，不允许修改配置
+    #[arg(long)]
+    pub read_only: bool,
+}
+
+impl McpArgs {
+    /// 逐行读取 JSON-RPC 消息并应答，stdin 关闭时退出
+    ///
+    /// stdout 只用于协议消息，其余输出一律写到 stderr。
+    pub async fn execute(&self, client: &CfClient) -> Result<()> {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+        while let Some(line) = lines.next_line().await.context("读取 MCP 消息失败")? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some(response) = self.handle_message(client, &line).await else {
+                continue;
+            };
+            let mut message = response.to_string();
+            message.push('\n');
+            stdout.write_all(message.as_bytes()).await.context("写入 MCP 响应失败")?;
+            stdout.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// 处理一条消息，通知 (无 id) 不需要应答
+    async fn handle_message(&self, client: &CfClient, line: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(e) => return Some(rpc_error(Value::Null, -32700, &format!("无法解析 JSON: {}", e))),
+        };
+        let id = message.get("id").cloned()?;
+        let params = &message["params"];
+
+        let result = match message["method"].as_str().unwrap_or_default() {
+            "initialize" => initialize(params),
+            "ping" => json!({}),
+            "tools/list" => json!({ "tools": self.tools() }),
+            "tools/call" => self.call_tool(client, params).await,
+            method => return Some(rpc_error(id, -32601, &format!("不支持的方法: {}", method))),
+        };
+        Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+    }
+
+    fn tools(&self) -> Vec<Value> {
+        let zone = json!({ "type": "string", "description": "域名或 Zone ID" });
+        let mut tools = vec![
+            json!({
+                "name": "list_zones",
+                "description": "列出账户下的全部域名 (Zone)",
+                "inputSchema": { "type": "object", "properties": {} },
+            }),
+            json!({
+                "name": "list_dns_records",
+                "description": "列出域名的 DNS 记录，可按类型或名称过滤",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "zone": zone,
+                        "type": { "type": "string", "description": "记录类型，如 A、CNAME、TXT" },
+                        "name": { "type": "string", "description": "记录名称包含的文本" },
+                    },
+                    "required": ["zone"],
+                },
+            }),
+            json!({
+                "name": "get_zone_config",
+                "description": "获取域名的 DNS、SSL/TLS、安全级别与缓存等配置摘要",
+                "inputSchema": { "type": "object", "properties": { "zone": zone }, "required": ["zone"] },
+            }),
+        ];
+        if !self.read_only {
+            tools.push(json!({
+                "name": "execute_action",
+                "description": format!(
+                    "修改域名配置。操作先经过安全审查: 会导致故障的操作被阻止，风险等级可能被调高；\
+                     审查后风险高于 {} 的操作需要在用户明确同意后以 confirm: true 重新调用。\
+                     params 与 cfai AI 助手的操作格式相同: dns_create/dns_update 需要 type、name、content \
+                     (可选 ttl、proxied、priority、comment，update 另需 record_id)，dns_delete 需要 record_id，\
+                     cache_purge 需要 type (purge_all/purge_urls/purge_tags/purge_hosts) 与对应列表，\
+                     firewall_rule 需要 type (block_ip/whitelist_ip/security_level/under_attack/browser_check)，\
+                     ssl_set 需要 setting 与 value/enable，setting_update 需要 setting_id 与 value",
+                    self.auto_approve
+                ),
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "zone": zone,
+                        "type": {
+                            "type": "string",
+                            "enum": ["dns_create", "dns_update", "dns_delete", "ssl_set", "cache_purge", "firewall_rule", "setting_update"],
+                        },
+                        "description": { "type": "string", "description": "操作描述" },
+                        "params": { "type": "object", "description": "操作参数" },
+                        "risk": { "type": "string", "enum": ["low", "medium", "high"], "description": "调用方评估的风险等级 (只会被调高，不会低于操作类型的最低等级)" },
+                        "confirm": { "type": "boolean", "description": "用户已确认执行超出自动批准等级的操作" },
+                    },
+                    "required": ["zone", "type", "description", "params"],
+                },
+            }));
+        }
+        tools
+    }
+
+    /// 调用工具；工具自身的错误以 isError 结果返回，便于客户端把错误交给模型处理
+    async fn call_tool(&self, client: &CfClient, params: &Value) -> Value {
+        let args = &params["arguments"];
+        let result = match params["name"].as_str().unwrap_or_default() {
+            "list_zones" => list_zones(client).await,
+            "list_dns_records" => list_dns_records(client, args).await,
+            "get_zone_config" => get_zone_config(client, args).await,
+            "execute_action" if !self.read_only => self.execute_action(client, args).await,
+            name => Err(ValidationError(format!("未知的工具: {}", name)).into()),
+        };
+        match result {
+            Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+            Err(e) => json!({ "content": [{ "type": "text", "text": format!("{:#}", e) }], "isError": true }),
+        }
+    }
+
+    /// 经安全审查与风险确认后通过执行器执行单个操作
+    async fn execute_action(&self, client: &CfClient, args: &Value) -> Result<String> {
+        let zone_id = resolve_zone_id(client, required_str(args, "zone")?).await?;
+        let mut args = args.clone();
+        if args["risk"].is_null() {
+            args["risk"] = json!("low");
+        }
+        let mut action: SuggestedAction =
+            serde_json::from_value(args.clone()).map_err(|e| ValidationError(format!("操作格式无效: {}", e)))?;
+        // 调用方自报的 risk 不可信: 至少为操作类型的最低等级
+        safety::enforce_minimum_risk(&mut action);
+
+        // 无法审查时不自动执行
+        match LiveZoneState::fetch(client, &zone_id).await {
+            Ok(state) => safety::review_actions(std::slice::from_mut(&mut action), &state),
+            Err(e) => {
+                action.risk = "high".to_string();
+                action.review_notes.push(format!("安全审查失败: {:#}", e));
+            }
+        }
+        let notes = action.review_notes.join("; ");
+        if action.blocked {
+            anyhow::bail!("已被安全审查阻止: {}", notes);
+        }
+        let confirmed = args["confirm"].as_bool().unwrap_or(false);
+        if !confirmed && safety::risk_rank(&action.risk) > safety::risk_rank(&self.auto_approve) {
+            return Ok(format!(
+                "未执行: 审查后的风险等级为 {}，超过自动批准等级 {}。{}请向用户说明风险，获得同意后以 confirm: true 重新调用。",
+                action.risk,
+                self.auto_approve,
+                if notes.is_empty() { String::new() } else { format!("审查意见: {}。", notes) }
+            ));
+        }
+
+        let outcomes = executor::run_actions(client, &zone_id, std::slice::from_ref(&action), &mut AutoApprove).await?;
+        match outcomes.into_iter().next() {
+            Some(ActionOutcome::Applied(message)) => Ok(message),
+            Some(ActionOutcome::Failed(e)) => anyhow::bail!("执行失败: {}", e),
+            Some(ActionOutcome::Blocked(notes)) => anyhow::bail!("已被安全审查阻止: {}", notes),
+            Some(ActionOutcome::Skipped) | None => anyhow::bail!("操作未执行"),
+        }
+    }
+}
+
+/// 协商协议版本: 客户端请求的版本受支持时沿用，否则使用最新版本
+fn initialize(params: &Value) -> Value {
+    let requested = params["protocolVersion"].as_str().unwrap_or_default();
+    let version = PROTOCOL_VERSIONS
+        .iter()
+        .find(|v| **v == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "cfai", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn required_str<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    args[key]
+        .as_str()
+        .ok_or_else(|| ValidationError(format!("缺少参数: {}", key)).into())
+}
+
+async fn list_zones(client: &CfClient) -> Result<String> {
+    let zones: Vec<Value> = client
+        .list_all_zones()
+        .await?
+        .into_iter()
+        .map(|z| {
+            json!({
+                "id": z.id,
+                "name": z.name,
+                "status": z.status,
+                "paused": z.paused,
+                "plan": z.plan.and_then(|p| p.name),
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&zones)?)
+}
+
+async fn list_dns_records(client: &CfClient, args: &Value) -> Result<String> {
+    let zone_id = resolve_zone_id(client, required_str(args, "zone")?).await?;
+    let record_type = args["type"].as_str();
+    let name = args["name"].as_str().map(|n| n.to_lowercase());
+    let records: Vec<Value> = client
+        .list_all_dns_records(&zone_id)
+        .await?
+        .into_iter()
+        .filter(|r| record_type.is_none_or(|t| r.record_type.eq_ignore_ascii_case(t)))
+        .filter(|r| name.as_ref().is_none_or(|n| r.name.to_lowercase().contains(n.as_str())))
+        .map(|r| {
+            json!({
+                "id": r.id,
+                "type": r.record_type,
+                "name": r.name,
+                "content": r.content,
+                "ttl": r.ttl,
+                "proxied": r.proxied,
+                "priority": r.priority,
+                "comment": r.comment,
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&records)?)
+}
+
+async fn get_zone_config(client: &CfClient, args: &Value) -> Result<String> {
+    let zone_id = resolve_zone_id(client, required_str(args, "zone")?).await?;
+    Ok(collect_zone_context(client, &zone_id, "all").await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_negotiates_protocol_version() {
+        let result = initialize(&json!({ "protocolVersion": "2024-11-05" }));
+        assert_eq!(result["protocolVersion"], "2024-11-05");
+        let result = initialize(&json!({ "protocolVersion": "1999-01-01" }));
+        assert_eq!(result["protocolVersion"], PROTOCOL_VERSIONS[0]);
+        assert_eq!(result["serverInfo"]["name"], "cfai");
+    }
+}
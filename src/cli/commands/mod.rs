@@ -3,18 +3,50 @@ pub mod dns;
 pub mod ssl;
 pub mod firewall;
 pub mod cache;
+pub mod cache_local;
 pub mod page_rules;
+pub mod redirects;
+pub mod phase_rules;
+pub mod config_rules;
 pub mod workers;
+pub mod access;
+pub mod account;
+pub mod r2;
+pub mod d1;
 pub mod analytics;
+pub mod logs;
+pub mod guard;
+pub mod scheduler;
+pub mod ddns;
+pub mod healthcheck;
+pub mod scan;
+pub mod probe;
+pub mod ip;
+pub mod speed;
+pub mod zaraz;
+pub mod backup;
+pub mod apply;
+pub mod export;
+pub mod audit;
 pub mod ai;
+pub mod notify;
 pub mod config;
 pub mod install;
 pub mod interactive;
 pub mod self_update;
 pub mod update;
+pub mod version;
+pub mod mcp;
+#[cfg(feature = "serve")]
+pub mod serve;
 
+use anyhow::Result;
+use clap::builder::BoolishValueParser;
 use clap::{Parser, Subcommand};
 
+use crate::api::client::CfClient;
+use crate::config::settings::AppConfig;
+
 /// CFAI - AI 驱动的 Cloudflare 管理工具
 #[derive(Parser, Debug)]
 #[command(
@@ -35,6 +67,34 @@ pub struct Cli {
     /// 启用详细输出
     #[arg(short, long, global = true)]
     pub verbose: bool,
+
+    /// 安静模式: 只输出数据与错误，省略横幅、提示和表情符号 (适合脚本 / CI)
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// 非交互模式: 不等待输入，需要确认的操作直接报错 (请配合 --yes)；CI 或标准输入不是终端时自动开启
+    #[arg(long, global = true, env = "CFAI_NON_INTERACTIVE", value_parser = BoolishValueParser::new())]
+    pub non_interactive: bool,
+
+    /// 自动确认删除、还原等需要确认的操作 (对没有 --yes 选项的子命令同样生效，不适用于 AI 建议的操作)
+    #[arg(short = 'y', long, global = true, env = "CFAI_YES", value_parser = BoolishValueParser::new())]
+    pub yes: bool,
+
+    /// 使用指定的配置 Profile (覆盖 `config profile use` 的设置)
+    #[arg(long, global = true, env = "CFAI_PROFILE")]
+    pub profile: Option<String>,
+
+    /// 不使用本地缓存 (域名对应的 Zone ID、交互模式的域名列表、API 响应缓存)
+    #[arg(long, global = true, env = "CFAI_NO_CACHE")]
+    pub no_cache: bool,
+
+    /// 记录每个 API 请求的耗时，命令结束时输出按接口汇总的统计 (排查慢请求)
+    #[arg(long, global = true, alias = "profile-requests")]
+    pub timings: bool,
+
+    /// 命令完成后发送通知 (无论 notify.on 设置与是否有变更)
+    #[arg(long, global = true, env = "CFAI_NOTIFY")]
+    pub notify: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -57,21 +117,101 @@ pub enum Commands {
     /// 缓存管理
     Cache(cache::CacheArgs),
 
+    /// 加速设置 (Argo Smart Routing / Tiered Cache)
+    Speed(speed::SpeedArgs),
+
+    /// Zaraz 第三方工具管理
+    Zaraz(zaraz::ZarazArgs),
+
+    /// 管理 cfai 本地缓存 (域名列表等)
+    #[command(name = "cache-local")]
+    CacheLocal(cache_local::CacheLocalArgs),
+
     /// 页面规则管理
     #[command(alias = "pr")]
     PageRules(page_rules::PageRulesArgs),
 
+    /// 重定向规则管理 (Redirect Rules)
+    #[command(alias = "redirect")]
+    Redirects(redirects::RedirectsArgs),
+
+    /// 配置规则管理 (Configuration Rules，按 URL 覆盖域名设置)
+    #[command(name = "config-rules")]
+    ConfigRules(config_rules::ConfigRulesArgs),
+
     /// Workers 管理
     #[command(alias = "w")]
     Workers(workers::WorkersArgs),
 
+    /// Zero Trust Access 应用与策略管理
+    Access(access::AccessArgs),
+
+    /// R2 对象存储管理
+    R2(r2::R2Args),
+
+    /// D1 数据库管理
+    D1(d1::D1Args),
+
+    /// Cloudflare 账户与成员
+    #[command(alias = "accounts")]
+    Account(account::AccountArgs),
+
     /// 流量分析
     #[command(alias = "stats")]
     Analytics(analytics::AnalyticsArgs),
 
+    /// 实时请求日志 (Instant Logs)
+    Logs(logs::LogsArgs),
+
+    /// 自动防护: 流量超过阈值时开启 Under Attack 模式，恢复正常后还原
+    Guard(guard::GuardArgs),
+
+    /// 定时设置: 列出、删除或运行执行器 (由 `firewall schedule` 添加)
+    Scheduler(scheduler::SchedulerArgs),
+
+    /// 动态 DNS: 公网 IP 变化时自动更新 A/AAAA 记录
+    Ddns(ddns::DdnsArgs),
+
+    /// 源站健康检查 (Standalone Health Checks)
+    #[command(alias = "hc")]
+    Healthcheck(healthcheck::HealthcheckArgs),
+
+    /// 使用 URL Scanner 扫描可疑链接
+    Scan(scan::ScanArgs),
+
+    /// 发送真实请求检查经过 Cloudflare 的响应 (边缘节点、缓存状态、TLS、响应头)
+    Probe(probe::ProbeArgs),
+
+    /// 查询 IP: 是否属于 Cloudflare、反向解析与 ASN，以及相关的 IP 访问规则
+    Ip(ip::IpArgs),
+
+    /// 备份域名配置
+    Backup(backup::BackupArgs),
+
+    /// 从备份还原域名配置
+    Restore(backup::RestoreArgs),
+
+    /// 按声明式配置文件同步域名配置 (先显示变更计划，类似 terraform plan/apply)
+    Apply(apply::ApplyArgs),
+
+    /// 导出域名配置为基础设施即代码 (Terraform)
+    Export(export::ExportArgs),
+
+    /// 查看 DNS 记录与域名设置的变更审计日志
+    Audit(audit::AuditArgs),
+
+    /// 撤销最近一次可撤销的变更
+    Undo(audit::UndoArgs),
+
+    /// 撤销审计日志中的指定变更
+    Rollback(audit::RollbackArgs),
+
     /// AI 智能助手
     Ai(ai::AiArgs),
 
+    /// 通知渠道 (Slack / Discord / Webhook / 邮件)
+    Notify(notify::NotifyArgs),
+
     /// 配置管理
     Config(config::ConfigArgs),
 
@@ -84,7 +224,116 @@ pub enum Commands {
     /// 交互模式
     Interactive(interactive::InteractiveArgs),
 
+    /// 版本与 API 兼容性信息
+    Version(version::VersionArgs),
+
+    /// MCP 服务 (stdio)，供 Claude Desktop 等 AI 客户端查询与修改配置
+    Mcp(mcp::McpArgs),
+
+    /// 启动本地 HTTP API (域名、DNS 增删改、清除缓存、AI 分析)
+    #[cfg(feature = "serve")]
+    Serve(serve::ServeArgs),
+
     /// 启动图形界面 (GUI)
     #[cfg(feature = "gui")]
     Gui,
+
+    /// 全屏终端界面 (TUI)，适用于没有图形界面的服务器
+    #[cfg(feature = "tui")]
+    Tui,
+}
+
+impl Commands {
+    /// 是否无需 Cloudflare 客户端即可执行 (配置、安装、更新、审计、AI 用量等本地命令)
+    pub fn is_local(&self) -> bool {
+        match self {
+            Commands::Config(args) => !args.needs_client(),
+            Commands::Version(args) => !args.check_api,
+            Commands::Ai(args) => args.is_local(),
+            Commands::Install(_)
+            | Commands::Update(_)
+            | Commands::Audit(_)
+            | Commands::CacheLocal(_)
+            | Commands::Notify(_) => {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 是否需要有效的 Cloudflare 认证 (AI 纯问答不需要)
+    pub fn needs_auth(&self) -> bool {
+        !matches!(self, Commands::Ai(args) if matches!(args.command, ai::AiCommands::Ask { .. }))
+    }
+
+    /// 执行本地命令，仅在 `is_local()` 为 true 时调用
+    pub async fn execute_local(&self, format: &str, profile: Option<&str>) -> Result<()> {
+        match self {
+            Commands::Config(args) => args.execute(profile).await,
+            Commands::Install(args) => args.execute().await,
+            Commands::Update(args) => args.execute().await,
+            Commands::Version(args) => args.execute(None, format).await,
+            Commands::Audit(args) => args.execute(format).await,
+            Commands::CacheLocal(args) => args.execute().await,
+            Commands::Notify(args) => args.execute(profile).await,
+            Commands::Ai(args) => args.execute_local(format, profile).await,
+            _ => unreachable!(),
+        }
+    }
+
+    /// 使用已创建的 Cloudflare 客户端执行命令
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        match self {
+            Commands::Zone(args) => args.execute(client, format).await,
+            Commands::Dns(args) => args.execute(client, config, format).await,
+            Commands::Ssl(args) => args.execute(client, format).await,
+            Commands::Firewall(args) => args.execute(client, format).await,
+            Commands::Cache(args) => args.execute(client, config, format).await,
+            Commands::Speed(args) => args.execute(client, format).await,
+            Commands::Zaraz(args) => args.execute(client, format).await,
+            Commands::PageRules(args) => args.execute(client, format).await,
+            Commands::Redirects(args) => args.execute(client, format).await,
+            Commands::ConfigRules(args) => args.execute(client, format).await,
+            Commands::Workers(args) => args.execute(client, config, format).await,
+            Commands::Access(args) => args.execute(client, config, format).await,
+            Commands::R2(args) => args.execute(client, config, format).await,
+            Commands::D1(args) => args.execute(client, config, format).await,
+            Commands::Account(args) => args.execute(client, config, format).await,
+            Commands::Config(args) => args.execute_with_client(client, config, format).await,
+            Commands::Analytics(args) => args.execute(client, config, format).await,
+            Commands::Logs(args) => args.execute(client, format).await,
+            Commands::Guard(args) => args.execute(client, config, format).await,
+            Commands::Scheduler(args) => args.execute(client, config, format).await,
+            Commands::Ddns(args) => args.execute(client).await,
+            Commands::Healthcheck(args) => args.execute(client, format).await,
+            Commands::Scan(args) => args.execute(client, config, format).await,
+            Commands::Probe(args) => args.execute(client, config, format).await,
+            Commands::Ip(args) => args.execute(client, format).await,
+            Commands::Backup(args) => args.execute(client, format).await,
+            Commands::Restore(args) => args.execute(client, format).await,
+            Commands::Apply(args) => args.execute(client).await,
+            Commands::Export(args) => args.execute(client).await,
+            Commands::Undo(args) => args.execute(client).await,
+            Commands::Rollback(args) => args.execute(client).await,
+            Commands::Ai(args) => args.execute(client, config, format).await,
+            Commands::Version(args) => args.execute(Some(client), format).await,
+            Commands::Mcp(args) => args.execute(client).await,
+            #[cfg(feature = "serve")]
+            Commands::Serve(args) => args.execute(client, config).await,
+            #[cfg(feature = "tui")]
+            Commands::Tui => crate::tui::run(client).await,
+            Commands::Install(_)
+            | Commands::Update(_)
+            | Commands::Interactive(_)
+            | Commands::Audit(_)
+            | Commands::CacheLocal(_)
+            | Commands::Notify(_) => {
+                unreachable!()
+            }
+            #[cfg(feature = "gui")]
+            Commands::Gui => {
+                unreachable!()
+            }
+        }
+    }
 }
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::config::settings::AppConfig;
+use crate::notify::{self, Channel, Level, Notification};
+
+#[derive(Args, Debug)]
+pub struct NotifyArgs {
+    #[command(subcommand)]
+    pub command: NotifyCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NotifyCommands {
+    /// 向已配置的通知渠道发送测试消息
+    Test {
+        /// 只测试指定渠道 (slack/discord/webhook/email)
+        #[arg(short, long)]
+        channel: Option<String>,
+    },
+}
+
+impl NotifyArgs {
+    pub async fn execute(&self, profile: Option<&str>) -> Result<()> {
+        let config = AppConfig::load_effective(profile)?;
+        match &self.command {
+            NotifyCommands::Test { channel } => {
+                let channels = match channel {
+                    Some(name) => vec![Channel::parse(name)?],
+                    None => config.notify.channels(),
+                };
+                if channels.is_empty() {
+                    anyhow::bail!(ValidationError(
+                        "未配置通知渠道，运行 `cfai config set notify.slack_webhook URL` 等进行配置".into()
+                    ));
+                }
+
+                let notification = Notification::new(
+                    Level::Success,
+                    "cfai 测试通知",
+                    "如果你看到这条消息，说明 cfai 的通知渠道配置正确。",
+                );
+                let mut failed = 0;
+                for channel in channels {
+                    output::loading(&format!("正在发送 {} 通知...", channel.name()));
+                    match notify::send_to(&config, channel, &notification).await {
+                        Ok(()) => output::success(&format!("{} 通知发送成功", channel.name())),
+                        Err(e) => {
+                            failed += 1;
+                            output::error(&format!("{} 通知发送失败: {:#}", channel.name(), e));
+                        }
+                    }
+                }
+                if failed > 0 {
+                    anyhow::bail!("{} 个通知渠道发送失败", failed);
+                }
+            }
+        }
+        Ok(())
+    }
+}
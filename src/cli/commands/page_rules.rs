@@ -3,7 +3,9 @@ use clap::{Args, Subcommand};
 
 use crate::api::client::CfClient;
 use crate::cli::output;
+use crate::cli::prompt;
 use crate::cli::commands::zone::resolve_zone_id;
+use crate::models::zone::QuotaResource;
 
 #[derive(Args, Debug)]
 pub struct PageRulesArgs {
@@ -151,10 +153,7 @@ impl PageRulesArgs {
                 let zone_id = resolve_zone_id(client, domain).await?;
 
                 if !yes {
-                    let confirm = dialoguer::Confirm::new()
-                        .with_prompt("确定要删除此页面规则吗？")
-                        .default(false)
-                        .interact()?;
+                    let confirm = prompt::confirm("确定要删除此页面规则吗？")?;
                     if !confirm {
                         output::info("已取消");
                         return Ok(());
@@ -172,6 +171,7 @@ impl PageRulesArgs {
                 status,
             } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
+                client.check_quota(&zone_id, QuotaResource::PageRule).await?;
                 let rule = client
                     .create_redirect_rule(&zone_id, pattern, target, *status)
                     .await?;
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::models::rulesets::*;
+
+/// 列出阶段入口规则集中的规则，动作参数以 key=value 概括显示
+///
+/// 缓存规则、配置规则等只需展示表达式与设置的阶段共用此列表。
+pub async fn list_rules(
+    client: &CfClient,
+    domain: &str,
+    phase: &str,
+    label: &str,
+    format: &str,
+) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let rules = client
+        .get_phase_ruleset(&zone_id, phase)
+        .await?
+        .and_then(|r| r.rules)
+        .unwrap_or_default();
+
+    if format == "json" {
+        output::print_json(&rules);
+        return Ok(());
+    }
+
+    output::title(&format!("{} - {} (共 {} 条)", label, domain, rules.len()));
+
+    if rules.is_empty() {
+        output::info(&format!("没有{}", label));
+        return Ok(());
+    }
+
+    let mut table = output::create_table(vec!["ID", "描述", "匹配", "设置", "状态"]);
+    for rule in &rules {
+        table.add_row(vec![
+            rule.id.clone().unwrap_or("-".into()),
+            rule.description.clone().unwrap_or("-".into()),
+            rule.expression.clone().unwrap_or("-".into()),
+            rule.parameters_summary(),
+            if rule.enabled == Some(false) {
+                "已停用".yellow().to_string()
+            } else {
+                "已启用".green().to_string()
+            },
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// 确认后删除阶段入口规则集中的一条规则
+pub async fn delete_rule(
+    client: &CfClient,
+    domain: &str,
+    phase: &str,
+    label: &str,
+    rule_id: &str,
+    yes: bool,
+) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let entrypoint = client
+        .get_phase_ruleset(&zone_id, phase)
+        .await?
+        .ok_or_else(|| ValidationError(format!("该域名没有{}", label)))?;
+    let rule = entrypoint
+        .find_rule(rule_id)
+        .ok_or_else(|| ValidationError(format!("未找到{}: {}", label, rule_id)))?;
+
+    if !yes {
+        let confirm = prompt::confirm(format!(
+            "确定要删除{} {} 吗？",
+            label,
+            rule.description.as_deref().unwrap_or(rule_id).yellow()
+        ))?;
+        if !confirm {
+            output::info("已取消");
+            return Ok(());
+        }
+    }
+
+    let entry_id = entrypoint.id.as_deref().unwrap_or_default();
+    client.delete_ruleset_rule(&zone_id, entry_id, rule_id).await?;
+    output::success(&format!("{} {} 已删除", label, rule_id));
+    Ok(())
+}
+
+/// 由 --from 或 --expression 得到规则表达式
+pub fn rule_expression(from: Option<&str>, expression: Option<&str>) -> Result<String> {
+    match (expression, from) {
+        (Some(expr), _) => Ok(expr.to_string()),
+        (None, Some(from)) => url_pattern_expression(from),
+        (None, None) => anyhow::bail!(ValidationError("请指定 --from 或 --expression".into())),
+    }
+}
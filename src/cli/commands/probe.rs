@@ -0,0 +1,249 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::ai::analyzer::AiAnalyzer;
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::config::settings::AppConfig;
+
+use super::ai::troubleshoot;
+use super::zone::zone_for_host;
+
+/// 发送真实请求，检查经过 Cloudflare 的响应 (边缘节点、缓存、TLS、协议与响应头)
+#[derive(Args, Debug)]
+pub struct ProbeArgs {
+    /// 要探测的 URL (省略协议时使用 https)
+    pub url: String,
+    /// 请求方法
+    #[arg(short = 'X', long, default_value = "GET")]
+    pub method: String,
+    /// 附加请求头 (可重复，如 -H "Cookie: a=1")
+    #[arg(short = 'H', long = "header", value_name = "NAME: VALUE")]
+    pub headers: Vec<String>,
+    /// 跟随重定向
+    #[arg(short = 'L', long)]
+    pub follow: bool,
+    /// 将结果交给 AI 诊断，可附带问题描述 (未指定时在交互终端中询问)
+    #[arg(long, value_name = "问题", num_args = 0..=1, default_missing_value = "")]
+    pub ai: Option<String>,
+}
+
+/// 探测结果
+#[derive(Debug, Serialize)]
+struct ProbeResult {
+    url: String,
+    status: u16,
+    http_version: String,
+    /// 是否经过 Cloudflare (响应带 cf-ray)
+    via_cloudflare: bool,
+    cf_ray: Option<String>,
+    /// 处理请求的边缘节点 (机场代码)
+    colo: Option<String>,
+    cache_status: Option<String>,
+    /// 客户端与边缘之间的 TLS 版本 (来自 /cdn-cgi/trace)
+    tls_version: Option<String>,
+    server_timing: Option<String>,
+    /// 收到响应头的耗时 (毫秒)
+    ttfb_ms: u128,
+    total_ms: u128,
+    body_bytes: usize,
+    headers: Vec<(String, String)>,
+}
+
+impl ProbeArgs {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        let url = if self.url.contains("://") { self.url.clone() } else { format!("https://{}", self.url) };
+        let parsed = reqwest::Url::parse(&url).map_err(|_| ValidationError(format!("无效的 URL: {}", url)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            anyhow::bail!(ValidationError(format!("只支持 HTTP(S) 地址: {}", url)));
+        }
+
+        let redirect = if self.follow { reqwest::redirect::Policy::limited(10) } else { reqwest::redirect::Policy::none() };
+        let http = config
+            .network
+            .apply(reqwest::Client::builder(), Duration::from_secs(30))?
+            .redirect(redirect)
+            .user_agent(concat!("cfai/", env!("CARGO_PKG_VERSION"), " (probe)"))
+            .build()
+            .context("创建 HTTP 客户端失败")?;
+
+        if format != "json" {
+            output::loading(&format!("正在请求 {}", url));
+        }
+        let result = probe(&http, &parsed, &self.method, &self.headers).await?;
+
+        if format == "json" {
+            output::print_json(&result);
+        } else {
+            print_result(&result);
+        }
+
+        let issue = match &self.ai {
+            Some(issue) => issue.clone(),
+            None if format != "json" && prompt::is_interactive() => {
+                let ask = dialoguer::Confirm::new()
+                    .with_prompt("将探测结果交给 AI 诊断？")
+                    .default(false)
+                    .interact()?;
+                if !ask {
+                    return Ok(());
+                }
+                String::new()
+            }
+            None => return Ok(()),
+        };
+        let issue = if issue.is_empty() && prompt::is_interactive() {
+            dialoguer::Input::<String>::new()
+                .with_prompt("描述遇到的问题")
+                .default("请检查此请求的响应是否存在问题".to_string())
+                .interact_text()?
+        } else if issue.is_empty() {
+            "请检查此请求的响应是否存在问题".to_string()
+        } else {
+            issue
+        };
+
+        let zone = zone_for_host(client, parsed.host_str().unwrap_or_default()).await.ok().flatten();
+        let mut analyzer = AiAnalyzer::new(config)?;
+        let domain = zone.map(|z| z.name);
+        troubleshoot(client, &mut analyzer, &issue, domain.as_deref(), Some(&result.to_context()), false).await
+    }
+}
+
+async fn probe(http: &reqwest::Client, url: &reqwest::Url, method: &str, headers: &[String]) -> Result<ProbeResult> {
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| ValidationError(format!("无效的请求方法: {}", method)))?;
+    let mut request = http.request(method, url.clone());
+    for header in headers {
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| ValidationError(format!("请求头格式应为 NAME: VALUE: {}", header)))?;
+        request = request.header(name.trim(), value.trim());
+    }
+
+    let started = Instant::now();
+    let response = request.send().await.with_context(|| format!("请求 {} 失败", url))?;
+    let ttfb = started.elapsed();
+    let status = response.status().as_u16();
+    let http_version = format!("{:?}", response.version());
+    let final_url = response.url().to_string();
+    let headers: Vec<(String, String)> = response
+        .headers()
+        .iter()
+        .map(|(k, v)| (k.to_string(), String::from_utf8_lossy(v.as_bytes()).into_owned()))
+        .collect();
+    let body = response.bytes().await.context("读取响应内容失败")?;
+    let total = started.elapsed();
+
+    let header = |name: &str| headers.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+    let cf_ray = header("cf-ray");
+    let tls_version = match (&cf_ray, url.scheme()) {
+        (Some(_), "https") => trace_tls(http, url).await,
+        _ => None,
+    };
+    Ok(ProbeResult {
+        url: final_url,
+        status,
+        http_version,
+        via_cloudflare: cf_ray.is_some(),
+        colo: cf_ray.as_deref().and_then(ray_colo),
+        cf_ray,
+        cache_status: header("cf-cache-status"),
+        tls_version,
+        server_timing: header("server-timing"),
+        ttfb_ms: ttfb.as_millis(),
+        total_ms: total.as_millis(),
+        body_bytes: body.len(),
+        headers,
+    })
+}
+
+/// 通过同一主机的 /cdn-cgi/trace 获取边缘看到的 TLS 版本
+async fn trace_tls(http: &reqwest::Client, url: &reqwest::Url) -> Option<String> {
+    let trace = url.join("/cdn-cgi/trace").ok()?;
+    let body = http.get(trace).send().await.ok()?.text().await.ok()?;
+    body.lines()
+        .find_map(|line| line.strip_prefix("tls="))
+        .map(str::to_string)
+}
+
+/// cf-ray 的后缀为边缘节点代码，如 `8a1b2c3d4e5f6a7b-SJC`
+fn ray_colo(ray: &str) -> Option<String> {
+    ray.rsplit_once('-').map(|(_, colo)| colo.to_string())
+}
+
+impl ProbeResult {
+    /// 供 AI 诊断使用的文本摘要
+    fn to_context(&self) -> String {
+        let mut context = format!(
+            "HTTP 探测结果:\nURL: {}\n状态码: {}\n协议: {}\n经过 Cloudflare: {}\n",
+            self.url, self.status, self.http_version, self.via_cloudflare
+        );
+        let optional = [
+            ("边缘节点", &self.colo),
+            ("缓存状态", &self.cache_status),
+            ("TLS", &self.tls_version),
+            ("Server-Timing", &self.server_timing),
+        ];
+        for (label, value) in optional {
+            if let Some(value) = value {
+                context.push_str(&format!("{}: {}\n", label, value));
+            }
+        }
+        context.push_str(&format!("首字节耗时: {} ms, 总耗时: {} ms\n响应头:\n", self.ttfb_ms, self.total_ms));
+        for (name, value) in &self.headers {
+            context.push_str(&format!("  {}: {}\n", name, value));
+        }
+        context
+    }
+}
+
+fn print_result(result: &ProbeResult) {
+    output::title(&format!("HTTP 探测 - {}", result.url));
+    let status = result.status.to_string();
+    output::kv_colored("状态码", &status, result.status < 400);
+    output::kv("协议", &result.http_version);
+    if result.via_cloudflare {
+        output::kv_colored("经过 Cloudflare", "是", true);
+    } else {
+        output::kv_colored("经过 Cloudflare", "否 (响应没有 cf-ray)", false);
+    }
+    let opt = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+    output::kv("CF-Ray", &opt(&result.cf_ray));
+    output::kv("边缘节点", &opt(&result.colo));
+    output::kv("缓存状态", &opt(&result.cache_status));
+    output::kv("TLS", &opt(&result.tls_version));
+    output::kv("Server-Timing", &opt(&result.server_timing));
+    output::kv(
+        "耗时",
+        &format!(
+            "首字节 {} ms / 总计 {} ms ({})",
+            result.ttfb_ms,
+            result.total_ms,
+            output::format_bytes(result.body_bytes as u64)
+        ),
+    );
+
+    println!("\n{}", "响应头".bold());
+    for (name, value) in &result.headers {
+        println!("  {}: {}", name.cyan(), value);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ray_colo() {
+        assert_eq!(ray_colo("8a1b2c3d4e5f6a7b-SJC").as_deref(), Some("SJC"));
+        assert_eq!(ray_colo("invalid"), None);
+    }
+}
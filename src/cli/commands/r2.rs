@@ -0,0 +1,241 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::config::settings::AppConfig;
+use crate::models::r2::*;
+
+#[derive(Args, Debug)]
+pub struct R2Args {
+    #[command(subcommand)]
+    pub command: R2Commands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum R2Commands {
+    /// 列出 R2 存储桶
+    #[command(alias = "ls")]
+    List {
+        /// 同时查询每个存储桶的用量
+        #[arg(long)]
+        usage: bool,
+    },
+
+    /// 创建 R2 存储桶
+    Create {
+        /// 存储桶名称
+        name: String,
+        /// 位置提示 (apac/eeur/enam/weur/wnam/oc)
+        #[arg(long)]
+        location: Option<String>,
+    },
+
+    /// 删除 R2 存储桶 (存储桶必须为空)
+    #[command(alias = "rm")]
+    Delete {
+        /// 存储桶名称
+        name: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// 列出存储桶中的对象
+    Objects {
+        /// 存储桶名称
+        bucket: String,
+        /// 只列出指定前缀的对象
+        #[arg(short, long)]
+        prefix: Option<String>,
+        /// 最多列出的对象数
+        #[arg(short, long, default_value = "100")]
+        limit: u32,
+    },
+
+    /// 查看存储桶用量
+    Usage {
+        /// 存储桶名称
+        bucket: String,
+    },
+}
+
+impl R2Args {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        let account_id = config
+            .cloudflare
+            .account_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("R2 管理需要 Account ID，请运行 `cfai config setup`"))?;
+
+        match &self.command {
+            R2Commands::List { usage } => {
+                let buckets = client.list_r2_buckets(account_id).await?;
+
+                let mut usages = Vec::with_capacity(buckets.len());
+                if *usage {
+                    for bucket in &buckets {
+                        usages.push(client.get_r2_bucket_usage(account_id, &bucket.name).await.ok());
+                    }
+                }
+
+                if format == "json" {
+                    if *usage {
+                        let items: Vec<_> = buckets
+                            .iter()
+                            .zip(&usages)
+                            .map(|(b, u)| serde_json::json!({ "bucket": b, "usage": u }))
+                            .collect();
+                        output::print_json(&items);
+                    } else {
+                        output::print_json(&buckets);
+                    }
+                    return Ok(());
+                }
+
+                output::title(&format!("R2 存储桶 (共 {} 个)", buckets.len()));
+
+                if buckets.is_empty() {
+                    output::info("没有 R2 存储桶");
+                    return Ok(());
+                }
+
+                let mut headers = vec!["名称", "位置", "存储类型", "创建时间"];
+                if *usage {
+                    headers.extend(["对象数", "存储量"]);
+                }
+                let mut table = output::create_table(headers);
+                for (i, b) in buckets.iter().enumerate() {
+                    let mut row = vec![
+                        b.name.clone(),
+                        b.location.clone().unwrap_or("-".into()),
+                        b.storage_class.clone().unwrap_or("-".into()),
+                        b.creation_date.clone().unwrap_or("-".into()),
+                    ];
+                    if *usage {
+                        let u = usages.get(i).cloned().flatten();
+                        row.push(
+                            u.as_ref()
+                                .and_then(|u| u.object_count)
+                                .map(|c| c.to_string())
+                                .unwrap_or("-".into()),
+                        );
+                        row.push(
+                            u.as_ref()
+                                .and_then(|u| u.payload_size)
+                                .map(output::format_bytes)
+                                .unwrap_or("-".into()),
+                        );
+                    }
+                    table.add_row(row);
+                }
+                println!("{table}");
+            }
+
+            R2Commands::Create { name, location } => {
+                let request = CreateR2BucketRequest {
+                    name: name.clone(),
+                    location_hint: location.clone(),
+                };
+                let bucket = client.create_r2_bucket(account_id, &request).await?;
+
+                if format == "json" {
+                    output::print_json(&bucket);
+                    return Ok(());
+                }
+                output::success(&format!(
+                    "R2 存储桶 {} 已创建{}",
+                    name,
+                    bucket
+                        .location
+                        .as_deref()
+                        .map(|l| format!(" (位置: {})", l))
+                        .unwrap_or_default()
+                ));
+            }
+
+            R2Commands::Delete { name, yes } => {
+                if !yes {
+                    let confirm = prompt::confirm(format!("确定要删除 R2 存储桶 {} 吗？", name.yellow()))?;
+                    if !confirm {
+                        output::info("已取消");
+                        return Ok(());
+                    }
+                }
+
+                client.delete_r2_bucket(account_id, name).await?;
+                output::success(&format!("R2 存储桶 {} 已删除", name));
+            }
+
+            R2Commands::Objects {
+                bucket,
+                prefix,
+                limit,
+            } => {
+                let params = R2ObjectListParams {
+                    prefix: prefix.clone(),
+                    per_page: Some(*limit),
+                };
+                let objects = client.list_r2_objects(account_id, bucket, &params).await?;
+
+                if format == "json" {
+                    output::print_json(&objects);
+                    return Ok(());
+                }
+
+                output::title(&format!("R2 对象 - {} (共 {} 个)", bucket, objects.len()));
+
+                if objects.is_empty() {
+                    output::info("没有对象");
+                    return Ok(());
+                }
+
+                let mut table = output::create_table(vec!["Key", "大小", "存储类型", "修改时间"]);
+                for o in &objects {
+                    table.add_row(vec![
+                        o.key.clone(),
+                        o.size.map(output::format_bytes).unwrap_or("-".into()),
+                        o.storage_class.clone().unwrap_or("-".into()),
+                        o.last_modified.clone().unwrap_or("-".into()),
+                    ]);
+                }
+                println!("{table}");
+                if objects.len() as u32 >= *limit {
+                    output::tip(&format!("仅显示前 {} 个对象，可使用 --limit 或 --prefix 缩小范围", limit));
+                }
+            }
+
+            R2Commands::Usage { bucket } => {
+                let usage = client.get_r2_bucket_usage(account_id, bucket).await?;
+
+                if format == "json" {
+                    output::print_json(&usage);
+                    return Ok(());
+                }
+
+                output::title(&format!("R2 用量 - {}", bucket));
+                output::kv(
+                    "对象数",
+                    &usage.object_count.map(|c| c.to_string()).unwrap_or("-".into()),
+                );
+                output::kv(
+                    "存储量",
+                    &usage.payload_size.map(output::format_bytes).unwrap_or("-".into()),
+                );
+                output::kv(
+                    "元数据",
+                    &usage.metadata_size.map(output::format_bytes).unwrap_or("-".into()),
+                );
+                output::kv(
+                    "未完成的分片上传",
+                    &usage.upload_count.map(|c| c.to_string()).unwrap_or("-".into()),
+                );
+                output::kv("统计截止", usage.end.as_deref().unwrap_or("-"));
+            }
+        }
+
+        Ok(())
+    }
+}
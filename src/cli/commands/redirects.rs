@@ -0,0 +1,166 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::cli::commands::phase_rules::{delete_rule, rule_expression};
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::models::rulesets::*;
+
+#[derive(Args, Debug)]
+pub struct RedirectsArgs {
+    #[command(subcommand)]
+    pub command: RedirectsCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RedirectsCommands {
+    /// 列出重定向规则
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 添加重定向规则
+    Add {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 匹配的 URL 模式 (如 example.com/old*，支持 * 通配符)
+        #[arg(long, required_unless_present = "expression")]
+        from: Option<String>,
+        /// 直接使用规则表达式匹配 (代替 --from)
+        #[arg(long, conflicts_with = "from")]
+        expression: Option<String>,
+        /// 跳转目标 URL
+        #[arg(long)]
+        to: String,
+        /// HTTP 状态码 (301/302/303/307/308)
+        #[arg(short, long, default_value = "301")]
+        status: u16,
+        /// 保留原请求的查询参数
+        #[arg(long)]
+        preserve_query: bool,
+        /// 规则描述
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// 删除重定向规则
+    #[command(alias = "rm")]
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 规则 ID
+        rule_id: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+impl RedirectsArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match &self.command {
+            RedirectsCommands::List { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let rules = client
+                    .get_phase_ruleset(&zone_id, REDIRECT_PHASE)
+                    .await?
+                    .and_then(|r| r.rules)
+                    .unwrap_or_default();
+
+                if format == "json" {
+                    output::print_json(&rules);
+                    return Ok(());
+                }
+
+                output::title(&format!("重定向规则 - {} (共 {} 条)", domain, rules.len()));
+
+                if rules.is_empty() {
+                    output::info("没有重定向规则");
+                    return Ok(());
+                }
+
+                let mut table = output::create_table(vec!["ID", "描述", "匹配", "目标", "状态码", "状态"]);
+                for rule in &rules {
+                    table.add_row(vec![
+                        rule.id.clone().unwrap_or("-".into()),
+                        rule.description.clone().unwrap_or("-".into()),
+                        rule.expression.clone().unwrap_or("-".into()),
+                        rule.redirect_target().unwrap_or("(动态)").to_string(),
+                        rule.redirect_status().map(|s| s.to_string()).unwrap_or("-".into()),
+                        if rule.enabled == Some(false) {
+                            "已停用".yellow().to_string()
+                        } else {
+                            "已启用".green().to_string()
+                        },
+                    ]);
+                }
+                println!("{table}");
+            }
+
+            RedirectsCommands::Add {
+                domain,
+                from,
+                expression,
+                to,
+                status,
+                preserve_query,
+                description,
+            } => {
+                if !REDIRECT_STATUS_CODES.contains(status) {
+                    anyhow::bail!(ValidationError(format!(
+                        "不支持的状态码: {} (可选: 301/302/303/307/308)",
+                        status
+                    )));
+                }
+                if !to.starts_with("http://") && !to.starts_with("https://") {
+                    anyhow::bail!(ValidationError(format!(
+                        "跳转目标必须是完整 URL (http:// 或 https:// 开头): {}",
+                        to
+                    )));
+                }
+                let expression = rule_expression(from.as_deref(), expression.as_deref())?;
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let rule = RulesetRuleRequest {
+                    action: "redirect".into(),
+                    expression: expression.clone(),
+                    description: Some(description.clone().unwrap_or_else(|| {
+                        format!("{} → {}", from.as_deref().unwrap_or(&expression), to)
+                    })),
+                    enabled: Some(true),
+                    action_parameters: Some(serde_json::json!({
+                        "from_value": {
+                            "status_code": status,
+                            "target_url": { "value": to },
+                            "preserve_query_string": preserve_query,
+                        }
+                    })),
+                };
+
+                let result = client.add_phase_rule(&zone_id, REDIRECT_PHASE, &rule).await?;
+
+                if format == "json" {
+                    output::print_json(&result);
+                    return Ok(());
+                }
+                output::success(&format!("重定向规则已添加 ({} → {})", status, to));
+                output::kv("匹配表达式", &expression);
+            }
+
+            RedirectsCommands::Delete {
+                domain,
+                rule_id,
+                yes,
+            } => {
+                delete_rule(client, domain, REDIRECT_PHASE, "重定向规则", rule_id, *yes).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
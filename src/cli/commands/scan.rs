@@ -0,0 +1,179 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::config::settings::AppConfig;
+use crate::models::urlscanner::{UrlScanReport, UrlScanRequest};
+
+/// 使用 Cloudflare URL Scanner 扫描可疑链接
+#[derive(Args, Debug)]
+pub struct ScanArgs {
+    /// 要扫描的 URL
+    #[arg(required_unless_present = "id")]
+    pub url: Option<String>,
+    /// 查看已有扫描的报告 (扫描 ID)
+    #[arg(long, conflicts_with = "url")]
+    pub id: Option<String>,
+    /// 报告可见性: unlisted 不出现在公开搜索中
+    #[arg(long, default_value = "unlisted", value_parser = ["public", "unlisted"])]
+    pub visibility: String,
+    /// 等待扫描完成的最长时间 (秒)
+    #[arg(long, default_value_t = 120)]
+    pub timeout: u64,
+    /// 只提交扫描，不等待结果
+    #[arg(long)]
+    pub no_wait: bool,
+}
+
+impl ScanArgs {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        let account_id = config
+            .cloudflare
+            .account_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("URL 扫描需要 Account ID，请运行 `cfai config setup`"))?;
+
+        let scan_id = match (&self.id, &self.url) {
+            (Some(id), _) => id.clone(),
+            (None, Some(url)) => {
+                let url = if url.contains("://") { url.clone() } else { format!("https://{}", url) };
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    anyhow::bail!(ValidationError(format!("只支持扫描 HTTP(S) 地址: {}", url)));
+                }
+                let visibility = if self.visibility == "public" { "Public" } else { "Unlisted" };
+                let request = UrlScanRequest {
+                    url,
+                    visibility: visibility.to_string(),
+                };
+                let submission = client.submit_url_scan(account_id, &request).await?;
+                if self.no_wait {
+                    if format == "json" {
+                        output::print_json(&submission);
+                    } else {
+                        output::success(&format!("已提交扫描: {}", submission.uuid));
+                        output::tip(&format!("稍后运行 `cfai scan --id {}` 查看报告", submission.uuid));
+                    }
+                    return Ok(());
+                }
+                submission.uuid
+            }
+            (None, None) => unreachable!(),
+        };
+
+        let report = self.wait_for_report(client, account_id, &scan_id).await?;
+        if format == "json" {
+            output::print_json(&report);
+            return Ok(());
+        }
+        print_report(&scan_id, &report);
+        Ok(())
+    }
+
+    /// 轮询扫描报告直到完成或超时
+    async fn wait_for_report(&self, client: &CfClient, account_id: &str, scan_id: &str) -> Result<UrlScanReport> {
+        let deadline = Instant::now() + Duration::from_secs(self.timeout);
+        let mut announced = false;
+        loop {
+            if let Some(report) = client.get_url_scan(account_id, scan_id).await? {
+                return Ok(report);
+            }
+            if Instant::now() >= deadline {
+                anyhow::bail!(
+                    "扫描 {} 在 {} 秒内未完成，稍后运行 `cfai scan --id {}` 查看报告",
+                    scan_id,
+                    self.timeout,
+                    scan_id
+                );
+            }
+            if !announced {
+                output::loading(&format!("正在扫描 ({})", scan_id));
+                announced = true;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(5)) => {}
+                _ = tokio::signal::ctrl_c() => anyhow::bail!("已取消等待，扫描 ID: {}", scan_id),
+            }
+        }
+    }
+}
+
+fn print_report(scan_id: &str, report: &UrlScanReport) {
+    let page = &report.page;
+    output::title(&format!(
+        "URL 扫描报告 - {}",
+        report.task.url.as_deref().or(page.url.as_deref()).unwrap_or(scan_id)
+    ));
+
+    let verdict = &report.verdicts.overall;
+    let verdict_text = match verdict.malicious {
+        Some(true) => "恶意".red().bold().to_string(),
+        Some(false) => "未发现恶意行为".green().to_string(),
+        None => "-".into(),
+    };
+    output::kv("结论", &verdict_text);
+    if !verdict.categories.is_empty() {
+        output::kv("威胁类别", &verdict.categories.join(", "));
+    }
+
+    output::kv("最终地址", page.url.as_deref().unwrap_or("-"));
+    output::kv("页面标题", page.title.as_deref().unwrap_or("-"));
+    if let Some(status) = &page.status {
+        output::kv("状态码", &status.as_str().map(String::from).unwrap_or(status.to_string()));
+    }
+    output::kv(
+        "IP",
+        &format!(
+            "{} ({}{})",
+            page.ip.as_deref().unwrap_or("-"),
+            page.country.as_deref().unwrap_or("-"),
+            page.asnname
+                .as_deref()
+                .map(|name| format!(", {}", name))
+                .unwrap_or_default()
+        ),
+    );
+    if let Some(server) = &page.server {
+        output::kv("服务器", server);
+    }
+
+    let technologies: Vec<&str> = report
+        .meta
+        .processors
+        .wappa
+        .data
+        .iter()
+        .filter_map(|t| t.app.as_deref())
+        .collect();
+    if !technologies.is_empty() {
+        output::kv("技术栈", &technologies.join(", "));
+    }
+    output::kv(
+        "请求",
+        &format!("{} 个域名, {} 个 IP", report.lists.domains.len(), report.lists.ips.len()),
+    );
+
+    if !report.lists.certificates.is_empty() {
+        println!();
+        let mut table = output::create_table(vec!["证书主体", "签发者", "有效期至"]);
+        for cert in &report.lists.certificates {
+            table.add_row(vec![
+                cert.subject_name.clone().unwrap_or("-".into()),
+                cert.issuer.clone().unwrap_or("-".into()),
+                cert.valid_to
+                    .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                    .map(|t| t.format("%Y-%m-%d").to_string())
+                    .unwrap_or("-".into()),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    println!();
+    output::kv("扫描 ID", scan_id);
+    output::kv("完整报告", &format!("https://radar.cloudflare.com/scan/{}", scan_id));
+}
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveTime, Timelike};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use croner::Cron;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::config::settings::{AppConfig, ScheduleEntry};
+use crate::models::common::CfResponse;
+use crate::notify::{self, Level, Notification};
+
+use super::zone::resolve_zone_id;
+
+/// 定时设置: 查看、删除，或运行执行器
+#[derive(Args, Debug)]
+pub struct SchedulerArgs {
+    #[command(subcommand)]
+    pub command: SchedulerCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SchedulerCommands {
+    /// 列出定时设置
+    #[command(alias = "ls")]
+    List,
+
+    /// 删除定时设置
+    #[command(alias = "rm")]
+    Remove {
+        /// 定时设置 ID
+        id: u32,
+    },
+
+    /// 运行执行器: 每分钟检查一次，到点时修改域名设置 (Ctrl+C 退出)
+    Run {
+        /// 只检查一次后退出 (适合由系统 cron 调用)
+        #[arg(long)]
+        once: bool,
+    },
+}
+
+impl SchedulerArgs {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig, format: &str) -> Result<()> {
+        match &self.command {
+            SchedulerCommands::List => print_schedules(&config.schedules, format),
+            SchedulerCommands::Remove { id } => {
+                if !remove_schedule(*id)? {
+                    anyhow::bail!(ValidationError(format!("定时设置不存在: {}", id)));
+                }
+                output::success(&format!("定时设置 {} 已删除", id));
+                Ok(())
+            }
+            SchedulerCommands::Run { once } => run(client, config, *once).await,
+        }
+    }
+}
+
+impl ScheduleEntry {
+    /// 检查时间段、cron 表达式与一次性时间是否有效
+    pub fn validate(&self) -> Result<()> {
+        if let Some(at) = &self.at {
+            if self.from.is_some() || self.to.is_some() || self.cron.is_some() || self.revert.is_some() {
+                anyhow::bail!(ValidationError("一次性定时设置不能与时间段或 --cron 同时使用".into()));
+            }
+            parse_at(at)?;
+            return Ok(());
+        }
+        match (&self.from, &self.to, &self.cron) {
+            (Some(from), Some(to), None) => {
+                parse_time(from)?;
+                parse_time(to)?;
+            }
+            (None, None, Some(cron)) => {
+                parse_cron(cron)?;
+                if self.revert.is_some() {
+                    anyhow::bail!(ValidationError("--revert 只能用于时间段 (--from/--to)".into()));
+                }
+            }
+            (None, None, None) => anyhow::bail!(ValidationError("请指定时间段 (--from/--to) 或 --cron".into())),
+            (_, _, Some(_)) => anyhow::bail!(ValidationError("时间段与 --cron 不能同时使用".into())),
+            _ => anyhow::bail!(ValidationError("--from 与 --to 需要同时指定".into())),
+        }
+        Ok(())
+    }
+
+    /// 触发条件的说明
+    pub fn describe(&self) -> String {
+        if let Some(at) = self.at.as_deref().and_then(|at| parse_at(at).ok()) {
+            return format!("一次性: {}", at.format("%Y-%m-%d %H:%M"));
+        }
+        match (&self.from, &self.to, &self.cron) {
+            (Some(from), Some(to), _) => match &self.revert {
+                Some(revert) => format!("{}-{} (其余时间 {})", from, to, revert),
+                None => format!("{}-{}", from, to),
+            },
+            (_, _, Some(cron)) => format!("cron: {}", cron),
+            _ => "-".to_string(),
+        }
+    }
+
+    /// 此刻应设置的值: 时间段内为 value、时间段外为 revert；cron 只在匹配的分钟返回 value；
+    /// 一次性设置到点 (或错过时间后) 返回 value
+    fn desired_value(&self, now: &DateTime<Local>) -> Result<Option<&str>> {
+        if let Some(at) = &self.at {
+            return Ok((*now >= parse_at(at)?).then_some(self.value.as_str()));
+        }
+        if let (Some(from), Some(to)) = (&self.from, &self.to) {
+            let inside = in_window(parse_time(from)?, parse_time(to)?, now.time());
+            return Ok(if inside { Some(self.value.as_str()) } else { self.revert.as_deref() });
+        }
+        if let Some(cron) = &self.cron {
+            let minute = now.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(*now);
+            let matched = parse_cron(cron)?.is_time_matching(&minute).unwrap_or(false);
+            return Ok(matched.then_some(self.value.as_str()));
+        }
+        Ok(None)
+    }
+}
+
+/// 添加定时设置并写入配置文件，返回新 ID
+pub fn add_schedule(mut entry: ScheduleEntry) -> Result<u32> {
+    entry.validate()?;
+    let mut stored = AppConfig::load()?;
+    entry.id = stored.schedules.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+    let id = entry.id;
+    stored.schedules.push(entry);
+    stored.save()?;
+    Ok(id)
+}
+
+/// 删除定时设置并写入配置文件，返回是否存在
+pub fn remove_schedule(id: u32) -> Result<bool> {
+    let mut stored = AppConfig::load()?;
+    let before = stored.schedules.len();
+    stored.schedules.retain(|s| s.id != id);
+    if stored.schedules.len() == before {
+        return Ok(false);
+    }
+    stored.save()?;
+    Ok(true)
+}
+
+fn parse_at(value: &str) -> Result<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Local))
+        .map_err(|_| ValidationError(format!("一次性执行时间格式应为 RFC 3339: {}", value)).into())
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| ValidationError(format!("时间格式应为 HH:MM: {}", value)).into())
+}
+
+fn parse_cron(expression: &str) -> Result<Cron> {
+    Cron::new(expression)
+        .parse()
+        .map_err(|e| ValidationError(format!("无效的 cron 表达式 {}: {}", expression, e)).into())
+}
+
+/// 时间是否在 [from, to) 内，to 早于 from 时表示跨午夜
+fn in_window(from: NaiveTime, to: NaiveTime, now: NaiveTime) -> bool {
+    if from <= to {
+        from <= now && now < to
+    } else {
+        now >= from || now < to
+    }
+}
+
+/// 设置值按 JSON 解析 (数字、布尔值、对象)，否则作为字符串
+fn setting_value(value: &str) -> serde_json::Value {
+    serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
+}
+
+fn print_schedules(schedules: &[ScheduleEntry], format: &str) -> Result<()> {
+    if format == "json" {
+        output::print_json(&schedules);
+        return Ok(());
+    }
+    if schedules.is_empty() {
+        output::info("没有定时设置，使用 `cfai firewall schedule` 添加");
+        return Ok(());
+    }
+    output::title(&format!("定时设置 (共 {} 条)", schedules.len()));
+    let mut table = output::create_table(vec!["ID", "域名", "设置", "值", "时间"]);
+    for s in schedules {
+        table.add_row(vec![s.id.to_string(), s.domain.clone(), s.setting.clone(), s.value.clone(), s.describe()]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+async fn run(client: &CfClient, config: &AppConfig, once: bool) -> Result<()> {
+    if config.schedules.is_empty() {
+        output::warn("没有定时设置，使用 `cfai firewall schedule` 添加");
+    }
+    if !once {
+        output::info("定时设置执行器已启动，每分钟检查一次，按 Ctrl+C 停止");
+    }
+
+    // 每条定时设置最近一次确认的值，避免每分钟重复请求
+    let mut applied: HashMap<u32, String> = HashMap::new();
+    let mut last_cron_minute: HashMap<u32, i64> = HashMap::new();
+    loop {
+        // 每次重新读取配置，运行期间添加或删除的定时设置立即生效
+        let schedules = AppConfig::load().map(|c| c.schedules).unwrap_or_else(|_| config.schedules.clone());
+        let now = Local::now();
+        let minute = now.timestamp() / 60;
+        for entry in &schedules {
+            let desired = match entry.desired_value(&now) {
+                Ok(Some(value)) => value,
+                Ok(None) => continue,
+                Err(e) => {
+                    log(&format!("{} [{}] {:#}", "✗".red(), entry.id, e));
+                    continue;
+                }
+            };
+            let fresh = if entry.cron.is_some() {
+                last_cron_minute.insert(entry.id, minute) != Some(minute)
+            } else {
+                applied.get(&entry.id).map(String::as_str) != Some(desired)
+            };
+            if !fresh {
+                continue;
+            }
+            let result = apply(client, entry, desired).await;
+            match &result {
+                Ok(false) => {
+                    applied.insert(entry.id, desired.to_string());
+                }
+                Ok(true) => {
+                    applied.insert(entry.id, desired.to_string());
+                    let message = format!("{} {} = {}", entry.domain, entry.setting, desired);
+                    log(&format!("{} [{}] {}", "✓".green(), entry.id, message));
+                    notify::send_quietly(config, &Notification::new(Level::Success, "定时设置已应用", &message)).await;
+                }
+                Err(e) => log(&format!("{} [{}] {:#}", "✗".red(), entry.id, e)),
+            }
+            // 一次性设置应用后即删除，失败时下一分钟重试
+            if entry.at.is_some() && result.is_ok() {
+                if let Err(e) = remove_schedule(entry.id) {
+                    log(&format!("{} [{}] {:#}", "✗".red(), entry.id, e));
+                }
+            }
+        }
+
+        if once {
+            return Ok(());
+        }
+        // 等到下一分钟开始
+        let wait = 60 - Local::now().second() as u64;
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(wait)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// 修改设置，当前值已一致时不发请求，返回是否有修改
+async fn apply(client: &CfClient, entry: &ScheduleEntry, value: &str) -> Result<bool> {
+    let zone_id = resolve_zone_id(client, &entry.domain).await?;
+    let value = setting_value(value);
+    if let Ok(current) = client.get_zone_setting(&zone_id, &entry.setting).await {
+        if current.value == value {
+            return Ok(false);
+        }
+    }
+    let _: CfResponse<serde_json::Value> = client
+        .patch_zone_setting(&zone_id, &entry.setting, value)
+        .await
+        .with_context(|| format!("修改 {} 的 {} 失败", entry.domain, entry.setting))?;
+    Ok(true)
+}
+
+fn log(message: &str) {
+    println!("{} {}", format!("[{}]", Local::now().format("%Y-%m-%d %H:%M:%S")).dimmed(), message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_window() {
+        let t = |s: &str| NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+        assert!(in_window(t("09:00"), t("18:00"), t("12:30")));
+        assert!(!in_window(t("09:00"), t("18:00"), t("18:00")));
+        assert!(in_window(t("22:00"), t("06:00"), t("23:15")));
+        assert!(in_window(t("22:00"), t("06:00"), t("05:59")));
+        assert!(!in_window(t("22:00"), t("06:00"), t("12:00")));
+
+        let entry = ScheduleEntry {
+            id: 1,
+            domain: "example.com".into(),
+            setting: "security_level".into(),
+            value: "high".into(),
+            from: Some("22:00".into()),
+            to: Some("06:00".into()),
+            revert: Some("medium".into()),
+            cron: None,
+            at: None,
+        };
+        entry.validate().unwrap();
+        let at = |h, m| Local::now().with_hour(h).unwrap().with_minute(m).unwrap();
+        assert_eq!(entry.desired_value(&at(23, 0)).unwrap(), Some("high"));
+        assert_eq!(entry.desired_value(&at(12, 0)).unwrap(), Some("medium"));
+
+        let cron = ScheduleEntry { from: None, to: None, revert: None, cron: Some("30 8 * * *".into()), ..entry };
+        cron.validate().unwrap();
+        assert_eq!(cron.desired_value(&at(8, 30)).unwrap(), Some("high"));
+        assert_eq!(cron.desired_value(&at(8, 31)).unwrap(), None);
+        assert!(ScheduleEntry { cron: Some("bad".into()), ..cron.clone() }.validate().is_err());
+
+        let once = ScheduleEntry { cron: None, at: Some(at(9, 0).to_rfc3339()), ..cron };
+        once.validate().unwrap();
+        assert_eq!(once.desired_value(&at(8, 59)).unwrap(), None);
+        assert_eq!(once.desired_value(&at(9, 1)).unwrap(), Some("high"));
+    }
+}
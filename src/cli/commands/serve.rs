@@ -0,0 +1,347 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use clap::Args;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::ai::analyzer::AiAnalyzer;
+use crate::ai::safety::{self, LiveZoneState};
+use crate::api::client::CfClient;
+use crate::api::error::{ApiError, ValidationError};
+use crate::cli::exit;
+use crate::cli::output;
+use crate::config::settings::AppConfig;
+use crate::models::cache::PurgeTarget;
+use crate::models::dns::{managed_comment, DnsRecordRequest};
+
+use super::ai::{analyze_zone, collect_zone_context};
+use super::zone::resolve_zone_id;
+
+/// 本地 HTTP API: 供其他工具、仪表盘或家庭自动化以 JSON 调用 cfai
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// 监听端口
+    #[arg(short, long, default_value_t = 8787)]
+    pub port: u16,
+    /// 监听地址 (默认只监听本机)
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+    /// 访问令牌 (请求头 `Authorization: Bearer <令牌>`)，未指定时随机生成
+    #[arg(long, env = "CFAI_SERVE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+}
+
+struct ServeState {
+    client: CfClient,
+    config: AppConfig,
+    token: String,
+}
+
+impl ServeArgs {
+    pub async fn execute(&self, client: &CfClient, config: &AppConfig) -> Result<()> {
+        let (token, generated) = match &self.token {
+            Some(token) if token.len() < 16 => {
+                anyhow::bail!(ValidationError("访问令牌至少需要 16 个字符".into()))
+            }
+            Some(token) => (token.clone(), false),
+            None => (random_token()?, true),
+        };
+        let addr: SocketAddr = format!("{}:{}", self.host, self.port)
+            .parse()
+            .map_err(|_| ValidationError(format!("无效的监听地址: {}:{}", self.host, self.port)))?;
+
+        let state = Arc::new(ServeState {
+            client: client.clone(),
+            config: config.clone(),
+            token: token.clone(),
+        });
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("监听 {} 失败", addr))?;
+
+        output::success(&format!("HTTP API 已启动: http://{}", addr));
+        if generated {
+            output::kv("访问令牌", &token);
+        }
+        if !addr.ip().is_loopback() {
+            output::warn("正在监听非本机地址，请确保网络可信或通过 HTTPS 反向代理访问");
+        }
+        output::info("按 Ctrl+C 停止");
+
+        axum::serve(listener, router(state))
+            .with_graceful_shutdown(async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await
+            .context("HTTP 服务异常退出")
+    }
+}
+
+fn router(state: Arc<ServeState>) -> Router {
+    let api = Router::new()
+        .route("/zones", get(list_zones))
+        .route("/zones/{zone}/dns", get(list_dns).post(create_dns))
+        .route(
+            "/zones/{zone}/dns/{id}",
+            put(update_dns).patch(patch_dns).delete(delete_dns),
+        )
+        .route("/zones/{zone}/purge", post(purge))
+        .route("/zones/{zone}/analyze", post(analyze))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    Router::new()
+        .route("/health", get(health))
+        .merge(api)
+        .with_state(state)
+}
+
+/// 生成 32 位十六进制随机令牌
+fn random_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow::anyhow!("生成访问令牌失败: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+async fn require_token(State(state): State<Arc<ServeState>>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), state.token.as_bytes()) => next.run(request).await,
+        _ => error_response(StatusCode::UNAUTHORIZED, "缺少或无效的访问令牌"),
+    }
+}
+
+/// 比较令牌时耗时不随匹配的前缀长度变化
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 处理函数的错误，按错误类型转换为 HTTP 状态码
+struct ServeError(anyhow::Error);
+
+impl From<anyhow::Error> for ServeError {
+    fn from(err: anyhow::Error) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for ServeError {
+    fn into_response(self) -> Response {
+        error_response(status_for(&self.0), &format!("{:#}", self.0))
+    }
+}
+
+/// 错误对应的 HTTP 状态码 (分类与进程退出码一致)
+fn status_for(err: &anyhow::Error) -> StatusCode {
+    match exit::code_for(err) {
+        exit::VALIDATION_ERROR => StatusCode::BAD_REQUEST,
+        exit::AUTH_ERROR => StatusCode::FORBIDDEN,
+        exit::API_ERROR => match err.downcast_ref::<ApiError>() {
+            // 资源不存在等客户端错误原样返回，其余视为上游故障
+            Some(api) if (400..500).contains(&api.status) => {
+                StatusCode::from_u16(api.status).unwrap_or(StatusCode::BAD_GATEWAY)
+            }
+            _ => StatusCode::BAD_GATEWAY,
+        },
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "success": false, "error": message }))).into_response()
+}
+
+type ApiResult = std::result::Result<Json<Value>, ServeError>;
+
+fn ok(result: impl serde::Serialize) -> ApiResult {
+    Ok(Json(json!({ "success": true, "result": result })))
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+async fn list_zones(State(state): State<Arc<ServeState>>) -> ApiResult {
+    ok(state.client.list_all_zones().await?)
+}
+
+async fn list_dns(State(state): State<Arc<ServeState>>, Path(zone): Path<String>) -> ApiResult {
+    let zone_id = resolve_zone_id(&state.client, &zone).await?;
+    ok(state.client.list_all_dns_records(&zone_id).await?)
+}
+
+async fn create_dns(
+    State(state): State<Arc<ServeState>>,
+    Path(zone): Path<String>,
+    Json(mut request): Json<DnsRecordRequest>,
+) -> ApiResult {
+    let zone_id = resolve_zone_id(&state.client, &zone).await?;
+    request.comment = Some(managed_comment(request.comment.as_deref()));
+    ok(state.client.create_dns_record(&zone_id, &request).await?)
+}
+
+async fn update_dns(
+    State(state): State<Arc<ServeState>>,
+    Path((zone, id)): Path<(String, String)>,
+    Json(request): Json<DnsRecordRequest>,
+) -> ApiResult {
+    let zone_id = resolve_zone_id(&state.client, &zone).await?;
+    ok(state.client.update_dns_record(&zone_id, &id, &request).await?)
+}
+
+async fn patch_dns(
+    State(state): State<Arc<ServeState>>,
+    Path((zone, id)): Path<(String, String)>,
+    Json(patch): Json<Value>,
+) -> ApiResult {
+    let zone_id = resolve_zone_id(&state.client, &zone).await?;
+    ok(state.client.patch_dns_record(&zone_id, &id, &patch).await?)
+}
+
+async fn delete_dns(
+    State(state): State<Arc<ServeState>>,
+    Path((zone, id)): Path<(String, String)>,
+) -> ApiResult {
+    let zone_id = resolve_zone_id(&state.client, &zone).await?;
+    state.client.delete_dns_record(&zone_id, &id).await?;
+    ok(json!({ "id": id }))
+}
+
+/// 清除缓存请求，只能指定一种方式
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PurgeRequest {
+    everything: bool,
+    urls: Vec<String>,
+    prefixes: Vec<String>,
+    tags: Vec<String>,
+    hosts: Vec<String>,
+}
+
+async fn purge(
+    State(state): State<Arc<ServeState>>,
+    Path(zone): Path<String>,
+    Json(request): Json<PurgeRequest>,
+) -> ApiResult {
+    let targets = [
+        request.everything,
+        !request.urls.is_empty(),
+        !request.prefixes.is_empty(),
+        !request.tags.is_empty(),
+        !request.hosts.is_empty(),
+    ];
+    if targets.iter().filter(|t| **t).count() != 1 {
+        return Err(anyhow::Error::from(ValidationError(
+            "请指定且只指定 everything / urls / prefixes / tags / hosts 中的一项".into(),
+        ))
+        .into());
+    }
+
+    let zone_id = resolve_zone_id(&state.client, &zone).await?;
+    let client = &state.client;
+    if request.everything {
+        client.purge_all_cache(&zone_id).await?;
+    } else if !request.urls.is_empty() {
+        client
+            .purge_cache_batched(&zone_id, PurgeTarget::Urls, &request.urls, |_, _, _| {})
+            .await?;
+    } else if !request.prefixes.is_empty() {
+        client
+            .purge_cache_batched(&zone_id, PurgeTarget::Prefixes, &request.prefixes, |_, _, _| {})
+            .await?;
+    } else if !request.tags.is_empty() {
+        client.purge_cache_by_tags(&zone_id, request.tags).await?;
+    } else {
+        client.purge_cache_by_hosts(&zone_id, request.hosts).await?;
+    }
+    ok(json!({ "zone_id": zone_id }))
+}
+
+#[derive(Deserialize)]
+struct AnalyzeRequest {
+    /// dns / security / performance / all
+    #[serde(rename = "type", default = "default_analysis_type")]
+    analysis_type: String,
+}
+
+fn default_analysis_type() -> String {
+    "all".to_string()
+}
+
+/// AI 分析域名配置；建议的操作经安全审查后返回，不会执行
+async fn analyze(
+    State(state): State<Arc<ServeState>>,
+    Path(zone): Path<String>,
+    request: Option<Json<AnalyzeRequest>>,
+) -> ApiResult {
+    let analysis_type = request.map(|Json(r)| r.analysis_type).unwrap_or_else(default_analysis_type);
+    let analyzer = AiAnalyzer::new(&state.config)?;
+    let zone_id = resolve_zone_id(&state.client, &zone).await?;
+    let context = collect_zone_context(&state.client, &zone_id, &analysis_type).await;
+    let result = analyze_zone(&analyzer, &zone, &context, &analysis_type).await?;
+
+    let mut actions = result.actions.unwrap_or_default();
+    if !actions.is_empty() {
+        if let Ok(live) = LiveZoneState::fetch(&state.client, &zone_id).await {
+            safety::review_actions(&mut actions, &live);
+        }
+    }
+    let actions: Vec<Value> = actions
+        .iter()
+        .map(|action| {
+            json!({
+                "type": action.action_type,
+                "description": action.description,
+                "params": action.params,
+                "risk": action.risk,
+                "review_notes": action.review_notes,
+                "blocked": action.blocked,
+            })
+        })
+        .collect();
+    ok(json!({
+        "content": result.content,
+        "actions": actions,
+        "tokens_used": result.tokens_used,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_for_error_kinds() {
+        let not_found: anyhow::Error = ApiError {
+            status: 404,
+            codes: vec![81044],
+            message: "Record not found".into(),
+        }
+        .into();
+        assert_eq!(status_for(&not_found.context("删除 DNS 记录失败")), StatusCode::NOT_FOUND);
+
+        let upstream: anyhow::Error = ApiError {
+            status: 500,
+            codes: vec![],
+            message: "internal".into(),
+        }
+        .into();
+        assert_eq!(status_for(&upstream), StatusCode::BAD_GATEWAY);
+        assert_eq!(status_for(&ValidationError("x".into()).into()), StatusCode::BAD_REQUEST);
+
+        assert!(constant_time_eq(b"secret-token", b"secret-token"));
+        assert!(!constant_time_eq(b"secret-token", b"secret-tokem"));
+        assert!(!constant_time_eq(b"secret", b"secret-token"));
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::api::client::CfClient;
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::output;
+use crate::models::zone::ZoneSetting;
+
+#[derive(Args, Debug)]
+pub struct SpeedArgs {
+    #[command(subcommand)]
+    pub command: SpeedCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SpeedCommands {
+    /// 查看 Argo Smart Routing 与 Tiered Cache 状态
+    Status {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 开启/关闭 Argo Smart Routing (按量计费)
+    Argo {
+        /// on/off
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 设置 Tiered Cache (on: 通用拓扑，smart: 智能拓扑，off: 关闭)
+    #[command(name = "tiered-cache")]
+    TieredCache {
+        /// on/off/smart
+        #[arg(value_parser = ["on", "off", "smart"])]
+        state: String,
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 查看或设置图片优化 (Polish / WebP / Mirage)，不带参数时只查看
+    Images {
+        /// 域名或 Zone ID
+        domain: String,
+        /// Polish 图片压缩模式
+        #[arg(long, value_parser = ["off", "lossless", "lossy"])]
+        polish: Option<String>,
+        /// 向支持的浏览器提供 WebP 格式 (需开启 Polish)
+        #[arg(long, value_parser = ["on", "off"])]
+        webp: Option<String>,
+        /// Mirage 移动端图片优化
+        #[arg(long, value_parser = ["on", "off"])]
+        mirage: Option<String>,
+    },
+}
+
+impl SpeedArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match &self.command {
+            SpeedCommands::Status { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let (argo, tiered, smart) = tokio::join!(
+                    client.get_argo_smart_routing(&zone_id),
+                    client.get_tiered_caching(&zone_id),
+                    client.get_smart_tiered_cache(&zone_id),
+                );
+
+                if format == "json" {
+                    output::print_json(&serde_json::json!({
+                        "argo_smart_routing": argo.as_ref().ok().map(|s| &s.value),
+                        "tiered_caching": tiered.as_ref().ok().map(|s| &s.value),
+                        "smart_tiered_cache": smart.as_ref().ok().map(|s| &s.value),
+                    }));
+                    return Ok(());
+                }
+
+                output::title(&format!("加速设置 - {}", domain));
+                output::kv("Argo Smart Routing", &describe(&argo));
+                let tiered_value = match (&tiered, &smart) {
+                    (Ok(t), Ok(s)) if is_on(t) && is_on(s) => output::status_badge("on") + " (smart)",
+                    _ => describe(&tiered),
+                };
+                output::kv("Tiered Cache", &tiered_value);
+            }
+
+            SpeedCommands::Argo { state, domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let setting = client.set_argo_smart_routing(&zone_id, state == "on").await?;
+                if format == "json" {
+                    output::print_json(&setting);
+                    return Ok(());
+                }
+                output::success(&format!(
+                    "Argo Smart Routing 已{}",
+                    if state == "on" { "开启" } else { "关闭" }
+                ));
+            }
+
+            SpeedCommands::TieredCache { state, domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                // 智能拓扑依赖 Tiered Cache: 开启时先开 Tiered Cache，关闭时先关智能拓扑
+                let setting = match state.as_str() {
+                    "smart" => {
+                        let setting = client.set_tiered_caching(&zone_id, true).await?;
+                        client.set_smart_tiered_cache(&zone_id, true).await?;
+                        setting
+                    }
+                    "on" => {
+                        let setting = client.set_tiered_caching(&zone_id, true).await?;
+                        client.set_smart_tiered_cache(&zone_id, false).await?;
+                        setting
+                    }
+                    _ => {
+                        client.set_smart_tiered_cache(&zone_id, false).await?;
+                        client.set_tiered_caching(&zone_id, false).await?
+                    }
+                };
+                if format == "json" {
+                    output::print_json(&setting);
+                    return Ok(());
+                }
+                output::success(match state.as_str() {
+                    "smart" => "Tiered Cache 已开启 (智能拓扑)",
+                    "on" => "Tiered Cache 已开启 (通用拓扑)",
+                    _ => "Tiered Cache 已关闭",
+                });
+            }
+
+            SpeedCommands::Images {
+                domain,
+                polish,
+                webp,
+                mirage,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                for (setting_id, value) in [("polish", polish), ("webp", webp), ("mirage", mirage)] {
+                    let Some(value) = value else { continue };
+                    client
+                        .update_zone_setting(&zone_id, setting_id, serde_json::json!(value))
+                        .await?;
+                    if format != "json" {
+                        output::success(&format!("{} 已设置为 {}", setting_id, value));
+                    }
+                }
+
+                let images = client.get_image_settings(&zone_id).await;
+                if format == "json" {
+                    output::print_json(&images);
+                    return Ok(());
+                }
+
+                output::title(&format!("图片优化 - {}", domain));
+                let show = |value: &Option<String>| match value {
+                    Some(v) => output::status_badge(v),
+                    None => "获取失败 (可能需要 Pro 及以上套餐)".to_string(),
+                };
+                output::kv("Polish", &show(&images.polish));
+                output::kv("WebP", &show(&images.webp));
+                output::kv("Mirage", &show(&images.mirage));
+                if images.webp.as_deref() == Some("on") && images.polish.as_deref() == Some("off") {
+                    output::warn("WebP 转换只在 Polish 开启时生效");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_on(setting: &ZoneSetting) -> bool {
+    setting.value.as_str() == Some("on")
+}
+
+/// 设置值的展示文字，获取失败时显示原因
+fn describe(setting: &Result<ZoneSetting>) -> String {
+    match setting {
+        Ok(s) => output::status_badge(s.value.as_str().unwrap_or("-")),
+        Err(e) => format!("获取失败: {:#}", e),
+    }
+}
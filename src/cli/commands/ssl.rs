@@ -1,9 +1,19 @@
-use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::Serialize;
 
 use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
 use crate::cli::output;
-use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::prompt;
+use crate::cli::commands::zone::{resolve_zone_id, zone_for_host};
+use crate::models::dns::{caa_data, managed_comment, DnsRecordRequest};
+use crate::models::ssl::*;
+use crate::tls_inspect::{self, TlsReport};
 
 #[derive(Args, Debug)]
 pub struct SslArgs {
@@ -33,7 +43,7 @@ pub enum SslCommands {
         domain: String,
     },
 
-    /// 列出 SSL 证书
+    /// 列出 SSL 证书包 (含验证状态)
     #[command(alias = "ls")]
     List {
         /// 域名或 Zone ID
@@ -57,6 +67,28 @@ pub enum SslCommands {
         version: String,
     },
 
+    /// 开启或关闭 TLS 1.3
+    Tls13 {
+        /// 域名或 Zone ID
+        domain: String,
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+    },
+
+    /// 开启或关闭 0-RTT 连接恢复 (TLS 1.3 early data，降低重连延迟，但请求可能被重放)
+    EarlyData {
+        /// 域名或 Zone ID
+        domain: String,
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+    },
+
+    /// 管理 TLS 1.2 及以下使用的加密套件
+    Ciphers {
+        #[command(subcommand)]
+        command: CipherCommands,
+    },
+
     /// 列出源服务器证书
     OriginCerts {
         /// 域名或 Zone ID
@@ -71,6 +103,201 @@ pub enum SslCommands {
         #[arg(default_value = "on")]
         toggle: String,
     },
+
+    /// 列出已上传的自定义证书
+    CustomCerts {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 上传自定义证书
+    UploadCert {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 证书文件 (PEM，可包含中间证书)
+        #[arg(long)]
+        cert: PathBuf,
+        /// 私钥文件 (PEM，未加密)
+        #[arg(long)]
+        key: PathBuf,
+        /// 证书链构建方式 (ubiquitous/optimal/force)
+        #[arg(long)]
+        bundle_method: Option<String>,
+        /// 私钥存放区域 (us/eu/highest_security)
+        #[arg(long)]
+        geo: Option<String>,
+        /// 证书类型 (sni_custom/legacy_custom)
+        #[arg(long = "type")]
+        cert_type: Option<String>,
+    },
+
+    /// 删除自定义证书
+    DeleteCert {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 证书 ID
+        cert_id: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// 直接与边缘和源站握手，查看证书链、协议与加密套件，诊断 525/526 错误
+    Inspect {
+        /// 主机名 (如 www.example.com)
+        hostname: String,
+        /// 源站地址 (IP 或主机名，默认取 DNS 记录的内容)
+        #[arg(long)]
+        origin: Option<String>,
+        /// 端口
+        #[arg(long, default_value_t = 443)]
+        port: u16,
+    },
+
+    /// 查看或设置 Total TLS (为每个已代理的主机名自动签发证书，需要 Advanced Certificate Manager)
+    TotalTls {
+        /// 域名或 Zone ID
+        domain: String,
+        /// on/off (省略时查看当前设置)
+        #[arg(value_parser = ["on", "off"])]
+        state: Option<String>,
+        /// 证书颁发机构
+        #[arg(long, value_parser = CERTIFICATE_AUTHORITIES.to_vec())]
+        ca: Option<String>,
+    },
+
+    /// 订购高级证书包 (需要 Advanced Certificate Manager)
+    OrderCert {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 证书包含的主机名 (逗号分隔，如 example.com,*.example.com；根域名会自动加入)
+        #[arg(long, value_delimiter = ',', required = true)]
+        hosts: Vec<String>,
+        /// 有效期 (天): lets_encrypt 仅 90，google 14/30/90，ssl_com 14/30/90/365
+        #[arg(long, default_value_t = 90)]
+        validity: u32,
+        /// 证书颁发机构
+        #[arg(long, default_value = "lets_encrypt", value_parser = CERTIFICATE_AUTHORITIES.to_vec())]
+        ca: String,
+        /// 域名验证方式 (txt/http/email)
+        #[arg(long, default_value = "txt")]
+        validation: String,
+        /// 在证书中加入 Cloudflare 品牌主机名 (sni.cloudflaressl.com)
+        #[arg(long)]
+        cloudflare_branding: bool,
+    },
+
+    /// 检查 CAA 记录是否允许 Cloudflare 通用证书使用的 CA (及 Let's Encrypt) 签发证书
+    CaaCheck {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 创建缺少的 CAA 记录
+        #[arg(long)]
+        fix: bool,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// 自定义主机名 (SSL for SaaS): 为客户的域名签发证书并回源到本域名
+    #[command(name = "custom-hostnames", alias = "ch")]
+    CustomHostnames {
+        #[command(subcommand)]
+        command: CustomHostnameCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CustomHostnameCommands {
+    /// 列出自定义主机名
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 按主机名过滤
+        #[arg(long)]
+        hostname: Option<String>,
+    },
+
+    /// 查看自定义主机名的验证状态 (验证记录与失败原因)
+    Status {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 自定义主机名或其 ID
+        hostname: String,
+    },
+
+    /// 添加自定义主机名
+    Create {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 客户的主机名 (如 shop.customer.com)
+        hostname: String,
+        /// 证书验证方式 (http/txt/email)
+        #[arg(long, default_value = "http")]
+        method: String,
+        /// 最小 TLS 版本 (1.0/1.1/1.2/1.3)
+        #[arg(long)]
+        min_tls: Option<String>,
+        /// 回源到指定源站 (默认使用回源地址)
+        #[arg(long)]
+        origin: Option<String>,
+    },
+
+    /// 重新触发证书验证
+    Revalidate {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 自定义主机名或其 ID
+        hostname: String,
+    },
+
+    /// 删除自定义主机名
+    Delete {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 自定义主机名或其 ID
+        hostname: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// 查看或设置默认回源地址 (不带参数时只查看)
+    Fallback {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 回源地址 (需为本域名下已代理的记录)
+        origin: Option<String>,
+        /// 删除默认回源地址
+        #[arg(long, conflicts_with = "origin")]
+        delete: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CipherCommands {
+    /// 查看当前允许的加密套件与可选值
+    #[command(alias = "ls")]
+    List {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 只允许指定的加密套件 (逗号分隔，`modern` 表示推荐的 ECDHE + AEAD 套件)
+    Set {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 加密套件
+        #[arg(value_delimiter = ',', required = true)]
+        ciphers: Vec<String>,
+    },
+
+    /// 恢复 Cloudflare 默认的加密套件
+    Reset {
+        /// 域名或 Zone ID
+        domain: String,
+    },
 }
 
 impl SslArgs {
@@ -145,20 +372,9 @@ impl SslArgs {
                     return Ok(());
                 }
 
-                output::title(&format!("SSL 证书 - {} (共 {} 个)", domain, certs.len()));
+                output::title(&format!("SSL 证书包 - {} (共 {} 个)", domain, certs.len()));
                 for cert in &certs {
-                    output::kv("ID", cert.id.as_deref().unwrap_or("-"));
-                    output::kv(
-                        "主机",
-                        &cert
-                            .hosts
-                            .as_ref()
-                            .map(|h| h.join(", "))
-                            .unwrap_or("-".into()),
-                    );
-                    output::kv("状态", cert.status.as_deref().unwrap_or("-"));
-                    output::kv("签发者", cert.issuer.as_deref().unwrap_or("-"));
-                    output::kv("过期时间", cert.expires_on.as_deref().unwrap_or("-"));
+                    print_certificate_pack(cert);
                     println!();
                 }
             }
@@ -179,6 +395,23 @@ impl SslArgs {
                 output::success(&format!("最小 TLS 版本已设置为: {}", version));
             }
 
+            SslCommands::Tls13 { domain, state } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.set_tls_1_3(&zone_id, state == "on").await?;
+                output::success(&format!("TLS 1.3 已{}", if state == "on" { "开启" } else { "关闭" }));
+            }
+
+            SslCommands::EarlyData { domain, state } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                client.set_early_data(&zone_id, state == "on").await?;
+                output::success(&format!("0-RTT 已{}", if state == "on" { "开启" } else { "关闭" }));
+                if state == "on" {
+                    output::tip("0-RTT 请求可能被重放，非幂等的接口 (如下单、支付) 请在源站检查 Cf-0rtt-Unique 请求头");
+                }
+            }
+
+            SslCommands::Ciphers { command } => execute_ciphers(client, command, format).await?,
+
             SslCommands::OriginCerts { domain } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
                 let certs = client.list_origin_certificates(&zone_id).await?;
@@ -215,8 +448,904 @@ impl SslArgs {
                     if enable { "开启" } else { "关闭" }
                 ));
             }
+
+            SslCommands::CustomCerts { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let certs = client.list_custom_certificates(&zone_id).await?;
+
+                if format == "json" {
+                    output::print_json(&certs);
+                    return Ok(());
+                }
+
+                output::title(&format!("自定义证书 - {} (共 {} 个)", domain, certs.len()));
+                if certs.is_empty() {
+                    output::info("没有自定义证书");
+                    return Ok(());
+                }
+
+                let mut table =
+                    output::create_table(vec!["ID", "主机", "状态", "签发者", "链方式", "区域", "过期时间"]);
+                for c in &certs {
+                    table.add_row(vec![
+                        c.id.clone().unwrap_or("-".into()),
+                        c.hosts.as_ref().map(|h| h.join(", ")).unwrap_or("-".into()),
+                        c.status.clone().unwrap_or("-".into()),
+                        c.issuer.clone().unwrap_or("-".into()),
+                        c.bundle_method.clone().unwrap_or("-".into()),
+                        c.geo_restrictions.as_ref().map(|g| g.label.clone()).unwrap_or("-".into()),
+                        c.expires_on.clone().unwrap_or("-".into()),
+                    ]);
+                }
+                println!("{table}");
+            }
+
+            SslCommands::UploadCert {
+                domain,
+                cert,
+                key,
+                bundle_method,
+                geo,
+                cert_type,
+            } => {
+                check_choice("证书链构建方式", bundle_method, BUNDLE_METHODS)?;
+                check_choice("私钥存放区域", geo, GEO_RESTRICTIONS)?;
+                check_choice("证书类型", cert_type, CUSTOM_CERT_TYPES)?;
+
+                let certificate = std::fs::read_to_string(cert)
+                    .with_context(|| format!("读取证书文件失败: {}", cert.display()))?;
+                let private_key = std::fs::read_to_string(key)
+                    .with_context(|| format!("读取私钥文件失败: {}", key.display()))?;
+                let chain_len = validate_custom_cert_pem(&certificate, &private_key)?;
+
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let request = UploadCustomCertRequest {
+                    certificate,
+                    private_key,
+                    bundle_method: bundle_method.clone(),
+                    geo_restrictions: geo.clone().map(|label| GeoRestrictions { label }),
+                    cert_type: cert_type.clone(),
+                };
+                let uploaded = client.upload_custom_certificate(&zone_id, &request).await?;
+
+                if format == "json" {
+                    output::print_json(&uploaded);
+                    return Ok(());
+                }
+
+                output::success(&format!("自定义证书已上传 (证书链 {} 张)", chain_len));
+                output::kv("证书 ID", uploaded.id.as_deref().unwrap_or("-"));
+                output::kv(
+                    "主机",
+                    &uploaded.hosts.as_ref().map(|h| h.join(", ")).unwrap_or("-".into()),
+                );
+                output::kv("状态", uploaded.status.as_deref().unwrap_or("-"));
+                output::kv("过期时间", uploaded.expires_on.as_deref().unwrap_or("-"));
+            }
+
+            SslCommands::DeleteCert { domain, cert_id, yes } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+
+                if !yes {
+                    let confirm = prompt::confirm(format!("确定要删除自定义证书 {} 吗？", cert_id))?;
+                    if !confirm {
+                        output::info("已取消");
+                        return Ok(());
+                    }
+                }
+
+                client.delete_custom_certificate(&zone_id, cert_id).await?;
+                output::success("自定义证书已删除");
+            }
+
+            SslCommands::Inspect { hostname, origin, port } => {
+                inspect_tls(client, hostname, origin.as_deref(), *port, format).await?;
+            }
+
+            SslCommands::TotalTls { domain, state, ca } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let total = match state {
+                    Some(state) => client.set_total_tls(&zone_id, state == "on", ca.as_deref()).await?,
+                    None if ca.is_some() => anyhow::bail!(ValidationError("--ca 需要与 on 一起使用".into())),
+                    None => client.get_total_tls(&zone_id).await?,
+                };
+
+                if format == "json" {
+                    output::print_json(&total);
+                    return Ok(());
+                }
+                if state.is_some() {
+                    output::success(&format!(
+                        "Total TLS 已{}",
+                        if total.enabled == Some(true) { "开启" } else { "关闭" }
+                    ));
+                }
+                output::title(&format!("Total TLS - {}", domain));
+                output::kv_colored(
+                    "状态",
+                    if total.enabled == Some(true) { "开启" } else { "关闭" },
+                    total.enabled == Some(true),
+                );
+                output::kv("CA", total.certificate_authority.as_deref().unwrap_or("默认"));
+                if let Some(days) = total.validity_period {
+                    output::kv("有效期", &format!("{} 天", days));
+                }
+            }
+
+            SslCommands::OrderCert {
+                domain,
+                hosts,
+                validity,
+                ca,
+                validation,
+                cloudflare_branding,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let zone = client.get_zone(&zone_id).await?.name.to_lowercase();
+                // 证书包必须包含根域名
+                let mut unique = vec![zone.clone()];
+                for host in hosts.iter().map(|h| h.trim().trim_end_matches('.').to_lowercase()) {
+                    if host != zone && !host.ends_with(&format!(".{}", zone)) {
+                        anyhow::bail!(ValidationError(format!("{} 不属于域名 {}", host, zone)));
+                    }
+                    if !unique.contains(&host) {
+                        unique.push(host);
+                    }
+                }
+                let order = CertificatePackOrder {
+                    pack_type: "advanced".to_string(),
+                    hosts: unique,
+                    validation_method: validation.to_lowercase(),
+                    validity_days: *validity,
+                    certificate_authority: ca.clone(),
+                    cloudflare_branding: cloudflare_branding.then_some(true),
+                };
+                order.validate()?;
+
+                let pack = client.order_certificate_pack(&zone_id, &order).await?;
+                if format == "json" {
+                    output::print_json(&pack);
+                    return Ok(());
+                }
+                output::success("证书包已提交订购，验证通过后自动签发");
+                print_certificate_pack(&pack);
+                output::tip(&format!("使用 `cfai ssl list {}` 查看验证状态", domain));
+            }
+
+            SslCommands::CaaCheck { domain, fix, yes } => caa_check(client, domain, *fix, *yes, format).await?,
+
+            SslCommands::CustomHostnames { command } => {
+                execute_custom_hostnames(client, command, format).await?;
+            }
         }
 
         Ok(())
     }
 }
+
+async fn execute_custom_hostnames(
+    client: &CfClient,
+    command: &CustomHostnameCommands,
+    format: &str,
+) -> Result<()> {
+    match command {
+        CustomHostnameCommands::List { domain, hostname } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let hostnames = client.list_custom_hostnames(&zone_id, hostname.as_deref()).await?;
+
+            if format == "json" {
+                output::print_json(&hostnames);
+                return Ok(());
+            }
+
+            output::title(&format!("自定义主机名 - {} (共 {} 个)", domain, hostnames.len()));
+            if hostnames.is_empty() {
+                output::info("没有自定义主机名");
+                return Ok(());
+            }
+
+            let mut table = output::create_table(vec!["ID", "主机名", "状态", "证书状态", "验证方式", "源站"]);
+            for h in &hostnames {
+                let ssl = h.ssl.as_ref();
+                table.add_row(vec![
+                    h.id.clone().unwrap_or("-".into()),
+                    h.hostname.clone().unwrap_or("-".into()),
+                    output::status_badge(h.status.as_deref().unwrap_or("-")),
+                    ssl.and_then(|s| s.status.clone()).unwrap_or("-".into()),
+                    ssl.and_then(|s| s.method.clone()).unwrap_or("-".into()),
+                    h.custom_origin_server.clone().unwrap_or("默认".into()),
+                ]);
+            }
+            println!("{table}");
+        }
+
+        CustomHostnameCommands::Status { domain, hostname } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let id = resolve_custom_hostname(client, &zone_id, hostname).await?;
+            let custom = client.get_custom_hostname(&zone_id, &id).await?;
+            print_custom_hostname(&custom, format);
+        }
+
+        CustomHostnameCommands::Create {
+            domain,
+            hostname,
+            method,
+            min_tls,
+            origin,
+        } => {
+            check_choice("验证方式", &Some(method.clone()), DCV_METHODS)?;
+            check_choice("TLS 版本", min_tls, &["1.0", "1.1", "1.2", "1.3"])?;
+
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let request = CustomHostnameRequest {
+                hostname: hostname.clone(),
+                ssl: CustomHostnameSslRequest {
+                    method: method.clone(),
+                    cert_type: "dv".to_string(),
+                    settings: CustomHostnameTlsSettings {
+                        min_tls_version: min_tls.clone(),
+                        ..Default::default()
+                    },
+                },
+                custom_origin_server: origin.clone(),
+            };
+            let created = client.create_custom_hostname(&zone_id, &request).await?;
+
+            if format != "json" {
+                output::success(&format!("自定义主机名 {} 已添加", hostname));
+                output::info("客户需将主机名 CNAME 到回源地址，并完成下方的验证");
+            }
+            print_custom_hostname(&created, format);
+        }
+
+        CustomHostnameCommands::Revalidate { domain, hostname } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let id = resolve_custom_hostname(client, &zone_id, hostname).await?;
+            let current = client.get_custom_hostname(&zone_id, &id).await?;
+            let ssl = current.ssl.as_ref();
+            let method = ssl.and_then(|s| s.method.as_deref()).unwrap_or("http");
+            let cert_type = ssl.and_then(|s| s.cert_type.as_deref()).unwrap_or("dv");
+            let updated = client
+                .revalidate_custom_hostname(&zone_id, &id, method, cert_type)
+                .await?;
+
+            if format != "json" {
+                output::success(&format!("已重新触发 {} 的证书验证", hostname));
+            }
+            print_custom_hostname(&updated, format);
+        }
+
+        CustomHostnameCommands::Delete { domain, hostname, yes } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let id = resolve_custom_hostname(client, &zone_id, hostname).await?;
+
+            if !yes {
+                let confirm = prompt::confirm(format!("确定要删除自定义主机名 {} 吗？客户域名将无法再访问", hostname))?;
+                if !confirm {
+                    output::info("已取消");
+                    return Ok(());
+                }
+            }
+
+            client.delete_custom_hostname(&zone_id, &id).await?;
+            output::success(&format!("自定义主机名 {} 已删除", hostname));
+        }
+
+        CustomHostnameCommands::Fallback { domain, origin, delete } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            if *delete {
+                client.delete_fallback_origin(&zone_id).await?;
+                output::success("默认回源地址已删除");
+                return Ok(());
+            }
+
+            let fallback = match origin {
+                Some(origin) => client.set_fallback_origin(&zone_id, origin).await?,
+                None => client.get_fallback_origin(&zone_id).await?,
+            };
+
+            if format == "json" {
+                output::print_json(&fallback);
+                return Ok(());
+            }
+
+            if let Some(origin) = origin {
+                output::success(&format!("默认回源地址已设置为 {}", origin));
+            }
+            output::title(&format!("默认回源地址 - {}", domain));
+            output::kv("回源地址", fallback.origin.as_deref().unwrap_or("-"));
+            output::kv_colored(
+                "状态",
+                fallback.status.as_deref().unwrap_or("-"),
+                fallback.status.as_deref() == Some("active"),
+            );
+            for error in fallback.errors.iter().flatten() {
+                output::warn(error);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 按主机名或 ID 查找自定义主机名，返回其 ID
+async fn resolve_custom_hostname(client: &CfClient, zone_id: &str, hostname: &str) -> Result<String> {
+    if !hostname.contains('.') {
+        return Ok(hostname.to_string());
+    }
+    client
+        .list_custom_hostnames(zone_id, Some(hostname))
+        .await?
+        .into_iter()
+        .find(|h| h.hostname.as_deref() == Some(hostname))
+        .and_then(|h| h.id)
+        .ok_or_else(|| ValidationError(format!("未找到自定义主机名: {}", hostname)).into())
+}
+
+/// 显示自定义主机名的证书与所有权验证信息
+fn print_custom_hostname(custom: &CustomHostname, format: &str) {
+    if format == "json" {
+        output::print_json(custom);
+        return;
+    }
+
+    output::title(&format!("自定义主机名 - {}", custom.hostname.as_deref().unwrap_or("-")));
+    output::kv("ID", custom.id.as_deref().unwrap_or("-"));
+    output::kv_colored(
+        "状态",
+        custom.status.as_deref().unwrap_or("-"),
+        custom.status.as_deref() == Some("active"),
+    );
+    output::kv("源站", custom.custom_origin_server.as_deref().unwrap_or("默认回源地址"));
+
+    if let Some(ssl) = &custom.ssl {
+        output::kv_colored(
+            "证书状态",
+            ssl.status.as_deref().unwrap_or("-"),
+            ssl.status.as_deref() == Some("active"),
+        );
+        output::kv("验证方式", ssl.method.as_deref().unwrap_or("-"));
+        if let Some(expires) = &ssl.expires_on {
+            output::kv("过期时间", expires);
+        }
+        print_validation(&ssl.validation_records, &ssl.validation_errors);
+    }
+
+    if custom.status.as_deref() != Some("active") {
+        if let Some(ov) = &custom.ownership_verification {
+            output::kv(
+                "所有权验证",
+                &format!(
+                    "{} {} → {}",
+                    ov.record_type.as_deref().unwrap_or("TXT"),
+                    ov.name.as_deref().unwrap_or("-"),
+                    ov.value.as_deref().unwrap_or("-")
+                ),
+            );
+        }
+        if let Some(http) = &custom.ownership_verification_http {
+            output::kv(
+                "所有权验证 (HTTP)",
+                &format!(
+                    "{} → {}",
+                    http.http_url.as_deref().unwrap_or("-"),
+                    http.http_body.as_deref().unwrap_or("-")
+                ),
+            );
+        }
+    }
+    for error in custom.verification_errors.iter().flatten() {
+        output::warn(&format!("主机名验证失败: {}", error));
+    }
+}
+
+async fn execute_ciphers(client: &CfClient, command: &CipherCommands, format: &str) -> Result<()> {
+    match command {
+        CipherCommands::List { domain } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            let current = client.get_ciphers(&zone_id).await?;
+            if format == "json" {
+                output::print_json(&serde_json::json!({
+                    "ciphers": current,
+                    "available": CIPHER_SUITES.iter().map(|(c, _)| c).collect::<Vec<_>>(),
+                }));
+                return Ok(());
+            }
+            output::title(&format!("加密套件 - {}", domain));
+            if current.is_empty() {
+                output::info("使用 Cloudflare 默认的加密套件");
+            }
+            let mut table = output::create_table(vec!["加密套件", "推荐", "已允许"]);
+            for (cipher, modern) in CIPHER_SUITES {
+                let allowed = current.is_empty() || current.iter().any(|c| c == cipher);
+                table.add_row(vec![
+                    cipher.to_string(),
+                    if *modern { "✓".green().to_string() } else { String::new() },
+                    if allowed { "✓".to_string() } else { "-".dimmed().to_string() },
+                ]);
+            }
+            println!("{table}");
+            output::tip("TLS 1.3 的加密套件由 Cloudflare 固定，不受此设置影响");
+        }
+        CipherCommands::Set { domain, ciphers } => {
+            let ciphers = parse_ciphers(ciphers)?;
+            let zone_id = resolve_zone_id(client, domain).await?;
+            client.set_ciphers(&zone_id, &ciphers).await?;
+            output::success(&format!("已只允许 {} 个加密套件", ciphers.len()));
+            if ciphers.iter().all(|c| c.contains("ECDSA")) || ciphers.iter().all(|c| !c.contains("ECDSA")) {
+                output::warn("未同时包含 ECDSA 与 RSA 套件，使用另一种证书的客户端可能无法连接");
+            }
+        }
+        CipherCommands::Reset { domain } => {
+            let zone_id = resolve_zone_id(client, domain).await?;
+            client.set_ciphers(&zone_id, &[]).await?;
+            output::success("已恢复 Cloudflare 默认的加密套件");
+        }
+    }
+    Ok(())
+}
+
+/// 证书验证记录与失败原因
+fn print_validation(records: &Option<Vec<ValidationRecord>>, errors: &Option<Vec<DcvError>>) {
+    for record in records.iter().flatten() {
+        if let (Some(name), Some(value)) = (&record.txt_name, &record.txt_value) {
+            output::kv("TXT 验证", &format!("{} → {}", name, value));
+        }
+        if let (Some(url), Some(body)) = (&record.http_url, &record.http_body) {
+            output::kv("HTTP 验证", &format!("{} → {}", url, body));
+        }
+        if let Some(emails) = &record.emails {
+            output::kv("邮件验证", &emails.join(", "));
+        }
+    }
+    for error in errors.iter().flatten() {
+        output::warn(&format!("证书验证失败: {}", error.message.as_deref().unwrap_or("-")));
+    }
+}
+
+fn print_certificate_pack(cert: &SslCertificate) {
+    output::kv("ID", cert.id.as_deref().unwrap_or("-"));
+    output::kv("类型", cert.pack_type.as_deref().unwrap_or("-"));
+    output::kv(
+        "主机",
+        &cert
+            .hosts
+            .as_ref()
+            .map(|h| h.join(", "))
+            .unwrap_or("-".into()),
+    );
+    output::kv_colored(
+        "状态",
+        cert.status.as_deref().unwrap_or("-"),
+        cert.status.as_deref() == Some("active"),
+    );
+    if let Some(ca) = &cert.certificate_authority {
+        output::kv("CA", ca);
+    }
+    if let Some(days) = cert.validity_days {
+        output::kv("有效期", &format!("{} 天", days));
+    }
+    if let Some(method) = &cert.validation_method {
+        output::kv("验证方式", method);
+    }
+    if let Some(issuer) = &cert.issuer {
+        output::kv("签发者", issuer);
+    }
+    if let Some(expires) = &cert.expires_on {
+        output::kv("过期时间", expires);
+    }
+    if cert.status.as_deref() != Some("active") {
+        print_validation(&cert.validation_records, &cert.validation_errors);
+    }
+}
+
+/// 检查可选参数是否在允许的取值范围内
+fn check_choice(label: &str, value: &Option<String>, choices: &[&str]) -> Result<()> {
+    match value {
+        Some(v) if !choices.contains(&v.as_str()) => Err(ValidationError(format!(
+            "未知的{}: {} (可选: {})",
+            label,
+            v,
+            choices.join("/")
+        ))
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// 一端 (边缘或源站) 的握手结果
+#[derive(Debug, Default, Serialize)]
+struct Endpoint {
+    address: Option<String>,
+    /// 能否建立 TCP 连接
+    connected: bool,
+    report: Option<TlsReport>,
+    error: Option<String>,
+}
+
+impl Endpoint {
+    async fn probe(addr: std::net::SocketAddr, hostname: &str) -> Self {
+        let mut endpoint = Endpoint { address: Some(addr.to_string()), ..Default::default() };
+        let result = match tls_inspect::connect(addr).await {
+            Ok(tcp) => {
+                endpoint.connected = true;
+                tls_inspect::handshake(tcp, hostname).await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(report) => endpoint.report = Some(report),
+            Err(e) => endpoint.error = Some(format!("{:#}", e)),
+        }
+        endpoint
+    }
+
+    fn failed(error: String) -> Self {
+        Endpoint { error: Some(error), ..Default::default() }
+    }
+}
+
+/// 诊断结论
+#[derive(Debug, Serialize)]
+struct Finding {
+    problem: bool,
+    message: String,
+}
+
+async fn inspect_tls(client: &CfClient, hostname: &str, origin: Option<&str>, port: u16, format: &str) -> Result<()> {
+    let host = hostname
+        .split_once("://")
+        .map_or(hostname, |(_, rest)| rest)
+        .split(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('.')
+        .to_lowercase();
+    if host.is_empty() {
+        anyhow::bail!(ValidationError(format!("无效的主机名: {}", hostname)));
+    }
+
+    let zone = zone_for_host(client, &host).await?;
+    let (ssl_mode, records) = match &zone {
+        Some(zone) => {
+            let mode = client.get_ssl_mode(&zone.id).await.ok();
+            let records: Vec<_> = client
+                .list_all_dns_records(&zone.id)
+                .await?
+                .into_iter()
+                .filter(|r| matches!(r.record_type.as_str(), "A" | "AAAA" | "CNAME") && r.name.eq_ignore_ascii_case(&host))
+                .collect();
+            (mode, records)
+        }
+        None => (None, Vec::new()),
+    };
+    let proxied = records.iter().any(|r| r.proxied == Some(true));
+
+    if format != "json" {
+        output::loading(&format!("正在与 {} 握手", host));
+    }
+    let edge = match first_addr(&host, port).await {
+        Ok(addr) => Endpoint::probe(addr, &host).await,
+        Err(e) => Endpoint::failed(format!("{:#}", e)),
+    };
+
+    // 未代理的记录直接指向源站，边缘结果即源站结果
+    let origin_target = match origin {
+        Some(origin) => Some(origin.to_string()),
+        None if proxied => records
+            .iter()
+            .find(|r| r.record_type != "CNAME")
+            .or_else(|| records.first())
+            .map(|r| r.content.clone()),
+        None => None,
+    };
+    let origin = match &origin_target {
+        Some(target) => Some(match first_addr(target, port).await {
+            Ok(addr) => Endpoint::probe(addr, &host).await,
+            Err(e) => Endpoint::failed(format!("{:#}", e)),
+        }),
+        None => None,
+    };
+
+    let findings = diagnose(ssl_mode.as_deref(), proxied, &edge, origin.as_ref());
+
+    if format == "json" {
+        output::print_json(&serde_json::json!({
+            "hostname": host,
+            "zone": zone.as_ref().map(|z| &z.name),
+            "ssl_mode": ssl_mode,
+            "proxied": proxied,
+            "edge": edge,
+            "origin": origin,
+            "findings": findings,
+        }));
+        return Ok(());
+    }
+
+    output::title(&format!("TLS 检查 - {}", host));
+    output::kv("所属域名", zone.as_ref().map_or("- (不在当前账户中)", |z| z.name.as_str()));
+    output::kv("SSL 模式", ssl_mode.as_deref().unwrap_or("-"));
+    output::kv("代理状态", if proxied { "已代理" } else if records.is_empty() { "-" } else { "仅 DNS" });
+
+    print_endpoint(if proxied { "边缘 (Cloudflare)" } else { "服务器" }, &edge);
+    if let Some(origin) = &origin {
+        print_endpoint("源站", origin);
+    }
+
+    println!("\n{}", "诊断".bold());
+    for finding in &findings {
+        if finding.problem {
+            output::warn(&finding.message);
+        } else {
+            output::info(&finding.message);
+        }
+    }
+    Ok(())
+}
+
+/// 按名称检查区域中的 CAA 记录，`fix` 时为缺少的 CA 创建记录 (没有 CAA 记录时在根域名创建)
+async fn caa_check(client: &CfClient, domain: &str, fix: bool, yes: bool, format: &str) -> Result<()> {
+    let zone_id = resolve_zone_id(client, domain).await?;
+    let zone = client.get_zone(&zone_id).await?.name;
+    let mut by_name: BTreeMap<String, Vec<CaaRecord>> = BTreeMap::new();
+    let mut invalid = Vec::new();
+    for record in client.list_all_dns_records(&zone_id).await?.into_iter().filter(|r| r.record_type == "CAA") {
+        match CaaRecord::parse(&record.content) {
+            Some(caa) => by_name.entry(record.name.clone()).or_default().push(caa),
+            None => invalid.push(format!("{} {}", record.name, record.content)),
+        }
+    }
+    let checks: Vec<CaaCheck> = by_name.into_iter().map(|(name, records)| check_caa(&name, records)).collect();
+
+    let request = |name: &str, tag: &str, ca: &str| -> Result<DnsRecordRequest> {
+        Ok(DnsRecordRequest {
+            record_type: "CAA".to_string(),
+            name: name.to_string(),
+            content: String::new(),
+            ttl: Some(1),
+            proxied: None,
+            priority: None,
+            comment: Some(managed_comment(None)),
+            tags: None,
+            data: Some(caa_data(0, tag, ca)?),
+        })
+    };
+    let mut fixes = Vec::new();
+    if checks.is_empty() {
+        for (ca, _) in CLOUDFLARE_CAS {
+            fixes.push(request(&zone, "issue", ca)?);
+        }
+    }
+    for check in &checks {
+        for ca in &check.missing_issue {
+            fixes.push(request(&check.name, "issue", ca)?);
+        }
+        for ca in &check.missing_issuewild {
+            fixes.push(request(&check.name, "issuewild", ca)?);
+        }
+    }
+
+    if format == "json" {
+        output::print_json(&serde_json::json!({
+            "zone": zone,
+            "checks": checks,
+            "invalid": invalid,
+            "fixes": fixes,
+        }));
+    } else {
+        print_caa_checks(&zone, &checks, &invalid);
+    }
+    if fixes.is_empty() || (format == "json" && !fix) {
+        return Ok(());
+    }
+
+    if format != "json" {
+        println!("\n{}", "建议添加的记录".bold());
+        for f in &fixes {
+            let data = f.data.as_ref().map(|d| {
+                format!("0 {} \"{}\"", d["tag"].as_str().unwrap_or_default(), d["value"].as_str().unwrap_or_default())
+            });
+            println!("  {} CAA {}", f.name, data.unwrap_or_default());
+        }
+    }
+    if !fix {
+        output::tip("加 --fix 创建以上 CAA 记录");
+        return Ok(());
+    }
+    if !yes && !prompt::confirm(format!("确定要创建 {} 条 CAA 记录吗？", fixes.len()))? {
+        output::info("已取消");
+        return Ok(());
+    }
+    for f in &fixes {
+        client
+            .create_dns_record(&zone_id, f)
+            .await
+            .with_context(|| format!("创建 {} 的 CAA 记录失败", f.name))?;
+    }
+    output::success(&format!("已创建 {} 条 CAA 记录", fixes.len()));
+    Ok(())
+}
+
+/// CA 的名称 (如 pki.goog → Google Trust Services)
+fn ca_name(ca: &str) -> &str {
+    CLOUDFLARE_CAS.iter().find(|(c, _)| *c == ca).map_or(ca, |(_, name)| name)
+}
+
+fn print_caa_checks(zone: &str, checks: &[CaaCheck], invalid: &[String]) {
+    output::title(&format!("CAA 检查 - {}", zone));
+    for record in invalid {
+        output::error(&format!("无法解析的 CAA 记录: {}", record));
+    }
+    if checks.is_empty() {
+        output::info("未设置 CAA 记录，任何 CA 都可以签发证书");
+        output::tip("添加 CAA 记录可限制签发证书的 CA，同时需要允许 Cloudflare 使用的 CA");
+        return;
+    }
+    for check in checks {
+        println!("\n{}", check.name.bold());
+        for r in &check.records {
+            println!("  {} {} \"{}\"", r.flags, r.tag.cyan(), r.value);
+        }
+        if check.ok() {
+            output::success("允许 Cloudflare 使用的 CA 签发证书");
+        }
+        for ca in &check.missing_issue {
+            output::error(&format!("issue 未允许 {} ({})，通用证书签发或续期可能失败", ca_name(ca), ca));
+        }
+        for ca in &check.missing_issuewild {
+            output::error(&format!("issuewild 未允许 {} ({})，通配符证书无法签发", ca_name(ca), ca));
+        }
+        for problem in &check.problems {
+            output::warn(problem);
+        }
+        for note in &check.notes {
+            println!("  {}", note.dimmed());
+        }
+    }
+}
+
+/// 解析主机名或 IP 为连接地址
+async fn first_addr(host: &str, port: u16) -> Result<std::net::SocketAddr> {
+    if let Ok(ip) = host.parse::<std::net::IpAddr>() {
+        return Ok(std::net::SocketAddr::new(ip, port));
+    }
+    tokio::net::lookup_host((host, port))
+        .await
+        .with_context(|| format!("解析 {} 失败", host))?
+        .next()
+        .with_context(|| format!("{} 没有可用的地址", host))
+}
+
+fn print_endpoint(label: &str, endpoint: &Endpoint) {
+    println!("\n{} {}", label.bold(), endpoint.address.as_deref().unwrap_or_default().dimmed());
+    let Some(report) = &endpoint.report else {
+        output::kv_colored("握手", endpoint.error.as_deref().unwrap_or("失败"), false);
+        return;
+    };
+    output::kv("协议", &report.protocol);
+    output::kv("加密套件", &report.cipher_suite);
+    output::kv("ALPN", report.alpn.as_deref().unwrap_or("-"));
+    match &report.verify_error {
+        None => output::kv_colored("证书校验", "通过", true),
+        Some(error) => output::kv_colored("证书校验", error, false),
+    }
+    for (i, cert) in report.chain.iter().enumerate() {
+        let days = if cert.days_left < 0 {
+            "已过期".red().to_string()
+        } else if cert.days_left < 14 {
+            format!("剩余 {} 天", cert.days_left).yellow().to_string()
+        } else {
+            format!("剩余 {} 天", cert.days_left)
+        };
+        println!("  {} {}", format!("[{}]", i).dimmed(), cert.subject);
+        println!("      签发者: {}", cert.issuer);
+        println!("      有效期至: {} ({})", cert.not_after, days);
+    }
+    if let Some(leaf) = report.chain.first() {
+        output::kv("SAN", &leaf.sans.join(", "));
+    }
+}
+
+/// 根据 SSL 模式与两端握手结果给出结论
+fn diagnose(ssl_mode: Option<&str>, proxied: bool, edge: &Endpoint, origin: Option<&Endpoint>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut problem = |message: String| findings.push(Finding { problem: true, message });
+
+    match &edge.report {
+        None => problem(format!("无法完成 TLS 握手: {}", edge.error.as_deref().unwrap_or("-"))),
+        Some(report) => {
+            if let Some(error) = &report.verify_error {
+                problem(format!("{}证书校验失败: {}", if proxied { "边缘" } else { "" }, error));
+            }
+            if let Some(leaf) = report.chain.first().filter(|c| (0..14).contains(&c.days_left)) {
+                problem(format!("证书将在 {} 天后过期", leaf.days_left));
+            }
+        }
+    }
+
+    if let Some(origin) = origin {
+        match ssl_mode {
+            Some("off") => problem("SSL 模式为 off，访问者无法通过 HTTPS 访问".into()),
+            Some("flexible") => {
+                findings.push(Finding {
+                    problem: false,
+                    message: "SSL 模式为 flexible，Cloudflare 以 HTTP 回源，源站 443 端口不参与".into(),
+                });
+            }
+            mode => {
+                let strict = mode == Some("strict");
+                match &origin.report {
+                    None if !origin.connected => problem(format!(
+                        "源站无法连接，Cloudflare 将返回 521/522: {}",
+                        origin.error.as_deref().unwrap_or("-")
+                    )),
+                    None => problem(format!(
+                        "源站 TLS 握手失败，Cloudflare 将返回 525: {}",
+                        origin.error.as_deref().unwrap_or("-")
+                    )),
+                    Some(report) if strict && !report.strict_ok => problem(format!(
+                        "源站证书无法通过 Full (strict) 校验，Cloudflare 将返回 526: {}",
+                        report.verify_error.as_deref().unwrap_or("-")
+                    )),
+                    Some(report) if !report.trusted && !report.strict_ok => findings.push(Finding {
+                        problem: false,
+                        message: "源站证书不受信任，当前模式不校验源站证书；切换到 strict 前需更换证书".into(),
+                    }),
+                    Some(_) => {}
+                }
+            }
+        }
+    } else if proxied {
+        findings.push(Finding {
+            problem: false,
+            message: "无法确定源站地址，可使用 --origin 指定".into(),
+        });
+    }
+
+    if !findings.iter().any(|f| f.problem) {
+        findings.push(Finding { problem: false, message: "未发现问题".into() });
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(strict_ok: bool) -> TlsReport {
+        TlsReport {
+            address: "192.0.2.1:443".into(),
+            protocol: "TLSv1_3".into(),
+            cipher_suite: "TLS13_AES_128_GCM_SHA256".into(),
+            alpn: None,
+            trusted: false,
+            verify_error: Some("证书链不受信任".into()),
+            strict_ok,
+            chain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diagnose_origin() {
+        let trusted = TlsReport { trusted: true, verify_error: None, ..report(true) };
+        let edge = Endpoint { connected: true, report: Some(trusted), ..Default::default() };
+        let problems = |mode, origin: &Endpoint| {
+            diagnose(Some(mode), true, &edge, Some(origin))
+                .into_iter()
+                .filter(|f| f.problem)
+                .map(|f| f.message)
+                .collect::<Vec<_>>()
+        };
+
+        let refused = Endpoint { error: Some("连接失败".into()), ..Default::default() };
+        assert!(problems("full", &refused)[0].contains("521"));
+        let handshake = Endpoint { connected: true, error: Some("握手失败".into()), ..Default::default() };
+        assert!(problems("strict", &handshake)[0].contains("525"));
+        let self_signed = Endpoint { connected: true, report: Some(report(false)), ..Default::default() };
+        assert!(problems("strict", &self_signed)[0].contains("526"));
+        assert!(problems("full", &self_signed).is_empty());
+        let origin_ca = Endpoint { connected: true, report: Some(report(true)), ..Default::default() };
+        assert!(problems("strict", &origin_ca).is_empty());
+        assert!(problems("flexible", &refused).is_empty());
+    }
+}
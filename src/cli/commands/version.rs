@@ -0,0 +1,140 @@
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+
+use crate::api::client::CfClient;
+use crate::api::deprecation::{DeprecationLog, KNOWN_SUNSETS};
+use crate::cli::output;
+
+#[derive(Args, Debug)]
+pub struct VersionArgs {
+    /// 检查 Cloudflare API 连通性，并列出最近收到的接口弃用通知
+    #[arg(long)]
+    pub check_api: bool,
+}
+
+impl VersionArgs {
+    pub async fn execute(&self, client: Option<&CfClient>, format: &str) -> Result<()> {
+        let version = env!("CARGO_PKG_VERSION");
+        let target = format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS);
+        let mut features = Vec::new();
+        if cfg!(feature = "gui") {
+            features.push("gui");
+        }
+        if cfg!(feature = "tui") {
+            features.push("tui");
+        }
+        if cfg!(feature = "serve") {
+            features.push("serve");
+        }
+
+        let client = match client {
+            Some(c) if self.check_api => c,
+            _ => {
+                if format == "json" {
+                    output::print_json(&serde_json::json!({
+                        "version": version,
+                        "target": target,
+                        "features": features,
+                    }));
+                    return Ok(());
+                }
+                print_build_info(version, &target, &features);
+                return Ok(());
+            }
+        };
+
+        // 实时请求一次 API，顺带记录可能返回的弃用响应头
+        let started = std::time::Instant::now();
+        let api_check = client.verify_token().await;
+        let latency_ms = started.elapsed().as_millis();
+
+        let log = DeprecationLog::load()?;
+        let recent = log.recent();
+
+        if format == "json" {
+            output::print_json(&serde_json::json!({
+                "version": version,
+                "target": target,
+                "features": features,
+                "api": {
+                    "ok": api_check.as_ref().map(|v| *v).unwrap_or(false),
+                    "error": api_check.as_ref().err().map(|e| format!("{:#}", e)),
+                    "latency_ms": latency_ms,
+                },
+                "deprecations": recent,
+                "known_sunsets": KNOWN_SUNSETS.iter().map(|s| serde_json::json!({
+                    "endpoint": s.endpoint,
+                    "feature": s.feature,
+                    "sunset": s.sunset,
+                    "replacement": s.replacement,
+                })).collect::<Vec<_>>(),
+            }));
+            return Ok(());
+        }
+
+        print_build_info(version, &target, &features);
+
+        output::title("API 兼容性检查");
+        match &api_check {
+            Ok(true) => output::kv_colored("API 连通性", &format!("正常 ({} ms)", latency_ms), true),
+            Ok(false) => output::kv_colored("API 连通性", "Token 无效", false),
+            Err(e) => output::kv_colored("API 连通性", &format!("{:#}", e), false),
+        }
+
+        println!();
+        if recent.is_empty() {
+            output::success("最近未收到 Cloudflare API 弃用通知");
+        } else {
+            output::warn(&format!("最近收到 {} 条 API 弃用通知:", recent.len()));
+            let mut table = output::create_table(vec!["接口", "Deprecation", "Sunset", "最后出现", "说明"]);
+            for notice in &recent {
+                table.add_row(vec![
+                    notice.endpoint.as_str(),
+                    notice.deprecation.as_deref().unwrap_or("-"),
+                    notice.sunset.as_deref().unwrap_or("-"),
+                    notice.last_seen.get(..10).unwrap_or(&notice.last_seen),
+                    notice.link.as_deref().unwrap_or("-"),
+                ]);
+            }
+            println!("{table}");
+        }
+
+        println!();
+        output::warn("以下功能依赖已宣布下线的接口:");
+        for sunset in KNOWN_SUNSETS {
+            let seen = recent.iter().any(|n| n.endpoint.starts_with(sunset.endpoint));
+            println!(
+                "  {} {} {}{}",
+                "•".yellow(),
+                sunset.feature.bold(),
+                format!("({})", sunset.endpoint).dimmed(),
+                if seen { " ⚠️ 已收到弃用响应头".red().to_string() } else { String::new() }
+            );
+            println!(
+                "      下线时间: {}  替代方案: {}",
+                sunset.sunset.unwrap_or("已弃用，未公布日期"),
+                sunset.replacement
+            );
+        }
+        println!(
+            "\n{}",
+            format!("弃用记录文件: {}", DeprecationLog::path()?.display()).dimmed()
+        );
+
+        Ok(())
+    }
+}
+
+fn print_build_info(version: &str, target: &str, features: &[&str]) {
+    output::title(&format!("CFAI v{}", version));
+    output::kv("构建目标", target);
+    output::kv(
+        "启用特性",
+        &if features.is_empty() {
+            "(无，仅 CLI)".to_string()
+        } else {
+            features.join(", ")
+        },
+    );
+}
@@ -1,9 +1,20 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use clap::{Args, Subcommand};
+use colored::Colorize;
+use futures_util::StreamExt;
+use tokio_tungstenite::tungstenite::Message;
 
 use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::api::workers::connect_worker_tail;
+use crate::cli::commands::analytics::sparkline;
 use crate::cli::output;
+use crate::cli::prompt;
 use crate::config::settings::AppConfig;
+use crate::models::analytics::parse_time;
+use crate::models::workers::{CreateQueueRequest, QueueBinding, TailEvent, WorkerStats};
 
 #[derive(Args, Debug)]
 pub struct WorkersArgs {
@@ -38,6 +49,74 @@ pub enum WorkersCommands {
 
     /// 列出 Workers 自定义域名
     Domains,
+
+    /// 管理 Queues 队列
+    #[command(alias = "queue")]
+    Queues {
+        #[command(subcommand)]
+        command: QueueCommands,
+    },
+
+    /// 查看 Durable Objects
+    #[command(name = "do", alias = "durable-objects")]
+    DurableObjects {
+        #[command(subcommand)]
+        command: DurableObjectCommands,
+    },
+
+    /// 实时查看 Workers 日志与异常 (Ctrl+C 退出)
+    Tail {
+        /// 脚本名称
+        script: String,
+        /// 按调用结果过滤
+        #[arg(long, value_parser = ["ok", "error", "canceled"])]
+        status: Option<String>,
+        /// 按 HTTP 方法过滤 (可重复指定)
+        #[arg(long)]
+        method: Vec<String>,
+    },
+
+    /// 查看 Workers 调用统计 (请求数、错误率、CPU 时间分位数、子请求)
+    Stats {
+        /// 脚本名称
+        script: String,
+        /// 起始时间 (如 1h、24h、7d，或 ISO8601)
+        #[arg(short, long, default_value = "24h", allow_hyphen_values = true)]
+        since: String,
+        /// 结束时间 (默认现在)
+        #[arg(short, long, default_value = "0", allow_hyphen_values = true)]
+        until: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum QueueCommands {
+    /// 列出队列
+    #[command(alias = "ls")]
+    List,
+
+    /// 创建队列
+    Create {
+        /// 队列名称
+        name: String,
+    },
+
+    /// 删除队列
+    #[command(alias = "rm")]
+    Delete {
+        /// 队列名称或 ID
+        queue: String,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DurableObjectCommands {
+    /// 列出 Durable Object 命名空间
+    #[command(alias = "ls")]
+    List,
 }
 
 impl WorkersArgs {
@@ -83,10 +162,7 @@ impl WorkersArgs {
 
             WorkersCommands::Delete { name, yes } => {
                 if !yes {
-                    let confirm = dialoguer::Confirm::new()
-                        .with_prompt(format!("确定要删除 Worker {} 吗？", name))
-                        .default(false)
-                        .interact()?;
+                    let confirm = prompt::confirm(format!("确定要删除 Worker {} 吗？", name))?;
                     if !confirm {
                         output::info("已取消");
                         return Ok(());
@@ -162,8 +238,366 @@ impl WorkersArgs {
                 }
                 println!("{table}");
             }
+
+            WorkersCommands::Queues { command } => match command {
+                QueueCommands::List => {
+                    let queues = client.list_queues(account_id).await?;
+
+                    if format == "json" {
+                        output::print_json(&queues);
+                        return Ok(());
+                    }
+
+                    output::title(&format!("Queues 队列 (共 {} 个)", queues.len()));
+
+                    if queues.is_empty() {
+                        output::info("没有队列");
+                        return Ok(());
+                    }
+
+                    let mut table =
+                        output::create_table(vec!["ID", "名称", "生产者", "消费者", "创建时间"]);
+                    for q in &queues {
+                        table.add_row(vec![
+                            q.queue_id.clone().unwrap_or("-".into()),
+                            q.queue_name.clone(),
+                            binding_scripts(&q.producers, q.producers_total_count),
+                            binding_scripts(&q.consumers, q.consumers_total_count),
+                            q.created_on.clone().unwrap_or("-".into()),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+
+                QueueCommands::Create { name } => {
+                    let request = CreateQueueRequest {
+                        queue_name: name.clone(),
+                    };
+                    let queue = client.create_queue(account_id, &request).await?;
+
+                    if format == "json" {
+                        output::print_json(&queue);
+                        return Ok(());
+                    }
+                    output::success(&format!("队列 {} 已创建", queue.queue_name));
+                    if let Some(id) = &queue.queue_id {
+                        output::kv("ID", id);
+                    }
+                }
+
+                QueueCommands::Delete { queue, yes } => {
+                    let queues = client.list_queues(account_id).await?;
+                    let target = queues
+                        .iter()
+                        .find(|q| q.queue_id.as_deref() == Some(queue) || q.queue_name == *queue)
+                        .ok_or_else(|| anyhow::anyhow!("未找到队列: {}", queue))?;
+                    let queue_id = target.queue_id.as_deref().unwrap_or_default();
+
+                    if !yes {
+                        let confirm = prompt::confirm(format!(
+                            "确定要删除队列 {} 吗？未消费的消息将丢失",
+                            target.queue_name.yellow()
+                        ))?;
+                        if !confirm {
+                            output::info("已取消");
+                            return Ok(());
+                        }
+                    }
+
+                    client.delete_queue(account_id, queue_id).await?;
+                    output::success(&format!("队列 {} 已删除", target.queue_name));
+                }
+            },
+
+            WorkersCommands::DurableObjects { command } => match command {
+                DurableObjectCommands::List => {
+                    let namespaces = client.list_durable_object_namespaces(account_id).await?;
+
+                    if format == "json" {
+                        output::print_json(&namespaces);
+                        return Ok(());
+                    }
+
+                    output::title(&format!("Durable Object 命名空间 (共 {} 个)", namespaces.len()));
+
+                    if namespaces.is_empty() {
+                        output::info("没有 Durable Object 命名空间");
+                        return Ok(());
+                    }
+
+                    let mut table = output::create_table(vec!["ID", "名称", "脚本", "类", "存储"]);
+                    for ns in &namespaces {
+                        table.add_row(vec![
+                            ns.id.as_deref().unwrap_or("-"),
+                            ns.name.as_deref().unwrap_or("-"),
+                            ns.script.as_deref().unwrap_or("-"),
+                            ns.class.as_deref().unwrap_or("-"),
+                            match ns.use_sqlite {
+                                Some(true) => "SQLite",
+                                Some(false) => "KV",
+                                None => "-",
+                            },
+                        ]);
+                    }
+                    println!("{table}");
+                }
+            },
+
+            WorkersCommands::Tail {
+                script,
+                status,
+                method,
+            } => {
+                tail_worker(client, account_id, script, status.as_deref(), method, format).await?;
+            }
+
+            WorkersCommands::Stats { script, since, until } => {
+                let now = chrono::Utc::now();
+                let since = parse_time(since, now)?;
+                let until = parse_time(until, now)?;
+                if since >= until {
+                    anyhow::bail!(ValidationError("起始时间必须早于结束时间".into()));
+                }
+                let stats = client.get_worker_stats(account_id, script, since, until).await?;
+
+                if format == "json" {
+                    output::print_json(&stats);
+                    return Ok(());
+                }
+                print_worker_stats(&stats, since);
+            }
         }
 
         Ok(())
     }
 }
+
+/// 生产者 / 消费者脚本列表，列表为空时显示总数
+fn binding_scripts(bindings: &[QueueBinding], total: Option<u32>) -> String {
+    let scripts: Vec<&str> = bindings.iter().filter_map(|b| b.script()).collect();
+    if scripts.is_empty() {
+        total.map(|t| t.to_string()).unwrap_or("-".into())
+    } else {
+        scripts.join(", ")
+    }
+}
+
+/// 服务端过滤条件 (客户端仍会再过滤一次，兼容忽略过滤器的旧会话)
+fn print_worker_stats(stats: &WorkerStats, since: chrono::DateTime<chrono::Utc>) {
+    output::title(&format!(
+        "Workers 调用统计 - {} ({} 起)",
+        stats.script,
+        since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+    ));
+    if stats.requests == 0 {
+        output::info("该时间范围内没有调用");
+        return;
+    }
+
+    output::kv("调用次数", &output::format_number(stats.requests));
+    output::kv_colored(
+        "错误",
+        &format!("{} ({:.2}%)", output::format_number(stats.errors), stats.error_rate()),
+        stats.errors == 0,
+    );
+    output::kv("子请求", &output::format_number(stats.subrequests));
+    let cpu = |us: Option<f64>| us.map(|us| format!("{:.2} ms", us / 1000.0)).unwrap_or_else(|| "-".into());
+    output::kv(
+        "CPU 时间",
+        &format!(
+            "P50 {} / P90 {} / P99 {}",
+            cpu(stats.cpu_time_p50),
+            cpu(stats.cpu_time_p90),
+            cpu(stats.cpu_time_p99)
+        ),
+    );
+
+    if stats.timeseries.len() > 1 {
+        let requests: Vec<u64> = stats.timeseries.iter().map(|p| p.requests).collect();
+        let errors: Vec<u64> = stats.timeseries.iter().map(|p| p.errors).collect();
+        println!();
+        println!("  {} {}", "调用 (每小时)".dimmed(), sparkline(&requests).cyan());
+        println!("  {} {}", "错误 (每小时)".dimmed(), sparkline(&errors).red());
+        if let Some(peak) = stats.timeseries.iter().filter(|p| p.errors > 0).max_by_key(|p| p.errors) {
+            output::kv("错误最多的时段", &format!("{} ({} 次)", peak.time, peak.errors));
+        }
+    }
+}
+
+fn tail_filters(status: Option<&str>, methods: &[String]) -> serde_json::Value {
+    let mut filters = Vec::new();
+    match status {
+        Some("ok") => filters.push(serde_json::json!({ "outcome": ["ok"] })),
+        Some("canceled") => filters.push(serde_json::json!({ "outcome": ["canceled"] })),
+        Some("error") => filters.push(serde_json::json!({
+            "outcome": ["exception", "exceededCpu", "exceededMemory", "unknown"]
+        })),
+        _ => {}
+    }
+    if !methods.is_empty() {
+        let methods: Vec<String> = methods.iter().map(|m| m.to_uppercase()).collect();
+        filters.push(serde_json::json!({ "method": methods }));
+    }
+    serde_json::Value::Array(filters)
+}
+
+fn matches_filters(event: &TailEvent, status: Option<&str>, methods: &[String]) -> bool {
+    let status_ok = match status {
+        Some("error") => event.is_error(),
+        Some("ok") => !event.is_error() && event.outcome.as_deref() != Some("canceled"),
+        Some("canceled") => event.outcome.as_deref() == Some("canceled"),
+        _ => true,
+    };
+    let method_ok = methods.is_empty()
+        || event
+            .request()
+            .and_then(|r| r.method.as_deref())
+            .is_some_and(|m| methods.iter().any(|f| f.eq_ignore_ascii_case(m)));
+    status_ok && method_ok
+}
+
+/// 持续接收 tail 事件，会话过期或连接断开时自动创建新会话
+async fn tail_worker(
+    client: &CfClient,
+    account_id: &str,
+    script: &str,
+    status: Option<&str>,
+    methods: &[String],
+    format: &str,
+) -> Result<()> {
+    let filters = tail_filters(status, methods);
+    let mut first = true;
+
+    loop {
+        let tail = client.create_worker_tail(account_id, script).await?;
+        let mut stream = match connect_worker_tail(&tail, filters.clone()).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                let _ = client.delete_worker_tail(account_id, script, &tail.id).await;
+                return Err(e);
+            }
+        };
+        if first {
+            output::info(&format!("正在实时查看 Worker {} 的日志，按 Ctrl+C 退出", script));
+            first = false;
+        } else {
+            output::info("tail 会话已重新连接");
+        }
+
+        // 在会话到期前主动重连，避免服务端关闭连接时丢失事件
+        let expires_in = tail
+            .expires_at
+            .as_deref()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .and_then(|t| (t.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok())
+            .map(|d| d.saturating_sub(Duration::from_secs(30)))
+            .unwrap_or(Duration::from_secs(3600));
+        let expiry = tokio::time::sleep(expires_in);
+        tokio::pin!(expiry);
+
+        let stop = loop {
+            tokio::select! {
+                message = stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => print_tail_message(text.as_bytes(), status, methods, format),
+                    Some(Ok(Message::Binary(data))) => print_tail_message(&data, status, methods, format),
+                    Some(Ok(Message::Close(_))) | None => break false,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        output::warn(&format!("tail 连接中断: {}", e));
+                        break false;
+                    }
+                },
+                _ = &mut expiry => break false,
+                _ = tokio::signal::ctrl_c() => break true,
+            }
+        };
+
+        let _ = stream.close(None).await;
+        let _ = client.delete_worker_tail(account_id, script, &tail.id).await;
+        if stop {
+            return Ok(());
+        }
+        output::info("tail 会话已结束，正在重新连接...");
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+fn print_tail_message(data: &[u8], status: Option<&str>, methods: &[String], format: &str) {
+    let event: TailEvent = match serde_json::from_slice(data) {
+        Ok(event) => event,
+        Err(e) => {
+            output::warn(&format!("无法解析 tail 事件: {}", e));
+            return;
+        }
+    };
+    if !matches_filters(&event, status, methods) {
+        return;
+    }
+
+    if format == "json" {
+        // 每个事件输出一行 JSON，便于管道处理
+        if let Ok(line) = serde_json::to_string(&event) {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    let time = event
+        .event_timestamp
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".into());
+    let outcome = event.outcome.as_deref().unwrap_or("unknown");
+    let outcome = if event.is_error() { outcome.red().bold() } else { outcome.green() };
+
+    let trigger = match (event.request(), event.event.as_ref().and_then(|e| e.cron.as_deref())) {
+        (Some(req), _) => {
+            let status = match event.status() {
+                Some(code) if code >= 500 => code.to_string().red().to_string(),
+                Some(code) if code >= 400 => code.to_string().yellow().to_string(),
+                Some(code) => code.to_string().green().to_string(),
+                None => "-".dimmed().to_string(),
+            };
+            format!(
+                "{} {} {}",
+                req.method.as_deref().unwrap_or("-").bold(),
+                req.url.as_deref().unwrap_or("-"),
+                status
+            )
+        }
+        (None, Some(cron)) => format!("{} {}", "cron".bold(), cron),
+        (None, None) => "-".into(),
+    };
+    println!("{} [{}] {}", time.dimmed(), outcome, trigger);
+
+    for log in &event.logs {
+        let message = log
+            .message
+            .iter()
+            .map(|v| match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let level = log.level.as_deref().unwrap_or("log");
+        let level = match level {
+            "error" => level.red(),
+            "warn" => level.yellow(),
+            "debug" => level.dimmed(),
+            _ => level.cyan(),
+        };
+        println!("    {} {}", level, message);
+    }
+    for ex in &event.exceptions {
+        println!(
+            "    {} {}: {}",
+            "✗".red(),
+            ex.name.as_deref().unwrap_or("Error").red().bold(),
+            ex.message.as_deref().unwrap_or("")
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::commands::zone::resolve_zone_id;
+use crate::cli::output;
+use crate::models::zaraz::ZarazTool;
+
+#[derive(Args, Debug)]
+pub struct ZarazArgs {
+    #[command(subcommand)]
+    pub command: ZarazCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZarazCommands {
+    /// 查看 Zaraz 状态 (是否自动注入、发布方式、工具数量)
+    Status {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 列出已配置的第三方工具
+    #[command(alias = "ls")]
+    Tools {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 开启 Zaraz (自动向页面注入 Zaraz 脚本)
+    Enable {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 关闭 Zaraz (不再注入脚本，所有工具停止加载)
+    Disable {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 启用/停用单个工具
+    Tool {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 工具 ID (见 `zaraz tools`)
+        tool_id: String,
+        /// on/off
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+    },
+}
+
+impl ZarazArgs {
+    pub async fn execute(&self, client: &CfClient, format: &str) -> Result<()> {
+        match &self.command {
+            ZarazCommands::Status { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let config = client.get_zaraz_config(&zone_id).await?;
+                let workflow = client.get_zaraz_workflow(&zone_id).await.ok();
+                let tools = ZarazTool::list(&config);
+                let enabled = auto_inject(&config);
+
+                if format == "json" {
+                    output::print_json(&serde_json::json!({
+                        "enabled": enabled,
+                        "workflow": workflow,
+                        "tools": tools.len(),
+                        "tools_enabled": tools.iter().filter(|t| t.enabled).count(),
+                    }));
+                    return Ok(());
+                }
+
+                output::title(&format!("Zaraz - {}", domain));
+                output::kv_colored("自动注入", if enabled { "开启" } else { "关闭" }, enabled);
+                output::kv("发布方式", workflow.as_deref().unwrap_or("-"));
+                output::kv(
+                    "工具",
+                    &format!(
+                        "{} 个 (启用 {} 个)",
+                        tools.len(),
+                        tools.iter().filter(|t| t.enabled).count()
+                    ),
+                );
+            }
+
+            ZarazCommands::Tools { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let config = client.get_zaraz_config(&zone_id).await?;
+                let tools = ZarazTool::list(&config);
+
+                if format == "json" {
+                    output::print_json(&tools);
+                    return Ok(());
+                }
+
+                output::title(&format!("Zaraz 工具 - {} (共 {} 个)", domain, tools.len()));
+                if tools.is_empty() {
+                    output::info("没有配置第三方工具");
+                    return Ok(());
+                }
+                let mut table = output::create_table(vec!["ID", "名称", "组件", "状态"]);
+                for tool in &tools {
+                    table.add_row(vec![
+                        tool.id.as_str(),
+                        tool.name.as_str(),
+                        tool.component.as_deref().or(tool.tool_type.as_deref()).unwrap_or("-"),
+                        &output::status_badge(if tool.enabled { "on" } else { "off" }),
+                    ]);
+                }
+                println!("{table}");
+                if !auto_inject(&config) {
+                    output::warn("Zaraz 当前未开启，工具不会加载 (运行 `cfai zaraz enable` 开启)");
+                }
+            }
+
+            ZarazCommands::Enable { domain } | ZarazCommands::Disable { domain } => {
+                let enable = matches!(self.command, ZarazCommands::Enable { .. });
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let mut config = client.get_zaraz_config(&zone_id).await?;
+                let settings = config
+                    .get_mut("settings")
+                    .and_then(|s| s.as_object_mut())
+                    .ok_or_else(|| anyhow::anyhow!("Zaraz 配置缺少 settings 字段"))?;
+                settings.insert("autoInjectScript".into(), serde_json::json!(enable));
+
+                client.update_zaraz_config(&zone_id, &config).await?;
+                output::success(&format!("Zaraz 已{}", if enable { "开启" } else { "关闭" }));
+                note_workflow(client, &zone_id).await;
+            }
+
+            ZarazCommands::Tool {
+                domain,
+                tool_id,
+                state,
+            } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let mut config = client.get_zaraz_config(&zone_id).await?;
+                let tool = config
+                    .get_mut("tools")
+                    .and_then(|t| t.get_mut(tool_id.as_str()))
+                    .and_then(|t| t.as_object_mut())
+                    .ok_or_else(|| ValidationError(format!("未找到 Zaraz 工具: {}", tool_id)))?;
+                tool.insert("enabled".into(), serde_json::json!(state == "on"));
+                let name = tool.get("name").and_then(|n| n.as_str()).unwrap_or(tool_id).to_string();
+
+                client.update_zaraz_config(&zone_id, &config).await?;
+                output::success(&format!(
+                    "工具 {} 已{}",
+                    name,
+                    if state == "on" { "启用" } else { "停用" }
+                ));
+                note_workflow(client, &zone_id).await;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 是否自动向页面注入 Zaraz 脚本 (即 Zaraz 是否生效)
+fn auto_inject(config: &serde_json::Value) -> bool {
+    config["settings"]["autoInjectScript"].as_bool().unwrap_or(false)
+}
+
+/// 预览发布模式下修改不会立即生效，提醒用户发布
+async fn note_workflow(client: &CfClient, zone_id: &str) {
+    if client.get_zaraz_workflow(zone_id).await.ok().as_deref() == Some("preview") {
+        output::warn("Zaraz 使用预览发布模式，需在控制台发布后才会生效");
+    }
+}
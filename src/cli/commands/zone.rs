@@ -1,9 +1,15 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use colored::Colorize;
+use dialoguer::theme::Theme;
+use dialoguer::{FuzzySelect, Input};
 
+use crate::api::backup::plan_restore;
 use crate::api::client::CfClient;
+use crate::cli::commands::backup;
 use crate::cli::output;
+use crate::cli::prompt;
+use crate::config::zone_cache;
 use crate::models::zone::*;
 
 #[derive(Args, Debug)]
@@ -93,6 +99,62 @@ pub enum ZoneCommands {
         /// 设置值
         value: String,
     },
+
+    /// 查看套餐配额及用量
+    Limits {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// Zone Hold: 防止该域名被其他账户添加
+    Hold {
+        #[command(subcommand)]
+        command: HoldCommands,
+    },
+
+    /// 对比两个域名的配置 (DNS、设置、SSL、页面规则)
+    Diff {
+        /// 源域名 (作为基准)
+        zone_a: String,
+        /// 目标域名
+        zone_b: String,
+        /// 将目标域名同步为与源域名一致
+        #[arg(long)]
+        apply: bool,
+        /// 同步时允许修改或删除不是由 cfai 创建的 DNS 记录
+        #[arg(long, requires = "apply")]
+        force: bool,
+        /// 跳过确认
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HoldCommands {
+    /// 查看 Zone Hold 状态
+    Status {
+        /// 域名或 Zone ID
+        domain: String,
+    },
+
+    /// 开启 Zone Hold
+    Enable {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 同时阻止其他账户添加子域名 (如 shop.example.com)
+        #[arg(long)]
+        include_subdomains: bool,
+    },
+
+    /// 解除 Zone Hold
+    Disable {
+        /// 域名或 Zone ID
+        domain: String,
+        /// 只临时解除指定小时数，到期后自动恢复保护
+        #[arg(long)]
+        hours: Option<u32>,
+    },
 }
 
 impl ZoneArgs {
@@ -225,11 +287,15 @@ impl ZoneArgs {
             ZoneCommands::Delete { domain, yes } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
 
+                if client.get_zone_hold(&zone_id).await.is_ok_and(|h| h.hold == Some(true)) {
+                    output::warn(&format!(
+                        "{} 已开启 Zone Hold，删除后保护随之失效，其他账户将可以添加该域名",
+                        domain
+                    ));
+                }
+
                 if !yes {
-                    let confirm = dialoguer::Confirm::new()
-                        .with_prompt(format!("确定要删除域名 {} 吗？此操作不可逆！", domain.red()))
-                        .default(false)
-                        .interact()?;
+                    let confirm = prompt::confirm(format!("确定要删除域名 {} 吗？此操作不可逆！", domain.red()))?;
                     if !confirm {
                         output::info("已取消删除操作");
                         return Ok(());
@@ -252,6 +318,44 @@ impl ZoneArgs {
                 output::success(&format!("域名 {} 已恢复", zone.name));
             }
 
+            ZoneCommands::Hold { command } => match command {
+                HoldCommands::Status { domain } => {
+                    let zone_id = resolve_zone_id(client, domain).await?;
+                    let hold = client.get_zone_hold(&zone_id).await?;
+                    print_hold(domain, &hold, format);
+                }
+                HoldCommands::Enable {
+                    domain,
+                    include_subdomains,
+                } => {
+                    let zone_id = resolve_zone_id(client, domain).await?;
+                    let hold = client.enable_zone_hold(&zone_id, *include_subdomains).await?;
+                    if format != "json" {
+                        output::success(&format!("已开启 {} 的 Zone Hold", domain));
+                    }
+                    print_hold(domain, &hold, format);
+                }
+                HoldCommands::Disable { domain, hours } => {
+                    let zone_id = resolve_zone_id(client, domain).await?;
+                    let hold_after = hours.map(|h| {
+                        (chrono::Utc::now() + chrono::Duration::hours(h as i64))
+                            .format("%Y-%m-%dT%H:%M:%SZ")
+                            .to_string()
+                    });
+                    let hold = client.disable_zone_hold(&zone_id, hold_after.as_deref()).await?;
+                    if format != "json" {
+                        match hours {
+                            Some(h) => output::success(&format!(
+                                "已临时解除 {} 的 Zone Hold，{} 小时后自动恢复",
+                                domain, h
+                            )),
+                            None => output::success(&format!("已解除 {} 的 Zone Hold", domain)),
+                        }
+                    }
+                    print_hold(domain, &hold, format);
+                }
+            },
+
             ZoneCommands::Check { domain } => {
                 let zone_id = resolve_zone_id(client, domain).await?;
                 client.check_zone_activation(&zone_id).await?;
@@ -317,6 +421,155 @@ impl ZoneArgs {
                     serde_json::to_string(&setting.value).unwrap_or_default()
                 ));
             }
+
+            ZoneCommands::Limits { domain } => {
+                let zone_id = resolve_zone_id(client, domain).await?;
+                let zone = client.get_zone(&zone_id).await?;
+                let limits = PlanLimits::for_plan(zone.plan.as_ref());
+
+                let mut usage = Vec::new();
+                for resource in [QuotaResource::PageRule, QuotaResource::RateLimit] {
+                    let used = client.get_quota_usage(&zone_id, resource).await.ok();
+                    usage.push((resource, used, limits.limit_of(resource)));
+                }
+
+                if format == "json" {
+                    let items: Vec<_> = usage
+                        .iter()
+                        .map(|(r, used, max)| {
+                            serde_json::json!({ "resource": r.label(), "used": used, "limit": max })
+                        })
+                        .collect();
+                    output::print_json(&serde_json::json!({
+                        "zone": zone.name,
+                        "limits": limits,
+                        "usage": items,
+                    }));
+                    return Ok(());
+                }
+
+                output::title(&format!("套餐配额: {}", zone.name));
+                output::kv(
+                    "套餐",
+                    &format!(
+                        "{} ({})",
+                        zone.plan.as_ref().and_then(|p| p.name.as_deref()).unwrap_or("-"),
+                        limits.plan
+                    ),
+                );
+
+                let mut table = output::create_table(vec!["项目", "已用", "上限", "剩余"]);
+                for (resource, used, max) in &usage {
+                    let (used_str, left_str) = match used {
+                        Some(u) if u >= max => (u.to_string(), "0".red().to_string()),
+                        Some(u) => (u.to_string(), (max - u).to_string().green().to_string()),
+                        None => ("?".to_string(), "?".to_string()),
+                    };
+                    table.add_row(vec![
+                        resource.label().to_string(),
+                        used_str,
+                        max.to_string(),
+                        left_str,
+                    ]);
+                }
+                table.add_row(vec![
+                    "自定义 WAF 规则".to_string(),
+                    "-".to_string(),
+                    limits.custom_rules.to_string(),
+                    "-".to_string(),
+                ]);
+                table.add_row(vec![
+                    "DNS 记录".to_string(),
+                    "-".to_string(),
+                    limits.dns_records.to_string(),
+                    "-".to_string(),
+                ]);
+                println!("{table}");
+
+                output::kv("缓存清除方式", &limits.purge_types.join(", "));
+                output::kv(
+                    "单次清除 URL 上限",
+                    &limits.purge_urls_per_request.to_string(),
+                );
+                if limits.plan == "free" {
+                    output::tip("2024 年 9 月后创建的免费域名 DNS 记录上限为 200 条");
+                }
+            }
+
+            ZoneCommands::Diff {
+                zone_a,
+                zone_b,
+                apply,
+                force,
+                yes,
+            } => {
+                let id_a = resolve_zone_id(client, zone_a).await?;
+                let id_b = resolve_zone_id(client, zone_b).await?;
+                let (mut a, mut b) =
+                    tokio::try_join!(client.snapshot_zone(&id_a), client.snapshot_zone(&id_b))?;
+                // 防火墙规则不参与域名对比
+                a.firewall_rules = None;
+                b.firewall_rules = None;
+
+                let changes = plan_restore(&a, &b, true);
+
+                if format == "json" {
+                    let diff: Vec<_> = changes
+                        .iter()
+                        .map(|c| {
+                            serde_json::json!({
+                                "section": c.section,
+                                "change": format!("{:?}", c.kind).to_lowercase(),
+                                "target": c.target,
+                                "before": c.before,
+                                "after": c.after,
+                            })
+                        })
+                        .collect();
+                    output::print_json(&diff);
+                    return Ok(());
+                }
+
+                output::title(&format!("配置对比: {} → {}", a.zone.name, b.zone.name));
+                if changes.is_empty() {
+                    output::success("两个域名的配置一致");
+                    return Ok(());
+                }
+                println!("{}", format!("--- {}", b.zone.name).red());
+                println!("{}", format!("+++ {}", a.zone.name).green());
+                backup::print_changes(&changes);
+                output::info(&format!(
+                    "共 {} 处差异 (+ 需在 {} 新增, - 需从 {} 删除, ~ 需修改)",
+                    changes.len(),
+                    b.zone.name,
+                    b.zone.name
+                ));
+
+                if !apply {
+                    output::tip("添加 --apply 将目标域名同步为与源域名一致");
+                    return Ok(());
+                }
+
+                let (changes, _) =
+                    backup::protect_unmanaged(changes, b.dns_records.as_deref().unwrap_or_default(), *force);
+                if changes.is_empty() {
+                    return Ok(());
+                }
+
+                if !yes {
+                    let confirm = prompt::confirm(format!(
+                        "确定要将 {} 同步为与 {} 一致吗？",
+                        b.zone.name.yellow(),
+                        a.zone.name
+                    ))?;
+                    if !confirm {
+                        output::info("已取消同步操作");
+                        return Ok(());
+                    }
+                }
+
+                backup::apply_changes(client, &id_b, &changes).await?;
+            }
         }
 
         Ok(())
@@ -332,3 +585,77 @@ pub async fn resolve_zone_id(client: &CfClient, domain_or_id: &str) -> Result<St
     // 否则按域名查找
     client.find_zone_id(domain_or_id).await
 }
+
+/// 账户下的域名名称，本地缓存未过期 (且未指定 --no-cache) 时不请求 API
+pub async fn zone_names(client: &CfClient) -> Result<Vec<String>> {
+    let cache_key = client.zone_cache_key();
+    if let Some(zones) = cache_key.and_then(zone_cache::load) {
+        return Ok(zones.into_iter().map(|z| z.name).collect());
+    }
+
+    output::loading("正在获取域名列表...");
+    let zones = client.list_all_zones().await?;
+    if let Some(key) = cache_key {
+        if let Err(e) = zone_cache::store(key, &zones) {
+            tracing::debug!("写入域名缓存失败: {:#}", e);
+        }
+    }
+    Ok(zones.into_iter().map(|z| z.name).collect())
+}
+
+/// 交互式选择域名: 输入关键字模糊搜索，也可手动输入列表外的域名，按 Esc 取消时返回 None
+pub fn pick_domain(theme: &dyn Theme, domains: &[String]) -> Result<Option<String>> {
+    prompt::require_interactive("请直接指定域名")?;
+    let manual = || -> Result<Option<String>> {
+        Ok(Some(Input::with_theme(theme).with_prompt("域名 (如: example.com)").interact_text()?))
+    };
+    if domains.is_empty() {
+        output::warn("未找到域名，请手动输入");
+        return manual();
+    }
+
+    let mut items: Vec<&str> = domains.iter().map(String::as_str).collect();
+    items.push("✍️  手动输入其他域名");
+    let selection = FuzzySelect::with_theme(theme)
+        .with_prompt("选择域名 (输入关键字搜索，Esc 取消)")
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+    match selection {
+        Some(i) if i < domains.len() => Ok(Some(domains[i].clone())),
+        Some(_) => manual(),
+        None => Ok(None),
+    }
+}
+
+/// 找到主机名所属的域名 (取最长匹配)
+pub async fn zone_for_host(client: &CfClient, host: &str) -> Result<Option<Zone>> {
+    let host = host.trim_end_matches('.').to_lowercase();
+    Ok(client
+        .list_all_zones()
+        .await?
+        .into_iter()
+        .filter(|z| {
+            let name = z.name.to_lowercase();
+            host == name || host.ends_with(&format!(".{}", name))
+        })
+        .max_by_key(|z| z.name.len()))
+}
+
+/// 显示 Zone Hold 状态
+fn print_hold(domain: &str, hold: &ZoneHold, format: &str) {
+    if format == "json" {
+        output::print_json(hold);
+        return;
+    }
+    let active = hold.hold == Some(true);
+    output::title(&format!("Zone Hold - {}", domain));
+    output::kv_colored("保护", if active { "已开启" } else { "未开启" }, active);
+    output::kv(
+        "包含子域名",
+        if hold.include_subdomains == Some(true) { "是" } else { "否" },
+    );
+    if let Some(after) = hold.hold_after.as_deref().filter(|a| !a.is_empty()) {
+        output::kv("临时解除至", after);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::api::error::{ApiError, ValidationError};
+
+/// 进程退出码 (供脚本按结果分支处理)
+pub const SUCCESS: i32 = 0;
+/// 其他未分类错误
+pub const GENERAL_ERROR: i32 = 1;
+/// 参数或输入校验失败 (与 clap 的参数错误退出码一致)
+pub const VALIDATION_ERROR: i32 = 2;
+/// 认证失败: 未配置凭据、Token 无效或权限不足
+pub const AUTH_ERROR: i32 = 3;
+/// Cloudflare API 返回错误或网络请求失败
+pub const API_ERROR: i32 = 4;
+/// 批量操作部分失败
+pub const PARTIAL_FAILURE: i32 = 5;
+
+/// 批量操作中部分条目失败
+#[derive(Debug, thiserror::Error)]
+#[error("{total} 项中有 {failed} 项失败")]
+pub struct PartialFailure {
+    pub total: usize,
+    pub failed: usize,
+}
+
+/// 未配置可用的 Cloudflare 凭据
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct AuthError(pub String);
+
+/// 根据错误链中的错误类型确定退出码
+///
+/// `downcast_ref` 会穿过 `.context()` 包装查找，部分失败优先于其内部的 API 错误。
+pub fn code_for(err: &anyhow::Error) -> i32 {
+    if err.downcast_ref::<PartialFailure>().is_some() {
+        PARTIAL_FAILURE
+    } else if err.downcast_ref::<AuthError>().is_some() {
+        AUTH_ERROR
+    } else if let Some(api) = err.downcast_ref::<ApiError>() {
+        if api.is_auth() {
+            AUTH_ERROR
+        } else {
+            API_ERROR
+        }
+    } else if err.downcast_ref::<ValidationError>().is_some() {
+        VALIDATION_ERROR
+    } else if err.downcast_ref::<reqwest::Error>().is_some() {
+        API_ERROR
+    } else {
+        GENERAL_ERROR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_for() {
+        let auth: anyhow::Error = ApiError {
+            status: 400,
+            codes: vec![9109],
+            message: "Invalid access token".into(),
+        }
+        .into();
+        assert_eq!(code_for(&auth), AUTH_ERROR);
+
+        let api: anyhow::Error = ApiError {
+            status: 404,
+            codes: vec![1001],
+            message: "not found".into(),
+        }
+        .into();
+        let api = api.context("获取域名失败");
+        assert_eq!(code_for(&api), API_ERROR);
+
+        let partial = api.context(PartialFailure { total: 3, failed: 1 });
+        assert_eq!(code_for(&partial), PARTIAL_FAILURE);
+        assert_eq!(code_for(&anyhow::anyhow!("其他错误")), GENERAL_ERROR);
+    }
+}
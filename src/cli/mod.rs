@@ -1,2 +1,4 @@
 pub mod commands;
+pub mod exit;
 pub mod output;
+pub mod prompt;
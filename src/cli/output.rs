@@ -1,6 +1,24 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use colored::Colorize;
 use comfy_table::{Cell, CellAlignment, Color, ContentArrangement, Table};
 
+/// 安静模式: 只输出数据 (表格 / JSON / 键值) 与错误，省略横幅、提示、进度和表情符号
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// 开启或关闭安静模式 (开启时同时关闭彩色输出)
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+    if quiet {
+        colored::control::set_override(false);
+    }
+}
+
+/// 是否处于安静模式
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
 /// 输出格式
 #[derive(Debug, Clone, PartialEq)]
 pub enum OutputFormat {
@@ -23,6 +41,9 @@ impl std::str::FromStr for OutputFormat {
 
 /// 打印欢迎横幅
 pub fn print_banner() {
+    if is_quiet() {
+        return;
+    }
     println!("{}", r#"
    ____  _____    _    ___
   / ___|  ___|  / \  |_ _|
@@ -36,58 +57,93 @@ pub fn print_banner() {
 
 /// 打印分隔线
 pub fn separator() {
+    if is_quiet() {
+        return;
+    }
     println!("{}", "─".repeat(60).dimmed());
 }
 
 /// 打印双线分隔线
 pub fn separator_bold() {
+    if is_quiet() {
+        return;
+    }
     println!("{}", "═".repeat(60).bold());
 }
 
 /// 打印带图标的步骤
 pub fn step(num: usize, msg: &str) {
+    if is_quiet() {
+        return;
+    }
     println!("\n{} {}", format!("步骤 {}:", num).bold().cyan(), msg);
     separator();
 }
 
 /// 打印成功消息
 pub fn success(msg: &str) {
+    if is_quiet() {
+        return;
+    }
     println!("{} {}", "✅".green(), msg.green());
 }
 
 /// 打印错误消息
 pub fn error(msg: &str) {
+    if is_quiet() {
+        eprintln!("error: {}", msg);
+        return;
+    }
     eprintln!("{} {}", "❌".red(), msg.red());
 }
 
-/// 打印警告消息
+/// 打印警告消息 (安静模式下输出到 stderr)
 pub fn warn(msg: &str) {
+    if is_quiet() {
+        eprintln!("warning: {}", msg);
+        return;
+    }
     println!("{} {}", "⚠️ ".yellow(), msg.yellow());
 }
 
 /// 打印信息消息
 pub fn info(msg: &str) {
+    if is_quiet() {
+        return;
+    }
     println!("{} {}", "ℹ️ ".blue(), msg);
 }
 
 /// 打印提示消息
 pub fn tip(msg: &str) {
+    if is_quiet() {
+        return;
+    }
     println!("{} {}", "💡".bright_yellow(), msg.bright_yellow());
 }
 
 /// 打印加载中消息
 pub fn loading(msg: &str) {
+    if is_quiet() {
+        return;
+    }
     println!("{} {}...", "⏳".cyan(), msg.cyan());
 }
 
 /// 打印标题
 pub fn title(msg: &str) {
+    if is_quiet() {
+        return;
+    }
     println!("\n{}", msg.bold().cyan());
     separator();
 }
 
 /// 打印大标题（带边框）
 pub fn title_box(msg: &str) {
+    if is_quiet() {
+        return;
+    }
     let width = 60;
     let padding = (width - msg.len() - 4) / 2;
     let left_pad = " ".repeat(padding);
@@ -125,6 +181,9 @@ pub fn list_numbered(num: usize, msg: &str) {
 
 /// 打印进度信息
 pub fn progress(current: usize, total: usize, msg: &str) {
+    if is_quiet() {
+        return;
+    }
     println!(
         "{} [{}/{}] {}",
         "▶".cyan(),
@@ -152,6 +211,31 @@ pub fn create_table(headers: Vec<&str>) -> Table {
     table
 }
 
+/// 打印请求耗时汇总 (输出到 stderr，不影响 JSON 等数据输出)
+pub fn print_timings(timings: &[crate::api::timing::EndpointTiming]) {
+    if timings.is_empty() {
+        eprintln!("{}", "未发出 API 请求".dimmed());
+        return;
+    }
+    let ms = |d: std::time::Duration| format!("{:.0} ms", d.as_secs_f64() * 1000.0);
+    let mut table = create_table(vec!["接口", "次数", "最短", "平均", "最长", "合计"]);
+    for t in timings {
+        table.add_row(vec![
+            t.endpoint.clone(),
+            t.count.to_string(),
+            ms(t.min),
+            ms(t.avg),
+            ms(t.max),
+            ms(t.total),
+        ]);
+    }
+    let count: usize = timings.iter().map(|t| t.count).sum();
+    let total: std::time::Duration = timings.iter().map(|t| t.total).sum();
+    eprintln!();
+    eprintln!("{table}");
+    eprintln!("共 {} 次请求，累计 {}", count, ms(total));
+}
+
 /// 打印 JSON 格式
 pub fn print_json<T: serde::Serialize>(data: &T) {
     match serde_json::to_string_pretty(data) {
@@ -162,10 +246,19 @@ pub fn print_json<T: serde::Serialize>(data: &T) {
 
 /// 打印 AI 分析结果
 pub fn print_ai_result(content: &str, tokens: Option<u32>) {
+    print_ai_result_header();
+    println!("{}", content);
+    print_ai_result_footer(tokens);
+}
+
+/// 打印 AI 分析结果标题 (流式输出时先于内容打印)
+pub fn print_ai_result_header() {
     println!("\n{}", "🤖 AI 分析结果".bold().cyan());
     separator();
-    println!("{}", content);
+}
 
+/// 打印 AI 分析结果结尾 (Token 用量)
+pub fn print_ai_result_footer(tokens: Option<u32>) {
     if let Some(t) = tokens {
         println!("\n{}", format!("💬 Token 用量: {}", t).dimmed());
     }
@@ -184,11 +277,17 @@ pub fn badge(label: &str, status: &str, is_good: bool) {
 
 /// 打印命令建议
 pub fn suggest_command(desc: &str, cmd: &str) {
+    if is_quiet() {
+        return;
+    }
     println!("  {} {}", desc.dimmed(), cmd.cyan());
 }
 
 /// 打印空行
 pub fn newline() {
+    if is_quiet() {
+        return;
+    }
     println!();
 }
 
@@ -210,12 +309,16 @@ pub fn print_ai_actions(actions: &[crate::ai::analyzer::SuggestedAction]) {
         };
 
         println!(
-            "  {}. {} {} [{}]",
+            "  {}. {} {} [{}]{}",
             i + 1,
             risk_color,
             action.description,
-            action.action_type.dimmed()
+            action.action_type.dimmed(),
+            if action.blocked { " ⛔ 已阻止".red().bold().to_string() } else { String::new() }
         );
+        for note in &action.review_notes {
+            println!("       {} {}", "↳".dimmed(), note.yellow());
+        }
     }
 }
 
@@ -261,6 +364,10 @@ pub fn status_badge(status: &str) -> String {
         "moved" => format!("{}", "● moved".blue()),
         "deleted" => format!("{}", "● deleted".red()),
         "deactivated" => format!("{}", "● deactivated".dimmed()),
+        "healthy" => format!("{}", "● healthy".green()),
+        "unhealthy" => format!("{}", "● unhealthy".red()),
+        "suspended" => format!("{}", "● suspended".dimmed()),
+        "unknown" => format!("{}", "● unknown".yellow()),
         "on" | "true" | "enabled" => format!("{}", "● ON".green()),
         "off" | "false" | "disabled" => format!("{}", "● OFF".red()),
         _ => status.to_string(),
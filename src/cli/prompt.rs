@@ -0,0 +1,93 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use dialoguer::Confirm;
+
+use crate::api::error::ValidationError;
+
+/// 非交互模式: 不等待用户输入，确认提示直接报错，其余提示使用安全的默认值
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// 开启或关闭非交互模式 (--non-interactive)
+pub fn set_non_interactive(non_interactive: bool) {
+    NON_INTERACTIVE.store(non_interactive, Ordering::Relaxed);
+}
+
+/// 全局 --yes: 所有确认提示直接视为同意
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// 开启或关闭自动确认 (全局 --yes / CFAI_YES)
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+/// 能否向用户提问: 未指定 --non-interactive、不在 CI 中，且标准输入与输出都是终端
+pub fn is_interactive() -> bool {
+    !NON_INTERACTIVE.load(Ordering::Relaxed)
+        && !in_ci(std::env::var("CI").ok().as_deref())
+        && std::io::stdin().is_terminal()
+        && console::user_attended()
+}
+
+/// CI 服务普遍设置 `CI=true` (或 `1`)
+fn in_ci(value: Option<&str>) -> bool {
+    value.is_some_and(|v| !v.is_empty() && !v.eq_ignore_ascii_case("false") && v != "0")
+}
+
+/// 危险操作的确认 (默认为否)；指定全局 --yes 时直接同意，无法提问时报错，提示使用 --yes 跳过确认
+pub fn confirm(prompt: impl Into<String>) -> Result<bool> {
+    if ASSUME_YES.load(Ordering::Relaxed) {
+        return Ok(true);
+    }
+    let prompt = prompt.into();
+    if !is_interactive() {
+        anyhow::bail!(ValidationError(format!(
+            "非交互模式下无法确认「{}」，确认无误后请加 --yes 重新执行",
+            console::strip_ansi_codes(&prompt)
+        )));
+    }
+    Ok(Confirm::new().with_prompt(prompt).default(false).interact()?)
+}
+
+/// AI 建议操作的确认: 全局 --yes 不适用，必须由用户在终端确认；无法提问时视为不同意
+pub fn confirm_ai_action(prompt: impl Into<String>, default: bool) -> Result<bool> {
+    if !is_interactive() {
+        return Ok(false);
+    }
+    Ok(Confirm::new().with_prompt(prompt.into()).default(default).interact()?)
+}
+
+/// 一般性的是 / 否提问: 指定全局 --yes 时直接同意，无法提问时使用默认值
+pub fn ask(prompt: impl Into<String>, default: bool) -> Result<bool> {
+    if ASSUME_YES.load(Ordering::Relaxed) {
+        return Ok(true);
+    }
+    if !is_interactive() {
+        return Ok(default);
+    }
+    Ok(Confirm::new().with_prompt(prompt.into()).default(default).interact()?)
+}
+
+/// 必须由用户输入的操作 (向导、菜单等)，无法提问时报错并给出替代方式
+pub fn require_interactive(alternative: &str) -> Result<()> {
+    if is_interactive() {
+        return Ok(());
+    }
+    anyhow::bail!(ValidationError(format!("当前为非交互模式 (非终端、CI 或 --non-interactive)，{}", alternative)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_ci() {
+        assert!(in_ci(Some("true")));
+        assert!(in_ci(Some("1")));
+        assert!(!in_ci(Some("false")));
+        assert!(!in_ci(Some("0")));
+        assert!(!in_ci(Some("")));
+        assert!(!in_ci(None));
+    }
+}
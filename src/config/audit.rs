@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+
+use crate::config::settings::AppConfig;
+use crate::models::audit::{AuditChange, AuditEntry};
+
+/// 审计日志保留的最大条目数
+const MAX_AUDIT_ENTRIES: usize = 500;
+
+/// 串行化同一进程内的读写 (GUI 中多个后台任务可能同时记录)
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 审计日志文件路径
+pub fn path() -> Result<PathBuf> {
+    Ok(AppConfig::config_dir()?.join("audit_log.json"))
+}
+
+/// 加载全部条目 (按时间顺序)，文件不存在时返回空列表
+pub fn load() -> Result<Vec<AuditEntry>> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    read()
+}
+
+/// 追加一条变更记录
+pub fn record(zone_id: &str, change: AuditChange) -> Result<AuditEntry> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let now = chrono::Local::now();
+    let entry = AuditEntry {
+        id: format!("{:x}", now.timestamp_micros()),
+        timestamp: now.to_rfc3339(),
+        zone_id: zone_id.to_string(),
+        change,
+        undone: false,
+    };
+
+    let mut entries = read()?;
+    entries.push(entry.clone());
+    if entries.len() > MAX_AUDIT_ENTRIES {
+        let excess = entries.len() - MAX_AUDIT_ENTRIES;
+        entries.drain(..excess);
+    }
+    write(&entries)?;
+    Ok(entry)
+}
+
+/// 将条目标记为已撤销
+pub fn mark_undone(id: &str) -> Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = read()?;
+    if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+        entry.undone = true;
+    }
+    write(&entries)
+}
+
+fn read() -> Result<Vec<AuditEntry>> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("读取审计日志失败: {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("解析审计日志失败: {}", path.display()))
+}
+
+fn write(entries: &[AuditEntry]) -> Result<()> {
+    let path = path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(entries)?;
+    std::fs::write(&path, content).with_context(|| format!("写入审计日志失败: {}", path.display()))
+}
@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::settings::AppConfig;
+use crate::models::firewall::CloudflareIps;
+
+/// Cloudflare IP 段缓存的有效期 (秒)，官方列表很少变化
+pub const IP_RANGES_CACHE_TTL_SECS: i64 = 24 * 3600;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedIpRanges {
+    /// 获取时间 (Unix 秒)
+    fetched_at: i64,
+    ranges: CloudflareIps,
+}
+
+/// 缓存文件路径
+pub fn path() -> Result<PathBuf> {
+    Ok(AppConfig::config_dir()?.join("cloudflare_ips.json"))
+}
+
+/// 读取缓存的 IP 段及其已缓存的秒数，缓存不存在或损坏时返回 None
+pub fn load() -> Option<(CloudflareIps, i64)> {
+    let content = std::fs::read_to_string(path().ok()?).ok()?;
+    let cached: CachedIpRanges = serde_json::from_str(&content).ok()?;
+    Some((cached.ranges, chrono::Utc::now().timestamp() - cached.fetched_at))
+}
+
+/// 保存 IP 段
+pub fn store(ranges: &CloudflareIps) -> Result<()> {
+    let path = path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
+    }
+    let cached = CachedIpRanges {
+        fetched_at: chrono::Utc::now().timestamp(),
+        ranges: ranges.clone(),
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&cached)?)
+        .with_context(|| format!("写入 IP 段缓存失败: {}", path.display()))
+}
@@ -1 +1,6 @@
+pub mod secrets;
 pub mod settings;
+pub mod audit;
+pub mod zone_cache;
+pub mod ip_ranges;
+pub mod response_cache;
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::settings::AppConfig;
+
+/// 串行化同一进程内的读写
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 缓存文件: 按 Profile 分别保存 GET 请求 (路径与查询参数) 的响应体
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct ResponseCacheFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, BTreeMap<String, CachedResponse>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedResponse {
+    /// 获取时间 (Unix 秒)
+    fetched_at: i64,
+    body: String,
+}
+
+/// 缓存文件路径
+pub fn path() -> Result<PathBuf> {
+    Ok(AppConfig::config_dir()?.join("response_cache.json"))
+}
+
+/// 读取未过期的响应体及其获取时间
+pub fn load(key: &str, request: &str, ttl_secs: i64) -> Option<(String, i64)> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let cached = read().ok()?.profiles.remove(key)?.remove(request)?;
+    let age = chrono::Utc::now().timestamp() - cached.fetched_at;
+    (0..ttl_secs).contains(&age).then_some((cached.body, cached.fetched_at))
+}
+
+/// 保存响应体，同时清理已过期的条目
+pub fn store(key: &str, request: &str, body: &str, ttl_secs: i64) -> Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = read().unwrap_or_default();
+    let now = chrono::Utc::now().timestamp();
+    for entries in file.profiles.values_mut() {
+        entries.retain(|_, cached| (0..ttl_secs).contains(&(now - cached.fetched_at)));
+    }
+    file.profiles.retain(|_, entries| !entries.is_empty());
+    file.profiles.entry(key.to_string()).or_default().insert(
+        request.to_string(),
+        CachedResponse {
+            fetched_at: now,
+            body: body.to_string(),
+        },
+    );
+    write(&file)
+}
+
+/// 移除 `matches` 返回 true 的请求的缓存
+pub fn invalidate(key: &str, matches: impl Fn(&str) -> bool) -> Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let Ok(mut file) = read() else {
+        return Ok(());
+    };
+    let Some(entries) = file.profiles.get_mut(key) else {
+        return Ok(());
+    };
+    let before = entries.len();
+    entries.retain(|request, _| !matches(request));
+    if entries.len() == before {
+        return Ok(());
+    }
+    write(&file)
+}
+
+/// 删除缓存文件，返回缓存是否存在
+pub fn clear() -> Result<bool> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = path()?;
+    if !path.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_file(&path).with_context(|| format!("删除缓存失败: {}", path.display()))?;
+    Ok(true)
+}
+
+fn read() -> Result<ResponseCacheFile> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(ResponseCacheFile::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("读取响应缓存失败: {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("解析响应缓存失败: {}", path.display()))
+}
+
+fn write(file: &ResponseCacheFile) -> Result<()> {
+    let path = path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
+    }
+    let content = serde_json::to_string(file)?;
+    std::fs::write(&path, content).with_context(|| format!("写入响应缓存失败: {}", path.display()))
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+
+/// 系统钥匙串 (macOS Keychain / Windows Credential Manager / Secret Service) 中的服务名
+///
+/// 开启 `secure_storage` 后，API Token / Key 以配置键 (如 `cloudflare.api_token`)
+/// 为账户名存入钥匙串，不再写入配置文件。
+#[cfg_attr(not(feature = "keyring"), allow(dead_code))]
+const SERVICE: &str = "cfai";
+
+/// 当前构建是否支持系统钥匙串
+pub fn is_supported() -> bool {
+    cfg!(feature = "keyring")
+}
+
+#[cfg(feature = "keyring")]
+fn entry(key: &str) -> Result<keyring::Entry> {
+    use anyhow::Context;
+    keyring::Entry::new(SERVICE, key).with_context(|| format!("打开钥匙串条目失败: {}", key))
+}
+
+/// 读取凭据，不存在时返回 None
+#[cfg(feature = "keyring")]
+pub fn get(key: &str) -> Result<Option<String>> {
+    match entry(key)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow::anyhow!("从钥匙串读取 {} 失败: {}", key, e)),
+    }
+}
+
+/// 写入凭据
+#[cfg(feature = "keyring")]
+pub fn set(key: &str, value: &str) -> Result<()> {
+    entry(key)?
+        .set_password(value)
+        .map_err(|e| anyhow::anyhow!("写入钥匙串 {} 失败: {}", key, e))
+}
+
+/// 删除凭据 (不存在时忽略)
+#[cfg(feature = "keyring")]
+pub fn delete(key: &str) -> Result<()> {
+    match entry(key)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(anyhow::anyhow!("删除钥匙串 {} 失败: {}", key, e)),
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+fn unsupported() -> anyhow::Error {
+    anyhow::anyhow!("当前构建未启用 keyring 特性，无法使用系统钥匙串 (secure_storage)")
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn get(_key: &str) -> Result<Option<String>> {
+    Err(unsupported())
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn set(_key: &str, _value: &str) -> Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn delete(_key: &str) -> Result<()> {
+    Err(unsupported())
+}
@@ -1,19 +1,58 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::ai::analyzer::AiAnalyzer;
+use crate::ai::provider::AiProvider;
+use crate::api::client::{AuthMethod, CfClient, RetryPolicy};
+use crate::config::secrets;
+
+/// 顶层 `[cloudflare]` 配置对应的 Profile 名称
+pub const DEFAULT_PROFILE: &str = "default";
+
+fn profile_not_found(name: &str) -> anyhow::Error {
+    anyhow::anyhow!("未找到 Profile: {}，运行 `cfai config profile list` 查看", name)
+}
 
 /// 应用配置
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
+    /// 当前使用的 Profile (None 表示顶层 `[cloudflare]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    /// 将 API Token / Key 存入系统钥匙串而不是配置文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secure_storage: Option<bool>,
     pub cloudflare: CloudflareConfig,
     pub ai: AiConfig,
     #[serde(default)]
     pub defaults: DefaultsConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    #[serde(default)]
+    pub guard: GuardConfig,
+    #[serde(default)]
+    pub gui: GuiConfig,
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+    /// 定时切换的域名设置 (`cfai firewall schedule`，由 `cfai scheduler run` 执行)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<ScheduleEntry>,
+    /// 命名 Profile (`[profiles.<名称>]`)，每个包含一套 Cloudflare 认证信息
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, CloudflareConfig>,
+    /// 加载时从钥匙串读取失败的键名，保存时不会因为值为空而删除对应凭据
+    #[serde(skip)]
+    pub unreadable_secrets: BTreeSet<String>,
 }
 
 /// Cloudflare 配置
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CloudflareConfig {
     /// API Token (推荐方式)
     pub api_token: Option<String>,
@@ -23,12 +62,19 @@ pub struct CloudflareConfig {
     pub api_key: Option<String>,
     /// 账户 ID
     pub account_id: Option<String>,
+    /// 请求失败 (429 / 5xx / 网络错误) 时的最大重试次数，默认 3
+    pub max_retries: Option<u32>,
+    /// 首次重试前的等待时间 (毫秒)，之后每次翻倍，默认 500
+    pub retry_backoff_ms: Option<u64>,
 }
 
 /// AI 配置
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AiConfig {
-    /// AI API 地址 (OpenAI 兼容)
+    /// 接口类型: openai (OpenAI 兼容，默认) / anthropic / gemini
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// AI API 地址
     pub api_url: Option<String>,
     /// AI API Key
     pub api_key: Option<String>,
@@ -38,6 +84,26 @@ pub struct AiConfig {
     pub max_tokens: Option<u32>,
     /// 温度参数
     pub temperature: Option<f32>,
+    /// 是否以流式 (SSE) 方式接收响应，默认开启
+    pub stream: Option<bool>,
+    /// 是否通过工具调用 (function calling) 获取操作建议，默认开启
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<bool>,
+    /// 输入 Token 单价 (美元 / 千 Token)，用于估算费用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_price_per_1k: Option<f64>,
+    /// 输出 Token 单价 (美元 / 千 Token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_price_per_1k: Option<f64>,
+    /// 每月费用预算 (美元)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_budget: Option<f64>,
+    /// 超出预算时的处理: warn (仅提示，默认) / block (阻止请求)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_action: Option<String>,
+    /// 健康报告推送的 Webhook 地址 (Slack / Discord)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_webhook: Option<String>,
 }
 
 /// 默认配置
@@ -51,23 +117,244 @@ pub struct DefaultsConfig {
     pub color: Option<bool>,
 }
 
+/// 网络配置 (同时作用于 Cloudflare API 与 AI 请求)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NetworkConfig {
+    /// Cloudflare API 地址 (用于 API 网关或测试用的 Mock 服务)，默认官方地址
+    pub api_base_url: Option<String>,
+    /// HTTP(S) 代理地址，如 http://proxy.example.com:8080 (未设置时沿用 HTTPS_PROXY 等环境变量)
+    pub proxy_url: Option<String>,
+    /// 额外信任的根证书文件 (PEM 格式，可包含多张证书)
+    pub ca_bundle_path: Option<String>,
+    /// 请求超时 (秒)，默认 Cloudflare 30 秒、AI 120 秒
+    pub timeout_secs: Option<u64>,
+}
+
+/// 通知配置 (变更命令的执行结果与监控告警)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NotifyConfig {
+    /// 何时通知变更命令的执行结果: never (默认) / failure (仅失败) / always
+    pub on: Option<String>,
+    /// Slack Incoming Webhook 地址
+    pub slack_webhook: Option<String>,
+    /// Discord Webhook 地址
+    pub discord_webhook: Option<String>,
+    /// 通用 HTTP Webhook 地址 (POST JSON)
+    pub webhook_url: Option<String>,
+    /// SMTP 服务器
+    pub smtp_host: Option<String>,
+    /// SMTP 端口，465 使用 TLS，其余使用 STARTTLS，默认 587
+    pub smtp_port: Option<u16>,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    /// 发件人，默认使用 SMTP 用户名
+    pub email_from: Option<String>,
+    /// 收件人，多个以逗号分隔
+    pub email_to: Option<String>,
+}
+
+/// 自动缓解 (`cfai guard`) 的阈值与动作
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GuardConfig {
+    /// 每秒请求数达到该值时开启防护
+    pub requests_per_second: Option<f64>,
+    /// 统计窗口内的威胁数达到该值时开启防护
+    pub threats: Option<u64>,
+    /// 检测间隔 (秒)，默认 60
+    pub interval_secs: Option<u64>,
+    /// 统计窗口 (分钟)，默认 5
+    pub window_minutes: Option<i64>,
+    /// 流量持续低于阈值多久后解除防护 (分钟)，默认 15
+    pub cooldown_minutes: Option<u64>,
+    /// 开启防护时同时创建速率限制规则
+    pub rate_limit: Option<bool>,
+    /// 速率限制: 单个 IP 在统计周期内允许的请求数，默认 100
+    pub rate_limit_threshold: Option<u32>,
+    /// 速率限制统计周期 (秒)，默认 60
+    pub rate_limit_period: Option<u32>,
+    /// 速率限制动作 (ban/challenge/js_challenge/managed_challenge)，默认 managed_challenge
+    pub rate_limit_action: Option<String>,
+}
+
+/// 只读 API 请求的响应缓存 (默认关闭)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ResponseCacheConfig {
+    /// 开启后相同的 GET 请求在有效期内直接使用缓存 (内存 + 磁盘)，对同一 Zone / 账户的变更请求会使其失效
+    #[serde(default)]
+    pub enabled: bool,
+    /// 缓存有效期 (秒)，默认 30
+    pub ttl_secs: Option<u64>,
+}
+
+impl ResponseCacheConfig {
+    pub fn ttl_secs(&self) -> u64 {
+        self.ttl_secs.unwrap_or(30)
+    }
+}
+
+/// 一条定时设置: 按时间段 (from/to)、cron 表达式或一次性时间 (at) 切换域名设置
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScheduleEntry {
+    pub id: u32,
+    /// 域名或 Zone ID
+    pub domain: String,
+    /// 域名设置项，如 security_level
+    pub setting: String,
+    /// 设置值 (JSON 值或字符串)
+    pub value: String,
+    /// 时间段开始 (本地时间 HH:MM)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// 时间段结束 (本地时间 HH:MM，可跨午夜)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// 时间段之外恢复的值，未设置时时间段结束后不做改动
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<String>,
+    /// cron 表达式 (5 段，本地时间)，到点时应用一次
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    /// 一次性执行时间 (RFC 3339)，应用后自动删除该定时设置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at: Option<String>,
+}
+
+/// 支持自动刷新的 GUI 页面数据
+pub const GUI_REFRESH_PAGES: &[&str] = &[
+    "dashboard",
+    "dns",
+    "ssl",
+    "firewall",
+    "firewall_events",
+    "cache",
+    "page_rules",
+    "workers",
+    "analytics",
+];
+
+/// 未设置时的自动刷新间隔 (秒)
+const DEFAULT_GUI_REFRESH: &[(&str, u64)] = &[("analytics", 60), ("firewall_events", 30)];
+
+/// GUI 设置
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GuiConfig {
+    /// 各页面数据的自动刷新间隔 (秒，0 表示关闭)，键见 `GUI_REFRESH_PAGES`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub refresh: BTreeMap<String, u64>,
+    /// 后台监控与桌面告警
+    #[serde(default)]
+    pub monitor: GuiMonitorConfig,
+}
+
+/// GUI 后台监控: 关闭窗口后继续轮询流量，突增时发送桌面通知
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GuiMonitorConfig {
+    /// 开启后关闭窗口只最小化，并在后台监控
+    #[serde(default)]
+    pub enabled: bool,
+    /// 监控的域名，为空时监控当前选中的域名
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zones: Vec<String>,
+    /// 轮询间隔 (秒)，默认 60
+    pub interval_secs: Option<u64>,
+    /// 请求速率或威胁数达到近期平均值的该倍数时告警，默认 3
+    pub spike_factor: Option<f64>,
+    /// 低于该请求速率 (次/秒) 时不视为突增，默认 1
+    pub min_requests_per_second: Option<f64>,
+    /// 低于该威胁数时不视为突增，默认 20
+    pub min_threats: Option<u64>,
+}
+
+impl GuiMonitorConfig {
+    pub fn interval_secs(&self) -> u64 {
+        self.interval_secs.unwrap_or(60)
+    }
+
+    pub fn spike_factor(&self) -> f64 {
+        self.spike_factor.unwrap_or(3.0)
+    }
+
+    pub fn min_requests_per_second(&self) -> f64 {
+        self.min_requests_per_second.unwrap_or(1.0)
+    }
+
+    pub fn min_threats(&self) -> u64 {
+        self.min_threats.unwrap_or(20)
+    }
+}
+
+impl GuiConfig {
+    /// 页面数据的自动刷新间隔，未开启时返回 None
+    pub fn refresh_secs(&self, page: &str) -> Option<u64> {
+        self.refresh
+            .get(page)
+            .copied()
+            .or_else(|| DEFAULT_GUI_REFRESH.iter().find(|(p, _)| *p == page).map(|(_, s)| *s))
+            .filter(|secs| *secs > 0)
+    }
+}
+
+impl NetworkConfig {
+    /// 按网络配置设置 HTTP 客户端，未配置超时时使用 `default_timeout`
+    pub fn apply(
+        &self,
+        builder: reqwest::ClientBuilder,
+        default_timeout: Duration,
+    ) -> Result<reqwest::ClientBuilder> {
+        let mut builder =
+            builder.timeout(self.timeout_secs.map(Duration::from_secs).unwrap_or(default_timeout));
+
+        if let Some(url) = &self.proxy_url {
+            let proxy = reqwest::Proxy::all(url).with_context(|| format!("无效的代理地址: {}", url))?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(path) = &self.ca_bundle_path {
+            let pem = std::fs::read(path).with_context(|| format!("读取根证书失败: {}", path))?;
+            let certs = reqwest::Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("解析根证书失败: {}", path))?;
+            if certs.is_empty() {
+                anyhow::bail!("根证书文件中没有证书: {}", path);
+            }
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        Ok(builder)
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
-            cloudflare: CloudflareConfig {
-                api_token: None,
-                email: None,
-                api_key: None,
-                account_id: None,
-            },
+            active_profile: None,
+            secure_storage: None,
+            cloudflare: CloudflareConfig::default(),
             ai: AiConfig {
+                provider: None,
                 api_url: Some("https://api.openai.com/v1".to_string()),
                 api_key: None,
                 model: Some("gpt-4o".to_string()),
                 max_tokens: Some(4096),
                 temperature: Some(0.7),
+                stream: Some(true),
+                tools: None,
+                input_price_per_1k: None,
+                output_price_per_1k: None,
+                monthly_budget: None,
+                budget_action: None,
+                report_webhook: None,
             },
             defaults: DefaultsConfig::default(),
+            network: NetworkConfig::default(),
+            notify: NotifyConfig::default(),
+            guard: GuardConfig::default(),
+            gui: GuiConfig::default(),
+            response_cache: ResponseCacheConfig::default(),
+            schedules: Vec::new(),
+            profiles: BTreeMap::new(),
+            unreadable_secrets: BTreeSet::new(),
         }
     }
 }
@@ -75,10 +362,14 @@ impl Default for AppConfig {
 impl AppConfig {
     /// 获取配置文件路径
     pub fn config_path() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
+        Ok(Self::config_dir()?.join("config.toml"))
+    }
+
+    /// 获取配置目录 (同时存放对话历史等本地数据)
+    pub fn config_dir() -> Result<PathBuf> {
+        Ok(dirs::config_dir()
             .context("无法获取配置目录")?
-            .join("cfai");
-        Ok(config_dir.join("config.toml"))
+            .join("cfai"))
     }
 
     /// 加载配置
@@ -92,12 +383,96 @@ impl AppConfig {
         let content = std::fs::read_to_string(&path)
             .with_context(|| format!("读取配置文件失败: {}", path.display()))?;
 
-        let config: AppConfig = toml::from_str(&content)
+        let mut config: AppConfig = toml::from_str(&content)
             .with_context(|| format!("解析配置文件失败: {}", path.display()))?;
 
+        if config.uses_secure_storage() {
+            // 配置文件中仍有明文值时以文件为准 (尚未迁移)
+            let mut unreadable = BTreeSet::new();
+            for (key, slot) in config.secret_slots() {
+                if slot.is_none() {
+                    match secrets::get(&key) {
+                        Ok(value) => *slot = value,
+                        Err(e) => {
+                            tracing::warn!("{:#}", e);
+                            unreadable.insert(key);
+                        }
+                    }
+                }
+            }
+            config.unreadable_secrets = unreadable;
+        }
+
         Ok(config)
     }
 
+    /// 是否启用了系统钥匙串存储
+    pub fn uses_secure_storage(&self) -> bool {
+        self.secure_storage == Some(true)
+    }
+
+    /// 所有敏感配置项 (钥匙串中的键名, 字段)
+    pub fn secret_slots(&mut self) -> Vec<(String, &mut Option<String>)> {
+        let mut slots = vec![
+            ("cloudflare.api_token".to_string(), &mut self.cloudflare.api_token),
+            ("cloudflare.api_key".to_string(), &mut self.cloudflare.api_key),
+            ("ai.api_key".to_string(), &mut self.ai.api_key),
+            ("notify.smtp_password".to_string(), &mut self.notify.smtp_password),
+        ];
+        for (name, cf) in self.profiles.iter_mut() {
+            slots.push((format!("profiles.{}.api_token", name), &mut cf.api_token));
+            slots.push((format!("profiles.{}.api_key", name), &mut cf.api_key));
+        }
+        slots
+    }
+
+    /// 加载命令执行时使用的有效配置: 应用 Profile 后再以环境变量覆盖
+    ///
+    /// 返回的配置中 `cloudflare` 已被替换为 Profile 的内容，不应再保存。
+    pub fn load_effective(profile: Option<&str>) -> Result<Self> {
+        let mut config = Self::load()?;
+        config.apply_profile(profile)?;
+        Ok(config.merge_env())
+    }
+
+    /// 保存由 `load_effective` 得到的配置，`cloudflare` 写回选中的 Profile
+    pub fn save_effective(&self, profile: Option<&str>) -> Result<()> {
+        let mut stored = self.clone();
+        if let Some(name) = self.selected_profile(profile).map(|p| p.to_string()) {
+            stored.cloudflare = Self::load()?.cloudflare;
+            stored.profiles.insert(name, self.cloudflare.clone());
+        }
+        stored.save()
+    }
+
+    /// 选中的 Profile 名称 (命令行指定优先，其次为 `active_profile`)，默认 Profile 返回 None
+    pub fn selected_profile<'a>(&'a self, profile: Option<&'a str>) -> Option<&'a str> {
+        profile
+            .or(self.active_profile.as_deref())
+            .filter(|p| *p != DEFAULT_PROFILE)
+    }
+
+    /// 用选中 Profile 的认证信息替换顶层 `cloudflare`
+    pub fn apply_profile(&mut self, profile: Option<&str>) -> Result<()> {
+        if let Some(name) = self.selected_profile(profile).map(|p| p.to_string()) {
+            self.cloudflare = self.profile(&name)?.clone();
+        }
+        Ok(())
+    }
+
+    /// 按名称获取 Profile
+    pub fn profile(&self, name: &str) -> Result<&CloudflareConfig> {
+        self.profiles.get(name).ok_or_else(|| profile_not_found(name))
+    }
+
+    /// 选中 Profile 的 Cloudflare 配置 (用于修改并保存)
+    pub fn cloudflare_mut(&mut self, profile: Option<&str>) -> Result<&mut CloudflareConfig> {
+        match self.selected_profile(profile).map(|p| p.to_string()) {
+            Some(name) => self.profiles.get_mut(&name).ok_or_else(|| profile_not_found(&name)),
+            None => Ok(&mut self.cloudflare),
+        }
+    }
+
     /// 从环境变量覆盖
     pub fn merge_env(mut self) -> Self {
         if let Ok(token) = std::env::var("CLOUDFLARE_API_TOKEN") {
@@ -112,6 +487,17 @@ impl AppConfig {
         if let Ok(account_id) = std::env::var("CLOUDFLARE_ACCOUNT_ID") {
             self.cloudflare.account_id = Some(account_id);
         }
+        if let Ok(retries) = std::env::var("CLOUDFLARE_MAX_RETRIES") {
+            if let Ok(r) = retries.parse() {
+                self.cloudflare.max_retries = Some(r);
+            }
+        }
+        if let Ok(url) = std::env::var("CLOUDFLARE_API_BASE") {
+            self.network.api_base_url = Some(url);
+        }
+        if let Ok(provider) = std::env::var("AI_PROVIDER") {
+            self.ai.provider = Some(provider);
+        }
         if let Ok(url) = std::env::var("AI_API_URL") {
             self.ai.api_url = Some(url);
         }
@@ -131,6 +517,16 @@ impl AppConfig {
                 self.ai.temperature = Some(t);
             }
         }
+        if let Ok(stream) = std::env::var("AI_STREAM") {
+            if let Ok(s) = stream.parse() {
+                self.ai.stream = Some(s);
+            }
+        }
+        if let Ok(tools) = std::env::var("AI_TOOLS") {
+            if let Ok(t) = tools.parse() {
+                self.ai.tools = Some(t);
+            }
+        }
         self
     }
 
@@ -142,7 +538,21 @@ impl AppConfig {
                 .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
         }
 
-        let content = toml::to_string_pretty(self).context("序列化配置失败")?;
+        // 启用钥匙串时，敏感字段写入钥匙串并从文件中移除
+        let mut stored = self.clone();
+        if stored.uses_secure_storage() {
+            let unreadable = std::mem::take(&mut stored.unreadable_secrets);
+            for (key, slot) in stored.secret_slots() {
+                match slot.take() {
+                    Some(value) => secrets::set(&key, &value)?,
+                    // 读取失败的凭据并非被用户清除，保留钥匙串中的原值
+                    None if unreadable.contains(&key) => {}
+                    None => secrets::delete(&key)?,
+                }
+            }
+        }
+
+        let content = toml::to_string_pretty(&stored).context("序列化配置失败")?;
         std::fs::write(&path, content)
             .with_context(|| format!("写入配置文件失败: {}", path.display()))?;
 
@@ -170,24 +580,41 @@ impl AppConfig {
         Ok(())
     }
 
-    /// 获取 AI 配置中的 API URL
+    /// Cloudflare API 请求重试策略
+    pub fn retry_policy(&self) -> RetryPolicy {
+        let default = RetryPolicy::default();
+        RetryPolicy {
+            max_retries: self.cloudflare.max_retries.unwrap_or(default.max_retries),
+            base_delay: self
+                .cloudflare
+                .retry_backoff_ms
+                .map(std::time::Duration::from_millis)
+                .unwrap_or(default.base_delay),
+            ..default
+        }
+    }
+
+    /// 获取 AI 接口类型
+    pub fn ai_provider(&self) -> Result<AiProvider> {
+        AiProvider::parse(self.ai.provider.as_deref())
+    }
+
+    /// 获取 AI 配置中的 API URL，未设置时使用接口类型的默认地址
     pub fn ai_api_url(&self) -> String {
-        self.ai
-            .api_url
-            .clone()
-            .unwrap_or_else(|| "https://api.openai.com/v1".to_string())
+        self.ai.api_url.clone().unwrap_or_else(|| {
+            self.ai_provider().unwrap_or_default().default_api_url().to_string()
+        })
     }
 
-    /// 获取 AI 模型名
+    /// 获取 AI 模型名，未设置时使用接口类型的默认模型
     pub fn ai_model(&self) -> String {
-        self.ai
-            .model
-            .clone()
-            .unwrap_or_else(|| "gpt-4o".to_string())
+        self.ai.model.clone().unwrap_or_else(|| {
+            self.ai_provider().unwrap_or_default().default_model().to_string()
+        })
     }
 
     /// 交互式配置向导
-    pub fn interactive_setup() -> Result<Self> {
+    pub async fn interactive_setup() -> Result<Self> {
         use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 
         let theme = ColorfulTheme::default();
@@ -196,7 +623,18 @@ impl AppConfig {
         println!("{}", "║        🚀 CFAI 配置向导 - 交互式设置             ║".cyan());
         println!("{}", "╚══════════════════════════════════════════════════╝".cyan());
 
-        let mut config = AppConfig::default();
+        // 保留已有的 Profile、网络、通知、自动防护、GUI、响应缓存与定时设置，向导只重新配置默认认证和 AI 设置
+        let existing = AppConfig::load().unwrap_or_default();
+        let mut config = AppConfig {
+            profiles: existing.profiles,
+            network: existing.network,
+            notify: existing.notify,
+            guard: existing.guard,
+            gui: existing.gui,
+            response_cache: existing.response_cache,
+            schedules: existing.schedules,
+            ..AppConfig::default()
+        };
 
         // ========== Cloudflare 配置 ==========
         println!("\n{}", "📡 第一步：配置 Cloudflare API 访问".bold().green());
@@ -264,30 +702,35 @@ impl AppConfig {
             _ => unreachable!(),
         }
 
-        // Account ID (可选)
-        println!();
-        let need_account_id = Confirm::with_theme(&theme)
-            .with_prompt("是否需要配置 Account ID？(某些 Workers 功能需要)")
-            .default(false)
-            .interact()?;
-
-        if need_account_id {
-            println!("\n{}", "获取 Account ID:".yellow());
-            println!("  1. 访问: {}", "https://dash.cloudflare.com/".cyan());
-            println!("  2. 在右侧边栏可以找到 Account ID");
-            println!();
-
-            let account_id: String = Input::with_theme(&theme)
-                .with_prompt("请输入 Account ID")
-                .allow_empty(true)
-                .interact_text()?;
-
-            if !account_id.trim().is_empty() {
-                config.cloudflare.account_id = Some(account_id.trim().to_string());
-                println!("{}", "✓ Account ID 已设置".green());
+        // ========== 在线验证凭据并选择账户 ==========
+        let auth = match (
+            &config.cloudflare.api_token,
+            &config.cloudflare.email,
+            &config.cloudflare.api_key,
+        ) {
+            (Some(token), _, _) => AuthMethod::ApiToken(token.clone()),
+            (None, Some(email), Some(key)) => AuthMethod::ApiKey {
+                email: email.clone(),
+                key: key.clone(),
+            },
+            _ => unreachable!(),
+        };
+        let client = CfClient::new(auth, &config.network)?.with_retry(config.retry_policy());
+
+        println!("\n{}", "🔍 正在验证凭据...".dimmed());
+        if let Err(e) = verify_credentials(&client, config.cloudflare.api_token.is_some()).await {
+            println!("{} {:#}", "✗ 凭据验证失败:".red(), e);
+            let keep = Confirm::with_theme(&theme)
+                .with_prompt("仍然保存这些凭据吗？")
+                .default(false)
+                .interact()?;
+            if !keep {
+                anyhow::bail!("已取消配置，请检查凭据后重新运行 `cfai config setup`");
             }
         }
 
+        config.cloudflare.account_id = select_account(&theme, &client).await?;
+
         // ========== AI 配置 ==========
         println!("\n{}", "🤖 第二步：配置 AI 智能助手 (可选)".bold().green());
         println!("{}", "─".repeat(50).dimmed());
@@ -306,13 +749,17 @@ impl AppConfig {
             println!("\n{}", "支持的 AI 服务：".yellow());
             println!("  • OpenAI (GPT-4, GPT-3.5)");
             println!("  • DeepSeek");
+            println!("  • Anthropic Claude (原生 Messages API)");
+            println!("  • Google Gemini (原生 generateContent API)");
             println!("  • 任何兼容 OpenAI API 的服务");
             println!();
 
             let ai_presets = vec![
                 "OpenAI (https://api.openai.com/v1)",
                 "DeepSeek (https://api.deepseek.com)",
-                "自定义 API 地址"
+                "Anthropic Claude (https://api.anthropic.com/v1)",
+                "Google Gemini (https://generativelanguage.googleapis.com/v1beta)",
+                "自定义 API 地址 (OpenAI 兼容)"
             ];
 
             let ai_preset = Select::with_theme(&theme)
@@ -321,15 +768,24 @@ impl AppConfig {
                 .default(0)
                 .interact()?;
 
+            let provider = match ai_preset {
+                2 => AiProvider::Anthropic,
+                3 => AiProvider::Gemini,
+                _ => AiProvider::OpenAi,
+            };
+            config.ai.provider = match provider {
+                AiProvider::OpenAi => None,
+                _ => Some(provider.name().to_string()),
+            };
+
             let ai_url = match ai_preset {
-                0 => "https://api.openai.com/v1".to_string(),
                 1 => "https://api.deepseek.com".to_string(),
-                2 => {
+                4 => {
                     Input::with_theme(&theme)
                         .with_prompt("请输入自定义 API 地址")
                         .interact_text()?
                 }
-                _ => unreachable!(),
+                _ => provider.default_api_url().to_string(),
             };
             config.ai.api_url = Some(ai_url.clone());
             println!("{}", format!("✓ AI API 地址已设置: {}", ai_url).green());
@@ -347,34 +803,66 @@ impl AppConfig {
             }
 
             // 模型选择
-            let model_options = vec![
-                "gpt-4o (推荐 - 最强大)",
-                "gpt-4o-mini (更快，成本更低)",
-                "gpt-3.5-turbo (经济实惠)",
-                "deepseek-chat",
-                "自定义模型"
-            ];
+            let model_presets: &[(&str, &str)] = match provider {
+                AiProvider::OpenAi => &[
+                    ("gpt-4o", "gpt-4o (推荐 - 最强大)"),
+                    ("gpt-4o-mini", "gpt-4o-mini (更快，成本更低)"),
+                    ("gpt-3.5-turbo", "gpt-3.5-turbo (经济实惠)"),
+                    ("deepseek-chat", "deepseek-chat"),
+                ],
+                AiProvider::Anthropic => &[
+                    ("claude-sonnet-4-5", "claude-sonnet-4-5 (推荐)"),
+                    ("claude-opus-4-1", "claude-opus-4-1 (最强大)"),
+                    ("claude-3-5-haiku-latest", "claude-3-5-haiku-latest (更快，成本更低)"),
+                ],
+                AiProvider::Gemini => &[
+                    ("gemini-2.5-flash", "gemini-2.5-flash (推荐)"),
+                    ("gemini-2.5-pro", "gemini-2.5-pro (最强大)"),
+                    ("gemini-2.5-flash-lite", "gemini-2.5-flash-lite (经济实惠)"),
+                ],
+            };
+            let mut model_options: Vec<&str> = model_presets.iter().map(|(_, label)| *label).collect();
+            model_options.push("自定义模型");
 
+            let default_model = if ai_preset == 1 { 3 } else { 0 };
             let model_choice = Select::with_theme(&theme)
                 .with_prompt("选择 AI 模型")
                 .items(&model_options)
-                .default(0)
+                .default(default_model)
                 .interact()?;
 
-            let model = match model_choice {
-                0 => "gpt-4o".to_string(),
-                1 => "gpt-4o-mini".to_string(),
-                2 => "gpt-3.5-turbo".to_string(),
-                3 => "deepseek-chat".to_string(),
-                4 => {
+            let model = match model_presets.get(model_choice) {
+                Some((name, _)) => name.to_string(),
+                None => {
                     Input::with_theme(&theme)
                         .with_prompt("请输入模型名称")
                         .interact_text()?
                 }
-                _ => unreachable!(),
             };
             config.ai.model = Some(model.clone());
             println!("{}", format!("✓ AI 模型已设置: {}", model).green());
+
+            // 发送 1 个 Token 的测试请求，避免到第一次 `ai ask` 才发现 Key 或模型不可用
+            if config.ai.api_key.is_some() {
+                println!("\n{}", "🔍 正在测试 AI 服务...".dimmed());
+                match AiAnalyzer::new(&config)?.probe().await {
+                    Ok(latency) => println!(
+                        "{}",
+                        format!("✓ 模型 {} 可用 (响应 {} ms)", model, latency.as_millis()).green()
+                    ),
+                    Err(e) => {
+                        println!("{} {:#}", "✗ AI 服务测试失败:".red(), e);
+                        let keep = Confirm::with_theme(&theme)
+                            .with_prompt("仍然保存这些 AI 设置吗？")
+                            .default(false)
+                            .interact()?;
+                        if !keep {
+                            config.ai = AppConfig::default().ai;
+                            println!("{}", "ℹ 已清除 AI 配置，可稍后运行 'cfai config setup' 重新配置".dimmed());
+                        }
+                    }
+                }
+            }
         } else {
             println!("{}", "ℹ 跳过 AI 配置，您可以稍后运行 'cfai config setup' 重新配置".dimmed());
         }
@@ -420,3 +908,104 @@ impl AppConfig {
         Ok(config)
     }
 }
+
+/// 在线验证凭据：Token 显示状态与权限组，Global API Key 显示账户邮箱
+async fn verify_credentials(client: &CfClient, is_token: bool) -> Result<()> {
+    if !is_token {
+        let user = client.get_user().await?;
+        println!(
+            "{}",
+            format!("✓ 凭据有效 ({})", user["email"].as_str().unwrap_or("-")).green()
+        );
+        return Ok(());
+    }
+
+    let verification = client.verify_token_details().await?;
+    if verification.status != "active" {
+        anyhow::bail!("Token 状态为 {}", verification.status);
+    }
+    println!(
+        "{}",
+        format!(
+            "✓ Token 有效{}",
+            verification
+                .expires_on
+                .as_deref()
+                .map(|e| format!(" (过期时间: {})", e))
+                .unwrap_or_default()
+        )
+        .green()
+    );
+
+    match client.get_api_token(&verification.id).await {
+        Ok(token) => {
+            let permissions = token.permission_names();
+            if !permissions.is_empty() {
+                println!("  {} {}", "权限:".dimmed(), permissions.join(", "));
+            }
+        }
+        Err(_) => println!(
+            "  {}",
+            "无法读取 Token 权限 (需要 \"API Tokens Read\" 权限)，可稍后运行 `cfai config token-info` 查看".dimmed()
+        ),
+    }
+    Ok(())
+}
+
+/// 通过 `/accounts` 列出可访问的账户供选择，获取失败时回退为手动输入
+async fn select_account(
+    theme: &dialoguer::theme::ColorfulTheme,
+    client: &CfClient,
+) -> Result<Option<String>> {
+    use dialoguer::{Confirm, Input, Select};
+
+    println!();
+    let accounts = match client.list_accounts().await {
+        Ok(accounts) if !accounts.is_empty() => accounts,
+        result => {
+            if let Err(e) = result {
+                println!("{} {:#}", "⚠ 无法自动获取账户列表:".yellow(), e);
+            }
+            let need_account_id = Confirm::with_theme(theme)
+                .with_prompt("是否需要配置 Account ID？(Workers / R2 / Access 等功能需要)")
+                .default(false)
+                .interact()?;
+            if !need_account_id {
+                return Ok(None);
+            }
+
+            println!("\n{}", "获取 Account ID:".yellow());
+            println!("  1. 访问: {}", "https://dash.cloudflare.com/".cyan());
+            println!("  2. 在右侧边栏可以找到 Account ID");
+            println!();
+
+            let account_id: String = Input::with_theme(theme)
+                .with_prompt("请输入 Account ID")
+                .allow_empty(true)
+                .interact_text()?;
+            let account_id = account_id.trim();
+            if account_id.is_empty() {
+                return Ok(None);
+            }
+            println!("{}", "✓ Account ID 已设置".green());
+            return Ok(Some(account_id.to_string()));
+        }
+    };
+
+    let mut items: Vec<String> = accounts
+        .iter()
+        .map(|a| format!("{} ({})", a.name, a.id))
+        .collect();
+    items.push("不设置 Account ID".to_string());
+
+    let choice = Select::with_theme(theme)
+        .with_prompt("选择默认账户 (Workers / R2 / Access 等功能需要)")
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    Ok(accounts.get(choice).map(|a| {
+        println!("{}", format!("✓ 已选择账户: {}", a.name).green());
+        a.id.clone()
+    }))
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::settings::{AppConfig, CloudflareConfig};
+use crate::models::zone::Zone;
+
+/// 域名列表缓存的有效期 (秒)
+pub const ZONE_CACHE_TTL_SECS: i64 = 300;
+
+/// 域名 → Zone ID 映射的有效期 (秒)，Zone ID 在域名删除重建前不会变化
+pub const ZONE_ID_CACHE_TTL_SECS: i64 = 7 * 24 * 3600;
+
+/// 串行化同一进程内的读写
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 缓存文件: 按 Profile 分别保存域名列表与域名对应的 Zone ID
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct ZoneCacheFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, CachedZones>,
+    #[serde(default)]
+    zone_ids: BTreeMap<String, BTreeMap<String, CachedZoneId>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedZones {
+    /// 获取时间 (Unix 秒)
+    fetched_at: i64,
+    zones: Vec<CachedZone>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedZoneId {
+    id: String,
+    /// 获取时间 (Unix 秒)
+    fetched_at: i64,
+}
+
+/// 缓存的域名
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CachedZone {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+}
+
+/// 缓存文件路径
+pub fn path() -> Result<PathBuf> {
+    Ok(AppConfig::config_dir()?.join("zone_cache.json"))
+}
+
+/// Profile 与凭据对应的缓存键 (未使用 Profile 时为 default)
+///
+/// 附带凭据指纹 (FNV-1a，不保存凭据本身)，`config setup` 切换账户或更换 Token 后不会读到旧账户的缓存
+pub fn cache_key(profile: Option<&str>, credentials: &CloudflareConfig) -> String {
+    let identity = [&credentials.api_token, &credentials.email, &credentials.api_key]
+        .iter()
+        .map(|v| v.as_deref().unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let fingerprint = identity
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+    format!("{}@{:016x}", profile.unwrap_or("default"), fingerprint)
+}
+
+/// 读取未过期的域名列表，缓存不存在、已过期或损坏时返回 None
+pub fn load(key: &str) -> Option<Vec<CachedZone>> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let cached = read().ok()?.profiles.remove(key)?;
+    let age = chrono::Utc::now().timestamp() - cached.fetched_at;
+    (0..ZONE_CACHE_TTL_SECS).contains(&age).then_some(cached.zones)
+}
+
+/// 保存域名列表
+pub fn store(key: &str, zones: &[Zone]) -> Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = read().unwrap_or_default();
+    file.profiles.insert(
+        key.to_string(),
+        CachedZones {
+            fetched_at: chrono::Utc::now().timestamp(),
+            zones: zones
+                .iter()
+                .map(|z| CachedZone {
+                    id: z.id.clone(),
+                    name: z.name.clone(),
+                    status: z.status.clone(),
+                })
+                .collect(),
+        },
+    );
+    write(&file)
+}
+
+/// 读取域名对应的 Zone ID，未命中时再从未过期的域名列表中查找
+pub fn load_zone_id(key: &str, domain: &str) -> Option<String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let file = read().ok()?;
+    let domain = domain.to_lowercase();
+    let now = chrono::Utc::now().timestamp();
+
+    if let Some(cached) = file.zone_ids.get(key).and_then(|ids| ids.get(&domain)) {
+        if (0..ZONE_ID_CACHE_TTL_SECS).contains(&(now - cached.fetched_at)) {
+            return Some(cached.id.clone());
+        }
+    }
+    let zones = file.profiles.get(key)?;
+    if !(0..ZONE_CACHE_TTL_SECS).contains(&(now - zones.fetched_at)) {
+        return None;
+    }
+    zones.zones.iter().find(|z| z.name == domain).map(|z| z.id.clone())
+}
+
+/// 保存域名对应的 Zone ID
+pub fn store_zone_id(key: &str, domain: &str, zone_id: &str) -> Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = read().unwrap_or_default();
+    file.zone_ids.entry(key.to_string()).or_default().insert(
+        domain.to_lowercase(),
+        CachedZoneId {
+            id: zone_id.to_string(),
+            fetched_at: chrono::Utc::now().timestamp(),
+        },
+    );
+    write(&file)
+}
+
+/// 移除指定 Zone 的所有缓存 (域名被删除后调用)
+pub fn forget_zone(key: &str, zone_id: &str) -> Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let Ok(mut file) = read() else {
+        return Ok(());
+    };
+    if let Some(ids) = file.zone_ids.get_mut(key) {
+        ids.retain(|_, cached| cached.id != zone_id);
+    }
+    if let Some(cached) = file.profiles.get_mut(key) {
+        cached.zones.retain(|z| z.id != zone_id);
+    }
+    write(&file)
+}
+
+/// 删除缓存文件，返回缓存是否存在
+pub fn clear() -> Result<bool> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = path()?;
+    if !path.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_file(&path).with_context(|| format!("删除缓存失败: {}", path.display()))?;
+    Ok(true)
+}
+
+fn read() -> Result<ZoneCacheFile> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(ZoneCacheFile::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("读取域名缓存失败: {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("解析域名缓存失败: {}", path.display()))
+}
+
+fn write(file: &ZoneCacheFile) -> Result<()> {
+    let path = path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(file)?;
+    std::fs::write(&path, content).with_context(|| format!("写入域名缓存失败: {}", path.display()))
+}
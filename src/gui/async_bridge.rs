@@ -1,23 +1,131 @@
 use std::future::Future;
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 
-use super::state::AsyncResult;
+use tokio::sync::Notify;
+
+use super::state::{AppState, AsyncResult};
+use crate::progress::{ProgressReporter, ProgressState};
+
+/// Cancellation flag shared by every task spawned under it.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<CancelInner>);
+
+#[derive(Default)]
+struct CancelInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the token is cancelled.
+    pub async fn cancelled(&self) {
+        loop {
+            // Register before checking the flag so a concurrent cancel() can't be missed
+            let notified = self.0.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Result channel message, tagged with the token of the task that produced it
+pub type TaggedResult = (CancelToken, AsyncResult);
+
+/// Sends results to the UI thread unless their token has been cancelled.
+#[derive(Clone)]
+pub struct ResultSender {
+    tx: mpsc::Sender<TaggedResult>,
+    token: CancelToken,
+}
+
+impl ResultSender {
+    pub fn new(tx: mpsc::Sender<TaggedResult>, token: CancelToken) -> Self {
+        Self { tx, token }
+    }
+
+    pub fn send(&self, result: AsyncResult) {
+        if !self.token.is_cancelled() {
+            let _ = self.tx.send((self.token.clone(), result));
+        }
+    }
+}
+
+/// Forwards progress updates to the status bar.
+pub struct ProgressSender {
+    sender: ResultSender,
+    ctx: eframe::egui::Context,
+}
+
+impl ProgressSender {
+    pub fn new(state: &AppState, ctx: &eframe::egui::Context) -> Arc<Self> {
+        Arc::new(Self {
+            sender: state.result_sender(),
+            ctx: ctx.clone(),
+        })
+    }
+
+    fn send(&self, state: &ProgressState) {
+        self.sender.send(AsyncResult::Progress(state.clone()));
+        self.ctx.request_repaint();
+    }
+}
+
+impl ProgressReporter for ProgressSender {
+    fn item_started(&self, state: &ProgressState) {
+        self.send(state);
+    }
+
+    fn item_finished(&self, state: &ProgressState, _item: &str, _error: Option<&str>) {
+        self.send(state);
+    }
+
+    // The task's own result clears the indicator
+    fn finished(&self, _state: &ProgressState) {}
+}
 
 /// Spawn an async task from the synchronous egui update() context.
-pub fn spawn_async<F, Fut>(
+///
+/// The task is bound to the current page's cancellation token: it is aborted,
+/// and its result dropped, when the user navigates away or clicks Cancel.
+pub fn spawn_async<F, Fut>(state: &AppState, ctx: &eframe::egui::Context, f: F)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = AsyncResult> + Send + 'static,
+{
+    spawn_with(&state.tokio_handle, state.result_sender(), ctx, f);
+}
+
+/// Spawn an async task whose lifetime is tied to `sender`'s token.
+pub fn spawn_with<F, Fut>(
     handle: &tokio::runtime::Handle,
-    tx: &mpsc::Sender<AsyncResult>,
+    sender: ResultSender,
     ctx: &eframe::egui::Context,
     f: F,
 ) where
     F: FnOnce() -> Fut + Send + 'static,
     Fut: Future<Output = AsyncResult> + Send + 'static,
 {
-    let tx = tx.clone();
     let ctx = ctx.clone();
     handle.spawn(async move {
-        let result = f().await;
-        let _ = tx.send(result);
-        ctx.request_repaint();
+        let token = sender.token.clone();
+        tokio::select! {
+            result = f() => {
+                sender.send(result);
+                ctx.request_repaint();
+            }
+            _ = token.cancelled() => {}
+        }
     });
 }
@@ -1,4 +1,5 @@
 mod async_bridge;
+mod monitor;
 mod pages;
 mod router;
 mod state;
@@ -23,14 +24,17 @@ impl eframe::App for CfaiApp {
         // 1. Drain async results
         self.drain_results();
 
-        // 2. Expire notifications
+        // 2. Expire notifications, refresh stale page data, keep the monitor in sync
         self.state.notifications.retain(|n| !n.is_expired());
+        self.auto_refresh(ctx);
+        self.sync_monitor(ctx);
+        self.minimize_instead_of_close(ctx);
 
         // 3. Render sidebar
         let page_changed = router::render_sidebar(&mut self.state, ctx);
 
         // 4. Status bar
-        widgets::status_bar::render_status_bar(&self.state, ctx);
+        widgets::status_bar::render_status_bar(&mut self.state, ctx);
 
         // 5. Central panel with current page
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -38,6 +42,7 @@ impl eframe::App for CfaiApp {
                 match self.state.current_page {
                     Page::Dashboard => pages::dashboard::render(&mut self.state, ctx, ui),
                     Page::Zone => pages::zone::render(&mut self.state, ctx, ui),
+                    Page::Compare => pages::compare::render(&mut self.state, ctx, ui),
                     Page::Dns => pages::dns::render(&mut self.state, ctx, ui),
                     Page::Ssl => pages::ssl::render(&mut self.state, ctx, ui),
                     Page::Firewall => pages::firewall::render(&mut self.state, ctx, ui),
@@ -53,6 +58,8 @@ impl eframe::App for CfaiApp {
 
         // 6. Overlays
         widgets::notification::render_notifications(&mut self.state, ctx);
+        widgets::notification::render_notification_center(&mut self.state, ctx);
+        let palette_changed = widgets::command_palette::render_command_palette(&mut self.state, ctx);
         widgets::confirm_dialog::render_confirm_dialog(&mut self.state, ctx);
 
         // 7. Auto-load zones on first frame
@@ -61,9 +68,10 @@ impl eframe::App for CfaiApp {
             pages::dashboard::load_zones(&mut self.state, ctx);
             // Verify connection
             let client = self.state.client.as_ref().unwrap().clone();
-            async_bridge::spawn_async(
+            // Not tied to the dashboard: navigating away must not lose the connection status
+            async_bridge::spawn_with(
                 &self.state.tokio_handle,
-                &self.state.tx,
+                self.state.detached_sender(),
                 ctx,
                 move || async move {
                     let result = client.verify_token().await;
@@ -73,24 +81,155 @@ impl eframe::App for CfaiApp {
         }
 
         // 8. Load data when page changes or zone changes
-        if page_changed {
+        if page_changed || palette_changed {
             self.on_page_enter(ctx);
         }
     }
 }
 impl CfaiApp {
+    /// (Re)start the background monitor when its zones or settings change
+    fn sync_monitor(&mut self, ctx: &egui::Context) {
+        let settings = self.state.config.gui.monitor.clone();
+        let targets: Vec<monitor::MonitorTarget> = match (&self.state.client, settings.enabled) {
+            (Some(_), true) if settings.zones.is_empty() => self.state.selected_zone.iter().cloned().collect(),
+            (Some(_), true) => self
+                .state
+                .zones
+                .iter()
+                .filter(|z| settings.zones.iter().any(|name| name.eq_ignore_ascii_case(&z.name)))
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        }
+        .into_iter()
+        .map(|z| monitor::MonitorTarget { zone_id: z.id, name: z.name })
+        .collect();
+
+        let key = format!(
+            "{}|{:?}",
+            targets.iter().map(|t| t.zone_id.as_str()).collect::<Vec<_>>().join(","),
+            settings
+        );
+        if key == self.state.monitor_key {
+            return;
+        }
+        self.state.monitor_key = key;
+        if let Some(token) = self.state.monitor_cancel.take() {
+            token.cancel();
+        }
+        self.state.monitor_zones = targets.iter().map(|t| t.name.clone()).collect();
+        let Some(client) = self.state.client.clone().filter(|_| !targets.is_empty()) else {
+            return;
+        };
+        let token = async_bridge::CancelToken::default();
+        monitor::start(
+            &self.state.tokio_handle,
+            client,
+            targets,
+            settings,
+            async_bridge::ResultSender::new(self.state.tx.clone(), token.clone()),
+            token.clone(),
+            ctx,
+        );
+        self.state.monitor_cancel = Some(token);
+    }
+
+    /// While monitoring, closing the window minimizes it; Quit in the status bar exits
+    fn minimize_instead_of_close(&mut self, ctx: &egui::Context) {
+        if self.state.monitor_cancel.is_none()
+            || self.state.quit_requested
+            || !ctx.input(|i| i.viewport().close_requested())
+        {
+            return;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+        if !self.state.background_hint_shown {
+            self.state.background_hint_shown = true;
+            monitor::desktop_notify(
+                "cfai is still monitoring",
+                &format!(
+                    "Watching {} for traffic spikes. Use Quit in the status bar to exit.",
+                    self.state.monitor_zones.join(", ")
+                ),
+            );
+        }
+    }
+
+    /// Reload the current page's data once its refresh interval has elapsed
+    fn auto_refresh(&mut self, ctx: &egui::Context) {
+        let Some(feed) = DataFeed::for_page(&self.state.current_page, &self.state.firewall_tab) else {
+            return;
+        };
+        let Some(secs) = self.state.config.gui.refresh_secs(feed.config_key()) else {
+            return;
+        };
+        let interval = std::time::Duration::from_secs(secs);
+        let status = self.state.feeds.entry(feed).or_default();
+        // The first load happens on page enter; wait for it before scheduling refreshes
+        let Some(finished) = status.finished else {
+            return;
+        };
+        if status.refreshing {
+            return;
+        }
+        let elapsed = finished.elapsed();
+        if elapsed < interval {
+            ctx.request_repaint_after(interval - elapsed);
+            return;
+        }
+        if self.refresh_feed(feed, ctx) {
+            self.state.feeds.entry(feed).or_default().refreshing = true;
+        }
+    }
+
+    /// Start loading a feed; returns false when it can't be loaded (no zone or account)
+    fn refresh_feed(&mut self, feed: DataFeed, ctx: &egui::Context) -> bool {
+        if feed == DataFeed::Zones {
+            pages::dashboard::load_zones(&mut self.state, ctx);
+            return true;
+        }
+        if feed == DataFeed::Workers {
+            let Some(aid) = self.state.config.cloudflare.account_id.clone() else {
+                return false;
+            };
+            pages::workers::load_workers(&mut self.state, ctx, &aid);
+            return true;
+        }
+        let Some(zid) = self.state.zone_id() else {
+            return false;
+        };
+        match feed {
+            DataFeed::Dns => pages::dns::load_dns(&mut self.state, ctx, &zid),
+            DataFeed::Ssl => pages::ssl::load_ssl_status(&mut self.state, ctx, &zid),
+            DataFeed::Firewall => pages::firewall::load_firewall(&mut self.state, ctx, &zid),
+            DataFeed::FirewallEvents => pages::firewall::load_firewall_events(&mut self.state, ctx, &zid),
+            DataFeed::Cache => pages::cache::load_cache_status(&mut self.state, ctx, &zid),
+            DataFeed::PageRules => pages::page_rules::load_page_rules(&mut self.state, ctx, &zid),
+            DataFeed::Analytics => pages::analytics::load_analytics(&mut self.state, ctx, &zid),
+            DataFeed::Zones | DataFeed::Workers => unreachable!(),
+        }
+        true
+    }
+
     fn on_page_enter(&mut self, ctx: &egui::Context) {
+        // Loads for the previous page / zone would overwrite the new page's state
+        self.state.cancel_pending();
+        // Freshness belongs to the previous zone / page load
+        self.state.feeds.clear();
         let zone_id = self.state.zone_id();
         match self.state.current_page {
             Page::Dashboard => {
                 pages::dashboard::load_zones(&mut self.state, ctx);
             }
-            Page::Zone => {
+            Page::Zone | Page::Compare => {
                 // Zones already loaded from dashboard
             }
             Page::Dns => {
                 if let Some(zid) = &zone_id {
                     pages::dns::load_dns(&mut self.state, ctx, zid);
+                    self.state.dnssec = None;
+                    pages::dns::load_dnssec(&mut self.state, ctx, zid);
                 }
             }
             Page::Ssl => {
@@ -101,6 +240,10 @@ impl CfaiApp {
             Page::Firewall => {
                 if let Some(zid) = &zone_id {
                     pages::firewall::load_firewall(&mut self.state, ctx, zid);
+                    self.state.firewall_events.clear();
+                    if self.state.firewall_tab == FirewallTab::Events {
+                        pages::firewall::load_firewall_events(&mut self.state, ctx, zid);
+                    }
                 }
             }
             Page::Cache => {
@@ -127,9 +270,36 @@ impl CfaiApp {
         }
     }
 
+    /// Summarize a DNS bulk action, quoting the first failure
+    fn notify_bulk(&mut self, verb: &str, done: usize, errors: &[String]) {
+        match errors.first() {
+            None => self.state.notify(format!("{} DNS records {}", done, verb), NotifLevel::Success),
+            Some(first) => self.state.notify(
+                format!("{} DNS records {}, {} failed: {}", done, verb, errors.len(), first),
+                if done > 0 { NotifLevel::Warning } else { NotifLevel::Error },
+            ),
+        }
+    }
+
     fn drain_results(&mut self) {
-        while let Ok(result) = self.state.rx.try_recv() {
+        while let Ok((token, result)) = self.state.rx.try_recv() {
+            // Sent just before the task was cancelled
+            if token.is_cancelled() {
+                continue;
+            }
+            if let AsyncResult::MonitorAlert(zone, message) = result {
+                self.state.notify(format!("{}: {}", zone, message), NotifLevel::Warning);
+                continue;
+            }
+            if let AsyncResult::Progress(progress) = result {
+                self.state.progress = Some(progress);
+                continue;
+            }
             self.state.clear_loading();
+            self.state.background_result = match result.feed_outcome() {
+                Some((feed, error)) => self.state.record_feed(feed, error),
+                None => false,
+            };
             match result {
                 AsyncResult::ZonesLoaded(res) => match res {
                     Ok(zones) => {
@@ -173,8 +343,18 @@ impl CfaiApp {
                     Ok(settings) => self.state.zone_settings = settings,
                     Err(e) => self.state.notify(format!("Load settings failed: {}", e), NotifLevel::Error),
                 },
+                AsyncResult::ZonesCompared(res) => match res {
+                    Ok(pair) => self.state.compare_result = Some(pair),
+                    Err(e) => self.state.notify(format!("Compare failed: {}", e), NotifLevel::Error),
+                },
+                AsyncResult::ZoneHealthLoaded(health) => self.state.zone_health = health,
                 AsyncResult::DnsRecordsLoaded(res) => match res {
-                    Ok(records) => self.state.dns_records = records,
+                    Ok(records) => {
+                        self.state
+                            .dns_selected
+                            .retain(|id| records.iter().any(|r| r.id.as_deref() == Some(id)));
+                        self.state.dns_records = records;
+                    }
                     Err(e) => self.state.notify(format!("Load DNS failed: {}", e), NotifLevel::Error),
                 },
                 AsyncResult::DnsRecordCreated(res) => match res {
@@ -192,9 +372,41 @@ impl CfaiApp {
                             *r = record;
                         }
                         self.state.dns_edit_form = None;
+                        self.state.dns_inline_edit = None;
                     }
                     Err(e) => self.state.notify(format!("Update DNS failed: {}", e), NotifLevel::Error),
                 },
+                AsyncResult::DnsBulkUpdated(results) => {
+                    let mut errors = Vec::new();
+                    let mut updated = 0;
+                    for res in results {
+                        match res {
+                            Ok(record) => {
+                                if let Some(r) = self.state.dns_records.iter_mut().find(|r| r.id == record.id) {
+                                    *r = record;
+                                }
+                                updated += 1;
+                            }
+                            Err(e) => errors.push(e.to_string()),
+                        }
+                    }
+                    self.notify_bulk("updated", updated, &errors);
+                }
+                AsyncResult::DnsBulkDeleted(results) => {
+                    let mut errors = Vec::new();
+                    let mut deleted = 0;
+                    for res in results {
+                        match res {
+                            Ok(id) => {
+                                self.state.dns_records.retain(|r| r.id.as_deref() != Some(&id));
+                                self.state.dns_selected.remove(&id);
+                                deleted += 1;
+                            }
+                            Err(e) => errors.push(e.to_string()),
+                        }
+                    }
+                    self.notify_bulk("deleted", deleted, &errors);
+                }
                 AsyncResult::DnsRecordDeleted(res) => match res {
                     Ok(id) => {
                         self.state.dns_records.retain(|r| r.id.as_deref() != Some(&id));
@@ -213,6 +425,10 @@ impl CfaiApp {
                     }
                     Err(e) => self.state.notify(format!("Export failed: {}", e), NotifLevel::Error),
                 },
+                AsyncResult::DnssecLoaded(res) => match res {
+                    Ok(dnssec) => self.state.dnssec = Some(dnssec),
+                    Err(e) => self.state.notify(format!("Load DNSSEC failed: {}", e), NotifLevel::Error),
+                },
                 AsyncResult::SslStatusLoaded(res) => match res {
                     Ok((mode, https, min_tls)) => {
                         self.state.ssl_mode = mode;
@@ -267,6 +483,28 @@ impl CfaiApp {
                     Ok(limits) => self.state.rate_limits = limits,
                     Err(e) => self.state.notify(format!("Load rate limits failed: {}", e), NotifLevel::Error),
                 },
+                AsyncResult::FirewallEventsLoaded(res) => match res {
+                    Ok(events) => self.state.firewall_events = events,
+                    Err(e) => self.state.notify(format!("Load firewall events failed: {}", e), NotifLevel::Error),
+                },
+                AsyncResult::RateLimitSaved(res) => match res {
+                    Ok(rule) => {
+                        self.state.notify("Rate limit saved", NotifLevel::Success);
+                        match self.state.rate_limits.iter_mut().find(|r| r.id == rule.id) {
+                            Some(r) => *r = rule,
+                            None => self.state.rate_limits.push(rule),
+                        }
+                        self.state.rl_form = RateLimitForm::default();
+                    }
+                    Err(e) => self.state.notify(format!("Save rate limit failed: {}", e), NotifLevel::Error),
+                },
+                AsyncResult::RateLimitDeleted(res) => match res {
+                    Ok(id) => {
+                        self.state.rate_limits.retain(|r| r.id.as_deref() != Some(&id));
+                        self.state.notify("Rate limit deleted", NotifLevel::Success);
+                    }
+                    Err(e) => self.state.notify(format!("Delete rate limit failed: {}", e), NotifLevel::Error),
+                },
                 AsyncResult::FirewallActionDone(res) => match res {
                     Ok(msg) => self.state.notify(msg, NotifLevel::Success),
                     Err(e) => self.state.notify(format!("Firewall action failed: {}", e), NotifLevel::Error),
@@ -291,9 +529,16 @@ impl CfaiApp {
                     Ok(rules) => self.state.page_rules = rules,
                     Err(e) => self.state.notify(format!("Load page rules failed: {}", e), NotifLevel::Error),
                 },
-                AsyncResult::PageRuleCreated(res) => match res {
-                    Ok(msg) => self.state.notify(msg, NotifLevel::Success),
-                    Err(e) => self.state.notify(format!("Create page rule failed: {}", e), NotifLevel::Error),
+                AsyncResult::PageRuleSaved(res) => match res {
+                    Ok(rule) => {
+                        self.state.notify("Page rule saved", NotifLevel::Success);
+                        match self.state.page_rules.iter_mut().find(|r| r.id == rule.id) {
+                            Some(r) => *r = rule,
+                            None => self.state.page_rules.push(rule),
+                        }
+                        self.state.page_rule_form = None;
+                    }
+                    Err(e) => self.state.notify(format!("Save page rule failed: {}", e), NotifLevel::Error),
                 },
                 AsyncResult::PageRuleDeleted(res) => match res {
                     Ok(id) => {
@@ -325,12 +570,29 @@ impl CfaiApp {
                     }
                     Err(e) => self.state.notify(format!("Delete worker failed: {}", e), NotifLevel::Error),
                 },
+                AsyncResult::WorkerStatsLoaded(res) => match res {
+                    Ok(stats) => self.state.worker_stats = Some(stats),
+                    Err(e) => self.state.notify(format!("Load worker stats failed: {}", e), NotifLevel::Error),
+                },
                 AsyncResult::AnalyticsLoaded(res) => match res {
                     Ok(dashboard) => self.state.analytics = Some(dashboard),
                     Err(e) => self.state.notify(format!("Load analytics failed: {}", e), NotifLevel::Error),
                 },
+                AsyncResult::DnsAnalyticsLoaded(res) => match res {
+                    Ok(dns) => self.state.dns_analytics = Some(dns),
+                    Err(e) => self.state.notify(format!("Load DNS analytics failed: {}", e), NotifLevel::Error),
+                },
+                AsyncResult::AiToken(text) => self.state.ai_streaming.push_str(&text),
+                AsyncResult::AiActionApplied(msg_idx, action_idx, res) => match res {
+                    Ok(msg) => {
+                        self.state.ai_applied.insert((msg_idx, action_idx));
+                        self.state.notify(msg, NotifLevel::Success);
+                    }
+                    Err(e) => self.state.notify(format!("Apply action failed: {}", e), NotifLevel::Error),
+                },
                 AsyncResult::AiResponse(res) => match res {
                     Ok(result) => {
+                        self.state.ai_streaming.clear();
                         self.state.ai_messages.push(AiChatMessage {
                             role: "assistant".to_string(),
                             content: result.content,
@@ -338,6 +600,7 @@ impl CfaiApp {
                         });
                     }
                     Err(e) => {
+                        self.state.ai_streaming.clear();
                         self.state.ai_messages.push(AiChatMessage {
                             role: "assistant".to_string(),
                             content: format!("Error: {}", e),
@@ -363,20 +626,24 @@ impl CfaiApp {
                         self.state.notify(format!("Verify failed: {}", e), NotifLevel::Error);
                     }
                 },
+                // Handled above without touching the loading indicator
+                AsyncResult::MonitorAlert(..) | AsyncResult::Progress(_) => {}
             }
         }
+        self.state.background_result = false;
     }
 }
 
 /// Launch the GUI window
-pub fn launch_gui() -> Result<()> {
-    let config = AppConfig::load()?.merge_env();
+pub fn launch_gui(profile: Option<&str>) -> Result<()> {
+    let config = AppConfig::load_effective(profile)?;
 
     let client = create_client_if_configured(&config);
 
     let handle = tokio::runtime::Handle::current();
 
-    let state = AppState::new(config, client, handle);
+    let mut state = AppState::new(config, client, handle);
+    state.profile = profile.map(|p| p.to_string());
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -398,15 +665,23 @@ pub fn launch_gui() -> Result<()> {
 }
 
 fn create_client_if_configured(config: &AppConfig) -> Option<CfClient> {
-    if let Some(token) = &config.cloudflare.api_token {
-        CfClient::new(AuthMethod::ApiToken(token.clone())).ok()
+    let client = if let Some(token) = &config.cloudflare.api_token {
+        CfClient::new(AuthMethod::ApiToken(token.clone()), &config.network).ok()
     } else if let (Some(email), Some(key)) = (&config.cloudflare.email, &config.cloudflare.api_key) {
-        CfClient::new(AuthMethod::ApiKey {
-            email: email.clone(),
-            key: key.clone(),
-        })
+        CfClient::new(
+            AuthMethod::ApiKey {
+                email: email.clone(),
+                key: key.clone(),
+            },
+            &config.network,
+        )
         .ok()
     } else {
         None
-    }
+    };
+    client.map(|c| {
+        c.with_retry(config.retry_policy())
+            .with_audit(true)
+            .with_response_cache(crate::response_cache(config, None, false))
+    })
 }
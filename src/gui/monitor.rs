@@ -0,0 +1,186 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use eframe::egui;
+
+use super::async_bridge::{CancelToken, ResultSender};
+use super::state::AsyncResult;
+use crate::api::client::CfClient;
+use crate::config::settings::GuiMonitorConfig;
+use crate::models::analytics::LiveAnalytics;
+
+/// Samples averaged into the baseline a new sample is compared against
+const BASELINE_SAMPLES: usize = 10;
+
+/// Samples needed before spikes are reported
+const MIN_BASELINE_SAMPLES: usize = 3;
+
+/// Statistics window for each poll (minutes)
+const WINDOW_MINUTES: i64 = 5;
+
+/// Zone polled by the background monitor
+#[derive(Clone)]
+pub struct MonitorTarget {
+    pub zone_id: String,
+    pub name: String,
+}
+
+/// Detects traffic spikes and threat surges against a zone's recent average
+#[derive(Default)]
+pub struct SurgeDetector {
+    /// (requests per second, threats) of recent normal samples
+    history: VecDeque<(f64, u64)>,
+    alerting: bool,
+}
+
+impl SurgeDetector {
+    /// Feed a sample; returns the alert message when the zone enters an alert state
+    pub fn observe(&mut self, live: &LiveAnalytics, settings: &GuiMonitorConfig) -> Option<String> {
+        let rps = live.requests_per_second();
+        let threats = live.threats;
+        let mut reasons = Vec::new();
+
+        if self.history.len() >= MIN_BASELINE_SAMPLES {
+            let count = self.history.len() as f64;
+            let base_rps = self.history.iter().map(|(r, _)| r).sum::<f64>() / count;
+            let base_threats = self.history.iter().map(|(_, t)| *t as f64).sum::<f64>() / count;
+            let factor = settings.spike_factor();
+
+            if rps >= settings.min_requests_per_second() && rps >= base_rps * factor {
+                reasons.push(format!("traffic spike {:.1} req/s (usually {:.1})", rps, base_rps));
+            }
+            if threats >= settings.min_threats() && threats as f64 >= base_threats * factor {
+                reasons.push(format!("threat surge {} threats (usually {:.0})", threats, base_threats));
+            }
+        }
+
+        let alert = !reasons.is_empty();
+        // A spike must not raise the baseline it is measured against
+        if !alert {
+            self.history.push_back((rps, threats));
+            if self.history.len() > BASELINE_SAMPLES {
+                self.history.pop_front();
+            }
+        }
+        let entered = alert && !self.alerting;
+        self.alerting = alert;
+        entered.then(|| format!("{} in the last {} minutes", reasons.join(", "), WINDOW_MINUTES))
+    }
+}
+
+/// Poll the targets until `token` is cancelled, raising desktop notifications on spikes.
+///
+/// Runs on the tokio runtime rather than per frame so it keeps working while the
+/// window is minimized and egui stops repainting.
+pub fn start(
+    handle: &tokio::runtime::Handle,
+    client: CfClient,
+    targets: Vec<MonitorTarget>,
+    settings: GuiMonitorConfig,
+    sender: ResultSender,
+    token: CancelToken,
+    ctx: &egui::Context,
+) {
+    let ctx = ctx.clone();
+    handle.spawn(async move {
+        let mut detectors: HashMap<String, SurgeDetector> = HashMap::new();
+        let interval = Duration::from_secs(settings.interval_secs().max(10));
+        loop {
+            for target in &targets {
+                let live = tokio::select! {
+                    live = client.get_live_analytics(&target.zone_id, WINDOW_MINUTES) => live,
+                    _ = token.cancelled() => return,
+                };
+                // Failed polls are retried on the next round; they aren't alerts
+                let Ok(live) = live else {
+                    continue;
+                };
+                let detector = detectors.entry(target.zone_id.clone()).or_default();
+                if let Some(message) = detector.observe(&live, &settings) {
+                    desktop_notify(&format!("cfai: {} alert", target.name), &message);
+                    sender.send(AsyncResult::MonitorAlert(target.name.clone(), message));
+                    ctx.request_repaint();
+                }
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = token.cancelled() => return,
+            }
+        }
+    });
+}
+
+/// Show a native desktop notification using the platform's notifier
+pub fn desktop_notify(title: &str, body: &str) {
+    use std::process::{Command, Stdio};
+
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            body.replace('"', "'"),
+            title.replace('"', "'")
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(target_os = "windows") {
+        let escape = |s: &str| s.replace('\'', "''");
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Warning; $n.Visible = $true; \
+             $n.ShowBalloonTip(10000, '{}', '{}', 'Warning'); Start-Sleep -Seconds 10; $n.Dispose()",
+            escape(title),
+            escape(body)
+        );
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", &script]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=cfai", "--urgency=critical", title, body]);
+        command
+    };
+    // Best effort: a missing notifier must not affect monitoring
+    let _ = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(requests: u64, threats: u64) -> LiveAnalytics {
+        LiveAnalytics {
+            since: String::new(),
+            until: String::new(),
+            window_secs: 100,
+            requests,
+            cached_requests: 0,
+            bytes: 0,
+            threats,
+            per_minute: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_surge_detector_alerts_once_per_spike() {
+        let settings = GuiMonitorConfig::default();
+        let mut detector = SurgeDetector::default();
+        for _ in 0..MIN_BASELINE_SAMPLES {
+            assert!(detector.observe(&sample(1000, 5), &settings).is_none());
+        }
+
+        let alert = detector.observe(&sample(5000, 5), &settings).unwrap();
+        assert!(alert.contains("traffic spike 50.0 req/s (usually 10.0)"));
+        assert!(detector.observe(&sample(6000, 5), &settings).is_none());
+
+        // Back to normal, then a threat surge alerts again
+        assert!(detector.observe(&sample(1000, 5), &settings).is_none());
+        let alert = detector.observe(&sample(1000, 40), &settings).unwrap();
+        assert!(alert.starts_with("threat surge 40 threats"));
+    }
+}
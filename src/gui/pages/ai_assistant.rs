@@ -1,9 +1,11 @@
 use eframe::egui;
 
-use crate::ai::analyzer::AiAnalyzer;
+use crate::ai::analyzer::{AiAnalyzer, SuggestedAction};
+use crate::ai::safety::{self, LiveZoneState};
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
 use crate::gui::theme;
+use crate::gui::widgets::confirm_dialog;
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.heading("AI Assistant");
@@ -28,6 +30,8 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.add_space(4.0);
 
     // Chat messages
+    let zone_selected = state.selected_zone.is_some();
+    let mut apply: Option<(usize, usize)> = None;
     let scroll_height = ui.available_height() - 60.0;
     egui::ScrollArea::vertical()
         .id_salt("ai_chat")
@@ -37,7 +41,7 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
             if state.ai_messages.is_empty() {
                 ui.label(egui::RichText::new("Ask me anything about Cloudflare...").weak());
             }
-            for msg in &state.ai_messages {
+            for (msg_idx, msg) in state.ai_messages.iter().enumerate() {
                 let is_user = msg.role == "user";
                 let bg = if is_user {
                     egui::Color32::from_rgb(55, 65, 81)
@@ -68,7 +72,7 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                                 if !actions.is_empty() {
                                     ui.add_space(4.0);
                                     ui.label(egui::RichText::new("Suggested Actions:").strong());
-                                    for action in actions {
+                                    for (action_idx, action) in actions.iter().enumerate() {
                                         let risk_color = match action.risk.as_str() {
                                             "low" => theme::SUCCESS,
                                             "medium" => theme::WARNING,
@@ -77,8 +81,35 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                                         };
                                         ui.horizontal(|ui| {
                                             ui.label(egui::RichText::new(format!("[{}]", action.risk)).color(risk_color).small());
+                                            if action.blocked {
+                                                ui.label(egui::RichText::new("[blocked]").color(theme::DANGER).small());
+                                            }
                                             ui.label(egui::RichText::new(&action.description).small());
+                                            if state.ai_applied.contains(&(msg_idx, action_idx)) {
+                                                ui.label(egui::RichText::new("Applied").color(theme::SUCCESS).small());
+                                            } else {
+                                                let hint = if action.blocked {
+                                                    "Blocked by safety review"
+                                                } else if !zone_selected {
+                                                    "Select a zone first"
+                                                } else {
+                                                    "Apply this action to the selected zone"
+                                                };
+                                                let button = ui
+                                                    .add_enabled(
+                                                        zone_selected && !action.blocked && !state.loading,
+                                                        egui::Button::new(egui::RichText::new("Apply").small()),
+                                                    )
+                                                    .on_hover_text(hint)
+                                                    .on_disabled_hover_text(hint);
+                                                if button.clicked() {
+                                                    apply = Some((msg_idx, action_idx));
+                                                }
+                                            }
                                         });
+                                        for note in &action.review_notes {
+                                            ui.label(egui::RichText::new(format!("    ! {}", note)).color(theme::WARNING).small());
+                                        }
                                     }
                                 }
                             }
@@ -86,8 +117,25 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                 });
                 ui.add_space(4.0);
             }
+
+            // Streaming reply in progress
+            if !state.ai_streaming.is_empty() {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(31, 41, 55))
+                    .rounding(8.0)
+                    .inner_margin(egui::Margin::same(10.0))
+                    .show(ui, |ui| {
+                        ui.set_max_width(ui.available_width() * 0.8);
+                        ui.label(egui::RichText::new("AI").small().strong().color(theme::SUCCESS));
+                        ui.label(&state.ai_streaming);
+                    });
+            }
         });
 
+    if let Some((msg_idx, action_idx)) = apply {
+        request_apply(state, ctx, msg_idx, action_idx);
+    }
+
     // Input area
     ui.separator();
     ui.horizontal(|ui| {
@@ -103,6 +151,41 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     });
 }
 
+/// Apply a suggested action: low risk runs immediately, medium/high risk goes through the confirm dialog
+fn request_apply(state: &mut AppState, ctx: &egui::Context, msg_idx: usize, action_idx: usize) {
+    let action: SuggestedAction = match state
+        .ai_messages
+        .get(msg_idx)
+        .and_then(|m| m.actions.as_ref())
+        .and_then(|a| a.get(action_idx))
+    {
+        Some(a) => a.clone(),
+        None => return,
+    };
+    let (client, zone) = match (&state.client, &state.selected_zone) {
+        (Some(c), Some(z)) => (c.clone(), z.clone()),
+        _ => {
+            state.notify("Select a zone first", NotifLevel::Warning);
+            return;
+        }
+    };
+
+    if action.risk == "low" {
+        confirm_dialog::apply_ai_action(state, ctx, client, zone.id, msg_idx, action_idx, action);
+        return;
+    }
+
+    let params = serde_json::to_string_pretty(&action.params).unwrap_or_default();
+    state.confirm_dialog = Some(ConfirmDialog {
+        title: format!("Apply {}-risk action", action.risk),
+        message: format!(
+            "{}\n\nZone: {}\nType: {}\nParams:\n{}",
+            action.description, zone.name, action.action_type, params
+        ),
+        action: ConfirmAction::ApplyAiAction(zone.id, msg_idx, action_idx, action),
+    });
+}
+
 fn send_ai_message(state: &mut AppState, ctx: &egui::Context) {
     let input = state.ai_input.trim().to_string();
     if input.is_empty() { return; }
@@ -116,14 +199,23 @@ fn send_ai_message(state: &mut AppState, ctx: &egui::Context) {
 
     let config = state.config.clone();
     let mode = state.ai_mode.clone();
+    let client = state.client.clone();
+    let zone_id = state.zone_id();
+    let token_tx = state.result_sender();
+    let token_ctx = ctx.clone();
+    state.ai_streaming.clear();
     state.set_loading("AI thinking...");
 
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
-        let analyzer = match AiAnalyzer::new(&config) {
+    spawn_async(state, ctx, move || async move {
+        let mut analyzer = match AiAnalyzer::new(&config) {
             Ok(a) => a,
             Err(e) => return AsyncResult::AiResponse(Err(e)),
         };
-        let result = match mode {
+        analyzer.on_token(move |text| {
+            token_tx.send(AsyncResult::AiToken(text.to_string()));
+            token_ctx.request_repaint();
+        });
+        let mut result = match mode {
             AiMode::Ask => analyzer.ask(&input).await,
             AiMode::AnalyzeDns => analyzer.analyze_dns(&input).await,
             AiMode::AnalyzeSecurity => analyzer.analyze_security(&input).await,
@@ -131,6 +223,14 @@ fn send_ai_message(state: &mut AppState, ctx: &egui::Context) {
             AiMode::Troubleshoot => analyzer.troubleshoot(&input).await,
             AiMode::AutoConfig => analyzer.auto_config(&input).await,
         };
+        // Review suggested actions against the selected zone's live state
+        if let (Ok(response), Some(client), Some(zone_id)) = (&mut result, client, zone_id) {
+            if let Some(actions) = response.actions.as_mut().filter(|a| !a.is_empty()) {
+                if let Ok(live) = LiveZoneState::fetch(&client, &zone_id).await {
+                    safety::review_actions(actions, &live);
+                }
+            }
+        }
         AsyncResult::AiResponse(result)
     });
 }
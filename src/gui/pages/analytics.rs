@@ -2,8 +2,9 @@ use eframe::egui;
 
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
-use crate::models::analytics::AnalyticsParams;
+use crate::models::analytics::{top_breakdown, AnalyticsParams};
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.heading("Analytics");
@@ -29,8 +30,31 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                 load_analytics(state, ctx, &zone_id);
             }
         }
+        render_freshness(ui, state, DataFeed::Analytics);
     });
-    ui.add_space(8.0);
+    ui.add_space(4.0);
+
+    ui.horizontal(|ui| {
+        for (tab, label) in &[(AnalyticsTab::Traffic, "Traffic"), (AnalyticsTab::Dns, "DNS")] {
+            if ui.selectable_label(state.analytics_tab == *tab, *label).clicked() && state.analytics_tab != *tab {
+                state.analytics_tab = tab.clone();
+                let loaded = match tab {
+                    AnalyticsTab::Traffic => state.analytics.is_some(),
+                    AnalyticsTab::Dns => state.dns_analytics.is_some(),
+                };
+                if !loaded {
+                    load_analytics(state, ctx, &zone_id);
+                }
+            }
+        }
+    });
+    ui.separator();
+    ui.add_space(4.0);
+
+    if state.analytics_tab == AnalyticsTab::Dns {
+        render_dns(state, ui);
+        return;
+    }
 
     let dashboard = match &state.analytics {
         Some(d) => d.clone(),
@@ -97,8 +121,46 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
             render_bandwidth_chart(ui, timeseries);
         }
     }
+
+    if let Some(requests) = dashboard.totals.as_ref().and_then(|t| t.requests.as_ref()) {
+        let status = top_breakdown(requests.http_status.as_ref(), BREAKDOWN_TOP);
+        let countries = top_breakdown(requests.country.as_ref(), BREAKDOWN_TOP);
+        let content_types = top_breakdown(requests.content_type.as_ref(), BREAKDOWN_TOP);
+        let total = requests.all.unwrap_or(0);
+
+        if !status.is_empty() {
+            ui.add_space(8.0);
+            ui.label(egui::RichText::new("Status Codes").strong());
+            render_status_chart(ui, &status);
+        }
+        ui.add_space(8.0);
+        ui.columns(2, |cols| {
+            if !countries.is_empty() {
+                cols[0].label(egui::RichText::new("Top Countries").strong());
+                render_pie(&mut cols[0], &countries, total);
+            }
+            if !content_types.is_empty() {
+                cols[1].label(egui::RichText::new("Content Types").strong());
+                render_pie(&mut cols[1], &content_types, total);
+            }
+        });
+    }
 }
 
+/// Number of entries shown in each breakdown panel
+const BREAKDOWN_TOP: usize = 8;
+
+const PIE_COLORS: [egui::Color32; BREAKDOWN_TOP] = [
+    theme::INFO,
+    theme::SUCCESS,
+    theme::ACCENT,
+    theme::DANGER,
+    theme::WARNING,
+    egui::Color32::from_rgb(168, 85, 247),
+    egui::Color32::from_rgb(20, 184, 166),
+    egui::Color32::from_rgb(236, 72, 153),
+];
+
 fn stat_card(ui: &mut egui::Ui, label: &str, value: u64, color: egui::Color32) {
     egui::Frame::none()
         .fill(egui::Color32::from_rgb(31, 41, 55))
@@ -163,12 +225,150 @@ fn render_bandwidth_chart(ui: &mut egui::Ui, timeseries: &[crate::models::analyt
         });
 }
 
+fn render_status_chart(ui: &mut egui::Ui, status: &[(String, u64)]) {
+    use egui_plot::{Bar, BarChart, Plot};
+
+    let color = |code: &str| match code.chars().next() {
+        Some('2') => theme::SUCCESS,
+        Some('3') => theme::INFO,
+        Some('4') => theme::WARNING,
+        Some('5') => theme::DANGER,
+        _ => egui::Color32::GRAY,
+    };
+    let bars: Vec<Bar> = status
+        .iter()
+        .enumerate()
+        .map(|(i, (code, count))| Bar::new(i as f64, *count as f64).name(code).fill(color(code)).width(0.6))
+        .collect();
+
+    Plot::new("status_chart")
+        .height(160.0)
+        .allow_zoom(false)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.bar_chart(BarChart::new(bars).name("Requests"));
+        });
+}
+
+/// Pie chart of the top entries with a legend; the remainder is grouped as "Other"
+fn render_pie(ui: &mut egui::Ui, top: &[(String, u64)], total: u64) {
+    let shown: u64 = top.iter().map(|(_, n)| n).sum();
+    let total = total.max(shown);
+    if total == 0 {
+        return;
+    }
+    let mut entries = top.to_vec();
+    if total > shown {
+        entries.push(("Other".to_string(), total - shown));
+    }
+    let color = |i: usize| PIE_COLORS.get(i).copied().unwrap_or(egui::Color32::GRAY);
+    ui.horizontal(|ui| {
+        let size = 140.0;
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+        let center = rect.center();
+        let radius = size / 2.0 - 4.0;
+        let mut mesh = egui::Mesh::default();
+        let mut start = -std::f32::consts::FRAC_PI_2;
+        for (i, (_, count)) in entries.iter().enumerate() {
+            let sweep = *count as f32 / total as f32 * std::f32::consts::TAU;
+            let fill = color(i);
+            let steps = ((sweep / 0.05).ceil() as usize).max(1);
+            let base = mesh.vertices.len() as u32;
+            mesh.colored_vertex(center, fill);
+            for step in 0..=steps {
+                let angle = start + sweep * step as f32 / steps as f32;
+                mesh.colored_vertex(center + radius * egui::vec2(angle.cos(), angle.sin()), fill);
+            }
+            for step in 0..steps as u32 {
+                mesh.add_triangle(base, base + 1 + step, base + 2 + step);
+            }
+            start += sweep;
+        }
+        ui.painter().add(egui::Shape::mesh(mesh));
+
+        ui.vertical(|ui| {
+            for (i, (name, count)) in entries.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("\u{25A0}").color(color(i)));
+                    ui.label(format!("{} {} ({:.1}%)", name, format_number(*count), *count as f64 / total as f64 * 100.0));
+                });
+            }
+        });
+    });
+}
+
+fn render_dns(state: &AppState, ui: &mut egui::Ui) {
+    let dns = match &state.dns_analytics {
+        Some(d) => d,
+        None => {
+            ui.label("No DNS analytics data. Click Refresh to load.");
+            return;
+        }
+    };
+    if dns.total == 0 {
+        ui.label("No DNS queries in this period.");
+        return;
+    }
+
+    let nxdomain = dns.nxdomain_rate();
+    ui.horizontal(|ui| {
+        stat_card(ui, "DNS Queries", dns.total, theme::ACCENT);
+        egui::Frame::none()
+            .fill(egui::Color32::from_rgb(31, 41, 55))
+            .rounding(6.0)
+            .inner_margin(egui::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.set_min_width(150.0);
+                ui.label(egui::RichText::new("NXDOMAIN Rate").small().weak());
+                let color = if nxdomain >= 20.0 { theme::DANGER } else { theme::SUCCESS };
+                ui.label(egui::RichText::new(format!("{:.1}%", nxdomain)).size(20.0).color(color).strong());
+            });
+    });
+    ui.add_space(8.0);
+
+    ui.columns(2, |cols| {
+        cols[0].label(egui::RichText::new("Query Types").strong());
+        render_pie(&mut cols[0], &dns.by_type, dns.total);
+        cols[1].label(egui::RichText::new("Response Codes").strong());
+        render_pie(&mut cols[1], &dns.by_response_code, dns.total);
+    });
+    ui.add_space(8.0);
+
+    ui.label(egui::RichText::new("Top Query Names").strong());
+    egui::Grid::new("dns_top_names")
+        .num_columns(3)
+        .striped(true)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.strong("Name");
+            ui.strong("Queries");
+            ui.strong("Share");
+            ui.end_row();
+            for (name, count) in &dns.by_name {
+                ui.label(name);
+                ui.label(format_number(*count));
+                ui.label(format!("{:.1}%", *count as f64 / dns.total as f64 * 100.0));
+                ui.end_row();
+            }
+        });
+}
+
 pub fn load_analytics(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let client = match &state.client { Some(c) => c.clone(), None => return };
     let zid = zone_id.to_string();
     let period = state.analytics_period.clone();
     state.set_loading("Loading analytics...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    if state.analytics_tab == AnalyticsTab::Dns {
+        let until = chrono::Utc::now();
+        let since = until - if period == "7d" { chrono::Duration::days(7) } else { chrono::Duration::hours(24) };
+        spawn_async(state, ctx, move || async move {
+            let result = client.get_dns_analytics(&zid, since, until, BREAKDOWN_TOP).await;
+            AsyncResult::DnsAnalyticsLoaded(result)
+        });
+        return;
+    }
+    spawn_async(state, ctx, move || async move {
         let params = match period.as_str() {
             "7d" => AnalyticsParams::last_7d(),
             _ => AnalyticsParams::last_24h(),
@@ -2,7 +2,9 @@ use eframe::egui;
 
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
+use crate::models::cache::PurgeTarget;
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.heading("Cache Management");
@@ -16,9 +18,12 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
         }
     };
 
-    if ui.button("\u{1F504} Refresh").clicked() {
-        load_cache_status(state, ctx, &zone_id);
-    }
+    ui.horizontal(|ui| {
+        if ui.button("\u{1F504} Refresh").clicked() {
+            load_cache_status(state, ctx, &zone_id);
+        }
+        render_freshness(ui, state, DataFeed::Cache);
+    });
     ui.add_space(8.0);
 
     // Cache Level
@@ -96,7 +101,7 @@ pub fn load_cache_status(state: &mut AppState, ctx: &egui::Context, zone_id: &st
     };
     let zid = zone_id.to_string();
     state.set_loading("Loading cache status...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let level = client.get_cache_level(&zid).await;
         let ttl = client.get_browser_cache_ttl(&zid).await;
         match (level, ttl) {
@@ -114,7 +119,7 @@ fn set_cache_level(state: &mut AppState, ctx: &egui::Context, zone_id: &str, lev
     let zid = zone_id.to_string();
     let l = level.to_string();
     state.set_loading("Setting cache level...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_cache_level(&zid, &l).await;
         AsyncResult::CacheActionDone(result.map(|_| format!("Cache level set to {}", l)))
     });
@@ -124,7 +129,7 @@ fn set_browser_ttl(state: &mut AppState, ctx: &egui::Context, zone_id: &str, ttl
     let client = match &state.client { Some(c) => c.clone(), None => return };
     let zid = zone_id.to_string();
     state.set_loading("Setting browser TTL...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_browser_cache_ttl(&zid, ttl).await;
         AsyncResult::CacheActionDone(result.map(|_| format!("Browser TTL set to {}", ttl)))
     });
@@ -134,7 +139,7 @@ fn toggle_dev_mode(state: &mut AppState, ctx: &egui::Context, zone_id: &str, ena
     let client = match &state.client { Some(c) => c.clone(), None => return };
     let zid = zone_id.to_string();
     state.set_loading("Toggling dev mode...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_development_mode(&zid, enable).await;
         AsyncResult::CacheActionDone(result.map(|_| if enable { "Dev mode enabled" } else { "Dev mode disabled" }.to_string()))
     });
@@ -148,8 +153,8 @@ fn purge_by_urls(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let zid = zone_id.to_string();
     state.purge_urls_input.clear();
     state.set_loading("Purging URLs...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
-        let result = client.purge_cache_by_urls(&zid, urls).await;
+    spawn_async(state, ctx, move || async move {
+        let result = client.purge_cache_batched(&zid, PurgeTarget::Urls, &urls, |_, _, _| {}).await;
         AsyncResult::CachePurged(result.map(|_| format!("Purged {} URLs", count)))
     });
 }
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use eframe::egui;
+
+use crate::api::backup::plan_restore;
+use crate::gui::async_bridge::spawn_async;
+use crate::gui::state::*;
+use crate::gui::theme;
+use crate::models::backup::{ChangeKind, ZoneBackup};
+use crate::models::zone::ZoneSetting;
+
+pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
+    ui.heading("Compare Zones");
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        zone_picker(ui, "compare_zone_a", "Zone A:", &state.zones, &mut state.compare_zone_a);
+        ui.separator();
+        zone_picker(ui, "compare_zone_b", "Zone B:", &state.zones, &mut state.compare_zone_b);
+        ui.separator();
+        let ready = match (&state.compare_zone_a, &state.compare_zone_b) {
+            (Some(a), Some(b)) => a.id != b.id,
+            _ => false,
+        };
+        if ui.add_enabled(ready, egui::Button::new("Compare")).clicked() {
+            load_compare(state, ctx);
+        }
+        ui.checkbox(&mut state.compare_only_diff, "Only differences");
+    });
+    ui.add_space(8.0);
+
+    let (a, b) = match &state.compare_result {
+        Some(r) => r.clone(),
+        None => {
+            ui.label("Select two zones and click Compare.");
+            return;
+        }
+    };
+    let only_diff = state.compare_only_diff;
+
+    // SSL & security overview
+    ui.label(egui::RichText::new("SSL & Security").strong());
+    let mut security_a = a.ssl.clone().unwrap_or_default();
+    let mut security_b = b.ssl.clone().unwrap_or_default();
+    security_a.extend(find_setting(&a, "security_level"));
+    security_b.extend(find_setting(&b, "security_level"));
+    settings_grid(ui, "compare_ssl", &a, &b, &security_a, &security_b, only_diff);
+    ui.add_space(8.0);
+
+    // DNS summary
+    ui.label(egui::RichText::new("DNS Summary").strong());
+    dns_summary(ui, &a, &b);
+    ui.add_space(8.0);
+
+    // Record / page rule differences (relative to zone A)
+    let mut diffable_a = a.clone();
+    diffable_a.ssl = None;
+    diffable_a.settings = None;
+    diffable_a.firewall_rules = None;
+    let changes = plan_restore(&diffable_a, &b, true);
+    ui.label(egui::RichText::new(format!("DNS & Page Rule Differences ({})", changes.len())).strong());
+    if changes.is_empty() {
+        ui.label(egui::RichText::new("No differences").color(theme::SUCCESS));
+    } else {
+        egui::Grid::new("compare_changes")
+            .num_columns(4)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.strong("Section");
+                ui.strong("Item");
+                ui.strong(&a.zone.name);
+                ui.strong(&b.zone.name);
+                ui.end_row();
+                for change in &changes {
+                    let (left, right, color) = match change.kind {
+                        ChangeKind::Create => (change.after.clone(), None, theme::SUCCESS),
+                        ChangeKind::Delete => (None, change.before.clone(), theme::DANGER),
+                        ChangeKind::Update => (change.after.clone(), change.before.clone(), theme::WARNING),
+                    };
+                    ui.label(change.section);
+                    ui.label(egui::RichText::new(&change.target).color(color));
+                    ui.label(egui::RichText::new(left.unwrap_or_else(|| "-".to_string())).small());
+                    ui.label(egui::RichText::new(right.unwrap_or_else(|| "-".to_string())).small());
+                    ui.end_row();
+                }
+            });
+    }
+    ui.add_space(8.0);
+
+    // Other zone settings
+    ui.label(egui::RichText::new("Zone Settings").strong());
+    let settings_a = a.settings.clone().unwrap_or_default();
+    let settings_b = b.settings.clone().unwrap_or_default();
+    settings_grid(ui, "compare_settings", &a, &b, &settings_a, &settings_b, only_diff);
+}
+
+fn zone_picker(
+    ui: &mut egui::Ui,
+    id: &str,
+    label: &str,
+    zones: &[crate::models::zone::Zone],
+    selected: &mut Option<crate::models::zone::Zone>,
+) {
+    ui.label(label);
+    let text = selected.as_ref().map(|z| z.name.clone()).unwrap_or_else(|| "Select zone...".to_string());
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(text)
+        .width(180.0)
+        .show_ui(ui, |ui| {
+            for zone in zones {
+                let is_sel = selected.as_ref().map(|z| z.id == zone.id).unwrap_or(false);
+                if ui.selectable_label(is_sel, &zone.name).clicked() {
+                    *selected = Some(zone.clone());
+                }
+            }
+        });
+}
+
+fn find_setting(backup: &ZoneBackup, id: &str) -> Option<ZoneSetting> {
+    backup
+        .settings
+        .as_ref()
+        .and_then(|s| s.iter().find(|s| s.id == id).cloned())
+}
+
+fn value_text(setting: Option<&ZoneSetting>) -> String {
+    match setting.map(|s| &s.value) {
+        Some(serde_json::Value::String(v)) => v.clone(),
+        Some(other) => other.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn settings_grid(
+    ui: &mut egui::Ui,
+    id: &str,
+    a: &ZoneBackup,
+    b: &ZoneBackup,
+    settings_a: &[ZoneSetting],
+    settings_b: &[ZoneSetting],
+    only_diff: bool,
+) {
+    let mut ids: Vec<&str> = settings_a.iter().chain(settings_b).map(|s| s.id.as_str()).collect();
+    ids.sort();
+    ids.dedup();
+
+    egui::Grid::new(id)
+        .num_columns(3)
+        .striped(true)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            ui.strong("Setting");
+            ui.strong(&a.zone.name);
+            ui.strong(&b.zone.name);
+            ui.end_row();
+            for setting_id in ids {
+                let sa = settings_a.iter().find(|s| s.id == setting_id);
+                let sb = settings_b.iter().find(|s| s.id == setting_id);
+                let same = sa.map(|s| &s.value) == sb.map(|s| &s.value);
+                if only_diff && same {
+                    continue;
+                }
+                let color = if same { ui.visuals().text_color() } else { theme::WARNING };
+                ui.label(egui::RichText::new(setting_id).color(color));
+                ui.label(egui::RichText::new(value_text(sa)).small().color(color));
+                ui.label(egui::RichText::new(value_text(sb)).small().color(color));
+                ui.end_row();
+            }
+        });
+}
+
+fn dns_summary(ui: &mut egui::Ui, a: &ZoneBackup, b: &ZoneBackup) {
+    let count = |backup: &ZoneBackup| {
+        let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
+        for r in backup.dns_records.iter().flatten() {
+            *by_type.entry(r.record_type.clone()).or_default() += 1;
+        }
+        by_type
+    };
+    let (count_a, count_b) = (count(a), count(b));
+    let mut types: Vec<&String> = count_a.keys().chain(count_b.keys()).collect();
+    types.sort();
+    types.dedup();
+
+    egui::Grid::new("compare_dns_summary")
+        .num_columns(3)
+        .striped(true)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            ui.strong("Type");
+            ui.strong(&a.zone.name);
+            ui.strong(&b.zone.name);
+            ui.end_row();
+            for t in types {
+                let ca = count_a.get(t).copied().unwrap_or(0);
+                let cb = count_b.get(t).copied().unwrap_or(0);
+                let color = if ca == cb { ui.visuals().text_color() } else { theme::WARNING };
+                ui.label(egui::RichText::new(t).strong().color(theme::ACCENT));
+                ui.label(egui::RichText::new(ca.to_string()).color(color));
+                ui.label(egui::RichText::new(cb.to_string()).color(color));
+                ui.end_row();
+            }
+            let pa = a.page_rules.as_ref().map(|r| r.len().to_string()).unwrap_or_else(|| "-".to_string());
+            let pb = b.page_rules.as_ref().map(|r| r.len().to_string()).unwrap_or_else(|| "-".to_string());
+            ui.label("Page Rules");
+            ui.label(pa);
+            ui.label(pb);
+            ui.end_row();
+        });
+}
+
+fn load_compare(state: &mut AppState, ctx: &egui::Context) {
+    let client = match &state.client {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let (id_a, id_b) = match (&state.compare_zone_a, &state.compare_zone_b) {
+        (Some(a), Some(b)) => (a.id.clone(), b.id.clone()),
+        _ => return,
+    };
+    state.set_loading("Comparing zones...");
+    spawn_async(state, ctx, move || async move {
+        let result = tokio::try_join!(client.snapshot_zone(&id_a), client.snapshot_zone(&id_b));
+        AsyncResult::ZonesCompared(result)
+    });
+}
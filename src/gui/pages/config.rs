@@ -1,5 +1,6 @@
 use eframe::egui;
 
+use crate::config::settings::{DEFAULT_PROFILE, GUI_REFRESH_PAGES};
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
 use crate::gui::theme;
@@ -16,6 +17,12 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
             verify_token(state, ctx);
         }
         ui.checkbox(&mut state.config_show_secrets, "Show Secrets");
+        let profile = state
+            .config
+            .selected_profile(state.profile.as_deref())
+            .unwrap_or(DEFAULT_PROFILE)
+            .to_string();
+        ui.label(egui::RichText::new(format!("Profile: {}", profile)).weak());
     });
     ui.add_space(8.0);
 
@@ -37,6 +44,7 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
             ui.label(egui::RichText::new("AI Configuration").strong().color(theme::ACCENT));
             ui.add_space(4.0);
 
+            config_field(ui, "Provider", &mut state.config_edit.ai.provider, true);
             config_field(ui, "API URL", &mut state.config_edit.ai.api_url, true);
             config_field(ui, "API Key", &mut state.config_edit.ai.api_key, state.config_show_secrets);
             config_field(ui, "Model", &mut state.config_edit.ai.model, true);
@@ -56,6 +64,11 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                     state.config_edit.ai.temperature = Some(temp);
                 }
             });
+
+            let mut stream = state.config_edit.ai.stream.unwrap_or(true);
+            if ui.checkbox(&mut stream, "Stream responses").changed() {
+                state.config_edit.ai.stream = Some(stream);
+            }
         });
         ui.add_space(8.0);
 
@@ -81,6 +94,86 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
             });
         });
 
+        ui.add_space(8.0);
+
+        // Auto refresh section
+        ui.group(|ui| {
+            ui.label(egui::RichText::new("Auto Refresh").strong().color(theme::ACCENT));
+            ui.label(egui::RichText::new("Seconds between background reloads of each page (0 = off)").small().weak());
+            ui.add_space(4.0);
+
+            egui::Grid::new("gui_refresh").num_columns(2).show(ui, |ui| {
+                for page in GUI_REFRESH_PAGES {
+                    ui.label(format!("{}:", page));
+                    let mut secs = state.config_edit.gui.refresh_secs(page).unwrap_or(0);
+                    if ui.add(egui::DragValue::new(&mut secs).range(0..=3600).suffix(" s")).changed() {
+                        state.config_edit.gui.refresh.insert(page.to_string(), secs);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+        ui.add_space(8.0);
+
+        // Background monitor section
+        ui.group(|ui| {
+            ui.label(egui::RichText::new("Background Monitor").strong().color(theme::ACCENT));
+            ui.label(
+                egui::RichText::new("Keeps polling traffic after the window is closed and raises desktop notifications on spikes")
+                    .small()
+                    .weak(),
+            );
+            ui.add_space(4.0);
+
+            let monitor = &mut state.config_edit.gui.monitor;
+            ui.checkbox(&mut monitor.enabled, "Minimize instead of closing and monitor in the background");
+            ui.add_enabled_ui(monitor.enabled, |ui| {
+                ui.label("Zones (none checked = selected zone):");
+                ui.horizontal_wrapped(|ui| {
+                    for zone in &state.zones {
+                        let mut checked = monitor.zones.iter().any(|z| z.eq_ignore_ascii_case(&zone.name));
+                        if ui.checkbox(&mut checked, &zone.name).changed() {
+                            if checked {
+                                monitor.zones.push(zone.name.clone());
+                            } else {
+                                monitor.zones.retain(|z| !z.eq_ignore_ascii_case(&zone.name));
+                            }
+                        }
+                    }
+                });
+                egui::Grid::new("gui_monitor").num_columns(2).show(ui, |ui| {
+                    ui.label("Poll every:");
+                    let mut interval = monitor.interval_secs();
+                    if ui.add(egui::DragValue::new(&mut interval).range(10..=3600).suffix(" s")).changed() {
+                        monitor.interval_secs = Some(interval);
+                    }
+                    ui.end_row();
+                    ui.label("Alert at:");
+                    let mut factor = monitor.spike_factor();
+                    if ui
+                        .add(egui::DragValue::new(&mut factor).range(1.5..=20.0).speed(0.1).suffix("x recent average"))
+                        .changed()
+                    {
+                        monitor.spike_factor = Some(factor);
+                    }
+                    ui.end_row();
+                    ui.label("Ignore below:");
+                    ui.horizontal(|ui| {
+                        let mut rps = monitor.min_requests_per_second();
+                        if ui.add(egui::DragValue::new(&mut rps).range(0.0..=100000.0).suffix(" req/s")).changed() {
+                            monitor.min_requests_per_second = Some(rps);
+                        }
+                        let mut threats = monitor.min_threats();
+                        if ui.add(egui::DragValue::new(&mut threats).range(1..=1000000).suffix(" threats")).changed() {
+                            monitor.min_threats = Some(threats);
+                        }
+                    });
+                    ui.end_row();
+                });
+            });
+        });
+
         ui.add_space(12.0);
         let path = crate::config::settings::AppConfig::config_path()
             .map(|p| p.display().to_string())
@@ -106,7 +199,7 @@ fn config_field(ui: &mut egui::Ui, label: &str, value: &mut Option<String>, show
 
 fn save_config(state: &mut AppState, _ctx: &egui::Context) {
     let config = state.config_edit.clone();
-    match config.save() {
+    match config.save_effective(state.profile.as_deref()) {
         Ok(()) => {
             state.config = config;
             state.notify("Configuration saved", NotifLevel::Success);
@@ -126,7 +219,7 @@ fn verify_token(state: &mut AppState, ctx: &egui::Context) {
         }
     };
     state.set_loading("Verifying token...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.verify_token().await;
         AsyncResult::TokenVerified(result)
     });
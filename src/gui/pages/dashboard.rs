@@ -1,9 +1,10 @@
 use eframe::egui;
 
 use crate::gui::async_bridge::spawn_async;
-use crate::gui::state::{AppState, AsyncResult, NotifLevel};
+use crate::gui::state::{AppState, AsyncResult, DataFeed, NotifLevel};
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
-use crate::models::zone::ZoneListParams;
+use crate::models::zone::{ZoneHealth, ZoneListParams};
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.heading("Dashboard");
@@ -14,6 +15,7 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
             load_zones(state, ctx);
         }
         ui.label(format!("{} zones loaded", state.zones.len()));
+        render_freshness(ui, state, DataFeed::Zones);
     });
     ui.add_space(8.0);
 
@@ -22,6 +24,9 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
         return;
     }
 
+    render_health(state, ctx, ui);
+    ui.add_space(12.0);
+
     // Zone cards grid
     let available_width = ui.available_width();
     let card_width = 300.0_f32;
@@ -103,6 +108,142 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
         });
 }
 
+fn render_health(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.strong("Zone Health");
+        if ui.button("\u{1F50D} Check Health").clicked() {
+            load_zone_health(state, ctx);
+        }
+        if !state.zone_health.is_empty() {
+            let issues = state.zone_health.iter().filter(|h| has_issue(h)).count();
+            let (text, color) = if issues == 0 {
+                ("No issues found".to_string(), theme::SUCCESS)
+            } else {
+                (format!("{} zone(s) need attention", issues), theme::WARNING)
+            };
+            ui.label(egui::RichText::new(text).color(color));
+        }
+    });
+
+    if state.zone_health.is_empty() {
+        ui.label(
+            egui::RichText::new("Check status, SSL, security level, cache hit rate and DNSSEC of all zones at once.")
+                .weak(),
+        );
+        return;
+    }
+
+    ui.add_space(4.0);
+    egui::ScrollArea::vertical()
+        .id_salt("zone_health_scroll")
+        .max_height(260.0)
+        .show(ui, |ui| {
+            egui::Grid::new("zone_health_grid")
+                .num_columns(7)
+                .striped(true)
+                .spacing([16.0, 6.0])
+                .show(ui, |ui| {
+                    for header in ["Zone", "Status", "SSL", "Security", "Cache Hit 24h", "DNSSEC", ""] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+
+                    for health in state.zone_health.clone() {
+                        ui.label(egui::RichText::new(&health.zone_name).color(theme::ACCENT));
+                        let status_color = match health.status.as_str() {
+                            "active" => theme::SUCCESS,
+                            "pending" => theme::WARNING,
+                            _ => theme::DANGER,
+                        };
+                        health_cell(ui, Some(health.status.as_str()), Some(status_color));
+                        health_cell(ui, health.ssl_mode.as_deref(), ssl_color(health.ssl_mode.as_deref()));
+                        health_cell(
+                            ui,
+                            health.security_level.as_deref(),
+                            security_color(health.security_level.as_deref()),
+                        );
+                        let hit_rate = health.cache_hit_rate.map(|r| format!("{:.1}%", r));
+                        health_cell(ui, hit_rate.as_deref(), cache_color(health.cache_hit_rate));
+                        health_cell(ui, health.dnssec.as_deref(), dnssec_color(health.dnssec.as_deref()));
+                        if ui.small_button("Select").clicked() {
+                            if let Some(zone) = state.zones.iter().find(|z| z.id == health.zone_id) {
+                                state.selected_zone = Some(zone.clone());
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Render a value cell; missing values (fetch failed or not permitted) are shown dimmed.
+fn health_cell(ui: &mut egui::Ui, value: Option<&str>, color: Option<egui::Color32>) {
+    match (value, color) {
+        (Some(v), Some(c)) => ui.label(egui::RichText::new(v).color(c)),
+        (Some(v), None) => ui.label(v),
+        (None, _) => ui.label(egui::RichText::new("-").weak()),
+    };
+}
+
+fn ssl_color(mode: Option<&str>) -> Option<egui::Color32> {
+    match mode? {
+        "off" => Some(theme::DANGER),
+        "flexible" => Some(theme::WARNING),
+        "strict" | "full" => Some(theme::SUCCESS),
+        _ => None,
+    }
+}
+
+fn security_color(level: Option<&str>) -> Option<egui::Color32> {
+    match level? {
+        "off" | "essentially_off" => Some(theme::WARNING),
+        "under_attack" => Some(theme::DANGER),
+        _ => None,
+    }
+}
+
+fn cache_color(rate: Option<f64>) -> Option<egui::Color32> {
+    let rate = rate?;
+    Some(if rate < 20.0 { theme::WARNING } else { theme::SUCCESS })
+}
+
+fn dnssec_color(status: Option<&str>) -> Option<egui::Color32> {
+    match status? {
+        "active" => Some(theme::SUCCESS),
+        "pending" | "disabled" => Some(theme::WARNING),
+        _ => Some(theme::DANGER),
+    }
+}
+
+/// Whether any indicator of the zone is highlighted as a warning or danger.
+fn has_issue(h: &ZoneHealth) -> bool {
+    let bad = |c: Option<egui::Color32>| matches!(c, Some(c) if c == theme::WARNING || c == theme::DANGER);
+    h.status != "active"
+        || bad(ssl_color(h.ssl_mode.as_deref()))
+        || bad(security_color(h.security_level.as_deref()))
+        || bad(cache_color(h.cache_hit_rate))
+        || bad(dnssec_color(h.dnssec.as_deref()))
+}
+
+pub fn load_zone_health(state: &mut AppState, ctx: &egui::Context) {
+    let client = match &state.client {
+        Some(c) => c.clone(),
+        None => {
+            state.notify("No client configured", NotifLevel::Error);
+            return;
+        }
+    };
+    if state.zones.is_empty() {
+        state.notify("No zones loaded", NotifLevel::Warning);
+        return;
+    }
+    let zones = state.zones.clone();
+    state.set_loading(&format!("Checking health of {} zones...", zones.len()));
+    spawn_async(state, ctx, move || async move {
+        AsyncResult::ZoneHealthLoaded(client.get_zones_health(&zones).await)
+    });
+}
+
 pub fn load_zones(state: &mut AppState, ctx: &egui::Context) {
     let client = match &state.client {
         Some(c) => c.clone(),
@@ -112,7 +253,7 @@ pub fn load_zones(state: &mut AppState, ctx: &egui::Context) {
         }
     };
     state.set_loading("Loading zones...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let params = ZoneListParams {
             per_page: Some(50),
             ..Default::default()
@@ -1,9 +1,11 @@
 use eframe::egui;
 
-use crate::gui::async_bridge::spawn_async;
+use crate::gui::async_bridge::{spawn_async, ProgressSender};
 use crate::gui::state::*;
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
-use crate::models::dns::{DnsListParams, DnsRecordRequest};
+use crate::models::dns::{managed_comment, DnsListParams, DnsRecord, DnsRecordRequest};
+use crate::progress::Progress;
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.heading("DNS Management");
@@ -20,7 +22,9 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         if ui.button("\u{1F504} Refresh").clicked() {
             load_dns(state, ctx, &zone_id);
+            load_dnssec(state, ctx, &zone_id);
         }
+        render_freshness(ui, state, DataFeed::Dns);
         ui.separator();
         ui.label("Type:");
         egui::ComboBox::from_id_salt("dns_type_filter")
@@ -51,6 +55,9 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     });
     ui.add_space(4.0);
 
+    render_dnssec(state, ctx, ui, &zone_id);
+    ui.add_space(4.0);
+
     // Add record form
     if state.dns_show_add {
         render_add_form(state, ctx, ui, &zone_id);
@@ -75,12 +82,31 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.label(format!("{} records", filtered.len()));
     ui.add_space(4.0);
 
+    if !state.dns_selected.is_empty() {
+        render_bulk_actions(state, ctx, ui, &zone_id);
+        ui.add_space(4.0);
+    }
+
+    let filtered_ids: Vec<String> = filtered.iter().filter_map(|r| r.id.clone()).collect();
+    let mut inline_save = None;
+
     egui::ScrollArea::vertical().show(ui, |ui| {
         egui::Grid::new("dns_table")
-            .num_columns(7)
+            .num_columns(8)
             .striped(true)
             .spacing([12.0, 4.0])
             .show(ui, |ui| {
+                let mut all = !filtered_ids.is_empty()
+                    && filtered_ids.iter().all(|id| state.dns_selected.contains(id));
+                if ui.checkbox(&mut all, "").on_hover_text("Select all shown").changed() {
+                    for id in &filtered_ids {
+                        if all {
+                            state.dns_selected.insert(id.clone());
+                        } else {
+                            state.dns_selected.remove(id);
+                        }
+                    }
+                }
                 ui.strong("Type");
                 ui.strong("Name");
                 ui.strong("Content");
@@ -91,9 +117,41 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                 ui.end_row();
 
                 for record in &filtered {
+                    let id = record.id.clone().unwrap_or_default();
+                    let mut checked = state.dns_selected.contains(&id);
+                    if ui.checkbox(&mut checked, "").changed() {
+                        if checked {
+                            state.dns_selected.insert(id.clone());
+                        } else {
+                            state.dns_selected.remove(&id);
+                        }
+                    }
                     ui.label(egui::RichText::new(&record.record_type).strong().color(theme::ACCENT));
                     ui.label(&record.name);
-                    ui.label(egui::RichText::new(&record.content).small());
+
+                    // Content: double-click to edit in place, Enter saves, Esc / click away cancels
+                    let edit_id = egui::Id::new(("dns_inline_edit", &id));
+                    match state.dns_inline_edit.as_mut().filter(|(eid, _)| *eid == id) {
+                        Some((_, content)) => {
+                            let resp = ui.add(egui::TextEdit::singleline(content).id(edit_id).desired_width(220.0));
+                            if resp.lost_focus() {
+                                if ui.input(|i| i.key_pressed(egui::Key::Enter)) && *content != record.content {
+                                    inline_save = Some((id.clone(), content.clone()));
+                                } else {
+                                    state.dns_inline_edit = None;
+                                }
+                            }
+                        }
+                        None => {
+                            let resp = ui
+                                .add(egui::Label::new(egui::RichText::new(&record.content).small()).sense(egui::Sense::click()))
+                                .on_hover_text("Double-click to edit");
+                            if resp.double_clicked() && !id.is_empty() {
+                                state.dns_inline_edit = Some((id.clone(), record.content.clone()));
+                                ui.memory_mut(|m| m.request_focus(edit_id));
+                            }
+                        }
+                    }
                     let proxied = record.proxied.unwrap_or(false);
                     ui.label(if proxied { "\u{1F7E0}" } else { "\u{26AA}" });
                     ui.label(format!("{}", record.ttl.unwrap_or(1)));
@@ -101,16 +159,7 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
 
                     ui.horizontal(|ui| {
                         if ui.small_button("Edit").clicked() {
-                            state.dns_edit_form = Some(DnsEditForm {
-                                record_id: record.id.clone().unwrap_or_default(),
-                                record_type: record.record_type.clone(),
-                                name: record.name.clone(),
-                                content: record.content.clone(),
-                                ttl: record.ttl.unwrap_or(1).to_string(),
-                                proxied: record.proxied.unwrap_or(false),
-                                priority: record.priority.map(|p| p.to_string()).unwrap_or_default(),
-                                comment: record.comment.clone().unwrap_or_default(),
-                            });
+                            state.dns_edit_form = Some(DnsEditForm::from_record(record));
                         }
                         if ui.small_button(egui::RichText::new("Del").color(theme::DANGER)).clicked() {
                             if let Some(id) = &record.id {
@@ -127,6 +176,134 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                 }
             });
     });
+
+    if let Some((record_id, content)) = inline_save {
+        patch_dns(state, ctx, &zone_id, &record_id, serde_json::json!({ "content": content }));
+    }
+}
+
+/// Toolbar for the checked records
+fn render_bulk_actions(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui, zone_id: &str) {
+    let selected: Vec<DnsRecord> = state
+        .dns_records
+        .iter()
+        .filter(|r| r.id.as_ref().is_some_and(|id| state.dns_selected.contains(id)))
+        .cloned()
+        .collect();
+    let ids: Vec<String> = selected.iter().filter_map(|r| r.id.clone()).collect();
+    // Only address and CNAME records can be proxied
+    let proxiable: Vec<String> = selected
+        .iter()
+        .filter(|r| matches!(r.record_type.as_str(), "A" | "AAAA" | "CNAME"))
+        .filter_map(|r| r.id.clone())
+        .collect();
+
+    egui::Frame::none()
+        .fill(egui::Color32::from_rgb(31, 41, 55))
+        .rounding(6.0)
+        .inner_margin(egui::Margin::same(8.0))
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("{} selected", ids.len())).strong());
+                ui.separator();
+                for (label, proxied) in [("\u{1F7E0} Proxy on", true), ("\u{26AA} Proxy off", false)] {
+                    if ui.button(label).clicked() {
+                        if proxiable.len() < ids.len() {
+                            state.notify(
+                                format!("Skipping {} records that can't be proxied", ids.len() - proxiable.len()),
+                                NotifLevel::Info,
+                            );
+                        }
+                        if !proxiable.is_empty() {
+                            bulk_patch(state, ctx, zone_id, proxiable.clone(), serde_json::json!({ "proxied": proxied }));
+                        }
+                    }
+                }
+                ui.separator();
+                ui.label("TTL:");
+                ui.add(egui::TextEdit::singleline(&mut state.dns_bulk_ttl).desired_width(60.0));
+                if ui.button("Apply TTL").on_hover_text("1 = auto, otherwise 60-86400 seconds").clicked() {
+                    match state.dns_bulk_ttl.trim().parse::<u32>() {
+                        Ok(ttl) if ttl == 1 || (60..=86400).contains(&ttl) => {
+                            bulk_patch(state, ctx, zone_id, ids.clone(), serde_json::json!({ "ttl": ttl }));
+                        }
+                        _ => state.notify("TTL must be 1 (auto) or 60-86400 seconds", NotifLevel::Warning),
+                    }
+                }
+                ui.separator();
+                if ui.button(egui::RichText::new("Delete selected").color(theme::DANGER)).clicked() {
+                    state.confirm_dialog = Some(ConfirmDialog {
+                        title: "Delete DNS Records".to_string(),
+                        message: format!("Delete {} selected DNS records?", ids.len()),
+                        action: ConfirmAction::DeleteDnsRecords(zone_id.to_string(), ids.clone()),
+                    });
+                }
+                if ui.button("Clear selection").clicked() {
+                    state.dns_selected.clear();
+                }
+            });
+        });
+}
+
+fn render_dnssec(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui, zone_id: &str) {
+    let status = state.dnssec.as_ref().map(|d| d.status().to_string());
+    ui.horizontal(|ui| {
+        ui.label("DNSSEC:");
+        match status.as_deref() {
+            None => {
+                ui.label(egui::RichText::new("unknown").weak());
+            }
+            Some(status) => {
+                let color = match status {
+                    "active" => theme::SUCCESS,
+                    "disabled" => theme::DANGER,
+                    _ => theme::WARNING,
+                };
+                ui.label(egui::RichText::new(status).strong().color(color));
+                if status == "disabled" {
+                    if ui.small_button("Enable").clicked() {
+                        enable_dnssec(state, ctx, zone_id);
+                    }
+                } else if ui.small_button(egui::RichText::new("Disable").color(theme::DANGER)).clicked() {
+                    state.confirm_dialog = Some(ConfirmDialog {
+                        title: "Disable DNSSEC".to_string(),
+                        message: "Remove the DS record at your registrar first, otherwise validating resolvers will fail to resolve this zone. Disable DNSSEC?".to_string(),
+                        action: ConfirmAction::DisableDnssec(zone_id.to_string()),
+                    });
+                }
+            }
+        }
+    });
+
+    let Some(dnssec) = &state.dnssec else {
+        return;
+    };
+    if dnssec.status() != "pending" {
+        return;
+    }
+    if let Some(ds) = &dnssec.ds {
+        ui.label(
+            egui::RichText::new("Add this DS record at your registrar to finish enabling DNSSEC:")
+                .small()
+                .color(theme::WARNING),
+        );
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(ds).monospace().small());
+            if ui.small_button("Copy").clicked() {
+                ctx.copy_text(ds.clone());
+            }
+        });
+        ui.label(
+            egui::RichText::new(format!(
+                "Key tag {}  \u{00B7}  Algorithm {}  \u{00B7}  Digest type {}",
+                dnssec.key_tag.map(|k| k.to_string()).unwrap_or_default(),
+                dnssec.algorithm.as_deref().unwrap_or("-"),
+                dnssec.digest_type.as_deref().unwrap_or("-"),
+            ))
+            .small()
+            .weak(),
+        );
+    }
 }
 
 fn render_add_form(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui, zone_id: &str) {
@@ -212,13 +389,36 @@ pub fn load_dns(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     };
     let zid = zone_id.to_string();
     state.set_loading("Loading DNS records...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let params = DnsListParams { per_page: Some(100), ..Default::default() };
         let result = client.list_dns_records(&zid, &params).await;
         AsyncResult::DnsRecordsLoaded(result.map(|r| r.result.unwrap_or_default()))
     });
 }
 
+pub fn load_dnssec(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
+    let client = match &state.client {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let zid = zone_id.to_string();
+    spawn_async(state, ctx, move || async move {
+        AsyncResult::DnssecLoaded(client.get_dnssec(&zid).await)
+    });
+}
+
+fn enable_dnssec(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
+    let client = match &state.client {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let zid = zone_id.to_string();
+    state.set_loading("Enabling DNSSEC...");
+    spawn_async(state, ctx, move || async move {
+        AsyncResult::DnssecLoaded(client.set_dnssec(&zid, true).await)
+    });
+}
+
 fn create_dns(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let client = match &state.client {
         Some(c) => c.clone(),
@@ -232,12 +432,13 @@ fn create_dns(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
         ttl: form.ttl.parse().ok(),
         proxied: Some(form.proxied),
         priority: form.priority.parse().ok(),
-        comment: if form.comment.is_empty() { None } else { Some(form.comment.clone()) },
+        comment: Some(managed_comment(Some(form.comment.as_str()).filter(|c| !c.is_empty()))),
         tags: None,
+        data: None,
     };
     let zid = zone_id.to_string();
     state.set_loading("Creating DNS record...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.create_dns_record(&zid, &req).await;
         AsyncResult::DnsRecordCreated(result)
     });
@@ -261,16 +462,53 @@ fn update_dns(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
         priority: form.priority.parse().ok(),
         comment: if form.comment.is_empty() { None } else { Some(form.comment.clone()) },
         tags: None,
+        data: None,
     };
     let zid = zone_id.to_string();
     let rid = form.record_id.clone();
     state.set_loading("Updating DNS record...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.update_dns_record(&zid, &rid, &req).await;
         AsyncResult::DnsRecordUpdated(result)
     });
 }
 
+fn patch_dns(state: &mut AppState, ctx: &egui::Context, zone_id: &str, record_id: &str, patch: serde_json::Value) {
+    let client = match &state.client {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let zid = zone_id.to_string();
+    let rid = record_id.to_string();
+    state.set_loading("Updating DNS record...");
+    spawn_async(state, ctx, move || async move {
+        let result = client.patch_dns_record(&zid, &rid, &patch).await;
+        AsyncResult::DnsRecordUpdated(result)
+    });
+}
+
+/// Apply the same partial update to several records, one request each
+fn bulk_patch(state: &mut AppState, ctx: &egui::Context, zone_id: &str, record_ids: Vec<String>, patch: serde_json::Value) {
+    let client = match &state.client {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let zid = zone_id.to_string();
+    state.set_loading(&format!("Updating {} DNS records...", record_ids.len()));
+    let mut progress = Progress::new("Updating DNS records", record_ids.len(), ProgressSender::new(state, ctx));
+    spawn_async(state, ctx, move || async move {
+        let mut results = Vec::new();
+        for rid in record_ids {
+            progress.start(rid.as_str());
+            let result = client.patch_dns_record(&zid, &rid, &patch).await;
+            progress.record(&result);
+            results.push(result);
+        }
+        progress.finish();
+        AsyncResult::DnsBulkUpdated(results)
+    });
+}
+
 fn export_dns(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let client = match &state.client {
         Some(c) => c.clone(),
@@ -278,7 +516,7 @@ fn export_dns(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     };
     let zid = zone_id.to_string();
     state.set_loading("Exporting DNS...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.export_dns_records(&zid).await;
         AsyncResult::DnsExported(result)
     });
@@ -2,7 +2,11 @@ use eframe::egui;
 
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
+use crate::models::analytics::parse_time;
+use crate::models::firewall::{FirewallEventQuery, RateLimitRequest, FIREWALL_EVENT_ACTIONS, RATE_LIMIT_MODES};
+use crate::models::zone::QuotaResource;
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.heading("Firewall Management");
@@ -16,10 +20,42 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
         }
     };
 
-    if ui.button("\u{1F504} Refresh").clicked() {
-        load_firewall(state, ctx, &zone_id);
+    ui.horizontal(|ui| {
+        if ui.button("\u{1F504} Refresh").clicked() {
+            match state.firewall_tab {
+                FirewallTab::Rules => load_firewall(state, ctx, &zone_id),
+                FirewallTab::Events => load_firewall_events(state, ctx, &zone_id),
+            }
+        }
+        if let Some(feed) = DataFeed::for_page(&state.current_page, &state.firewall_tab) {
+            render_freshness(ui, state, feed);
+        }
+    });
+    ui.add_space(4.0);
+
+    // Tab bar
+    ui.horizontal(|ui| {
+        for (tab, label) in &[(FirewallTab::Rules, "Rules"), (FirewallTab::Events, "Events")] {
+            let selected = state.firewall_tab == *tab;
+            if ui.selectable_label(selected, *label).clicked() && !selected {
+                state.firewall_tab = tab.clone();
+                if *tab == FirewallTab::Events && state.firewall_events.is_empty() {
+                    load_firewall_events(state, ctx, &zone_id);
+                }
+            }
+        }
+    });
+    ui.separator();
+    ui.add_space(4.0);
+
+    match state.firewall_tab {
+        FirewallTab::Rules => render_rules(state, ctx, ui, &zone_id),
+        FirewallTab::Events => render_events(state, ctx, ui, &zone_id),
     }
-    ui.add_space(8.0);
+}
+
+fn render_rules(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui, zone_id: &str) {
+    let zone_id = zone_id.to_string();
 
     // Security Level
     ui.group(|ui| {
@@ -54,8 +90,12 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.group(|ui| {
         ui.label(egui::RichText::new("IP Access Control").strong());
         ui.horizontal(|ui| {
-            ui.label("IP:");
-            ui.add(egui::TextEdit::singleline(&mut state.fw_ip_input).desired_width(150.0));
+            ui.label("Target:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.fw_ip_input)
+                    .hint_text("IP, CIDR, AS13335 or CN")
+                    .desired_width(150.0),
+            );
             ui.label("Note:");
             ui.add(egui::TextEdit::singleline(&mut state.fw_note_input).desired_width(150.0));
             if ui.button(egui::RichText::new("Block").color(theme::DANGER)).clicked() {
@@ -72,11 +112,12 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.label(egui::RichText::new("IP Access Rules").strong());
     egui::ScrollArea::vertical().id_salt("ip_rules").max_height(200.0).show(ui, |ui| {
         egui::Grid::new("ip_rules_table")
-            .num_columns(5)
+            .num_columns(6)
             .striped(true)
             .spacing([12.0, 4.0])
             .show(ui, |ui| {
-                ui.strong("IP");
+                ui.strong("Target");
+                ui.strong("Type");
                 ui.strong("Mode");
                 ui.strong("Notes");
                 ui.strong("Created");
@@ -86,6 +127,15 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                 for rule in state.ip_access_rules.clone() {
                     let ip = rule.configuration.as_ref().and_then(|c| c.value.clone()).unwrap_or_default();
                     ui.label(&ip);
+                    let target_type = match rule.configuration.as_ref().and_then(|c| c.target.as_deref()) {
+                        Some("ip") => "IPv4",
+                        Some("ip6") => "IPv6",
+                        Some("ip_range") => "CIDR",
+                        Some("asn") => "ASN",
+                        Some("country") => "Country",
+                        _ => "-",
+                    };
+                    ui.label(egui::RichText::new(target_type).small());
                     let mode = rule.mode.as_deref().unwrap_or("-");
                     let mc = match mode {
                         "block" => theme::DANGER,
@@ -133,6 +183,198 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                 }
             });
     });
+    ui.add_space(8.0);
+
+    // Rate Limits
+    ui.group(|ui| {
+        let editing = state.rl_form.rule_id.is_some();
+        ui.label(egui::RichText::new(if editing { "Edit Rate Limit" } else { "Add Rate Limit" }).strong());
+        ui.horizontal(|ui| {
+            ui.label("URL:");
+            ui.add(egui::TextEdit::singleline(&mut state.rl_form.url).desired_width(220.0).hint_text("*example.com/api/*"));
+            ui.label("Threshold:");
+            ui.add(egui::TextEdit::singleline(&mut state.rl_form.threshold).desired_width(60.0));
+            ui.label("Period (s):");
+            ui.add(egui::TextEdit::singleline(&mut state.rl_form.period).desired_width(60.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Action:");
+            egui::ComboBox::from_id_salt("rl_mode")
+                .selected_text(&state.rl_form.mode)
+                .show_ui(ui, |ui| {
+                    for mode in RATE_LIMIT_MODES {
+                        ui.selectable_value(&mut state.rl_form.mode, mode.to_string(), *mode);
+                    }
+                });
+            let has_timeout = matches!(state.rl_form.mode.as_str(), "ban" | "simulate");
+            ui.add_enabled_ui(has_timeout, |ui| {
+                ui.label("Timeout (s):");
+                ui.add(egui::TextEdit::singleline(&mut state.rl_form.timeout).desired_width(60.0));
+            });
+            ui.label("Description:");
+            ui.add(egui::TextEdit::singleline(&mut state.rl_form.description).desired_width(150.0));
+            if ui.button(if editing { "Save" } else { "Add" }).clicked() {
+                save_rate_limit(state, ctx, &zone_id);
+            }
+            if editing && ui.button("Cancel").clicked() {
+                state.rl_form = RateLimitForm::default();
+            }
+        });
+    });
+    ui.add_space(4.0);
+
+    egui::ScrollArea::vertical().id_salt("rate_limits").max_height(200.0).show(ui, |ui| {
+        egui::Grid::new("rate_limits_table")
+            .num_columns(6)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.strong("URL");
+                ui.strong("Threshold");
+                ui.strong("Action");
+                ui.strong("Description");
+                ui.strong("Enabled");
+                ui.strong("Actions");
+                ui.end_row();
+
+                for rule in state.rate_limits.clone() {
+                    let request = RateLimitRequest::from_rule(&rule);
+                    let mode = request.action.mode.clone().unwrap_or_else(|| "-".to_string());
+                    ui.label(request.url().unwrap_or("-"));
+                    ui.label(format!("{} / {}s", request.threshold, request.period));
+                    match request.action.timeout {
+                        Some(t) => ui.label(format!("{} ({}s)", mode, t)),
+                        None => ui.label(&mode),
+                    };
+                    ui.label(rule.description.as_deref().unwrap_or("-"));
+                    let enabled = rule.disabled != Some(true);
+                    ui.label(egui::RichText::new(if enabled { "Yes" } else { "No" })
+                        .color(if enabled { theme::SUCCESS } else { theme::WARNING }));
+                    ui.horizontal(|ui| {
+                        if let Some(id) = &rule.id {
+                            if ui.small_button("Edit").clicked() {
+                                state.rl_form = RateLimitForm {
+                                    rule_id: Some(id.clone()),
+                                    url: request.url().unwrap_or_default().to_string(),
+                                    threshold: request.threshold.to_string(),
+                                    period: request.period.to_string(),
+                                    mode: mode.clone(),
+                                    timeout: request.action.timeout.map(|t| t.to_string()).unwrap_or_default(),
+                                    description: rule.description.clone().unwrap_or_default(),
+                                };
+                            }
+                            if ui.small_button(egui::RichText::new("Delete").color(theme::DANGER)).clicked() {
+                                state.confirm_dialog = Some(ConfirmDialog {
+                                    title: "Delete Rate Limit".to_string(),
+                                    message: format!("Delete rate limit for {}?", request.url().unwrap_or("-")),
+                                    action: ConfirmAction::DeleteRateLimit(zone_id.clone(), id.clone()),
+                                });
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn render_events(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui, zone_id: &str) {
+    ui.horizontal(|ui| {
+        ui.label("Action:");
+        egui::ComboBox::from_id_salt("fw_events_action")
+            .selected_text(if state.fw_events_action.is_empty() { "all" } else { &state.fw_events_action })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut state.fw_events_action, String::new(), "all");
+                for action in FIREWALL_EVENT_ACTIONS {
+                    ui.selectable_value(&mut state.fw_events_action, action.to_string(), *action);
+                }
+            });
+        ui.label("Since:");
+        egui::ComboBox::from_id_salt("fw_events_since")
+            .selected_text(&state.fw_events_since)
+            .show_ui(ui, |ui| {
+                for since in ["30m", "1h", "6h", "24h", "3d"] {
+                    ui.selectable_value(&mut state.fw_events_since, since.to_string(), since);
+                }
+            });
+        if ui.button("Apply").clicked() {
+            load_firewall_events(state, ctx, zone_id);
+        }
+    });
+    ui.add_space(4.0);
+
+    if state.firewall_events.is_empty() {
+        ui.label("No firewall events in this time range.");
+        return;
+    }
+
+    egui::ScrollArea::vertical().id_salt("fw_events").show(ui, |ui| {
+        egui::Grid::new("fw_events_table")
+            .num_columns(7)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.strong("Time");
+                ui.strong("Action");
+                ui.strong("Source");
+                ui.strong("Rule ID");
+                ui.strong("Client IP");
+                ui.strong("Country");
+                ui.strong("Request");
+                ui.end_row();
+
+                for e in &state.firewall_events {
+                    ui.label(egui::RichText::new(e.datetime.as_deref().unwrap_or("-")).small());
+                    let action = e.action.as_deref().unwrap_or("-");
+                    let color = match action {
+                        "block" => theme::DANGER,
+                        "challenge" | "managed_challenge" | "jschallenge" => theme::WARNING,
+                        _ => egui::Color32::GRAY,
+                    };
+                    ui.label(egui::RichText::new(action).color(color));
+                    ui.label(e.source.as_deref().unwrap_or("-"));
+                    ui.label(egui::RichText::new(e.rule_id.as_deref().unwrap_or("-")).small());
+                    ui.label(e.client_ip.as_deref().unwrap_or("-"));
+                    ui.label(e.client_country.as_deref().unwrap_or("-"));
+                    ui.label(format!(
+                        "{} {}{}",
+                        e.method.as_deref().unwrap_or(""),
+                        e.host.as_deref().unwrap_or(""),
+                        e.path.as_deref().unwrap_or("")
+                    ));
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+pub fn load_firewall_events(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
+    let client = match &state.client {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let zid = zone_id.to_string();
+    let now = chrono::Utc::now();
+    let since = match parse_time(&state.fw_events_since, now) {
+        Ok(t) => t,
+        Err(e) => {
+            state.notify(format!("{}", e), NotifLevel::Error);
+            return;
+        }
+    };
+    let query = FirewallEventQuery {
+        since,
+        until: now,
+        action: Some(state.fw_events_action.clone()).filter(|a| !a.is_empty()),
+        client_ip: None,
+        limit: 200,
+    };
+    state.set_loading("Loading firewall events...");
+
+    spawn_async(state, ctx, move || async move {
+        let result = client.list_firewall_events(&zid, &query).await;
+        AsyncResult::FirewallEventsLoaded(result)
+    });
 }
 
 pub fn load_firewall(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
@@ -143,22 +385,72 @@ pub fn load_firewall(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let zid = zone_id.to_string();
     let zid2 = zid.clone();
     let zid3 = zid.clone();
+    let zid4 = zid.clone();
     let c2 = client.clone();
     let c3 = client.clone();
+    let c4 = client.clone();
     state.set_loading("Loading firewall...");
 
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.list_firewall_rules(&zid).await;
         AsyncResult::FirewallRulesLoaded(result)
     });
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = c2.list_ip_access_rules(&zid2).await;
         AsyncResult::IpAccessRulesLoaded(result)
     });
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = c3.get_security_level(&zid3).await;
         AsyncResult::SecurityLevelLoaded(result)
     });
+    spawn_async(state, ctx, move || async move {
+        let result = c4.list_rate_limits(&zid4).await;
+        AsyncResult::RateLimitsLoaded(result)
+    });
+}
+
+fn save_rate_limit(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
+    let client = match &state.client { Some(c) => c.clone(), None => return };
+    let form = &state.rl_form;
+    let (Ok(threshold), Ok(period)) = (form.threshold.trim().parse::<u32>(), form.period.trim().parse::<u32>()) else {
+        state.notify("Threshold and period must be numbers", NotifLevel::Warning);
+        return;
+    };
+    let timeout = match form.mode.as_str() {
+        "ban" | "simulate" => Some(form.timeout.trim().parse::<u32>().unwrap_or(period)),
+        _ => None,
+    };
+    let description = form.description.trim().to_string();
+    let url = form.url.trim().to_string();
+    let rule_id = form.rule_id.clone();
+    let mut request = RateLimitRequest::new(&url, threshold, period, &form.mode, timeout);
+    request.description = (!description.is_empty()).then_some(description);
+
+    let zid = zone_id.to_string();
+    state.set_loading("Saving rate limit...");
+    spawn_async(state, ctx, move || async move {
+        let result = async {
+            match rule_id {
+                // Keep match conditions the form doesn't expose (methods, schemes, response)
+                Some(id) => {
+                    let current = client.get_rate_limit(&zid, &id).await?;
+                    let mut merged = RateLimitRequest::from_rule(&current);
+                    merged.set_url(&url);
+                    merged.threshold = request.threshold;
+                    merged.period = request.period;
+                    merged.action = request.action;
+                    merged.description = request.description;
+                    client.update_rate_limit(&zid, &id, &merged).await
+                }
+                None => {
+                    client.check_quota(&zid, QuotaResource::RateLimit).await?;
+                    client.create_rate_limit(&zid, &request).await
+                }
+            }
+        }
+        .await;
+        AsyncResult::RateLimitSaved(result)
+    });
 }
 
 fn set_security_level(state: &mut AppState, ctx: &egui::Context, zone_id: &str, level: &str) {
@@ -166,7 +458,7 @@ fn set_security_level(state: &mut AppState, ctx: &egui::Context, zone_id: &str,
     let zid = zone_id.to_string();
     let l = level.to_string();
     state.set_loading("Setting security level...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_security_level(&zid, &l).await;
         AsyncResult::FirewallActionDone(result.map(|_| format!("Security level set to {}", l)))
     });
@@ -176,7 +468,7 @@ fn set_under_attack(state: &mut AppState, ctx: &egui::Context, zone_id: &str, en
     let client = match &state.client { Some(c) => c.clone(), None => return };
     let zid = zone_id.to_string();
     state.set_loading("Setting Under Attack mode...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_under_attack_mode(&zid, enable).await;
         AsyncResult::FirewallActionDone(result.map(|_| if enable { "Under Attack enabled" } else { "Under Attack disabled" }.to_string()))
     });
@@ -190,8 +482,8 @@ fn block_ip(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let zid = zone_id.to_string();
     state.fw_ip_input.clear();
     state.fw_note_input.clear();
-    state.set_loading("Blocking IP...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    state.set_loading("Blocking...");
+    spawn_async(state, ctx, move || async move {
         let note_opt = if note.is_empty() { None } else { Some(note.as_str()) };
         let result = client.block_ip(&zid, &ip, note_opt).await;
         AsyncResult::IpRuleCreated(result.map(|_| format!("Blocked {}", ip)))
@@ -206,8 +498,8 @@ fn whitelist_ip(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let zid = zone_id.to_string();
     state.fw_ip_input.clear();
     state.fw_note_input.clear();
-    state.set_loading("Whitelisting IP...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    state.set_loading("Whitelisting...");
+    spawn_async(state, ctx, move || async move {
         let note_opt = if note.is_empty() { None } else { Some(note.as_str()) };
         let result = client.whitelist_ip(&zid, &ip, note_opt).await;
         AsyncResult::IpRuleCreated(result.map(|_| format!("Whitelisted {}", ip)))
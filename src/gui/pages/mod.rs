@@ -1,5 +1,6 @@
 pub mod dashboard;
 pub mod zone;
+pub mod compare;
 pub mod dns;
 pub mod ssl;
 pub mod firewall;
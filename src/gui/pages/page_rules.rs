@@ -2,7 +2,10 @@ use eframe::egui;
 
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
+use crate::models::page_rules::*;
+use crate::models::zone::QuotaResource;
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.heading("Page Rules");
@@ -16,35 +19,22 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
         }
     };
 
-    if ui.button("\u{1F504} Refresh").clicked() {
-        load_page_rules(state, ctx, &zone_id);
-    }
-    ui.add_space(8.0);
-
-    // Create redirect form
-    ui.group(|ui| {
-        ui.label(egui::RichText::new("Create Redirect Rule").strong());
-        ui.horizontal(|ui| {
-            ui.label("URL Pattern:");
-            ui.text_edit_singleline(&mut state.redirect_form.url_pattern);
-        });
-        ui.horizontal(|ui| {
-            ui.label("Redirect To:");
-            ui.text_edit_singleline(&mut state.redirect_form.redirect_url);
-            ui.label("Status:");
-            egui::ComboBox::from_id_salt("redirect_status")
-                .selected_text(state.redirect_form.status_code.to_string())
-                .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut state.redirect_form.status_code, 301, "301 Permanent");
-                    ui.selectable_value(&mut state.redirect_form.status_code, 302, "302 Temporary");
-                });
-            if ui.button("Create").clicked() {
-                create_redirect(state, ctx, &zone_id);
-            }
-        });
+    ui.horizontal(|ui| {
+        if ui.button("\u{1F504} Refresh").clicked() {
+            load_page_rules(state, ctx, &zone_id);
+        }
+        if state.page_rule_form.is_none() && ui.button("+ New Rule").clicked() {
+            state.page_rule_form = Some(PageRuleForm::default());
+        }
+        render_freshness(ui, state, DataFeed::PageRules);
     });
     ui.add_space(8.0);
 
+    if state.page_rule_form.is_some() {
+        render_form(state, ctx, ui, &zone_id);
+        ui.add_space(8.0);
+    }
+
     // Page rules table
     if state.page_rules.is_empty() {
         ui.label("No page rules.");
@@ -59,7 +49,7 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                     ui.strong("Actions");
                     ui.strong("Priority");
                     ui.strong("Status");
-                    ui.strong("Delete");
+                    ui.strong("");
                     ui.end_row();
 
                     for rule in state.page_rules.clone() {
@@ -90,15 +80,20 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
                         let sc = if status == "active" { theme::SUCCESS } else { theme::WARNING };
                         ui.label(egui::RichText::new(status).color(sc));
 
-                        if let Some(id) = &rule.id {
-                            if ui.small_button(egui::RichText::new("Delete").color(theme::DANGER)).clicked() {
-                                state.confirm_dialog = Some(ConfirmDialog {
-                                    title: "Delete Page Rule".to_string(),
-                                    message: format!("Delete page rule for '{}'?", pattern),
-                                    action: ConfirmAction::DeletePageRule(zone_id.clone(), id.clone()),
-                                });
+                        ui.horizontal(|ui| {
+                            if let Some(id) = &rule.id {
+                                if ui.small_button("Edit").clicked() {
+                                    state.page_rule_form = Some(form_from_rule(&rule));
+                                }
+                                if ui.small_button(egui::RichText::new("Delete").color(theme::DANGER)).clicked() {
+                                    state.confirm_dialog = Some(ConfirmDialog {
+                                        title: "Delete Page Rule".to_string(),
+                                        message: format!("Delete page rule for '{}'?", pattern),
+                                        action: ConfirmAction::DeletePageRule(zone_id.clone(), id.clone()),
+                                    });
+                                }
                             }
-                        }
+                        });
                         ui.end_row();
                     }
                 });
@@ -110,23 +105,222 @@ pub fn load_page_rules(state: &mut AppState, ctx: &egui::Context, zone_id: &str)
     let client = match &state.client { Some(c) => c.clone(), None => return };
     let zid = zone_id.to_string();
     state.set_loading("Loading page rules...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.list_page_rules(&zid).await;
         AsyncResult::PageRulesLoaded(result)
     });
 }
 
-fn create_redirect(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
+const CACHE_LEVELS: &[&str] = &["bypass", "basic", "simplified", "aggressive", "cache_everything"];
+const SSL_MODES: &[&str] = &["off", "flexible", "full", "strict"];
+
+fn render_form(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui, zone_id: &str) {
+    let mut save = false;
+    let mut cancel = false;
+
+    if let Some(form) = &mut state.page_rule_form {
+        let editing = form.rule_id.is_some();
+        ui.group(|ui| {
+            ui.label(egui::RichText::new(if editing { "Edit Page Rule" } else { "New Page Rule" }).strong());
+            egui::Grid::new("page_rule_form").num_columns(2).spacing([8.0, 6.0]).show(ui, |ui| {
+                ui.label("URL Pattern:");
+                ui.add(egui::TextEdit::singleline(&mut form.url_pattern)
+                    .hint_text("*example.com/images/*")
+                    .desired_width(320.0));
+                ui.end_row();
+
+                ui.label("Priority:");
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut form.priority)
+                        .hint_text("auto")
+                        .desired_width(60.0));
+                    ui.checkbox(&mut form.active, "Active");
+                });
+                ui.end_row();
+
+                ui.label("Forwarding URL:");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut form.forwarding, "");
+                    ui.add_enabled_ui(form.forwarding, |ui| {
+                        ui.add(egui::TextEdit::singleline(&mut form.forwarding_url)
+                            .hint_text("https://www.example.com/$1")
+                            .desired_width(260.0));
+                        egui::ComboBox::from_id_salt("page_rule_status_code")
+                            .selected_text(form.status_code.to_string())
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut form.status_code, 301, "301 Permanent");
+                                ui.selectable_value(&mut form.status_code, 302, "302 Temporary");
+                            });
+                    });
+                });
+                ui.end_row();
+
+                ui.add_enabled_ui(!form.forwarding, |ui| ui.label("Cache Level:"));
+                ui.add_enabled_ui(!form.forwarding, |ui| {
+                    setting_combo(ui, "page_rule_cache_level", &mut form.cache_level, CACHE_LEVELS);
+                });
+                ui.end_row();
+
+                ui.add_enabled_ui(!form.forwarding, |ui| ui.label("SSL:"));
+                ui.add_enabled_ui(!form.forwarding, |ui| {
+                    setting_combo(ui, "page_rule_ssl", &mut form.ssl, SSL_MODES);
+                });
+                ui.end_row();
+            });
+
+            if !form.other_actions.is_empty() {
+                let ids: Vec<_> = form.other_actions.iter().filter_map(|a| a.id.as_deref()).collect();
+                ui.label(egui::RichText::new(format!("Other actions kept as-is: {}", ids.join(", "))).small().weak());
+            }
+            if form.forwarding {
+                ui.label(egui::RichText::new("Forwarding URL cannot be combined with other actions.").small().weak());
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(if editing { "Save" } else { "Create" }).clicked() {
+                    save = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+    }
+
+    if save {
+        save_page_rule(state, ctx, zone_id);
+    }
+    if cancel {
+        state.page_rule_form = None;
+    }
+}
+
+/// Combo box for an optional setting; the empty value means "not set"
+fn setting_combo(ui: &mut egui::Ui, id: &str, value: &mut String, options: &[&str]) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(if value.is_empty() { "(not set)" } else { value.as_str() })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(value, String::new(), "(not set)");
+            for opt in options {
+                ui.selectable_value(value, opt.to_string(), *opt);
+            }
+        });
+}
+
+fn form_from_rule(rule: &PageRule) -> PageRuleForm {
+    let mut form = PageRuleForm {
+        rule_id: rule.id.clone(),
+        url_pattern: rule
+            .targets
+            .as_ref()
+            .and_then(|t| t.first())
+            .and_then(|t| t.constraint.as_ref())
+            .and_then(|c| c.value.clone())
+            .unwrap_or_default(),
+        priority: rule.priority.map(|p| p.to_string()).unwrap_or_default(),
+        active: rule.status.as_deref() != Some("disabled"),
+        ..Default::default()
+    };
+
+    for action in rule.actions.clone().unwrap_or_default() {
+        let value = action.value.clone().unwrap_or_default();
+        match action.id.as_deref() {
+            Some("forwarding_url") => {
+                form.forwarding = true;
+                form.forwarding_url = value["url"].as_str().unwrap_or_default().to_string();
+                form.status_code = value["status_code"].as_u64().unwrap_or(301) as u16;
+            }
+            Some("cache_level") => form.cache_level = value.as_str().unwrap_or_default().to_string(),
+            Some("ssl") => form.ssl = value.as_str().unwrap_or_default().to_string(),
+            _ => form.other_actions.push(action),
+        }
+    }
+    form
+}
+
+/// Validate the form and build the API request
+fn build_request(form: &PageRuleForm) -> Result<CreatePageRuleRequest, String> {
+    let pattern = form.url_pattern.trim();
+    if pattern.is_empty() {
+        return Err("URL pattern is required".into());
+    }
+    if pattern.contains(char::is_whitespace) {
+        return Err("URL pattern must not contain spaces".into());
+    }
+    let priority = match form.priority.trim() {
+        "" => None,
+        p => match p.parse::<i32>() {
+            Ok(n) if n >= 1 => Some(n),
+            _ => return Err("Priority must be a positive number".into()),
+        },
+    };
+
+    let mut actions = Vec::new();
+    if form.forwarding {
+        let url = form.forwarding_url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err("Forwarding URL must start with http:// or https://".into());
+        }
+        if !form.other_actions.is_empty() {
+            return Err("Forwarding URL cannot be combined with the rule's other actions".into());
+        }
+        actions.push(PageRuleAction {
+            id: Some("forwarding_url".into()),
+            value: Some(serde_json::json!({ "url": url, "status_code": form.status_code })),
+        });
+    } else {
+        for (id, value) in [("cache_level", &form.cache_level), ("ssl", &form.ssl)] {
+            if !value.is_empty() {
+                actions.push(PageRuleAction {
+                    id: Some(id.into()),
+                    value: Some(serde_json::json!(value)),
+                });
+            }
+        }
+        actions.extend(form.other_actions.iter().cloned());
+    }
+    if actions.is_empty() {
+        return Err("Choose at least one action".into());
+    }
+
+    Ok(CreatePageRuleRequest {
+        targets: vec![PageRuleTarget {
+            target: Some("url".into()),
+            constraint: Some(PageRuleConstraint {
+                operator: Some("matches".into()),
+                value: Some(pattern.to_string()),
+            }),
+        }],
+        actions,
+        priority,
+        status: Some(if form.active { "active" } else { "disabled" }.into()),
+    })
+}
+
+fn save_page_rule(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     let client = match &state.client { Some(c) => c.clone(), None => return };
-    let pattern = state.redirect_form.url_pattern.trim().to_string();
-    let target = state.redirect_form.redirect_url.trim().to_string();
-    let status = state.redirect_form.status_code;
-    if pattern.is_empty() || target.is_empty() { return; }
+    let Some(form) = &state.page_rule_form else { return };
+    let request = match build_request(form) {
+        Ok(r) => r,
+        Err(msg) => {
+            state.notify(msg, NotifLevel::Warning);
+            return;
+        }
+    };
+    let rule_id = form.rule_id.clone();
     let zid = zone_id.to_string();
-    state.redirect_form = RedirectForm::default();
-    state.set_loading("Creating redirect...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
-        let result = client.create_redirect_rule(&zid, &pattern, &target, status).await;
-        AsyncResult::PageRuleCreated(result.map(|_| format!("Redirect created: {} -> {}", pattern, target)))
+    state.set_loading("Saving page rule...");
+    spawn_async(state, ctx, move || async move {
+        let result = async {
+            match rule_id {
+                Some(id) => client.update_page_rule(&zid, &id, &request).await,
+                None => {
+                    client.check_quota(&zid, QuotaResource::PageRule).await?;
+                    client.create_page_rule(&zid, &request).await
+                }
+            }
+        }
+        .await;
+        AsyncResult::PageRuleSaved(result)
     });
 }
@@ -2,6 +2,7 @@ use eframe::egui;
 
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
@@ -16,10 +17,13 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
         }
     };
 
-    if ui.button("\u{1F504} Refresh").clicked() {
-        load_ssl_status(state, ctx, &zone_id);
-        load_ssl_certs(state, ctx, &zone_id);
-    }
+    ui.horizontal(|ui| {
+        if ui.button("\u{1F504} Refresh").clicked() {
+            load_ssl_status(state, ctx, &zone_id);
+            load_ssl_certs(state, ctx, &zone_id);
+        }
+        render_freshness(ui, state, DataFeed::Ssl);
+    });
     ui.add_space(8.0);
 
     // SSL Mode
@@ -107,7 +111,7 @@ pub fn load_ssl_status(state: &mut AppState, ctx: &egui::Context, zone_id: &str)
     };
     let zid = zone_id.to_string();
     state.set_loading("Loading SSL status...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let mode = client.get_ssl_mode(&zid).await;
         let https = client.get_always_https(&zid).await;
         let min_tls_result = client.get_zone_setting(&zid, "min_tls_version").await;
@@ -131,7 +135,7 @@ fn load_ssl_certs(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
         None => return,
     };
     let zid = zone_id.to_string();
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.list_ssl_certificates(&zid).await;
         AsyncResult::SslCertificatesLoaded(result)
     });
@@ -145,7 +149,7 @@ fn set_ssl_mode(state: &mut AppState, ctx: &egui::Context, zone_id: &str, mode:
     let zid = zone_id.to_string();
     let m = mode.to_string();
     state.set_loading("Setting SSL mode...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_ssl_mode(&zid, &m).await;
         AsyncResult::SslModeSet(result.map(|_| m))
     });
@@ -158,7 +162,7 @@ fn toggle_always_https(state: &mut AppState, ctx: &egui::Context, zone_id: &str,
     };
     let zid = zone_id.to_string();
     state.set_loading("Toggling HTTPS...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_always_https(&zid, enable).await;
         AsyncResult::SslToggled(result.map(|_| if enable { "HTTPS enabled" } else { "HTTPS disabled" }.to_string()))
     });
@@ -172,7 +176,7 @@ fn set_min_tls(state: &mut AppState, ctx: &egui::Context, zone_id: &str, version
     let zid = zone_id.to_string();
     let v = version.to_string();
     state.set_loading("Setting min TLS...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.set_ssl_min_tls(&zid, &v).await;
         AsyncResult::SslToggled(result.map(|_| format!("Min TLS set to {}", v)))
     });
@@ -2,6 +2,7 @@ use eframe::egui;
 
 use crate::gui::async_bridge::spawn_async;
 use crate::gui::state::*;
+use crate::gui::widgets::freshness::render_freshness;
 use crate::gui::theme;
 
 pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
@@ -18,6 +19,7 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
         if ui.button("\u{1F504} Refresh").clicked() {
             load_workers(state, ctx, &account_id);
         }
+        render_freshness(ui, state, DataFeed::Workers);
     });
     ui.add_space(4.0);
 
@@ -39,14 +41,17 @@ pub fn render(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     ui.add_space(4.0);
 
     match state.workers_tab {
-        WorkersTab::Scripts => render_scripts(state, ctx, ui),
+        WorkersTab::Scripts => {
+            render_scripts(state, ctx, ui);
+            render_stats(state, ui);
+        }
         WorkersTab::Routes => render_routes(state, ui),
         WorkersTab::Kv => render_kv(state, ui),
         WorkersTab::Domains => render_domains(state, ui),
     }
 }
 
-fn render_scripts(state: &mut AppState, _ctx: &egui::Context, ui: &mut egui::Ui) {
+fn render_scripts(state: &mut AppState, ctx: &egui::Context, ui: &mut egui::Ui) {
     if state.worker_scripts.is_empty() {
         ui.label("No worker scripts.");
         return;
@@ -71,19 +76,73 @@ fn render_scripts(state: &mut AppState, _ctx: &egui::Context, ui: &mut egui::Ui)
                 ui.label(handlers);
                 ui.label(egui::RichText::new(script.modified_on.as_deref().unwrap_or("-")).small());
                 if let Some(name) = &script.id {
-                    if ui.small_button(egui::RichText::new("Delete").color(theme::DANGER)).clicked() {
-                        state.confirm_dialog = Some(ConfirmDialog {
-                            title: "Delete Worker".to_string(),
-                            message: format!("Delete worker '{}'?", name),
-                            action: ConfirmAction::DeleteWorker(name.clone()),
-                        });
-                    }
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Stats").clicked() {
+                            load_worker_stats(state, ctx, name);
+                        }
+                        if ui.small_button(egui::RichText::new("Delete").color(theme::DANGER)).clicked() {
+                            state.confirm_dialog = Some(ConfirmDialog {
+                                title: "Delete Worker".to_string(),
+                                message: format!("Delete worker '{}'?", name),
+                                action: ConfirmAction::DeleteWorker(name.clone()),
+                            });
+                        }
+                    });
+                } else {
+                    ui.label("");
                 }
                 ui.end_row();
             }
         });
 }
 
+/// Last 24h invocation summary and errors-over-time chart for the selected script
+fn render_stats(state: &AppState, ui: &mut egui::Ui) {
+    use egui_plot::{Line, Plot, PlotPoints};
+
+    let Some(stats) = &state.worker_stats else {
+        return;
+    };
+    ui.add_space(12.0);
+    ui.label(egui::RichText::new(format!("Stats: {} (last 24h)", stats.script)).strong());
+    let cpu = |us: Option<f64>| us.map(|us| format!("{:.2} ms", us / 1000.0)).unwrap_or_else(|| "-".into());
+    ui.horizontal(|ui| {
+        ui.label(format!("Requests: {}", stats.requests));
+        ui.separator();
+        let color = if stats.errors > 0 { theme::DANGER } else { theme::SUCCESS };
+        ui.label(egui::RichText::new(format!("Errors: {} ({:.2}%)", stats.errors, stats.error_rate())).color(color));
+        ui.separator();
+        ui.label(format!("Subrequests: {}", stats.subrequests));
+        ui.separator();
+        ui.label(format!(
+            "CPU P50/P90/P99: {} / {} / {}",
+            cpu(stats.cpu_time_p50),
+            cpu(stats.cpu_time_p90),
+            cpu(stats.cpu_time_p99)
+        ));
+    });
+
+    if stats.timeseries.is_empty() {
+        ui.label("No invocations in this period.");
+        return;
+    }
+    ui.add_space(4.0);
+    ui.label(egui::RichText::new("Errors Over Time").strong());
+    let errors: PlotPoints = stats
+        .timeseries
+        .iter()
+        .enumerate()
+        .map(|(i, p)| [i as f64, p.errors as f64])
+        .collect();
+    Plot::new("worker_errors_chart")
+        .height(160.0)
+        .show_axes(true)
+        .include_y(0.0)
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(errors).name("Errors").color(theme::DANGER));
+        });
+}
+
 fn render_routes(state: &mut AppState, ui: &mut egui::Ui) {
     if state.worker_routes.is_empty() {
         ui.label("No worker routes.");
@@ -165,15 +224,15 @@ pub fn load_workers(state: &mut AppState, ctx: &egui::Context, account_id: &str)
     let c3 = client.clone();
     state.set_loading("Loading workers...");
 
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.list_workers(&aid).await;
         AsyncResult::WorkersLoaded(result)
     });
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = c2.list_kv_namespaces(&aid2).await;
         AsyncResult::KvNamespacesLoaded(result)
     });
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = c3.list_worker_domains(&aid3).await;
         AsyncResult::WorkerDomainsLoaded(result)
     });
@@ -181,9 +240,23 @@ pub fn load_workers(state: &mut AppState, ctx: &egui::Context, account_id: &str)
     // Routes need zone_id
     if let Some(zone_id) = state.zone_id() {
         let c4 = state.client.as_ref().unwrap().clone();
-        spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+        spawn_async(state, ctx, move || async move {
             let result = c4.list_worker_routes(&zone_id).await;
             AsyncResult::WorkerRoutesLoaded(result)
         });
     }
 }
+
+fn load_worker_stats(state: &mut AppState, ctx: &egui::Context, script: &str) {
+    let client = match &state.client { Some(c) => c.clone(), None => return };
+    let account_id = state.config.cloudflare.account_id.clone().unwrap_or_default();
+    let script = script.to_string();
+    state.set_loading("Loading worker stats...");
+    spawn_async(state, ctx, move || async move {
+        let until = chrono::Utc::now();
+        let result = client
+            .get_worker_stats(&account_id, &script, until - chrono::Duration::hours(24), until)
+            .await;
+        AsyncResult::WorkerStatsLoaded(result)
+    });
+}
@@ -153,7 +153,7 @@ fn load_zones(state: &mut AppState, ctx: &egui::Context) {
         None => return,
     };
     state.set_loading("Loading zones...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let params = ZoneListParams { per_page: Some(50), ..Default::default() };
         let result = client.list_zones(&params).await;
         AsyncResult::ZonesLoaded(result.map(|r| r.result.unwrap_or_default()))
@@ -169,7 +169,7 @@ fn add_zone(state: &mut AppState, ctx: &egui::Context) {
     let account_id = state.config.cloudflare.account_id.clone();
     state.zone_add_domain.clear();
     state.set_loading("Creating zone...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let req = CreateZoneRequest {
             name: domain,
             account: account_id.map(|id| crate::models::zone::CreateZoneAccount { id }),
@@ -188,7 +188,7 @@ fn toggle_pause(state: &mut AppState, ctx: &egui::Context, zone_id: &str, curren
     };
     let zid = zone_id.to_string();
     state.set_loading("Toggling zone...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.toggle_zone_pause(&zid, !currently_paused).await;
         AsyncResult::ZoneToggled(result)
     });
@@ -201,7 +201,7 @@ fn load_settings(state: &mut AppState, ctx: &egui::Context, zone_id: &str) {
     };
     let zid = zone_id.to_string();
     state.set_loading("Loading settings...");
-    spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+    spawn_async(state, ctx, move || async move {
         let result = client.get_zone_settings(&zid).await;
         AsyncResult::ZoneSettingsLoaded(result)
     });
@@ -1,7 +1,24 @@
 use eframe::egui;
 
-use super::state::{AppState, Page};
+use super::state::{AppState, NotifLevel, Page};
 use super::theme::ACCENT;
+use crate::progress::format_eta;
+
+/// Sidebar entries: (page, icon, label)
+pub const NAV_ITEMS: &[(Page, &str, &str)] = &[
+    (Page::Dashboard, "\u{1F4CA}", "Dashboard"),
+    (Page::Zone, "\u{1F310}", "Zones"),
+    (Page::Compare, "\u{1F50D}", "Compare"),
+    (Page::Dns, "\u{1F4E1}", "DNS"),
+    (Page::Ssl, "\u{1F512}", "SSL/TLS"),
+    (Page::Firewall, "\u{1F6E1}\u{FE0F}", "Firewall"),
+    (Page::Cache, "\u{26A1}", "Cache"),
+    (Page::PageRules, "\u{1F4C4}", "Page Rules"),
+    (Page::Workers, "\u{2699}\u{FE0F}", "Workers"),
+    (Page::Analytics, "\u{1F4C8}", "Analytics"),
+    (Page::AiAssistant, "\u{1F916}", "AI Assistant"),
+    (Page::Config, "\u{1F527}", "Settings"),
+];
 
 pub fn render_sidebar(state: &mut AppState, ctx: &egui::Context) -> bool {
     let mut page_changed = false;
@@ -19,21 +36,18 @@ pub fn render_sidebar(state: &mut AppState, ctx: &egui::Context) -> bool {
             ui.separator();
             ui.add_space(4.0);
 
-            let nav_items: &[(Page, &str, &str)] = &[
-                (Page::Dashboard, "\u{1F4CA}", "Dashboard"),
-                (Page::Zone, "\u{1F310}", "Zones"),
-                (Page::Dns, "\u{1F4E1}", "DNS"),
-                (Page::Ssl, "\u{1F512}", "SSL/TLS"),
-                (Page::Firewall, "\u{1F6E1}\u{FE0F}", "Firewall"),
-                (Page::Cache, "\u{26A1}", "Cache"),
-                (Page::PageRules, "\u{1F4C4}", "Page Rules"),
-                (Page::Workers, "\u{2699}\u{FE0F}", "Workers"),
-                (Page::Analytics, "\u{1F4C8}", "Analytics"),
-                (Page::AiAssistant, "\u{1F916}", "AI Assistant"),
-                (Page::Config, "\u{1F527}", "Settings"),
-            ];
+            if ui
+                .add_sized(
+                    [ui.available_width(), 0.0],
+                    egui::Button::new(egui::RichText::new("\u{1F50E} Search...   Ctrl+K").weak()),
+                )
+                .clicked()
+            {
+                state.show_palette = true;
+            }
+            ui.add_space(4.0);
 
-            for (page, icon, label) in nav_items {
+            for (page, icon, label) in NAV_ITEMS {
                 let is_selected = state.current_page == *page;
                 let text = format!("{} {}", icon, label);
                 let response = ui.selectable_label(is_selected, text);
@@ -100,6 +114,29 @@ pub fn render_sidebar(state: &mut AppState, ctx: &egui::Context) -> bool {
                         ui.label(
                             egui::RichText::new(&state.loading_label).small().weak(),
                         );
+                        if let Some(progress) = &state.progress {
+                            let mut text = format!("{}/{}", progress.done, progress.total);
+                            if let Some(eta) = progress.eta {
+                                text.push_str(&format!(" · {} left", format_eta(eta)));
+                            }
+                            ui.add(
+                                egui::ProgressBar::new(progress.fraction())
+                                    .desired_width(160.0)
+                                    .text(egui::RichText::new(text).small()),
+                            );
+                        }
+                        if ui
+                            .small_button("\u{2715}")
+                            .on_hover_text("Cancel")
+                            .clicked()
+                        {
+                            let label = state.loading_label.clone();
+                            state.cancel_pending();
+                            state.notify(
+                                format!("Cancelled: {}", label.trim_end_matches("...")),
+                                NotifLevel::Warning,
+                            );
+                        }
                     });
                 }
             });
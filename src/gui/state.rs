@@ -1,15 +1,20 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::mpsc;
 use tokio::runtime::Handle;
 
+use super::async_bridge::{CancelToken, ResultSender, TaggedResult};
+
 use crate::api::client::CfClient;
 use crate::config::settings::AppConfig;
-use crate::models::analytics::AnalyticsDashboard;
-use crate::models::dns::DnsRecord;
-use crate::models::firewall::{FirewallRule, IpAccessRule, RateLimitRule};
-use crate::models::page_rules::PageRule;
+use crate::models::analytics::{AnalyticsDashboard, DnsAnalytics};
+use crate::models::backup::ZoneBackup;
+use crate::models::dns::{DnsRecord, Dnssec};
+use crate::models::firewall::{FirewallEvent, FirewallRule, IpAccessRule, RateLimitRule};
+use crate::models::page_rules::{PageRule, PageRuleAction};
 use crate::models::ssl::{SslCertificate, SslVerification};
-use crate::models::workers::{KvNamespace, WorkerDomain, WorkerRoute, WorkerScript};
-use crate::models::zone::{Zone, ZoneSetting};
+use crate::models::workers::{KvNamespace, WorkerDomain, WorkerRoute, WorkerScript, WorkerStats};
+use crate::progress::ProgressState;
+use crate::models::zone::{Zone, ZoneHealth, ZoneSetting};
 
 use crate::ai::analyzer::{AnalysisResult, SuggestedAction};
 
@@ -20,12 +25,18 @@ pub enum AsyncResult {
     ZoneDeleted(anyhow::Result<String>),
     ZoneToggled(anyhow::Result<Zone>),
     ZoneSettingsLoaded(anyhow::Result<Vec<ZoneSetting>>),
+    ZonesCompared(anyhow::Result<(ZoneBackup, ZoneBackup)>),
+    ZoneHealthLoaded(Vec<ZoneHealth>),
 
     DnsRecordsLoaded(anyhow::Result<Vec<DnsRecord>>),
     DnsRecordCreated(anyhow::Result<DnsRecord>),
     DnsRecordUpdated(anyhow::Result<DnsRecord>),
     DnsRecordDeleted(anyhow::Result<String>),
+    /// One result per selected record
+    DnsBulkUpdated(Vec<anyhow::Result<DnsRecord>>),
+    DnsBulkDeleted(Vec<anyhow::Result<String>>),
     DnsExported(anyhow::Result<String>),
+    DnssecLoaded(anyhow::Result<Dnssec>),
 
     SslStatusLoaded(anyhow::Result<(String, bool, String)>),
     SslModeSet(anyhow::Result<String>),
@@ -39,6 +50,9 @@ pub enum AsyncResult {
     IpRuleDeleted(anyhow::Result<String>),
     SecurityLevelLoaded(anyhow::Result<String>),
     RateLimitsLoaded(anyhow::Result<Vec<RateLimitRule>>),
+    FirewallEventsLoaded(anyhow::Result<Vec<FirewallEvent>>),
+    RateLimitSaved(anyhow::Result<RateLimitRule>),
+    RateLimitDeleted(anyhow::Result<String>),
     FirewallActionDone(anyhow::Result<String>),
 
     CacheStatusLoaded(anyhow::Result<(String, u32, bool)>),
@@ -46,7 +60,7 @@ pub enum AsyncResult {
     CacheActionDone(anyhow::Result<String>),
 
     PageRulesLoaded(anyhow::Result<Vec<PageRule>>),
-    PageRuleCreated(anyhow::Result<String>),
+    PageRuleSaved(anyhow::Result<PageRule>),
     PageRuleDeleted(anyhow::Result<String>),
 
     WorkersLoaded(anyhow::Result<Vec<WorkerScript>>),
@@ -54,19 +68,114 @@ pub enum AsyncResult {
     KvNamespacesLoaded(anyhow::Result<Vec<KvNamespace>>),
     WorkerDomainsLoaded(anyhow::Result<Vec<WorkerDomain>>),
     WorkerDeleted(anyhow::Result<String>),
+    WorkerStatsLoaded(anyhow::Result<WorkerStats>),
 
     AnalyticsLoaded(anyhow::Result<AnalyticsDashboard>),
+    DnsAnalyticsLoaded(anyhow::Result<DnsAnalytics>),
 
     AiResponse(anyhow::Result<AnalysisResult>),
+    /// Incremental text of a streaming AI response
+    AiToken(String),
+    /// (message index, action index, result message)
+    AiActionApplied(usize, usize, anyhow::Result<String>),
 
     ConfigSaved(anyhow::Result<()>),
     TokenVerified(anyhow::Result<bool>),
+
+    /// (zone name, message) from the background monitor
+    MonitorAlert(String, String),
+    /// Progress update from a long-running task
+    Progress(ProgressState),
+}
+
+impl AsyncResult {
+    /// Data feed refreshed by this result and the error if the load failed
+    pub fn feed_outcome(&self) -> Option<(DataFeed, Option<String>)> {
+        fn error<T>(result: &anyhow::Result<T>) -> Option<String> {
+            result.as_ref().err().map(|e| e.to_string())
+        }
+        match self {
+            AsyncResult::ZonesLoaded(r) => Some((DataFeed::Zones, error(r))),
+            AsyncResult::DnsRecordsLoaded(r) => Some((DataFeed::Dns, error(r))),
+            AsyncResult::SslStatusLoaded(r) => Some((DataFeed::Ssl, error(r))),
+            AsyncResult::FirewallRulesLoaded(r) => Some((DataFeed::Firewall, error(r))),
+            AsyncResult::IpAccessRulesLoaded(r) => Some((DataFeed::Firewall, error(r))),
+            AsyncResult::FirewallEventsLoaded(r) => Some((DataFeed::FirewallEvents, error(r))),
+            AsyncResult::CacheStatusLoaded(r) => Some((DataFeed::Cache, error(r))),
+            AsyncResult::PageRulesLoaded(r) => Some((DataFeed::PageRules, error(r))),
+            AsyncResult::WorkersLoaded(r) => Some((DataFeed::Workers, error(r))),
+            AsyncResult::AnalyticsLoaded(r) => Some((DataFeed::Analytics, error(r))),
+            AsyncResult::DnsAnalyticsLoaded(r) => Some((DataFeed::Analytics, error(r))),
+            _ => None,
+        }
+    }
+}
+
+/// Page data that can be refreshed in the background
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataFeed {
+    Zones,
+    Dns,
+    Ssl,
+    Firewall,
+    FirewallEvents,
+    Cache,
+    PageRules,
+    Workers,
+    Analytics,
+}
+
+impl DataFeed {
+    /// Key of the refresh interval in `[gui.refresh]`
+    pub fn config_key(self) -> &'static str {
+        match self {
+            DataFeed::Zones => "dashboard",
+            DataFeed::Dns => "dns",
+            DataFeed::Ssl => "ssl",
+            DataFeed::Firewall => "firewall",
+            DataFeed::FirewallEvents => "firewall_events",
+            DataFeed::Cache => "cache",
+            DataFeed::PageRules => "page_rules",
+            DataFeed::Workers => "workers",
+            DataFeed::Analytics => "analytics",
+        }
+    }
+
+    /// Feed displayed on a page
+    pub fn for_page(page: &Page, firewall_tab: &FirewallTab) -> Option<Self> {
+        match page {
+            Page::Dashboard => Some(DataFeed::Zones),
+            Page::Dns => Some(DataFeed::Dns),
+            Page::Ssl => Some(DataFeed::Ssl),
+            Page::Firewall if *firewall_tab == FirewallTab::Events => Some(DataFeed::FirewallEvents),
+            Page::Firewall => Some(DataFeed::Firewall),
+            Page::Cache => Some(DataFeed::Cache),
+            Page::PageRules => Some(DataFeed::PageRules),
+            Page::Workers => Some(DataFeed::Workers),
+            Page::Analytics => Some(DataFeed::Analytics),
+            Page::Zone | Page::Compare | Page::AiAssistant | Page::Config => None,
+        }
+    }
+}
+
+/// Freshness of a data feed
+#[derive(Default)]
+pub struct FeedStatus {
+    /// Time of the last successful load
+    pub updated_at: Option<chrono::DateTime<chrono::Local>>,
+    /// When the last load finished (successfully or not), drives the refresh timer
+    pub finished: Option<std::time::Instant>,
+    /// Error of the last load; the data on screen is stale
+    pub error: Option<String>,
+    /// A background refresh is in flight
+    pub refreshing: bool,
 }
 /// Navigation pages
 #[derive(Debug, Clone, PartialEq)]
 pub enum Page {
     Dashboard,
     Zone,
+    Compare,
     Dns,
     Ssl,
     Firewall,
@@ -103,6 +212,16 @@ impl Notification {
     }
 }
 
+/// Number of notifications kept in the notification center
+pub const NOTIFICATION_HISTORY_LIMIT: usize = 50;
+
+/// Notification kept in the notification center after its toast expires
+pub struct NotificationEntry {
+    pub message: String,
+    pub level: NotifLevel,
+    pub time: chrono::DateTime<chrono::Local>,
+}
+
 /// AI chat message
 #[derive(Clone)]
 pub struct AiChatMessage {
@@ -159,19 +278,77 @@ pub struct DnsEditForm {
     pub comment: String,
 }
 
-/// Redirect form for page rules
-pub struct RedirectForm {
+impl DnsEditForm {
+    pub fn from_record(record: &DnsRecord) -> Self {
+        Self {
+            record_id: record.id.clone().unwrap_or_default(),
+            record_type: record.record_type.clone(),
+            name: record.name.clone(),
+            content: record.content.clone(),
+            ttl: record.ttl.unwrap_or(1).to_string(),
+            proxied: record.proxied.unwrap_or(false),
+            priority: record.priority.map(|p| p.to_string()).unwrap_or_default(),
+            comment: record.comment.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Page rule create/edit form
+pub struct PageRuleForm {
+    /// Rule being edited (None when creating a new rule)
+    pub rule_id: Option<String>,
     pub url_pattern: String,
-    pub redirect_url: String,
+    pub priority: String,
+    pub active: bool,
+    pub forwarding: bool,
+    pub forwarding_url: String,
     pub status_code: u16,
+    /// Empty string leaves the setting untouched
+    pub cache_level: String,
+    pub ssl: String,
+    /// Actions on an existing rule that the form doesn't expose (kept on save)
+    pub other_actions: Vec<PageRuleAction>,
 }
 
-impl Default for RedirectForm {
+impl Default for PageRuleForm {
     fn default() -> Self {
         Self {
+            rule_id: None,
             url_pattern: String::new(),
-            redirect_url: String::new(),
+            priority: String::new(),
+            active: true,
+            forwarding: false,
+            forwarding_url: String::new(),
             status_code: 301,
+            cache_level: String::new(),
+            ssl: String::new(),
+            other_actions: Vec::new(),
+        }
+    }
+}
+
+/// Rate limit add/edit form
+pub struct RateLimitForm {
+    /// Rule being edited (None when creating a new rule)
+    pub rule_id: Option<String>,
+    pub url: String,
+    pub threshold: String,
+    pub period: String,
+    pub mode: String,
+    pub timeout: String,
+    pub description: String,
+}
+
+impl Default for RateLimitForm {
+    fn default() -> Self {
+        Self {
+            rule_id: None,
+            url: String::new(),
+            threshold: "100".to_string(),
+            period: "60".to_string(),
+            mode: "ban".to_string(),
+            timeout: "600".to_string(),
+            description: String::new(),
         }
     }
 }
@@ -185,6 +362,20 @@ pub enum WorkersTab {
     Domains,
 }
 
+/// Analytics tab
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnalyticsTab {
+    Traffic,
+    Dns,
+}
+
+/// Firewall tab
+#[derive(Debug, Clone, PartialEq)]
+pub enum FirewallTab {
+    Rules,
+    Events,
+}
+
 /// Confirm dialog
 pub struct ConfirmDialog {
     pub title: String,
@@ -197,10 +388,16 @@ pub struct ConfirmDialog {
 pub enum ConfirmAction {
     DeleteZone(String),
     DeleteDnsRecord(String, String),
+    /// (zone_id, record ids)
+    DeleteDnsRecords(String, Vec<String>),
+    DisableDnssec(String),
     DeletePageRule(String, String),
     DeleteWorker(String),
     PurgeAllCache(String),
     DeleteIpRule(String, String),
+    DeleteRateLimit(String, String),
+    /// (zone_id, message index, action index, action)
+    ApplyAiAction(String, usize, usize, SuggestedAction),
 }
 
 /// Full application state
@@ -209,24 +406,56 @@ pub struct AppState {
     pub config: AppConfig,
     pub client: Option<CfClient>,
     pub tokio_handle: Handle,
-    pub tx: mpsc::Sender<AsyncResult>,
-    pub rx: mpsc::Receiver<AsyncResult>,
+    pub tx: mpsc::Sender<TaggedResult>,
+    pub rx: mpsc::Receiver<TaggedResult>,
+    /// Cancels the tasks spawned for the current page visit
+    pub cancel: CancelToken,
     pub loading: bool,
     pub loading_label: String,
+    /// Determinate progress of the running task, if it reports any
+    pub progress: Option<ProgressState>,
     pub notifications: Vec<Notification>,
+    /// Most recent notifications, newest first
+    pub notification_history: VecDeque<NotificationEntry>,
+    /// Notifications received since the notification center was last opened
+    pub unread_notifications: usize,
+    pub show_notification_center: bool,
+    /// Background monitor task, running while enabled and zones are available
+    pub monitor_cancel: Option<CancelToken>,
+    /// Zone names and settings the running monitor was started with
+    pub monitor_key: String,
+    pub monitor_zones: Vec<String>,
+    /// Close for real instead of minimizing to keep monitoring
+    pub quit_requested: bool,
+    pub background_hint_shown: bool,
+    /// Ctrl+K command palette
+    pub show_palette: bool,
+    pub palette_query: String,
+    pub palette_selected: usize,
+    /// Errors from background refreshes go to the notification history without a toast
+    pub background_result: bool,
     pub connection_ok: Option<bool>,
 
     // Navigation
     pub current_page: Page,
+    /// Last load of each page's data (auto refresh and stale indicators)
+    pub feeds: HashMap<DataFeed, FeedStatus>,
     pub zones: Vec<Zone>,
     pub selected_zone: Option<Zone>,
     pub zones_loaded: bool,
+    pub zone_health: Vec<ZoneHealth>,
 
     // Zone page
     pub zone_search: String,
     pub zone_add_domain: String,
     pub zone_settings: Vec<ZoneSetting>,
 
+    // Compare page
+    pub compare_zone_a: Option<Zone>,
+    pub compare_zone_b: Option<Zone>,
+    pub compare_result: Option<(ZoneBackup, ZoneBackup)>,
+    pub compare_only_diff: bool,
+
     // DNS page
     pub dns_records: Vec<DnsRecord>,
     pub dns_filter_type: String,
@@ -234,6 +463,12 @@ pub struct AppState {
     pub dns_add_form: DnsAddForm,
     pub dns_edit_form: Option<DnsEditForm>,
     pub dns_show_add: bool,
+    /// Record ids checked for bulk actions
+    pub dns_selected: HashSet<String>,
+    pub dns_bulk_ttl: String,
+    /// (record id, content) being edited in the grid
+    pub dns_inline_edit: Option<(String, String)>,
+    pub dnssec: Option<Dnssec>,
 
     // SSL page
     pub ssl_mode: String,
@@ -249,6 +484,12 @@ pub struct AppState {
     pub rate_limits: Vec<RateLimitRule>,
     pub fw_ip_input: String,
     pub fw_note_input: String,
+    pub rl_form: RateLimitForm,
+    pub firewall_tab: FirewallTab,
+    pub firewall_events: Vec<FirewallEvent>,
+    /// Event filters ("" = all actions)
+    pub fw_events_action: String,
+    pub fw_events_since: String,
 
     // Cache page
     pub cache_level: String,
@@ -258,7 +499,7 @@ pub struct AppState {
 
     // Page Rules page
     pub page_rules: Vec<PageRule>,
-    pub redirect_form: RedirectForm,
+    pub page_rule_form: Option<PageRuleForm>,
 
     // Workers page
     pub worker_scripts: Vec<WorkerScript>,
@@ -266,19 +507,29 @@ pub struct AppState {
     pub kv_namespaces: Vec<KvNamespace>,
     pub worker_domains: Vec<WorkerDomain>,
     pub workers_tab: WorkersTab,
+    /// Invocation stats of the script selected on the Scripts tab
+    pub worker_stats: Option<WorkerStats>,
 
     // Analytics page
     pub analytics: Option<AnalyticsDashboard>,
     pub analytics_period: String,
+    pub analytics_tab: AnalyticsTab,
+    pub dns_analytics: Option<DnsAnalytics>,
 
     // AI Assistant page
     pub ai_messages: Vec<AiChatMessage>,
     pub ai_input: String,
     pub ai_mode: AiMode,
+    /// Partial assistant reply while a streaming response is in progress
+    pub ai_streaming: String,
+    /// (message index, action index) of AI actions already applied successfully
+    pub ai_applied: HashSet<(usize, usize)>,
 
     // Config page
     pub config_edit: AppConfig,
     pub config_show_secrets: bool,
+    /// Profile selected with `--profile` (None = active profile from config)
+    pub profile: Option<String>,
 
     // Confirm dialog
     pub confirm_dialog: Option<ConfirmDialog>,
@@ -294,23 +545,47 @@ impl AppState {
             tokio_handle: handle,
             tx,
             rx,
+            cancel: CancelToken::default(),
             loading: false,
             loading_label: String::new(),
+            progress: None,
             notifications: Vec::new(),
+            notification_history: VecDeque::new(),
+            unread_notifications: 0,
+            show_notification_center: false,
+            show_palette: false,
+            monitor_cancel: None,
+            monitor_key: String::new(),
+            monitor_zones: Vec::new(),
+            quit_requested: false,
+            background_hint_shown: false,
+            palette_query: String::new(),
+            palette_selected: 0,
+            background_result: false,
             connection_ok: None,
             current_page: Page::Dashboard,
+            feeds: HashMap::new(),
             zones: Vec::new(),
             selected_zone: None,
             zones_loaded: false,
+            zone_health: Vec::new(),
             zone_search: String::new(),
             zone_add_domain: String::new(),
             zone_settings: Vec::new(),
+            compare_zone_a: None,
+            compare_zone_b: None,
+            compare_result: None,
+            compare_only_diff: true,
             dns_records: Vec::new(),
             dns_filter_type: String::new(),
             dns_search: String::new(),
             dns_add_form: DnsAddForm::default(),
+            dns_selected: HashSet::new(),
+            dns_bulk_ttl: "1".to_string(),
+            dns_inline_edit: None,
             dns_edit_form: None,
             dns_show_add: false,
+            dnssec: None,
             ssl_mode: String::new(),
             ssl_always_https: false,
             ssl_min_tls: "1.0".to_string(),
@@ -322,30 +597,91 @@ impl AppState {
             rate_limits: Vec::new(),
             fw_ip_input: String::new(),
             fw_note_input: String::new(),
+            rl_form: RateLimitForm::default(),
+            firewall_tab: FirewallTab::Rules,
+            firewall_events: Vec::new(),
+            fw_events_action: String::new(),
+            fw_events_since: "1h".to_string(),
             cache_level: String::new(),
             browser_cache_ttl: 0,
             dev_mode_on: false,
             purge_urls_input: String::new(),
             page_rules: Vec::new(),
-            redirect_form: RedirectForm::default(),
+            page_rule_form: None,
             worker_scripts: Vec::new(),
             worker_routes: Vec::new(),
             kv_namespaces: Vec::new(),
             worker_domains: Vec::new(),
             workers_tab: WorkersTab::Scripts,
+            worker_stats: None,
             analytics: None,
             analytics_period: "24h".to_string(),
+            analytics_tab: AnalyticsTab::Traffic,
+            dns_analytics: None,
             ai_messages: Vec::new(),
             ai_input: String::new(),
             ai_mode: AiMode::Ask,
+            ai_streaming: String::new(),
+            ai_applied: HashSet::new(),
             config_edit,
             config_show_secrets: false,
+            profile: None,
             confirm_dialog: None,
         }
     }
 
     pub fn notify(&mut self, msg: impl Into<String>, level: NotifLevel) {
-        self.notifications.push(Notification::new(msg.into(), level));
+        let message = msg.into();
+        self.notification_history.push_front(NotificationEntry {
+            message: message.clone(),
+            level: level.clone(),
+            time: chrono::Local::now(),
+        });
+        self.notification_history.truncate(NOTIFICATION_HISTORY_LIMIT);
+        if !self.show_notification_center {
+            self.unread_notifications = (self.unread_notifications + 1).min(NOTIFICATION_HISTORY_LIMIT);
+        }
+        // The stale badge already shows failed background refreshes
+        if self.background_result && level == NotifLevel::Error {
+            return;
+        }
+        self.notifications.push(Notification::new(message, level));
+    }
+
+    /// Record the outcome of a data load; returns whether it was a background refresh
+    pub fn record_feed(&mut self, feed: DataFeed, error: Option<String>) -> bool {
+        let status = self.feeds.entry(feed).or_default();
+        let background = std::mem::take(&mut status.refreshing);
+        status.finished = Some(std::time::Instant::now());
+        match error {
+            Some(error) => status.error = Some(error),
+            None => {
+                status.error = None;
+                status.updated_at = Some(chrono::Local::now());
+            }
+        }
+        background
+    }
+
+    /// Sender bound to the current cancellation token
+    pub fn result_sender(&self) -> ResultSender {
+        ResultSender::new(self.tx.clone(), self.cancel.clone())
+    }
+
+    /// Sender for tasks that must outlive page navigation and Cancel
+    pub fn detached_sender(&self) -> ResultSender {
+        ResultSender::new(self.tx.clone(), CancelToken::default())
+    }
+
+    /// Abort in-flight tasks so their results can't overwrite newer state
+    pub fn cancel_pending(&mut self) {
+        self.cancel.cancel();
+        self.cancel = CancelToken::default();
+        self.clear_loading();
+        self.ai_streaming.clear();
+        for status in self.feeds.values_mut() {
+            status.refreshing = false;
+        }
     }
 
     pub fn set_loading(&mut self, label: &str) {
@@ -356,6 +692,7 @@ impl AppState {
     pub fn clear_loading(&mut self) {
         self.loading = false;
         self.loading_label.clear();
+        self.progress = None;
     }
 
     pub fn zone_id(&self) -> Option<String> {
@@ -0,0 +1,334 @@
+use std::net::IpAddr;
+
+use eframe::egui;
+
+use super::super::router::NAV_ITEMS;
+use super::super::state::*;
+use crate::models::dns::DnsRecord;
+use crate::models::zone::Zone;
+
+/// Number of matches shown at once
+const MAX_RESULTS: usize = 12;
+
+/// What happens when a palette entry is chosen
+#[derive(Clone)]
+enum PaletteAction {
+    GoTo(Page),
+    SelectZone(Zone),
+    EditDns(DnsRecord),
+    AddDns,
+    PurgeUrls,
+    PurgeEverything,
+    BlockIp,
+    WhitelistIp,
+    FirewallEvents,
+    NewPageRule,
+}
+
+#[derive(Clone)]
+struct PaletteItem {
+    label: String,
+    detail: String,
+    action: PaletteAction,
+}
+
+/// Values typed after an action name ("block 1.2.3.4") used to pre-fill forms
+#[derive(Default)]
+struct PaletteArgs {
+    ip: Option<String>,
+    urls: Vec<String>,
+}
+
+/// Split the query into the text to match and form arguments
+fn parse_query(query: &str) -> (String, PaletteArgs) {
+    let mut args = PaletteArgs::default();
+    let mut words = Vec::new();
+    for token in query.split_whitespace() {
+        if token.starts_with("http://") || token.starts_with("https://") {
+            args.urls.push(token.to_string());
+        } else if args.ip.is_none() && is_ip_or_cidr(token) {
+            args.ip = Some(token.to_string());
+        } else {
+            words.push(token);
+        }
+    }
+    (words.join(" "), args)
+}
+
+fn is_ip_or_cidr(token: &str) -> bool {
+    let (ip, prefix) = token.split_once('/').unwrap_or((token, "0"));
+    ip.parse::<IpAddr>().is_ok() && prefix.parse::<u8>().is_ok()
+}
+
+/// Case-insensitive subsequence match; higher scores rank first.
+/// Consecutive characters and word starts score extra, long candidates slightly less.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    let chars: Vec<char> = candidate.to_lowercase().chars().collect();
+    let bonus = |i: usize| if i == 0 || !chars[i - 1].is_alphanumeric() { 3 } else { 0 };
+    let Some((first, rest)) = query.split_first() else {
+        return Some(-(chars.len() as i32) / 4);
+    };
+
+    // best[i]: best score with the current query char matched at position i
+    let mut best: Vec<Option<i32>> = (0..chars.len())
+        .map(|i| (chars[i] == *first).then(|| 1 + bonus(i)))
+        .collect();
+    for q in rest {
+        let mut next = vec![None; chars.len()];
+        let mut before_gap: Option<i32> = None;
+        for i in 0..chars.len() {
+            if i >= 2 {
+                before_gap = before_gap.max(best[i - 2]);
+            }
+            if chars[i] != *q {
+                continue;
+            }
+            let consecutive = i.checked_sub(1).and_then(|j| best[j]).map(|s| s + 4);
+            next[i] = consecutive.max(before_gap).map(|s| s + 1 + bonus(i));
+        }
+        best = next;
+    }
+
+    best.into_iter().flatten().max().map(|score| score * 4 - chars.len() as i32 / 4)
+}
+
+fn palette_items(state: &AppState, args: &PaletteArgs) -> Vec<PaletteItem> {
+    let mut items = Vec::new();
+    let ip = args.ip.as_deref().unwrap_or_default();
+    let urls = args.urls.join(" ");
+
+    let actions = [
+        (format!("Purge cache by URL {}", urls), "Cache", PaletteAction::PurgeUrls),
+        ("Purge everything".to_string(), "Cache", PaletteAction::PurgeEverything),
+        (format!("Block IP {}", ip), "Firewall", PaletteAction::BlockIp),
+        (format!("Whitelist IP {}", ip), "Firewall", PaletteAction::WhitelistIp),
+        ("Firewall events".to_string(), "Firewall", PaletteAction::FirewallEvents),
+        (format!("Add DNS record {}", ip), "DNS", PaletteAction::AddDns),
+        (format!("New page rule {}", urls), "Page Rules", PaletteAction::NewPageRule),
+    ];
+    for (label, detail, action) in actions {
+        items.push(PaletteItem {
+            label: label.trim_end().to_string(),
+            detail: detail.to_string(),
+            action,
+        });
+    }
+
+    for (page, icon, label) in NAV_ITEMS {
+        items.push(PaletteItem {
+            label: format!("{} Go to {}", icon, label),
+            detail: "Page".to_string(),
+            action: PaletteAction::GoTo(page.clone()),
+        });
+    }
+
+    for zone in &state.zones {
+        items.push(PaletteItem {
+            label: zone.name.clone(),
+            detail: format!("Zone · {}", zone.status),
+            action: PaletteAction::SelectZone(zone.clone()),
+        });
+    }
+
+    // Records from another zone are left over from before a zone switch
+    let zone_id = state.zone_id();
+    for record in state
+        .dns_records
+        .iter()
+        .filter(|r| r.zone_id.is_none() || r.zone_id == zone_id)
+    {
+        items.push(PaletteItem {
+            label: format!("{} {}", record.record_type, record.name),
+            detail: format!("DNS · {}", record.content),
+            action: PaletteAction::EditDns(record.clone()),
+        });
+    }
+
+    items
+}
+
+/// Go to `page`; returns whether its data needs loading
+fn navigate(state: &mut AppState, page: Page) -> bool {
+    if state.current_page == page {
+        return false;
+    }
+    state.current_page = page;
+    true
+}
+
+/// Jump to the entry's page with its form pre-filled; returns whether the page changed
+fn execute(state: &mut AppState, action: PaletteAction, args: PaletteArgs) -> bool {
+    match action {
+        PaletteAction::GoTo(page) => navigate(state, page),
+        PaletteAction::SelectZone(zone) => {
+            state.selected_zone = Some(zone);
+            true
+        }
+        PaletteAction::EditDns(record) => {
+            state.dns_edit_form = Some(DnsEditForm::from_record(&record));
+            navigate(state, Page::Dns)
+        }
+        PaletteAction::AddDns => {
+            let mut form = DnsAddForm::default();
+            if let Some(ip) = args.ip {
+                if ip.contains(':') {
+                    form.record_type = "AAAA".to_string();
+                }
+                form.content = ip;
+            }
+            state.dns_add_form = form;
+            state.dns_show_add = true;
+            navigate(state, Page::Dns)
+        }
+        PaletteAction::PurgeUrls => {
+            if !args.urls.is_empty() {
+                state.purge_urls_input = args.urls.join("\n");
+            }
+            navigate(state, Page::Cache)
+        }
+        PaletteAction::PurgeEverything => {
+            if let Some(zone_id) = state.zone_id() {
+                state.confirm_dialog = Some(ConfirmDialog {
+                    title: "Purge All Cache".to_string(),
+                    message: "This will purge ALL cached files. Continue?".to_string(),
+                    action: ConfirmAction::PurgeAllCache(zone_id),
+                });
+            }
+            navigate(state, Page::Cache)
+        }
+        PaletteAction::BlockIp | PaletteAction::WhitelistIp => {
+            if let Some(ip) = args.ip {
+                state.fw_ip_input = ip;
+            }
+            let tab_changed = state.firewall_tab != FirewallTab::Rules;
+            state.firewall_tab = FirewallTab::Rules;
+            navigate(state, Page::Firewall) || tab_changed
+        }
+        PaletteAction::FirewallEvents => {
+            let tab_changed = state.firewall_tab != FirewallTab::Events;
+            state.firewall_tab = FirewallTab::Events;
+            navigate(state, Page::Firewall) || tab_changed
+        }
+        PaletteAction::NewPageRule => {
+            state.page_rule_form = Some(PageRuleForm {
+                url_pattern: args.urls.first().cloned().unwrap_or_default(),
+                ..Default::default()
+            });
+            navigate(state, Page::PageRules)
+        }
+    }
+}
+
+/// Ctrl+K palette; returns true when the chosen entry changed page, tab or zone
+pub fn render_command_palette(state: &mut AppState, ctx: &egui::Context) -> bool {
+    if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+        state.show_palette = !state.show_palette;
+    }
+    if !state.show_palette {
+        state.palette_query.clear();
+        state.palette_selected = 0;
+        return false;
+    }
+
+    let (query, args) = parse_query(&state.palette_query);
+    let mut matches: Vec<(i32, PaletteItem)> = palette_items(state, &args)
+        .into_iter()
+        .filter_map(|item| fuzzy_score(&query, &item.label).map(|score| (score, item)))
+        .collect();
+    // Without a query keep the natural order: actions, pages, zones, records
+    if !query.is_empty() {
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    }
+    let items: Vec<PaletteItem> = matches.into_iter().take(MAX_RESULTS).map(|(_, item)| item).collect();
+
+    let (up, down, enter, escape) = ctx.input_mut(|i| {
+        (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+        )
+    });
+    if escape {
+        state.show_palette = false;
+        return false;
+    }
+    if up {
+        state.palette_selected = state.palette_selected.saturating_sub(1);
+    }
+    if down {
+        state.palette_selected += 1;
+    }
+    state.palette_selected = state.palette_selected.min(items.len().saturating_sub(1));
+
+    let mut chosen = if enter { items.get(state.palette_selected).cloned() } else { None };
+
+    egui::Window::new("command_palette")
+        .title_bar(false)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 80.0))
+        .fixed_size([520.0, 0.0])
+        .show(ctx, |ui| {
+            let edit = ui.add(
+                egui::TextEdit::singleline(&mut state.palette_query)
+                    .hint_text("Search zones, DNS records, pages and actions...")
+                    .desired_width(f32::INFINITY),
+            );
+            edit.request_focus();
+            if edit.changed() {
+                state.palette_selected = 0;
+            }
+            ui.separator();
+
+            if items.is_empty() {
+                ui.label(egui::RichText::new("No matches").weak());
+            }
+            for (idx, item) in items.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.selectable_label(idx == state.palette_selected, &item.label).clicked() {
+                        chosen = Some(item.clone());
+                    }
+                    ui.label(egui::RichText::new(&item.detail).small().weak());
+                });
+            }
+            ui.add_space(4.0);
+            ui.label(
+                egui::RichText::new("\u{2191}\u{2193} select · Enter open · Esc close · append an IP or URL to pre-fill")
+                    .small()
+                    .weak(),
+            );
+        });
+
+    let Some(item) = chosen else {
+        return false;
+    };
+    state.show_palette = false;
+    execute(state, item.action, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_ranks_word_starts() {
+        assert!(fuzzy_score("xyz", "Purge everything").is_none());
+        let contiguous = fuzzy_score("dns", "Add DNS record").unwrap();
+        let scattered = fuzzy_score("dns", "Add new service").unwrap();
+        assert!(contiguous > scattered);
+        assert!(fuzzy_score("pe", "Purge everything").unwrap() > fuzzy_score("pe", "Compare").unwrap());
+    }
+
+    #[test]
+    fn test_parse_query_extracts_form_arguments() {
+        let (query, args) = parse_query("block 203.0.113.0/24");
+        assert_eq!(query, "block");
+        assert_eq!(args.ip.as_deref(), Some("203.0.113.0/24"));
+
+        let (query, args) = parse_query("purge https://example.com/a https://example.com/b");
+        assert_eq!(query, "purge");
+        assert_eq!(args.urls.len(), 2);
+    }
+}
@@ -1,7 +1,9 @@
 use eframe::egui;
 use super::super::state::{AppState, ConfirmAction, NotifLevel};
-use super::super::async_bridge::spawn_async;
+use super::super::async_bridge::{spawn_async, ProgressSender};
 use super::super::state::AsyncResult;
+use crate::ai::executor;
+use crate::progress::Progress;
 
 pub fn render_confirm_dialog(state: &mut AppState, ctx: &egui::Context) {
     let dialog = match &state.confirm_dialog {
@@ -57,7 +59,7 @@ fn execute_confirm_action(state: &mut AppState, ctx: &egui::Context, action: Con
         ConfirmAction::DeleteZone(zone_id) => {
             state.set_loading("Deleting zone...");
             let zid = zone_id.clone();
-            spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+            spawn_async(state, ctx, move || async move {
                 let result = client.delete_zone(&zid).await;
                 AsyncResult::ZoneDeleted(result.map(|_| zid))
             });
@@ -66,16 +68,37 @@ fn execute_confirm_action(state: &mut AppState, ctx: &egui::Context, action: Con
             state.set_loading("Deleting DNS record...");
             let zid = zone_id.clone();
             let rid = record_id.clone();
-            spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+            spawn_async(state, ctx, move || async move {
                 let result = client.delete_dns_record(&zid, &rid).await;
                 AsyncResult::DnsRecordDeleted(result.map(|_| rid))
             });
         }
+        ConfirmAction::DeleteDnsRecords(zone_id, record_ids) => {
+            state.set_loading(&format!("Deleting {} DNS records...", record_ids.len()));
+            let mut progress = Progress::new("Deleting DNS records", record_ids.len(), ProgressSender::new(state, ctx));
+            spawn_async(state, ctx, move || async move {
+                let mut results = Vec::new();
+                for rid in record_ids {
+                    progress.start(rid.as_str());
+                    let result = client.delete_dns_record(&zone_id, &rid).await.map(|_| rid);
+                    progress.record(&result);
+                    results.push(result);
+                }
+                progress.finish();
+                AsyncResult::DnsBulkDeleted(results)
+            });
+        }
+        ConfirmAction::DisableDnssec(zone_id) => {
+            state.set_loading("Disabling DNSSEC...");
+            spawn_async(state, ctx, move || async move {
+                AsyncResult::DnssecLoaded(client.set_dnssec(&zone_id, false).await)
+            });
+        }
         ConfirmAction::DeletePageRule(zone_id, rule_id) => {
             state.set_loading("Deleting page rule...");
             let zid = zone_id.clone();
             let rid = rule_id.clone();
-            spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+            spawn_async(state, ctx, move || async move {
                 let result = client.delete_page_rule(&zid, &rid).await;
                 AsyncResult::PageRuleDeleted(result.map(|_| rid))
             });
@@ -84,7 +107,7 @@ fn execute_confirm_action(state: &mut AppState, ctx: &egui::Context, action: Con
             state.set_loading("Deleting worker...");
             let account_id = state.config.cloudflare.account_id.clone().unwrap_or_default();
             let n = name.clone();
-            spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+            spawn_async(state, ctx, move || async move {
                 let result = client.delete_worker(&account_id, &n).await;
                 AsyncResult::WorkerDeleted(result.map(|_| n))
             });
@@ -92,7 +115,7 @@ fn execute_confirm_action(state: &mut AppState, ctx: &egui::Context, action: Con
         ConfirmAction::PurgeAllCache(zone_id) => {
             state.set_loading("Purging all cache...");
             let zid = zone_id.clone();
-            spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+            spawn_async(state, ctx, move || async move {
                 let result = client.purge_all_cache(&zid).await;
                 AsyncResult::CachePurged(result.map(|_| "All cache purged".to_string()))
             });
@@ -101,10 +124,44 @@ fn execute_confirm_action(state: &mut AppState, ctx: &egui::Context, action: Con
             state.set_loading("Deleting IP rule...");
             let zid = zone_id.clone();
             let rid = rule_id.clone();
-            spawn_async(&state.tokio_handle, &state.tx, ctx, move || async move {
+            spawn_async(state, ctx, move || async move {
                 let result = client.delete_ip_access_rule(&zid, &rid).await;
                 AsyncResult::IpRuleDeleted(result.map(|_| rid))
             });
         }
+        ConfirmAction::DeleteRateLimit(zone_id, rule_id) => {
+            state.set_loading("Deleting rate limit...");
+            let zid = zone_id.clone();
+            let rid = rule_id.clone();
+            spawn_async(state, ctx, move || async move {
+                let result = client.delete_rate_limit(&zid, &rid).await;
+                AsyncResult::RateLimitDeleted(result.map(|_| rid))
+            });
+        }
+        ConfirmAction::ApplyAiAction(zone_id, msg_idx, action_idx, action) => {
+            apply_ai_action(state, ctx, client, zone_id, msg_idx, action_idx, action);
+        }
     }
 }
+
+/// Execute an AI suggested action through the shared executor (also used for low-risk actions without confirmation)
+pub fn apply_ai_action(
+    state: &mut AppState,
+    ctx: &egui::Context,
+    client: crate::api::client::CfClient,
+    zone_id: String,
+    msg_idx: usize,
+    action_idx: usize,
+    action: crate::ai::analyzer::SuggestedAction,
+) {
+    state.set_loading(&format!("Applying: {}", action.description));
+    spawn_async(state, ctx, move || async move {
+        let result = executor::run_actions(&client, &zone_id, std::slice::from_ref(&action), &mut executor::AutoApprove)
+            .await
+            .and_then(|outcomes| match outcomes.into_iter().next() {
+                Some(outcome) => outcome.into_result(),
+                None => anyhow::bail!("No action executed"),
+            });
+        AsyncResult::AiActionApplied(msg_idx, action_idx, result)
+    });
+}
@@ -0,0 +1,27 @@
+use eframe::egui;
+use super::super::state::{AppState, DataFeed};
+use super::super::theme;
+
+/// "Updated hh:mm:ss" label with a stale badge when the last refresh failed
+pub fn render_freshness(ui: &mut egui::Ui, state: &AppState, feed: DataFeed) {
+    let Some(status) = state.feeds.get(&feed) else {
+        return;
+    };
+
+    if let Some(error) = &status.error {
+        let label = if status.updated_at.is_some() { "\u{26A0} Stale" } else { "\u{26A0} Load failed" };
+        ui.label(egui::RichText::new(label).small().strong().color(theme::WARNING))
+            .on_hover_text(format!("Last refresh failed: {}", error));
+    }
+    if let Some(updated) = status.updated_at {
+        ui.label(
+            egui::RichText::new(format!("Updated {}", updated.format("%H:%M:%S")))
+                .small()
+                .weak(),
+        );
+    }
+    if let Some(secs) = state.config.gui.refresh_secs(feed.config_key()) {
+        ui.label(egui::RichText::new(format!("\u{00B7} auto every {}s", secs)).small().weak())
+            .on_hover_text(format!("Change with `cfai config set gui.refresh.{} <secs>`", feed.config_key()));
+    }
+}
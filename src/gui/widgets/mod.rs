@@ -1,3 +1,5 @@
 pub mod status_bar;
 pub mod notification;
 pub mod confirm_dialog;
+pub mod freshness;
+pub mod command_palette;
@@ -2,6 +2,16 @@ use eframe::egui;
 use super::super::state::{AppState, NotifLevel};
 use super::super::theme;
 
+/// Color and icon for a notification level
+fn level_style(level: &NotifLevel) -> (egui::Color32, &'static str) {
+    match level {
+        NotifLevel::Success => (theme::SUCCESS, "\u{2705}"),
+        NotifLevel::Error => (theme::DANGER, "\u{274C}"),
+        NotifLevel::Warning => (theme::WARNING, "\u{26A0}\u{FE0F}"),
+        NotifLevel::Info => (theme::INFO, "\u{2139}\u{FE0F}"),
+    }
+}
+
 pub fn render_notifications(state: &mut AppState, ctx: &egui::Context) {
     // Remove expired
     state.notifications.retain(|n| !n.is_expired());
@@ -15,12 +25,7 @@ pub fn render_notifications(state: &mut AppState, ctx: &egui::Context) {
         .order(egui::Order::Foreground)
         .show(ctx, |ui| {
             for notif in &state.notifications {
-                let (color, prefix) = match notif.level {
-                    NotifLevel::Success => (theme::SUCCESS, "\u{2705}"),
-                    NotifLevel::Error => (theme::DANGER, "\u{274C}"),
-                    NotifLevel::Warning => (theme::WARNING, "\u{26A0}\u{FE0F}"),
-                    NotifLevel::Info => (theme::INFO, "\u{2139}\u{FE0F}"),
-                };
+                let (color, prefix) = level_style(&notif.level);
                 egui::Frame::none()
                     .fill(egui::Color32::from_rgb(31, 41, 55))
                     .stroke(egui::Stroke::new(1.0, color))
@@ -37,3 +42,69 @@ pub fn render_notifications(state: &mut AppState, ctx: &egui::Context) {
             }
         });
 }
+
+/// Notification center opened from the bell in the status bar
+pub fn render_notification_center(state: &mut AppState, ctx: &egui::Context) {
+    if !state.show_notification_center {
+        return;
+    }
+
+    let mut open = true;
+    let mut clear = false;
+    egui::Window::new("Notifications")
+        .open(&mut open)
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -36.0))
+        .default_width(420.0)
+        .collapsible(false)
+        .resizable(true)
+        .show(ctx, |ui| {
+            if state.notification_history.is_empty() {
+                ui.label(egui::RichText::new("No notifications yet").weak());
+                return;
+            }
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} recent", state.notification_history.len()))
+                        .small()
+                        .weak(),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("Clear").clicked() {
+                        clear = true;
+                    }
+                });
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                for entry in &state.notification_history {
+                    let (color, prefix) = level_style(&entry.level);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(entry.time.format("%H:%M:%S").to_string())
+                                .small()
+                                .weak(),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("Copy").on_hover_text("Copy message").clicked() {
+                                ctx.copy_text(entry.message.clone());
+                            }
+                        });
+                    });
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(format!("{} {}", prefix, entry.message)).color(color),
+                        )
+                        .wrap()
+                        .selectable(true),
+                    );
+                    ui.separator();
+                }
+            });
+        });
+
+    state.show_notification_center = open;
+    if clear {
+        state.notification_history.clear();
+    }
+}
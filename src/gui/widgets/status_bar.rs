@@ -1,7 +1,7 @@
 use eframe::egui;
 use super::super::state::AppState;
 
-pub fn render_status_bar(state: &AppState, ctx: &egui::Context) {
+pub fn render_status_bar(state: &mut AppState, ctx: &egui::Context) {
     egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
         ui.horizontal(|ui| {
             if let Some(zone) = &state.selected_zone {
@@ -20,6 +20,35 @@ pub fn render_status_bar(state: &AppState, ctx: &egui::Context) {
             }
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let bell = if state.unread_notifications > 0 {
+                    egui::RichText::new(format!("\u{1F514} {}", state.unread_notifications))
+                        .small()
+                        .strong()
+                } else {
+                    egui::RichText::new("\u{1F514}").small()
+                };
+                if ui
+                    .selectable_label(state.show_notification_center, bell)
+                    .on_hover_text("Notification history")
+                    .clicked()
+                {
+                    state.show_notification_center = !state.show_notification_center;
+                    state.unread_notifications = 0;
+                }
+                ui.separator();
+                if !state.monitor_zones.is_empty() {
+                    if ui.small_button("Quit").on_hover_text("Stop monitoring and exit").clicked() {
+                        state.quit_requested = true;
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                    }
+                    ui.label(
+                        egui::RichText::new(format!("\u{1F4E1} Monitoring {}", state.monitor_zones.join(", ")))
+                            .small()
+                            .color(super::super::theme::SUCCESS),
+                    )
+                    .on_hover_text("Closing the window keeps monitoring in the background");
+                    ui.separator();
+                }
                 ui.label(egui::RichText::new("CFAI v0.3.8").small().weak());
             });
         });
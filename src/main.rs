@@ -5,14 +5,23 @@ mod config;
 #[cfg(feature = "gui")]
 mod gui;
 mod models;
+mod notify;
+mod progress;
+mod resolver;
+mod tls_inspect;
+#[cfg(feature = "tui")]
+mod tui;
 
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
 
 use crate::api::client::{AuthMethod, CfClient};
+use crate::api::response_cache::ResponseCache;
 use crate::cli::commands::{Cli, Commands};
+use crate::cli::exit::{self, AuthError};
 use crate::cli::output;
+use crate::cli::prompt;
 use crate::config::settings::AppConfig;
 
 #[tokio::main]
@@ -27,12 +36,15 @@ async fn main() {
 
     if let Err(e) = run().await {
         output::error(&format!("{:#}", e));
-        std::process::exit(1);
+        std::process::exit(exit::code_for(&e));
     }
 }
 
 async fn run() -> Result<()> {
     let cli = Cli::parse();
+    output::set_quiet(cli.quiet);
+    prompt::set_non_interactive(cli.non_interactive);
+    prompt::set_assume_yes(cli.yes);
 
     // 设置 verbose 日志
     if cli.verbose {
@@ -53,78 +65,86 @@ async fn run() -> Result<()> {
             println!("💡 提示：直接运行 {} 进入交互模式\n", "cfai".cyan());
 
             let interactive_args = cli::commands::interactive::InteractiveArgs { once: false };
-            return interactive_args.execute(&cli.format, cli.verbose).await;
+            return interactive_args
+                .execute(&cli.format, cli.profile.as_deref(), cli.no_cache)
+                .await;
         }
     };
 
-    // Config / 安装 / 更新 / 交互 命令不需要认证
+    // Config / 安装 / 更新 / 交互 / 本地缓存 命令不需要认证
     match &command {
-        Commands::Config(config_args) => return config_args.execute().await,
-        Commands::Install(args) => return args.execute().await,
-        Commands::Update(args) => return args.execute().await,
-        Commands::Interactive(args) => {
-            return args.execute(&cli.format, cli.verbose).await
-        }
+        Commands::Interactive(args) => return args.execute(&cli.format, cli.profile.as_deref(), cli.no_cache).await,
         #[cfg(feature = "gui")]
         Commands::Gui => {
-            return crate::gui::launch_gui();
+            return crate::gui::launch_gui(cli.profile.as_deref());
         }
+        cmd if cmd.is_local() => return cmd.execute_local(&cli.format, cli.profile.as_deref()).await,
         _ => {}
     }
 
     // 加载配置并检查是否需要初始化
-    let config = ensure_config_exists().await?;
+    let config = ensure_config_exists(cli.profile.as_deref()).await?;
 
     // AI 命令可能不需要 Cloudflare 认证 (如纯问答)
-    let needs_cf_client = !matches!(&command, Commands::Ai(ai_args) if matches!(&ai_args.command, cli::commands::ai::AiCommands::Ask { .. }));
-
-    if needs_cf_client {
+    if command.needs_auth() {
         if let Err(e) = config.validate() {
             eprintln!("{}", e);
-            eprintln!(
-                "\n{} 运行 {} 进行配置",
-                "提示:".yellow(),
-                "cfai config setup".cyan()
-            );
-            std::process::exit(1);
+            if !output::is_quiet() {
+                eprintln!(
+                    "\n{} 运行 {} 进行配置",
+                    "提示:".yellow(),
+                    "cfai config setup".cyan()
+                );
+            }
+            std::process::exit(exit::AUTH_ERROR);
         }
     }
 
     // 创建 Cloudflare 客户端
-    let client = create_client(&config)?;
-    let format = &cli.format;
-
-    match &command {
-        Commands::Zone(args) => args.execute(&client, format).await,
-        Commands::Dns(args) => args.execute(&client, format).await,
-        Commands::Ssl(args) => args.execute(&client, format).await,
-        Commands::Firewall(args) => args.execute(&client, format).await,
-        Commands::Cache(args) => args.execute(&client, format).await,
-        Commands::PageRules(args) => args.execute(&client, format).await,
-        Commands::Workers(args) => args.execute(&client, &config, format).await,
-        Commands::Analytics(args) => args.execute(&client, format).await,
-        Commands::Ai(args) => args.execute(&client, &config, format).await,
-        Commands::Config(_) | Commands::Install(_) | Commands::Update(_) | Commands::Interactive(_) => {
-            unreachable!()
-        }
-        #[cfg(feature = "gui")]
-        Commands::Gui => {
-            unreachable!()
+    let client = create_client(&config)?
+        .with_zone_cache(zone_cache_key(&config, cli.profile.as_deref(), cli.no_cache))
+        .with_response_cache(response_cache(&config, cli.profile.as_deref(), cli.no_cache))
+        .with_timings(cli.timings);
+    let mut result = command.execute(&client, &config, &cli.format).await;
+    // 缓存的 Zone ID 已失效 (域名被删除重建等) 时清除缓存，重新查找后再试一次
+    let client = match &result {
+        Err(e) if client.evict_stale_zone_ids(e) => {
+            if cli.format != "json" {
+                output::info("缓存的 Zone ID 已失效，重新查找域名后重试");
+            }
+            let client = client.with_fresh_writes();
+            result = command.execute(&client, &config, &cli.format).await;
+            client
         }
+        _ => client,
+    };
+    if cli.timings {
+        output::print_timings(&client.request_timings());
     }
+
+    // 变更命令按 notify.on 通知执行结果
+    let command_line = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    notify::command_finished(&config, &client, &command_line, &result, cli.notify).await;
+    result
 }
 
 /// 确保配置文件存在，如果不存在则引导用户创建
-async fn ensure_config_exists() -> Result<AppConfig> {
-    use dialoguer::Confirm;
-
-    let config = AppConfig::load()?.merge_env();
+async fn ensure_config_exists(profile: Option<&str>) -> Result<AppConfig> {
+    let config = AppConfig::load_effective(profile)?;
 
     // 检查是否已配置 Cloudflare 认证
     let has_cf_token = config.cloudflare.api_token.is_some();
     let has_cf_key = config.cloudflare.email.is_some() && config.cloudflare.api_key.is_some();
 
     if !has_cf_token && !has_cf_key {
+        // 脚本 / CI 中无法交互引导，直接以认证错误退出
+        if output::is_quiet() || !prompt::is_interactive() {
+            return Err(AuthError(
+                "未配置 Cloudflare 认证信息，请设置 CLOUDFLARE_API_TOKEN 环境变量或运行 `cfai config setup`".into(),
+            )
+            .into());
+        }
+
         output::title("🎉 欢迎使用 CFAI");
         println!("\n检测到您是第一次使用 CFAI，需要进行初始配置。");
         println!("CFAI 是一个 AI 驱动的 Cloudflare 管理工具，可以帮助您：");
@@ -133,24 +153,36 @@ async fn ensure_config_exists() -> Result<AppConfig> {
         println!("  • 使用 AI 进行智能分析和优化");
         println!();
 
-        let should_setup = Confirm::new()
-            .with_prompt("是否现在进行配置？")
-            .default(true)
-            .interact()?;
+        let should_setup = prompt::ask("是否现在进行配置？", true)?;
 
         if should_setup {
-            return AppConfig::interactive_setup();
+            return AppConfig::interactive_setup().await;
         } else {
             output::info("您可以稍后运行 'cfai config setup' 进行配置");
-            std::process::exit(0);
+            std::process::exit(exit::SUCCESS);
         }
     }
 
     Ok(config)
 }
 
+/// 域名 → Zone ID 本地缓存的键，指定 `--no-cache` 时不使用缓存
+pub(crate) fn zone_cache_key(config: &AppConfig, profile: Option<&str>, no_cache: bool) -> Option<String> {
+    (!no_cache).then(|| config::zone_cache::cache_key(config.selected_profile(profile), &config.cloudflare))
+}
+
+/// 按配置创建 GET 响应缓存 (未开启或指定 --no-cache 时为 None)
+pub(crate) fn response_cache(config: &AppConfig, profile: Option<&str>, no_cache: bool) -> Option<ResponseCache> {
+    (config.response_cache.enabled && !no_cache).then(|| {
+        ResponseCache::new(
+            config::zone_cache::cache_key(config.selected_profile(profile), &config.cloudflare),
+            config.response_cache.ttl_secs(),
+        )
+    })
+}
+
 /// 创建 Cloudflare API 客户端
-fn create_client(config: &AppConfig) -> Result<CfClient> {
+pub(crate) fn create_client(config: &AppConfig) -> Result<CfClient> {
     let auth = if let Some(token) = &config.cloudflare.api_token {
         AuthMethod::ApiToken(token.clone())
     } else if let (Some(email), Some(key)) = (&config.cloudflare.email, &config.cloudflare.api_key)
@@ -164,5 +196,7 @@ fn create_client(config: &AppConfig) -> Result<CfClient> {
         AuthMethod::ApiToken(String::new())
     };
 
-    CfClient::new(auth)
+    Ok(CfClient::new(auth, &config.network)?
+        .with_retry(config.retry_policy())
+        .with_audit(true))
 }
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+/// Access 应用
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccessApplication {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub domain: Option<String>,
+    #[serde(rename = "type")]
+    pub app_type: Option<String>,
+    pub aud: Option<String>,
+    pub session_duration: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 创建 Access 应用请求
+#[derive(Debug, Serialize)]
+pub struct CreateAccessAppRequest {
+    pub name: String,
+    pub domain: String,
+    #[serde(rename = "type")]
+    pub app_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_duration: Option<String>,
+}
+
+/// Access 策略
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccessPolicy {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    /// allow / deny / non_identity / bypass
+    pub decision: Option<String>,
+    pub precedence: Option<i32>,
+    pub include: Option<Vec<serde_json::Value>>,
+    pub exclude: Option<Vec<serde_json::Value>>,
+    pub require: Option<Vec<serde_json::Value>>,
+    pub created_at: Option<String>,
+}
+
+/// 创建 Access 策略请求
+#[derive(Debug, Serialize)]
+pub struct CreateAccessPolicyRequest {
+    pub name: String,
+    pub decision: String,
+    pub include: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precedence: Option<i32>,
+}
+
+/// 由邮箱 / 邮箱域名 / IP 段构造策略的 include 规则
+pub fn access_include_rules(
+    emails: &[String],
+    email_domains: &[String],
+    ips: &[String],
+    everyone: bool,
+) -> Vec<serde_json::Value> {
+    if everyone {
+        return vec![serde_json::json!({ "everyone": {} })];
+    }
+    emails
+        .iter()
+        .map(|e| serde_json::json!({ "email": { "email": e } }))
+        .chain(
+            email_domains
+                .iter()
+                .map(|d| serde_json::json!({ "email_domain": { "domain": d.trim_start_matches('@') } })),
+        )
+        .chain(ips.iter().map(|ip| serde_json::json!({ "ip": { "ip": ip } })))
+        .collect()
+}
+
+/// 将 include/exclude/require 规则转为简短描述，如 `email:a@b.com`
+pub fn describe_access_rule(rule: &serde_json::Value) -> String {
+    let Some((kind, value)) = rule.as_object().and_then(|o| o.iter().next()) else {
+        return rule.to_string();
+    };
+    let detail = value
+        .as_object()
+        .and_then(|o| o.values().find_map(|v| v.as_str()))
+        .unwrap_or("");
+    if detail.is_empty() {
+        kind.clone()
+    } else {
+        format!("{}:{}", kind, detail)
+    }
+}
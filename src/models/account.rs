@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Cloudflare 账户
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Account {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub account_type: Option<String>,
+    pub created_on: Option<String>,
+    pub settings: Option<AccountSettings>,
+}
+
+/// 账户设置
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountSettings {
+    pub enforce_twofactor: Option<bool>,
+    pub abuse_contact_email: Option<String>,
+    pub default_nameservers: Option<String>,
+}
+
+/// 账户成员
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountMember {
+    pub id: String,
+    pub status: Option<String>,
+    pub user: Option<MemberUser>,
+    pub roles: Option<Vec<MemberRole>>,
+}
+
+impl AccountMember {
+    /// 成员邮箱
+    pub fn email(&self) -> &str {
+        self.user
+            .as_ref()
+            .and_then(|u| u.email.as_deref())
+            .unwrap_or("-")
+    }
+
+    /// 逗号分隔的角色名
+    pub fn role_names(&self) -> String {
+        match &self.roles {
+            Some(roles) if !roles.is_empty() => roles
+                .iter()
+                .map(|r| r.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => "-".to_string(),
+        }
+    }
+}
+
+/// 成员的用户信息
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MemberUser {
+    pub id: Option<String>,
+    pub email: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub two_factor_authentication_enabled: Option<bool>,
+}
+
+/// 成员角色
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MemberRole {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+}
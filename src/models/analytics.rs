@@ -1,6 +1,9 @@
-use chrono::{Duration, Utc};
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::api::error::ValidationError;
+
 /// 分析数据总览
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AnalyticsDashboard {
@@ -80,6 +83,31 @@ pub struct AnalyticsTimeseries {
     pub uniques: Option<AnalyticsUniques>,
 }
 
+/// 分布数据 (键为状态码、国家或内容类型，值为数量) 中数量最多的前 `n` 项
+pub fn top_breakdown(map: Option<&serde_json::Value>, n: usize) -> Vec<(String, u64)> {
+    let mut entries: Vec<(String, u64)> = map
+        .and_then(|m| m.as_object())
+        .map(|m| m.iter().map(|(k, v)| (k.clone(), v.as_u64().unwrap_or(0))).collect())
+        .unwrap_or_default();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries.truncate(n);
+    entries
+}
+
+/// 合并多个分布数据 (同名项相加)，全部为空时返回 None
+pub fn merge_breakdowns<'a>(maps: impl IntoIterator<Item = Option<&'a serde_json::Value>>) -> Option<serde_json::Value> {
+    let mut merged = serde_json::Map::new();
+    let mut found = false;
+    for map in maps.into_iter().flatten().filter_map(|m| m.as_object()) {
+        found = true;
+        for (key, value) in map {
+            let total = merged.get(key).and_then(|v| v.as_u64()).unwrap_or(0) + value.as_u64().unwrap_or(0);
+            merged.insert(key.clone(), total.into());
+        }
+    }
+    found.then_some(serde_json::Value::Object(merged))
+}
+
 /// 分析查询参数
 #[derive(Debug, Serialize, Default, Clone)]
 pub struct AnalyticsParams {
@@ -89,6 +117,103 @@ pub struct AnalyticsParams {
     pub until: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub continuous: Option<bool>,
+    /// 时间序列粒度 (None 表示按时间范围自动选择)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<AnalyticsGranularity>,
+}
+
+/// 时间序列粒度
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyticsGranularity {
+    #[serde(rename = "1h")]
+    Hour,
+    #[serde(rename = "1d")]
+    Day,
+}
+
+impl AnalyticsGranularity {
+    /// 超过该时长的范围自动按天聚合
+    const AUTO_HOURLY_MAX_DAYS: i64 = 3;
+
+    /// 解析 "1h" / "1d"
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "1h" | "hour" => Ok(Self::Hour),
+            "1d" | "day" => Ok(Self::Day),
+            _ => anyhow::bail!(ValidationError(format!("不支持的粒度: {} (可选 1h / 1d)", s))),
+        }
+    }
+
+    /// 按时间范围自动选择粒度
+    pub fn auto(since: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        if until - since > Duration::days(Self::AUTO_HOURLY_MAX_DAYS) {
+            Self::Day
+        } else {
+            Self::Hour
+        }
+    }
+
+    /// 对应的 GraphQL 数据集
+    pub fn dataset(&self) -> &'static str {
+        match self {
+            Self::Hour => "httpRequests1hGroups",
+            Self::Day => "httpRequests1dGroups",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Hour => "1h",
+            Self::Day => "1d",
+        }
+    }
+}
+
+/// 解析时间参数
+///
+/// 支持 `0` / `now`、相对时间 (`-1440` 分钟，或 `-30m` / `-12h` / `-7d`，也可省略负号写作 `1h`)、
+/// RFC3339 (`2024-05-01T08:00:00+08:00`)、不带时区的 `2024-05-01T08:00:00` (按 UTC)
+/// 以及日期 `2024-05-01` (当天 00:00 UTC)。
+pub fn parse_time(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+    if input == "0" || input.eq_ignore_ascii_case("now") {
+        return Ok(now);
+    }
+
+    // "1h" 等省略负号的时长同样表示"多久之前"
+    let is_bare_duration = input.len() > 1
+        && input.ends_with(['m', 'h', 'd'])
+        && input[..input.len() - 1].chars().all(|c| c.is_ascii_digit());
+    if let Some(rel) = input.strip_prefix('-').or(is_bare_duration.then_some(input)) {
+        let (num, unit) = match rel.char_indices().last() {
+            Some((i, c)) if c.is_ascii_alphabetic() => (&rel[..i], c),
+            _ => (rel, 'm'),
+        };
+        let amount: i64 = num
+            .parse()
+            .map_err(|_| ValidationError(format!("无法解析相对时间: {}", input)))?;
+        let offset = match unit {
+            'm' => Duration::try_minutes(amount),
+            'h' => Duration::try_hours(amount),
+            'd' => Duration::try_days(amount),
+            _ => anyhow::bail!(ValidationError(format!("不支持的时间单位: {} (可选 m / h / d)", unit))),
+        };
+        return offset
+            .and_then(|offset| now.checked_sub_signed(offset))
+            .ok_or_else(|| ValidationError(format!("相对时间超出范围: {}", input)).into());
+    }
+
+    if let Ok(t) = DateTime::parse_from_rfc3339(input) {
+        return Ok(t.with_timezone(&Utc));
+    }
+    if let Ok(t) = NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(t.and_utc());
+    }
+    if let Ok(d) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    anyhow::bail!(ValidationError(format!("无法解析时间: {} (支持 ISO8601、YYYY-MM-DD 或 -1440 / -24h 等相对时间)", input)))
 }
 
 impl AnalyticsParams {
@@ -100,6 +225,7 @@ impl AnalyticsParams {
             since: Some(yesterday.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
             until: Some(now.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
             continuous: Some(true),
+            granularity: Some(AnalyticsGranularity::Hour),
         }
     }
 
@@ -111,44 +237,180 @@ impl AnalyticsParams {
             since: Some(week_ago.format("%Y-%m-%d").to_string()),
             until: Some(now.format("%Y-%m-%d").to_string()),
             continuous: Some(true),
+            granularity: Some(AnalyticsGranularity::Day),
         }
     }
 
-    /// 获取时间范围 (用于 GraphQL 查询)
-    pub fn get_time_range(&self) -> (String, String) {
+    /// 解析起止时间与粒度，未指定时默认最近 24 小时
+    pub fn resolve(&self) -> Result<(DateTime<Utc>, DateTime<Utc>, AnalyticsGranularity)> {
         let now = Utc::now();
-
-        let since = self.since.clone().unwrap_or_else(|| {
-            (now - Duration::hours(24)).format("%Y-%m-%dT%H:%M:%SZ").to_string()
-        });
-
-        let until = self.until.clone().unwrap_or_else(|| {
-            now.format("%Y-%m-%dT%H:%M:%SZ").to_string()
-        });
-
-        // 如果是相对时间 (如 -1440 分钟)，转换为 ISO8601
-        let since = if since.starts_with('-') {
-            if let Ok(minutes) = since.parse::<i64>() {
-                (now + Duration::minutes(minutes)).format("%Y-%m-%dT%H:%M:%SZ").to_string()
-            } else {
-                since
-            }
-        } else {
-            since
+        let since = match &self.since {
+            Some(s) => parse_time(s, now)?,
+            None => now - Duration::hours(24),
         };
-
-        let until = if until == "0" {
-            now.format("%Y-%m-%dT%H:%M:%SZ").to_string()
-        } else if until.starts_with('-') {
-            if let Ok(minutes) = until.parse::<i64>() {
-                (now + Duration::minutes(minutes)).format("%Y-%m-%dT%H:%M:%SZ").to_string()
-            } else {
-                until
-            }
-        } else {
-            until
+        let until = match &self.until {
+            Some(s) => parse_time(s, now)?,
+            None => now,
         };
+        if since >= until {
+            anyhow::bail!(ValidationError("起始时间必须早于结束时间".into()));
+        }
+        let granularity = self
+            .granularity
+            .unwrap_or_else(|| AnalyticsGranularity::auto(since, until));
+        Ok((since, until, granularity))
+    }
+}
+
+/// 实时流量快照 (最近几分钟的汇总，用于 `analytics watch`)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct LiveAnalytics {
+    pub since: String,
+    pub until: String,
+    /// 统计窗口长度 (秒)
+    pub window_secs: u64,
+    pub requests: u64,
+    pub cached_requests: u64,
+    pub bytes: u64,
+    pub threats: u64,
+    /// 每分钟请求数 (按时间升序)
+    pub per_minute: Vec<u64>,
+}
+
+impl LiveAnalytics {
+    /// 窗口内平均每秒请求数
+    pub fn requests_per_second(&self) -> f64 {
+        if self.window_secs == 0 {
+            return 0.0;
+        }
+        self.requests as f64 / self.window_secs as f64
+    }
+
+    /// 超过威胁数或每秒请求数阈值时返回原因
+    pub fn exceeds(&self, threats: Option<u64>, rps: Option<f64>) -> Option<String> {
+        let mut reasons = Vec::new();
+        if let Some(limit) = threats.filter(|l| self.threats >= *l) {
+            reasons.push(format!("威胁数 {} ≥ {}", self.threats, limit));
+        }
+        let current = self.requests_per_second();
+        if let Some(limit) = rps.filter(|l| current >= *l) {
+            reasons.push(format!("请求速率 {:.1}/s ≥ {}/s", current, limit));
+        }
+        (!reasons.is_empty()).then(|| reasons.join("，"))
+    }
+
+    /// 缓存命中率 (百分比)
+    pub fn cache_hit_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.cached_requests as f64 / self.requests as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time() {
+        let now = parse_time("2024-05-02T12:00:00Z", Utc::now()).unwrap();
+        assert_eq!(parse_time("0", now).unwrap(), now);
+        assert_eq!(parse_time("-1440", now).unwrap(), now - Duration::days(1));
+        assert_eq!(parse_time("-12h", now).unwrap(), now - Duration::hours(12));
+        assert_eq!(parse_time("1h", now).unwrap(), now - Duration::hours(1));
+        assert_eq!(
+            parse_time("2024-05-01", now).unwrap(),
+            parse_time("2024-05-01T00:00:00", now).unwrap()
+        );
+        assert_eq!(
+            parse_time("2024-05-02T20:00:00+08:00", now).unwrap(),
+            now
+        );
+        assert!(parse_time("yesterday", now).is_err());
+        assert!(parse_time("-3w", now).is_err());
+        assert!(parse_time("-xh", now).unwrap_err().downcast_ref::<ValidationError>().is_some());
+        // 超出范围的相对时间报错而不是 panic
+        assert!(parse_time("-9999999999999999d", now).is_err());
+        assert!(parse_time("99999999999999m", now).is_err());
+    }
+
+    #[test]
+    fn test_breakdowns() {
+        let a = serde_json::json!({"200": 90, "404": 5, "500": 5});
+        let b = serde_json::json!({"200": 10, "301": 20});
+        let merged = merge_breakdowns([Some(&a), None, Some(&b)]).unwrap();
+        assert_eq!(
+            top_breakdown(Some(&merged), 3),
+            [("200".to_string(), 100), ("301".to_string(), 20), ("404".to_string(), 5)]
+        );
+        assert_eq!(merge_breakdowns([None, None]), None);
+        assert!(top_breakdown(None, 5).is_empty());
+    }
+}
+
+/// Bot 评分分布 (GraphQL httpRequestsAdaptiveGroups 按 botScore 聚合)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct BotScoreAnalytics {
+    pub since: String,
+    pub until: String,
+    pub total: u64,
+    /// 评分 1: 确定为自动化
+    pub automated: u64,
+    /// 评分 2-29: 可能为自动化
+    pub likely_automated: u64,
+    /// 评分 30-99: 可能为人类
+    pub likely_human: u64,
+    /// 无评分 (未启用 Bot 管理或请求未评分)
+    pub unscored: u64,
+    /// 按评分来源 (Machine Learning、Heuristics、Verified Bot 等) 汇总的请求数
+    pub by_source: Vec<(String, u64)>,
+}
 
-        (since, until)
+impl BotScoreAnalytics {
+    /// 按评分归入对应区间
+    pub fn add(&mut self, score: Option<u64>, source: Option<&str>, count: u64) {
+        self.total += count;
+        match score {
+            Some(1) => self.automated += count,
+            Some(2..=29) => self.likely_automated += count,
+            Some(30..=99) => self.likely_human += count,
+            _ => self.unscored += count,
+        }
+        let source = source.filter(|s| !s.is_empty()).unwrap_or("-").to_string();
+        match self.by_source.iter_mut().find(|(s, _)| *s == source) {
+            Some((_, n)) => *n += count,
+            None => self.by_source.push((source, count)),
+        }
+    }
+}
+
+/// DNS 查询统计 (GraphQL dnsAnalyticsAdaptiveGroups)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct DnsAnalytics {
+    pub since: String,
+    pub until: String,
+    pub total: u64,
+    /// 按查询名称汇总的查询数 (降序)
+    pub by_name: Vec<(String, u64)>,
+    /// 按记录类型汇总
+    pub by_type: Vec<(String, u64)>,
+    /// 按响应码 (NOERROR、NXDOMAIN 等) 汇总
+    pub by_response_code: Vec<(String, u64)>,
+}
+
+impl DnsAnalytics {
+    /// NXDOMAIN 占比 (百分比)
+    pub fn nxdomain_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let nxdomain: u64 = self
+            .by_response_code
+            .iter()
+            .filter(|(code, _)| code.eq_ignore_ascii_case("NXDOMAIN"))
+            .map(|(_, n)| n)
+            .sum();
+        nxdomain as f64 / self.total as f64 * 100.0
     }
 }
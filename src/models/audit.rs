@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::dns::DnsRecord;
+use crate::models::firewall::RateLimitRule;
+
+/// 审计日志条目: 一次变更及其修改前的状态
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    /// 条目 ID (`cfai rollback` 时可只输入前缀)
+    pub id: String,
+    pub timestamp: String,
+    pub zone_id: String,
+    #[serde(flatten)]
+    pub change: AuditChange,
+    /// 是否已通过 undo / rollback 撤销
+    #[serde(default)]
+    pub undone: bool,
+}
+
+/// 被记录的变更
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditChange {
+    DnsCreate {
+        record: DnsRecord,
+    },
+    DnsUpdate {
+        before: Box<DnsRecord>,
+        after: Box<DnsRecord>,
+    },
+    DnsDelete {
+        before: DnsRecord,
+    },
+    SettingChange {
+        setting_id: String,
+        /// 修改前的值，获取失败时为 None (无法撤销)
+        before: Option<serde_json::Value>,
+        after: serde_json::Value,
+    },
+    RateLimitCreate {
+        rule: RateLimitRule,
+    },
+    RateLimitDelete {
+        before: RateLimitRule,
+    },
+}
+
+impl AuditChange {
+    /// 简短描述
+    pub fn describe(&self) -> String {
+        let dns = |r: &DnsRecord| format!("{} {} → {}", r.record_type, r.name, r.content);
+        let rate_limit = |r: &RateLimitRule| {
+            format!(
+                "{} ({} 次/{}s)",
+                r.url().unwrap_or("-"),
+                r.threshold.unwrap_or(0),
+                r.period.unwrap_or(0)
+            )
+        };
+        match self {
+            AuditChange::DnsCreate { record } => format!("创建 DNS 记录 {}", dns(record)),
+            AuditChange::DnsUpdate { before, after } => {
+                format!("修改 DNS 记录 {} (原: {})", dns(after), before.content)
+            }
+            AuditChange::DnsDelete { before } => format!("删除 DNS 记录 {}", dns(before)),
+            AuditChange::SettingChange {
+                setting_id,
+                before,
+                after,
+            } => format!(
+                "设置 {}: {} → {}",
+                setting_id,
+                before.as_ref().map(|v| v.to_string()).unwrap_or("?".into()),
+                after
+            ),
+            AuditChange::RateLimitCreate { rule } => format!("创建速率限制规则 {}", rate_limit(rule)),
+            AuditChange::RateLimitDelete { before } => format!("删除速率限制规则 {}", rate_limit(before)),
+        }
+    }
+
+    /// 撤销此变更所需的信息是否齐全
+    pub fn is_reversible(&self) -> bool {
+        match self {
+            AuditChange::DnsCreate { record } => record.id.is_some(),
+            AuditChange::DnsUpdate { before, .. } => before.id.is_some(),
+            AuditChange::DnsDelete { .. } => true,
+            AuditChange::SettingChange { before, .. } => before.is_some(),
+            AuditChange::RateLimitCreate { rule } => rule.id.is_some(),
+            AuditChange::RateLimitDelete { .. } => true,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::dns::{DnsRecord, DnsRecordRequest};
+use crate::models::firewall::{CreateFirewallRuleRequest, CreateIpAccessRuleRequest, FirewallRule};
+use crate::models::page_rules::{CreatePageRuleRequest, PageRule};
+use crate::models::zone::ZoneSetting;
+
+/// 当前备份文件格式版本
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// 归入 SSL/TLS 分组的设置项
+pub const SSL_SETTING_IDS: &[&str] = &[
+    "ssl",
+    "always_use_https",
+    "min_tls_version",
+    "tls_1_3",
+    "automatic_https_rewrites",
+    "opportunistic_encryption",
+];
+
+/// 域名配置快照
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ZoneBackup {
+    /// 备份格式版本
+    pub version: u32,
+    /// 生成备份的 CFAI 版本
+    pub cfai_version: String,
+    /// 备份时间 (RFC 3339)
+    pub created_at: String,
+    pub zone: BackupZone,
+    /// SSL/TLS 相关设置 (None 表示备份时获取失败)
+    pub ssl: Option<Vec<ZoneSetting>>,
+    /// 其余域名设置
+    pub settings: Option<Vec<ZoneSetting>>,
+    pub dns_records: Option<Vec<DnsRecord>>,
+    pub page_rules: Option<Vec<PageRule>>,
+    pub firewall_rules: Option<Vec<FirewallRule>>,
+}
+
+/// 备份所属域名
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BackupZone {
+    pub id: String,
+    pub name: String,
+    pub plan: Option<String>,
+}
+
+/// 还原/同步变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Create,
+    Update,
+    Delete,
+}
+
+/// 单条待执行变更
+#[derive(Debug, Clone)]
+pub struct PlannedChange {
+    pub kind: ChangeKind,
+    /// 所属分组 (DNS / SSL / 设置 / 页面规则 / 防火墙规则 / IP 访问规则)
+    pub section: &'static str,
+    /// 变更对象描述
+    pub target: String,
+    /// 变更前后的值
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub op: ChangeOp,
+}
+
+/// 变更对应的 API 操作
+#[derive(Debug, Clone)]
+pub enum ChangeOp {
+    CreateDns(DnsRecordRequest),
+    UpdateDns(String, DnsRecordRequest),
+    DeleteDns(String),
+    UpdateSetting(String, serde_json::Value),
+    CreatePageRule(CreatePageRuleRequest),
+    UpdatePageRule(String, CreatePageRuleRequest),
+    DeletePageRule(String),
+    CreateFirewallRule(CreateFirewallRuleRequest),
+    DeleteFirewallRule(String),
+    CreateIpAccessRule(CreateIpAccessRuleRequest),
+    UpdateIpAccessRule(String, CreateIpAccessRuleRequest),
+    DeleteIpAccessRule(String),
+}
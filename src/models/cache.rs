@@ -1,5 +1,89 @@
 use serde::{Deserialize, Serialize};
 
+/// 单次清除请求最多携带的 URL / 前缀数量
+pub const PURGE_BATCH_SIZE: usize = 30;
+
+/// 可分批清除的缓存目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurgeTarget {
+    Urls,
+    Prefixes,
+}
+
+impl PurgeTarget {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PurgeTarget::Urls => "URL",
+            PurgeTarget::Prefixes => "前缀",
+        }
+    }
+}
+
+/// 按站点地图清除时最多读取的站点地图文件数 (含索引文件)
+pub const SITEMAP_MAX_FILES: usize = 50;
+
+/// 站点地图的内容: 普通站点地图列出页面 URL，站点地图索引列出子站点地图
+#[derive(Debug, Default, PartialEq)]
+pub struct Sitemap {
+    pub urls: Vec<String>,
+    pub sitemaps: Vec<String>,
+}
+
+/// 解析 sitemap.xml，提取 `<loc>` 中的地址 (兼容 CDATA 与 XML 实体)
+pub fn parse_sitemap(xml: &str) -> Sitemap {
+    let mut locs = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<loc>") {
+        rest = &rest[start + "<loc>".len()..];
+        let Some(end) = rest.find("</loc>") else { break };
+        let loc = rest[..end].trim();
+        let loc = loc
+            .strip_prefix("<![CDATA[")
+            .and_then(|l| l.strip_suffix("]]>"))
+            .unwrap_or(loc)
+            .trim();
+        let loc = loc
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&");
+        if !loc.is_empty() {
+            locs.push(loc);
+        }
+        rest = &rest[end..];
+    }
+
+    if xml.contains("<sitemapindex") {
+        Sitemap { urls: Vec::new(), sitemaps: locs }
+    } else {
+        Sitemap { urls: locs, sitemaps: Vec::new() }
+    }
+}
+
+/// URL 是否属于该域名 (域名本身或其子域名)
+pub fn url_in_zone(url: &str, zone: &str) -> bool {
+    let Some(host) = url::Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_lowercase)) else {
+        return false;
+    };
+    let zone = zone.to_lowercase();
+    host == zone || host.ends_with(&format!(".{}", zone))
+}
+
+/// URL 的路径是否以任一前缀开头，未指定前缀时全部匹配
+pub fn url_matches_paths(url: &str, paths: &[String]) -> bool {
+    if paths.is_empty() {
+        return true;
+    }
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+    paths.iter().any(|p| {
+        let prefix = if p.starts_with('/') { p.clone() } else { format!("/{}", p) };
+        url.path().starts_with(&prefix)
+    })
+}
+
 /// 缓存清除请求
 #[derive(Debug, Serialize)]
 pub struct PurgeCacheRequest {
@@ -56,3 +140,39 @@ pub struct CacheRule {
     pub action_parameters: Option<serde_json::Value>,
     pub enabled: Option<bool>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sitemap() {
+        let sitemap = parse_sitemap(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/</loc></url>
+  <url><loc> https://example.com/blog?a=1&amp;b=2 </loc><lastmod>2026-01-01</lastmod></url>
+  <url><loc><![CDATA[https://www.example.com/docs/intro]]></loc></url>
+</urlset>"#,
+        );
+        assert_eq!(
+            sitemap.urls,
+            ["https://example.com/", "https://example.com/blog?a=1&b=2", "https://www.example.com/docs/intro"]
+        );
+        assert!(sitemap.sitemaps.is_empty());
+
+        let index = parse_sitemap(
+            "<sitemapindex><sitemap><loc>https://example.com/posts.xml</loc></sitemap></sitemapindex>",
+        );
+        assert_eq!(index.sitemaps, ["https://example.com/posts.xml"]);
+        assert!(index.urls.is_empty());
+
+        assert!(url_in_zone("https://www.example.com/docs", "example.com"));
+        assert!(!url_in_zone("https://notexample.com/", "example.com"));
+        let paths = vec!["blog".to_string(), "/docs/".to_string()];
+        assert!(url_matches_paths("https://example.com/blog/post-1", &paths));
+        assert!(url_matches_paths("https://example.com/docs/intro", &paths));
+        assert!(!url_matches_paths("https://example.com/about", &paths));
+        assert!(url_matches_paths("https://example.com/about", &[]));
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// D1 数据库
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct D1Database {
+    pub uuid: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub created_at: Option<String>,
+    /// 数据库文件大小 (字节)
+    pub file_size: Option<u64>,
+    pub num_tables: Option<u64>,
+}
+
+/// 创建数据库请求
+#[derive(Debug, Serialize)]
+pub struct CreateD1DatabaseRequest {
+    pub name: String,
+    /// 位置提示 (apac/eeur/enam/weur/wnam/oc)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_location_hint: Option<String>,
+}
+
+/// 执行 SQL 请求
+#[derive(Debug, Serialize)]
+pub struct D1QueryRequest {
+    pub sql: String,
+    /// 绑定到 `?` 占位符的参数
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<String>,
+}
+
+/// 单条 SQL 语句的执行结果 (raw 格式，保留列顺序)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct D1QueryResult {
+    pub results: Option<D1RawResults>,
+    pub success: Option<bool>,
+    pub meta: Option<D1QueryMeta>,
+}
+
+/// 列名与按行排列的值
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct D1RawResults {
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// 执行统计
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct D1QueryMeta {
+    pub changes: Option<u64>,
+    /// 执行耗时 (毫秒)
+    pub duration: Option<f64>,
+    pub rows_read: Option<u64>,
+    pub rows_written: Option<u64>,
+    pub last_row_id: Option<i64>,
+    pub served_by: Option<String>,
+}
@@ -1,5 +1,8 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::api::error::ValidationError;
+
 /// DNS 记录类型
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum DnsRecordType {
@@ -56,6 +59,204 @@ impl std::str::FromStr for DnsRecordType {
     }
 }
 
+/// 相对名称补全为完整主机名 (`@` 表示根域名)
+pub fn absolute_name(name: &str, zone: &str) -> String {
+    let name = name.trim_end_matches('.');
+    if name == "@" || name.eq_ignore_ascii_case(zone) {
+        zone.to_string()
+    } else if name.to_lowercase().ends_with(&format!(".{}", zone.to_lowercase())) {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, zone)
+    }
+}
+
+/// 记录名称是否匹配模式 (不区分大小写，`*` 匹配任意字符，如 `*.example.com`)
+pub fn name_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_lowercase();
+    let name = name.trim_end_matches('.').to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// SRV 记录的 data (服务名与协议写在记录名称中，如 `_sip._tcp`)
+pub fn srv_data(priority: u16, weight: u16, port: u16, target: &str) -> serde_json::Value {
+    serde_json::json!({
+        "priority": priority,
+        "weight": weight,
+        "port": port,
+        "target": target.trim_end_matches('.'),
+    })
+}
+
+/// 解析区域文件写法的 SRV 值: `优先级 权重 端口 目标`，如 `10 5 5060 sip.example.com`；
+/// 也接受 Cloudflare 显示的 `权重 端口 目标`，此时使用 `priority` (默认 0)
+pub fn parse_srv_content(content: &str, priority: Option<u16>) -> Result<serde_json::Value> {
+    let invalid = || ValidationError(format!("SRV 记录值格式应为「优先级 权重 端口 目标」: {}", content));
+    let number = |s: &str| s.parse::<u16>().map_err(|_| invalid());
+    match content.split_whitespace().collect::<Vec<_>>()[..] {
+        [p, weight, port, target] => Ok(srv_data(number(p)?, number(weight)?, number(port)?, target)),
+        [weight, port, target] => Ok(srv_data(priority.unwrap_or(0), number(weight)?, number(port)?, target)),
+        _ => anyhow::bail!(invalid()),
+    }
+}
+
+/// CAA 记录的 data
+pub fn caa_data(flags: u8, tag: &str, value: &str) -> Result<serde_json::Value> {
+    if !["issue", "issuewild", "iodef"].contains(&tag) {
+        anyhow::bail!(ValidationError(format!("CAA 标签应为 issue、issuewild 或 iodef: {}", tag)));
+    }
+    Ok(serde_json::json!({ "flags": flags, "tag": tag, "value": value.trim_matches('"') }))
+}
+
+/// 解析区域文件写法的 CAA 值: `标志 标签 "值"`，如 `0 issue "letsencrypt.org"`
+pub fn parse_caa_content(content: &str) -> Result<serde_json::Value> {
+    let invalid = || ValidationError(format!("CAA 记录值格式应为「标志 标签 \"值\"」: {}", content));
+    let mut parts = content.trim().splitn(3, char::is_whitespace);
+    let (Some(flags), Some(tag), Some(value)) = (parts.next(), parts.next(), parts.next()) else {
+        anyhow::bail!(invalid());
+    };
+    caa_data(flags.parse().map_err(|_| invalid())?, &tag.to_lowercase(), value.trim())
+}
+
+/// 按记录值推导 SRV、CAA 记录的 data (无法解析或其他类型时为 None)
+pub fn structured_data(record_type: &str, content: &str, priority: Option<u16>) -> Option<serde_json::Value> {
+    match record_type {
+        "SRV" => parse_srv_content(content, priority).ok(),
+        "CAA" => parse_caa_content(content).ok(),
+        _ => None,
+    }
+}
+
+/// 主机名在区域内的解析路径 (`dns resolve`)
+#[derive(Debug, Serialize)]
+pub struct Resolution {
+    pub host: String,
+    pub steps: Vec<ResolveStep>,
+    /// 首个应答记录是否开启代理 (访客连接到 Cloudflare 边缘)
+    pub proxied: bool,
+    /// 最终的 A/AAAA 等记录值
+    pub targets: Vec<String>,
+    /// CNAME 链指向区域外的主机名
+    pub external: Option<String>,
+    /// CNAME 链出现循环
+    pub looped: bool,
+}
+
+/// 解析路径中的一跳
+#[derive(Debug, Serialize)]
+pub struct ResolveStep {
+    /// 查询的名称
+    pub name: String,
+    /// 应答的记录名称 (通配符时为 `*.example.com`)
+    pub matched: String,
+    pub wildcard: bool,
+    /// 应答此次查询的记录 (为空表示名称存在但没有该类型的记录)
+    pub records: Vec<DnsRecord>,
+}
+
+/// 只根据区域内的记录推断主机名的解析结果: 先精确匹配，再按最近的上级名称匹配通配符，
+/// 并跟随区域内的 CNAME。`record_types` 为查询类型 (CNAME 总是应答)
+pub fn resolve_host(records: &[DnsRecord], host: &str, zone: &str, record_types: &[&str]) -> Resolution {
+    let host = host.trim_end_matches('.').to_lowercase();
+    let zone = zone.trim_end_matches('.').to_lowercase();
+    let mut resolution = Resolution {
+        host: host.clone(),
+        steps: Vec::new(),
+        proxied: false,
+        targets: Vec::new(),
+        external: None,
+        looped: false,
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut name = host;
+    loop {
+        if name != zone && !name.ends_with(&format!(".{}", zone)) {
+            resolution.external = Some(name);
+            break;
+        }
+        if !seen.insert(name.clone()) {
+            resolution.looped = true;
+            break;
+        }
+        let Some((matched, wildcard)) = owner_name(records, &name, &zone) else {
+            break;
+        };
+        let answers: Vec<DnsRecord> = records
+            .iter()
+            .filter(|r| r.name.trim_end_matches('.').eq_ignore_ascii_case(&matched))
+            .filter(|r| r.record_type == "CNAME" || record_types.iter().any(|t| t.eq_ignore_ascii_case(&r.record_type)))
+            .cloned()
+            .collect();
+        if resolution.steps.is_empty() {
+            resolution.proxied = answers.iter().any(|r| r.proxied == Some(true));
+        }
+        let cname = answers.iter().find(|r| r.record_type == "CNAME").map(|r| r.content.trim_end_matches('.').to_lowercase());
+        if cname.is_none() {
+            resolution.targets = answers.iter().map(|r| r.content.clone()).collect();
+        }
+        resolution.steps.push(ResolveStep { name, matched, wildcard, records: answers });
+        match cname {
+            Some(target) => name = target,
+            None => break,
+        }
+    }
+    resolution
+}
+
+/// 应答某名称的记录名称: 名称本身存在 (有记录或有下级记录) 时为它自己，
+/// 否则为最近存在的上级名称下的通配符；都没有时返回 None
+fn owner_name(records: &[DnsRecord], name: &str, zone: &str) -> Option<(String, bool)> {
+    let exists = |n: &str| {
+        records.iter().any(|r| {
+            let owner = r.name.trim_end_matches('.').to_lowercase();
+            owner == n || owner.ends_with(&format!(".{}", n))
+        })
+    };
+    if exists(name) {
+        return Some((name.to_string(), false));
+    }
+    let mut parent = name;
+    while let Some((_, rest)) = parent.split_once('.') {
+        parent = rest;
+        if exists(parent) || parent == zone {
+            let wildcard = format!("*.{}", parent);
+            let found = records.iter().any(|r| r.name.trim_end_matches('.').eq_ignore_ascii_case(&wildcard));
+            return found.then_some((wildcard, true));
+        }
+    }
+    None
+}
+
+/// cfai 创建的记录在备注末尾带有此标记，apply 等批量操作默认只修改或删除带标记的记录
+pub const MANAGED_MARKER: &str = "[cfai]";
+
+/// 也可通过此标签声明记录由 cfai 管理 (标签需要付费套餐)
+pub const MANAGED_TAG: &str = "cfai:managed";
+
+/// 在备注末尾追加管理标记 (已有标记时不重复追加)
+pub fn managed_comment(comment: Option<&str>) -> String {
+    match comment.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(c) if c.ends_with(MANAGED_MARKER) => c.to_string(),
+        Some(c) => format!("{} {}", c, MANAGED_MARKER),
+        None => MANAGED_MARKER.to_string(),
+    }
+}
+
 /// DNS 记录
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DnsRecord {
@@ -75,14 +276,43 @@ pub struct DnsRecord {
     pub modified_on: Option<String>,
     pub comment: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// SRV、CAA 等记录的结构化字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl DnsRecord {
+    /// 是否由 cfai 管理 (备注带有标记或带有管理标签)
+    pub fn is_managed(&self) -> bool {
+        self.comment.as_deref().is_some_and(|c| c.trim_end().ends_with(MANAGED_MARKER))
+            || self.tags.as_ref().is_some_and(|t| t.iter().any(|t| t == MANAGED_TAG))
+    }
+
+    /// 转为可重新创建 / 覆盖此记录的请求
+    pub fn to_request(&self) -> DnsRecordRequest {
+        DnsRecordRequest {
+            record_type: self.record_type.clone(),
+            name: self.name.clone(),
+            // 带 data 的记录由 data 决定记录值
+            content: if self.data.is_some() { String::new() } else { self.content.clone() },
+            ttl: self.ttl,
+            proxied: self.proxied,
+            priority: self.priority,
+            comment: self.comment.clone(),
+            tags: self.tags.clone().filter(|t| !t.is_empty()),
+            data: self.data.clone(),
+        }
+    }
 }
 
 /// 创建/更新 DNS 记录请求
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DnsRecordRequest {
     #[serde(rename = "type")]
     pub record_type: String,
     pub name: String,
+    /// 记录值 (SRV、CAA 等使用 `data` 时为空)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ttl: Option<u32>,
@@ -94,6 +324,9 @@ pub struct DnsRecordRequest {
     pub comment: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    /// SRV、CAA 等记录的结构化字段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
 /// DNS 记录列表过滤
@@ -121,9 +354,118 @@ pub struct DnsListParams {
     pub tag: Option<String>,
 }
 
+/// DNSSEC 状态及需要在注册商处添加的 DS 记录信息
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Dnssec {
+    /// active / pending / disabled / pending-disabled / error
+    pub status: Option<String>,
+    pub algorithm: Option<String>,
+    pub digest: Option<String>,
+    pub digest_algorithm: Option<String>,
+    pub digest_type: Option<String>,
+    /// 完整 DS 记录 (可直接提交给注册商)
+    pub ds: Option<String>,
+    pub flags: Option<u32>,
+    pub key_tag: Option<u32>,
+    pub key_type: Option<String>,
+    pub public_key: Option<String>,
+    pub modified_on: Option<String>,
+}
+
+impl Dnssec {
+    pub fn status(&self) -> &str {
+        self.status.as_deref().unwrap_or("disabled")
+    }
+}
+
 /// DNS 记录导入/导出格式
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DnsImportResult {
     pub recs_added: Option<u32>,
     pub total_records_parsed: Option<u32>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_matches() {
+        assert!(name_matches("www.example.com", "WWW.example.com."));
+        assert!(!name_matches("www.example.com", "www.example.org"));
+        assert!(name_matches("*.example.com", "a.b.example.com"));
+        assert!(name_matches("*.example.com", "*.example.com"));
+        assert!(!name_matches("*.example.com", "example.com"));
+        assert!(name_matches("api-*.example.com", "api-eu.example.com"));
+        assert!(!name_matches("api-*.example.com", "web.example.com"));
+    }
+
+    #[test]
+    fn test_resolve_host() {
+        let record = |t: &str, name: &str, content: &str, proxied: bool| -> DnsRecord {
+            serde_json::from_value(serde_json::json!({
+                "type": t, "name": name, "content": content, "proxied": proxied
+            }))
+            .unwrap()
+        };
+        let records = vec![
+            record("CNAME", "www.example.com", "app.example.com", true),
+            record("A", "app.example.com", "192.0.2.1", false),
+            record("CNAME", "*.example.com", "lb.example.net", false),
+            record("TXT", "_acme.deep.example.com", "token", false),
+            record("CNAME", "a.example.com", "b.example.com", false),
+            record("CNAME", "b.example.com", "a.example.com", false),
+        ];
+        let types = ["A", "AAAA"];
+
+        let r = resolve_host(&records, "WWW.example.com.", "example.com", &types);
+        assert_eq!(r.steps.len(), 2);
+        assert!(r.proxied);
+        assert_eq!(r.targets, ["192.0.2.1"]);
+
+        let r = resolve_host(&records, "x.y.example.com", "example.com", &types);
+        assert_eq!(r.steps[0].matched, "*.example.com");
+        assert!(r.steps[0].wildcard);
+        assert_eq!(r.external.as_deref(), Some("lb.example.net"));
+
+        // 存在下级记录的名称不匹配通配符
+        let r = resolve_host(&records, "deep.example.com", "example.com", &types);
+        assert!(!r.steps[0].wildcard);
+        assert!(r.steps[0].records.is_empty());
+
+        assert!(resolve_host(&records, "a.example.com", "example.com", &types).looped);
+    }
+
+    #[test]
+    fn test_structured_record_data() {
+        assert_eq!(
+            parse_srv_content("10 5 5060 sip.example.com.", None).unwrap(),
+            serde_json::json!({ "priority": 10, "weight": 5, "port": 5060, "target": "sip.example.com" })
+        );
+        assert_eq!(parse_srv_content("5 5060 sip.example.com", Some(10)).unwrap()["priority"], 10);
+        assert!(parse_srv_content("5 sip.example.com", None).is_err());
+        assert!(parse_srv_content("10 5 99999 sip.example.com", None).is_err());
+
+        assert_eq!(
+            parse_caa_content("0 issue \"letsencrypt.org\"").unwrap(),
+            serde_json::json!({ "flags": 0, "tag": "issue", "value": "letsencrypt.org" })
+        );
+        assert!(parse_caa_content("0 issuer letsencrypt.org").is_err());
+        assert!(parse_caa_content("0 issue").is_err());
+
+        let request = DnsRecordRequest {
+            record_type: "CAA".into(),
+            name: "@".into(),
+            content: String::new(),
+            ttl: None,
+            proxied: None,
+            priority: None,
+            comment: None,
+            tags: None,
+            data: Some(caa_data(128, "iodef", "mailto:ca@example.com").unwrap()),
+        };
+        let body = serde_json::to_value(&request).unwrap();
+        assert!(body.get("content").is_none());
+        assert_eq!(body["data"]["flags"], 128);
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::api::error::ValidationError;
+use crate::models::backup::PlannedChange;
+use crate::models::dns::{DnsRecord, DnsRecordRequest};
+
+/// 模板变量
+pub struct TemplateVar {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// 默认值 (可引用 {domain} 等内置变量)，None 表示需要用户通过 --var 指定
+    pub default: Option<&'static str>,
+}
+
+/// 模板中的单条记录
+///
+/// `name` 为相对根域的记录名 (`@` 表示根域)，`name` 与 `content` 中的 `{变量}` 在应用时替换。
+pub struct TemplateRecord {
+    pub record_type: &'static str,
+    pub name: &'static str,
+    pub content: &'static str,
+    pub priority: Option<u16>,
+    pub proxied: bool,
+    /// 缺少所需变量时跳过而不是报错 (如需在服务商后台生成的 DKIM 公钥)
+    pub optional: bool,
+}
+
+/// 内置 DNS 记录模板
+pub struct DnsTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub vars: &'static [TemplateVar],
+    pub records: &'static [TemplateRecord],
+}
+
+const fn record(record_type: &'static str, name: &'static str, content: &'static str) -> TemplateRecord {
+    TemplateRecord {
+        record_type,
+        name,
+        content,
+        priority: None,
+        proxied: false,
+        optional: false,
+    }
+}
+
+const fn mx(content: &'static str, priority: u16) -> TemplateRecord {
+    TemplateRecord {
+        priority: Some(priority),
+        ..record("MX", "@", content)
+    }
+}
+
+const fn optional(record: TemplateRecord) -> TemplateRecord {
+    TemplateRecord {
+        optional: true,
+        ..record
+    }
+}
+
+/// 所有内置模板 (均可使用内置变量 {domain} 与 {domain_dashed}，如 example-com)
+pub const DNS_TEMPLATES: &[DnsTemplate] = &[
+    DnsTemplate {
+        name: "google-workspace",
+        description: "Google Workspace 邮箱 (MX + SPF，可选 DKIM 与域名验证)",
+        vars: &[
+            TemplateVar {
+                name: "dkim_key",
+                description: "Admin 控制台生成的 DKIM 记录值 (v=DKIM1; k=rsa; p=...)",
+                default: None,
+            },
+            TemplateVar {
+                name: "verification",
+                description: "google-site-verification 验证码",
+                default: None,
+            },
+        ],
+        records: &[
+            mx("smtp.google.com", 1),
+            record("TXT", "@", "v=spf1 include:_spf.google.com ~all"),
+            optional(record("TXT", "google._domainkey", "{dkim_key}")),
+            optional(record("TXT", "@", "google-site-verification={verification}")),
+        ],
+    },
+    DnsTemplate {
+        name: "microsoft-365",
+        description: "Microsoft 365 / Exchange Online 邮箱 (MX + SPF + Autodiscover，可选 DKIM)",
+        vars: &[TemplateVar {
+            name: "tenant",
+            description: "租户名 (xxx.onmicrosoft.com 中的 xxx)，用于 DKIM CNAME",
+            default: None,
+        }],
+        records: &[
+            mx("{domain_dashed}.mail.protection.outlook.com", 0),
+            record("TXT", "@", "v=spf1 include:spf.protection.outlook.com -all"),
+            record("CNAME", "autodiscover", "autodiscover.outlook.com"),
+            optional(record(
+                "CNAME",
+                "selector1._domainkey",
+                "selector1-{domain_dashed}._domainkey.{tenant}.onmicrosoft.com",
+            )),
+            optional(record(
+                "CNAME",
+                "selector2._domainkey",
+                "selector2-{domain_dashed}._domainkey.{tenant}.onmicrosoft.com",
+            )),
+        ],
+    },
+    DnsTemplate {
+        name: "github-pages",
+        description: "GitHub Pages 自定义域名 (根域 A 记录 + www CNAME)",
+        vars: &[TemplateVar {
+            name: "github_user",
+            description: "GitHub 用户名或组织名 (www 指向 <github_user>.github.io)",
+            default: None,
+        }],
+        records: &[
+            record("A", "@", "185.199.108.153"),
+            record("A", "@", "185.199.109.153"),
+            record("A", "@", "185.199.110.153"),
+            record("A", "@", "185.199.111.153"),
+            record("CNAME", "www", "{github_user}.github.io"),
+        ],
+    },
+    DnsTemplate {
+        name: "vercel",
+        description: "Vercel 托管 (根域 A 记录 + www CNAME)",
+        vars: &[],
+        records: &[
+            record("A", "@", "76.76.21.21"),
+            record("CNAME", "www", "cname.vercel-dns.com"),
+        ],
+    },
+    DnsTemplate {
+        name: "spf-dkim-dmarc-basic",
+        description: "基础邮件认证 (SPF + DMARC 监控模式，可选 DKIM)",
+        vars: &[
+            TemplateVar {
+                name: "dmarc_email",
+                description: "接收 DMARC 汇总报告的邮箱",
+                default: Some("dmarc@{domain}"),
+            },
+            TemplateVar {
+                name: "dkim_selector",
+                description: "DKIM 选择器",
+                default: Some("default"),
+            },
+            TemplateVar {
+                name: "dkim_key",
+                description: "DKIM 记录值 (v=DKIM1; k=rsa; p=...)",
+                default: None,
+            },
+        ],
+        records: &[
+            record("TXT", "@", "v=spf1 mx ~all"),
+            record("TXT", "_dmarc", "v=DMARC1; p=none; rua=mailto:{dmarc_email}"),
+            optional(record("TXT", "{dkim_selector}._domainkey", "{dkim_key}")),
+        ],
+    },
+];
+
+impl DnsTemplate {
+    /// 按名称查找内置模板
+    pub fn find(name: &str) -> Result<&'static DnsTemplate> {
+        DNS_TEMPLATES
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                ValidationError(format!(
+                    "未知的 DNS 模板: {} (可选: {})",
+                    name,
+                    DNS_TEMPLATES.iter().map(|t| t.name).collect::<Vec<_>>().join(" / ")
+                ))
+                .into()
+            })
+    }
+
+    /// 将模板渲染为待创建的记录
+    ///
+    /// 返回 (记录, 因缺少变量而跳过的可选记录说明)。必需记录缺少变量时报错。
+    pub fn render(
+        &self,
+        zone_name: &str,
+        user_vars: &BTreeMap<String, String>,
+    ) -> Result<(Vec<DnsRecordRequest>, Vec<String>)> {
+        if let Some(unknown) = user_vars.keys().find(|k| !self.vars.iter().any(|v| v.name == *k)) {
+            anyhow::bail!(ValidationError(format!(
+                "模板 {} 不支持变量: {} (可用: {})",
+                self.name,
+                unknown,
+                self.var_names()
+            )));
+        }
+
+        let mut vars = BTreeMap::new();
+        vars.insert("domain".to_string(), zone_name.to_string());
+        vars.insert("domain_dashed".to_string(), zone_name.replace('.', "-"));
+        for var in self.vars {
+            let value = user_vars
+                .get(var.name)
+                .cloned()
+                .or_else(|| var.default.map(|d| substitute(d, &vars)));
+            if let Some(value) = value {
+                vars.insert(var.name.to_string(), value);
+            }
+        }
+
+        let mut records = Vec::new();
+        let mut skipped = Vec::new();
+        for tpl in self.records {
+            let name = substitute(tpl.name, &vars);
+            let content = substitute(tpl.content, &vars);
+            if let Some(missing) = unresolved(&name).or_else(|| unresolved(&content)) {
+                if tpl.optional {
+                    skipped.push(format!("{} {} (未指定 --var {}=...)", tpl.record_type, name, missing));
+                    continue;
+                }
+                anyhow::bail!(ValidationError(format!(
+                    "模板 {} 需要变量 {}，请使用 --var {}=... 指定",
+                    self.name, missing, missing
+                )));
+            }
+            records.push(DnsRecordRequest {
+                record_type: tpl.record_type.to_string(),
+                name: if name == "@" {
+                    zone_name.to_string()
+                } else {
+                    format!("{}.{}", name, zone_name)
+                },
+                content,
+                ttl: Some(1),
+                proxied: matches!(tpl.record_type, "A" | "AAAA" | "CNAME").then_some(tpl.proxied),
+                priority: tpl.priority,
+                comment: Some(format!("cfai template: {}", self.name)),
+                tags: None,
+                data: None,
+            });
+        }
+        Ok((records, skipped))
+    }
+
+    /// 逗号分隔的变量名
+    pub fn var_names(&self) -> String {
+        if self.vars.is_empty() {
+            return "无".to_string();
+        }
+        self.vars.iter().map(|v| v.name).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// 模板应用计划
+pub struct TemplatePlan {
+    /// 待执行的变更 (替换冲突记录时，删除排在新增之前)
+    pub changes: Vec<PlannedChange>,
+    /// 已存在、无需重复创建的记录
+    pub existing: Vec<String>,
+    /// 与模板冲突的现有记录及原因
+    pub conflicts: Vec<(DnsRecord, String)>,
+}
+
+fn substitute(input: &str, vars: &BTreeMap<String, String>) -> String {
+    vars.iter()
+        .fold(input.to_string(), |s, (k, v)| s.replace(&format!("{{{}}}", k), v))
+}
+
+/// 返回第一个未替换的 {变量} 名
+fn unresolved(input: &str) -> Option<&str> {
+    let start = input.find('{')?;
+    let end = input[start..].find('}')?;
+    Some(&input[start + 1..start + end])
+}
+
+/// 解析 `--var key=value`
+pub fn parse_template_vars(pairs: &[String]) -> Result<BTreeMap<String, String>> {
+    pairs
+        .iter()
+        .map(|pair| match pair.split_once('=') {
+            Some((k, v)) if !k.trim().is_empty() => Ok((k.trim().to_string(), v.to_string())),
+            _ => Err(ValidationError(format!("变量格式应为 key=value: {}", pair)).into()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let tpl = DnsTemplate::find("spf-dkim-dmarc-basic").unwrap();
+        let (records, skipped) = tpl.render("example.com", &BTreeMap::new()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].name, "_dmarc.example.com");
+        assert_eq!(records[1].content, "v=DMARC1; p=none; rua=mailto:dmarc@example.com");
+        assert_eq!(skipped.len(), 1);
+
+        let tpl = DnsTemplate::find("github-pages").unwrap();
+        assert!(tpl.render("example.com", &BTreeMap::new()).is_err());
+        let vars = parse_template_vars(&["github_user=octocat".to_string()]).unwrap();
+        let (records, _) = tpl.render("example.com", &vars).unwrap();
+        assert_eq!(records[4].name, "www.example.com");
+        assert_eq!(records[4].content, "octocat.github.io");
+    }
+}
@@ -0,0 +1,385 @@
+//! 邮件安全记录 (SPF / DKIM / DMARC) 的解析与检查
+
+use serde::Serialize;
+
+/// SPF 最多允许的 DNS 查询次数 (RFC 7208)，超过时接收方判定为 permerror
+pub const SPF_LOOKUP_LIMIT: usize = 10;
+
+/// 问题级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// 一条检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailFinding {
+    /// SPF / DKIM / DMARC
+    pub record: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// DKIM 选择器及其记录
+#[derive(Debug, Clone, Serialize)]
+pub struct DkimKey {
+    pub selector: String,
+    /// TXT 记录值；CNAME 委托给邮件服务商时为空
+    pub value: Option<String>,
+    /// CNAME 委托的目标
+    pub target: Option<String>,
+}
+
+/// 审计结果
+#[derive(Debug, Serialize)]
+pub struct EmailAudit {
+    pub domain: String,
+    pub spf: Vec<String>,
+    pub dmarc: Vec<String>,
+    pub dkim: Vec<DkimKey>,
+    /// SPF 需要的 DNS 查询次数 (含 include 展开)
+    pub spf_lookups: Option<usize>,
+    pub findings: Vec<EmailFinding>,
+}
+
+impl EmailAudit {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings.iter().filter(|f| f.severity == severity).count()
+    }
+}
+
+fn finding(record: &str, severity: Severity, message: impl Into<String>) -> EmailFinding {
+    EmailFinding { record: record.to_string(), severity, message: message.into() }
+}
+
+/// TXT 记录值: 去掉引号并拼接分段 (如 `"v=spf1 " "-all"`)
+pub fn txt_value(content: &str) -> String {
+    let content = content.trim();
+    if !content.starts_with('"') {
+        return content.to_string();
+    }
+    content.split('"').skip(1).step_by(2).collect()
+}
+
+pub fn is_spf(value: &str) -> bool {
+    let value = value.trim_start().to_lowercase();
+    value == "v=spf1" || value.starts_with("v=spf1 ")
+}
+
+pub fn is_dmarc(value: &str) -> bool {
+    value.trim_start().to_uppercase().starts_with("V=DMARC1")
+}
+
+/// SPF 的机制与修饰符 (不含 `v=spf1`)
+fn spf_terms(spf: &str) -> impl Iterator<Item = &str> {
+    spf.split_whitespace().skip(1)
+}
+
+/// 拆分 SPF 机制: (限定符, 名称, 参数)，如 `~all` → ('~', "all", None)
+fn spf_mechanism(term: &str) -> (char, String, Option<&str>) {
+    let (qualifier, rest) = match term.chars().next() {
+        Some(q @ ('+' | '-' | '~' | '?')) => (q, &term[1..]),
+        _ => ('+', term),
+    };
+    match rest.find([':', '/']) {
+        Some(i) => (qualifier, rest[..i].to_lowercase(), Some(rest[i..].trim_start_matches(':'))),
+        None => (qualifier, rest.to_lowercase(), None),
+    }
+}
+
+/// 需要额外 DNS 查询的机制数 (include、a、mx、ptr、exists 与 redirect)
+pub fn spf_lookup_terms(spf: &str) -> usize {
+    spf_terms(spf)
+        .filter(|term| {
+            term.to_lowercase().starts_with("redirect=")
+                || matches!(spf_mechanism(term).1.as_str(), "include" | "a" | "mx" | "ptr" | "exists")
+        })
+        .count()
+}
+
+/// include 与 redirect 引用的域名
+pub fn spf_includes(spf: &str) -> Vec<String> {
+    spf_terms(spf)
+        .filter_map(|term| match term.split_once('=') {
+            Some((name, domain)) if name.eq_ignore_ascii_case("redirect") => Some(domain.to_string()),
+            _ => match spf_mechanism(term) {
+                (_, name, Some(domain)) if name == "include" => Some(domain.to_string()),
+                _ => None,
+            },
+        })
+        .collect()
+}
+
+/// 检查 SPF: 语法、过于宽松的机制、查询次数。`lookups` 为含 include 展开的查询次数
+pub fn check_spf(records: &[String], lookups: usize) -> Vec<EmailFinding> {
+    let mut findings = Vec::new();
+    let spf = match records {
+        [] => {
+            findings.push(finding(
+                "SPF",
+                Severity::Warning,
+                "未配置 SPF，任何服务器都可以冒用此域名发信 (不发信的域名可设置 v=spf1 -all)",
+            ));
+            return findings;
+        }
+        [spf] => spf,
+        _ => {
+            findings.push(finding(
+                "SPF",
+                Severity::Error,
+                format!("存在 {} 条 SPF 记录，接收方会判定为 permerror，请合并为一条", records.len()),
+            ));
+            &records[0]
+        }
+    };
+
+    let mut all = None;
+    let mut redirect = false;
+    for term in spf_terms(spf) {
+        if all.is_some() {
+            findings.push(finding("SPF", Severity::Warning, format!("{} 位于 all 之后，不会生效", term)));
+        }
+        if let Some((name, value)) = term.split_once('=').filter(|(name, _)| !name.contains(':')) {
+            match name.to_lowercase().as_str() {
+                "redirect" => redirect = true,
+                "exp" => {}
+                _ => findings.push(finding("SPF", Severity::Info, format!("未知的修饰符 {}={}", name, value))),
+            }
+            continue;
+        }
+        let (qualifier, name, value) = spf_mechanism(term);
+        match name.as_str() {
+            "all" => {
+                all = Some(qualifier);
+                match qualifier {
+                    '+' => findings.push(finding("SPF", Severity::Error, format!("{} 允许任何服务器发信，等同于没有 SPF", term))),
+                    '?' => findings.push(finding("SPF", Severity::Warning, "?all 对未列出的服务器不做判断，建议使用 ~all 或 -all")),
+                    '~' => findings.push(finding(
+                        "SPF",
+                        Severity::Info,
+                        "~all 只将未列出的服务器标记为可疑，确认发信来源完整后可改为 -all",
+                    )),
+                    _ => {}
+                }
+            }
+            "include" | "exists" if value.is_none_or(str::is_empty) => {
+                findings.push(finding("SPF", Severity::Error, format!("{} 缺少域名", term)));
+            }
+            "ip4" | "ip6" => check_spf_ip(term, &name, value, &mut findings),
+            "ptr" => findings.push(finding("SPF", Severity::Warning, "ptr 机制已不建议使用 (慢且不可靠)，请改用 ip4/ip6 或 include")),
+            "include" | "exists" | "a" | "mx" => {}
+            _ => findings.push(finding("SPF", Severity::Error, format!("无效的机制: {}", term))),
+        }
+    }
+    if all.is_none() && !redirect {
+        findings.push(finding("SPF", Severity::Warning, "没有以 all 结尾，未列出的服务器结果为中立 (建议添加 ~all 或 -all)"));
+    }
+    if lookups > SPF_LOOKUP_LIMIT {
+        findings.push(finding(
+            "SPF",
+            Severity::Error,
+            format!("需要 {} 次 DNS 查询，超过 {} 次的限制，接收方会判定为 permerror", lookups, SPF_LOOKUP_LIMIT),
+        ));
+    }
+    findings
+}
+
+fn check_spf_ip(term: &str, name: &str, value: Option<&str>, findings: &mut Vec<EmailFinding>) {
+    let (max, broad) = if name == "ip4" { (32, 16) } else { (128, 32) };
+    let Some(value) = value.filter(|v| !v.is_empty()) else {
+        findings.push(finding("SPF", Severity::Error, format!("{} 缺少地址", term)));
+        return;
+    };
+    let (addr, prefix) = value.split_once('/').unwrap_or((value, ""));
+    let valid_addr = if name == "ip4" {
+        addr.parse::<std::net::Ipv4Addr>().is_ok()
+    } else {
+        addr.parse::<std::net::Ipv6Addr>().is_ok()
+    };
+    let prefix = if prefix.is_empty() { Some(max) } else { prefix.parse::<u8>().ok().filter(|p| *p <= max) };
+    match prefix {
+        Some(prefix) if valid_addr && prefix < broad => findings.push(finding(
+            "SPF",
+            Severity::Warning,
+            format!("{} 的范围过大，允许大量无关服务器发信", term),
+        )),
+        Some(_) if valid_addr => {}
+        _ => findings.push(finding("SPF", Severity::Error, format!("无效的地址: {}", term))),
+    }
+}
+
+/// 解析 `k=v; k=v` 形式的标签 (DKIM、DMARC)，格式错误的片段返回 Err
+fn parse_tags(value: &str) -> Vec<Result<(String, String), String>> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.split_once('=') {
+            Some((k, v)) if !k.trim().is_empty() => Ok((k.trim().to_lowercase(), v.trim().to_string())),
+            _ => Err(part.to_string()),
+        })
+        .collect()
+}
+
+/// 检查 DMARC: 策略、报告地址与标签语法
+pub fn check_dmarc(records: &[String]) -> Vec<EmailFinding> {
+    let mut findings = Vec::new();
+    let dmarc = match records {
+        [] => {
+            findings.push(finding(
+                "DMARC",
+                Severity::Error,
+                "未配置 DMARC，收件方不会拒收冒充此域名的邮件 (建议先设置 v=DMARC1; p=none; rua=mailto:...)",
+            ));
+            return findings;
+        }
+        [dmarc] => dmarc,
+        _ => {
+            findings.push(finding("DMARC", Severity::Error, format!("存在 {} 条 DMARC 记录，收件方会忽略 DMARC", records.len())));
+            &records[0]
+        }
+    };
+
+    let mut tags = Vec::new();
+    for tag in parse_tags(dmarc) {
+        match tag {
+            Ok(tag) => tags.push(tag),
+            Err(part) => findings.push(finding("DMARC", Severity::Error, format!("无法解析的片段: {}", part))),
+        }
+    }
+    if tags.first().is_none_or(|(k, v)| k != "v" || v != "DMARC1") {
+        findings.push(finding("DMARC", Severity::Error, "第一个标签必须是 v=DMARC1"));
+    }
+    let get = |name: &str| tags.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str());
+    let policy = |value: &str| ["none", "quarantine", "reject"].contains(&value.to_lowercase().as_str());
+
+    match get("p") {
+        None => findings.push(finding("DMARC", Severity::Error, "缺少策略 (p=)，记录无效")),
+        Some(p) if !policy(p) => findings.push(finding("DMARC", Severity::Error, format!("无效的策略 p={}", p))),
+        Some(p) if p.eq_ignore_ascii_case("none") => findings.push(finding(
+            "DMARC",
+            Severity::Warning,
+            "p=none 只收集报告、不拦截冒充邮件，确认合法发信通过后请改为 quarantine 或 reject",
+        )),
+        Some(_) => {}
+    }
+    if let Some(sp) = get("sp") {
+        if !policy(sp) {
+            findings.push(finding("DMARC", Severity::Error, format!("无效的子域名策略 sp={}", sp)));
+        } else if sp.eq_ignore_ascii_case("none") && get("p").is_some_and(|p| !p.eq_ignore_ascii_case("none")) {
+            findings.push(finding("DMARC", Severity::Warning, "sp=none 使子域名不受主策略保护"));
+        }
+    }
+    if let Some(pct) = get("pct") {
+        match pct.parse::<u8>() {
+            Ok(100) => {}
+            Ok(pct) if pct < 100 => findings.push(finding(
+                "DMARC",
+                Severity::Warning,
+                format!("pct={} 只对 {}% 的邮件执行策略", pct, pct),
+            )),
+            _ => findings.push(finding("DMARC", Severity::Error, format!("无效的 pct={} (应为 0-100)", pct))),
+        }
+    }
+    for name in ["adkim", "aspf"] {
+        if let Some(value) = get(name).filter(|v| !v.eq_ignore_ascii_case("r") && !v.eq_ignore_ascii_case("s")) {
+            findings.push(finding("DMARC", Severity::Error, format!("无效的 {}={} (应为 r 或 s)", name, value)));
+        }
+    }
+    match get("rua") {
+        None => findings.push(finding("DMARC", Severity::Info, "未设置汇总报告地址 (rua=)，无法了解谁在以此域名发信")),
+        Some(rua) => {
+            for uri in rua.split(',').map(str::trim).filter(|u| !u.to_lowercase().starts_with("mailto:")) {
+                findings.push(finding("DMARC", Severity::Error, format!("报告地址应以 mailto: 开头: {}", uri)));
+            }
+        }
+    }
+    const KNOWN: [&str; 12] = ["v", "p", "sp", "np", "rua", "ruf", "adkim", "aspf", "pct", "fo", "rf", "ri"];
+    for (k, _) in tags.iter().filter(|(k, _)| !KNOWN.contains(&k.as_str())) {
+        findings.push(finding("DMARC", Severity::Warning, format!("未知的标签 {}", k)));
+    }
+    findings
+}
+
+/// 检查一个 DKIM 选择器的公钥记录
+pub fn check_dkim(key: &DkimKey) -> Vec<EmailFinding> {
+    let mut findings = Vec::new();
+    let record = format!("DKIM {}", key.selector);
+    let Some(value) = &key.value else {
+        return findings;
+    };
+    let mut tags = Vec::new();
+    for tag in parse_tags(value) {
+        match tag {
+            Ok(tag) => tags.push(tag),
+            Err(part) => findings.push(finding(&record, Severity::Error, format!("无法解析的片段: {}", part))),
+        }
+    }
+    let get = |name: &str| tags.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str());
+
+    if get("v").is_some_and(|v| v != "DKIM1") || tags.iter().position(|(k, _)| k == "v").is_some_and(|i| i > 0) {
+        findings.push(finding(&record, Severity::Error, "v= 只能是 DKIM1 且必须位于第一个"));
+    }
+    let key_type = get("k").unwrap_or("rsa").to_lowercase();
+    if key_type != "rsa" && key_type != "ed25519" {
+        findings.push(finding(&record, Severity::Error, format!("不支持的密钥类型 k={}", key_type)));
+    }
+    match get("p").map(|p| p.split_whitespace().collect::<String>()) {
+        None => findings.push(finding(&record, Severity::Error, "缺少公钥 (p=)")),
+        Some(p) if p.is_empty() => findings.push(finding(&record, Severity::Warning, "公钥为空，此选择器已被吊销")),
+        // 1024 位 RSA 公钥的 Base64 约 216 个字符，2048 位约 392 个
+        Some(p) if key_type == "rsa" && p.len() < 300 => findings.push(finding(
+            &record,
+            Severity::Warning,
+            "RSA 密钥约为 1024 位或更短，建议更换为 2048 位",
+        )),
+        Some(_) => {}
+    }
+    if get("t").is_some_and(|t| t.split(':').any(|flag| flag.trim().eq_ignore_ascii_case("y"))) {
+        findings.push(finding(&record, Severity::Warning, "处于测试模式 (t=y)，收件方不会依据签名结果处理邮件"));
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(findings: &[EmailFinding], severity: Severity) -> Vec<&str> {
+        findings.iter().filter(|f| f.severity == severity).map(|f| f.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_check_spf() {
+        assert_eq!(txt_value("\"v=spf1 \" \"-all\""), "v=spf1 -all");
+        let spf = "v=spf1 ip4:192.0.2.0/24 include:_spf.example.net mx redirect=_spf.example.org";
+        assert_eq!(spf_lookup_terms(spf), 3);
+        assert_eq!(spf_includes(spf), ["_spf.example.net", "_spf.example.org"]);
+
+        assert!(check_spf(&["v=spf1 mx -all".into()], 1).is_empty());
+        let findings = check_spf(&["v=spf1 ip4:10.0.0.0/8 ptr foo +all mx".into()], 11);
+        assert_eq!(messages(&findings, Severity::Error).len(), 3);
+        assert_eq!(messages(&findings, Severity::Warning).len(), 3);
+        assert_eq!(messages(&check_spf(&["v=spf1 -all".into(), "v=spf1 mx -all".into()], 1), Severity::Error).len(), 1);
+        assert_eq!(check_spf(&[], 0)[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_check_dmarc_and_dkim() {
+        assert!(check_dmarc(&["v=DMARC1; p=reject; rua=mailto:d@example.com".into()]).is_empty());
+        let findings = check_dmarc(&["v=DMARC1; p=none; pct=50; rua=https://example.com; foo=bar".into()]);
+        assert_eq!(messages(&findings, Severity::Error), ["报告地址应以 mailto: 开头: https://example.com"]);
+        assert_eq!(messages(&findings, Severity::Warning).len(), 3);
+        assert_eq!(check_dmarc(&["p=reject; v=DMARC1".into()]).len(), 2);
+        assert_eq!(check_dmarc(&[])[0].severity, Severity::Error);
+
+        let key = |value: &str| DkimKey { selector: "s1".into(), value: Some(value.into()), target: None };
+        assert!(check_dkim(&key(&format!("v=DKIM1; k=rsa; p={}", "A".repeat(392)))).is_empty());
+        let findings = check_dkim(&key("v=DKIM1; t=y; p=MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQC"));
+        assert_eq!(messages(&findings, Severity::Warning).len(), 2);
+        assert_eq!(messages(&check_dkim(&key("k=dsa")), Severity::Error).len(), 2);
+    }
+}
@@ -22,6 +22,26 @@ pub struct FirewallFilter {
     pub description: Option<String>,
 }
 
+/// 创建防火墙规则请求
+#[derive(Debug, Serialize, Clone)]
+pub struct CreateFirewallRuleRequest {
+    pub filter: CreateFirewallFilter,
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CreateFirewallFilter {
+    pub expression: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
 /// WAF 规则组
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct WafRuleGroup {
@@ -51,6 +71,49 @@ pub struct IpAccessConfig {
     pub value: Option<String>,
 }
 
+/// Cloudflare 公布的边缘节点 IP 段
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CloudflareIps {
+    pub ipv4_cidrs: Option<Vec<String>>,
+    pub ipv6_cidrs: Option<Vec<String>>,
+    pub etag: Option<String>,
+}
+
+impl CloudflareIps {
+    /// 包含该 IP 的网段
+    pub fn find(&self, ip: std::net::IpAddr) -> Option<&str> {
+        self.ipv4_cidrs
+            .iter()
+            .chain(self.ipv6_cidrs.iter())
+            .flatten()
+            .find(|cidr| cidr_contains(cidr, ip))
+            .map(String::as_str)
+    }
+}
+
+/// 判断 CIDR (或单个 IP) 是否包含该 IP
+pub fn cidr_contains(cidr: &str, ip: std::net::IpAddr) -> bool {
+    use std::net::IpAddr;
+
+    let (network, prefix) = match cidr.trim().split_once('/') {
+        Some((network, prefix)) => (network, prefix.parse::<u32>().ok()),
+        None => (cidr.trim(), None),
+    };
+    match (network.parse::<IpAddr>(), ip) {
+        (Ok(IpAddr::V4(network)), IpAddr::V4(ip)) => {
+            let prefix = prefix.unwrap_or(32).min(32);
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (Ok(IpAddr::V6(network)), IpAddr::V6(ip)) => {
+            let prefix = prefix.unwrap_or(128).min(128);
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct IpAccessScope {
     pub id: Option<String>,
@@ -59,7 +122,7 @@ pub struct IpAccessScope {
 }
 
 /// 创建 IP 访问规则请求
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct CreateIpAccessRuleRequest {
     pub mode: String,
     pub configuration: IpAccessRuleConfig,
@@ -67,12 +130,57 @@ pub struct CreateIpAccessRuleRequest {
     pub notes: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash)]
 pub struct IpAccessRuleConfig {
     pub target: String,
     pub value: String,
 }
 
+impl IpAccessRuleConfig {
+    /// 识别 IP、CIDR、ASN (AS13335) 或国家代码 (CN)，无法识别时返回 None
+    pub fn parse(entry: &str) -> Option<Self> {
+        use std::net::IpAddr;
+
+        let entry = entry.trim();
+        let config = |target: &str, value: String| Some(Self { target: target.to_string(), value });
+        if let Ok(ip) = entry.parse::<IpAddr>() {
+            return config(if ip.is_ipv4() { "ip" } else { "ip6" }, ip.to_string());
+        }
+        if let Some((network, prefix)) = entry.split_once('/') {
+            let network: IpAddr = network.parse().ok()?;
+            let prefix: u8 = prefix.parse().ok()?;
+            if prefix > if network.is_ipv4() { 32 } else { 128 } {
+                return None;
+            }
+            return config("ip_range", format!("{}/{}", network, prefix));
+        }
+        let upper = entry.to_uppercase();
+        if let Some(asn) = upper.strip_prefix("AS") {
+            if !asn.is_empty() && asn.chars().all(|c| c.is_ascii_digit()) {
+                return config("asn", upper);
+            }
+        }
+        if upper.len() == 2 && upper.chars().all(|c| c.is_ascii_alphabetic()) {
+            return config("country", upper);
+        }
+        None
+    }
+
+    /// 是否与已有规则的目标相同
+    pub fn matches(&self, existing: &IpAccessConfig) -> bool {
+        let (Some(target), Some(value)) = (&existing.target, &existing.value) else {
+            return false;
+        };
+        if target != &self.target {
+            return false;
+        }
+        match Self::parse(value) {
+            Some(parsed) => parsed.value == self.value,
+            None => value.eq_ignore_ascii_case(&self.value),
+        }
+    }
+}
+
 /// 用户代理规则
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct UserAgentRule {
@@ -89,6 +197,60 @@ pub struct UserAgentConfig {
     pub value: Option<String>,
 }
 
+/// 创建用户代理规则请求
+#[derive(Debug, Serialize, Clone)]
+pub struct UserAgentRuleRequest {
+    pub mode: String,
+    pub configuration: UserAgentRuleConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UserAgentRuleConfig {
+    /// 固定为 ua
+    pub target: String,
+    pub value: String,
+}
+
+impl UserAgentRuleRequest {
+    /// 按完整的 User-Agent 字符串匹配
+    pub fn new(user_agent: &str, mode: &str) -> Self {
+        Self {
+            mode: mode.to_string(),
+            configuration: UserAgentRuleConfig {
+                target: "ua".to_string(),
+                value: user_agent.to_string(),
+            },
+            description: None,
+            paused: false,
+        }
+    }
+}
+
+/// Zone Lockdown 规则: 只允许指定 IP 访问匹配的 URL
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LockdownRule {
+    pub id: Option<String>,
+    pub description: Option<String>,
+    pub paused: Option<bool>,
+    pub urls: Option<Vec<String>>,
+    pub configurations: Option<Vec<IpAccessConfig>>,
+    pub created_on: Option<String>,
+}
+
+/// 创建 Zone Lockdown 规则请求
+#[derive(Debug, Serialize, Clone)]
+pub struct LockdownRuleRequest {
+    pub urls: Vec<String>,
+    /// 允许访问的 IP (target 为 ip 或 ip_range)
+    pub configurations: Vec<IpAccessRuleConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub paused: bool,
+}
+
 /// 速率限制规则
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RateLimitRule {
@@ -105,28 +267,123 @@ pub struct RateLimitRule {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RateLimitAction {
     pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u32>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RateLimitMatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub request: Option<RateLimitMatchRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub response: Option<RateLimitMatchResponse>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RateLimitMatchRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub methods: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub schemes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RateLimitMatchResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Vec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub origin_traffic: Option<bool>,
 }
 
+/// 速率限制动作 (ban/simulate 需要 timeout，质询类动作不能带 timeout)
+pub const RATE_LIMIT_MODES: &[&str] = &[
+    "ban",
+    "simulate",
+    "challenge",
+    "js_challenge",
+    "managed_challenge",
+];
+
+/// 创建/更新速率限制规则请求
+#[derive(Debug, Serialize, Clone)]
+pub struct RateLimitRequest {
+    pub threshold: u32,
+    pub period: u32,
+    pub action: RateLimitAction,
+    #[serde(rename = "match")]
+    pub match_config: RateLimitMatch,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+}
+
+impl RateLimitRule {
+    /// 匹配的 URL 模式
+    pub fn url(&self) -> Option<&str> {
+        self.match_config.as_ref()?.request.as_ref()?.url.as_deref()
+    }
+}
+
+impl RateLimitRequest {
+    /// 按 URL 模式创建规则 (匹配所有方法和协议)
+    pub fn new(url: &str, threshold: u32, period: u32, mode: &str, timeout: Option<u32>) -> Self {
+        Self {
+            threshold,
+            period,
+            action: RateLimitAction {
+                mode: Some(mode.to_string()),
+                timeout,
+            },
+            match_config: RateLimitMatch {
+                request: Some(RateLimitMatchRequest {
+                    methods: None,
+                    schemes: None,
+                    url: Some(url.to_string()),
+                }),
+                response: None,
+            },
+            description: None,
+            disabled: None,
+        }
+    }
+
+    /// 以已有规则为基础构造更新请求
+    pub fn from_rule(rule: &RateLimitRule) -> Self {
+        Self {
+            threshold: rule.threshold.unwrap_or(0),
+            period: rule.period.unwrap_or(0),
+            action: rule.action.clone().unwrap_or(RateLimitAction {
+                mode: None,
+                timeout: None,
+            }),
+            match_config: rule.match_config.clone().unwrap_or(RateLimitMatch {
+                request: None,
+                response: None,
+            }),
+            description: rule.description.clone(),
+            disabled: rule.disabled,
+        }
+    }
+
+    /// 匹配的 URL 模式
+    pub fn url(&self) -> Option<&str> {
+        self.match_config.request.as_ref()?.url.as_deref()
+    }
+
+    /// 修改匹配的 URL 模式，保留其余匹配条件
+    pub fn set_url(&mut self, url: &str) {
+        let request = self.match_config.request.get_or_insert(RateLimitMatchRequest {
+            methods: None,
+            schemes: None,
+            url: None,
+        });
+        request.url = Some(url.to_string());
+    }
+}
+
 /// 安全级别
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -166,3 +423,111 @@ impl std::str::FromStr for SecurityLevel {
         }
     }
 }
+
+/// 防火墙事件 (GraphQL firewallEventsAdaptive 数据集)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FirewallEvent {
+    pub datetime: Option<String>,
+    pub action: Option<String>,
+    /// 触发事件的产品 (firewallCustom、waf、rateLimit、securityLevel 等)
+    pub source: Option<String>,
+    #[serde(rename = "ruleId")]
+    pub rule_id: Option<String>,
+    #[serde(rename = "clientIP")]
+    pub client_ip: Option<String>,
+    #[serde(rename = "clientCountryName")]
+    pub client_country: Option<String>,
+    #[serde(rename = "clientRequestHTTPMethodName")]
+    pub method: Option<String>,
+    #[serde(rename = "clientRequestHTTPHost")]
+    pub host: Option<String>,
+    #[serde(rename = "clientRequestPath")]
+    pub path: Option<String>,
+    #[serde(rename = "userAgent")]
+    pub user_agent: Option<String>,
+    #[serde(rename = "rayName")]
+    pub ray_id: Option<String>,
+}
+
+/// 防火墙事件动作 (用于过滤)
+pub const FIREWALL_EVENT_ACTIONS: &[&str] = &[
+    "block",
+    "challenge",
+    "managed_challenge",
+    "jschallenge",
+    "log",
+    "allow",
+    "skip",
+];
+
+/// 防火墙事件查询条件
+#[derive(Debug, Clone)]
+pub struct FirewallEventQuery {
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub until: chrono::DateTime<chrono::Utc>,
+    pub action: Option<String>,
+    pub client_ip: Option<String>,
+    pub limit: u32,
+}
+
+/// Bot 管理配置 (Bot Fight Mode / Super Bot Fight Mode / Enterprise Bot Management)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct BotManagementConfig {
+    /// Bot Fight Mode (免费版)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fight_mode: Option<bool>,
+    /// 注入 JavaScript 检测
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_js: Option<bool>,
+    /// Super Bot Fight Mode: 确定为自动化流量的处理方式 (allow/block/managed_challenge)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbfm_definitely_automated: Option<String>,
+    /// Super Bot Fight Mode: 可能为自动化流量的处理方式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbfm_likely_automated: Option<String>,
+    /// Super Bot Fight Mode: 已验证 Bot 的处理方式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbfm_verified_bots: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbfm_static_resource_protection: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimize_wordpress: Option<bool>,
+    /// AI 爬虫拦截 (block/disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_bots_protection: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub using_latest_model: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cidr_contains() {
+        let ip = |s: &str| s.parse().unwrap();
+        assert!(cidr_contains("104.16.0.0/13", ip("104.23.255.1")));
+        assert!(!cidr_contains("104.16.0.0/13", ip("104.24.0.1")));
+        assert!(cidr_contains("203.0.113.9", ip("203.0.113.9")));
+        assert!(cidr_contains("0.0.0.0/0", ip("198.51.100.1")));
+        assert!(cidr_contains("2606:4700::/32", ip("2606:4700:10::1")));
+        assert!(!cidr_contains("2606:4700::/32", ip("104.16.0.1")));
+        assert!(!cidr_contains("invalid", ip("104.16.0.1")));
+    }
+
+    #[test]
+    fn test_parse_access_target() {
+        let parse = |s: &str| IpAccessRuleConfig::parse(s).map(|c| (c.target, c.value));
+        let pair = |t: &str, v: &str| Some((t.to_string(), v.to_string()));
+        assert_eq!(parse("203.0.113.7"), pair("ip", "203.0.113.7"));
+        assert_eq!(parse("2001:DB8::1"), pair("ip6", "2001:db8::1"));
+        assert_eq!(parse("198.51.100.0/24"), pair("ip_range", "198.51.100.0/24"));
+        assert_eq!(parse("as13335"), pair("asn", "AS13335"));
+        assert_eq!(parse("cn"), pair("country", "CN"));
+        assert_eq!(parse("10.0.0.0/33"), None);
+        assert_eq!(parse("example.com"), None);
+
+        let existing = IpAccessConfig { target: Some("ip6".into()), value: Some("2001:0db8::1".into()) };
+        assert!(IpAccessRuleConfig::parse("2001:db8::1").unwrap().matches(&existing));
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+/// 可选的检查区域
+pub const HEALTHCHECK_REGIONS: &[&str] = &[
+    "WNAM", "ENAM", "WEU", "EEU", "NSAM", "SSAM", "OC", "ME", "NAF", "SAF", "IN", "SEAS", "NEAS",
+    "ALL_REGIONS",
+];
+
+/// 独立健康检查 (Standalone Health Check)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Healthcheck {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// 源站地址 (主机名或 IP)
+    pub address: Option<String>,
+    /// HTTP / HTTPS / TCP
+    #[serde(rename = "type")]
+    pub check_type: Option<String>,
+    pub check_regions: Option<Vec<String>>,
+    pub consecutive_fails: Option<u32>,
+    pub consecutive_successes: Option<u32>,
+    /// 检查间隔 (秒)
+    pub interval: Option<u32>,
+    pub retries: Option<u32>,
+    /// 单次检查超时 (秒)
+    pub timeout: Option<u32>,
+    pub suspended: Option<bool>,
+    /// unknown / healthy / unhealthy / suspended
+    pub status: Option<String>,
+    /// 最近一次检查失败的原因
+    pub failure_reason: Option<String>,
+    pub http_config: Option<HealthcheckHttpConfig>,
+    pub tcp_config: Option<HealthcheckTcpConfig>,
+    pub created_on: Option<String>,
+    pub modified_on: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct HealthcheckHttpConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// 期望的状态码 (如 "200"、"2xx")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_codes: Option<Vec<String>>,
+    /// 响应体需包含的内容
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follow_redirects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_insecure: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct HealthcheckTcpConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+}
+
+/// 创建 / 更新健康检查请求
+#[derive(Debug, Serialize, Clone)]
+pub struct HealthcheckRequest {
+    pub name: String,
+    pub address: String,
+    #[serde(rename = "type")]
+    pub check_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_regions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consecutive_fails: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consecutive_successes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspended: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_config: Option<HealthcheckHttpConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_config: Option<HealthcheckTcpConfig>,
+}
+
+impl HealthcheckRequest {
+    /// 创建请求，其余参数使用 Cloudflare 默认值
+    pub fn new(name: &str, address: &str, check_type: &str) -> Self {
+        let mut request = Self {
+            name: name.to_string(),
+            address: address.to_string(),
+            check_type: String::new(),
+            description: None,
+            check_regions: None,
+            consecutive_fails: None,
+            consecutive_successes: None,
+            interval: None,
+            retries: None,
+            timeout: None,
+            suspended: None,
+            http_config: None,
+            tcp_config: None,
+        };
+        request.set_type(check_type);
+        request
+    }
+
+    /// 以已有健康检查为基础构造更新请求
+    pub fn from_healthcheck(check: &Healthcheck) -> Self {
+        Self {
+            name: check.name.clone().unwrap_or_default(),
+            address: check.address.clone().unwrap_or_default(),
+            check_type: check.check_type.clone().unwrap_or_else(|| "HTTP".to_string()),
+            description: check.description.clone(),
+            check_regions: check.check_regions.clone(),
+            consecutive_fails: check.consecutive_fails,
+            consecutive_successes: check.consecutive_successes,
+            interval: check.interval,
+            retries: check.retries,
+            timeout: check.timeout,
+            suspended: check.suspended,
+            http_config: check.http_config.clone(),
+            tcp_config: check.tcp_config.clone(),
+        }
+    }
+
+    /// 切换检查类型时补齐或移除对应的协议配置
+    pub fn set_type(&mut self, check_type: &str) {
+        self.check_type = check_type.to_string();
+        if check_type == "TCP" {
+            self.http_config = None;
+            self.tcp_config.get_or_insert_with(HealthcheckTcpConfig::default);
+        } else {
+            self.tcp_config = None;
+            self.http_config.get_or_insert_with(HealthcheckHttpConfig::default);
+        }
+    }
+}
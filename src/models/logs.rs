@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::error::ValidationError;
+
+/// 默认输出的日志字段
+pub const DEFAULT_LOG_FIELDS: &[&str] = &[
+    "EdgeStartTimestamp",
+    "EdgeResponseStatus",
+    "ClientRequestMethod",
+    "ClientRequestHost",
+    "ClientRequestPath",
+    "ClientIP",
+    "ClientCountry",
+    "CacheCacheStatus",
+    "RayID",
+];
+
+/// 创建 Instant Logs 会话请求
+#[derive(Debug, Serialize)]
+pub struct InstantLogsRequest {
+    /// 逗号分隔的字段列表
+    pub fields: String,
+    /// 采样率 (1 为全部，10 为十分之一)
+    pub sample: u32,
+    pub filter: String,
+    pub kind: String,
+}
+
+/// Instant Logs 会话
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InstantLogsJob {
+    /// WebSocket 地址 (wss://logs.cloudflare.com/...)
+    pub destination_conf: String,
+    pub session_id: Option<String>,
+    pub fields: Option<String>,
+    pub sample: Option<u32>,
+}
+
+/// 状态码过滤条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusFilter {
+    /// 等于
+    Exact(u16),
+    /// 大于等于
+    AtLeast(u16),
+    /// 同一类 (如 5xx)
+    Class(u16),
+}
+
+impl std::str::FromStr for StatusFilter {
+    type Err = ValidationError;
+
+    /// 支持 `500`、`>=500`、`>499`、`5xx`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let invalid = || ValidationError(format!("无效的状态码过滤: {} (示例: 500、>=500、5xx)", s));
+        if let Some(class) = s.strip_suffix("xx") {
+            return match class.parse::<u16>() {
+                Ok(c @ 1..=5) => Ok(StatusFilter::Class(c)),
+                _ => Err(invalid()),
+            };
+        }
+        if let Some(code) = s.strip_prefix(">=") {
+            return code.trim().parse().map(StatusFilter::AtLeast).map_err(|_| invalid());
+        }
+        if let Some(code) = s.strip_prefix('>') {
+            return code
+                .trim()
+                .parse::<u16>()
+                .map(|c| StatusFilter::AtLeast(c + 1))
+                .map_err(|_| invalid());
+        }
+        s.parse().map(StatusFilter::Exact).map_err(|_| invalid())
+    }
+}
+
+impl StatusFilter {
+    pub fn matches(&self, status: u16) -> bool {
+        match *self {
+            StatusFilter::Exact(code) => status == code,
+            StatusFilter::AtLeast(code) => status >= code,
+            StatusFilter::Class(class) => status / 100 == class,
+        }
+    }
+}
+
+/// 客户端日志过滤条件
+#[derive(Debug, Default, Clone)]
+pub struct LogFilter {
+    pub status: Option<StatusFilter>,
+    pub path_prefix: Option<String>,
+    pub host: Option<String>,
+}
+
+impl LogFilter {
+    /// 过滤依赖的字段，需要包含在会话字段中
+    pub fn required_fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.status.is_some() {
+            fields.push("EdgeResponseStatus");
+        }
+        if self.path_prefix.is_some() {
+            fields.push("ClientRequestPath");
+        }
+        if self.host.is_some() {
+            fields.push("ClientRequestHost");
+        }
+        fields
+    }
+
+    pub fn matches(&self, record: &serde_json::Value) -> bool {
+        let status_ok = self.status.is_none_or(|filter| {
+            record["EdgeResponseStatus"]
+                .as_u64()
+                .is_some_and(|s| filter.matches(s as u16))
+        });
+        let path_ok = self.path_prefix.as_deref().is_none_or(|prefix| {
+            record["ClientRequestPath"]
+                .as_str()
+                .is_some_and(|p| p.starts_with(prefix))
+        });
+        let host_ok = self.host.as_deref().is_none_or(|host| {
+            record["ClientRequestHost"]
+                .as_str()
+                .is_some_and(|h| h.eq_ignore_ascii_case(host))
+        });
+        status_ok && path_ok && host_ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_filter_parses_and_matches() {
+        let at_least: StatusFilter = ">=500".parse().unwrap();
+        assert!(at_least.matches(503) && !at_least.matches(404));
+        assert_eq!(">499".parse::<StatusFilter>().unwrap(), StatusFilter::AtLeast(500));
+        assert!("4xx".parse::<StatusFilter>().unwrap().matches(429));
+        assert!("404".parse::<StatusFilter>().unwrap().matches(404));
+        assert!("9xx".parse::<StatusFilter>().is_err());
+        assert!("abc".parse::<StatusFilter>().is_err());
+    }
+
+    #[test]
+    fn log_filter_combines_conditions() {
+        let filter = LogFilter {
+            status: Some(StatusFilter::AtLeast(500)),
+            path_prefix: Some("/api".into()),
+            host: None,
+        };
+        let record = |status: u16, path: &str| {
+            serde_json::json!({ "EdgeResponseStatus": status, "ClientRequestPath": path })
+        };
+        assert!(filter.matches(&record(502, "/api/users")));
+        assert!(!filter.matches(&record(200, "/api/users")));
+        assert!(!filter.matches(&record(502, "/static/app.js")));
+        assert!(!filter.matches(&serde_json::json!({})));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod common;
 pub mod dns;
+pub mod dns_template;
+pub mod email_security;
 pub mod zone;
 pub mod ssl;
 pub mod firewall;
@@ -7,3 +9,16 @@ pub mod cache;
 pub mod page_rules;
 pub mod workers;
 pub mod analytics;
+pub mod logs;
+pub mod backup;
+pub mod zone_spec;
+pub mod rulesets;
+pub mod access;
+pub mod r2;
+pub mod d1;
+pub mod audit;
+pub mod account;
+pub mod token;
+pub mod healthcheck;
+pub mod zaraz;
+pub mod urlscanner;
@@ -31,7 +31,7 @@ pub struct PageRuleAction {
 }
 
 /// 创建页面规则请求
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct CreatePageRuleRequest {
     pub targets: Vec<PageRuleTarget>,
     pub actions: Vec<PageRuleAction>,
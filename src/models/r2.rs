@@ -0,0 +1,72 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// R2 存储桶
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct R2Bucket {
+    pub name: String,
+    pub creation_date: Option<String>,
+    pub location: Option<String>,
+    pub storage_class: Option<String>,
+}
+
+/// 存储桶列表响应
+#[derive(Debug, Deserialize)]
+pub struct R2BucketList {
+    #[serde(default)]
+    pub buckets: Vec<R2Bucket>,
+}
+
+/// 创建存储桶请求
+#[derive(Debug, Serialize)]
+pub struct CreateR2BucketRequest {
+    pub name: String,
+    /// 位置提示 (apac/eeur/enam/weur/wnam/oc)
+    #[serde(rename = "locationHint", skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+}
+
+/// R2 对象
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct R2Object {
+    pub key: String,
+    #[serde(default, deserialize_with = "de_lenient_u64")]
+    pub size: Option<u64>,
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+    pub storage_class: Option<String>,
+}
+
+/// 列出对象的查询参数
+#[derive(Debug, Serialize, Default)]
+pub struct R2ObjectListParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<u32>,
+}
+
+/// 存储桶用量
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct R2BucketUsage {
+    /// 统计截止时间
+    pub end: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_u64")]
+    pub payload_size: Option<u64>,
+    #[serde(default, deserialize_with = "de_lenient_u64")]
+    pub metadata_size: Option<u64>,
+    #[serde(default, deserialize_with = "de_lenient_u64")]
+    pub object_count: Option<u64>,
+    #[serde(default, deserialize_with = "de_lenient_u64")]
+    pub upload_count: Option<u64>,
+}
+
+/// R2 API 中的数值有时以字符串返回，两种形式都接受
+fn de_lenient_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => n.as_u64(),
+        Some(serde_json::Value::String(s)) => s.parse().ok(),
+        _ => None,
+    })
+}
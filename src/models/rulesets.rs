@@ -0,0 +1,297 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::api::error::ValidationError;
+
+/// 规则集 (Rulesets Engine)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Ruleset {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub kind: Option<String>,
+    pub phase: Option<String>,
+    pub version: Option<String>,
+    pub rules: Option<Vec<RulesetRule>>,
+    pub last_updated: Option<String>,
+}
+
+/// 规则集中的单条规则
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RulesetRule {
+    pub id: Option<String>,
+    pub action: Option<String>,
+    pub expression: Option<String>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    pub action_parameters: Option<serde_json::Value>,
+    pub last_updated: Option<String>,
+}
+
+/// 创建或更新规则的请求
+#[derive(Debug, Serialize, Clone)]
+pub struct RulesetRuleRequest {
+    pub action: String,
+    pub expression: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_parameters: Option<serde_json::Value>,
+}
+
+impl RulesetRule {
+    /// execute 规则所部署的托管规则集 ID
+    pub fn executed_ruleset_id(&self) -> Option<&str> {
+        if self.action.as_deref() != Some("execute") {
+            return None;
+        }
+        self.action_parameters.as_ref()?.get("id")?.as_str()
+    }
+
+    /// 以当前规则内容构造更新请求
+    pub fn to_request(&self) -> RulesetRuleRequest {
+        RulesetRuleRequest {
+            action: self.action.clone().unwrap_or_default(),
+            expression: self.expression.clone().unwrap_or_else(|| "true".into()),
+            description: self.description.clone(),
+            enabled: self.enabled,
+            action_parameters: self.action_parameters.clone(),
+        }
+    }
+}
+
+/// 托管 WAF 规则集所在的阶段
+pub const WAF_MANAGED_PHASE: &str = "http_request_firewall_managed";
+
+/// WAF 自定义规则 (取代旧版防火墙规则) 所在的阶段
+pub const WAF_CUSTOM_PHASE: &str = "http_request_firewall_custom";
+
+/// 常用托管规则集 (别名, 规则集 ID, 名称)
+pub const MANAGED_RULESETS: &[(&str, &str, &str)] = &[
+    ("managed", "efb7b8c949ac4650a09736fc376e9aee", "Cloudflare Managed Ruleset"),
+    ("owasp", "4814384a9e5d4991b9815dcfc25d2f1f", "Cloudflare OWASP Core Ruleset"),
+    ("credentials", "c2e184081120413c86c3ab7e14069605", "Cloudflare Exposed Credentials Check"),
+    ("free", "77454fe2d30c4220b5701f6fdfb893ba", "Cloudflare Free Managed Ruleset"),
+];
+
+/// 将别名解析为托管规则集 ID，非别名时原样返回 (视为规则集 ID)
+pub fn managed_ruleset_id(input: &str) -> &str {
+    MANAGED_RULESETS
+        .iter()
+        .find(|(alias, _, _)| alias.eq_ignore_ascii_case(input))
+        .map(|(_, id, _)| *id)
+        .unwrap_or(input)
+}
+
+/// 已知托管规则集的名称
+pub fn managed_ruleset_name(id: &str) -> Option<&'static str> {
+    MANAGED_RULESETS
+        .iter()
+        .find(|(_, known, _)| *known == id)
+        .map(|(_, _, name)| *name)
+}
+
+/// 在 execute 规则的 action_parameters 中设置 (或移除) 单条托管规则的覆盖
+///
+/// `action` 与 `enabled` 均为 None 时移除该规则的覆盖。
+pub fn set_rule_override(
+    params: &mut serde_json::Value,
+    rule_id: &str,
+    action: Option<&str>,
+    enabled: Option<bool>,
+) {
+    if !params.is_object() {
+        *params = serde_json::json!({});
+    }
+    let overrides = &mut params["overrides"];
+    if !overrides.is_object() {
+        *overrides = serde_json::json!({});
+    }
+    let rules = &mut overrides["rules"];
+    if !rules.is_array() {
+        *rules = serde_json::json!([]);
+    }
+    let Some(rules) = rules.as_array_mut() else { return };
+
+    rules.retain(|r| r.get("id").and_then(|id| id.as_str()) != Some(rule_id));
+    if action.is_some() || enabled.is_some() {
+        let mut entry = serde_json::json!({ "id": rule_id });
+        if let Some(action) = action {
+            entry["action"] = serde_json::json!(action);
+        }
+        if let Some(enabled) = enabled {
+            entry["enabled"] = serde_json::json!(enabled);
+        }
+        rules.push(entry);
+    }
+}
+
+/// 重定向规则 (Redirect Rules / 动态重定向) 所在的阶段
+pub const REDIRECT_PHASE: &str = "http_request_dynamic_redirect";
+
+/// 重定向规则支持的状态码
+pub const REDIRECT_STATUS_CODES: &[u16] = &[301, 302, 303, 307, 308];
+
+/// 将 `example.com/old*` 形式的 URL 模式转换为规则表达式 (重定向、缓存、配置规则共用)
+///
+/// 协议可省略；主机名与路径分别匹配，包含 `*` 时使用 wildcard 运算符，否则精确匹配。
+/// 以 `/` 开头时只匹配路径。
+pub fn url_pattern_expression(pattern: &str) -> Result<String> {
+    let pattern = pattern.trim();
+    let without_scheme = pattern
+        .strip_prefix("https://")
+        .or_else(|| pattern.strip_prefix("http://"))
+        .unwrap_or(pattern);
+    let (host, path) = match without_scheme.find('/') {
+        Some(i) => (&without_scheme[..i], &without_scheme[i..]),
+        None => (without_scheme, ""),
+    };
+    if host.is_empty() && path.is_empty() {
+        anyhow::bail!(ValidationError(format!("无效的 URL 模式: {}", pattern)));
+    }
+
+    let condition = |field: &str, value: &str| {
+        let op = if value.contains('*') { "wildcard" } else { "eq" };
+        format!("{} {} \"{}\"", field, op, value.replace('\\', "\\\\").replace('"', "\\\""))
+    };
+    let mut parts = Vec::new();
+    if !host.is_empty() {
+        parts.push(condition("http.host", &host.to_lowercase()));
+    }
+    if !path.is_empty() && path != "/*" {
+        parts.push(condition("http.request.uri.path", path));
+    }
+    if parts.is_empty() {
+        // "/*" 匹配所有请求
+        return Ok("true".into());
+    }
+    Ok(parts.join(" and "))
+}
+
+/// 缓存规则 (Cache Rules) 所在的阶段
+pub const CACHE_RULES_PHASE: &str = "http_request_cache_settings";
+
+/// 配置规则 (Configuration Rules) 所在的阶段
+pub const CONFIG_RULES_PHASE: &str = "http_config_settings";
+
+/// 配置规则支持的设置项 (名称, 可选值)，可选值为 None 表示 on/off 开关
+pub const CONFIG_RULE_SETTINGS: &[(&str, Option<&[&str]>)] = &[
+    ("automatic_https_rewrites", None),
+    ("bic", None),
+    ("disable_apps", None),
+    ("disable_rum", None),
+    ("disable_zaraz", None),
+    ("email_obfuscation", None),
+    ("fonts", None),
+    ("hotlink_protection", None),
+    ("mirage", None),
+    ("opportunistic_encryption", None),
+    ("polish", Some(&["off", "lossless", "lossy", "webp"])),
+    ("rocket_loader", None),
+    (
+        "security_level",
+        Some(&["off", "essentially_off", "low", "medium", "high", "under_attack"]),
+    ),
+    ("server_side_excludes", None),
+    ("ssl", Some(&["off", "flexible", "full", "strict", "origin_pull"])),
+    ("sxg", None),
+];
+
+/// 解析配置规则的 `--set key=value`，开关类设置接受 on/off/true/false
+pub fn parse_config_setting(pair: &str) -> Result<(String, serde_json::Value)> {
+    let (key, value) = pair
+        .split_once('=')
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .ok_or_else(|| ValidationError(format!("设置格式应为 key=value: {}", pair)))?;
+    let (_, allowed) = CONFIG_RULE_SETTINGS
+        .iter()
+        .find(|(name, _)| *name == key)
+        .ok_or_else(|| {
+            ValidationError(format!(
+                "不支持的配置项: {} (可选: {})",
+                key,
+                CONFIG_RULE_SETTINGS.iter().map(|(n, _)| *n).collect::<Vec<_>>().join(" / ")
+            ))
+        })?;
+
+    let value = match allowed {
+        None => match value.to_lowercase().as_str() {
+            "on" | "true" => serde_json::json!(true),
+            "off" | "false" => serde_json::json!(false),
+            _ => anyhow::bail!(ValidationError(format!("{} 的值应为 on 或 off: {}", key, value))),
+        },
+        Some(values) if values.contains(&value) => serde_json::json!(value),
+        Some(values) => anyhow::bail!(ValidationError(format!(
+            "{} 的值无效: {} (可选: {})",
+            key,
+            value,
+            values.join(" / ")
+        ))),
+    };
+    Ok((key.to_string(), value))
+}
+
+impl Ruleset {
+    /// 按 ID 查找规则
+    pub fn find_rule(&self, rule_id: &str) -> Option<&RulesetRule> {
+        self.rules.as_ref()?.iter().find(|r| r.id.as_deref() == Some(rule_id))
+    }
+}
+
+impl RulesetRule {
+    /// 以 `key=value` 形式概括 action_parameters 的顶层字段
+    pub fn parameters_summary(&self) -> String {
+        match &self.action_parameters {
+            Some(serde_json::Value::Object(map)) if !map.is_empty() => map
+                .iter()
+                .map(|(k, v)| match v {
+                    serde_json::Value::String(s) => format!("{}={}", k, s),
+                    other => format!("{}={}", k, other),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => "-".into(),
+        }
+    }
+
+    /// 静态重定向规则的目标 URL
+    pub fn redirect_target(&self) -> Option<&str> {
+        self.action_parameters
+            .as_ref()?
+            .pointer("/from_value/target_url/value")?
+            .as_str()
+    }
+
+    /// 重定向状态码
+    pub fn redirect_status(&self) -> Option<u64> {
+        self.action_parameters
+            .as_ref()?
+            .pointer("/from_value/status_code")?
+            .as_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_pattern_expression() {
+        assert_eq!(
+            url_pattern_expression("example.com/old*").unwrap(),
+            r#"http.host eq "example.com" and http.request.uri.path wildcard "/old*""#
+        );
+        assert_eq!(
+            url_pattern_expression("https://*.Example.com/").unwrap(),
+            r#"http.host wildcard "*.example.com" and http.request.uri.path eq "/""#
+        );
+        assert_eq!(url_pattern_expression("example.com/*").unwrap(), r#"http.host eq "example.com""#);
+        assert_eq!(
+            url_pattern_expression("/blog").unwrap(),
+            r#"http.request.uri.path eq "/blog""#
+        );
+        assert!(url_pattern_expression("https://").is_err());
+    }
+}
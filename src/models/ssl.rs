@@ -1,5 +1,8 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::api::error::ValidationError;
+
 /// SSL/TLS 模式
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "lowercase")]
@@ -47,6 +50,181 @@ pub struct SslCertificate {
     pub modified_on: Option<String>,
     pub expires_on: Option<String>,
     pub priority: Option<i32>,
+    /// universal / advanced / total_tls ...
+    #[serde(rename = "type")]
+    pub pack_type: Option<String>,
+    /// lets_encrypt / google / ssl_com
+    pub certificate_authority: Option<String>,
+    pub validity_days: Option<u32>,
+    /// txt / http / email
+    pub validation_method: Option<String>,
+    pub validation_records: Option<Vec<ValidationRecord>>,
+    pub validation_errors: Option<Vec<DcvError>>,
+}
+
+/// 可选的证书颁发机构 (高级证书与 Total TLS)
+pub const CERTIFICATE_AUTHORITIES: &[&str] = &["lets_encrypt", "google", "ssl_com"];
+
+/// Total TLS: 为每个已代理的主机名自动签发证书 (需要 Advanced Certificate Manager)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TotalTls {
+    pub enabled: Option<bool>,
+    pub certificate_authority: Option<String>,
+    pub validity_period: Option<u32>,
+}
+
+/// 订购高级证书包请求
+#[derive(Debug, Serialize)]
+pub struct CertificatePackOrder {
+    /// 固定为 advanced
+    #[serde(rename = "type")]
+    pub pack_type: String,
+    pub hosts: Vec<String>,
+    pub validation_method: String,
+    pub validity_days: u32,
+    pub certificate_authority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloudflare_branding: Option<bool>,
+}
+
+impl CertificatePackOrder {
+    /// 检查 CA 是否支持所选的有效期与验证方式
+    pub fn validate(&self) -> Result<()> {
+        let validity: &[u32] = match self.certificate_authority.as_str() {
+            "lets_encrypt" => &[90],
+            "google" => &[14, 30, 90],
+            "ssl_com" => &[14, 30, 90, 365],
+            ca => anyhow::bail!(ValidationError(format!(
+                "不支持的 CA: {} (可选 {})",
+                ca,
+                CERTIFICATE_AUTHORITIES.join("/")
+            ))),
+        };
+        if !validity.contains(&self.validity_days) {
+            let days: Vec<String> = validity.iter().map(u32::to_string).collect();
+            anyhow::bail!(ValidationError(format!(
+                "{} 不支持 {} 天有效期 (可选 {})",
+                self.certificate_authority,
+                self.validity_days,
+                days.join("/")
+            )));
+        }
+        if !DCV_METHODS.contains(&self.validation_method.as_str()) {
+            anyhow::bail!(ValidationError(format!("不支持的验证方式: {} (可选 {})", self.validation_method, DCV_METHODS.join("/"))));
+        }
+        if self.validation_method == "email" && self.certificate_authority != "ssl_com" {
+            anyhow::bail!(ValidationError("只有 ssl_com 支持邮件验证".into()));
+        }
+        if self.validation_method == "http" && self.hosts.iter().any(|h| h.starts_with("*.")) {
+            anyhow::bail!(ValidationError("通配符主机名不能使用 HTTP 验证，请改用 txt".into()));
+        }
+        if self.hosts.is_empty() || self.hosts.len() > 50 {
+            anyhow::bail!(ValidationError("主机名数量应为 1-50 个".into()));
+        }
+        Ok(())
+    }
+}
+
+/// 自定义 (上传) 证书
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CustomCertificate {
+    pub id: Option<String>,
+    pub hosts: Option<Vec<String>>,
+    pub issuer: Option<String>,
+    pub signature: Option<String>,
+    pub status: Option<String>,
+    pub bundle_method: Option<String>,
+    pub geo_restrictions: Option<GeoRestrictions>,
+    pub uploaded_on: Option<String>,
+    pub modified_on: Option<String>,
+    pub expires_on: Option<String>,
+    pub priority: Option<i32>,
+}
+
+/// 私钥存放区域限制
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GeoRestrictions {
+    /// us / eu / highest_security
+    pub label: String,
+}
+
+/// 上传自定义证书请求
+#[derive(Debug, Serialize)]
+pub struct UploadCustomCertRequest {
+    pub certificate: String,
+    pub private_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_restrictions: Option<GeoRestrictions>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub cert_type: Option<String>,
+}
+
+pub const BUNDLE_METHODS: &[&str] = &["ubiquitous", "optimal", "force"];
+pub const GEO_RESTRICTIONS: &[&str] = &["us", "eu", "highest_security"];
+pub const CUSTOM_CERT_TYPES: &[&str] = &["sni_custom", "legacy_custom"];
+
+/// 上传前检查 PEM 内容，返回证书链中的证书数量
+///
+/// 只做结构检查 (PEM 边界与 Base64 内容)，证书与私钥是否匹配由 Cloudflare 校验。
+pub fn validate_custom_cert_pem(certificate: &str, private_key: &str) -> Result<usize> {
+    let certs = pem_blocks(certificate)?;
+    if certs.is_empty() || certs.iter().any(|(label, _)| label != "CERTIFICATE") {
+        anyhow::bail!(ValidationError(
+            "证书文件必须只包含 PEM 格式的证书 (-----BEGIN CERTIFICATE-----)".into()
+        ));
+    }
+
+    let keys = pem_blocks(private_key)?;
+    match keys.as_slice() {
+        [(label, _)] if label == "ENCRYPTED PRIVATE KEY" => {
+            anyhow::bail!(ValidationError("私钥已加密，请先解密 (如 openssl pkey -in key.pem -out plain.pem)".into()))
+        }
+        [(label, _)] if matches!(label.as_str(), "PRIVATE KEY" | "RSA PRIVATE KEY" | "EC PRIVATE KEY") => {}
+        [] => anyhow::bail!(ValidationError("私钥文件中未找到 PEM 格式的私钥".into())),
+        [(label, _)] => anyhow::bail!(ValidationError(format!("不支持的私钥类型: {}", label))),
+        _ => anyhow::bail!(ValidationError("私钥文件只能包含一个私钥".into())),
+    }
+
+    Ok(certs.len())
+}
+
+/// 解析 PEM 块，返回 (标签, Base64 内容)
+fn pem_blocks(input: &str) -> Result<Vec<(String, String)>> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(label) = line.strip_prefix("-----BEGIN ").and_then(|l| l.strip_suffix("-----")) {
+            if current.is_some() {
+                anyhow::bail!(ValidationError(format!("PEM 块未闭合: {}", line)));
+            }
+            current = Some((label.to_string(), String::new()));
+        } else if let Some(label) = line.strip_prefix("-----END ").and_then(|l| l.strip_suffix("-----")) {
+            match current.take() {
+                Some((begin, body)) if begin == label => {
+                    if body.is_empty() {
+                        anyhow::bail!(ValidationError(format!("PEM 块内容为空: {}", label)));
+                    }
+                    blocks.push((begin, body));
+                }
+                _ => anyhow::bail!(ValidationError(format!("PEM 边界不匹配: {}", line))),
+            }
+        } else if let Some((_, body)) = current.as_mut() {
+            // 传统加密私钥的 Proc-Type / DEK-Info 头
+            if line.contains(':') {
+                anyhow::bail!(ValidationError("私钥已加密，请先解密".into()));
+            }
+            if !line.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')) {
+                anyhow::bail!(ValidationError("PEM 内容不是有效的 Base64".into()));
+            }
+            body.push_str(line);
+        }
+    }
+    if let Some((label, _)) = current {
+        anyhow::bail!(ValidationError(format!("PEM 块未闭合: {}", label)));
+    }
+    Ok(blocks)
 }
 
 /// SSL 验证记录
@@ -59,6 +237,115 @@ pub struct SslVerification {
     pub verification_info: Option<serde_json::Value>,
 }
 
+/// 自定义主机名 (SSL for SaaS)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CustomHostname {
+    pub id: Option<String>,
+    pub hostname: Option<String>,
+    /// pending / active / moved / deleted ...
+    pub status: Option<String>,
+    pub ssl: Option<CustomHostnameSsl>,
+    /// 指向非默认回源地址时的源站
+    pub custom_origin_server: Option<String>,
+    /// 主机名所有权验证 (TXT 记录)
+    pub ownership_verification: Option<OwnershipVerification>,
+    /// 主机名所有权验证 (HTTP 文件)
+    pub ownership_verification_http: Option<OwnershipVerificationHttp>,
+    pub verification_errors: Option<Vec<String>>,
+    pub created_at: Option<String>,
+}
+
+/// 自定义主机名的证书
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CustomHostnameSsl {
+    pub id: Option<String>,
+    /// initializing / pending_validation / pending_issuance / active ...
+    pub status: Option<String>,
+    /// http / txt / email
+    pub method: Option<String>,
+    #[serde(rename = "type")]
+    pub cert_type: Option<String>,
+    pub validation_records: Option<Vec<ValidationRecord>>,
+    pub validation_errors: Option<Vec<DcvError>>,
+    pub settings: Option<CustomHostnameTlsSettings>,
+    pub expires_on: Option<String>,
+}
+
+/// 证书验证记录 (按验证方式只会给出其中一组字段)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ValidationRecord {
+    pub txt_name: Option<String>,
+    pub txt_value: Option<String>,
+    pub http_url: Option<String>,
+    pub http_body: Option<String>,
+    pub emails: Option<Vec<String>>,
+    pub status: Option<String>,
+}
+
+/// 证书验证失败原因
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DcvError {
+    pub message: Option<String>,
+}
+
+/// 按主机名覆盖的 TLS 设置
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct CustomHostnameTlsSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_tls_version: Option<String>,
+    /// on / off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http2: Option<String>,
+    /// on / off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_1_3: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ciphers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OwnershipVerification {
+    #[serde(rename = "type")]
+    pub record_type: Option<String>,
+    pub name: Option<String>,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OwnershipVerificationHttp {
+    pub http_url: Option<String>,
+    pub http_body: Option<String>,
+}
+
+/// 创建自定义主机名请求
+#[derive(Debug, Serialize)]
+pub struct CustomHostnameRequest {
+    pub hostname: String,
+    pub ssl: CustomHostnameSslRequest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_origin_server: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CustomHostnameSslRequest {
+    pub method: String,
+    #[serde(rename = "type")]
+    pub cert_type: String,
+    pub settings: CustomHostnameTlsSettings,
+}
+
+/// 自定义主机名的默认回源地址
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FallbackOrigin {
+    pub origin: Option<String>,
+    /// initializing / pending_deployment / active / deployment_timed_out ...
+    pub status: Option<String>,
+    pub errors: Option<Vec<String>>,
+    pub updated_at: Option<String>,
+}
+
+pub const DCV_METHODS: &[&str] = &["http", "txt", "email"];
+
 /// 源服务器证书请求
 #[derive(Debug, Serialize)]
 pub struct OriginCertificateRequest {
@@ -109,3 +396,216 @@ impl std::fmt::Display for MinTlsVersion {
         }
     }
 }
+
+/// 可配置的加密套件 (TLS 1.2 及以下；TLS 1.3 的套件固定不可修改): (名称, 是否推荐)
+pub const CIPHER_SUITES: &[(&str, bool)] = &[
+    ("ECDHE-ECDSA-AES128-GCM-SHA256", true),
+    ("ECDHE-ECDSA-CHACHA20-POLY1305", true),
+    ("ECDHE-RSA-AES128-GCM-SHA256", true),
+    ("ECDHE-RSA-CHACHA20-POLY1305", true),
+    ("ECDHE-ECDSA-AES256-GCM-SHA384", true),
+    ("ECDHE-RSA-AES256-GCM-SHA384", true),
+    ("ECDHE-ECDSA-AES128-SHA256", false),
+    ("ECDHE-RSA-AES128-SHA256", false),
+    ("ECDHE-ECDSA-AES256-SHA384", false),
+    ("ECDHE-RSA-AES256-SHA384", false),
+    ("ECDHE-ECDSA-AES128-SHA", false),
+    ("ECDHE-RSA-AES128-SHA", false),
+    ("ECDHE-RSA-AES256-SHA", false),
+    ("AES128-GCM-SHA256", false),
+    ("AES256-GCM-SHA384", false),
+    ("AES128-SHA256", false),
+    ("AES256-SHA256", false),
+    ("AES128-SHA", false),
+    ("AES256-SHA", false),
+    ("DES-CBC3-SHA", false),
+];
+
+/// 校验加密套件名称 (不区分大小写)，`modern` 展开为推荐的套件
+pub fn parse_ciphers(names: &[String]) -> Result<Vec<String>> {
+    let mut ciphers: Vec<String> = Vec::new();
+    for name in names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+        let matched: Vec<&str> = if name.eq_ignore_ascii_case("modern") {
+            CIPHER_SUITES.iter().filter(|(_, modern)| *modern).map(|(c, _)| *c).collect()
+        } else {
+            let cipher = CIPHER_SUITES.iter().find(|(c, _)| c.eq_ignore_ascii_case(name)).ok_or_else(|| {
+                ValidationError(format!("不支持的加密套件: {} (使用 `cfai ssl ciphers list` 查看可选值)", name))
+            })?;
+            vec![cipher.0]
+        };
+        for cipher in matched {
+            if !ciphers.iter().any(|c| c == cipher) {
+                ciphers.push(cipher.to_string());
+            }
+        }
+    }
+    if ciphers.is_empty() {
+        anyhow::bail!(ValidationError("请至少指定一个加密套件".into()));
+    }
+    Ok(ciphers)
+}
+
+/// Cloudflare 通用证书 (Universal SSL) 使用的 CA: (CAA 标识, 名称)，Let's Encrypt 也常用于源站证书
+pub const CLOUDFLARE_CAS: &[(&str, &str)] = &[
+    ("letsencrypt.org", "Let's Encrypt"),
+    ("pki.goog", "Google Trust Services"),
+    ("ssl.com", "SSL.com"),
+];
+
+/// CAA 记录 (`0 issue "letsencrypt.org"`)
+#[derive(Debug, Clone, Serialize)]
+pub struct CaaRecord {
+    pub flags: u8,
+    pub tag: String,
+    pub value: String,
+}
+
+impl CaaRecord {
+    /// 解析区域文件写法的记录值，格式不正确时返回 None
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut parts = content.trim().splitn(3, char::is_whitespace);
+        let flags = parts.next()?.parse().ok()?;
+        let tag = parts.next()?.to_lowercase();
+        let value = parts.next()?.trim().trim_matches('"').to_string();
+        Some(Self { flags, tag, value })
+    }
+
+    /// issue / issuewild 允许的 CA (`;` 之前的域名)，为空表示禁止签发
+    fn issuer(&self) -> String {
+        self.value.split(';').next().unwrap_or_default().trim().to_lowercase()
+    }
+}
+
+/// 同一名称下 CAA 记录的检查结果
+#[derive(Debug, Serialize)]
+pub struct CaaCheck {
+    pub name: String,
+    pub records: Vec<CaaRecord>,
+    /// 不能签发普通证书的 Cloudflare CA
+    pub missing_issue: Vec<String>,
+    /// 不能签发通配符证书的 Cloudflare CA
+    pub missing_issuewild: Vec<String>,
+    pub problems: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+impl CaaCheck {
+    pub fn ok(&self) -> bool {
+        self.missing_issue.is_empty() && self.missing_issuewild.is_empty() && self.problems.is_empty()
+    }
+}
+
+/// 检查 CAA 记录是否允许 Cloudflare 的 CA 签发证书 (通用证书包含通配符，issuewild 也要允许)
+pub fn check_caa(name: &str, records: Vec<CaaRecord>) -> CaaCheck {
+    let mut problems = Vec::new();
+    let mut notes = Vec::new();
+    let issuers = |tag: &str| -> Option<Vec<String>> {
+        let values: Vec<String> = records.iter().filter(|r| r.tag == tag).map(CaaRecord::issuer).collect();
+        (!values.is_empty()).then_some(values)
+    };
+    let missing = |allowed: &Option<Vec<String>>| -> Vec<String> {
+        match allowed {
+            Some(allowed) => CLOUDFLARE_CAS
+                .iter()
+                .filter(|(ca, _)| !allowed.iter().any(|a| a == ca))
+                .map(|(ca, _)| ca.to_string())
+                .collect(),
+            None => Vec::new(),
+        }
+    };
+
+    let issue = issuers("issue");
+    let issuewild = issuers("issuewild");
+    for (tag, allowed) in [("issue", &issue), ("issuewild", &issuewild)] {
+        if let Some(allowed) = allowed {
+            if allowed.iter().any(String::is_empty) && allowed.iter().any(|a| !a.is_empty()) {
+                problems.push(format!("{} 同时包含禁止签发 (\";\") 与允许的 CA，含义冲突", tag));
+            }
+        }
+    }
+    for record in &records {
+        if !["issue", "issuewild", "iodef"].contains(&record.tag.as_str()) {
+            if record.flags & 128 != 0 {
+                problems.push(format!("未知的关键标签 {} (flags=128)，所有 CA 都会拒绝签发", record.tag));
+            } else {
+                notes.push(format!("未知的标签 {}，CA 会忽略", record.tag));
+            }
+        }
+    }
+    if issue.is_some() && issuewild.is_none() {
+        notes.push("未设置 issuewild，通配符证书沿用 issue 的规则".to_string());
+    }
+    if !records.iter().any(|r| r.tag == "iodef") {
+        notes.push("可添加 iodef 记录，接收 CA 的违规签发报告".to_string());
+    }
+
+    CaaCheck {
+        name: name.to_string(),
+        missing_issue: missing(&issue),
+        missing_issuewild: missing(&issuewild),
+        records,
+        problems,
+        notes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caa(content: &str) -> CaaRecord {
+        CaaRecord::parse(content).unwrap()
+    }
+
+    #[test]
+    fn test_certificate_pack_order_validation() {
+        let order = |ca: &str, days: u32, method: &str| CertificatePackOrder {
+            pack_type: "advanced".into(),
+            hosts: vec!["example.com".into(), "*.example.com".into()],
+            validation_method: method.into(),
+            validity_days: days,
+            certificate_authority: ca.into(),
+            cloudflare_branding: None,
+        };
+        assert!(order("lets_encrypt", 90, "txt").validate().is_ok());
+        assert!(order("lets_encrypt", 30, "txt").validate().is_err());
+        assert!(order("ssl_com", 365, "email").validate().is_ok());
+        assert!(order("google", 90, "email").validate().is_err());
+        assert!(order("google", 90, "http").validate().is_err());
+        assert!(order("digicert", 90, "txt").validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_ciphers() {
+        let modern = parse_ciphers(&["modern".into()]).unwrap();
+        assert_eq!(modern.len(), 6);
+        assert!(modern.iter().all(|c| c.starts_with("ECDHE-")));
+        assert_eq!(parse_ciphers(&["aes128-sha".into()]).unwrap(), ["AES128-SHA"]);
+        assert_eq!(parse_ciphers(&["modern".into(), "ECDHE-RSA-AES128-GCM-SHA256".into()]).unwrap(), modern);
+        assert!(parse_ciphers(&["RC4-SHA".into()]).is_err());
+        assert!(parse_ciphers(&[]).is_err());
+    }
+
+    #[test]
+    fn test_check_caa() {
+        assert!(CaaRecord::parse("issue letsencrypt.org").is_none());
+
+        let all: Vec<CaaRecord> = CLOUDFLARE_CAS.iter().map(|(ca, _)| caa(&format!("0 issue \"{}\"", ca))).collect();
+        assert!(check_caa("example.com", all).ok());
+
+        let check = check_caa(
+            "example.com",
+            vec![caa("0 issue \"digicert.com; cansignhttpexchanges=yes\""), caa("0 issuewild \";\"")],
+        );
+        assert_eq!(check.missing_issue.len(), CLOUDFLARE_CAS.len());
+        assert_eq!(check.missing_issuewild.len(), CLOUDFLARE_CAS.len());
+
+        let check = check_caa(
+            "example.com",
+            vec![caa("0 issue \"letsencrypt.org\""), caa("0 issue \";\""), caa("128 tbs \"x\"")],
+        );
+        assert_eq!(check.problems.len(), 2);
+        assert_eq!(check.missing_issue, ["pki.goog", "ssl.com"]);
+        assert!(check.missing_issuewild.is_empty());
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// `/user/tokens/verify` 的结果
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TokenVerification {
+    pub id: String,
+    pub status: String,
+    pub expires_on: Option<String>,
+    pub not_before: Option<String>,
+}
+
+/// API Token 详情
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: Option<String>,
+    pub status: Option<String>,
+    pub issued_on: Option<String>,
+    pub modified_on: Option<String>,
+    pub expires_on: Option<String>,
+    pub policies: Option<Vec<TokenPolicy>>,
+}
+
+impl ApiToken {
+    /// 是否在指定范围内拥有任一权限组
+    pub fn has_permission(&self, scope: PermissionScope, names: &[&str]) -> bool {
+        self.policies.iter().flatten().any(|p| {
+            p.effect == "allow"
+                && p.covers(scope)
+                && p.permission_groups
+                    .iter()
+                    .any(|g| g.name.as_deref().is_some_and(|n| names.contains(&n)))
+        })
+    }
+
+    /// 缺少所需权限的 cfai 功能
+    pub fn missing_features(&self) -> Vec<&'static FeatureRequirement> {
+        FEATURE_REQUIREMENTS
+            .iter()
+            .filter(|r| !self.has_permission(r.scope, r.permissions))
+            .collect()
+    }
+
+    /// 所有 allow 策略中的权限组名 (去重)
+    pub fn permission_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .policies
+            .iter()
+            .flatten()
+            .filter(|p| p.effect == "allow")
+            .flat_map(|p| &p.permission_groups)
+            .map(|g| g.name.clone().unwrap_or_else(|| g.id.clone()))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// Token 策略: 对一组资源 (账户 / 域名) 授予的权限组
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TokenPolicy {
+    pub id: Option<String>,
+    /// allow / deny
+    pub effect: String,
+    /// 资源标识 (如 `com.cloudflare.api.account.zone.*`) → 取值
+    #[serde(default)]
+    pub resources: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub permission_groups: Vec<PermissionGroup>,
+}
+
+impl TokenPolicy {
+    /// 策略是否作用于该范围的资源
+    ///
+    /// 账户资源的值为对象时 (`{"com.cloudflare.api.account.zone.*": "*"}`) 表示该账户下的域名。
+    fn covers(&self, scope: PermissionScope) -> bool {
+        self.resources.iter().any(|(key, value)| match scope {
+            PermissionScope::Zone => key.contains(".zone.") || value.is_object(),
+            PermissionScope::Account => {
+                key.starts_with("com.cloudflare.api.account.") && !key.contains(".zone.") && !value.is_object()
+            }
+        })
+    }
+
+    /// 可读的资源范围描述
+    pub fn scope_labels(&self) -> Vec<String> {
+        self.resources
+            .iter()
+            .map(|(key, value)| {
+                let label = resource_label(key);
+                match value.as_object() {
+                    Some(nested) => format!(
+                        "{} 下的 {}",
+                        label,
+                        nested.keys().map(|k| resource_label(k)).collect::<Vec<_>>().join(", ")
+                    ),
+                    None => label,
+                }
+            })
+            .collect()
+    }
+}
+
+fn resource_label(key: &str) -> String {
+    if let Some(zone) = key.strip_prefix("com.cloudflare.api.account.zone.") {
+        return if zone == "*" { "所有域名".into() } else { format!("域名 {}", zone) };
+    }
+    if let Some(account) = key.strip_prefix("com.cloudflare.api.account.") {
+        return if account == "*" { "所有账户".into() } else { format!("账户 {}", account) };
+    }
+    if let Some(user) = key.strip_prefix("com.cloudflare.api.user.") {
+        return format!("用户 {}", user);
+    }
+    key.to_string()
+}
+
+/// 权限作用的资源范围
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermissionScope {
+    Zone,
+    Account,
+}
+
+/// cfai 功能所需的 Token 权限 (满足其一即可)
+pub struct FeatureRequirement {
+    pub feature: &'static str,
+    pub scope: PermissionScope,
+    pub permissions: &'static [&'static str],
+}
+
+pub const FEATURE_REQUIREMENTS: &[FeatureRequirement] = &[
+    FeatureRequirement {
+        feature: "域名管理 (zone)",
+        scope: PermissionScope::Zone,
+        permissions: &["Zone Read", "Zone Write"],
+    },
+    FeatureRequirement {
+        feature: "DNS 管理 (dns)",
+        scope: PermissionScope::Zone,
+        permissions: &["DNS Write"],
+    },
+    FeatureRequirement {
+        feature: "域名设置 / SSL 模式 (zone settings, ssl)",
+        scope: PermissionScope::Zone,
+        permissions: &["Zone Settings Write"],
+    },
+    FeatureRequirement {
+        feature: "SSL 证书 (ssl certs, ssl upload-cert, ssl custom-hostnames)",
+        scope: PermissionScope::Zone,
+        permissions: &["SSL and Certificates Write"],
+    },
+    FeatureRequirement {
+        feature: "防火墙 (firewall)",
+        scope: PermissionScope::Zone,
+        permissions: &["Firewall Services Write"],
+    },
+    FeatureRequirement {
+        feature: "加速设置 (speed argo, speed tiered-cache)",
+        scope: PermissionScope::Zone,
+        permissions: &["Zone Settings Write"],
+    },
+    FeatureRequirement {
+        feature: "Zaraz (zaraz)",
+        scope: PermissionScope::Zone,
+        permissions: &["Zaraz Edit"],
+    },
+    FeatureRequirement {
+        feature: "缓存清除 (cache purge)",
+        scope: PermissionScope::Zone,
+        permissions: &["Cache Purge"],
+    },
+    FeatureRequirement {
+        feature: "页面规则 (page-rules)",
+        scope: PermissionScope::Zone,
+        permissions: &["Page Rules Write"],
+    },
+    FeatureRequirement {
+        feature: "重定向规则 (redirects)",
+        scope: PermissionScope::Zone,
+        permissions: &["Dynamic URL Redirects Write"],
+    },
+    FeatureRequirement {
+        feature: "缓存规则 (cache rules)",
+        scope: PermissionScope::Zone,
+        permissions: &["Cache Settings Write"],
+    },
+    FeatureRequirement {
+        feature: "配置规则 (config-rules)",
+        scope: PermissionScope::Zone,
+        permissions: &["Config Settings Write"],
+    },
+    FeatureRequirement {
+        feature: "流量分析 (analytics)",
+        scope: PermissionScope::Zone,
+        permissions: &["Analytics Read", "Zone Analytics Read"],
+    },
+    FeatureRequirement {
+        feature: "实时请求日志 (logs tail)",
+        scope: PermissionScope::Zone,
+        permissions: &["Logs Write"],
+    },
+    FeatureRequirement {
+        feature: "健康检查 (healthcheck)",
+        scope: PermissionScope::Zone,
+        permissions: &["Health Checks Write"],
+    },
+    FeatureRequirement {
+        feature: "Workers (workers)",
+        scope: PermissionScope::Account,
+        permissions: &["Workers Scripts Write", "Workers Scripts Read"],
+    },
+    FeatureRequirement {
+        feature: "R2 存储 (r2)",
+        scope: PermissionScope::Account,
+        permissions: &["Workers R2 Storage Write", "Workers R2 Storage Read"],
+    },
+    FeatureRequirement {
+        feature: "Queues (workers queues)",
+        scope: PermissionScope::Account,
+        permissions: &["Queues Write", "Queues Read"],
+    },
+    FeatureRequirement {
+        feature: "D1 数据库 (d1)",
+        scope: PermissionScope::Account,
+        permissions: &["D1 Write", "D1 Read"],
+    },
+    FeatureRequirement {
+        feature: "Zero Trust Access (access)",
+        scope: PermissionScope::Account,
+        permissions: &["Access: Apps and Policies Write", "Access: Apps and Policies Read"],
+    },
+    FeatureRequirement {
+        feature: "URL 扫描 (scan)",
+        scope: PermissionScope::Account,
+        permissions: &["URL Scanner Write"],
+    },
+    FeatureRequirement {
+        feature: "账户信息 (account)",
+        scope: PermissionScope::Account,
+        permissions: &["Account Settings Read", "Account Settings Write"],
+    },
+];
+
+/// 权限组
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PermissionGroup {
+    pub id: String,
+    pub name: Option<String>,
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+/// 提交 URL 扫描请求
+#[derive(Debug, Serialize)]
+pub struct UrlScanRequest {
+    pub url: String,
+    /// Public / Unlisted
+    pub visibility: String,
+}
+
+/// 已提交的扫描任务
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UrlScanSubmission {
+    pub uuid: String,
+    pub url: Option<String>,
+    pub visibility: Option<String>,
+    pub message: Option<String>,
+}
+
+/// 扫描报告 (只解析摘要需要的字段)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct UrlScanReport {
+    pub task: ScanTask,
+    pub page: ScanPage,
+    pub verdicts: ScanVerdicts,
+    pub meta: ScanMeta,
+    pub lists: ScanLists,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanTask {
+    pub uuid: Option<String>,
+    pub url: Option<String>,
+    pub time: Option<String>,
+    pub visibility: Option<String>,
+}
+
+/// 最终落地页面
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanPage {
+    pub url: Option<String>,
+    pub domain: Option<String>,
+    pub ip: Option<String>,
+    pub country: Option<String>,
+    pub asn: Option<String>,
+    pub asnname: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<serde_json::Value>,
+    pub server: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanVerdicts {
+    pub overall: ScanVerdict,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanVerdict {
+    pub malicious: Option<bool>,
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanMeta {
+    pub processors: ScanProcessors,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanProcessors {
+    /// 识别出的技术栈
+    pub wappa: ScanTechnologies,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanTechnologies {
+    pub data: Vec<ScanTechnology>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanTechnology {
+    pub app: Option<String>,
+    pub confidence_total: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanLists {
+    pub certificates: Vec<ScanCertificate>,
+    pub domains: Vec<String>,
+    pub ips: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScanCertificate {
+    pub subject_name: Option<String>,
+    pub issuer: Option<String>,
+    /// Unix 时间戳 (秒)
+    pub valid_from: Option<i64>,
+    pub valid_to: Option<i64>,
+}
@@ -47,3 +47,165 @@ pub struct WorkerDomain {
     pub service: Option<String>,
     pub environment: Option<String>,
 }
+
+/// Queues 队列
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Queue {
+    pub queue_id: Option<String>,
+    pub queue_name: String,
+    pub created_on: Option<String>,
+    pub modified_on: Option<String>,
+    pub producers_total_count: Option<u32>,
+    pub consumers_total_count: Option<u32>,
+    #[serde(default)]
+    pub producers: Vec<QueueBinding>,
+    #[serde(default)]
+    pub consumers: Vec<QueueBinding>,
+}
+
+/// 队列的生产者 / 消费者
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct QueueBinding {
+    pub script: Option<String>,
+    /// 消费者使用 script_name 字段
+    pub script_name: Option<String>,
+    #[serde(rename = "type")]
+    pub binding_type: Option<String>,
+}
+
+impl QueueBinding {
+    pub fn script(&self) -> Option<&str> {
+        self.script.as_deref().or(self.script_name.as_deref())
+    }
+}
+
+/// 创建队列请求
+#[derive(Debug, Serialize)]
+pub struct CreateQueueRequest {
+    pub queue_name: String,
+}
+
+/// Durable Object 命名空间
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DurableObjectNamespace {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub script: Option<String>,
+    pub class: Option<String>,
+    pub use_sqlite: Option<bool>,
+}
+
+/// Workers 实时日志 (tail) 会话
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WorkerTail {
+    pub id: String,
+    /// WebSocket 地址 (wss://...)
+    pub url: String,
+    pub expires_at: Option<String>,
+}
+
+/// tail 会话推送的单次调用事件
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TailEvent {
+    pub outcome: Option<String>,
+    pub script_name: Option<String>,
+    /// 毫秒时间戳
+    pub event_timestamp: Option<i64>,
+    pub event: Option<TailTrigger>,
+    #[serde(default)]
+    pub logs: Vec<TailLog>,
+    #[serde(default)]
+    pub exceptions: Vec<TailException>,
+}
+
+/// 触发调用的事件 (HTTP 请求或定时任务)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TailTrigger {
+    pub request: Option<TailRequest>,
+    pub response: Option<TailResponse>,
+    pub cron: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TailRequest {
+    pub method: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TailResponse {
+    pub status: Option<u16>,
+}
+
+/// console.log 等输出
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TailLog {
+    #[serde(default)]
+    pub message: Vec<serde_json::Value>,
+    pub level: Option<String>,
+    pub timestamp: Option<i64>,
+}
+
+/// 未捕获的异常
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TailException {
+    pub name: Option<String>,
+    pub message: Option<String>,
+    pub timestamp: Option<i64>,
+}
+
+impl TailEvent {
+    /// 调用是否失败 (异常、超出 CPU/内存限制等)
+    pub fn is_error(&self) -> bool {
+        !self.exceptions.is_empty()
+            || !matches!(self.outcome.as_deref(), Some("ok") | Some("canceled") | None)
+    }
+
+    pub fn request(&self) -> Option<&TailRequest> {
+        self.event.as_ref().and_then(|e| e.request.as_ref())
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        self.event
+            .as_ref()
+            .and_then(|e| e.response.as_ref())
+            .and_then(|r| r.status)
+    }
+}
+
+/// Workers 调用统计 (GraphQL workersInvocationsAdaptive)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WorkerStats {
+    pub script: String,
+    pub since: String,
+    pub until: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub subrequests: u64,
+    /// CPU 时间分位数 (微秒)
+    pub cpu_time_p50: Option<f64>,
+    pub cpu_time_p90: Option<f64>,
+    pub cpu_time_p99: Option<f64>,
+    /// 按小时汇总 (按时间升序)
+    pub timeseries: Vec<WorkerStatsPoint>,
+}
+
+/// 一小时内的调用统计
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WorkerStatsPoint {
+    pub time: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub subrequests: u64,
+}
+
+impl WorkerStats {
+    /// 错误率 (百分比)
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.errors as f64 / self.requests as f64 * 100.0
+    }
+}
@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+/// Zaraz 配置中的第三方工具 (由完整配置解析，只保留展示所需字段)
+#[derive(Debug, Serialize, Clone)]
+pub struct ZarazTool {
+    pub id: String,
+    pub name: String,
+    /// component (托管组件) / custom-mc (自定义组件)
+    pub tool_type: Option<String>,
+    /// 组件名 (如 google-analytics-4、facebook-pixel)
+    pub component: Option<String>,
+    pub enabled: bool,
+}
+
+impl ZarazTool {
+    /// 从 Zaraz 配置的 `tools` 字段解析工具列表 (按名称排序)
+    pub fn list(config: &serde_json::Value) -> Vec<ZarazTool> {
+        let Some(tools) = config.get("tools").and_then(|t| t.as_object()) else {
+            return Vec::new();
+        };
+        let mut list: Vec<ZarazTool> = tools
+            .iter()
+            .map(|(id, tool)| ZarazTool {
+                id: id.clone(),
+                name: tool["name"].as_str().unwrap_or(id).to_string(),
+                tool_type: tool["type"].as_str().map(String::from),
+                component: tool["component"].as_str().map(String::from),
+                enabled: tool["enabled"].as_bool().unwrap_or(false),
+            })
+            .collect();
+        list.sort_by(|a, b| a.name.cmp(&b.name));
+        list
+    }
+}
@@ -23,6 +23,7 @@ pub struct Zone {
 pub struct ZonePlan {
     pub id: Option<String>,
     pub name: Option<String>,
+    pub legacy_id: Option<String>,
     pub price: Option<f64>,
     pub currency: Option<String>,
     pub frequency: Option<String>,
@@ -68,6 +69,39 @@ pub struct ZoneSetting {
     pub modified_on: Option<String>,
 }
 
+/// Zone Hold: 阻止其他账户添加该域名 (及子域名)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ZoneHold {
+    pub hold: Option<bool>,
+    /// 临时解除后恢复保护的时间 (RFC 3339)
+    pub hold_after: Option<String>,
+    pub include_subdomains: Option<bool>,
+}
+
+/// 图片优化设置 (Polish / WebP / Mirage)，单项获取失败时为 None
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ImageSettings {
+    /// off / lossless / lossy
+    pub polish: Option<String>,
+    /// on / off (仅在 Polish 开启时生效)
+    pub webp: Option<String>,
+    /// on / off
+    pub mirage: Option<String>,
+}
+
+/// 域名健康概览 (多域名对比用)，单项获取失败时为 None
+#[derive(Debug, Serialize, Clone)]
+pub struct ZoneHealth {
+    pub zone_id: String,
+    pub zone_name: String,
+    pub status: String,
+    pub ssl_mode: Option<String>,
+    pub security_level: Option<String>,
+    /// 最近 24 小时缓存命中率 (百分比)
+    pub cache_hit_rate: Option<f64>,
+    pub dnssec: Option<String>,
+}
+
 /// Zone 列表过滤参数
 #[derive(Debug, Serialize, Default)]
 pub struct ZoneListParams {
@@ -84,3 +118,81 @@ pub struct ZoneListParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub direction: Option<String>,
 }
+
+/// 受套餐限制的资源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaResource {
+    PageRule,
+    RateLimit,
+}
+
+impl QuotaResource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            QuotaResource::PageRule => "页面规则",
+            QuotaResource::RateLimit => "速率限制规则",
+        }
+    }
+}
+
+/// 套餐配额
+#[derive(Debug, Serialize, Clone)]
+pub struct PlanLimits {
+    /// free / pro / business / enterprise
+    pub plan: String,
+    pub page_rules: u32,
+    pub custom_rules: u32,
+    pub rate_limit_rules: u32,
+    pub dns_records: u32,
+    /// 单次按 URL 清除缓存的最大 URL 数
+    pub purge_urls_per_request: u32,
+    /// 支持的缓存清除方式
+    pub purge_types: &'static [&'static str],
+}
+
+impl PlanLimits {
+    /// 根据套餐推断配额 (数值参考 Cloudflare 官方文档)
+    pub fn for_plan(plan: Option<&ZonePlan>) -> Self {
+        let id = plan
+            .and_then(|p| p.legacy_id.clone().or_else(|| p.name.clone()))
+            .unwrap_or_default()
+            .to_lowercase();
+        let plan = if id.contains("enterprise") {
+            "enterprise"
+        } else if id.contains("business") {
+            "business"
+        } else if id.contains("pro") {
+            "pro"
+        } else {
+            "free"
+        };
+
+        let (page_rules, custom_rules, rate_limit_rules, dns_records, purge_urls) = match plan {
+            "enterprise" => (125, 1000, 100, 3500, 500),
+            "business" => (50, 100, 5, 3500, 30),
+            "pro" => (20, 20, 2, 3500, 30),
+            _ => (3, 5, 1, 1000, 30),
+        };
+        let purge_types: &'static [&'static str] = match plan {
+            "enterprise" => &["everything", "url", "hostname", "tag", "prefix"],
+            _ => &["everything", "url"],
+        };
+
+        Self {
+            plan: plan.to_string(),
+            page_rules,
+            custom_rules,
+            rate_limit_rules,
+            dns_records,
+            purge_urls_per_request: purge_urls,
+            purge_types,
+        }
+    }
+
+    pub fn limit_of(&self, resource: QuotaResource) -> u32 {
+        match resource {
+            QuotaResource::PageRule => self.page_rules,
+            QuotaResource::RateLimit => self.rate_limit_rules,
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use serde::Deserialize;
+
+use crate::api::error::ValidationError;
+use crate::models::backup::{ZoneBackup, SSL_SETTING_IDS};
+use crate::models::dns::{absolute_name, structured_data, DnsRecord};
+use crate::models::firewall::{CreateIpAccessRuleRequest, IpAccessRuleConfig};
+use crate::models::zone::ZoneSetting;
+
+/// 声明式域名配置 (`cfai apply -f`)
+///
+/// 未出现的分组不做比较；出现的分组以文件为准。
+#[derive(Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ZoneSpec {
+    /// 域名 (可用 --zone 覆盖)
+    pub zone: Option<String>,
+    /// SSL/TLS 设置，键为设置项 ID (`mode` 等同于 `ssl`)
+    #[serde(default)]
+    pub ssl: BTreeMap<String, serde_json::Value>,
+    /// 其余域名设置，键为设置项 ID
+    #[serde(default)]
+    pub settings: BTreeMap<String, serde_json::Value>,
+    pub dns: Option<Vec<DnsSpec>>,
+    pub firewall: Option<FirewallSpec>,
+}
+
+/// DNS 记录，名称可写相对名称 (`www`、`@`)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DnsSpec {
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub name: String,
+    pub content: String,
+    pub ttl: Option<u32>,
+    pub proxied: Option<bool>,
+    pub priority: Option<u16>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FirewallSpec {
+    pub ip_rules: Option<Vec<IpRuleSpec>>,
+}
+
+/// IP 访问规则，未指定 target 时按 value 推断 (IP / 网段 / ASN / 国家代码)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IpRuleSpec {
+    /// block / challenge / js_challenge / managed_challenge / whitelist
+    pub mode: String,
+    pub value: String,
+    pub target: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl ZoneSpec {
+    /// 转为可与当前配置比较的快照 (页面规则与防火墙规则不参与)
+    ///
+    /// 设置项必须是域名已有的设置；值的类型按当前值调整，如 `min_tls_version: 1.2`
+    /// 转为字符串、`always_use_https: true` 转为 "on"。
+    pub fn to_backup(&self, current: &ZoneBackup) -> anyhow::Result<ZoneBackup> {
+        let existing: Vec<&ZoneSetting> = current.ssl.iter().chain(&current.settings).flatten().collect();
+        let mut ssl = Vec::new();
+        let mut settings = Vec::new();
+        for (key, value) in self.ssl.iter().chain(&self.settings) {
+            let id = if key == "mode" { "ssl" } else { key.as_str() };
+            let Some(cur) = existing.iter().find(|s| s.id == id) else {
+                anyhow::bail!(ValidationError(format!("未知的设置项: {}", key)));
+            };
+            let setting = ZoneSetting {
+                id: id.to_string(),
+                value: coerce_setting(value, &cur.value),
+                editable: None,
+                modified_on: None,
+            };
+            if SSL_SETTING_IDS.contains(&id) {
+                ssl.push(setting);
+            } else {
+                settings.push(setting);
+            }
+        }
+
+        let zone_name = &current.zone.name;
+        let dns_records = self.dns.as_ref().map(|records| {
+            records
+                .iter()
+                .map(|r| DnsRecord {
+                    id: None,
+                    record_type: r.record_type.to_uppercase(),
+                    name: absolute_name(&r.name, zone_name),
+                    content: r.content.clone(),
+                    proxied: r.proxied,
+                    proxiable: None,
+                    ttl: r.ttl,
+                    priority: r.priority,
+                    locked: None,
+                    zone_id: None,
+                    zone_name: None,
+                    created_on: None,
+                    modified_on: None,
+                    comment: r.comment.clone(),
+                    tags: None,
+                    data: structured_data(&r.record_type.to_uppercase(), &r.content, r.priority),
+                })
+                .collect()
+        });
+
+        Ok(ZoneBackup {
+            version: current.version,
+            cfai_version: current.cfai_version.clone(),
+            created_at: current.created_at.clone(),
+            zone: current.zone.clone(),
+            ssl: (!ssl.is_empty()).then_some(ssl),
+            settings: (!settings.is_empty()).then_some(settings),
+            dns_records,
+            page_rules: None,
+            firewall_rules: None,
+        })
+    }
+
+    /// 声明的 IP 访问规则 (None 表示文件未管理此分组)
+    pub fn ip_rules(&self) -> anyhow::Result<Option<Vec<CreateIpAccessRuleRequest>>> {
+        let Some(rules) = self.firewall.as_ref().and_then(|f| f.ip_rules.as_ref()) else {
+            return Ok(None);
+        };
+        rules
+            .iter()
+            .map(|rule| {
+                let target = match &rule.target {
+                    Some(target) => target.clone(),
+                    None => infer_target(&rule.value)?.to_string(),
+                };
+                Ok(CreateIpAccessRuleRequest {
+                    mode: rule.mode.clone(),
+                    configuration: IpAccessRuleConfig {
+                        target,
+                        value: rule.value.clone(),
+                    },
+                    notes: rule.notes.clone(),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(Some)
+    }
+}
+
+/// 按当前值的类型调整声明的值，避免 YAML 书写习惯导致的无意义变更
+fn coerce_setting(value: &serde_json::Value, current: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match (value, current) {
+        (Value::Bool(b), Value::String(_)) => Value::String(if *b { "on" } else { "off" }.to_string()),
+        (Value::Number(n), Value::String(_)) => Value::String(n.to_string()),
+        _ => value.clone(),
+    }
+}
+
+/// 根据值推断 IP 访问规则的 target
+fn infer_target(value: &str) -> anyhow::Result<&'static str> {
+    if value.parse::<IpAddr>().is_ok() {
+        return Ok("ip");
+    }
+    if let Some((ip, prefix)) = value.split_once('/') {
+        if ip.parse::<IpAddr>().is_ok() && prefix.parse::<u8>().is_ok() {
+            return Ok("ip_range");
+        }
+    }
+    let upper = value.to_uppercase();
+    if upper.strip_prefix("AS").is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())) {
+        return Ok("asn");
+    }
+    if value.len() == 2 && value.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Ok("country");
+    }
+    anyhow::bail!(ValidationError(format!(
+        "无法识别 IP 访问规则的值: {} (支持 IP、CIDR 网段、AS 号与两位国家代码，或显式指定 target)",
+        value
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zone_spec_normalization() {
+        assert_eq!(absolute_name("@", "example.com"), "example.com");
+        assert_eq!(absolute_name("www", "example.com"), "www.example.com");
+        assert_eq!(absolute_name("api.example.com.", "example.com"), "api.example.com");
+
+        assert_eq!(coerce_setting(&serde_json::json!(1.2), &serde_json::json!("1.0")), "1.2");
+        assert_eq!(coerce_setting(&serde_json::json!(true), &serde_json::json!("off")), "on");
+        assert_eq!(coerce_setting(&serde_json::json!(14400), &serde_json::json!(3600)), 14400);
+
+        assert_eq!(infer_target("203.0.113.7").unwrap(), "ip");
+        assert_eq!(infer_target("2001:db8::/32").unwrap(), "ip_range");
+        assert_eq!(infer_target("AS13335").unwrap(), "asn");
+        assert_eq!(infer_target("cn").unwrap(), "country");
+        assert!(infer_target("bad value").is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde_json::json;
+
+use crate::api::client::CfClient;
+use crate::api::error::ValidationError;
+use crate::cli::output;
+use crate::config::settings::{AppConfig, NotifyConfig};
+
+/// Discord 单条消息的最大长度
+const DISCORD_MAX_CHARS: usize = 2000;
+
+/// Slack 单条消息的最大长度
+const SLACK_MAX_CHARS: usize = 39000;
+
+/// 通知级别
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Success,
+    Failure,
+    /// 监控告警
+    Alert,
+}
+
+impl Level {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Alert => "alert",
+        }
+    }
+
+    fn icon(&self) -> &'static str {
+        match self {
+            Self::Success => "✅",
+            Self::Failure => "❌",
+            Self::Alert => "🚨",
+        }
+    }
+}
+
+/// 一条通知
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub level: Level,
+    pub title: String,
+    pub message: String,
+}
+
+impl Notification {
+    pub fn new(level: Level, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            title: title.into(),
+            message: message.into(),
+        }
+    }
+
+    /// 纯文本形式 (标题 + 正文)
+    fn text(&self, bold: &str) -> String {
+        format!("{} {b}{}{b}\n{}", self.level.icon(), self.title, self.message, b = bold)
+    }
+}
+
+/// 通知渠道
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Channel {
+    Slack,
+    Discord,
+    Webhook,
+    Email,
+}
+
+/// 渠道名称 (用于 `notify test --channel`)
+pub const CHANNEL_NAMES: &[&str] = &["slack", "discord", "webhook", "email"];
+
+impl Channel {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "slack" => Ok(Self::Slack),
+            "discord" => Ok(Self::Discord),
+            "webhook" => Ok(Self::Webhook),
+            "email" => Ok(Self::Email),
+            _ => anyhow::bail!(ValidationError(format!(
+                "未知的通知渠道: {} (可选: {})",
+                name,
+                CHANNEL_NAMES.join("/")
+            ))),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+            Self::Webhook => "webhook",
+            Self::Email => "email",
+        }
+    }
+}
+
+impl NotifyConfig {
+    /// 已配置的通知渠道
+    pub fn channels(&self) -> Vec<Channel> {
+        let mut channels = Vec::new();
+        if self.slack_webhook.is_some() {
+            channels.push(Channel::Slack);
+        }
+        if self.discord_webhook.is_some() {
+            channels.push(Channel::Discord);
+        }
+        if self.webhook_url.is_some() {
+            channels.push(Channel::Webhook);
+        }
+        if self.smtp_host.is_some() && self.email_to.is_some() {
+            channels.push(Channel::Email);
+        }
+        channels
+    }
+}
+
+/// 向所有已配置的渠道发送通知，返回每个渠道的结果
+pub async fn send(config: &AppConfig, notification: &Notification) -> Vec<(Channel, Result<()>)> {
+    let mut results = Vec::new();
+    for channel in config.notify.channels() {
+        results.push((channel, send_to(config, channel, notification).await));
+    }
+    results
+}
+
+/// 发送通知，失败只提示不中断 (用于命令完成与监控告警)
+pub async fn send_quietly(config: &AppConfig, notification: &Notification) {
+    for (channel, result) in send(config, notification).await {
+        if let Err(e) = result {
+            output::warn(&format!("发送 {} 通知失败: {:#}", channel.name(), e));
+        }
+    }
+}
+
+/// 向指定渠道发送通知
+pub async fn send_to(config: &AppConfig, channel: Channel, notification: &Notification) -> Result<()> {
+    let notify = &config.notify;
+    let missing = || ValidationError(format!("未配置 {} 通知渠道", channel.name()));
+    match channel {
+        Channel::Slack => {
+            let url = notify.slack_webhook.as_deref().ok_or_else(missing)?;
+            post_json(config, url, &json!({ "text": truncate(&notification.text("*"), SLACK_MAX_CHARS) })).await
+        }
+        Channel::Discord => {
+            let url = notify.discord_webhook.as_deref().ok_or_else(missing)?;
+            post_json(
+                config,
+                url,
+                &json!({ "content": truncate(&notification.text("**"), DISCORD_MAX_CHARS) }),
+            )
+            .await
+        }
+        Channel::Webhook => {
+            let url = notify.webhook_url.as_deref().ok_or_else(missing)?;
+            let body = json!({
+                "source": "cfai",
+                "level": notification.level.name(),
+                "title": notification.title,
+                "message": notification.message,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            });
+            post_json(config, url, &body).await
+        }
+        Channel::Email => send_email(notify, notification).await,
+    }
+}
+
+/// 将 Markdown / 文本推送到 Slack 或 Discord Webhook (按地址识别，Discord 使用 `content` 字段)
+pub async fn post_webhook(config: &AppConfig, url: &str, text: &str) -> Result<()> {
+    let body = if is_discord(url) {
+        json!({ "content": truncate(text, DISCORD_MAX_CHARS) })
+    } else {
+        json!({ "text": truncate(text, SLACK_MAX_CHARS) })
+    };
+    post_json(config, url, &body).await
+}
+
+async fn post_json(config: &AppConfig, url: &str, body: &serde_json::Value) -> Result<()> {
+    let client = config
+        .network
+        .apply(reqwest::Client::builder(), Duration::from_secs(30))?
+        .build()
+        .context("创建 HTTP 客户端失败")?;
+    let resp = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .context("发送 Webhook 失败")?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        anyhow::bail!("Webhook 返回错误 (HTTP {}): {}", status, text);
+    }
+    Ok(())
+}
+
+/// 通过 SMTP 发送邮件 (465 端口使用 TLS，其余端口使用 STARTTLS)
+async fn send_email(notify: &NotifyConfig, notification: &Notification) -> Result<()> {
+    let host = notify
+        .smtp_host
+        .as_deref()
+        .ok_or_else(|| ValidationError("未配置 notify.smtp_host".into()))?;
+    let to = notify
+        .email_to
+        .as_deref()
+        .ok_or_else(|| ValidationError("未配置 notify.email_to".into()))?;
+    let from = notify
+        .email_from
+        .as_deref()
+        .or(notify.smtp_username.as_deref())
+        .ok_or_else(|| ValidationError("未配置 notify.email_from".into()))?;
+    let port = notify.smtp_port.unwrap_or(587);
+
+    let mut message = Message::builder()
+        .from(from.parse().with_context(|| format!("无效的发件人地址: {}", from))?)
+        .subject(format!("[cfai] {} {}", notification.level.icon(), notification.title));
+    for addr in to.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+        message = message.to(addr.parse().with_context(|| format!("无效的收件人地址: {}", addr))?);
+    }
+    let email = message
+        .header(ContentType::TEXT_PLAIN)
+        .body(notification.message.clone())
+        .context("构造邮件失败")?;
+
+    let builder = if port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+    }
+    .with_context(|| format!("无效的 SMTP 服务器: {}", host))?
+    .port(port)
+    .timeout(Some(Duration::from_secs(30)));
+    let builder = match (&notify.smtp_username, &notify.smtp_password) {
+        (Some(user), Some(password)) => builder.credentials(Credentials::new(user.clone(), password.clone())),
+        _ => builder,
+    };
+
+    builder.build().send(email).await.context("发送邮件失败")?;
+    Ok(())
+}
+
+/// 命令执行完成后按 `notify.on` 发送通知
+///
+/// 只有发出了变更请求的命令 (或指定 `--notify`) 才会通知，只读命令不打扰。
+pub async fn command_finished(
+    config: &AppConfig,
+    client: &CfClient,
+    command_line: &str,
+    result: &Result<()>,
+    forced: bool,
+) {
+    let writes = client.writes();
+    let wanted = match config.notify.on.as_deref() {
+        Some("always") => true,
+        Some("failure") => result.is_err(),
+        _ => false,
+    };
+    if !forced && (!wanted || writes.is_empty()) {
+        return;
+    }
+
+    let (level, title) = match result {
+        Ok(()) => (Level::Success, format!("cfai {} 执行成功", command_line)),
+        Err(_) => (Level::Failure, format!("cfai {} 执行失败", command_line)),
+    };
+    let mut message = String::new();
+    if let Err(e) = result {
+        message.push_str(&format!("错误: {:#}\n", e));
+    }
+    if !writes.is_empty() {
+        message.push_str(&format!("变更请求 ({}):\n", writes.len()));
+        for write in writes.iter().take(20) {
+            message.push_str(&format!("• {}\n", write));
+        }
+        if writes.len() > 20 {
+            message.push_str(&format!("… 另有 {} 个\n", writes.len() - 20));
+        }
+    }
+    if message.is_empty() {
+        message.push_str("未发出变更请求");
+    }
+
+    if forced && config.notify.channels().is_empty() {
+        output::warn("未配置通知渠道，运行 `cfai config set notify.slack_webhook URL` 等进行配置");
+        return;
+    }
+    send_quietly(config, &Notification::new(level, title, message)).await;
+}
+
+fn is_discord(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.ends_with("discord.com") || h.ends_with("discordapp.com")))
+        .unwrap_or(false)
+}
+
+/// 按字符数截断，超出时追加省略提示
+fn truncate(text: &str, max_chars: usize) -> String {
+    const SUFFIX: &str = "\n…(内容过长，已截断)";
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let keep = max_chars - SUFFIX.chars().count();
+    let mut truncated: String = text.chars().take(keep).collect();
+    truncated.push_str(SUFFIX);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_formatting() {
+        assert!(is_discord("https://discord.com/api/webhooks/1/abc"));
+        assert!(!is_discord("https://hooks.slack.com/services/T/B/X"));
+
+        let long = "报".repeat(3000);
+        let cut = truncate(&long, DISCORD_MAX_CHARS);
+        assert_eq!(cut.chars().count(), DISCORD_MAX_CHARS);
+        assert!(cut.ends_with("已截断)"));
+        assert_eq!(truncate("ok", DISCORD_MAX_CHARS), "ok");
+    }
+}
@@ -0,0 +1,203 @@
+//! 长时间操作的进度报告: 统计完成与失败的项数并估算剩余时间，由终端进度条或 GUI 展示
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::cli::output;
+
+/// 某一时刻的进度
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProgressState {
+    /// 操作名称
+    pub label: String,
+    pub total: usize,
+    /// 已处理 (含失败) 的项数
+    pub done: usize,
+    pub failed: usize,
+    /// 正在处理的项
+    pub current: Option<String>,
+    /// 预计剩余时间 (至少完成一项后才有)
+    pub eta: Option<Duration>,
+}
+
+impl ProgressState {
+    /// 完成比例 (0.0 ~ 1.0)
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.done as f32 / self.total as f32
+    }
+}
+
+/// 进度的展示方式 (终端进度条、GUI 状态栏等)
+pub trait ProgressReporter: Send + Sync {
+    /// 开始处理 `state.current`
+    fn item_started(&self, state: &ProgressState);
+    /// 一项处理完成，失败时 `error` 为原因
+    fn item_finished(&self, state: &ProgressState, item: &str, error: Option<&str>);
+    /// 全部处理完成
+    fn finished(&self, state: &ProgressState);
+}
+
+/// 进度跟踪: 调用方逐项调用 [`Progress::start`] 与 [`Progress::record`]，结果交给 reporter 展示
+pub struct Progress {
+    state: ProgressState,
+    started: Instant,
+    reporter: Arc<dyn ProgressReporter>,
+}
+
+impl Progress {
+    pub fn new(label: impl Into<String>, total: usize, reporter: Arc<dyn ProgressReporter>) -> Self {
+        Self {
+            state: ProgressState {
+                label: label.into(),
+                total,
+                ..Default::default()
+            },
+            started: Instant::now(),
+            reporter,
+        }
+    }
+
+    /// 在终端显示进度
+    pub fn terminal(label: impl Into<String>, total: usize) -> Self {
+        let label = label.into();
+        let reporter = Arc::new(TerminalProgress::new(&label, total));
+        Self::new(label, total, reporter)
+    }
+
+    /// 开始处理一项
+    pub fn start(&mut self, item: impl Into<String>) {
+        self.state.current = Some(item.into());
+        self.reporter.item_started(&self.state);
+    }
+
+    /// 记录当前项的结果，返回是否成功
+    pub fn record<T>(&mut self, result: &anyhow::Result<T>) -> bool {
+        let error = result.as_ref().err().map(|e| format!("{:#}", e));
+        let item = self.state.current.take().unwrap_or_default();
+        self.state.done += 1;
+        if error.is_some() {
+            self.state.failed += 1;
+        }
+        self.state.eta = estimate_remaining(self.started.elapsed(), self.state.done, self.state.total);
+        self.reporter.item_finished(&self.state, &item, error.as_deref());
+        error.is_none()
+    }
+
+    /// 结束并返回最终进度
+    pub fn finish(self) -> ProgressState {
+        self.reporter.finished(&self.state);
+        self.state
+    }
+}
+
+/// 按已完成项的平均耗时估算剩余时间
+fn estimate_remaining(elapsed: Duration, done: usize, total: usize) -> Option<Duration> {
+    if done == 0 || done > total {
+        return None;
+    }
+    Some(elapsed.mul_f64((total - done) as f64 / done as f64))
+}
+
+/// 剩余时间的简短写法，如 `1m05s`、`12s`
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// 终端进度条 (indicatif)；输出不是终端时逐项打印进度行，静默模式下不显示
+pub struct TerminalProgress {
+    bar: ProgressBar,
+    /// 不能绘制进度条时改为逐行输出
+    lines: bool,
+}
+
+impl TerminalProgress {
+    pub fn new(label: &str, total: usize) -> Self {
+        let bar = if output::is_quiet() { ProgressBar::hidden() } else { ProgressBar::new(total as u64) };
+        let lines = bar.is_hidden();
+        if let Ok(style) = ProgressStyle::with_template("{prefix} [{bar:30.cyan/dim}] {pos}/{len} {msg}") {
+            bar.set_style(style.progress_chars("=> "));
+        }
+        bar.set_prefix(label.to_string());
+        Self { bar, lines }
+    }
+}
+
+impl ProgressReporter for TerminalProgress {
+    fn item_started(&self, state: &ProgressState) {
+        let item = state.current.as_deref().unwrap_or_default();
+        if self.lines {
+            output::progress(state.done + 1, state.total, item);
+            return;
+        }
+        match state.eta {
+            Some(eta) => self.bar.set_message(format!("{} (剩余 {})", item, format_eta(eta))),
+            None => self.bar.set_message(item.to_string()),
+        }
+    }
+
+    fn item_finished(&self, _state: &ProgressState, item: &str, error: Option<&str>) {
+        self.bar.inc(1);
+        match error {
+            Some(error) if self.lines => output::error(error),
+            Some(error) => self.bar.suspend(|| output::error(&format!("{}: {}", item, error))),
+            None => {}
+        }
+    }
+
+    fn finished(&self, _state: &ProgressState) {
+        self.bar.finish_and_clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl ProgressReporter for Recorder {
+        fn item_started(&self, state: &ProgressState) {
+            self.0.lock().unwrap().push(format!("start {}", state.current.as_deref().unwrap_or_default()));
+        }
+        fn item_finished(&self, state: &ProgressState, item: &str, error: Option<&str>) {
+            self.0.lock().unwrap().push(format!("{} {}/{} {:?}", item, state.done, state.total, error));
+        }
+        fn finished(&self, state: &ProgressState) {
+            self.0.lock().unwrap().push(format!("finished {}", state.failed));
+        }
+    }
+
+    #[test]
+    fn test_progress_tracking() {
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 2, 6), Some(Duration::from_secs(20)));
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 0, 6), None);
+        assert_eq!(format_eta(Duration::from_secs(65)), "1m05s");
+        assert_eq!(format_eta(Duration::from_secs(12)), "12s");
+
+        let recorder = Arc::new(Recorder::default());
+        let mut progress = Progress::new("test", 2, recorder.clone());
+        progress.start("a");
+        assert!(progress.record(&anyhow::Ok(())));
+        progress.start("b");
+        assert!(!progress.record::<()>(&Err(anyhow::anyhow!("boom"))));
+        let state = progress.finish();
+        assert_eq!((state.done, state.failed), (2, 1));
+        assert_eq!(state.fraction(), 1.0);
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            ["start a", "a 1/2 None", "start b", "b 2/2 Some(\"boom\")", "finished 1"]
+        );
+    }
+}
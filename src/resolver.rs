@@ -0,0 +1,229 @@
+//! 直接向 DNS 服务器发送查询 (UDP，响应被截断时改用 TCP)，用于检查解析生效情况
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hickory_proto::op::{Message, MessageType, OpCode, Query};
+use hickory_proto::rr::{Name, RData, Record, RecordType};
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+/// 常用公共解析器
+pub const PUBLIC_RESOLVERS: &[(&str, &str)] = &[
+    ("Cloudflare", "1.1.1.1"),
+    ("Google", "8.8.8.8"),
+    ("Quad9", "9.9.9.9"),
+];
+
+/// 单次查询的超时时间
+pub const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 一条应答记录
+#[derive(Debug, Clone, Serialize)]
+pub struct Answer {
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub name: String,
+    pub ttl: u32,
+    /// 记录值 (域名不带末尾的点，TXT 为拼接后的文本，MX 为 "优先级 主机")
+    pub value: String,
+}
+
+/// 查询结果
+#[derive(Debug, Clone, Serialize)]
+pub struct Response {
+    /// 响应码 (NOERROR / NXDOMAIN / SERVFAIL 等)
+    pub rcode: String,
+    pub answers: Vec<Answer>,
+    /// 无结果时 SOA 给出的否定缓存时间 (秒)
+    pub negative_ttl: Option<u32>,
+}
+
+impl Response {
+    fn from_message(message: &Message) -> Self {
+        Self {
+            rcode: format!("{:?}", message.response_code()).to_uppercase(),
+            answers: message.answers().iter().filter_map(answer).collect(),
+            negative_ttl: message.name_servers().iter().find_map(|r| match r.data() {
+                Some(RData::SOA(soa)) => Some(soa.minimum().min(r.ttl())),
+                _ => None,
+            }),
+        }
+    }
+
+    /// 指定类型的记录值 (排序去重，便于比较)
+    pub fn values(&self, record_type: &str) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .answers
+            .iter()
+            .filter(|a| a.record_type.eq_ignore_ascii_case(record_type))
+            .map(|a| a.value.clone())
+            .collect();
+        values.sort();
+        values.dedup();
+        values
+    }
+
+    /// 解析器缓存的剩余时间: 有结果时取最小 TTL，无结果时取否定缓存时间
+    pub fn cache_ttl(&self, record_type: &str) -> Option<u32> {
+        self.answers
+            .iter()
+            .filter(|a| a.record_type.eq_ignore_ascii_case(record_type))
+            .map(|a| a.ttl)
+            .min()
+            .or(self.negative_ttl)
+    }
+}
+
+/// 向 `server` 查询 `name` 的 `record_type` 记录
+pub async fn query(server: SocketAddr, name: &str, record_type: &str, timeout: Duration) -> Result<Response> {
+    let record_type =
+        RecordType::from_str(&record_type.to_uppercase()).map_err(|_| anyhow::anyhow!("不支持的记录类型: {}", record_type))?;
+    let name = Name::from_ascii(name).with_context(|| format!("无效的域名: {}", name))?;
+
+    let id = random_id();
+    let mut request = Message::new();
+    request
+        .set_id(id)
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Query)
+        .set_recursion_desired(true)
+        .add_query(Query::query(name, record_type));
+    let bytes = request.to_vec().context("编码 DNS 查询失败")?;
+
+    let response = tokio::time::timeout(timeout, async {
+        let response = query_udp(server, &bytes, id).await?;
+        if response.truncated() {
+            return query_tcp(server, &bytes).await;
+        }
+        Ok(response)
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("查询 {} 超时", server))??;
+
+    Ok(Response::from_message(&response))
+}
+
+async fn query_udp(server: SocketAddr, bytes: &[u8], id: u16) -> Result<Message> {
+    let local: SocketAddr = if server.is_ipv6() { "[::]:0" } else { "0.0.0.0:0" }.parse()?;
+    let socket = UdpSocket::bind(local).await.context("创建 UDP 套接字失败")?;
+    socket.connect(server).await.with_context(|| format!("连接 {} 失败", server))?;
+    socket.send(bytes).await.context("发送 DNS 查询失败")?;
+
+    let mut buf = vec![0u8; 4096];
+    loop {
+        let len = socket.recv(&mut buf).await.context("接收 DNS 响应失败")?;
+        // 忽略不属于本次查询的响应
+        match Message::from_vec(&buf[..len]) {
+            Ok(message) if message.id() == id => return Ok(message),
+            _ => continue,
+        }
+    }
+}
+
+async fn query_tcp(server: SocketAddr, bytes: &[u8]) -> Result<Message> {
+    let mut stream = TcpStream::connect(server).await.with_context(|| format!("连接 {} 失败", server))?;
+    let len = u16::try_from(bytes.len()).context("DNS 查询过长")?;
+    stream.write_all(&len.to_be_bytes()).await?;
+    stream.write_all(bytes).await?;
+
+    let mut len = [0u8; 2];
+    stream.read_exact(&mut len).await.context("接收 DNS 响应失败")?;
+    let mut buf = vec![0u8; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut buf).await.context("接收 DNS 响应失败")?;
+    Message::from_vec(&buf).context("解析 DNS 响应失败")
+}
+
+fn random_id() -> u16 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos());
+    hasher.finish() as u16
+}
+
+fn answer(record: &Record) -> Option<Answer> {
+    let trim = |name: &Name| name.to_string().trim_end_matches('.').to_string();
+    let value = match record.data()? {
+        RData::A(a) => a.0.to_string(),
+        RData::AAAA(aaaa) => aaaa.0.to_string(),
+        RData::CNAME(name) => trim(&name.0),
+        RData::NS(name) => trim(&name.0),
+        RData::PTR(name) => trim(&name.0),
+        RData::MX(mx) => format!("{} {}", mx.preference(), trim(mx.exchange())),
+        RData::TXT(txt) => txt
+            .txt_data()
+            .iter()
+            .map(|part| String::from_utf8_lossy(part))
+            .collect::<String>(),
+        other => other.to_string(),
+    };
+    Some(Answer {
+        record_type: record.record_type().to_string(),
+        name: trim(record.name()),
+        ttl: record.ttl(),
+        value,
+    })
+}
+
+/// 本机配置的 DNS 服务器 (读取 /etc/resolv.conf，其他系统返回 None)
+pub fn system_resolver() -> Option<IpAddr> {
+    let content = std::fs::read_to_string("/etc/resolv.conf").ok()?;
+    content.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some("nameserver"), Some(addr)) => addr.split('%').next()?.parse().ok(),
+            _ => None,
+        }
+    })
+}
+
+/// 将主机名或 IP 解析为 DNS 服务器地址 (端口 53)
+pub async fn server_addr(host: &str) -> Result<SocketAddr> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, 53));
+    }
+    tokio::net::lookup_host((host, 53))
+        .await
+        .with_context(|| format!("解析 {} 失败", host))?
+        .next()
+        .with_context(|| format!("{} 没有可用的地址", host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_proto::rr::rdata::{A, MX, SOA, TXT};
+
+    #[test]
+    fn test_response_from_message() {
+        let name = Name::from_ascii("example.com.").unwrap();
+        let mut message = Message::new();
+        message.add_answer(Record::from_rdata(name.clone(), 120, RData::A(A::new(192, 0, 2, 2))));
+        message.add_answer(Record::from_rdata(name.clone(), 90, RData::A(A::new(192, 0, 2, 1))));
+        message.add_answer(Record::from_rdata(
+            name.clone(),
+            300,
+            RData::MX(MX::new(10, Name::from_ascii("mx.example.com.").unwrap())),
+        ));
+        message.add_answer(Record::from_rdata(
+            name.clone(),
+            300,
+            RData::TXT(TXT::new(vec!["v=spf1 ".into(), "-all".into()])),
+        ));
+        let response = Response::from_message(&message);
+        assert_eq!(response.rcode, "NOERROR");
+        assert_eq!(response.values("a"), ["192.0.2.1", "192.0.2.2"]);
+        assert_eq!(response.values("MX"), ["10 mx.example.com"]);
+        assert_eq!(response.values("TXT"), ["v=spf1 -all"]);
+        assert_eq!(response.cache_ttl("A"), Some(90));
+
+        let mut empty = Message::new();
+        let soa = SOA::new(name.clone(), name.clone(), 1, 3600, 600, 86400, 1800);
+        empty.add_name_server(Record::from_rdata(name, 1200, RData::SOA(soa)));
+        assert_eq!(Response::from_message(&empty).cache_ttl("A"), Some(1200));
+    }
+}
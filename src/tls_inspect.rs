@@ -0,0 +1,258 @@
+//! 直接与服务器完成 TLS 握手，记录协议、加密套件与证书链 (证书无效时也完成握手以便查看)
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use tokio_rustls::rustls::client::WebPkiServerVerifier;
+use tokio_rustls::rustls::crypto::{ring, CryptoProvider};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{self, CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use tokio_rustls::TlsConnector;
+use x509_parser::extensions::GeneralName;
+
+/// 连接与握手的超时时间
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 一次握手的结果
+#[derive(Debug, Serialize)]
+pub struct TlsReport {
+    pub address: String,
+    /// 协议版本，如 TLSv1_3
+    pub protocol: String,
+    pub cipher_suite: String,
+    pub alpn: Option<String>,
+    /// 证书链是否受公共根证书信任且与主机名匹配
+    pub trusted: bool,
+    /// 证书校验失败的原因
+    pub verify_error: Option<String>,
+    /// 作为源站证书时能否通过 Full (strict) 模式校验 (公共 CA 证书，或与主机名匹配的 Origin CA 证书)
+    pub strict_ok: bool,
+    /// 服务器发送的证书链 (第一张为站点证书)
+    pub chain: Vec<CertInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CertInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    /// 距离过期的天数 (已过期为负数)
+    pub days_left: i64,
+    pub sans: Vec<String>,
+}
+
+impl CertInfo {
+    /// 是否为 Cloudflare Origin CA 签发的源站证书 (只被 Cloudflare 信任)
+    pub fn is_origin_ca(&self) -> bool {
+        self.issuer.contains("CloudFlare Origin") || self.issuer.contains("Cloudflare Origin")
+    }
+
+    /// 证书 SAN 是否覆盖主机名 (支持一级通配符)
+    pub fn matches(&self, hostname: &str) -> bool {
+        let hostname = hostname.trim_end_matches('.').to_lowercase();
+        self.sans.iter().any(|san| {
+            let san = san.to_lowercase();
+            match san.strip_prefix("*.") {
+                Some(parent) => hostname
+                    .split_once('.')
+                    .is_some_and(|(label, rest)| !label.is_empty() && rest == parent),
+                None => san == hostname,
+            }
+        })
+    }
+}
+
+/// 建立 TCP 连接 (与握手分开，便于区分端口不通和握手失败)
+pub async fn connect(addr: SocketAddr) -> Result<TcpStream> {
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, TcpStream::connect(addr))
+        .await
+        .map_err(|_| anyhow::anyhow!("连接 {} 超时", addr))?
+        .with_context(|| format!("连接 {} 失败", addr))
+}
+
+/// 在已建立的连接上以 `hostname` 作为 SNI 完成握手
+pub async fn handshake(tcp: TcpStream, hostname: &str) -> Result<TlsReport> {
+    let addr = tcp.peer_addr().context("读取对端地址失败")?;
+    let provider = Arc::new(ring::default_provider());
+    let roots = RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let verifier = Arc::new(RecordingVerifier {
+        inner: WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+            .build()
+            .context("初始化证书校验失败")?,
+        provider: provider.clone(),
+        result: Mutex::default(),
+    });
+    let mut config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("初始化 TLS 配置失败")?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    let server_name = ServerName::try_from(hostname.to_string()).with_context(|| format!("无效的主机名: {}", hostname))?;
+    let stream = tokio::time::timeout(HANDSHAKE_TIMEOUT, TlsConnector::from(Arc::new(config)).connect(server_name, tcp))
+        .await
+        .map_err(|_| anyhow::anyhow!("与 {} 的 TLS 握手超时", addr))?
+        .with_context(|| format!("与 {} 的 TLS 握手失败", addr))?;
+
+    let (_, conn) = stream.get_ref();
+    let chain: Vec<CertInfo> = conn
+        .peer_certificates()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|der| cert_info(der))
+        .collect();
+    let error: Option<rustls::Error> = verifier.result.lock().unwrap().clone().flatten();
+    // Origin CA 不在公共根证书中，但 Cloudflare 回源时信任它
+    let strict_ok = match &error {
+        None => true,
+        Some(rustls::Error::InvalidCertificate(CertificateError::UnknownIssuer)) => chain
+            .first()
+            .is_some_and(|leaf: &CertInfo| leaf.is_origin_ca() && leaf.matches(hostname) && leaf.days_left >= 0),
+        Some(_) => false,
+    };
+    Ok(TlsReport {
+        address: addr.to_string(),
+        protocol: conn.protocol_version().map(|v| format!("{:?}", v)).unwrap_or_default(),
+        cipher_suite: conn
+            .negotiated_cipher_suite()
+            .map(|s| format!("{:?}", s.suite()))
+            .unwrap_or_default(),
+        alpn: conn.alpn_protocol().map(|p| String::from_utf8_lossy(p).into_owned()),
+        trusted: error.is_none(),
+        verify_error: error.as_ref().map(describe),
+        strict_ok,
+        chain,
+    })
+}
+
+fn cert_info(der: &CertificateDer) -> Option<CertInfo> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der.as_ref()).ok()?;
+    let time = |t: x509_parser::time::ASN1Time| {
+        chrono::DateTime::from_timestamp(t.timestamp(), 0).unwrap_or_default()
+    };
+    let not_after = time(cert.validity().not_after);
+    let sans = cert
+        .subject_alternative_name()
+        .ok()
+        .flatten()
+        .map(|ext| {
+            ext.value
+                .general_names
+                .iter()
+                .filter_map(|name| match name {
+                    GeneralName::DNSName(dns) => Some(dns.to_string()),
+                    GeneralName::IPAddress(ip) => match ip.len() {
+                        4 => Some(std::net::Ipv4Addr::from(<[u8; 4]>::try_from(*ip).ok()?).to_string()),
+                        16 => Some(std::net::Ipv6Addr::from(<[u8; 16]>::try_from(*ip).ok()?).to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(CertInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        not_before: time(cert.validity().not_before).to_rfc3339(),
+        not_after: not_after.to_rfc3339(),
+        days_left: (not_after - chrono::Utc::now()).num_days(),
+        sans,
+    })
+}
+
+/// 证书校验失败的中文说明
+fn describe(err: &rustls::Error) -> String {
+    match err {
+        rustls::Error::InvalidCertificate(cert) => match cert {
+            CertificateError::Expired | CertificateError::ExpiredContext { .. } => "证书已过期".to_string(),
+            CertificateError::NotValidYet | CertificateError::NotValidYetContext { .. } => "证书尚未生效".to_string(),
+            CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. } => {
+                "证书不包含此主机名".to_string()
+            }
+            CertificateError::UnknownIssuer => "证书链不受信任 (自签名、私有 CA 或缺少中间证书)".to_string(),
+            CertificateError::Revoked => "证书已被吊销".to_string(),
+            other => format!("证书无效: {:?}", other),
+        },
+        other => other.to_string(),
+    }
+}
+
+/// 按正常规则校验证书并记录结果，但总是允许握手继续
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    provider: Arc<CryptoProvider>,
+    /// Some(None) 表示校验通过
+    result: Mutex<Option<Option<rustls::Error>>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .err();
+        *self.result.lock().unwrap() = Some(result);
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cert_matches_hostname() {
+        let cert = CertInfo {
+            subject: "CN=example.com".into(),
+            issuer: "CN=CloudFlare Origin SSL Certificate Authority".into(),
+            not_before: String::new(),
+            not_after: String::new(),
+            days_left: 300,
+            sans: vec!["example.com".into(), "*.example.com".into()],
+        };
+        assert!(cert.is_origin_ca());
+        assert!(cert.matches("example.com"));
+        assert!(cert.matches("WWW.example.com."));
+        assert!(!cert.matches("a.b.example.com"));
+        assert!(!cert.matches("example.org"));
+    }
+}
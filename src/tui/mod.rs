@@ -0,0 +1,285 @@
+mod pages;
+mod state;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use tokio::sync::mpsc;
+
+use crate::api::client::CfClient;
+use crate::models::firewall::FirewallEventQuery;
+use state::*;
+
+/// 流量分析页面的自动刷新间隔
+const ANALYTICS_REFRESH: Duration = Duration::from_secs(60);
+
+/// 启动全屏终端界面
+pub async fn run(client: &CfClient) -> Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, client).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(terminal: &mut ratatui::DefaultTerminal, client: &CfClient) -> Result<()> {
+    let (key_tx, mut keys) = mpsc::unbounded_channel();
+    let (result_tx, mut results) = mpsc::unbounded_channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let input = spawn_input_reader(key_tx, stop.clone());
+
+    let mut app = TuiApp {
+        state: TuiState::new(),
+        client: client.clone(),
+        tx: result_tx,
+    };
+    app.load_zones();
+
+    let mut tick = tokio::time::interval(Duration::from_secs(1));
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| pages::render(frame, &mut app.state)) {
+            break Err(e.into());
+        }
+        tokio::select! {
+            key = keys.recv() => match key {
+                Some(key) => app.handle_key(key),
+                None => break Ok(()),
+            },
+            Some(result) = results.recv() => app.handle_result(result),
+            _ = tick.tick() => app.auto_refresh(),
+        }
+        if app.state.quit {
+            break Ok(());
+        }
+    };
+
+    stop.store(true, Ordering::SeqCst);
+    let _ = input.join();
+    result
+}
+
+/// 在独立线程中读取按键 (crossterm 的读取是阻塞的)
+fn spawn_input_reader(tx: mpsc::UnboundedSender<KeyEvent>, stop: Arc<AtomicBool>) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        while !stop.load(Ordering::SeqCst) {
+            match event::poll(Duration::from_millis(100)) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(_) => return,
+            }
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    if tx.send(key).is_err() {
+                        return;
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => return,
+            }
+        }
+    })
+}
+
+struct TuiApp {
+    state: TuiState,
+    client: CfClient,
+    tx: mpsc::UnboundedSender<TuiResult>,
+}
+
+impl TuiApp {
+    fn handle_key(&mut self, key: KeyEvent) {
+        let state = &mut self.state;
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            state.quit = true;
+            return;
+        }
+
+        if state.filter_editing {
+            match key.code {
+                KeyCode::Enter => state.filter_editing = false,
+                KeyCode::Esc => {
+                    state.filter_editing = false;
+                    state.dns_filter.clear();
+                }
+                KeyCode::Backspace => {
+                    state.dns_filter.pop();
+                }
+                KeyCode::Char(c) => state.dns_filter.push(c),
+                _ => {}
+            }
+            state.dns_table.select(Some(0));
+            return;
+        }
+
+        state.status = None;
+        match key.code {
+            KeyCode::Char('q') => state.quit = true,
+            KeyCode::Tab => self.switch_page(1),
+            KeyCode::BackTab => self.switch_page(PAGES.len() - 1),
+            KeyCode::Char(c @ '1'..='9') => {
+                if let Some((page, _)) = PAGES.get(c as usize - '1' as usize) {
+                    self.open_page(*page);
+                }
+            }
+            KeyCode::Char('r') => self.refresh(),
+            KeyCode::Char('/') if state.page == Page::Dns => state.filter_editing = true,
+            KeyCode::Up | KeyCode::Char('k') => move_selection(state, -1),
+            KeyCode::Down | KeyCode::Char('j') => move_selection(state, 1),
+            KeyCode::PageUp => move_selection(state, -10),
+            KeyCode::PageDown => move_selection(state, 10),
+            KeyCode::Enter if state.page == Page::Zones => {
+                let zone = state.zone_table.selected().and_then(|i| state.zones.get(i)).cloned();
+                if let Some(zone) = zone {
+                    state.info(format!("已选择 {}", zone.name));
+                    state.selected_zone = Some(zone);
+                    state.dns_records.clear();
+                    state.firewall = None;
+                    state.analytics = None;
+                    self.open_page(Page::Dns);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn switch_page(&mut self, offset: usize) {
+        let current = PAGES.iter().position(|(p, _)| *p == self.state.page).unwrap_or(0);
+        let (page, _) = PAGES[(current + offset) % PAGES.len()];
+        self.open_page(page);
+    }
+
+    /// 切换页面，数据未加载时加载
+    fn open_page(&mut self, page: Page) {
+        self.state.page = page;
+        let loaded = match page {
+            Page::Zones => !self.state.zones.is_empty(),
+            Page::Dns => !self.state.dns_records.is_empty(),
+            Page::Firewall => self.state.firewall.is_some(),
+            Page::Analytics => self.state.analytics.is_some(),
+        };
+        if !loaded {
+            self.refresh();
+        }
+    }
+
+    fn refresh(&mut self) {
+        if self.state.page == Page::Zones {
+            self.load_zones();
+            return;
+        }
+        let Some(zone_id) = self.state.zone_id() else {
+            return;
+        };
+        self.state.pending += 1;
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        match self.state.page {
+            Page::Zones => unreachable!(),
+            Page::Dns => {
+                tokio::spawn(async move {
+                    let result = client.list_all_dns_records(&zone_id).await;
+                    let _ = tx.send(TuiResult::Dns(zone_id, result));
+                });
+            }
+            Page::Firewall => {
+                tokio::spawn(async move {
+                    let result = load_firewall(&client, &zone_id).await;
+                    let _ = tx.send(TuiResult::Firewall(zone_id, result));
+                });
+            }
+            Page::Analytics => {
+                self.state.analytics_loaded_at = Some(Instant::now());
+                tokio::spawn(async move {
+                    let result = load_analytics(&client, &zone_id).await;
+                    let _ = tx.send(TuiResult::Analytics(zone_id, result));
+                });
+            }
+        }
+    }
+
+    fn load_zones(&mut self) {
+        self.state.pending += 1;
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let _ = tx.send(TuiResult::Zones(client.list_all_zones().await));
+        });
+    }
+
+    /// 流量分析页面定时刷新
+    fn auto_refresh(&mut self) {
+        let due = self
+            .state
+            .analytics_loaded_at
+            .is_some_and(|t| t.elapsed() >= ANALYTICS_REFRESH);
+        if self.state.page == Page::Analytics && due && self.state.pending == 0 {
+            self.refresh();
+        }
+    }
+
+    fn handle_result(&mut self, result: TuiResult) {
+        let state = &mut self.state;
+        state.pending = state.pending.saturating_sub(1);
+        let current = state.zone_id();
+        match result {
+            TuiResult::Zones(Ok(zones)) => {
+                if state.selected_zone.is_none() && zones.len() == 1 {
+                    state.selected_zone = zones.first().cloned();
+                }
+                state.zones = zones;
+            }
+            // 结果属于切换前的域名
+            TuiResult::Dns(zone_id, _) | TuiResult::Firewall(zone_id, _) | TuiResult::Analytics(zone_id, _)
+                if Some(&zone_id) != current.as_ref() => {}
+            TuiResult::Dns(_, Ok(records)) => state.dns_records = records,
+            TuiResult::Firewall(_, Ok(firewall)) => state.firewall = Some(firewall),
+            TuiResult::Analytics(_, Ok(analytics)) => state.analytics = Some(analytics),
+            TuiResult::Zones(Err(e))
+            | TuiResult::Dns(_, Err(e))
+            | TuiResult::Firewall(_, Err(e))
+            | TuiResult::Analytics(_, Err(e)) => state.error(format!("{:#}", e)),
+        }
+    }
+}
+
+fn move_selection(state: &mut TuiState, delta: isize) {
+    let Some((len, table)) = state.current_table() else {
+        return;
+    };
+    if len == 0 {
+        return;
+    }
+    let current = table.selected().unwrap_or(0) as isize;
+    table.select(Some((current + delta).clamp(0, len as isize - 1) as usize));
+}
+
+async fn load_firewall(client: &CfClient, zone_id: &str) -> Result<FirewallData> {
+    let now = chrono::Utc::now();
+    let query = FirewallEventQuery {
+        since: now - chrono::Duration::hours(24),
+        until: now,
+        action: None,
+        client_ip: None,
+        limit: 100,
+    };
+    let (security_level, ip_rules, events) = tokio::try_join!(
+        client.get_security_level(zone_id),
+        client.list_ip_access_rules(zone_id),
+        client.list_firewall_events(zone_id, &query),
+    )?;
+    Ok(FirewallData {
+        security_level,
+        ip_rules,
+        events,
+    })
+}
+
+async fn load_analytics(client: &CfClient, zone_id: &str) -> Result<Box<AnalyticsData>> {
+    let (live, dashboard) = tokio::try_join!(
+        client.get_live_analytics(zone_id, 60),
+        client.get_analytics_24h(zone_id),
+    )?;
+    Ok(Box::new(AnalyticsData { live, dashboard }))
+}
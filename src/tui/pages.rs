@@ -0,0 +1,315 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table, Tabs};
+use ratatui::Frame;
+
+use super::state::{Page, TuiState, PAGES};
+use crate::cli::output::{format_bytes, format_number};
+
+const ACCENT: Color = Color::Rgb(243, 128, 32);
+
+pub fn render(frame: &mut Frame, state: &mut TuiState) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    render_header(frame, state, header);
+    match state.page {
+        Page::Zones => render_zones(frame, state, body),
+        Page::Dns => render_dns(frame, state, body),
+        Page::Firewall => render_firewall(frame, state, body),
+        Page::Analytics => render_analytics(frame, state, body),
+    }
+    render_footer(frame, state, footer);
+}
+
+fn render_header(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let titles = PAGES.iter().enumerate().map(|(i, (_, title))| format!("{} {}", i + 1, title));
+    let selected = PAGES.iter().position(|(page, _)| *page == state.page).unwrap_or(0);
+    let zone = state
+        .selected_zone
+        .as_ref()
+        .map(|z| z.name.as_str())
+        .unwrap_or("(未选择域名)");
+    let tabs = Tabs::new(titles)
+        .select(selected)
+        .highlight_style(Style::new().fg(ACCENT).add_modifier(Modifier::BOLD))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" CFAI ".fg(ACCENT).bold())
+                .title(Line::from(format!(" {} ", zone)).right_aligned()),
+        );
+    frame.render_widget(tabs, area);
+}
+
+fn render_footer(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let mut spans = Vec::new();
+    if state.pending > 0 {
+        spans.push(Span::styled("加载中... ", Style::new().fg(Color::Yellow)));
+    }
+    match &state.status {
+        Some((message, true)) => spans.push(Span::styled(message.clone(), Style::new().fg(Color::Red))),
+        Some((message, false)) => spans.push(Span::raw(message.clone())),
+        None => {
+            let hints = match state.page {
+                Page::Zones => "↑↓ 选择  Enter 打开  ",
+                Page::Dns if state.filter_editing => "输入关键字  Enter 确定  Esc 清除  ",
+                Page::Dns => "↑↓ 选择  / 搜索  ",
+                _ => "",
+            };
+            spans.push(Span::styled(
+                format!("{}Tab/1-4 切换页面  r 刷新  q 退出", hints),
+                Style::new().fg(Color::DarkGray),
+            ));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn table_block(title: String) -> Block<'static> {
+    Block::default().borders(Borders::ALL).title(title)
+}
+
+fn highlight() -> Style {
+    Style::new().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+}
+
+fn render_zones(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    let selected_id = state.zone_id();
+    let rows = state.zones.iter().map(|zone| {
+        let marker = if Some(&zone.id) == selected_id.as_ref() { "●" } else { " " };
+        let status = match zone.status.as_str() {
+            "active" => zone.status.clone().green(),
+            _ => zone.status.clone().yellow(),
+        };
+        Row::new(vec![
+            Cell::from(marker).fg(ACCENT),
+            Cell::from(zone.name.clone()),
+            Cell::from(status),
+            Cell::from(zone.plan.as_ref().and_then(|p| p.name.clone()).unwrap_or_default()),
+            Cell::from(if zone.paused.unwrap_or(false) { "已暂停" } else { "" }),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Percentage(45),
+            Constraint::Length(12),
+            Constraint::Percentage(30),
+            Constraint::Length(8),
+        ],
+    )
+    .header(Row::new(vec!["", "域名", "状态", "套餐", ""]).bold())
+    .block(table_block(format!(" 域名 ({}) ", state.zones.len())))
+    .row_highlight_style(highlight());
+    frame.render_stateful_widget(table, area, &mut state.zone_table);
+}
+
+fn render_dns(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    if state.selected_zone.is_none() {
+        frame.render_widget(Paragraph::new("请先在域名页面选择域名").block(table_block(" DNS ".into())), area);
+        return;
+    }
+    let records = state.filtered_dns();
+    let title = if state.dns_filter.is_empty() && !state.filter_editing {
+        format!(" DNS 记录 ({}) ", records.len())
+    } else {
+        format!(" DNS 记录 ({}/{})  搜索: {}{} ", records.len(), state.dns_records.len(), state.dns_filter, if state.filter_editing { "▏" } else { "" })
+    };
+    let rows: Vec<Row> = records
+        .iter()
+        .map(|r| {
+            let proxy = if r.proxied.unwrap_or(false) { "已代理".fg(ACCENT) } else { "仅 DNS".dark_gray() };
+            let ttl = match r.ttl {
+                Some(1) | None => "自动".to_string(),
+                Some(ttl) => ttl.to_string(),
+            };
+            Row::new(vec![
+                Cell::from(r.record_type.clone()).fg(ACCENT).bold(),
+                Cell::from(r.name.clone()),
+                Cell::from(r.content.clone()),
+                Cell::from(proxy),
+                Cell::from(ttl),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Percentage(35),
+            Constraint::Percentage(45),
+            Constraint::Length(8),
+            Constraint::Length(6),
+        ],
+    )
+    .header(Row::new(vec!["类型", "名称", "内容", "代理", "TTL"]).bold())
+    .block(table_block(title))
+    .row_highlight_style(highlight());
+    frame.render_stateful_widget(table, area, &mut state.dns_table);
+}
+
+fn render_firewall(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    let Some(firewall) = &state.firewall else {
+        let text = if state.selected_zone.is_none() { "请先在域名页面选择域名" } else { "" };
+        frame.render_widget(Paragraph::new(text).block(table_block(" 防火墙 ".into())), area);
+        return;
+    };
+    let [level, rules, events] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Percentage(45),
+        Constraint::Min(0),
+    ])
+    .areas(area);
+
+    let level_text = Line::from(vec![
+        Span::raw("安全级别: "),
+        Span::styled(firewall.security_level.clone(), Style::new().fg(ACCENT).bold()),
+    ]);
+    frame.render_widget(Paragraph::new(level_text).block(Block::default().borders(Borders::ALL)), level);
+
+    let rule_rows: Vec<Row> = firewall
+        .ip_rules
+        .iter()
+        .map(|rule| {
+            let mode = rule.mode.clone().unwrap_or_default();
+            let mode = match mode.as_str() {
+                "block" => mode.red(),
+                "whitelist" => mode.green(),
+                _ => mode.yellow(),
+            };
+            let config = rule.configuration.as_ref();
+            Row::new(vec![
+                Cell::from(mode),
+                Cell::from(config.and_then(|c| c.value.clone()).unwrap_or_default()),
+                Cell::from(config.and_then(|c| c.target.clone()).unwrap_or_default()),
+                Cell::from(rule.notes.clone().unwrap_or_default()),
+            ])
+        })
+        .collect();
+    let rule_table = Table::new(
+        rule_rows,
+        [
+            Constraint::Length(18),
+            Constraint::Percentage(30),
+            Constraint::Length(10),
+            Constraint::Percentage(50),
+        ],
+    )
+    .header(Row::new(vec!["模式", "值", "类型", "备注"]).bold())
+    .block(table_block(format!(" IP 访问规则 ({}) ", firewall.ip_rules.len())))
+    .row_highlight_style(highlight());
+
+    let event_rows: Vec<Row> = firewall
+        .events
+        .iter()
+        .map(|e| {
+            let time = e
+                .datetime
+                .as_deref()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&chrono::Local).format("%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            Row::new(vec![
+                Cell::from(time).dark_gray(),
+                Cell::from(e.action.clone().unwrap_or_default()).red(),
+                Cell::from(e.client_ip.clone().unwrap_or_default()),
+                Cell::from(e.client_country.clone().unwrap_or_default()),
+                Cell::from(format!(
+                    "{} {}{}",
+                    e.method.as_deref().unwrap_or(""),
+                    e.host.as_deref().unwrap_or(""),
+                    e.path.as_deref().unwrap_or("")
+                )),
+            ])
+        })
+        .collect();
+    let event_table = Table::new(
+        event_rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(18),
+            Constraint::Length(16),
+            Constraint::Length(4),
+            Constraint::Min(0),
+        ],
+    )
+    .header(Row::new(vec!["时间", "动作", "IP", "国家", "请求"]).bold())
+    .block(table_block(format!(" 最近 24 小时防火墙事件 ({}) ", firewall.events.len())));
+
+    frame.render_stateful_widget(rule_table, rules, &mut state.firewall_table);
+    frame.render_widget(event_table, events);
+}
+
+fn render_analytics(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let Some(analytics) = &state.analytics else {
+        let text = if state.selected_zone.is_none() { "请先在域名页面选择域名" } else { "" };
+        frame.render_widget(Paragraph::new(text).block(table_block(" 流量分析 ".into())), area);
+        return;
+    };
+    let [totals, live, requests, threats] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Percentage(34),
+        Constraint::Percentage(33),
+        Constraint::Min(0),
+    ])
+    .areas(area);
+
+    let t = analytics.dashboard.totals.as_ref();
+    let requests_all = t.and_then(|t| t.requests.as_ref()).and_then(|r| r.all).unwrap_or(0);
+    let requests_cached = t.and_then(|t| t.requests.as_ref()).and_then(|r| r.cached).unwrap_or(0);
+    let bandwidth = t.and_then(|t| t.bandwidth.as_ref()).and_then(|b| b.all).unwrap_or(0);
+    let threat_count = t.and_then(|t| t.threats.as_ref()).and_then(|t| t.all).unwrap_or(0);
+    let cache_rate = requests_cached as f64 / requests_all.max(1) as f64 * 100.0;
+    let summary = vec![
+        Line::from(vec![
+            Span::raw("请求 "),
+            Span::styled(format_number(requests_all), Style::new().bold()),
+            Span::raw("   缓存命中率 "),
+            Span::styled(format!("{:.1}%", cache_rate), Style::new().bold()),
+            Span::raw("   带宽 "),
+            Span::styled(format_bytes(bandwidth), Style::new().bold()),
+            Span::raw("   威胁 "),
+            Span::styled(format_number(threat_count), Style::new().fg(Color::Red).bold()),
+        ]),
+        Line::from(format!(
+            "最近一小时 {} 次请求，{:.1} 次/秒",
+            format_number(analytics.live.requests),
+            analytics.live.requests_per_second()
+        ))
+        .dark_gray(),
+    ];
+    frame.render_widget(
+        Paragraph::new(summary).block(Block::default().borders(Borders::ALL).title(" 最近 24 小时 ")),
+        totals,
+    );
+
+    let sparkline = |title: String, data: Vec<u64>, color: Color| {
+        Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .data(data)
+            .style(Style::new().fg(color))
+    };
+    frame.render_widget(
+        sparkline(" 最近一小时 · 每分钟请求数 ".into(), analytics.live.per_minute.clone(), ACCENT),
+        live,
+    );
+
+    let series = analytics.dashboard.timeseries.as_deref().unwrap_or_default();
+    let hourly_requests: Vec<u64> = series
+        .iter()
+        .map(|s| s.requests.as_ref().and_then(|r| r.all).unwrap_or(0))
+        .collect();
+    let hourly_threats: Vec<u64> = series
+        .iter()
+        .map(|s| s.threats.as_ref().and_then(|t| t.all).unwrap_or(0))
+        .collect();
+    frame.render_widget(sparkline(" 24 小时 · 请求数 ".into(), hourly_requests, Color::Cyan), requests);
+    frame.render_widget(sparkline(" 24 小时 · 威胁数 ".into(), hourly_threats, Color::Red), threats);
+}
@@ -0,0 +1,135 @@
+use ratatui::widgets::TableState;
+
+use crate::models::analytics::{AnalyticsDashboard, LiveAnalytics};
+use crate::models::dns::DnsRecord;
+use crate::models::firewall::{FirewallEvent, IpAccessRule};
+use crate::models::zone::Zone;
+
+/// 页面 (与 GUI 的页面结构一致)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Page {
+    Zones,
+    Dns,
+    Firewall,
+    Analytics,
+}
+
+/// 页面顺序与标题
+pub const PAGES: &[(Page, &str)] = &[
+    (Page::Zones, "域名"),
+    (Page::Dns, "DNS"),
+    (Page::Firewall, "防火墙"),
+    (Page::Analytics, "流量分析"),
+];
+
+/// 后台任务的结果，带 Zone ID 的结果在切换域名后丢弃
+pub enum TuiResult {
+    Zones(anyhow::Result<Vec<Zone>>),
+    Dns(String, anyhow::Result<Vec<DnsRecord>>),
+    Firewall(String, anyhow::Result<FirewallData>),
+    Analytics(String, anyhow::Result<Box<AnalyticsData>>),
+}
+
+pub struct FirewallData {
+    pub security_level: String,
+    pub ip_rules: Vec<IpAccessRule>,
+    pub events: Vec<FirewallEvent>,
+}
+
+pub struct AnalyticsData {
+    /// 最近一小时，每分钟请求数
+    pub live: LiveAnalytics,
+    /// 最近 24 小时
+    pub dashboard: AnalyticsDashboard,
+}
+
+/// TUI 状态
+pub struct TuiState {
+    pub page: Page,
+    pub zones: Vec<Zone>,
+    pub zone_table: TableState,
+    pub selected_zone: Option<Zone>,
+
+    pub dns_records: Vec<DnsRecord>,
+    pub dns_table: TableState,
+    /// DNS 搜索关键字 (名称或内容)
+    pub dns_filter: String,
+    /// 正在输入搜索关键字
+    pub filter_editing: bool,
+
+    pub firewall: Option<FirewallData>,
+    pub firewall_table: TableState,
+
+    pub analytics: Option<Box<AnalyticsData>>,
+    pub analytics_loaded_at: Option<std::time::Instant>,
+
+    /// 进行中的请求数
+    pub pending: usize,
+    /// 底部状态栏消息 (错误或提示)
+    pub status: Option<(String, bool)>,
+    pub quit: bool,
+}
+
+impl TuiState {
+    pub fn new() -> Self {
+        Self {
+            page: Page::Zones,
+            zones: Vec::new(),
+            zone_table: TableState::default().with_selected(Some(0)),
+            selected_zone: None,
+            dns_records: Vec::new(),
+            dns_table: TableState::default().with_selected(Some(0)),
+            dns_filter: String::new(),
+            filter_editing: false,
+            firewall: None,
+            firewall_table: TableState::default().with_selected(Some(0)),
+            analytics: None,
+            analytics_loaded_at: None,
+            pending: 0,
+            status: None,
+            quit: false,
+        }
+    }
+
+    pub fn zone_id(&self) -> Option<String> {
+        self.selected_zone.as_ref().map(|z| z.id.clone())
+    }
+
+    /// 按搜索关键字过滤后的 DNS 记录
+    pub fn filtered_dns(&self) -> Vec<&DnsRecord> {
+        let filter = self.dns_filter.to_lowercase();
+        self.dns_records
+            .iter()
+            .filter(|r| {
+                filter.is_empty()
+                    || r.name.to_lowercase().contains(&filter)
+                    || r.content.to_lowercase().contains(&filter)
+                    || r.record_type.eq_ignore_ascii_case(&filter)
+            })
+            .collect()
+    }
+
+    /// 当前页面表格的行数与选中状态
+    pub fn current_table(&mut self) -> Option<(usize, &mut TableState)> {
+        match self.page {
+            Page::Zones => Some((self.zones.len(), &mut self.zone_table)),
+            Page::Dns => {
+                let len = self.filtered_dns().len();
+                Some((len, &mut self.dns_table))
+            }
+            Page::Firewall => {
+                let len = self.firewall.as_ref().map(|f| f.ip_rules.len()).unwrap_or(0);
+                Some((len, &mut self.firewall_table))
+            }
+            Page::Analytics => None,
+        }
+    }
+
+    pub fn error(&mut self, message: String) {
+        self.status = Some((message, true));
+    }
+
+    pub fn info(&mut self, message: String) {
+        self.status = Some((message, false));
+    }
+}
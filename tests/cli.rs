@@ -0,0 +1,1127 @@
+// 以模拟的 Cloudflare API 端到端运行 cfai 命令 (无需真实凭据)
+
+mod common;
+
+use common::{assert_success, stdout, MockCloudflare, ZONE_ID};
+
+#[test]
+fn zone_list_sends_token_and_prints_json() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", "/zones", "zones.json");
+
+    let output = mock.cfai(&["zone", "list", "--format", "json"]);
+    assert_success(&output);
+
+    let zones: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(zones[0]["name"], "example.com");
+    assert_eq!(zones[0]["id"], ZONE_ID);
+
+    let request = mock.last("GET", "/zones");
+    assert_eq!(request.authorization.as_deref(), Some("Bearer test-token"));
+}
+
+#[test]
+fn dns_list_resolves_zone_by_name() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", "/zones", "zones.json")
+        .fixture("GET", &format!("/zones/{}/dns_records", ZONE_ID), "dns_records.json");
+
+    let output = mock.cfai(&["dns", "list", "example.com", "--format", "json"]);
+    assert_success(&output);
+
+    let records: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(records.as_array().unwrap().len(), 2);
+    assert_eq!(records[0]["content"], "198.51.100.4");
+    assert!(mock.last("GET", "/zones").query.contains("name=example.com"));
+}
+
+#[test]
+fn zone_id_is_cached_between_runs() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", "/zones", "zones.json")
+        .fixture("GET", &format!("/zones/{}/dns_records", ZONE_ID), "dns_records.json");
+
+    assert_success(&mock.cfai(&["dns", "list", "example.com", "--format", "json"]));
+    assert_success(&mock.cfai(&["dns", "list", "example.com", "--format", "json"]));
+    assert_success(&mock.cfai(&["dns", "list", "example.com", "--format", "json", "--no-cache"]));
+
+    let lookups = mock.requests().iter().filter(|r| r.path == "/zones").count();
+    assert_eq!(lookups, 2);
+}
+
+#[test]
+fn stale_cached_zone_id_is_looked_up_again() {
+    let mock = MockCloudflare::start();
+    let stale = "0123456789abcdef0123456789abcdef";
+    let zones = |id: &str| {
+        format!(
+            r#"{{"success":true,"errors":[],"messages":[],"result":[{{"id":"{}","name":"example.com","status":"active"}}],
+                "result_info":{{"page":1,"per_page":20,"count":1,"total_count":1,"total_pages":1}}}}"#,
+            id
+        )
+    };
+    mock.respond("GET", "/zones", 200, &zones(stale))
+        .respond("GET", &format!("/zones/{}/dns_records", stale), 200, r#"{"success":true,"errors":[],"messages":[],"result":[]}"#);
+    assert_success(&mock.cfai(&["dns", "list", "example.com", "--format", "json"]));
+
+    // 域名在别处删除后重新添加: 旧 Zone ID 返回 404，查找后得到新的 Zone ID
+    mock.respond("GET", &format!("/zones/{}/dns_records", stale), 404, r#"{"success":false,"errors":[{"code":7003,"message":"Could not route"}],"messages":[],"result":null}"#)
+        .respond("GET", "/zones", 200, &zones(ZONE_ID))
+        .fixture("GET", &format!("/zones/{}/dns_records", ZONE_ID), "dns_records.json");
+    let output = mock.cfai(&["dns", "list", "example.com", "--format", "json"]);
+    assert_success(&output);
+    let records: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(records.as_array().unwrap().len(), 2);
+
+    // 之后直接使用新缓存的 Zone ID
+    assert_success(&mock.cfai(&["dns", "list", "example.com", "--format", "json"]));
+    assert_eq!(mock.requests().iter().filter(|r| r.path == "/zones").count(), 2);
+}
+
+#[test]
+fn response_cache_serves_repeated_reads_until_a_write() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/dns_records", ZONE_ID);
+    mock.fixture("GET", &path, "dns_records.json")
+        .fixture("POST", &path, "dns_record_created.json");
+    assert_success(&mock.cfai(&["config", "set", "response_cache.enabled", "true"]));
+
+    assert_success(&mock.cfai(&["dns", "list", ZONE_ID, "--format", "json"]));
+    assert_success(&mock.cfai(&["dns", "list", ZONE_ID, "--format", "json"]));
+    let reads = || mock.requests().iter().filter(|r| r.method == "GET" && r.path == path).count();
+    assert_eq!(reads(), 1);
+
+    // 写操作清除该域名的缓存
+    assert_success(&mock.cfai(&["dns", "add", ZONE_ID, "-t", "a", "-n", "www", "-c", "192.0.2.10"]));
+    assert_success(&mock.cfai(&["dns", "list", ZONE_ID, "--format", "json"]));
+    assert_eq!(reads(), 2);
+}
+
+#[test]
+fn dns_add_posts_record() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/dns_records", ZONE_ID);
+    mock.fixture("GET", "/zones", "zones.json")
+        .fixture("POST", &path, "dns_record_created.json");
+
+    let output = mock.cfai(&["dns", "add", "example.com", "-t", "a", "-n", "www", "-c", "192.0.2.10"]);
+    assert_success(&output);
+
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["type"], "A");
+    assert_eq!(body["name"], "www");
+    assert_eq!(body["content"], "192.0.2.10");
+}
+
+#[test]
+fn dns_add_builds_structured_srv_and_caa_data() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/dns_records", ZONE_ID);
+    mock.fixture("POST", &path, "dns_record_created.json");
+
+    let srv = [
+        "dns", "add", ZONE_ID, "-t", "srv", "-n", "@", "--srv-service", "sip", "--srv-proto", "_tcp",
+        "--priority", "10", "--srv-weight", "5", "--srv-port", "5060", "--srv-target", "sip.example.com",
+    ];
+    assert_success(&mock.cfai(&srv));
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["name"], "_sip._tcp");
+    assert!(body.get("content").is_none());
+    assert_eq!(
+        body["data"],
+        serde_json::json!({ "priority": 10, "weight": 5, "port": 5060, "target": "sip.example.com" })
+    );
+
+    assert_success(&mock.cfai(&["dns", "add", ZONE_ID, "-t", "CAA", "-n", "@", "-c", "0 issue \"letsencrypt.org\""]));
+    assert_eq!(
+        mock.last("POST", &path).json()["data"],
+        serde_json::json!({ "flags": 0, "tag": "issue", "value": "letsencrypt.org" })
+    );
+
+    // 类型不匹配的参数在发送请求前报错
+    let output = mock.cfai(&["dns", "add", ZONE_ID, "-t", "A", "-n", "@", "-c", "192.0.2.1", "--caa-tag", "issue"]);
+    assert!(!output.status.success());
+    assert_eq!(mock.requests().iter().filter(|r| r.method == "POST").count(), 2);
+}
+
+#[test]
+fn dns_proxy_toggles_only_proxiable_records() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/dns_records", ZONE_ID);
+    let record = format!("{}/372e67954025e0ba6aaa6d586b9e0b59", path);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("GET", &path, "dns_records.json")
+        .fixture("PATCH", &record, "dns_record_created.json");
+
+    assert_success(&mock.cfai(&["dns", "proxy", ZONE_ID, "@", "off"]));
+    assert_eq!(mock.last("PATCH", &record).json(), serde_json::json!({ "proxied": false }));
+    // TXT 记录不支持代理，不会被修改
+    assert_eq!(mock.requests().iter().filter(|r| r.method == "PATCH").count(), 1);
+}
+
+#[test]
+fn dns_resolve_explains_which_record_answers() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", "/zones", "zones.json")
+        .fixture("GET", &format!("/zones/{}/dns_records", ZONE_ID), "dns_records.json");
+
+    let output = mock.cfai(&["dns", "resolve", "https://Example.com/cart?id=1", "--format", "json"]);
+    assert_success(&output);
+    let resolution: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(resolution["host"], "example.com");
+    assert_eq!(resolution["proxied"], true);
+    assert_eq!(resolution["steps"][0]["records"].as_array().unwrap().len(), 1);
+    assert_eq!(resolution["targets"][0], resolution["steps"][0]["records"][0]["content"]);
+}
+
+#[test]
+fn dns_email_audit_reports_missing_dmarc() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("GET", &format!("/zones/{}/dns_records", ZONE_ID), "dns_records.json");
+
+    let output = mock.cfai(&["dns", "email-audit", ZONE_ID, "--no-resolve", "--format", "json"]);
+    assert_success(&output);
+    let audit: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(audit["spf"][0], "v=spf1 include:_spf.example.net ~all");
+    assert_eq!(audit["spf_lookups"], 1);
+    // 最严重的问题排在最前
+    assert_eq!(audit["findings"][0]["record"], "DMARC");
+    assert_eq!(audit["findings"][0]["severity"], "error");
+}
+
+#[test]
+fn ssl_status_reads_zone_settings() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", &format!("/zones/{}/settings/ssl", ZONE_ID), "setting_ssl.json")
+        .fixture(
+            "GET",
+            &format!("/zones/{}/settings/always_use_https", ZONE_ID),
+            "setting_always_use_https.json",
+        );
+
+    // 直接传入 Zone ID 时不查询域名
+    let output = mock.cfai(&["ssl", "status", ZONE_ID, "--format", "json"]);
+    assert_success(&output);
+
+    let status: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(status["ssl_mode"], "strict");
+    assert_eq!(status["always_https"], true);
+    assert!(mock.requests().iter().all(|r| r.path != "/zones"));
+}
+
+#[test]
+fn ssl_caa_check_adds_missing_cloudflare_cas() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/dns_records", ZONE_ID);
+    let records = serde_json::json!({
+        "success": true, "errors": [], "messages": [],
+        "result": [{ "id": "caa1", "type": "CAA", "name": "example.com", "content": "0 issue \"letsencrypt.org\"" }],
+        "result_info": { "page": 1, "per_page": 100, "count": 1, "total_count": 1, "total_pages": 1 }
+    });
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .respond("GET", &path, 200, &records.to_string())
+        .fixture("POST", &path, "dns_record_created.json");
+
+    assert_success(&mock.cfai(&["ssl", "caa-check", ZONE_ID]));
+    assert!(mock.requests().iter().all(|r| r.method != "POST"));
+
+    assert_success(&mock.cfai(&["ssl", "caa-check", ZONE_ID, "--fix", "-y"]));
+    let created: Vec<serde_json::Value> =
+        mock.requests().iter().filter(|r| r.method == "POST").map(|r| r.json()).collect();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["data"], serde_json::json!({ "flags": 0, "tag": "issue", "value": "pki.goog" }));
+}
+
+#[test]
+fn ssl_order_cert_adds_zone_apex_and_checks_ca_limits() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/ssl/certificate_packs/order", ZONE_ID);
+    let pack = r#"{"success":true,"errors":[],"messages":[],"result":{"id":"pack1","type":"advanced","status":"pending_validation"}}"#;
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .respond("POST", &path, 200, pack);
+
+    let output = mock.cfai(&["ssl", "order-cert", ZONE_ID, "--hosts", "*.example.com", "--validity", "30"]);
+    assert!(!output.status.success());
+    assert!(mock.requests().iter().all(|r| r.method != "POST"));
+
+    assert_success(&mock.cfai(&["ssl", "order-cert", ZONE_ID, "--hosts", "*.example.com,www.example.com", "--ca", "google"]));
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["hosts"], serde_json::json!(["example.com", "*.example.com", "www.example.com"]));
+    assert_eq!(body["certificate_authority"], "google");
+    assert_eq!(body["validity_days"], 90);
+    assert_eq!(body["type"], "advanced");
+}
+
+#[test]
+fn ssl_ciphers_set_expands_modern_preset() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/settings/ciphers", ZONE_ID);
+    mock.respond("PATCH", &path, 200, r#"{"success":true,"errors":[],"messages":[],"result":{"id":"ciphers","value":[]}}"#);
+
+    assert!(!mock.cfai(&["ssl", "ciphers", "set", ZONE_ID, "RC4-SHA"]).status.success());
+    assert_success(&mock.cfai(&["ssl", "ciphers", "set", ZONE_ID, "modern,AES128-GCM-SHA256"]));
+    let value = mock.last("PATCH", &path).json()["value"].clone();
+    assert_eq!(value.as_array().unwrap().len(), 7);
+    assert_eq!(value[6], "AES128-GCM-SHA256");
+}
+
+#[test]
+fn cache_crawler_hints_toggles_and_shows_in_status() {
+    let mock = MockCloudflare::start();
+    let flags = format!("/zones/{}/flags/products/cache/changes", ZONE_ID);
+    let ok = |result: &str| format!(r#"{{"success":true,"errors":[],"messages":[],"result":{}}}"#, result);
+    mock.respond("PUT", &flags, 200, &ok(r#"{"crawlhints_enabled":true}"#));
+    mock.respond("GET", &flags, 200, &ok(r#"{"crawlhints_enabled":true}"#));
+    for (id, value) in [("cache_level", r#""aggressive""#), ("browser_cache_ttl", "14400"), ("always_online", r#""off""#)] {
+        let path = format!("/zones/{}/settings/{}", ZONE_ID, id);
+        mock.respond("GET", &path, 200, &ok(&format!(r#"{{"id":"{}","value":{}}}"#, id, value)));
+    }
+
+    assert_success(&mock.cfai(&["cache", "crawler-hints", "on", ZONE_ID]));
+    let body = mock.last("PUT", &flags).json();
+    assert_eq!(body["feature"], "crawlhints_enabled");
+    assert_eq!(body["value"], true);
+
+    let output = mock.cfai(&["--format", "json", "cache", "status", ZONE_ID]);
+    assert_success(&output);
+    let status: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(status["always_online"], "off");
+    assert_eq!(status["crawler_hints"], "on");
+}
+
+#[test]
+fn cache_purge_sitemap_expands_index_and_filters_paths() {
+    let mock = MockCloudflare::start();
+    let purge = format!("/zones/{}/purge_cache", ZONE_ID);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .respond("POST", &purge, 200, r#"{"success":true,"errors":[],"messages":[],"result":{"id":"purge"}}"#)
+        .respond(
+            "GET",
+            "/sitemap_index.xml",
+            200,
+            &format!("<sitemapindex><sitemap><loc>{}/posts.xml</loc></sitemap></sitemapindex>", mock.base_url()),
+        )
+        .respond(
+            "GET",
+            "/posts.xml",
+            200,
+            "<urlset><url><loc>https://example.com/blog/a</loc></url><url><loc>https://example.com/about</loc></url>\
+             <url><loc>https://www.example.com/blog/b</loc></url><url><loc>https://other.org/blog/c</loc></url></urlset>",
+        );
+    let sitemap = format!("{}/sitemap_index.xml", mock.base_url());
+
+    assert_success(&mock.cfai(&["cache", "purge-sitemap", ZONE_ID, &sitemap, "-p", "/blog"]));
+    let files = mock.last("POST", &purge).json()["files"].clone();
+    assert_eq!(files, serde_json::json!(["https://example.com/blog/a", "https://www.example.com/blog/b"]));
+}
+
+#[test]
+fn restore_recreates_srv_record_with_data() {
+    let mock = MockCloudflare::start();
+    let records = format!("/zones/{}/dns_records", ZONE_ID);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("GET", &records, "dns_records.json")
+        .fixture("POST", &records, "dns_record_created.json")
+        .respond("GET", &format!("/zones/{}/settings", ZONE_ID), 200, r#"{"success":true,"errors":[],"messages":[],"result":[]}"#);
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("backup.json");
+    let backup = serde_json::json!({
+        "version": 1, "cfai_version": "0.0.0", "created_at": "2026-01-01T00:00:00Z",
+        "zone": { "id": "other", "name": "example.org", "plan": null },
+        "ssl": null, "settings": null, "page_rules": null, "firewall_rules": null,
+        "dns_records": [{
+            "type": "SRV", "name": "_sip._tcp.example.org", "content": "5 5060 sip.example.org", "priority": 10,
+            "data": { "priority": 10, "weight": 5, "port": 5060, "target": "sip.example.org" }
+        }],
+    });
+    std::fs::write(&file, backup.to_string()).unwrap();
+
+    assert_success(&mock.cfai(&["restore", file.to_str().unwrap(), "--zone", ZONE_ID, "-y"]));
+    let body = mock.last("POST", &records).json();
+    assert_eq!(body["name"], "_sip._tcp.example.com");
+    assert_eq!(body["data"]["target"], "sip.example.com");
+    assert_eq!(body["data"]["port"], 5060);
+    assert!(body.get("content").is_none());
+}
+
+#[test]
+fn cross_zone_restore_matches_txt_records_naming_the_zone() {
+    let mock = MockCloudflare::start();
+    let records = format!("/zones/{}/dns_records", ZONE_ID);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .respond(
+            "GET",
+            &records,
+            200,
+            r#"{"success":true,"errors":[],"messages":[],"result":[{"id":"4b3b5e6f0d1a2c3e4f5a6b7c8d9e0f1a","type":"TXT",
+                "name":"example.com","content":"\"v=spf1 include:_spf.example.com ~all\"","ttl":3600,"comment":"SPF"}],
+                "result_info":{"page":1,"per_page":100,"count":1,"total_count":1,"total_pages":1}}"#,
+        )
+        .respond("GET", &format!("/zones/{}/settings", ZONE_ID), 200, r#"{"success":true,"errors":[],"messages":[],"result":[]}"#);
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("backup.json");
+    let backup = serde_json::json!({
+        "version": 1, "cfai_version": "0.0.0", "created_at": "2026-01-01T00:00:00Z",
+        "zone": { "id": "other", "name": "example.org", "plan": null },
+        "ssl": null, "settings": null, "page_rules": null, "firewall_rules": null,
+        "dns_records": [{
+            "type": "TXT", "name": "example.org", "content": "\"v=spf1 include:_spf.example.org ~all\"",
+            "ttl": 3600, "comment": "SPF"
+        }],
+    });
+    std::fs::write(&file, backup.to_string()).unwrap();
+
+    // 记录值中的域名替换后与现有记录一致，不会重复创建
+    let output = mock.cfai(&["restore", file.to_str().unwrap(), "--zone", ZONE_ID, "-y"]);
+    assert_success(&output);
+    assert!(stdout(&output).contains("无需还原"), "{}", stdout(&output));
+    assert!(mock.requests().iter().all(|r| r.method == "GET"));
+}
+
+#[test]
+fn zone_diff_apply_keeps_unmanaged_records_without_force() {
+    let mock = MockCloudflare::start();
+    let source = "0123456789abcdef0123456789abcdef";
+    let records = format!("/zones/{}/dns_records", ZONE_ID);
+    let empty = r#"{"success":true,"errors":[],"messages":[],"result":[]}"#;
+    let deleted = r#"{"success":true,"errors":[],"messages":[],"result":{"id":"deleted"}}"#;
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("GET", &records, "dns_records.json")
+        .fixture("POST", &records, "dns_record_created.json")
+        .respond("DELETE", &format!("{}/372e67954025e0ba6aaa6d586b9e0b59", records), 200, deleted)
+        .respond("DELETE", &format!("{}/4b3b5e6f0d1a2c3e4f5a6b7c8d9e0f1a", records), 200, deleted)
+        .respond("GET", &format!("/zones/{}/settings", ZONE_ID), 200, empty)
+        .respond("GET", &format!("/zones/{}/settings", source), 200, empty)
+        .respond(
+            "GET",
+            &format!("/zones/{}", source),
+            200,
+            &format!(r#"{{"success":true,"errors":[],"messages":[],"result":{{"id":"{}","name":"example.org","status":"active"}}}}"#, source),
+        )
+        .respond(
+            "GET",
+            &format!("/zones/{}/dns_records", source),
+            200,
+            r#"{"success":true,"errors":[],"messages":[],"result":[{"type":"A","name":"www.example.org","content":"192.0.2.10","ttl":1}],
+                "result_info":{"page":1,"per_page":100,"count":1,"total_count":1,"total_pages":1}}"#,
+        );
+
+    // 目标域名的现有记录都没有 cfai 标记，同步时只新增、不删除
+    assert_success(&mock.cfai(&["zone", "diff", source, ZONE_ID, "--apply", "-y"]));
+    assert_eq!(mock.last("POST", &records).json()["name"], "www.example.com");
+    assert!(!mock.requests().iter().any(|r| r.method == "DELETE"));
+
+    assert_success(&mock.cfai(&["zone", "diff", source, ZONE_ID, "--apply", "--force", "-y"]));
+    assert_eq!(mock.requests().iter().filter(|r| r.method == "DELETE").count(), 2);
+}
+
+#[test]
+fn firewall_block_creates_access_rule() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/firewall/access_rules/rules", ZONE_ID);
+    mock.fixture("POST", &path, "access_rule_created.json");
+
+    let output = mock.cfai(&["firewall", "block", ZONE_ID, "203.0.113.7", "--note", "scanner"]);
+    assert_success(&output);
+
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["mode"], "block");
+    assert_eq!(body["configuration"]["value"], "203.0.113.7");
+    assert_eq!(body["notes"], "scanner");
+}
+
+#[test]
+fn confirmation_without_terminal_fails_instead_of_hanging() {
+    let mock = MockCloudflare::start();
+
+    let output = mock.cfai(&["zone", "delete", ZONE_ID]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--yes"));
+    assert!(mock.requests().iter().all(|r| r.method != "DELETE"));
+}
+
+#[test]
+fn global_yes_skips_confirmation() {
+    let mock = MockCloudflare::start();
+    let zone = format!("/zones/{}", ZONE_ID);
+    mock.respond("DELETE", &zone, 200, &format!(r#"{{"success":true,"errors":[],"messages":[],"result":{{"id":"{}"}}}}"#, ZONE_ID));
+
+    // 全局 --yes 写在子命令之前，由确认提示统一处理
+    assert_success(&mock.cfai(&["--yes", "zone", "delete", ZONE_ID]));
+    let output = mock
+        .command(&["zone", "delete", ZONE_ID])
+        .env("CLOUDFLARE_API_TOKEN", "test-token")
+        .env("CFAI_YES", "1")
+        .output()
+        .unwrap();
+    assert_success(&output);
+    assert_eq!(mock.requests().iter().filter(|r| r.method == "DELETE").count(), 2);
+}
+
+#[test]
+fn api_error_exits_with_api_code() {
+    let mock = MockCloudflare::start();
+
+    let output = mock.cfai(&["ssl", "status", ZONE_ID]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("7003"));
+}
+
+#[test]
+fn missing_credentials_exit_with_auth_code() {
+    let mock = MockCloudflare::start();
+
+    let output = mock.command(&["zone", "list"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert!(mock.requests().is_empty());
+}
+
+#[test]
+fn ai_report_writes_markdown() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", "/zones", "zones.json")
+        .fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("POST", "/chat/completions", "chat_completion.json");
+    let report = tempfile::tempdir().unwrap();
+    let path = report.path().join("report.md");
+
+    let output = mock
+        .command(&["ai", "report", "example.com", "--once", "-o", path.to_str().unwrap()])
+        .env("CLOUDFLARE_API_TOKEN", "test-token")
+        .env("AI_API_URL", mock.base_url())
+        .env("AI_API_KEY", "ai-key")
+        .env("AI_STREAM", "false")
+        .output()
+        .unwrap();
+    assert_success(&output);
+
+    let markdown = std::fs::read_to_string(&path).unwrap();
+    assert!(markdown.starts_with("# 🩺 example.com 健康报告"));
+    assert!(markdown.contains("85 分"));
+    assert!(markdown.contains("`setting_update` 开启浏览器完整性检查"));
+
+    let request = mock.last("POST", "/chat/completions");
+    assert_eq!(request.authorization.as_deref(), Some("Bearer ai-key"));
+    assert_eq!(request.json()["tools"][0]["function"]["name"], "suggest_actions");
+}
+
+#[test]
+fn config_verify_tests_ai_credentials() {
+    let mock = MockCloudflare::start();
+    mock.fixture("POST", "/chat/completions", "chat_completion.json");
+    let verify = |key: &str| {
+        mock.command(&["config", "verify"])
+            .env("CLOUDFLARE_API_TOKEN", "test-token")
+            .env("AI_API_URL", mock.base_url())
+            .env("AI_API_KEY", key)
+            .env("AI_MODEL", "gpt-4o-mini")
+            .output()
+            .unwrap()
+    };
+
+    let output = verify("ai-key");
+    assert_success(&output);
+    assert!(stdout(&output).contains("AI 模型 gpt-4o-mini 可用"), "{}", stdout(&output));
+    let request = mock.last("POST", "/chat/completions").json();
+    assert_eq!(request["max_tokens"], 1);
+    assert!(request.get("tools").is_none());
+
+    mock.respond("POST", "/chat/completions", 401, r#"{"error":{"message":"Incorrect API key"}}"#);
+    let output = verify("bad-key");
+    let text = format!("{}{}", stdout(&output), String::from_utf8_lossy(&output.stderr));
+    assert!(text.contains("AI API Key 无效或没有权限"), "{}", text);
+}
+
+#[test]
+fn mutation_sends_webhook_notification() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/firewall/access_rules/rules", ZONE_ID);
+    mock.fixture("POST", &path, "access_rule_created.json")
+        .respond("POST", "/hook", 200, "{}");
+
+    let hook = format!("{}/hook", mock.base_url());
+    assert_success(&mock.cfai(&["config", "set", "notify.webhook_url", &hook]));
+    assert_success(&mock.cfai(&["config", "set", "notify.on", "always"]));
+
+    // 只读命令不通知
+    mock.fixture("GET", &format!("/zones/{}/settings/ssl", ZONE_ID), "setting_ssl.json")
+        .fixture(
+            "GET",
+            &format!("/zones/{}/settings/always_use_https", ZONE_ID),
+            "setting_always_use_https.json",
+        );
+    assert_success(&mock.cfai(&["ssl", "status", ZONE_ID]));
+    assert!(mock.requests().iter().all(|r| r.path != "/hook"));
+
+    assert_success(&mock.cfai(&["firewall", "block", ZONE_ID, "203.0.113.7"]));
+    let body = mock.last("POST", "/hook").json();
+    assert_eq!(body["source"], "cfai");
+    assert_eq!(body["level"], "success");
+    assert!(body["message"].as_str().unwrap().contains(&format!("POST {}", path)));
+}
+
+#[test]
+fn guard_enables_under_attack_when_threshold_exceeded() {
+    let mock = MockCloudflare::start();
+    let setting = format!("/zones/{}/settings/security_level", ZONE_ID);
+    let rate_limits = format!("/zones/{}/rate_limits", ZONE_ID);
+    let level = r#"{"success":true,"errors":[],"messages":[],"result":{"id":"security_level","value":"medium"}}"#;
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("POST", "/graphql", "live_analytics_attack.json")
+        .respond("GET", &setting, 200, level)
+        .respond("PATCH", &setting, 200, level)
+        .respond(
+            "POST",
+            &rate_limits,
+            200,
+            r#"{"success":true,"errors":[],"messages":[],"result":{"id":"rl-guard","threshold":100,"period":60}}"#,
+        );
+
+    let output = mock.cfai(&["guard", ZONE_ID, "--threats", "1000", "--rate-limit", "--duration", "1"]);
+    assert_success(&output);
+
+    assert_eq!(mock.last("PATCH", &setting).json()["value"], "under_attack");
+    let rule = mock.last("POST", &rate_limits).json();
+    assert_eq!(rule["match"]["request"]["url"], "*example.com/*");
+    assert_eq!(rule["action"]["mode"], "managed_challenge");
+    // 退出时保留防护并提示恢复命令
+    assert!(stdout(&output).contains(&format!("cfai firewall level {} medium", ZONE_ID)));
+}
+
+#[test]
+fn scan_submits_url_and_prints_verdict() {
+    let mock = MockCloudflare::start();
+    mock.respond(
+        "POST",
+        "/accounts/acc-1/urlscanner/v2/scan",
+        200,
+        r#"{"uuid":"scan-1","url":"https://suspicious.example.net/login","visibility":"Unlisted","message":"Submission successful"}"#,
+    )
+    .fixture("GET", "/accounts/acc-1/urlscanner/v2/result/scan-1", "url_scan_result.json");
+
+    let output = mock
+        .command(&["scan", "suspicious.example.net/login"])
+        .env("CLOUDFLARE_API_TOKEN", "test-token")
+        .env("CLOUDFLARE_ACCOUNT_ID", "acc-1")
+        .output()
+        .expect("运行 cfai 失败");
+    assert_success(&output);
+
+    let request = mock.last("POST", "/accounts/acc-1/urlscanner/v2/scan").json();
+    assert_eq!(request["url"], "https://suspicious.example.net/login");
+    assert_eq!(request["visibility"], "Unlisted");
+    let out = stdout(&output);
+    assert!(out.contains("恶意") && out.contains("Phishing"));
+    assert!(out.contains("jQuery, Nginx"));
+}
+
+#[test]
+fn mcp_answers_tool_calls_and_blocks_unsafe_actions() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mock = MockCloudflare::start();
+    let dns = format!("/zones/{}/dns_records", ZONE_ID);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("GET", &dns, "dns_records.json")
+        .fixture("GET", &format!("/zones/{}/settings/ssl", ZONE_ID), "setting_ssl.json");
+
+    let requests = [
+        serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"protocolVersion": "2024-11-05"}}),
+        serde_json::json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+        serde_json::json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {
+            "name": "list_dns_records", "arguments": {"zone": ZONE_ID, "type": "txt"}}}),
+        // 封禁源站 IP 会被安全审查阻止
+        serde_json::json!({"jsonrpc": "2.0", "id": 4, "method": "tools/call", "params": {
+            "name": "execute_action", "arguments": {
+                "zone": ZONE_ID, "type": "firewall_rule", "description": "封禁 IP", "risk": "low",
+                "params": {"type": "block_ip", "ip": "198.51.100.4"}}}}),
+        // 修改设置至少为 medium，调用方省略或低报 risk 也不会自动执行
+        serde_json::json!({"jsonrpc": "2.0", "id": 5, "method": "tools/call", "params": {
+            "name": "execute_action", "arguments": {
+                "zone": ZONE_ID, "type": "setting_update", "description": "关闭 HTTPS 重定向",
+                "params": {"setting_id": "always_use_https", "value": "off"}}}}),
+    ];
+    let mut child = mock
+        .command(&["mcp"])
+        .env("CLOUDFLARE_API_TOKEN", "test-token")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("运行 cfai 失败");
+    let mut stdin = child.stdin.take().unwrap();
+    for request in &requests {
+        writeln!(stdin, "{}", request).unwrap();
+    }
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    assert_success(&output);
+
+    // 通知不应答，每个请求一行响应
+    let responses: Vec<serde_json::Value> = stdout(&output)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(responses.len(), 5);
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2024-11-05");
+    let tools = responses[1]["result"]["tools"].as_array().unwrap();
+    assert!(tools.iter().any(|t| t["name"] == "execute_action"));
+
+    let records: serde_json::Value =
+        serde_json::from_str(responses[2]["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(records.as_array().unwrap().len(), 1);
+    assert_eq!(records[0]["type"], "TXT");
+
+    assert_eq!(responses[3]["result"]["isError"], true);
+    assert!(responses[3]["result"]["content"][0]["text"].as_str().unwrap().contains("源站"));
+    let text = responses[4]["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(text.contains("未执行") && text.contains("medium"), "{}", text);
+    assert!(mock.requests().iter().all(|r| r.method == "GET"));
+}
+
+#[test]
+fn export_terraform_generates_resources_with_imports() {
+    let mock = MockCloudflare::start();
+    let zone = format!("/zones/{}", ZONE_ID);
+    mock.fixture("GET", &zone, "zone.json")
+        .fixture("GET", &format!("{}/dns_records", zone), "dns_records.json")
+        .respond(
+            "GET",
+            &format!("{}/settings", zone),
+            200,
+            r#"{"success":true,"errors":[],"messages":[],"result":[
+                {"id":"ssl","value":"strict","editable":true},
+                {"id":"minify","value":{"css":"on","html":"off"},"editable":true},
+                {"id":"advanced_ddos","value":"on","editable":false}]}"#,
+        )
+        .respond(
+            "GET",
+            &format!("{}/pagerules", zone),
+            200,
+            r#"{"success":true,"errors":[],"messages":[],"result":[{"id":"pr-1","priority":1,"status":"active",
+                "targets":[{"target":"url","constraint":{"operator":"matches","value":"example.com/old/*"}}],
+                "actions":[{"id":"forwarding_url","value":{"url":"https://example.com/new/$1","status_code":301}}]}]}"#,
+        )
+        .respond(
+            "GET",
+            &format!("{}/firewall/rules", zone),
+            200,
+            r#"{"success":true,"errors":[],"messages":[],"result":[{"id":"fw-1","action":"block","description":"bad bots",
+                "filter":{"expression":"(cf.client.bot)"}}]}"#,
+        );
+
+    let output = mock.cfai(&["export", "terraform", ZONE_ID]);
+    assert_success(&output);
+    let hcl = stdout(&output);
+
+    assert!(hcl.contains(r#"resource "cloudflare_dns_record" "a_root" {"#), "{}", hcl);
+    assert!(hcl.contains(&format!("id = \"{}/372e67954025e0ba6aaa6d586b9e0b59\"", ZONE_ID)));
+    assert!(hcl.contains(r#"setting_id = "ssl""#));
+    assert!(!hcl.contains("advanced_ddos"));
+    assert!(hcl.contains(r#"url         = "https://example.com/new/$1""#));
+    // 旧版防火墙规则转换为 WAF 自定义规则，规则集为新建，没有 import
+    assert!(hcl.contains(r#"expression  = "(cf.client.bot)""#));
+    assert!(!hcl.contains("to = cloudflare_ruleset"));
+}
+
+#[test]
+fn apply_reconciles_zone_with_yaml_spec() {
+    let mock = MockCloudflare::start();
+    let zone = format!("/zones/{}", ZONE_ID);
+    let rules = format!("{}/firewall/access_rules/rules", zone);
+    mock.fixture("GET", "/zones", "zones.json")
+        .fixture("GET", &zone, "zone.json")
+        .fixture("GET", &format!("{}/dns_records", zone), "dns_records.json")
+        .fixture("POST", &format!("{}/dns_records", zone), "dns_record_created.json")
+        .respond(
+            "GET",
+            &format!("{}/settings", zone),
+            200,
+            r#"{"success":true,"errors":[],"messages":[],"result":[
+                {"id":"ssl","value":"full","editable":true},
+                {"id":"always_use_https","value":"off","editable":true},
+                {"id":"min_tls_version","value":"1.2","editable":true}]}"#,
+        )
+        .fixture("PATCH", &format!("{}/settings/ssl", zone), "setting_ssl.json")
+        .fixture("PATCH", &format!("{}/settings/always_use_https", zone), "setting_always_use_https.json")
+        .respond("GET", &rules, 200, r#"{"success":true,"errors":[],"messages":[],"result":[]}"#)
+        .fixture("POST", &rules, "access_rule_created.json");
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("zone.yaml");
+    std::fs::write(
+        &file,
+        r#"
+zone: example.com
+ssl:
+  mode: strict
+settings:
+  always_use_https: true
+  min_tls_version: 1.2
+dns:
+  - { type: A, name: "@", content: 198.51.100.4, proxied: true }
+  - { type: TXT, name: "@", content: '"v=spf1 include:_spf.example.net ~all"', ttl: 3600, comment: SPF }
+  - { type: A, name: www, content: 192.0.2.10 }
+firewall:
+  ip_rules:
+    - { mode: block, value: 203.0.113.7, notes: scanner }
+"#,
+    )
+    .unwrap();
+    let file = file.to_str().unwrap();
+
+    let output = mock.cfai(&["apply", "-f", file, "--dry-run"]);
+    assert_success(&output);
+    let plan = stdout(&output);
+    assert!(plan.contains("4 项变更"), "{}", plan);
+    // 现有记录没有 cfai 标记，不会被修改
+    assert!(plan.contains("--force"));
+    assert!(!plan.contains("min_tls_version"));
+    assert!(mock.requests().iter().all(|r| r.method == "GET"));
+
+    assert_success(&mock.cfai(&["apply", "-f", file, "-y"]));
+    assert_eq!(mock.last("PATCH", &format!("{}/settings/ssl", zone)).json()["value"], "strict");
+    assert_eq!(mock.last("PATCH", &format!("{}/settings/always_use_https", zone)).json()["value"], "on");
+    let record = mock.last("POST", &format!("{}/dns_records", zone)).json();
+    assert_eq!(record["name"], "www.example.com");
+    assert_eq!(record["comment"], "[cfai]");
+    assert_eq!(mock.last("POST", &rules).json()["configuration"]["target"], "ip");
+    assert!(mock.requests().iter().all(|r| r.method != "DELETE" && r.method != "PUT"));
+}
+
+#[test]
+fn apply_updates_record_created_by_dns_add() {
+    let mock = MockCloudflare::start();
+    let records = format!("/zones/{}/dns_records", ZONE_ID);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("POST", &records, "dns_record_created.json")
+        .fixture("PUT", &format!("{}/9a7806061c88ada191ed06f989cc3dac", records), "dns_record_created.json")
+        .respond("GET", &format!("/zones/{}/settings", ZONE_ID), 200, r#"{"success":true,"errors":[],"messages":[],"result":[]}"#);
+
+    assert_success(&mock.cfai(&["dns", "add", ZONE_ID, "-t", "A", "-n", "www", "-c", "192.0.2.10"]));
+    let comment = mock.last("POST", &records).json()["comment"].clone();
+    assert_eq!(comment, "[cfai]");
+
+    // 列表中返回 dns add 刚创建的记录 (带管理标记)
+    let listed = serde_json::json!({
+        "success": true, "errors": [], "messages": [],
+        "result": [{ "id": "9a7806061c88ada191ed06f989cc3dac", "type": "A", "name": "www.example.com",
+                     "content": "192.0.2.10", "proxied": false, "ttl": 1, "comment": comment }],
+        "result_info": { "page": 1, "per_page": 100, "count": 1, "total_count": 1, "total_pages": 1 },
+    });
+    mock.respond("GET", &records, 200, &listed.to_string());
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("zone.yaml");
+    std::fs::write(&file, "dns:\n  - { type: A, name: www, content: 192.0.2.10, proxied: true }\n").unwrap();
+
+    assert_success(&mock.cfai(&["apply", "-f", file.to_str().unwrap(), "--zone", ZONE_ID, "-y"]));
+    let body = mock.last("PUT", &format!("{}/9a7806061c88ada191ed06f989cc3dac", records)).json();
+    assert_eq!(body["proxied"], true);
+}
+
+#[test]
+fn ddns_updates_record_when_public_ip_changes() {
+    let mock = MockCloudflare::start();
+    let records = format!("/zones/{}/dns_records", ZONE_ID);
+    let record = format!("{}/372e67954025e0ba6aaa6d586b9e0b59", records);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json")
+        .fixture("GET", &records, "dns_records.json")
+        .fixture("PATCH", &record, "dns_record_created.json")
+        .respond("GET", "/trace", 200, "fl=1\nip=203.0.113.9\nts=1\n");
+    let ip_url = format!("{}/trace", mock.base_url());
+
+    let output = mock.cfai(&["ddns", ZONE_ID, "@", "--ip-url", &ip_url]);
+    assert_success(&output);
+    assert!(stdout(&output).contains("198.51.100.4 → 203.0.113.9"), "{}", stdout(&output));
+    assert_eq!(mock.last("PATCH", &record).json()["content"], "203.0.113.9");
+}
+
+#[test]
+fn probe_reports_response_details() {
+    let mock = MockCloudflare::start();
+    mock.respond("GET", "/page", 200, r#"{"ok":true}"#);
+    let url = format!("{}/page", mock.base_url());
+
+    let output = mock.cfai(&["probe", &url, "-H", "X-Test: 1", "--format", "json"]);
+    assert_success(&output);
+    let result: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(result["status"], 200);
+    assert_eq!(result["http_version"], "HTTP/1.1");
+    assert_eq!(result["via_cloudflare"], false);
+    assert_eq!(result["body_bytes"], 11);
+}
+
+#[test]
+fn ip_reports_cloudflare_range_and_matching_rules() {
+    let mock = MockCloudflare::start();
+    mock.respond(
+        "GET",
+        "/ips",
+        200,
+        r#"{"success":true,"errors":[],"messages":[],"result":{
+            "ipv4_cidrs":["104.16.0.0/13","172.64.0.0/13"],"ipv6_cidrs":["2606:4700::/32"],"etag":"x"}}"#,
+    )
+    .respond(
+        "GET",
+        &format!("/zones/{}/firewall/access_rules/rules", ZONE_ID),
+        200,
+        r#"{"success":true,"errors":[],"messages":[],"result":[
+            {"id":"r1","mode":"block","configuration":{"target":"ip_range","value":"104.16.0.0/16"}},
+            {"id":"r2","mode":"whitelist","configuration":{"target":"ip","value":"198.51.100.1"}}]}"#,
+    );
+
+    let output = mock.cfai(&["ip", "104.16.132.229", "--zone", ZONE_ID, "--format", "json"]);
+    assert_success(&output);
+    let info: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(info["cloudflare_range"], "104.16.0.0/13");
+    assert_eq!(info["rules"].as_array().unwrap().len(), 1);
+    assert_eq!(info["rules"][0]["id"], "r1");
+
+    // 第二次查询使用缓存的 IP 段
+    let output = mock.cfai(&["ip", "2606:4700::1", "--format", "json"]);
+    assert_success(&output);
+    let info: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(info["cloudflare_range"], "2606:4700::/32");
+    assert_eq!(mock.requests().iter().filter(|r| r.path == "/ips").count(), 1);
+}
+
+#[test]
+fn firewall_block_list_skips_existing_rules() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/firewall/access_rules/rules", ZONE_ID);
+    mock.respond(
+        "GET",
+        &path,
+        200,
+        r#"{"success":true,"errors":[],"messages":[],"result":[
+            {"id":"r1","mode":"block","configuration":{"target":"ip","value":"203.0.113.7"}}]}"#,
+    )
+    .fixture("POST", &path, "access_rule_created.json");
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("ips.txt");
+    std::fs::write(&file, "# scanners\n203.0.113.7\n198.51.100.0/24, as64500\ncn\n203.0.113.7\n").unwrap();
+
+    let output = mock.cfai(&["firewall", "block-list", ZONE_ID, "--file", file.to_str().unwrap(), "-y"]);
+    assert_success(&output);
+
+    let created: Vec<_> = mock.requests().into_iter().filter(|r| r.method == "POST").map(|r| r.json()).collect();
+    let targets: Vec<_> = created.iter().map(|b| b["configuration"]["value"].as_str().unwrap().to_string()).collect();
+    assert_eq!(targets, ["198.51.100.0/24", "AS64500", "CN"]);
+    assert_eq!(created[1]["configuration"]["target"], "asn");
+}
+
+#[test]
+fn firewall_block_accepts_country_and_asn() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/firewall/access_rules/rules", ZONE_ID);
+    mock.fixture("POST", &path, "access_rule_created.json");
+
+    assert_success(&mock.cfai(&["firewall", "block", ZONE_ID, "--country", "xx"]));
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["configuration"]["target"], "country");
+    assert_eq!(body["configuration"]["value"], "XX");
+
+    assert_success(&mock.cfai(&["firewall", "whitelist", ZONE_ID, "--asn", "13335"]));
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["mode"], "whitelist");
+    assert_eq!(body["configuration"]["target"], "asn");
+    assert_eq!(body["configuration"]["value"], "AS13335");
+
+    let output = mock.cfai(&["firewall", "block", ZONE_ID, "not-an-ip"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn firewall_ua_block_add_creates_rule() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/firewall/ua_rules", ZONE_ID);
+    mock.respond(
+        "POST",
+        &path,
+        200,
+        r#"{"success":true,"errors":[],"messages":[],"result":{"id":"ua1","mode":"block","paused":false,
+            "configuration":{"target":"ua","value":"BadBot/1.0"}}}"#,
+    );
+
+    let output = mock.cfai(&["firewall", "ua-block", "add", ZONE_ID, "BadBot/1.0", "--mode", "managed_challenge"]);
+    assert_success(&output);
+    assert!(stdout(&output).contains("ua1"));
+
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["mode"], "managed_challenge");
+    assert_eq!(body["configuration"]["target"], "ua");
+    assert_eq!(body["configuration"]["value"], "BadBot/1.0");
+}
+
+#[test]
+fn firewall_lockdown_add_restricts_url_to_ips() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/firewall/lockdowns", ZONE_ID);
+    mock.respond(
+        "POST",
+        &path,
+        200,
+        r#"{"success":true,"errors":[],"messages":[],"result":{"id":"lk1","paused":false,
+            "urls":["example.com/wp-admin*"],"configurations":[{"target":"ip_range","value":"203.0.113.0/24"}]}}"#,
+    );
+
+    let output = mock.cfai(&[
+        "firewall", "lockdown", "add", ZONE_ID, "-u", "example.com/wp-admin*", "-i", "203.0.113.0/24", "-i", "2001:db8::1",
+    ]);
+    assert_success(&output);
+
+    let body = mock.last("POST", &path).json();
+    assert_eq!(body["urls"][0], "example.com/wp-admin*");
+    assert_eq!(body["configurations"][0]["target"], "ip_range");
+    assert_eq!(body["configurations"][1]["target"], "ip");
+    assert_eq!(body["configurations"][1]["value"], "2001:db8::1");
+}
+
+#[test]
+fn scheduler_applies_scheduled_setting() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/settings/rocket_loader", ZONE_ID);
+    mock.respond(
+        "PATCH",
+        &path,
+        200,
+        r#"{"success":true,"errors":[],"messages":[],"result":{"id":"rocket_loader","value":"on"}}"#,
+    );
+
+    let output = mock.cfai(&[
+        "firewall", "schedule", ZONE_ID, "--setting", "rocket_loader=on", "--from", "00:00", "--to", "12:00", "--revert", "off",
+    ]);
+    assert_success(&output);
+    let output = mock.cfai(&["firewall", "schedule", ZONE_ID, "--level", "extreme", "--cron", "0 22 * * *"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = mock.cfai(&["scheduler", "list", "--format", "json"]);
+    let schedules: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(schedules.as_array().unwrap().len(), 1);
+    assert_eq!(schedules[0]["setting"], "rocket_loader");
+
+    assert_success(&mock.cfai(&["scheduler", "run", "--once"]));
+    let value = mock.last("PATCH", &path).json()["value"].clone();
+    assert!(value == "on" || value == "off");
+
+    assert_success(&mock.cfai(&["scheduler", "remove", "1"]));
+    let output = mock.cfai(&["scheduler", "list", "--format", "json"]);
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&stdout(&output)).unwrap(), serde_json::json!([]));
+}
+
+#[test]
+fn cache_dev_mode_for_schedules_one_shot_disable() {
+    let mock = MockCloudflare::start();
+    let path = format!("/zones/{}/settings/development_mode", ZONE_ID);
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json").respond(
+        "PATCH",
+        &path,
+        200,
+        r#"{"success":true,"errors":[],"messages":[],"result":{"id":"development_mode","value":"on"}}"#,
+    );
+
+    assert_eq!(mock.cfai(&["cache", "dev-mode", ZONE_ID, "on", "--for", "5h"]).status.code(), Some(2));
+    assert_success(&mock.cfai(&["cache", "dev-mode", ZONE_ID, "on", "--for", "30m"]));
+    assert_eq!(mock.last("PATCH", &path).json()["value"], "on");
+
+    let output = mock.cfai(&["scheduler", "list", "--format", "json"]);
+    let schedules: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(schedules[0]["setting"], "development_mode");
+    assert_eq!(schedules[0]["value"], "off");
+    assert!(schedules[0]["at"].is_string());
+
+    let output = mock.cfai(&["--format", "json", "cache", "dev-mode", ZONE_ID, "status"]);
+    let status: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(status["scheduled_off"], schedules[0]["at"]);
+
+    // 还未到点，执行器不会关闭开发模式
+    assert_success(&mock.cfai(&["scheduler", "run", "--once"]));
+    assert_eq!(mock.requests().iter().filter(|r| r.method == "PATCH").count(), 1);
+}
+
+#[test]
+fn workers_stats_merges_hourly_groups() {
+    let mock = MockCloudflare::start();
+    mock.respond(
+        "POST",
+        "/graphql",
+        200,
+        r#"{"data":{"viewer":{"accounts":[{
+            "totals":[{"sum":{"requests":1000,"errors":25,"subrequests":300},
+                       "quantiles":{"cpuTimeP50":1200.0,"cpuTimeP90":4500.0,"cpuTimeP99":9800.0}}],
+            "series":[
+                {"dimensions":{"datetimeHour":"2024-05-01T10:00:00Z"},"sum":{"requests":400,"errors":5,"subrequests":100}},
+                {"dimensions":{"datetimeHour":"2024-05-01T10:00:00Z"},"sum":{"requests":100,"errors":15,"subrequests":50}},
+                {"dimensions":{"datetimeHour":"2024-05-01T11:00:00Z"},"sum":{"requests":500,"errors":5,"subrequests":150}}
+            ]}]}}}"#,
+    );
+
+    let output = mock
+        .command(&["workers", "stats", "api-worker", "-s", "7d", "--format", "json"])
+        .env("CLOUDFLARE_API_TOKEN", "test-token")
+        .env("CLOUDFLARE_ACCOUNT_ID", "acc-1")
+        .output()
+        .expect("运行 cfai 失败");
+    assert_success(&output);
+
+    let variables = &mock.last("POST", "/graphql").json()["variables"];
+    assert_eq!(variables["accountTag"], "acc-1");
+    assert_eq!(variables["script"], "api-worker");
+    let stats: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(stats["errors"], 25);
+    assert_eq!(stats["cpu_time_p99"], 9800.0);
+    assert_eq!(stats["timeseries"].as_array().unwrap().len(), 2);
+    assert_eq!(stats["timeseries"][0]["errors"], 20);
+}
+
+#[test]
+fn analytics_dns_reports_nxdomain_rate() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json").respond(
+        "POST",
+        "/graphql",
+        200,
+        r#"{"data":{"viewer":{"zones":[{
+            "total":[{"count":200}],
+            "byName":[{"count":150,"dimensions":{"value":"www.example.com"}},{"count":50,"dimensions":{"value":"missing.example.com"}}],
+            "byType":[{"count":120,"dimensions":{"value":"A"}},{"count":80,"dimensions":{"value":"AAAA"}}],
+            "byResponseCode":[{"count":150,"dimensions":{"value":"NOERROR"}},{"count":50,"dimensions":{"value":"NXDOMAIN"}}]
+        }]}}}"#,
+    );
+
+    let output = mock.cfai(&["analytics", "dns", ZONE_ID, "--top", "5"]);
+    assert_success(&output);
+
+    let request = mock.last("POST", "/graphql").json();
+    assert!(request["query"].as_str().unwrap().contains("limit: 5"));
+    let out = stdout(&output);
+    assert!(out.contains("missing.example.com"));
+    assert!(out.contains("25.0%"));
+}
+
+#[test]
+fn analytics_query_injects_zone_tag_and_prints_table() {
+    let mock = MockCloudflare::start();
+    mock.fixture("GET", &format!("/zones/{}", ZONE_ID), "zone.json").respond(
+        "POST",
+        "/graphql",
+        200,
+        r#"{"data":{"viewer":{"zones":[{"firewallEventsAdaptiveGroups":[
+            {"count":42,"dimensions":{"action":"block"}},
+            {"count":7,"dimensions":{"action":"managed_challenge"}}
+        ]}]}}}"#,
+    );
+
+    let query = "query Q($zoneTag: String!, $limit: Int!) { viewer { zones(filter: {zoneTag: $zoneTag}) { \
+                 firewallEventsAdaptiveGroups(limit: $limit) { count dimensions { action } } } } }";
+    let output = mock.cfai(&["analytics", "query", "--query", query, "--zone", ZONE_ID, "--var", "limit=5"]);
+    assert_success(&output);
+
+    let variables = &mock.last("POST", "/graphql").json()["variables"];
+    assert_eq!(variables["zoneTag"], ZONE_ID);
+    assert_eq!(variables["limit"], 5);
+    let out = stdout(&output);
+    assert!(out.contains("dimensions.action") && out.contains("managed_challenge"));
+}
@@ -0,0 +1,200 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+
+/// 测试用的 Zone ID (与 fixtures/zones.json 一致)
+pub const ZONE_ID: &str = "023e105f4ecef8ad9ca31a8372d0c353";
+
+/// 模拟服务收到的请求
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub authorization: Option<String>,
+    pub body: String,
+}
+
+impl Recorded {
+    /// 将请求体解析为 JSON
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).expect("请求体不是 JSON")
+    }
+}
+
+struct Route {
+    method: &'static str,
+    path: String,
+    status: u16,
+    body: String,
+}
+
+/// 模拟的 Cloudflare API 服务
+///
+/// 按方法与路径 (忽略查询参数) 返回预先录制的响应，未注册的路径返回 404。
+/// 每个连接只处理一个请求并以 `Connection: close` 结束，足以服务 CLI 的顺序请求。
+pub struct MockCloudflare {
+    base_url: String,
+    routes: Arc<Mutex<Vec<Route>>>,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+    config_dir: tempfile::TempDir,
+}
+
+impl MockCloudflare {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("绑定端口失败");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Arc<Mutex<Vec<Route>>> = Arc::default();
+        let requests: Arc<Mutex<Vec<Recorded>>> = Arc::default();
+
+        let (r, q) = (routes.clone(), requests.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle(stream, &r, &q);
+            }
+        });
+
+        Self {
+            base_url,
+            routes,
+            requests,
+            config_dir: tempfile::tempdir().expect("创建临时目录失败"),
+        }
+    }
+
+    /// 注册路由，响应体取自 tests/fixtures 下的文件
+    pub fn fixture(&self, method: &'static str, path: &str, fixture: &str) -> &Self {
+        self.respond(method, path, 200, &load_fixture(fixture))
+    }
+
+    /// 注册路由并指定状态码与响应体
+    pub fn respond(&self, method: &'static str, path: &str, status: u16, body: &str) -> &Self {
+        self.routes.lock().unwrap().push(Route {
+            method,
+            path: path.to_string(),
+            status,
+            body: body.to_string(),
+        });
+        self
+    }
+
+    /// 模拟服务地址
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// 最后一个匹配方法与路径的请求
+    pub fn last(&self, method: &str, path: &str) -> Recorded {
+        self.requests()
+            .into_iter()
+            .rev()
+            .find(|r| r.method == method && r.path == path)
+            .unwrap_or_else(|| panic!("未收到请求: {} {}", method, path))
+    }
+
+    /// 以模拟服务为 API 地址运行 cfai，配置目录使用独立的临时目录
+    pub fn cfai(&self, args: &[&str]) -> Output {
+        self.command(args)
+            .env("CLOUDFLARE_API_TOKEN", "test-token")
+            .output()
+            .expect("运行 cfai 失败")
+    }
+
+    /// 未设置凭据的 cfai 命令
+    pub fn command(&self, args: &[&str]) -> Command {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_cfai"));
+        cmd.args(args)
+            .env_clear()
+            .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+            .env("HOME", self.config_dir.path())
+            .env("XDG_CONFIG_HOME", self.config_dir.path())
+            .env("CLOUDFLARE_API_BASE", &self.base_url)
+            .env("CLOUDFLARE_MAX_RETRIES", "0")
+            .env("NO_COLOR", "1");
+        cmd
+    }
+}
+
+/// 命令的标准输出
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// 断言命令执行成功，失败时附带输出
+pub fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "cfai 执行失败 ({:?})\nstdout:\n{}\nstderr:\n{}",
+        output.status.code(),
+        stdout(output),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn load_fixture(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("读取 {} 失败: {}", path.display(), e))
+}
+
+fn handle(mut stream: TcpStream, routes: &Mutex<Vec<Route>>, requests: &Mutex<Vec<Recorded>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            match name.to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                "authorization" => authorization = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+
+    requests.lock().unwrap().push(Recorded {
+        method: method.clone(),
+        path: path.to_string(),
+        query: query.to_string(),
+        authorization,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    });
+
+    let (status, body) = routes
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .find(|r| r.method == method && r.path == path)
+        .map(|r| (r.status, r.body.clone()))
+        .unwrap_or_else(|| (404, load_fixture("not_found.json")));
+
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+}